@@ -36,6 +36,7 @@ fn main() -> anyhow::Result<()> {
             test_directory_module(out, "tests/misc_testsuite/memory64", strategy)?;
             test_directory_module(out, "tests/misc_testsuite/component-model", strategy)?;
             test_directory_module(out, "tests/misc_testsuite/function-references", strategy)?;
+            test_directory_module(out, "tests/misc_testsuite/exceptions", strategy)?;
             test_directory_module(out, "tests/misc_testsuite/winch", strategy)?;
             Ok(())
         })?;
@@ -63,6 +64,11 @@ fn main() -> anyhow::Result<()> {
                     strategy,
                 )?;
                 test_directory_module(out, "tests/spec_testsuite/proposals/tail-call", strategy)?;
+                test_directory_module(
+                    out,
+                    "tests/spec_testsuite/proposals/extended-const",
+                    strategy,
+                )?;
             } else {
                 println!(
                     "cargo:warning=The spec testsuite is disabled. To enable, run `git submodule \
@@ -9,6 +9,7 @@ cargo test \
     --exclude 'wasmtime-wasi-*' \
     --exclude wasi-tests \
     --exclude wasi-http-tests \
+    --exclude wasi-http-proxy-tests \
     --exclude command-tests \
     --exclude reactor-tests \
     $@
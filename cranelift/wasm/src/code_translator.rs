@@ -75,12 +75,12 @@ mod bounds_checks;
 
 use super::{hash_map, HashMap};
 use crate::environ::{FuncEnvironment, GlobalVariable};
-use crate::state::{ControlStackFrame, ElseData, FuncTranslationState};
+use crate::state::{CatchData, ControlStackFrame, ElseData, FuncTranslationState};
 use crate::translation_utils::{
     block_with_params, blocktype_params_results, f32_translation, f64_translation,
 };
 use crate::wasm_unsupported;
-use crate::{FuncIndex, GlobalIndex, MemoryIndex, TableIndex, TypeIndex, WasmResult};
+use crate::{FuncIndex, GlobalIndex, MemoryIndex, TableIndex, TagIndex, TypeIndex, WasmResult};
 use core::{i32, u32};
 use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
 use cranelift_codegen::ir::immediates::Offset32;
@@ -433,6 +433,12 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             let frame = state.control_stack.pop().unwrap();
             let next_block = frame.following_code();
             let return_count = frame.num_return_values();
+            if let Some(slot) = frame.caught_exception() {
+                // Falling off the end of a `catch` clause releases the
+                // exception it caught.
+                let handle = builder.ins().stack_load(I32, slot, 0);
+                environ.translate_exception_drop(builder, handle)?;
+            }
             let return_args = state.peekn_mut(return_count);
 
             canonicalise_then_jump(builder, next_block, return_args);
@@ -443,6 +449,9 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             // since we truncate the stack back to the original height
             // below.
 
+            let depth = state.control_stack.len();
+            translate_try_end(builder, state, environ, &frame, depth)?;
+
             builder.switch_to_block(next_block);
             builder.seal_block(next_block);
 
@@ -479,6 +488,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
          ***********************************************************************************/
         Operator::Br { relative_depth } => {
             let i = state.control_stack.len() - 1 - (*relative_depth as usize);
+            drop_caught_exceptions(builder, state, environ, i)?;
             let (return_count, br_destination) = {
                 let frame = &mut state.control_stack[i];
                 // We signal that all the code that follows until the next End is unreachable
@@ -495,7 +505,9 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             state.popn(return_count);
             state.reachable = false;
         }
-        Operator::BrIf { relative_depth } => translate_br_if(*relative_depth, builder, state),
+        Operator::BrIf { relative_depth } => {
+            translate_br_if(*relative_depth, builder, state, environ)?
+        }
         Operator::BrTable { targets } => {
             let default = targets.default();
             let mut min_depth = default;
@@ -516,7 +528,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             };
             let val = state.pop1();
             let mut data = Vec::with_capacity(targets.len() as usize);
-            if jump_args_count == 0 {
+            if jump_args_count == 0 && !branch_leaves_catch(state, min_depth) {
                 // No jump arguments
                 for depth in targets.targets() {
                     let depth = depth?;
@@ -539,7 +551,9 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
                 builder.ins().br_table(val, jt);
             } else {
                 // Here we have jump arguments, but Cranelift's br_table doesn't support them
-                // We then proceed to split the edges going out of the br_table
+                // We then proceed to split the edges going out of the br_table. This is also
+                // done when a branch leaves a `catch` clause, as the exception it caught must
+                // be released along the way.
                 let return_count = jump_args_count;
                 let mut dest_block_sequence = vec![];
                 let mut dest_block_map = HashMap::new();
@@ -569,8 +583,9 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
                 for (depth, dest_block) in dest_block_sequence {
                     builder.switch_to_block(dest_block);
                     builder.seal_block(dest_block);
+                    let i = state.control_stack.len() - 1 - depth;
+                    drop_caught_exceptions(builder, state, environ, i)?;
                     let real_dest_block = {
-                        let i = state.control_stack.len() - 1 - depth;
                         let frame = &mut state.control_stack[i];
                         frame.set_branched_to_exit();
                         frame.br_destination()
@@ -587,6 +602,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
                 let frame = &mut state.control_stack[0];
                 frame.num_return_values()
             };
+            drop_caught_exceptions(builder, state, environ, 0)?;
             {
                 let return_args = state.peekn_mut(return_count);
                 environ.handle_before_return(&return_args, builder);
@@ -597,16 +613,62 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             state.reachable = false;
         }
         /********************************** Exception handing **********************************/
-        Operator::Try { .. }
-        | Operator::Catch { .. }
-        | Operator::Throw { .. }
-        | Operator::Rethrow { .. }
-        | Operator::Delegate { .. }
-        | Operator::CatchAll => {
-            return Err(wasm_unsupported!(
-                "proposed exception handling operator {:?}",
-                op
-            ));
+        Operator::Try { blockty } => {
+            let (params, results) = blocktype_params_results(validator, *blockty)?;
+            let next = block_with_params(builder, results.clone(), environ)?;
+            let handler = builder.create_block();
+            builder.set_cold_block(handler);
+            state.push_try(next, handler, params.len(), results.len());
+        }
+        Operator::Catch { tag_index } => {
+            translate_catch(
+                builder,
+                state,
+                environ,
+                Some(TagIndex::from_u32(*tag_index)),
+            )?;
+        }
+        Operator::CatchAll => translate_catch(builder, state, environ, None)?,
+        Operator::Delegate { relative_depth } => {
+            let frame = state.control_stack.pop().unwrap();
+            let next_block = frame.following_code();
+            let return_count = frame.num_return_values();
+            let return_args = state.peekn_mut(return_count);
+            canonicalise_then_jump(builder, next_block, return_args);
+
+            // Exceptions which reached the handler of the `try` are propagated
+            // onwards from the frame that `relative_depth` refers to.
+            let depth = state.control_stack.len() - *relative_depth as usize;
+            translate_try_end(builder, state, environ, &frame, depth)?;
+
+            builder.switch_to_block(next_block);
+            builder.seal_block(next_block);
+            frame.truncate_value_stack_to_original_size(&mut state.stack);
+            state
+                .stack
+                .extend_from_slice(builder.block_params(next_block));
+        }
+        Operator::Throw { tag_index } => {
+            let num_args = validator
+                .resources()
+                .tag_at(*tag_index)
+                .expect("should be valid")
+                .len_inputs();
+            let args = state.peekn_mut(num_args);
+            environ.translate_throw(builder, TagIndex::from_u32(*tag_index), args)?;
+            state.popn(num_args);
+            translate_exception_propagate(builder, state, environ, state.control_stack.len())?;
+            state.reachable = false;
+        }
+        Operator::Rethrow { relative_depth } => {
+            let i = state.control_stack.len() - 1 - (*relative_depth as usize);
+            let slot = state.control_stack[i]
+                .caught_exception()
+                .expect("rethrow must be within a reachable catch clause");
+            let handle = builder.ins().stack_load(I32, slot, 0);
+            environ.translate_rethrow(builder, handle)?;
+            translate_exception_propagate(builder, state, environ, state.control_stack.len())?;
+            state.reachable = false;
         }
         /************************************ Calls ****************************************
          * The call instructions pop off their arguments from the stack and append their
@@ -641,6 +703,9 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             );
             state.popn(num_args);
             state.pushn(inst_results);
+            if environ.call_may_throw(FuncIndex::from_u32(*function_index)) {
+                translate_exception_check(builder, state, environ)?;
+            }
         }
         Operator::CallIndirect {
             type_index,
@@ -675,6 +740,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             );
             state.popn(num_args);
             state.pushn(inst_results);
+            translate_exception_check(builder, state, environ)?;
         }
         /******************************* Tail Calls ******************************************
          * The tail call instructions pop their arguments from the stack and
@@ -685,6 +751,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
          ************************************************************************************/
         Operator::ReturnCall { function_index } => {
             let (fref, num_args) = state.get_direct_func(builder.func, *function_index, environ)?;
+            drop_caught_exceptions(builder, state, environ, 0)?;
 
             // Bitcast any vector arguments to their default type, I8X16, before calling.
             let args = state.peekn_mut(num_args);
@@ -720,6 +787,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             let args = state.peekn_mut(num_args);
             bitcast_wasm_params(environ, sigref, args, builder);
 
+            drop_caught_exceptions(builder, state, environ, 0)?;
             environ.translate_return_call_indirect(
                 builder,
                 TableIndex::from_u32(*table_index),
@@ -744,6 +812,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             let args = state.peekn_mut(num_args);
             bitcast_wasm_params(environ, sigref, args, builder);
 
+            drop_caught_exceptions(builder, state, environ, 0)?;
             environ.translate_return_call_ref(builder, sigref, callee, state.peekn(num_args))?;
 
             state.popn(num_args);
@@ -2446,10 +2515,16 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
 
         Operator::BrOnNull { relative_depth } => {
             let r = state.pop1();
-            let (br_destination, inputs) = translate_br_if_args(*relative_depth, state);
             let is_null = environ.translate_ref_is_null(builder.cursor(), r)?;
             let else_block = builder.create_block();
-            canonicalise_brif(builder, is_null, br_destination, inputs, else_block, &[]);
+            if branch_leaves_catch(state, *relative_depth) {
+                let edge = builder.create_block();
+                builder.ins().brif(is_null, edge, &[], else_block, &[]);
+                translate_br_edge(*relative_depth, edge, builder, state, environ)?;
+            } else {
+                let (br_destination, inputs) = translate_br_if_args(*relative_depth, state);
+                canonicalise_brif(builder, is_null, br_destination, inputs, else_block, &[]);
+            }
 
             builder.seal_block(else_block); // The only predecessor is the current block.
             builder.switch_to_block(else_block);
@@ -2463,9 +2538,15 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             // If val is ref.null ht, then: pop the value val from the stack.
             // Else: Execute the instruction (br relative_depth).
             let is_null = environ.translate_ref_is_null(builder.cursor(), state.peek1())?;
-            let (br_destination, inputs) = translate_br_if_args(*relative_depth, state);
             let else_block = builder.create_block();
-            canonicalise_brif(builder, is_null, else_block, &[], br_destination, inputs);
+            if branch_leaves_catch(state, *relative_depth) {
+                let edge = builder.create_block();
+                builder.ins().brif(is_null, else_block, &[], edge, &[]);
+                translate_br_edge(*relative_depth, edge, builder, state, environ)?;
+            } else {
+                let (br_destination, inputs) = translate_br_if_args(*relative_depth, state);
+                canonicalise_brif(builder, is_null, else_block, &[], br_destination, inputs);
+            }
 
             // In the null case, pop the ref
             state.pop1();
@@ -2498,6 +2579,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             );
             state.popn(num_args);
             state.pushn(inst_results);
+            translate_exception_check(builder, state, environ)?;
         }
         Operator::RefAsNonNull => {
             let r = state.pop1();
@@ -2544,6 +2626,22 @@ fn translate_unreachable_operator<FE: FuncEnvironment + ?Sized>(
         Operator::Loop { blockty: _ } | Operator::Block { blockty: _ } => {
             state.push_block(ir::Block::reserved_value(), 0, 0);
         }
+        Operator::Try { blockty: _ } => {
+            // Nothing within this `try` is reachable, so neither are any of
+            // its `catch` clauses.
+            state.push_try(
+                ir::Block::reserved_value(),
+                ir::Block::reserved_value(),
+                0,
+                0,
+            );
+        }
+        Operator::Catch { tag_index } => {
+            // The body of the `try`, or one of its clauses, ended unreachable,
+            // but exceptions may still have been propagated to this clause.
+            translate_catch(builder, state, environ, Some(TagIndex::from_u32(tag_index)))?;
+        }
+        Operator::CatchAll => translate_catch(builder, state, environ, None)?,
         Operator::Else => {
             let i = state.control_stack.len() - 1;
             match state.control_stack[i] {
@@ -2599,13 +2697,11 @@ fn translate_unreachable_operator<FE: FuncEnvironment + ?Sized>(
                 _ => unreachable!(),
             }
         }
-        Operator::End => {
-            let stack = &mut state.stack;
-            let control_stack = &mut state.control_stack;
-            let frame = control_stack.pop().unwrap();
+        Operator::End | Operator::Delegate { .. } => {
+            let frame = state.control_stack.pop().unwrap();
 
             // Pop unused parameters from stack.
-            frame.truncate_value_stack_to_original_size(stack);
+            frame.truncate_value_stack_to_original_size(&mut state.stack);
 
             let reachable_anyway = match frame {
                 // If it is a loop we also have to seal the body loop block
@@ -2636,13 +2732,25 @@ fn translate_unreachable_operator<FE: FuncEnvironment + ?Sized>(
                 _ => false,
             };
 
+            // Exceptions may still have been propagated to the handler of a
+            // `try`, and are then propagated to the frames below `depth`.
+            let depth = match *op {
+                Operator::Delegate { relative_depth } => {
+                    state.control_stack.len() - relative_depth as usize
+                }
+                _ => state.control_stack.len(),
+            };
+            translate_try_end(builder, state, environ, &frame, depth)?;
+
             if frame.exit_is_branched_to() || reachable_anyway {
                 builder.switch_to_block(frame.following_code());
                 builder.seal_block(frame.following_code());
 
                 // And add the return values of the block but only if the next block is reachable
                 // (which corresponds to testing if the stack depth is 1)
-                stack.extend_from_slice(builder.block_params(frame.following_code()));
+                state
+                    .stack
+                    .extend_from_slice(builder.block_params(frame.following_code()));
                 state.reachable = true;
             }
         }
@@ -3185,18 +3293,53 @@ fn translate_vector_fcmp(
     state.push1(builder.ins().fcmp(cc, bitcast_a, bitcast_b))
 }
 
-fn translate_br_if(
+fn translate_br_if<FE: FuncEnvironment + ?Sized>(
     relative_depth: u32,
     builder: &mut FunctionBuilder,
     state: &mut FuncTranslationState,
-) {
+    environ: &mut FE,
+) -> WasmResult<()> {
     let val = state.pop1();
-    let (br_destination, inputs) = translate_br_if_args(relative_depth, state);
     let next_block = builder.create_block();
-    canonicalise_brif(builder, val, br_destination, inputs, next_block, &[]);
+    if branch_leaves_catch(state, relative_depth) {
+        let edge = builder.create_block();
+        builder.ins().brif(val, edge, &[], next_block, &[]);
+        translate_br_edge(relative_depth, edge, builder, state, environ)?;
+    } else {
+        let (br_destination, inputs) = translate_br_if_args(relative_depth, state);
+        canonicalise_brif(builder, val, br_destination, inputs, next_block, &[]);
+    }
 
     builder.seal_block(next_block); // The only predecessor is the current block.
     builder.switch_to_block(next_block);
+    Ok(())
+}
+
+/// Does a branch to `relative_depth` exit any `catch` clauses?
+fn branch_leaves_catch(state: &FuncTranslationState, relative_depth: u32) -> bool {
+    let i = state.control_stack.len() - 1 - (relative_depth as usize);
+    state.control_stack[i..]
+        .iter()
+        .any(|frame| frame.caught_exception().is_some())
+}
+
+/// Fills in `edge`, the block through which a conditional branch to
+/// `relative_depth` which exits `catch` clauses passes: it releases the
+/// exceptions caught by those clauses before jumping to the real destination.
+fn translate_br_edge<FE: FuncEnvironment + ?Sized>(
+    relative_depth: u32,
+    edge: ir::Block,
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+    environ: &mut FE,
+) -> WasmResult<()> {
+    builder.switch_to_block(edge);
+    builder.seal_block(edge);
+    let i = state.control_stack.len() - 1 - (relative_depth as usize);
+    drop_caught_exceptions(builder, state, environ, i)?;
+    let (br_destination, inputs) = translate_br_if_args(relative_depth, state);
+    canonicalise_then_jump(builder, br_destination, inputs);
+    Ok(())
 }
 
 fn translate_br_if_args(
@@ -3220,6 +3363,239 @@ fn translate_br_if_args(
     (br_destination, inputs)
 }
 
+/// Translates the `catch` or, if `tag` is `None`, the `catch_all` clause of
+/// the innermost `try` block.
+fn translate_catch<FE: FuncEnvironment + ?Sized>(
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+    environ: &mut FE,
+    tag: Option<TagIndex>,
+) -> WasmResult<()> {
+    let i = state.control_stack.len() - 1;
+    if state.reachable {
+        // Control falls off the end of the body of the `try`, or of its
+        // previous `catch` clause, so release that clause's exception and
+        // jump to the end of the `try`.
+        if let Some(slot) = state.control_stack[i].caught_exception() {
+            let handle = builder.ins().stack_load(I32, slot, 0);
+            environ.translate_exception_drop(builder, handle)?;
+        }
+        let frame = &mut state.control_stack[i];
+        frame.set_branched_to_exit();
+        let return_count = frame.num_return_values();
+        let destination = frame.following_code();
+        let return_args = state.peekn_mut(return_count);
+        canonicalise_then_jump(builder, destination, return_args);
+    }
+    state.control_stack[i].truncate_value_stack_to_original_size(&mut state.stack);
+
+    let catch = match &mut state.control_stack[i] {
+        ControlStackFrame::Try {
+            handler,
+            handler_is_used,
+            catch,
+            ..
+        } => {
+            if catch.is_none() {
+                // This is the first clause, so the handler of the `try`
+                // begins here. It takes the exception which was propagated to
+                // it and stores its handle for use by all the clauses.
+                *catch = Some(if *handler_is_used {
+                    builder.switch_to_block(*handler);
+                    builder.seal_block(*handler);
+                    let handle = environ.translate_exception_catch(builder)?;
+                    let slot = builder.create_sized_stack_slot(ir::StackSlotData::new(
+                        ir::StackSlotKind::ExplicitSlot,
+                        4,
+                    ));
+                    builder.ins().stack_store(handle, slot, 0);
+                    CatchData {
+                        handle: Some(slot),
+                        dispatch: Some(*handler),
+                    }
+                } else {
+                    CatchData {
+                        handle: None,
+                        dispatch: None,
+                    }
+                });
+            }
+            catch.as_mut().unwrap()
+        }
+        _ => unreachable!(),
+    };
+
+    // If no exception may reach this clause then its body is unreachable.
+    let (slot, dispatch) = match (catch.handle, catch.dispatch) {
+        (Some(slot), Some(dispatch)) => (slot, dispatch),
+        _ => {
+            state.reachable = false;
+            return Ok(());
+        }
+    };
+    builder.switch_to_block(dispatch);
+    match tag {
+        Some(tag) => {
+            let handle = builder.ins().stack_load(I32, slot, 0);
+            let matches = environ.translate_exception_matches(builder, handle, tag)?;
+            let body = builder.create_block();
+            let next = builder.create_block();
+            builder.ins().brif(matches, body, &[], next, &[]);
+            builder.seal_block(body);
+            builder.seal_block(next);
+            catch.dispatch = Some(next);
+
+            builder.switch_to_block(body);
+            let payload = environ.translate_exception_payload(builder, handle, tag)?;
+            state.stack.extend(payload);
+        }
+        None => catch.dispatch = None,
+    }
+    state.reachable = true;
+    Ok(())
+}
+
+/// Finishes off the handler of a `try` block once its last clause, which is
+/// `frame`, has been translated: exceptions that weren't caught by any of its
+/// clauses are propagated onwards to the frames below `depth`.
+fn translate_try_end<FE: FuncEnvironment + ?Sized>(
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+    environ: &mut FE,
+    frame: &ControlStackFrame,
+    depth: usize,
+) -> WasmResult<()> {
+    match *frame {
+        ControlStackFrame::Try {
+            handler,
+            handler_is_used: true,
+            catch: None,
+            ..
+        } => {
+            // A `try` without any clauses, or a `delegate`, catches nothing.
+            builder.switch_to_block(handler);
+            builder.seal_block(handler);
+            translate_exception_propagate(builder, state, environ, depth)?;
+        }
+        ControlStackFrame::Try {
+            catch:
+                Some(CatchData {
+                    handle: Some(slot),
+                    dispatch: Some(dispatch),
+                }),
+            ..
+        } => {
+            // The exception didn't match any `catch` clause, so throw it again.
+            builder.switch_to_block(dispatch);
+            let handle = builder.ins().stack_load(I32, slot, 0);
+            environ.translate_rethrow(builder, handle)?;
+            environ.translate_exception_drop(builder, handle)?;
+            translate_exception_propagate(builder, state, environ, depth)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Checks for an exception thrown by a call that was just made and, if there
+/// is one, propagates it.
+fn translate_exception_check<FE: FuncEnvironment + ?Sized>(
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+    environ: &mut FE,
+) -> WasmResult<()> {
+    if !environ.exceptions_enabled() {
+        return Ok(());
+    }
+    let pending = environ.translate_exception_pending(builder)?;
+    let propagate = builder.create_block();
+    builder.set_cold_block(propagate);
+    let next = builder.create_block();
+    builder.ins().brif(pending, propagate, &[], next, &[]);
+    builder.seal_block(propagate);
+    builder.seal_block(next);
+
+    builder.switch_to_block(propagate);
+    translate_exception_propagate(builder, state, environ, state.control_stack.len())?;
+    builder.switch_to_block(next);
+    Ok(())
+}
+
+/// Propagates the pending exception from within the frames at `depth` and
+/// above of the control stack, terminating the current block.
+///
+/// Exceptions are propagated to the handler of the innermost enclosing `try`
+/// block, if there is one. Otherwise the function returns early, leaving the
+/// exception pending so that its caller propagates it in turn.
+fn translate_exception_propagate<FE: FuncEnvironment + ?Sized>(
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+    environ: &mut FE,
+    depth: usize,
+) -> WasmResult<()> {
+    match state.exception_landing(depth) {
+        Some((i, handler)) => {
+            drop_caught_exceptions(builder, state, environ, i + 1)?;
+            builder.ins().jump(handler, &[]);
+        }
+        None => {
+            drop_caught_exceptions(builder, state, environ, 0)?;
+            let frame = &mut state.control_stack[0];
+            frame.set_branched_to_exit();
+            let exit = frame.following_code();
+            let types = builder
+                .block_params(exit)
+                .iter()
+                .map(|v| builder.func.dfg.value_type(*v))
+                .collect::<SmallVec<[Type; 4]>>();
+            // The caller ignores the results of a call which threw.
+            let results = types
+                .into_iter()
+                .map(|ty| zero_value(builder, ty))
+                .collect::<SmallVec<[Value; 4]>>();
+            builder.ins().jump(exit, &results);
+        }
+    }
+    Ok(())
+}
+
+/// Releases the exceptions caught by all `catch` clauses within the frames at
+/// index `from` and above of the control stack, as control is leaving them.
+fn drop_caught_exceptions<FE: FuncEnvironment + ?Sized>(
+    builder: &mut FunctionBuilder,
+    state: &FuncTranslationState,
+    environ: &mut FE,
+    from: usize,
+) -> WasmResult<()> {
+    for frame in state.control_stack[from..].iter() {
+        if let Some(slot) = frame.caught_exception() {
+            let handle = builder.ins().stack_load(I32, slot, 0);
+            environ.translate_exception_drop(builder, handle)?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates a zero value of type `ty`.
+fn zero_value(builder: &mut FunctionBuilder, ty: Type) -> Value {
+    if ty.is_vector() {
+        let constant_handle = builder
+            .func
+            .dfg
+            .constants
+            .insert(vec![0; ty.bytes() as usize].into());
+        builder.ins().vconst(ty, constant_handle)
+    } else if ty.is_ref() {
+        builder.ins().null(ty)
+    } else if ty == F32 {
+        builder.ins().f32const(ir::immediates::Ieee32::with_bits(0))
+    } else if ty == F64 {
+        builder.ins().f64const(ir::immediates::Ieee64::with_bits(0))
+    } else {
+        builder.ins().iconst(ty, 0)
+    }
+}
+
 /// Determine the returned value type of a WebAssembly operator
 fn type_of(operator: &Operator) -> Type {
     match operator {
@@ -7,6 +7,7 @@
 //! [Wasmtime]: https://github.com/bytecodealliance/wasmtime
 
 use crate::state::FuncTranslationState;
+use crate::wasm_unsupported;
 use crate::{
     DataIndex, ElemIndex, FuncIndex, Global, GlobalIndex, GlobalInit, Heap, HeapData, Memory,
     MemoryIndex, SignatureIndex, Table, TableIndex, Tag, TagIndex, TypeConvert, TypeIndex,
@@ -21,6 +22,7 @@ use cranelift_entity::PrimaryMap;
 use cranelift_frontend::FunctionBuilder;
 use std::boxed::Box;
 use std::string::ToString;
+use std::vec::Vec;
 use wasmparser::{FuncValidator, FunctionBody, Operator, ValidatorResources, WasmFeatures};
 
 /// The value of a WebAssembly global variable.
@@ -549,6 +551,112 @@ pub trait FuncEnvironment: TargetEnvironment {
         Ok(())
     }
 
+    /// Is the exception-handling proposal enabled?
+    ///
+    /// When it is, a check for a pending exception is emitted after every
+    /// call which may throw, via `translate_exception_pending`.
+    fn exceptions_enabled(&self) -> bool {
+        false
+    }
+
+    /// Whether a direct call to `callee` may leave an exception pending, and
+    /// so needs to be followed by a check for one.
+    fn call_may_throw(&self, _callee: FuncIndex) -> bool {
+        true
+    }
+
+    /// Emit code returning a non-zero `i32` if an exception is pending, that
+    /// is it has been thrown and not yet caught.
+    ///
+    /// Exceptions are not propagated by unwinding. Instead the environment
+    /// records a thrown exception somewhere, and the translated code checks
+    /// for it after every call and every `throw`, branching to the handler of
+    /// the innermost enclosing `try` block or returning to its caller.
+    fn translate_exception_pending(
+        &mut self,
+        builder: &mut FunctionBuilder,
+    ) -> WasmResult<ir::Value> {
+        let _ = builder;
+        Err(wasm_unsupported!("exceptions"))
+    }
+
+    /// Translate a `throw` WebAssembly instruction, making a new exception
+    /// for `tag` with the payload `args` pending.
+    fn translate_throw(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        tag: TagIndex,
+        args: &[ir::Value],
+    ) -> WasmResult<()> {
+        let _ = (builder, tag, args);
+        Err(wasm_unsupported!("exceptions"))
+    }
+
+    /// Emit code catching the pending exception at the start of the `catch`
+    /// clauses of a `try` block, returning an `i32` handle to it.
+    ///
+    /// The handle remains valid until it's passed to
+    /// `translate_exception_drop`, once control leaves the clauses.
+    fn translate_exception_catch(
+        &mut self,
+        builder: &mut FunctionBuilder,
+    ) -> WasmResult<ir::Value> {
+        let _ = builder;
+        Err(wasm_unsupported!("exceptions"))
+    }
+
+    /// Emit code returning a non-zero `i32` if the caught exception `handle`
+    /// was thrown with `tag`.
+    fn translate_exception_matches(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        handle: ir::Value,
+        tag: TagIndex,
+    ) -> WasmResult<ir::Value> {
+        let _ = (builder, handle, tag);
+        Err(wasm_unsupported!("exceptions"))
+    }
+
+    /// Emit code loading the payload of the caught exception `handle`, which
+    /// is known to have been thrown with `tag`.
+    fn translate_exception_payload(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        handle: ir::Value,
+        tag: TagIndex,
+    ) -> WasmResult<Vec<ir::Value>> {
+        let _ = (builder, handle, tag);
+        Err(wasm_unsupported!("exceptions"))
+    }
+
+    /// Translate a `rethrow` WebAssembly instruction, making the caught
+    /// exception `handle` pending again.
+    fn translate_rethrow(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        handle: ir::Value,
+    ) -> WasmResult<()> {
+        let _ = (builder, handle);
+        Err(wasm_unsupported!("exceptions"))
+    }
+
+    /// Emit code releasing the caught exception `handle` as control leaves
+    /// the `catch` clause which caught it.
+    fn translate_exception_drop(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        handle: ir::Value,
+    ) -> WasmResult<()> {
+        let _ = (builder, handle);
+        Err(wasm_unsupported!("exceptions"))
+    }
+
+    /// Whether the types of the values on the operand stack should be tracked, and made available
+    /// through `FuncTranslationState::operand_stack_types`, while translating reachable code.
+    fn needs_operand_stack_types(&self) -> bool {
+        false
+    }
+
     /// Optional callback for the `FunctionEnvironment` performing this translation to maintain
     /// internal state or prepare custom state for the operator to translate
     fn before_translate_operator(
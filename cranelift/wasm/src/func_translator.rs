@@ -255,10 +255,15 @@ fn parse_function_body<FE: FuncEnvironment + ?Sized>(
     debug_assert_eq!(state.control_stack.len(), 1, "State not initialized");
 
     environ.before_translate_function(builder, state)?;
+    let track_stack_types = environ.needs_operand_stack_types();
     while !reader.eof() {
         let pos = reader.original_position();
-        builder.set_srcloc(cur_srcloc(&reader));
+        state.srcloc = cur_srcloc(&reader);
+        builder.set_srcloc(state.srcloc);
         let op = reader.read_operator()?;
+        if track_stack_types {
+            update_stack_types(validator, state, environ);
+        }
         validator.op(pos, &op)?;
         environ.before_translate_operator(&op, builder, state)?;
         translate_operator(validator, &op, builder, state, environ)?;
@@ -288,6 +293,35 @@ fn parse_function_body<FE: FuncEnvironment + ?Sized>(
     Ok(())
 }
 
+/// Records the types of the values on the operand stack, as the validator sees them before the
+/// next operator, in `state`.
+///
+/// The types are only recorded in reachable code, where the validator's operand stack is known to
+/// match the translator's one.
+fn update_stack_types<FE: FuncEnvironment + ?Sized>(
+    validator: &FuncValidator<impl WasmModuleResources>,
+    state: &mut FuncTranslationState,
+    environ: &FE,
+) {
+    state.stack_types.clear();
+    if !state.reachable {
+        return;
+    }
+    let height = validator.operand_stack_height() as usize;
+    for depth in (0..height).rev() {
+        match validator.get_operand_type(depth) {
+            Some(Some(ty)) => state.stack_types.push(environ.convert_valtype(ty)),
+            _ => {
+                state.stack_types.clear();
+                return;
+            }
+        }
+    }
+    if state.stack_types.len() != state.stack.len() {
+        state.stack_types.clear();
+    }
+}
+
 /// Get the current source location from a reader.
 fn cur_srcloc(reader: &BinaryReader) -> ir::SourceLoc {
     // We record source locations as byte code offsets relative to the beginning of the file.
@@ -4,7 +4,9 @@
 //! value and control stacks during the translation of a single function.
 
 use crate::environ::{FuncEnvironment, GlobalVariable};
-use crate::{FuncIndex, GlobalIndex, Heap, MemoryIndex, TableIndex, TypeIndex, WasmResult};
+use crate::{
+    FuncIndex, GlobalIndex, Heap, MemoryIndex, TableIndex, TypeIndex, WasmResult, WasmType,
+};
 use crate::{HashMap, Occupied, Vacant};
 use cranelift_codegen::ir::{self, Block, Inst, Value};
 use std::vec::Vec;
@@ -39,8 +41,22 @@ pub enum ElseData {
     },
 }
 
-/// A control stack frame can be an `if`, a `block` or a `loop`, each one having the following
-/// fields:
+/// The state of the `catch` clauses of a `try` block, once translation has
+/// moved past the body of the `try`.
+#[derive(Debug)]
+pub struct CatchData {
+    /// The stack slot holding the handle of the caught exception, or `None`
+    /// if nothing within the body of the `try` may throw.
+    pub handle: Option<ir::StackSlot>,
+
+    /// The block which tests the caught exception against the remaining
+    /// `catch` clauses, or `None` once a `catch_all` clause has been seen or
+    /// if nothing may be caught at all.
+    pub dispatch: Option<Block>,
+}
+
+/// A control stack frame can be an `if`, a `block`, a `loop` or a `try`, each one having the
+/// following fields:
 ///
 /// - `destination`: reference to the `Block` that will hold the code after the control block;
 /// - `num_return_values`: number of values returned by the control block;
@@ -48,6 +64,9 @@ pub enum ElseData {
 ///
 /// The `loop` frame has a `header` field that references the `Block` that contains the beginning
 /// of the body of the loop.
+///
+/// The `try` frame has a `handler` field that references the `Block` which exceptions thrown
+/// within the body of the `try` are propagated to.
 #[derive(Debug)]
 pub enum ControlStackFrame {
     If {
@@ -84,6 +103,20 @@ pub enum ControlStackFrame {
         num_return_values: usize,
         original_stack_size: usize,
     },
+    Try {
+        destination: Block,
+        num_param_values: usize,
+        num_return_values: usize,
+        original_stack_size: usize,
+        exit_is_branched_to: bool,
+        handler: Block,
+        /// Does anything within the body of the `try` propagate exceptions to
+        /// `handler`?
+        handler_is_used: bool,
+        /// This is `None` while translating the body of the `try` and is set
+        /// to `Some` once the first `catch` clause is hit.
+        catch: Option<CatchData>,
+    },
 }
 
 /// Helper methods for the control stack objects.
@@ -98,6 +131,9 @@ impl ControlStackFrame {
             }
             | Self::Loop {
                 num_return_values, ..
+            }
+            | Self::Try {
+                num_return_values, ..
             } => num_return_values,
         }
     }
@@ -111,6 +147,9 @@ impl ControlStackFrame {
             }
             | Self::Loop {
                 num_param_values, ..
+            }
+            | Self::Try {
+                num_param_values, ..
             } => num_param_values,
         }
     }
@@ -118,12 +157,15 @@ impl ControlStackFrame {
         match *self {
             Self::If { destination, .. }
             | Self::Block { destination, .. }
-            | Self::Loop { destination, .. } => destination,
+            | Self::Loop { destination, .. }
+            | Self::Try { destination, .. } => destination,
         }
     }
     pub fn br_destination(&self) -> Block {
         match *self {
-            Self::If { destination, .. } | Self::Block { destination, .. } => destination,
+            Self::If { destination, .. }
+            | Self::Block { destination, .. }
+            | Self::Try { destination, .. } => destination,
             Self::Loop { header, .. } => header,
         }
     }
@@ -142,12 +184,16 @@ impl ControlStackFrame {
             | Self::Loop {
                 original_stack_size,
                 ..
+            }
+            | Self::Try {
+                original_stack_size,
+                ..
             } => original_stack_size,
         }
     }
     pub fn is_loop(&self) -> bool {
         match *self {
-            Self::If { .. } | Self::Block { .. } => false,
+            Self::If { .. } | Self::Block { .. } | Self::Try { .. } => false,
             Self::Loop { .. } => true,
         }
     }
@@ -161,6 +207,10 @@ impl ControlStackFrame {
             | Self::Block {
                 exit_is_branched_to,
                 ..
+            }
+            | Self::Try {
+                exit_is_branched_to,
+                ..
             } => exit_is_branched_to,
             Self::Loop { .. } => false,
         }
@@ -175,11 +225,27 @@ impl ControlStackFrame {
             | Self::Block {
                 ref mut exit_is_branched_to,
                 ..
+            }
+            | Self::Try {
+                ref mut exit_is_branched_to,
+                ..
             } => *exit_is_branched_to = true,
             Self::Loop { .. } => {}
         }
     }
 
+    /// Returns the stack slot holding the handle of the exception caught by
+    /// this frame, if this is a `try` block within its `catch` clauses.
+    pub fn caught_exception(&self) -> Option<ir::StackSlot> {
+        match *self {
+            Self::Try {
+                catch: Some(CatchData { handle, .. }),
+                ..
+            } => handle,
+            _ => None,
+        }
+    }
+
     /// Pop values from the value stack so that it is left at the
     /// input-parameters to an else-block.
     pub fn truncate_value_stack_to_else_params(&self, stack: &mut Vec<Value>) {
@@ -217,6 +283,11 @@ pub struct FuncTranslationState {
     /// A stack of values corresponding to the active values in the input wasm function at this
     /// point.
     pub(crate) stack: Vec<Value>,
+    /// The types of the values in `stack` before the operator being translated, when the
+    /// environment asks for them through `FuncEnvironment::needs_operand_stack_types`.
+    pub(crate) stack_types: Vec<WasmType>,
+    /// The source location of the operator being translated.
+    pub(crate) srcloc: ir::SourceLoc,
     /// A stack of active control flow operations at this point in the input wasm function.
     pub(crate) control_stack: Vec<ControlStackFrame>,
     /// Is the current translation state still reachable? This is false when translating operators
@@ -250,6 +321,25 @@ impl FuncTranslationState {
     pub fn reachable(&self) -> bool {
         self.reachable
     }
+
+    /// The values on the operand stack, from its bottom to its top.
+    pub fn operand_stack(&self) -> &[Value] {
+        &self.stack
+    }
+
+    /// The types of the values on the operand stack before the operator being translated, from
+    /// the bottom of the stack to its top.
+    ///
+    /// This is empty unless `FuncEnvironment::needs_operand_stack_types` returns true and the
+    /// operator is reachable.
+    pub fn operand_stack_types(&self) -> &[WasmType] {
+        &self.stack_types
+    }
+
+    /// The source location of the operator being translated.
+    pub fn srcloc(&self) -> ir::SourceLoc {
+        self.srcloc
+    }
 }
 
 impl FuncTranslationState {
@@ -257,6 +347,8 @@ impl FuncTranslationState {
     pub(crate) fn new() -> Self {
         Self {
             stack: Vec::new(),
+            stack_types: Vec::new(),
+            srcloc: ir::SourceLoc::default(),
             control_stack: Vec::new(),
             reachable: true,
             globals: HashMap::new(),
@@ -271,6 +363,8 @@ impl FuncTranslationState {
         debug_assert!(self.stack.is_empty());
         debug_assert!(self.control_stack.is_empty());
         self.reachable = true;
+        self.stack_types.clear();
+        self.srcloc = ir::SourceLoc::default();
         self.globals.clear();
         self.memory_to_heap.clear();
         self.tables.clear();
@@ -403,6 +497,50 @@ impl FuncTranslationState {
         });
     }
 
+    /// Push a try on the control stack.
+    pub(crate) fn push_try(
+        &mut self,
+        following_code: Block,
+        handler: Block,
+        num_param_types: usize,
+        num_result_types: usize,
+    ) {
+        debug_assert!(num_param_types <= self.stack.len());
+        self.control_stack.push(ControlStackFrame::Try {
+            destination: following_code,
+            original_stack_size: self.stack.len() - num_param_types,
+            num_param_values: num_param_types,
+            num_return_values: num_result_types,
+            exit_is_branched_to: false,
+            handler,
+            handler_is_used: false,
+            catch: None,
+        });
+    }
+
+    /// Returns the index within the control stack, and the handler block, of
+    /// the innermost `try` block below index `depth` whose body is being
+    /// translated, if any. This is where exceptions thrown from within all
+    /// frames at `depth` and above are propagated to.
+    pub(crate) fn exception_landing(&mut self, depth: usize) -> Option<(usize, Block)> {
+        self.control_stack[..depth]
+            .iter_mut()
+            .enumerate()
+            .rev()
+            .find_map(|(i, frame)| match frame {
+                ControlStackFrame::Try {
+                    handler,
+                    handler_is_used,
+                    catch: None,
+                    ..
+                } => {
+                    *handler_is_used = true;
+                    Some((i, *handler))
+                }
+                _ => None,
+            })
+    }
+
     /// Push an if on the control stack.
     pub(crate) fn push_if(
         &mut self,
@@ -70,6 +70,19 @@ typedef struct wasmtime_global {
   size_t index;
 } wasmtime_global_t;
 
+/// \brief Representation of an exception tag in Wasmtime.
+///
+/// Tags are represented with a 64-bit identifying integer in Wasmtime. They do
+/// not have any destructor associated with them. Tags cannot interoperate
+/// between #wasmtime_store_t instances and if the wrong tag is passed to the
+/// wrong store then it may trigger an assertion to abort the process.
+typedef struct wasmtime_tag {
+  /// Internal identifier of what store this belongs to, never zero.
+  uint64_t store_id;
+  /// Internal index within the store.
+  size_t index;
+} wasmtime_tag_t;
+
 /// \brief Discriminant of #wasmtime_extern_t
 typedef uint8_t wasmtime_extern_kind_t;
 
@@ -85,6 +98,13 @@ typedef uint8_t wasmtime_extern_kind_t;
 /// \brief Value of #wasmtime_extern_kind_t meaning that #wasmtime_extern_t is a
 /// memory
 #define WASMTIME_EXTERN_MEMORY 3
+/// \brief Value of #wasmtime_extern_kind_t meaning that #wasmtime_extern_t is an
+/// exception tag
+///
+/// Tags are not part of `wasm.h`, but this is also the value that
+/// #wasm_extern_kind and #wasm_externtype_kind return for a tag. There are
+/// no other functions operating on tags in the C API yet.
+#define WASMTIME_EXTERN_TAG 4
 
 /**
  * \typedef wasmtime_extern_union_t
@@ -105,6 +125,8 @@ typedef union wasmtime_extern_union {
     wasmtime_table_t table;
     /// Field used if #wasmtime_extern_t::kind is #WASMTIME_EXTERN_MEMORY
     wasmtime_memory_t memory;
+    /// Field used if #wasmtime_extern_t::kind is #WASMTIME_EXTERN_TAG
+    wasmtime_tag_t tag;
 } wasmtime_extern_union_t;
 
 /**
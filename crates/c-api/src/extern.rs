@@ -3,7 +3,7 @@ use crate::{
     CStoreContext, StoreRef,
 };
 use std::mem::ManuallyDrop;
-use wasmtime::{Extern, Func, Global, Memory, Table};
+use wasmtime::{Extern, Func, Global, Memory, Table, Tag};
 
 #[derive(Clone)]
 pub struct wasm_extern_t {
@@ -21,6 +21,7 @@ pub extern "C" fn wasm_extern_kind(e: &wasm_extern_t) -> wasm_externkind_t {
         Extern::Table(_) => crate::WASM_EXTERN_TABLE,
         Extern::Memory(_) => crate::WASM_EXTERN_MEMORY,
         Extern::SharedMemory(_) => todo!(),
+        Extern::Tag(_) => crate::WASM_EXTERN_TAG,
     }
 }
 
@@ -80,6 +81,7 @@ pub const WASMTIME_EXTERN_FUNC: wasmtime_extern_kind_t = 0;
 pub const WASMTIME_EXTERN_GLOBAL: wasmtime_extern_kind_t = 1;
 pub const WASMTIME_EXTERN_TABLE: wasmtime_extern_kind_t = 2;
 pub const WASMTIME_EXTERN_MEMORY: wasmtime_extern_kind_t = 3;
+pub const WASMTIME_EXTERN_TAG: wasmtime_extern_kind_t = 4;
 
 #[repr(C)]
 pub union wasmtime_extern_union {
@@ -87,6 +89,7 @@ pub union wasmtime_extern_union {
     pub table: Table,
     pub global: Global,
     pub memory: Memory,
+    pub tag: Tag,
 }
 
 impl wasmtime_extern_t {
@@ -96,6 +99,7 @@ impl wasmtime_extern_t {
             WASMTIME_EXTERN_GLOBAL => Extern::Global(self.of.global),
             WASMTIME_EXTERN_TABLE => Extern::Table(self.of.table),
             WASMTIME_EXTERN_MEMORY => Extern::Memory(self.of.memory),
+            WASMTIME_EXTERN_TAG => Extern::Tag(self.of.tag),
             other => panic!("unknown wasm_extern_kind_t: {}", other),
         }
     }
@@ -121,6 +125,10 @@ impl From<Extern> for wasmtime_extern_t {
                 of: wasmtime_extern_union { memory },
             },
             Extern::SharedMemory(_memory) => todo!(),
+            Extern::Tag(tag) => wasmtime_extern_t {
+                kind: WASMTIME_EXTERN_TAG,
+                of: wasmtime_extern_union { tag },
+            },
         }
     }
 }
@@ -42,12 +42,20 @@ pub const SUPPORTED_WASM_FEATURES: &[(&str, &str)] = &[
     ("tail-call", "enables support for WebAssembly tail calls"),
     ("threads", "enables support for WebAssembly threads"),
     ("memory64", "enables support for 64-bit memories"),
+    (
+        "extended-const",
+        "enables support for extended constant expressions",
+    ),
     #[cfg(feature = "component-model")]
     ("component-model", "enables support for the component model"),
     (
         "function-references",
         "enables support for typed function references",
     ),
+    (
+        "exceptions",
+        "enables support for the exception-handling proposal",
+    ),
 ];
 
 pub const SUPPORTED_WASI_MODULES: &[(&str, &str)] = &[
@@ -228,9 +236,18 @@ pub struct CommonOptions {
 
     /// Enable the pooling allocator, in place of the on-demand
     /// allocator.
+    ///
+    /// Commands which instantiate for every request, like `serve`, use the
+    /// pooling allocator unless `--pooling-allocator=false` is passed.
     #[cfg(feature = "pooling-allocator")]
-    #[clap(long)]
-    pub pooling_allocator: bool,
+    #[clap(
+        long,
+        value_name = "true|false",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true"
+    )]
+    pub pooling_allocator: Option<bool>,
 
     /// Maximum stack size, in bytes, that wasm is allowed to consume before a
     /// stack overflow is reported.
@@ -347,7 +364,7 @@ impl CommonOptions {
 
         #[cfg(feature = "pooling-allocator")]
         {
-            if self.pooling_allocator {
+            if self.pooling_allocator == Some(true) {
                 config.allocation_strategy(wasmtime::InstanceAllocationStrategy::pooling());
             }
         }
@@ -372,9 +389,11 @@ impl CommonOptions {
             threads,
             multi_memory,
             memory64,
+            extended_const,
             #[cfg(feature = "component-model")]
             component_model,
             function_references,
+            exceptions,
         } = self.wasm_features.unwrap_or_default();
 
         if let Some(enable) = simd {
@@ -392,6 +411,9 @@ impl CommonOptions {
         if let Some(enable) = function_references {
             config.wasm_function_references(enable);
         }
+        if let Some(enable) = exceptions {
+            config.wasm_exceptions(enable);
+        }
         if let Some(enable) = multi_value {
             config.wasm_multi_value(enable);
         }
@@ -407,6 +429,9 @@ impl CommonOptions {
         if let Some(enable) = memory64 {
             config.wasm_memory64(enable);
         }
+        if let Some(enable) = extended_const {
+            config.wasm_extended_const(enable);
+        }
         #[cfg(feature = "component-model")]
         if let Some(enable) = component_model {
             config.wasm_component_model(enable);
@@ -446,9 +471,11 @@ pub struct WasmFeatures {
     pub threads: Option<bool>,
     pub multi_memory: Option<bool>,
     pub memory64: Option<bool>,
+    pub extended_const: Option<bool>,
     #[cfg(feature = "component-model")]
     pub component_model: Option<bool>,
     pub function_references: Option<bool>,
+    pub exceptions: Option<bool>,
 }
 
 fn parse_wasm_features(features: &str) -> Result<WasmFeatures> {
@@ -499,9 +526,11 @@ fn parse_wasm_features(features: &str) -> Result<WasmFeatures> {
         threads: all.or(values["threads"]),
         multi_memory: all.or(values["multi-memory"]),
         memory64: all.or(values["memory64"]),
+        extended_const: all.or(values["extended-const"]),
         #[cfg(feature = "component-model")]
         component_model: all.or(values["component-model"]),
         function_references: all.or(values["function-references"]),
+        exceptions: all.or(values["exceptions"]),
     })
 }
 
@@ -612,7 +641,9 @@ mod test {
             threads,
             multi_memory,
             memory64,
+            extended_const,
             function_references,
+            exceptions,
             #[cfg(feature = "component-model")]
             component_model,
         } = options.wasm_features.unwrap();
@@ -625,7 +656,9 @@ mod test {
         assert_eq!(threads, Some(true));
         assert_eq!(multi_memory, Some(true));
         assert_eq!(memory64, Some(true));
+        assert_eq!(extended_const, Some(true));
         assert_eq!(function_references, Some(true));
+        assert_eq!(exceptions, Some(true));
         assert_eq!(relaxed_simd, Some(true));
         #[cfg(feature = "component-model")]
         assert_eq!(component_model, Some(true));
@@ -647,7 +680,9 @@ mod test {
             threads,
             multi_memory,
             memory64,
+            extended_const,
             function_references,
+            exceptions,
             #[cfg(feature = "component-model")]
             component_model,
         } = options.wasm_features.unwrap();
@@ -660,7 +695,9 @@ mod test {
         assert_eq!(threads, Some(false));
         assert_eq!(multi_memory, Some(false));
         assert_eq!(memory64, Some(false));
+        assert_eq!(extended_const, Some(false));
         assert_eq!(function_references, Some(false));
+        assert_eq!(exceptions, Some(false));
         assert_eq!(relaxed_simd, Some(false));
         #[cfg(feature = "component-model")]
         assert_eq!(component_model, Some(false));
@@ -685,7 +722,9 @@ mod test {
             threads,
             multi_memory,
             memory64,
+            extended_const,
             function_references,
+            exceptions,
             #[cfg(feature = "component-model")]
             component_model,
         } = options.wasm_features.unwrap();
@@ -698,7 +737,9 @@ mod test {
         assert_eq!(threads, None);
         assert_eq!(multi_memory, Some(true));
         assert_eq!(memory64, Some(true));
+        assert_eq!(extended_const, None);
         assert_eq!(function_references, None);
+        assert_eq!(exceptions, None);
         assert_eq!(relaxed_simd, None);
         #[cfg(feature = "component-model")]
         assert_eq!(component_model, None);
@@ -744,6 +785,12 @@ mod test {
     feature_test!(test_threads_feature, threads, "threads");
     feature_test!(test_multi_memory_feature, multi_memory, "multi-memory");
     feature_test!(test_memory64_feature, memory64, "memory64");
+    feature_test!(
+        test_extended_const_feature,
+        extended_const,
+        "extended-const"
+    );
+    feature_test!(test_exceptions_feature, exceptions, "exceptions");
 
     #[test]
     fn test_default_modules() {
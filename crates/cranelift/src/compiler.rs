@@ -1,5 +1,5 @@
 use crate::debug::{DwarfSectionRelocTarget, ModuleMemoryOffset};
-use crate::func_environ::FuncEnvironment;
+use crate::func_environ::{CapturedWasmLocals, FuncEnvironment};
 use crate::{array_call_signature, native_call_signature, DEBUG_ASSERT_TRAP_CODE};
 use crate::{builder::LinkOptions, value_type, wasm_call_signature};
 use anyhow::{Context as _, Result};
@@ -27,11 +27,11 @@ use std::mem;
 use std::path;
 use std::sync::{Arc, Mutex};
 use wasmparser::{FuncValidatorAllocations, FunctionBody};
-use wasmtime_cranelift_shared::{CompiledFunction, ModuleTextBuilder};
+use wasmtime_cranelift_shared::{CompiledFunction, CompiledFunctionMetadata, ModuleTextBuilder};
 use wasmtime_environ::{
     AddressMapSection, CacheStore, CompileError, FlagValue, FunctionBodyData, FunctionLoc,
     ModuleTranslation, ModuleTypes, PtrSize, StackMapInformation, TrapEncodingBuilder, Tunables,
-    VMOffsets, WasmFunctionInfo,
+    TypeConvert, VMOffsets, WasmError, WasmFunctionInfo, WasmLocalsInfo,
 };
 
 #[cfg(feature = "component-model")]
@@ -206,6 +206,20 @@ impl wasmtime_environ::Compiler for Compiler {
         });
         context.func.stack_limit = Some(stack_limit);
         let FunctionBodyData { validator, body } = input;
+
+        if self.tunables.capture_wasm_locals {
+            let mut types = wasm_func_ty.params().to_vec();
+            for local in body.get_locals_reader().map_err(WasmError::from)? {
+                let (count, ty) = local.map_err(WasmError::from)?;
+                let ty = func_env.convert_valtype(ty);
+                types.extend((0..count).map(|_| ty));
+            }
+            func_env.capture_wasm_locals(&mut context.func, types);
+        }
+        if self.tunables.save_frame_vmctx {
+            func_env.save_frame_vmctx(&mut context.func);
+        }
+
         let mut validator =
             validator.into_validator(mem::take(&mut compiler.cx.validator_allocations));
         compiler.cx.func_translator.translate_body(
@@ -226,7 +240,10 @@ impl wasmtime_environ::Compiler for Compiler {
             write!(output, "{}", context.func.display()).unwrap();
         }
 
-        let (info, func) = compiler.finish_with_info(Some((&body, &self.tunables)))?;
+        let wasm_locals = func_env.take_wasm_locals();
+        let vmctx_slot = func_env.take_vmctx_slot();
+        let (info, func) =
+            compiler.finish_with_info(Some((&body, &self.tunables)), wasm_locals, vmctx_slot)?;
 
         let timing = cranelift_codegen::timing::take_current();
         log::debug!("{:?} translated in {:?}", func_index, timing.total());
@@ -574,8 +591,12 @@ impl wasmtime_environ::Compiler for Compiler {
         let functions_info = funcs
             .iter()
             .map(|(_, (_, func))| {
-                let f: &CompiledFunction<CompiledFuncEnv> = func.downcast_ref().unwrap();
-                f.metadata()
+                // Compilers that delegate the DWARF emission to Cranelift,
+                // like Winch, hand over the function metadata directly.
+                match func.downcast_ref::<CompiledFunction<CompiledFuncEnv>>() {
+                    Some(f) => f.metadata(),
+                    None => func.downcast_ref::<CompiledFunctionMetadata>().unwrap(),
+                }
             })
             .collect();
         let dwarf_sections = crate::debug::emit_dwarf(
@@ -1010,7 +1031,7 @@ impl FunctionCompiler<'_> {
     }
 
     fn finish(self) -> Result<CompiledFunction<CompiledFuncEnv>, CompileError> {
-        let (info, func) = self.finish_with_info(None)?;
+        let (info, func) = self.finish_with_info(None, None, None)?;
         assert!(info.stack_maps.is_empty());
         Ok(func)
     }
@@ -1018,6 +1039,8 @@ impl FunctionCompiler<'_> {
     fn finish_with_info(
         mut self,
         body_and_tunables: Option<(&FunctionBody<'_>, &Tunables)>,
+        wasm_locals: Option<CapturedWasmLocals>,
+        vmctx_slot: Option<ir::StackSlot>,
     ) -> Result<(WasmFunctionInfo, CompiledFunction<CompiledFuncEnv>), CompileError> {
         let context = &mut self.cx.codegen_context;
         let isa = &*self.compiler.isa;
@@ -1070,6 +1093,20 @@ impl FunctionCompiler<'_> {
         }
 
         let stack_maps = mach_stack_maps_to_stack_maps(compiled_code.buffer.stack_maps());
+
+        // Stack slots are addressed relative to the nominal stack pointer,
+        // which sits `frame_size` bytes below the frame pointer.
+        let frame_offset = |slot: ir::StackSlot| {
+            compiled_code.frame_size - compiled_code.sized_stackslot_offsets[slot]
+        };
+        let locals = wasm_locals.map(|captured| WasmLocalsInfo {
+            frame_offset: frame_offset(captured.locals_slot),
+            locals: captured.locals,
+            operand_stack_frame_offset: frame_offset(captured.operand_stack_slot),
+            operand_stacks: captured.operand_stacks.into(),
+        });
+        let vmctx_frame_offset = vmctx_slot.map(frame_offset);
+
         compiled_function
             .set_sized_stack_slots(std::mem::take(&mut context.func.sized_stack_slots));
         self.compiler.contexts.lock().unwrap().push(self.cx);
@@ -1078,6 +1115,8 @@ impl FunctionCompiler<'_> {
             WasmFunctionInfo {
                 start_srcloc: compiled_function.metadata().address_map.start_srcloc,
                 stack_maps: stack_maps.into(),
+                locals,
+                vmctx_frame_offset,
             },
             compiled_function,
         ))
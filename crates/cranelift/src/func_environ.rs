@@ -13,15 +13,15 @@ use cranelift_frontend::FunctionBuilder;
 use cranelift_frontend::Variable;
 use cranelift_wasm::{
     self, FuncIndex, FuncTranslationState, GlobalIndex, GlobalVariable, Heap, HeapData, HeapStyle,
-    MemoryIndex, TableIndex, TargetEnvironment, TypeIndex, WasmHeapType, WasmRefType, WasmResult,
-    WasmType,
+    MemoryIndex, TableIndex, TagIndex, TargetEnvironment, TypeIndex, WasmHeapType, WasmRefType,
+    WasmResult, WasmType,
 };
 use std::convert::TryFrom;
 use std::mem;
 use wasmparser::Operator;
 use wasmtime_environ::{
     BuiltinFunctionIndex, MemoryPlan, MemoryStyle, Module, ModuleTranslation, ModuleTypes, PtrSize,
-    TableStyle, Tunables, TypeConvert, VMOffsets, WASM_PAGE_SIZE,
+    TableStyle, Tunables, TypeConvert, VMOffsets, WasmLocal, WasmOperandStack, WASM_PAGE_SIZE,
 };
 use wasmtime_environ::{FUNCREF_INIT_BIT, FUNCREF_MASK};
 
@@ -156,10 +156,38 @@ pub struct FuncEnvironment<'module_environment> {
 
     fuel_consumed: i64,
 
+    /// When `Tunables::capture_wasm_locals` is enabled, the stack slots which
+    /// wasm locals and operand stack values are saved to.
+    wasm_locals: Option<CapturedWasmLocals>,
+
+    /// When `Tunables::save_frame_vmctx` is enabled, the stack slot which the
+    /// `VMContext` is saved to.
+    vmctx_slot: Option<ir::StackSlot>,
+
     #[cfg(feature = "wmemcheck")]
     wmemcheck: bool,
 }
 
+/// The stack slots that a function saves its wasm locals and operand stack
+/// values to, along with where each value lives within them.
+pub struct CapturedWasmLocals {
+    /// The stack slot that locals are saved to.
+    pub locals_slot: ir::StackSlot,
+    /// Every local, parameters included, in index order.
+    pub locals: Box<[WasmLocal]>,
+    /// The stack slot that operand stack values are saved to, which has an
+    /// 8-byte cell for each position of the operand stack.
+    pub operand_stack_slot: ir::StackSlot,
+    /// The operand stack at each call or trapping instruction translated so
+    /// far, in translation order.
+    pub operand_stacks: Vec<WasmOperandStack>,
+    /// The block that operand stack values were last saved in.
+    operand_stack_block: Option<ir::Block>,
+    /// The value last saved to each cell of `operand_stack_slot` within
+    /// `operand_stack_block`.
+    saved_operand_stack: Vec<Option<Value>>,
+}
+
 impl<'module_environment> FuncEnvironment<'module_environment> {
     pub fn new(
         isa: &'module_environment (dyn TargetIsa + 'module_environment),
@@ -200,11 +228,159 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
             // Start with at least one fuel being consumed because even empty
             // functions should consume at least some fuel.
             fuel_consumed: 1,
+            wasm_locals: None,
+            vmctx_slot: None,
             #[cfg(feature = "wmemcheck")]
             wmemcheck,
         }
     }
 
+    /// Configures this function to save the values of its wasm locals, which
+    /// have the types provided, to a stack slot created in `func`.
+    ///
+    /// Every local is saved on function entry and then again whenever it is
+    /// written to, so the slot always holds the current value of each local.
+    /// This is what allows recovering locals from a function's frame when
+    /// capturing a backtrace. The values on the operand stack are likewise
+    /// saved to a second stack slot before every call or trapping instruction.
+    pub fn capture_wasm_locals(
+        &mut self,
+        func: &mut Function,
+        types: impl IntoIterator<Item = WasmType>,
+    ) {
+        let mut size = 0;
+        let locals = types
+            .into_iter()
+            .map(|ty| {
+                // Each saved local gets an 8-byte cell in the slot. References
+                // and vectors aren't saved since they can't be represented in
+                // backtraces and core dumps.
+                let offset = match ty {
+                    WasmType::I32 | WasmType::I64 | WasmType::F32 | WasmType::F64 => {
+                        let offset = size;
+                        size += 8;
+                        Some(offset)
+                    }
+                    WasmType::V128 | WasmType::Ref(_) => None,
+                };
+                WasmLocal { ty, offset }
+            })
+            .collect();
+        let locals_slot = func.create_sized_stack_slot(ir::StackSlotData::new(
+            ir::StackSlotKind::ExplicitSlot,
+            size,
+        ));
+        // The operand stack slot grows as deeper operand stacks are saved.
+        let operand_stack_slot = func
+            .create_sized_stack_slot(ir::StackSlotData::new(ir::StackSlotKind::ExplicitSlot, 0));
+        self.wasm_locals = Some(CapturedWasmLocals {
+            locals_slot,
+            locals,
+            operand_stack_slot,
+            operand_stacks: Vec::new(),
+            operand_stack_block: None,
+            saved_operand_stack: Vec::new(),
+        });
+    }
+
+    /// Returns the stack slots that wasm locals and operand stack values are
+    /// saved to, and where each value lives within them, if
+    /// `capture_wasm_locals` was called.
+    pub fn take_wasm_locals(&mut self) -> Option<CapturedWasmLocals> {
+        self.wasm_locals.take()
+    }
+
+    /// Configures this function to save its `VMContext` pointer to a stack slot
+    /// created in `func` on entry, so that the instance a frame belongs to can
+    /// be recovered when capturing a backtrace.
+    pub fn save_frame_vmctx(&mut self, func: &mut Function) {
+        let slot = func.create_sized_stack_slot(ir::StackSlotData::new(
+            ir::StackSlotKind::ExplicitSlot,
+            self.pointer_type().bytes(),
+        ));
+        self.vmctx_slot = Some(slot);
+    }
+
+    /// Returns the stack slot that the `VMContext` is saved to, if
+    /// `save_frame_vmctx` was called.
+    pub fn take_vmctx_slot(&mut self) -> Option<ir::StackSlot> {
+        self.vmctx_slot.take()
+    }
+
+    /// Saves the current value of the wasm local `index` to the locals stack
+    /// slot, if locals are being captured and values of its type are saved.
+    fn save_wasm_local(&self, builder: &mut FunctionBuilder, index: u32) {
+        let captured = match &self.wasm_locals {
+            Some(captured) => captured,
+            None => return,
+        };
+        if let Some(offset) = captured.locals[index as usize].offset {
+            let val = builder.use_var(Variable::from_u32(index));
+            builder
+                .ins()
+                .stack_store(val, captured.locals_slot, i32::try_from(offset).unwrap());
+        }
+    }
+
+    /// Saves the values on the operand stack to the operand stack slot, if
+    /// locals are being captured, and records where each of them lives for the
+    /// instruction about to be translated.
+    ///
+    /// Values already saved to their cell within the current block aren't
+    /// saved again. Nothing is recorded when the operand stack is empty or the
+    /// types of its values aren't known.
+    fn save_operand_stack(&mut self, builder: &mut FunctionBuilder, state: &FuncTranslationState) {
+        let captured = match &mut self.wasm_locals {
+            Some(captured) => captured,
+            None => return,
+        };
+        let types = state.operand_stack_types();
+        if types.is_empty() {
+            return;
+        }
+        let stack = state.operand_stack();
+        debug_assert_eq!(stack.len(), types.len());
+
+        let block = builder.current_block();
+        if captured.operand_stack_block != block {
+            captured.operand_stack_block = block;
+            captured.saved_operand_stack.clear();
+        }
+        captured.saved_operand_stack.resize(stack.len(), None);
+        let slot = &mut builder.func.sized_stack_slots[captured.operand_stack_slot];
+        slot.size = slot.size.max(u32::try_from(stack.len() * 8).unwrap());
+
+        let mut values = Vec::with_capacity(stack.len());
+        for (i, (&val, &ty)) in stack.iter().zip(types).enumerate() {
+            // As with locals, references and vectors aren't saved.
+            let offset = match ty {
+                WasmType::I32 | WasmType::I64 | WasmType::F32 | WasmType::F64 => {
+                    u32::try_from(i * 8).unwrap()
+                }
+                WasmType::V128 | WasmType::Ref(_) => {
+                    values.push(WasmLocal { ty, offset: None });
+                    continue;
+                }
+            };
+            if captured.saved_operand_stack[i] != Some(val) {
+                builder.ins().stack_store(
+                    val,
+                    captured.operand_stack_slot,
+                    i32::try_from(offset).unwrap(),
+                );
+                captured.saved_operand_stack[i] = Some(val);
+            }
+            values.push(WasmLocal {
+                ty,
+                offset: Some(offset),
+            });
+        }
+        captured.operand_stacks.push(WasmOperandStack {
+            file_offset: state.srcloc().bits(),
+            values: values.into(),
+        });
+    }
+
     fn pointer_type(&self) -> ir::Type {
         self.isa.pointer_type()
     }
@@ -374,6 +550,36 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         builder.def_var(self.vmruntime_limits_ptr, interrupt_ptr);
     }
 
+    /// Returns the types of the payload of exceptions thrown with `tag`.
+    fn tag_params(&self, tag: TagIndex) -> &[WasmType] {
+        self.types[self.module.tags[tag]].params()
+    }
+
+    /// Creates a stack slot holding an exception payload of `len` values, in
+    /// the same layout as an array of `ValRaw`.
+    fn exception_payload_slot(&self, builder: &mut FunctionBuilder, len: usize) -> ir::StackSlot {
+        builder.create_sized_stack_slot(ir::StackSlotData::new(
+            ir::StackSlotKind::ExplicitSlot,
+            u32::try_from(len * mem::size_of::<u128>()).unwrap(),
+        ))
+    }
+
+    /// Calls the builtin `index` with the `vmctx` followed by `args`,
+    /// returning the call instruction.
+    fn call_exception_builtin(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        index: BuiltinFunctionIndex,
+        sig: ir::SigRef,
+        args: &[ir::Value],
+    ) -> ir::Inst {
+        let mut pos = builder.cursor();
+        let (vmctx, func_addr) = self.translate_load_builtin_function_address(&mut pos, index);
+        let mut call_args = vec![vmctx];
+        call_args.extend_from_slice(args);
+        builder.ins().call_indirect(sig, func_addr, &call_args)
+    }
+
     fn fuel_function_entry(&mut self, builder: &mut FunctionBuilder<'_>) {
         // On function entry we load the amount of fuel into a function-local
         // `self.fuel_var` to make fuel modifications fast locally. This cache
@@ -2385,12 +2591,165 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         Ok(())
     }
 
+    fn exceptions_enabled(&self) -> bool {
+        self.tunables.exceptions
+    }
+
+    fn call_may_throw(&self, callee: FuncIndex) -> bool {
+        self.translation.func_may_throw(callee)
+    }
+
+    fn translate_exception_pending(
+        &mut self,
+        builder: &mut FunctionBuilder,
+    ) -> WasmResult<ir::Value> {
+        // The `VMRuntimeLimits` pointer is loaded here rather than through the
+        // `vmruntime_limits_ptr` variable, which would otherwise have to be
+        // initialized, and kept alive across calls, in every function.
+        let pointer_type = self.pointer_type();
+        let vmctx = self.vmctx(builder.func);
+        let base = builder.ins().global_value(pointer_type, vmctx);
+        let offset = i32::try_from(self.offsets.vmctx_runtime_limits()).unwrap();
+        let limits = builder.ins().load(
+            pointer_type,
+            ir::MemFlags::trusted().with_readonly(),
+            base,
+            offset,
+        );
+        let offset = i32::from(self.offsets.ptr.vmruntime_limits_exception_pending());
+        Ok(builder
+            .ins()
+            .load(I32, ir::MemFlags::trusted(), limits, offset))
+    }
+
+    fn translate_throw(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        tag: TagIndex,
+        args: &[ir::Value],
+    ) -> WasmResult<()> {
+        // Spill the payload to the stack for the runtime to copy it out.
+        let slot = self.exception_payload_slot(builder, args.len());
+        let mut mflags = ir::MemFlags::trusted();
+        mflags.set_endianness(ir::Endianness::Little);
+        let values = builder.ins().stack_addr(self.pointer_type(), slot, 0);
+        for (i, arg) in args.iter().enumerate() {
+            let offset = i32::try_from(i * mem::size_of::<u128>()).unwrap();
+            builder.ins().store(mflags, *arg, values, offset);
+        }
+
+        let sig = self.builtin_function_signatures.throw(builder.func);
+        let tag = builder.ins().iconst(I32, i64::from(tag.as_u32()));
+        self.call_exception_builtin(builder, BuiltinFunctionIndex::throw(), sig, &[tag, values]);
+        Ok(())
+    }
+
+    fn translate_exception_catch(
+        &mut self,
+        builder: &mut FunctionBuilder,
+    ) -> WasmResult<ir::Value> {
+        let sig = self
+            .builtin_function_signatures
+            .exception_catch(builder.func);
+        let call =
+            self.call_exception_builtin(builder, BuiltinFunctionIndex::exception_catch(), sig, &[]);
+        Ok(builder.func.dfg.first_result(call))
+    }
+
+    fn translate_exception_matches(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        handle: ir::Value,
+        tag: TagIndex,
+    ) -> WasmResult<ir::Value> {
+        let sig = self
+            .builtin_function_signatures
+            .exception_matches(builder.func);
+        let tag = builder.ins().iconst(I32, i64::from(tag.as_u32()));
+        let call = self.call_exception_builtin(
+            builder,
+            BuiltinFunctionIndex::exception_matches(),
+            sig,
+            &[handle, tag],
+        );
+        Ok(builder.func.dfg.first_result(call))
+    }
+
+    fn translate_exception_payload(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        handle: ir::Value,
+        tag: TagIndex,
+    ) -> WasmResult<Vec<ir::Value>> {
+        let params = self.tag_params(tag).to_vec();
+        let slot = self.exception_payload_slot(builder, params.len());
+        let values = builder.ins().stack_addr(self.pointer_type(), slot, 0);
+        let sig = self
+            .builtin_function_signatures
+            .exception_payload(builder.func);
+        self.call_exception_builtin(
+            builder,
+            BuiltinFunctionIndex::exception_payload(),
+            sig,
+            &[handle, values],
+        );
+
+        let mut mflags = ir::MemFlags::trusted();
+        mflags.set_endianness(ir::Endianness::Little);
+        Ok(params
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| {
+                let offset = i32::try_from(i * mem::size_of::<u128>()).unwrap();
+                builder
+                    .ins()
+                    .load(super::value_type(self.isa, *ty), mflags, values, offset)
+            })
+            .collect())
+    }
+
+    fn translate_rethrow(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        handle: ir::Value,
+    ) -> WasmResult<()> {
+        let sig = self.builtin_function_signatures.rethrow(builder.func);
+        self.call_exception_builtin(builder, BuiltinFunctionIndex::rethrow(), sig, &[handle]);
+        Ok(())
+    }
+
+    fn translate_exception_drop(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        handle: ir::Value,
+    ) -> WasmResult<()> {
+        let sig = self
+            .builtin_function_signatures
+            .exception_drop(builder.func);
+        self.call_exception_builtin(
+            builder,
+            BuiltinFunctionIndex::exception_drop(),
+            sig,
+            &[handle],
+        );
+        Ok(())
+    }
+
+    fn needs_operand_stack_types(&self) -> bool {
+        self.tunables.capture_wasm_locals
+    }
+
     fn before_translate_operator(
         &mut self,
         op: &Operator,
         builder: &mut FunctionBuilder,
         state: &FuncTranslationState,
     ) -> WasmResult<()> {
+        // Save the operand stack wherever this frame may show up in a
+        // backtrace: while calling out, or when trapping.
+        if state.reachable() && may_call_or_trap(op) {
+            self.save_operand_stack(builder, state);
+        }
         if self.tunables.consume_fuel {
             self.fuel_before_op(op, builder, state.reachable());
         }
@@ -2403,6 +2762,11 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         builder: &mut FunctionBuilder,
         state: &FuncTranslationState,
     ) -> WasmResult<()> {
+        if state.reachable() {
+            if let Operator::LocalSet { local_index } | Operator::LocalTee { local_index } = op {
+                self.save_wasm_local(builder, *local_index);
+            }
+        }
         if self.tunables.consume_fuel && state.reachable() {
             self.fuel_after_op(op, builder);
         }
@@ -2425,6 +2789,20 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         builder: &mut FunctionBuilder,
         _state: &FuncTranslationState,
     ) -> WasmResult<()> {
+        // Save the `VMContext` and the initial value of every local first, so
+        // that they can be recovered if anything below traps.
+        if let Some(slot) = self.vmctx_slot {
+            let pointer_type = self.pointer_type();
+            let vmctx = self.vmctx(builder.func);
+            let vmctx = builder.ins().global_value(pointer_type, vmctx);
+            builder.ins().stack_store(vmctx, slot, 0);
+        }
+        if let Some(captured) = &self.wasm_locals {
+            for index in 0..captured.locals.len() {
+                self.save_wasm_local(builder, u32::try_from(index).unwrap());
+            }
+        }
+
         // If the `vmruntime_limits_ptr` variable will get used then we initialize
         // it here.
         if self.tunables.consume_fuel || self.tunables.epoch_interruption {
@@ -2587,3 +2965,74 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         }
     }
 }
+
+/// Whether `op` may call a function, or trap, when it executes. These are the
+/// instructions that a function's frame can be at when it shows up in a
+/// backtrace.
+fn may_call_or_trap(op: &Operator) -> bool {
+    match op {
+        // Loop headers check for fuel and epochs, which may trap or call into
+        // the host.
+        Operator::Unreachable
+        | Operator::Loop { .. }
+        | Operator::Call { .. }
+        | Operator::CallIndirect { .. }
+        | Operator::CallRef { .. }
+        | Operator::Throw { .. }
+        | Operator::Rethrow { .. }
+        | Operator::RefFunc { .. }
+        | Operator::RefAsNonNull
+        | Operator::I32DivS
+        | Operator::I32DivU
+        | Operator::I32RemS
+        | Operator::I32RemU
+        | Operator::I64DivS
+        | Operator::I64DivU
+        | Operator::I64RemS
+        | Operator::I64RemU
+        | Operator::I32TruncF32S
+        | Operator::I32TruncF32U
+        | Operator::I32TruncF64S
+        | Operator::I32TruncF64U
+        | Operator::I64TruncF32S
+        | Operator::I64TruncF32U
+        | Operator::I64TruncF64S
+        | Operator::I64TruncF64U
+        | Operator::MemoryGrow { .. }
+        | Operator::MemoryFill { .. }
+        | Operator::MemoryCopy { .. }
+        | Operator::MemoryInit { .. }
+        | Operator::DataDrop { .. }
+        | Operator::TableGet { .. }
+        | Operator::TableSet { .. }
+        | Operator::TableGrow { .. }
+        | Operator::TableFill { .. }
+        | Operator::TableCopy { .. }
+        | Operator::TableInit { .. }
+        | Operator::ElemDrop { .. } => true,
+        _ => accesses_memory(op),
+    }
+}
+
+macro_rules! def_accesses_memory {
+    ($( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident)*) => {
+        /// Whether `op` loads from or stores to linear memory, and so may trap
+        /// when out of bounds, which is the case of every instruction with a
+        /// `memarg` immediate.
+        fn accesses_memory(op: &Operator) -> bool {
+            match op {
+                $(
+                    Operator::$op $({ $($arg: _),* })? => {
+                        def_accesses_memory!(has_memarg $($($arg)*)?)
+                    }
+                )*
+            }
+        }
+    };
+
+    (has_memarg) => { false };
+    (has_memarg memarg $($rest:ident)*) => { true };
+    (has_memarg $arg:ident $($rest:ident)*) => { def_accesses_memory!(has_memarg $($rest)*) };
+}
+
+wasmparser::for_each_operator!(def_accesses_memory);
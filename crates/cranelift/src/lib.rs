@@ -11,6 +11,7 @@ use target_lexicon::Architecture;
 use wasmtime_cranelift_shared::CompiledFunctionMetadata;
 
 pub use builder::builder;
+pub use compiler::CompiledFuncEnv;
 use wasmtime_environ::Tunables;
 
 mod builder;
@@ -67,6 +67,19 @@ macro_rules! foreach_builtin_function {
             update_stack_pointer(vmctx: vmctx, value: i32);
             /// Invoked before memory.grow is called.
             update_mem_size(vmctx: vmctx, num_bytes: i32);
+            /// Invoked for wasm's `throw` instruction, with the exception's
+            /// payload stored at `values`.
+            throw(vmctx: vmctx, tag: i32, values: pointer);
+            /// Catches the pending exception, returning a handle to it.
+            exception_catch(vmctx: vmctx) -> i32;
+            /// Returns whether a caught exception was thrown with the given tag.
+            exception_matches(vmctx: vmctx, handle: i32, tag: i32) -> i32;
+            /// Copies the payload of a caught exception into `values`.
+            exception_payload(vmctx: vmctx, handle: i32, values: pointer);
+            /// Invoked for wasm's `rethrow` instruction.
+            rethrow(vmctx: vmctx, handle: i32);
+            /// Releases a caught exception once its `catch` clause is exited.
+            exception_drop(vmctx: vmctx, handle: i32);
         }
     };
 }
@@ -4,7 +4,7 @@
 use crate::{obj, Tunables};
 use crate::{
     DefinedFuncIndex, FilePos, FuncIndex, FunctionBodyData, ModuleTranslation, ModuleTypes,
-    PrimaryMap, StackMap, WasmError, WasmFuncType,
+    PrimaryMap, StackMap, WasmError, WasmFuncType, WasmType,
 };
 use anyhow::Result;
 use object::write::{Object, SymbolId};
@@ -25,6 +25,47 @@ use thiserror::Error;
 pub struct WasmFunctionInfo {
     pub start_srcloc: FilePos,
     pub stack_maps: Box<[StackMapInformation]>,
+    pub locals: Option<WasmLocalsInfo>,
+    pub vmctx_frame_offset: Option<u32>,
+}
+
+/// Description of where a function saves its wasm locals, and the values on
+/// its operand stack, within its stack frame, generated when
+/// `Tunables::capture_wasm_locals` is enabled.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WasmLocalsInfo {
+    /// The distance, in bytes, below the function's frame pointer at which the
+    /// saved locals start.
+    pub frame_offset: u32,
+    /// All of the function's locals, parameters included, in index order.
+    pub locals: Box<[WasmLocal]>,
+    /// The distance, in bytes, below the function's frame pointer at which the
+    /// saved operand stack values start.
+    pub operand_stack_frame_offset: u32,
+    /// The operand stack at each call or trapping instruction of the function
+    /// where it isn't empty, sorted by `file_offset`.
+    pub operand_stacks: Box<[WasmOperandStack]>,
+}
+
+/// A single wasm local, or operand stack value, within a `WasmLocalsInfo`.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct WasmLocal {
+    /// The type of this local.
+    pub ty: WasmType,
+    /// The offset of this local's value from the start of the saved locals, or
+    /// `None` if values of this type are not saved.
+    pub offset: Option<u32>,
+}
+
+/// The values on the operand stack when an instruction executes, before it
+/// pops its operands, within a `WasmLocalsInfo`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WasmOperandStack {
+    /// The offset of the instruction within the original wasm file.
+    pub file_offset: u32,
+    /// The values on the operand stack, from its bottom to its top, with
+    /// offsets relative to the start of the saved operand stack values.
+    pub values: Box<[WasmLocal]>,
 }
 
 /// Description of where a function is located in the text section of a
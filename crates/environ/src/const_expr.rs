@@ -0,0 +1,176 @@
+//! Constant expressions used to initialize globals, tables and memories.
+
+use crate::{FuncIndex, GlobalIndex, WasmError, WasmResult};
+use serde::{Deserialize, Serialize};
+use wasmparser::Operator;
+
+/// A WebAssembly constant expression.
+///
+/// These appear as the initializers of globals and tables, as the items of
+/// element segments, and as the offsets of active element and data segments.
+/// Without the extended-const proposal these are always a single operator, but
+/// with it they may be a short sequence of `i32`/`i64` arithmetic operators
+/// such as `global.get 0 i32.const 16 i32.add`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ConstExpr {
+    ops: Box<[ConstOp]>,
+}
+
+/// A single operator within a [`ConstExpr`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub enum ConstOp {
+    I32Const(i32),
+    I64Const(i64),
+    F32Const(u32),
+    F64Const(u64),
+    V128Const(u128),
+    GlobalGet(GlobalIndex),
+    RefNull,
+    RefFunc(FuncIndex),
+    I32Add,
+    I32Sub,
+    I32Mul,
+    I64Add,
+    I64Sub,
+    I64Mul,
+}
+
+impl ConstOp {
+    fn from_wasmparser(op: Operator<'_>) -> WasmResult<ConstOp> {
+        Ok(match op {
+            Operator::I32Const { value } => ConstOp::I32Const(value),
+            Operator::I64Const { value } => ConstOp::I64Const(value),
+            Operator::F32Const { value } => ConstOp::F32Const(value.bits()),
+            Operator::F64Const { value } => ConstOp::F64Const(value.bits()),
+            Operator::V128Const { value } => {
+                ConstOp::V128Const(u128::from_le_bytes(*value.bytes()))
+            }
+            Operator::GlobalGet { global_index } => {
+                ConstOp::GlobalGet(GlobalIndex::from_u32(global_index))
+            }
+            Operator::RefNull { hty: _ } => ConstOp::RefNull,
+            Operator::RefFunc { function_index } => {
+                ConstOp::RefFunc(FuncIndex::from_u32(function_index))
+            }
+            Operator::I32Add => ConstOp::I32Add,
+            Operator::I32Sub => ConstOp::I32Sub,
+            Operator::I32Mul => ConstOp::I32Mul,
+            Operator::I64Add => ConstOp::I64Add,
+            Operator::I64Sub => ConstOp::I64Sub,
+            Operator::I64Mul => ConstOp::I64Mul,
+            op => {
+                return Err(WasmError::Unsupported(format!(
+                    "unsupported operator in constant expression: {:?}",
+                    op
+                )))
+            }
+        })
+    }
+}
+
+impl ConstExpr {
+    /// Translates a validated constant expression from `wasmparser`.
+    pub fn from_wasmparser(expr: wasmparser::ConstExpr<'_>) -> WasmResult<ConstExpr> {
+        let mut reader = expr.get_binary_reader();
+        let mut ops = Vec::new();
+        loop {
+            match reader.read_operator()? {
+                Operator::End => break,
+                op => ops.push(ConstOp::from_wasmparser(op)?),
+            }
+        }
+        Ok(ConstExpr { ops: ops.into() })
+    }
+
+    /// Returns the operators that make up this expression.
+    pub fn ops(&self) -> &[ConstOp] {
+        &self.ops
+    }
+
+    /// Evaluates this expression as an integer.
+    ///
+    /// Offsets of segments, as well as extended-const global initializers,
+    /// are always `i32` or `i64` valued. The value returned here is the
+    /// result of the expression zero-extended to 64 bits, with `i32`
+    /// arithmetic wrapping at 32 bits.
+    ///
+    /// The `get_global` callback is used to resolve `global.get` operators
+    /// and must likewise return the zero-extended value of an integer global.
+    /// If it returns `None`, for example because globals aren't known yet at
+    /// compile time, then `None` is returned. `None` is also returned if this
+    /// expression contains a non-integer operator.
+    pub fn eval_int(&self, mut get_global: impl FnMut(GlobalIndex) -> Option<u64>) -> Option<u64> {
+        let mut stack: Vec<u64> = Vec::with_capacity(self.ops.len());
+        for op in self.ops.iter() {
+            let value = match *op {
+                ConstOp::I32Const(x) => u64::from(x as u32),
+                ConstOp::I64Const(x) => x as u64,
+                ConstOp::GlobalGet(index) => get_global(index)?,
+                ConstOp::I32Add | ConstOp::I32Sub | ConstOp::I32Mul => {
+                    let b = stack.pop()? as u32;
+                    let a = stack.pop()? as u32;
+                    u64::from(match op {
+                        ConstOp::I32Add => a.wrapping_add(b),
+                        ConstOp::I32Sub => a.wrapping_sub(b),
+                        _ => a.wrapping_mul(b),
+                    })
+                }
+                ConstOp::I64Add | ConstOp::I64Sub | ConstOp::I64Mul => {
+                    let b = stack.pop()?;
+                    let a = stack.pop()?;
+                    match op {
+                        ConstOp::I64Add => a.wrapping_add(b),
+                        ConstOp::I64Sub => a.wrapping_sub(b),
+                        _ => a.wrapping_mul(b),
+                    }
+                }
+                ConstOp::F32Const(_)
+                | ConstOp::F64Const(_)
+                | ConstOp::V128Const(_)
+                | ConstOp::RefNull
+                | ConstOp::RefFunc(_) => return None,
+            };
+            stack.push(value);
+        }
+        match stack[..] {
+            [value] => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expr(ops: &[ConstOp]) -> ConstExpr {
+        ConstExpr { ops: ops.into() }
+    }
+
+    #[test]
+    fn eval_int_wraps_i32() {
+        let e = expr(&[ConstOp::I32Const(-1), ConstOp::I32Const(2), ConstOp::I32Add]);
+        assert_eq!(e.eval_int(|_| None), Some(1));
+
+        let e = expr(&[ConstOp::I32Const(0), ConstOp::I32Const(1), ConstOp::I32Sub]);
+        assert_eq!(e.eval_int(|_| None), Some(u64::from(u32::MAX)));
+    }
+
+    #[test]
+    fn eval_int_globals() {
+        let e = expr(&[
+            ConstOp::GlobalGet(GlobalIndex::from_u32(0)),
+            ConstOp::I64Const(3),
+            ConstOp::I64Mul,
+        ]);
+        assert_eq!(e.eval_int(|_| None), None);
+        assert_eq!(e.eval_int(|_| Some(5)), Some(15));
+    }
+
+    #[test]
+    fn eval_int_non_integer() {
+        assert_eq!(expr(&[ConstOp::RefNull]).eval_int(|_| None), None);
+        assert_eq!(expr(&[ConstOp::F32Const(0)]).eval_int(|_| None), None);
+    }
+}
@@ -26,6 +26,7 @@
 mod address_map;
 mod builtin;
 mod compilation;
+mod const_expr;
 mod module;
 mod module_environ;
 mod module_types;
@@ -40,6 +41,7 @@ mod vmoffsets;
 pub use crate::address_map::*;
 pub use crate::builtin::*;
 pub use crate::compilation::*;
+pub use crate::const_expr::*;
 pub use crate::module::*;
 pub use crate::module_environ::*;
 pub use crate::module_types::*;
@@ -3,12 +3,13 @@ use crate::module::{
     ModuleType, TablePlan, TableSegment,
 };
 use crate::{
-    DataIndex, DefinedFuncIndex, ElemIndex, EntityIndex, EntityType, FuncIndex, GlobalIndex,
-    GlobalInit, MemoryIndex, ModuleTypesBuilder, PrimaryMap, SignatureIndex, TableIndex,
-    TableInitialValue, Tunables, TypeConvert, TypeIndex, WasmError, WasmFuncType, WasmHeapType,
-    WasmResult, WasmType,
+    ConstExpr, ConstOp, DataIndex, DefinedFuncIndex, ElemIndex, EntityIndex, EntityType, FuncIndex,
+    GlobalIndex, MemoryIndex, ModuleTypesBuilder, PrimaryMap, SignatureIndex, TableIndex,
+    TableInitialValue, TagIndex, Tunables, TypeConvert, TypeIndex, WasmError, WasmFuncType,
+    WasmHeapType, WasmResult, WasmType,
 };
 use cranelift_entity::packed_option::ReservedValue;
+use cranelift_entity::SecondaryMap;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -51,6 +52,10 @@ pub struct ModuleTranslation<'data> {
     /// References to the function bodies.
     pub function_body_inputs: PrimaryMap<DefinedFuncIndex, FunctionBodyData<'data>>,
 
+    /// When exceptions are enabled, whether each defined function may throw
+    /// an exception or let one propagate out of it. This is empty otherwise.
+    pub funcs_may_throw: PrimaryMap<DefinedFuncIndex, bool>,
+
     /// A list of type signatures which are considered exported from this
     /// module, or those that can possibly be called. This list is sorted, and
     /// trampolines for each of these signatures are required.
@@ -104,6 +109,15 @@ impl<'data> ModuleTranslation<'data> {
             .as_ref()
             .expect("module type information to be available")
     }
+
+    /// Returns whether calling the function `index` may leave an exception
+    /// pending, which is always assumed for imported functions.
+    pub fn func_may_throw(&self, index: FuncIndex) -> bool {
+        match self.module.defined_func_index(index) {
+            Some(index) => self.funcs_may_throw.get(index).copied().unwrap_or(true),
+            None => true,
+        }
+    }
 }
 
 /// Contains function data: byte code and its offset in the module.
@@ -192,6 +206,58 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
         Ok(self.result)
     }
 
+    /// Determines which defined functions may throw an exception: those which
+    /// throw one themselves, make indirect calls or call imported functions,
+    /// and transitively those which call any of them.
+    ///
+    /// This lets the compiler skip checking for an exception after direct
+    /// calls to functions which can't throw.
+    fn calculate_funcs_may_throw(&mut self) -> WasmResult<()> {
+        let module = &self.result.module;
+        let bodies = &self.result.function_body_inputs;
+        let mut may_throw = PrimaryMap::with_capacity(bodies.len());
+        let mut callers = SecondaryMap::<DefinedFuncIndex, Vec<DefinedFuncIndex>>::new();
+        let mut worklist = Vec::new();
+
+        for (index, data) in bodies.iter() {
+            let mut throws = false;
+            let mut reader = data.body.get_operators_reader()?;
+            while !reader.eof() {
+                match reader.read()? {
+                    Operator::Throw { .. }
+                    | Operator::Rethrow { .. }
+                    | Operator::CallIndirect { .. }
+                    | Operator::ReturnCallIndirect { .. }
+                    | Operator::CallRef { .. }
+                    | Operator::ReturnCallRef { .. } => throws = true,
+                    Operator::Call { function_index } | Operator::ReturnCall { function_index } => {
+                        match module.defined_func_index(FuncIndex::from_u32(function_index)) {
+                            Some(callee) => callers[callee].push(index),
+                            None => throws = true,
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            may_throw.push(throws);
+            if throws {
+                worklist.push(index);
+            }
+        }
+
+        while let Some(callee) = worklist.pop() {
+            for &caller in callers[callee].iter() {
+                if !may_throw[caller] {
+                    may_throw[caller] = true;
+                    worklist.push(caller);
+                }
+            }
+        }
+
+        self.result.funcs_may_throw = may_throw;
+        Ok(())
+    }
+
     fn translate_payload(&mut self, payload: Payload<'data>) -> WasmResult<()> {
         match payload {
             Payload::Version {
@@ -229,6 +295,10 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                     .collect();
                 self.result.exported_signatures.sort_unstable();
                 self.result.exported_signatures.dedup();
+
+                if self.tunables.exceptions {
+                    self.calculate_funcs_may_throw()?;
+                }
             }
 
             Payload::TypeSection(types) => {
@@ -282,9 +352,11 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                             self.result.module.num_imported_tables += 1;
                             EntityType::Table(self.convert_table_type(&ty))
                         }
-
-                        // doesn't get past validation
-                        TypeRef::Tag(_) => unreachable!(),
+                        TypeRef::Tag(ty) => {
+                            self.result.module.num_imported_tags += 1;
+                            let index = TypeIndex::from_u32(ty.func_type_idx);
+                            EntityType::Tag(self.result.module.types[index].unwrap_function())
+                        }
                     };
                     self.declare_import(import.module, import.name, ty);
                 }
@@ -318,25 +390,18 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                         wasmparser::TableInit::RefNull => TableInitialValue::Null {
                             precomputed: Vec::new(),
                         },
-                        wasmparser::TableInit::Expr(cexpr) => {
-                            let mut init_expr_reader = cexpr.get_binary_reader();
-                            match init_expr_reader.read_operator()? {
-                                Operator::RefNull { hty: _ } => TableInitialValue::Null {
-                                    precomputed: Vec::new(),
-                                },
-                                Operator::RefFunc { function_index } => {
-                                    let index = FuncIndex::from_u32(function_index);
-                                    self.flag_func_escaped(index);
-                                    TableInitialValue::FuncRef(index)
-                                }
-                                s => {
-                                    return Err(WasmError::Unsupported(format!(
-                                        "unsupported init expr in table section: {:?}",
-                                        s
-                                    )));
-                                }
+                        wasmparser::TableInit::Expr(cexpr) => match self.const_expr(cexpr)?.ops() {
+                            [ConstOp::RefNull] => TableInitialValue::Null {
+                                precomputed: Vec::new(),
+                            },
+                            [ConstOp::RefFunc(index)] => TableInitialValue::FuncRef(*index),
+                            s => {
+                                return Err(WasmError::Unsupported(format!(
+                                    "unsupported init expr in table section: {:?}",
+                                    s
+                                )));
                             }
-                        }
+                        },
                     };
                     self.result
                         .module
@@ -362,9 +427,15 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
             Payload::TagSection(tags) => {
                 self.validator.tag_section(&tags)?;
 
-                // This feature isn't enabled at this time, so we should
-                // never get here.
-                unreachable!();
+                let cnt = usize::try_from(tags.count()).unwrap();
+                self.result.module.tags.reserve_exact(cnt);
+
+                for entry in tags {
+                    let ty = entry?;
+                    let index = TypeIndex::from_u32(ty.func_type_idx);
+                    let sig_index = self.result.module.types[index].unwrap_function();
+                    self.result.module.tags.push(sig_index);
+                }
             }
 
             Payload::GlobalSection(globals) => {
@@ -375,31 +446,7 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
 
                 for entry in globals {
                     let wasmparser::Global { ty, init_expr } = entry?;
-                    let mut init_expr_reader = init_expr.get_binary_reader();
-                    let initializer = match init_expr_reader.read_operator()? {
-                        Operator::I32Const { value } => GlobalInit::I32Const(value),
-                        Operator::I64Const { value } => GlobalInit::I64Const(value),
-                        Operator::F32Const { value } => GlobalInit::F32Const(value.bits()),
-                        Operator::F64Const { value } => GlobalInit::F64Const(value.bits()),
-                        Operator::V128Const { value } => {
-                            GlobalInit::V128Const(u128::from_le_bytes(*value.bytes()))
-                        }
-                        Operator::RefNull { hty: _ } => GlobalInit::RefNullConst,
-                        Operator::RefFunc { function_index } => {
-                            let index = FuncIndex::from_u32(function_index);
-                            self.flag_func_escaped(index);
-                            GlobalInit::RefFunc(index)
-                        }
-                        Operator::GlobalGet { global_index } => {
-                            GlobalInit::GetGlobal(GlobalIndex::from_u32(global_index))
-                        }
-                        s => {
-                            return Err(WasmError::Unsupported(format!(
-                                "unsupported init expr in global section: {:?}",
-                                s
-                            )));
-                        }
-                    };
+                    let initializer = self.const_expr(init_expr)?;
                     let ty = self.convert_global_type(&ty);
                     self.result.module.globals.push(ty);
                     self.result.module.global_initializers.push(initializer);
@@ -423,9 +470,7 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                         ExternalKind::Table => EntityIndex::Table(TableIndex::from_u32(index)),
                         ExternalKind::Memory => EntityIndex::Memory(MemoryIndex::from_u32(index)),
                         ExternalKind::Global => EntityIndex::Global(GlobalIndex::from_u32(index)),
-
-                        // this never gets past validation
-                        ExternalKind::Tag => unreachable!(),
+                        ExternalKind::Tag => EntityIndex::Tag(TagIndex::from_u32(index)),
                     };
                     self.result
                         .module
@@ -471,13 +516,9 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                         ElementItems::Expressions(_ty, funcs) => {
                             elements.reserve(usize::try_from(funcs.count()).unwrap());
                             for func in funcs {
-                                let func = match func?.get_binary_reader().read_operator()? {
-                                    Operator::RefNull { .. } => FuncIndex::reserved_value(),
-                                    Operator::RefFunc { function_index } => {
-                                        let func = FuncIndex::from_u32(function_index);
-                                        self.flag_func_escaped(func);
-                                        func
-                                    }
+                                let func = match self.const_expr(func?)?.ops() {
+                                    [ConstOp::RefNull] => FuncIndex::reserved_value(),
+                                    [ConstOp::RefFunc(func)] => *func,
                                     s => {
                                         return Err(WasmError::Unsupported(format!(
                                             "unsupported init expr in element section: {:?}",
@@ -496,19 +537,7 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                             offset_expr,
                         } => {
                             let table_index = TableIndex::from_u32(table_index.unwrap_or(0));
-                            let mut offset_expr_reader = offset_expr.get_binary_reader();
-                            let (base, offset) = match offset_expr_reader.read_operator()? {
-                                Operator::I32Const { value } => (None, value as u32),
-                                Operator::GlobalGet { global_index } => {
-                                    (Some(GlobalIndex::from_u32(global_index)), 0)
-                                }
-                                ref s => {
-                                    return Err(WasmError::Unsupported(format!(
-                                        "unsupported init expr in element section: {:?}",
-                                        s
-                                    )));
-                                }
-                            };
+                            let offset = self.const_expr(offset_expr)?;
 
                             self.result
                                 .module
@@ -516,7 +545,6 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                                 .segments
                                 .push(TableSegment {
                                     table_index,
-                                    base,
                                     offset,
                                     elements: elements.into(),
                                 });
@@ -616,24 +644,13 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                         } => {
                             let range = mk_range(&mut self.result.total_data)?;
                             let memory_index = MemoryIndex::from_u32(memory_index);
-                            let mut offset_expr_reader = offset_expr.get_binary_reader();
-                            let (base, offset) = match offset_expr_reader.read_operator()? {
-                                Operator::I32Const { value } => (None, value as u64),
-                                Operator::I64Const { value } => (None, value as u64),
-                                Operator::GlobalGet { global_index } => {
-                                    (Some(GlobalIndex::from_u32(global_index)), 0)
-                                }
-                                s => {
-                                    return Err(WasmError::Unsupported(format!(
-                                        "unsupported init expr in data section: {:?}",
-                                        s
-                                    )));
-                                }
-                            };
+                            // Data offsets are integers, so unlike other
+                            // constant expressions they can't contain a
+                            // `ref.func` whose function escapes.
+                            let offset = ConstExpr::from_wasmparser(offset_expr)?;
 
                             initializers.push(MemoryInitializer {
                                 memory_index,
-                                base,
                                 offset,
                                 data: range,
                             });
@@ -787,8 +804,20 @@ and for re-adding support for interface types you can see this issue:
                 EntityIndex::Memory(self.result.module.memory_plans.push(plan))
             }
             EntityType::Global(ty) => EntityIndex::Global(self.result.module.globals.push(ty)),
-            EntityType::Tag(_) => unimplemented!(),
+            EntityType::Tag(ty) => EntityIndex::Tag(self.result.module.tags.push(ty)),
+        }
+    }
+
+    /// Translates a constant expression, flagging any functions it references
+    /// as escaping.
+    fn const_expr(&mut self, expr: wasmparser::ConstExpr<'_>) -> WasmResult<ConstExpr> {
+        let expr = ConstExpr::from_wasmparser(expr)?;
+        for op in expr.ops() {
+            if let ConstOp::RefFunc(func) = *op {
+                self.flag_func_escaped(func);
+            }
         }
+        Ok(expr)
     }
 
     fn flag_func_escaped(&mut self, func: FuncIndex) {
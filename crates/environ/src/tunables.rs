@@ -52,6 +52,19 @@ pub struct Tunables {
 
     /// Whether or not Wasm functions can be tail-called or not.
     pub tail_callable: bool,
+
+    /// Whether or not Wasm functions save their locals to their stack frame so
+    /// that they can be recovered when capturing backtraces.
+    pub capture_wasm_locals: bool,
+
+    /// Whether or not Wasm functions save their `VMContext` to their stack
+    /// frame so that the instance each frame belongs to can be recovered when
+    /// capturing backtraces.
+    pub save_frame_vmctx: bool,
+
+    /// Whether or not compiled code checks for, and propagates, exceptions
+    /// after every call it makes.
+    pub exceptions: bool,
 }
 
 impl Default for Tunables {
@@ -110,6 +123,9 @@ impl Default for Tunables {
             debug_adapter_modules: false,
             relaxed_simd_deterministic: false,
             tail_callable: false,
+            capture_wasm_locals: false,
+            save_frame_vmctx: false,
+            exceptions: false,
         }
     }
 }
@@ -189,6 +189,11 @@ pub trait PtrSize {
         self.vmruntime_limits_last_wasm_exit_pc() + self.size()
     }
 
+    /// Return the offset of the `exception_pending` field of `VMRuntimeLimits`.
+    fn vmruntime_limits_exception_pending(&self) -> u8 {
+        self.vmruntime_limits_last_wasm_entry_sp() + self.size()
+    }
+
     // Offsets within `VMMemoryDefinition`
 
     /// The offset of the `base` field.
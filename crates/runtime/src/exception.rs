@@ -0,0 +1,198 @@
+//! Runtime support for the WebAssembly exception-handling proposal.
+//!
+//! Exceptions are not propagated by unwinding the native stack. Instead a
+//! thrown exception is stored in the store's [`ExceptionState`] and the
+//! `exception_pending` flag of `VMRuntimeLimits` is set. Compiled code checks
+//! that flag after every call which may throw and either branches to the
+//! handler of its innermost enclosing `try` block or returns to its own caller,
+//! which then performs the same check. Direct calls to functions which can
+//! neither throw nor call anything that does are not followed by a check.
+//!
+//! Cranelift has no way to resume execution at a landing pad in the middle of
+//! a function, so table-driven unwinding would additionally need per-platform
+//! code to restore callee-saved registers from the unwind info. The flag keeps
+//! the cost of a thrown exception proportional to the number of frames it
+//! crosses without any of that machinery. Once an exception reaches a `catch` clause it
+//! is moved into a table of caught exceptions, where it's referred to by an
+//! integer handle until the end of the clause so that it may be rethrown.
+//!
+//! If an exception propagates all the way out of wasm then the embedder takes
+//! it back out of the store with [`ExceptionState::take_pending`] after the
+//! call returns. Host functions called by wasm throw exceptions with
+//! [`ExceptionState::throw`] before returning normally.
+
+use crate::{VMExternRef, VMRuntimeLimits, ValRaw};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use wasmtime_environ::{WasmHeapType, WasmType};
+
+/// The runtime identity of an exception tag.
+///
+/// Every tag defined by an instance, or created by the host, receives a fresh
+/// identity. Exceptions are matched against `catch` clauses by comparing
+/// these identities.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VMTag(u64);
+
+impl VMTag {
+    /// Allocates a new tag identity, distinct from all others.
+    pub fn new() -> VMTag {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        VMTag(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A thrown WebAssembly exception: a tag along with its payload values.
+#[derive(Clone)]
+pub struct VMException {
+    tag: VMTag,
+    params: Box<[WasmType]>,
+    payload: Box<[ValRaw]>,
+    /// Owned handles to every GC reference within `payload`, which keep the
+    /// raw pointers there alive for as long as this exception is.
+    refs: Vec<VMExternRef>,
+}
+
+impl VMException {
+    /// Creates a new exception for `tag` carrying `payload`, whose values
+    /// have the types listed in `params`.
+    ///
+    /// # Unsafety
+    ///
+    /// The values of `payload` must be valid for the types in `params`, and
+    /// any GC references among them must currently be rooted.
+    pub unsafe fn new(tag: VMTag, payload: Box<[ValRaw]>, params: &[WasmType]) -> VMException {
+        assert_eq!(payload.len(), params.len());
+        let refs = params
+            .iter()
+            .zip(payload.iter())
+            .filter_map(|(ty, val)| match ty {
+                WasmType::Ref(r) if r.heap_type == WasmHeapType::Extern => {
+                    let raw = val.get_externref().cast::<u8>();
+                    if raw.is_null() {
+                        None
+                    } else {
+                        Some(VMExternRef::clone_from_raw(raw))
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+        VMException {
+            tag,
+            params: params.into(),
+            payload,
+            refs,
+        }
+    }
+
+    /// Returns the tag this exception was thrown with.
+    pub fn tag(&self) -> VMTag {
+        self.tag
+    }
+
+    /// Returns the types of this exception's payload values.
+    pub fn params(&self) -> &[WasmType] {
+        &self.params
+    }
+
+    /// Returns the raw payload values of this exception.
+    pub fn payload(&self) -> &[ValRaw] {
+        &self.payload
+    }
+
+    /// Returns the GC references held within this exception's payload.
+    pub(crate) fn refs(&self) -> &[VMExternRef] {
+        &self.refs
+    }
+}
+
+impl fmt::Debug for VMException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VMException")
+            .field("tag", &self.tag)
+            .field("payload_len", &self.payload.len())
+            .finish()
+    }
+}
+
+/// Per-store exception state: the exception currently propagating, if any,
+/// along with all exceptions caught by currently executing `catch` clauses.
+#[derive(Default, Debug)]
+pub struct ExceptionState {
+    pending: Option<VMException>,
+    caught: Vec<Option<VMException>>,
+    free: Vec<u32>,
+}
+
+impl ExceptionState {
+    /// Makes `exception` the pending exception, to be propagated up the
+    /// stack the next time wasm code checks for it.
+    ///
+    /// # Unsafety
+    ///
+    /// `limits` must be the valid `VMRuntimeLimits` of the store owning this
+    /// state.
+    pub unsafe fn throw(&mut self, limits: *mut VMRuntimeLimits, exception: VMException) {
+        self.pending = Some(exception);
+        *(*limits).exception_pending.get() = 1;
+    }
+
+    /// Takes the pending exception out of this state, if there is one.
+    ///
+    /// # Unsafety
+    ///
+    /// Same as [`ExceptionState::throw`].
+    pub unsafe fn take_pending(&mut self, limits: *mut VMRuntimeLimits) -> Option<VMException> {
+        let exception = self.pending.take()?;
+        *(*limits).exception_pending.get() = 0;
+        Some(exception)
+    }
+
+    /// Moves the pending exception into the table of caught exceptions,
+    /// returning its handle.
+    ///
+    /// # Unsafety
+    ///
+    /// Same as [`ExceptionState::throw`].
+    pub(crate) unsafe fn catch(&mut self, limits: *mut VMRuntimeLimits) -> u32 {
+        let exception = self
+            .take_pending(limits)
+            .expect("caught an exception without one pending");
+        match self.free.pop() {
+            Some(handle) => {
+                self.caught[handle as usize] = Some(exception);
+                handle
+            }
+            None => {
+                self.caught.push(Some(exception));
+                u32::try_from(self.caught.len() - 1).unwrap()
+            }
+        }
+    }
+
+    /// Returns the caught exception for `handle`.
+    pub(crate) fn caught(&self, handle: u32) -> &VMException {
+        self.caught[handle as usize]
+            .as_ref()
+            .expect("use of a dropped exception handle")
+    }
+
+    /// Releases the caught exception for `handle` once its `catch` clause is
+    /// exited.
+    pub(crate) fn drop_caught(&mut self, handle: u32) {
+        let exception = self.caught[handle as usize].take();
+        debug_assert!(exception.is_some());
+        self.free.push(handle);
+    }
+
+    /// Releases all caught exceptions.
+    ///
+    /// Compiled code releases caught exceptions whenever it leaves a `catch`
+    /// clause, except when a trap unwinds through the clause. This is used
+    /// to clean up after such traps once wasm has been exited entirely.
+    pub fn clear_caught(&mut self) {
+        self.caught.clear();
+        self.free.clear();
+    }
+}
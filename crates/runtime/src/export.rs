@@ -1,6 +1,8 @@
 use crate::vmcontext::{
-    VMContext, VMFuncRef, VMGlobalDefinition, VMMemoryDefinition, VMTableDefinition,
+    VMContext, VMFuncRef, VMGlobalDefinition, VMMemoryDefinition, VMSharedSignatureIndex,
+    VMTableDefinition,
 };
+use crate::VMTag;
 use std::ptr::NonNull;
 use wasmtime_environ::{DefinedMemoryIndex, Global, MemoryPlan, TablePlan};
 
@@ -17,6 +19,9 @@ pub enum Export {
 
     /// A global export value.
     Global(ExportGlobal),
+
+    /// A tag export value.
+    Tag(ExportTag),
 }
 
 /// A function export value.
@@ -103,3 +108,18 @@ impl From<ExportGlobal> for Export {
         Export::Global(func)
     }
 }
+
+/// A tag export value.
+#[derive(Debug, Clone, Copy)]
+pub struct ExportTag {
+    /// The runtime identity of the tag.
+    pub tag: VMTag,
+    /// The signature describing the tag's payload.
+    pub signature: VMSharedSignatureIndex,
+}
+
+impl From<ExportTag> for Export {
+    fn from(func: ExportTag) -> Export {
+        Export::Tag(func)
+    }
+}
@@ -110,7 +110,7 @@ use std::mem;
 use std::ops::Deref;
 use std::ptr::{self, NonNull};
 use std::sync::atomic::{self, AtomicUsize, Ordering};
-use wasmtime_environ::StackMap;
+use wasmtime_environ::{StackMap, WasmFunctionInfo};
 
 /// An external reference to some opaque data.
 ///
@@ -803,6 +803,15 @@ pub trait ModuleInfoLookup {
 pub trait ModuleInfo {
     /// Lookup the stack map at a program counter value.
     fn lookup_stack_map(&self, pc: usize) -> Option<&StackMap>;
+
+    /// Lookup the information of the wasm function containing the program
+    /// counter value, which describes what the function saves within its
+    /// stack frame, if that can be read from the frame at that point.
+    fn lookup_wasm_func_info(&self, pc: usize) -> Option<&WasmFunctionInfo>;
+
+    /// Lookup the offset, within the original wasm module, of the instruction
+    /// at a program counter value, if the module has an address map.
+    fn lookup_wasm_file_offset(&self, pc: usize) -> Option<u32>;
 }
 
 #[derive(Debug, Default)]
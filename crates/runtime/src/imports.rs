@@ -1,4 +1,5 @@
 use crate::vmcontext::{VMFunctionImport, VMGlobalImport, VMMemoryImport, VMTableImport};
+use crate::VMTag;
 
 /// Resolved import pointers.
 ///
@@ -24,4 +25,7 @@ pub struct Imports<'a> {
 
     /// Resolved addresses for imported globals.
     pub globals: &'a [VMGlobalImport],
+
+    /// Resolved identities of imported tags.
+    pub tags: &'a [VMTag],
 }
@@ -12,8 +12,9 @@ use crate::vmcontext::{
     VMTableDefinition, VMTableImport,
 };
 use crate::{
-    ExportFunction, ExportGlobal, ExportMemory, ExportTable, Imports, ModuleRuntimeInfo,
-    SendSyncPtr, Store, VMFunctionBody, VMSharedSignatureIndex, WasmFault,
+    ExportFunction, ExportGlobal, ExportMemory, ExportTable, ExportTag, Imports, ModuleRuntimeInfo,
+    SendSyncPtr, Store, VMException, VMFunctionBody, VMSharedSignatureIndex, VMTag, ValRaw,
+    WasmFault,
 };
 use anyhow::Error;
 use anyhow::Result;
@@ -27,10 +28,11 @@ use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::{mem, ptr};
 use wasmtime_environ::{
-    packed_option::ReservedValue, DataIndex, DefinedGlobalIndex, DefinedMemoryIndex,
+    packed_option::ReservedValue, ConstOp, DataIndex, DefinedGlobalIndex, DefinedMemoryIndex,
     DefinedTableIndex, ElemIndex, EntityIndex, EntityRef, EntitySet, FuncIndex, GlobalIndex,
-    GlobalInit, HostPtr, MemoryIndex, MemoryPlan, Module, PrimaryMap, SignatureIndex, TableIndex,
-    TableInitialValue, Trap, VMOffsets, WasmHeapType, WasmRefType, WasmType, VMCONTEXT_MAGIC,
+    HostPtr, MemoryIndex, MemoryPlan, Module, PrimaryMap, SignatureIndex, TableIndex,
+    TableInitialValue, TagIndex, Trap, VMOffsets, WasmHeapType, WasmRefType, WasmType,
+    VMCONTEXT_MAGIC,
 };
 #[cfg(feature = "wmemcheck")]
 use wasmtime_wmemcheck::Wmemcheck;
@@ -91,6 +93,10 @@ pub struct Instance {
     /// If the index is present in the set, the segment has been dropped.
     dropped_data: EntitySet<DataIndex>,
 
+    /// The runtime identities of all tags, imported and defined, of this
+    /// instance. Defined tags receive fresh identities on instantiation.
+    tags: PrimaryMap<TagIndex, VMTag>,
+
     /// Hosts can store arbitrary per-instance information here.
     ///
     /// Most of the time from Wasmtime this is `Box::new(())`, a noop
@@ -176,6 +182,14 @@ impl Instance {
         let module = req.runtime_info.module();
         let dropped_elements = EntitySet::with_capacity(module.passive_elements.len());
         let dropped_data = EntitySet::with_capacity(module.passive_data_map.len());
+        debug_assert_eq!(req.imports.tags.len(), module.num_imported_tags);
+        let tags = req
+            .imports
+            .tags
+            .iter()
+            .copied()
+            .chain((module.num_imported_tags..module.tags.len()).map(|_| VMTag::new()))
+            .collect();
 
         #[cfg(not(feature = "wmemcheck"))]
         let _ = memory_plans;
@@ -188,6 +202,7 @@ impl Instance {
                 tables,
                 dropped_elements,
                 dropped_data,
+                tags,
                 host_state: req.host_state,
                 vmctx_self_reference: SendSyncPtr::new(
                     NonNull::new(ptr.cast::<u8>().add(mem::size_of::<Instance>()).cast()).unwrap(),
@@ -509,6 +524,38 @@ impl Instance {
         }
     }
 
+    fn get_exported_tag(&mut self, index: TagIndex) -> ExportTag {
+        let signature = self.module().tags[index];
+        ExportTag {
+            tag: self.tags[index],
+            signature: self.runtime_info.signature_ids()[signature.index()],
+        }
+    }
+
+    /// Creates a new exception for the tag `index` of this instance, reading
+    /// its payload from `values`.
+    ///
+    /// # Unsafety
+    ///
+    /// `values` must point to as many valid values as the tag's signature has
+    /// parameters.
+    pub(crate) unsafe fn new_exception(
+        &mut self,
+        index: TagIndex,
+        values: *const ValRaw,
+    ) -> VMException {
+        let signature = self.module().tags[index];
+        let params = self.runtime_info.signature(signature).params();
+        let payload = std::slice::from_raw_parts(values, params.len()).into();
+        VMException::new(self.tags[index], payload, params)
+    }
+
+    /// Returns whether `exception` was thrown with the tag `index` of this
+    /// instance.
+    pub(crate) fn exception_matches(&self, exception: &VMException, index: TagIndex) -> bool {
+        exception.tag() == self.tags[index]
+    }
+
     /// Return an iterator over the exports of this instance.
     ///
     /// Specifically, it provides access to the key-value pairs, where the keys
@@ -1152,28 +1199,15 @@ impl Instance {
             // Initialize the global before writing to it
             ptr::write(to, VMGlobalDefinition::new());
 
-            match *init {
-                GlobalInit::I32Const(x) => {
-                    let index = module.global_index(index);
-                    if index.index() == 0 {
-                        #[cfg(feature = "wmemcheck")]
-                        {
-                            if let Some(wmemcheck) = &mut self.wmemcheck_state {
-                                wmemcheck.set_stack_size(x as usize);
-                            }
-                        }
-                    }
-                    *(*to).as_i32_mut() = x;
-                }
-                GlobalInit::I64Const(x) => *(*to).as_i64_mut() = x,
-                GlobalInit::F32Const(x) => *(*to).as_f32_bits_mut() = x,
-                GlobalInit::F64Const(x) => *(*to).as_f64_bits_mut() = x,
-                GlobalInit::V128Const(x) => *(*to).as_u128_mut() = x,
-                GlobalInit::GetGlobal(x) => {
-                    let from = if let Some(def_x) = module.defined_global_index(x) {
+            match init.ops() {
+                [ConstOp::F32Const(x)] => *(*to).as_f32_bits_mut() = *x,
+                [ConstOp::F64Const(x)] => *(*to).as_f64_bits_mut() = *x,
+                [ConstOp::V128Const(x)] => *(*to).as_u128_mut() = *x,
+                [ConstOp::GlobalGet(x)] => {
+                    let from = if let Some(def_x) = module.defined_global_index(*x) {
                         self.global(def_x)
                     } else {
-                        &*self.imported_global(x).from
+                        &*self.imported_global(*x).from
                     };
                     // Globals of type `externref` need to manage the reference
                     // count as values move between globals, everything else is just
@@ -1186,14 +1220,43 @@ impl Instance {
                         _ => ptr::copy_nonoverlapping(from, to, 1),
                     }
                 }
-                GlobalInit::RefFunc(f) => {
-                    *(*to).as_func_ref_mut() = self.get_func_ref(f).unwrap();
+                [ConstOp::RefFunc(f)] => {
+                    *(*to).as_func_ref_mut() = self.get_func_ref(*f).unwrap();
                 }
-                GlobalInit::RefNullConst => match wasm_ty {
+                [ConstOp::RefNull] => match wasm_ty {
                     // `VMGlobalDefinition::new()` already zeroed out the bits
                     WasmType::Ref(WasmRefType { nullable: true, .. }) => {}
                     ty => panic!("unsupported reference type for global: {:?}", ty),
                 },
+                // Everything else is an integer-valued expression, possibly
+                // using the arithmetic from the extended-const proposal.
+                _ => {
+                    let x = init
+                        .eval_int(|global| {
+                            let def = self.defined_or_imported_global_ptr(global);
+                            Some(match module.globals[global].wasm_ty {
+                                WasmType::I64 => *(*def).as_u64(),
+                                _ => u64::from(*(*def).as_u32()),
+                            })
+                        })
+                        .expect("global initializer is not a constant integer");
+                    match wasm_ty {
+                        WasmType::I32 => {
+                            let index = module.global_index(index);
+                            if index.index() == 0 {
+                                #[cfg(feature = "wmemcheck")]
+                                {
+                                    if let Some(wmemcheck) = &mut self.wmemcheck_state {
+                                        wmemcheck.set_stack_size(x as usize);
+                                    }
+                                }
+                            }
+                            *(*to).as_u32_mut() = x as u32;
+                        }
+                        WasmType::I64 => *(*to).as_u64_mut() = x,
+                        ty => panic!("unsupported integer type for global: {:?}", ty),
+                    }
+                }
             }
         }
     }
@@ -1283,6 +1346,11 @@ impl InstanceHandle {
         self.instance_mut().get_exported_table(export)
     }
 
+    /// Lookup a tag by index.
+    pub fn get_exported_tag(&mut self, export: TagIndex) -> ExportTag {
+        self.instance_mut().get_exported_tag(export)
+    }
+
     /// Lookup an item with the given index.
     pub fn get_export_by_index(&mut self, export: EntityIndex) -> Export {
         match export {
@@ -1290,6 +1358,7 @@ impl InstanceHandle {
             EntityIndex::Global(i) => Export::Global(self.get_exported_global(i)),
             EntityIndex::Table(i) => Export::Table(self.get_exported_table(i)),
             EntityIndex::Memory(i) => Export::Memory(self.get_exported_memory(i)),
+            EntityIndex::Tag(i) => Export::Tag(self.get_exported_tag(i)),
         }
     }
 
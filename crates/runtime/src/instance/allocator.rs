@@ -6,7 +6,7 @@ use crate::{CompiledModuleId, ModuleRuntimeInfo, Store};
 use anyhow::{anyhow, bail, Result};
 use std::{alloc, any::Any, mem, ptr, sync::Arc};
 use wasmtime_environ::{
-    DefinedMemoryIndex, DefinedTableIndex, HostPtr, InitMemory, MemoryInitialization,
+    DefinedMemoryIndex, DefinedTableIndex, GlobalIndex, HostPtr, InitMemory, MemoryInitialization,
     MemoryInitializer, MemoryPlan, Module, PrimaryMap, TableInitialValue, TablePlan, TableSegment,
     Trap, VMOffsets, WasmType, WASM_PAGE_SIZE,
 };
@@ -463,19 +463,27 @@ pub trait InstanceAllocator: InstanceAllocatorImpl {
 // must use the defaults. This blanket impl provides both of those things.
 impl<T: InstanceAllocatorImpl> InstanceAllocator for T {}
 
-fn get_table_init_start(init: &TableSegment, instance: &mut Instance) -> Result<u32> {
-    match init.base {
-        Some(base) => {
-            let val = unsafe { *(*instance.defined_or_imported_global_ptr(base)).as_u32() };
-
-            init.offset
-                .checked_add(val)
-                .ok_or_else(|| anyhow!("element segment global base overflows"))
+/// Loads the `global` value and returns it as a `u64`, zero-extending 32-bit
+/// globals, for use when evaluating constant expressions.
+fn get_global_as_u64(instance: &mut Instance, global: GlobalIndex) -> u64 {
+    let is_i64 = instance.module().globals[global].wasm_ty == WasmType::I64;
+    unsafe {
+        let def = instance.defined_or_imported_global_ptr(global);
+        if is_i64 {
+            *(*def).as_u64()
+        } else {
+            u64::from(*(*def).as_u32())
         }
-        None => Ok(init.offset),
     }
 }
 
+fn get_table_init_start(init: &TableSegment, instance: &mut Instance) -> Result<u32> {
+    init.offset
+        .eval_int(|global| Some(get_global_as_u64(instance, global)))
+        .map(|offset| offset as u32)
+        .ok_or_else(|| anyhow!("element segment offset is not a constant integer"))
+}
+
 fn check_table_init_bounds(instance: &mut Instance, module: &Module) -> Result<()> {
     for segment in module.table_initialization.segments.iter() {
         let table = unsafe { &*instance.get_table(segment.table_index) };
@@ -532,26 +540,9 @@ fn initialize_tables(instance: &mut Instance, module: &Module) -> Result<()> {
 }
 
 fn get_memory_init_start(init: &MemoryInitializer, instance: &mut Instance) -> Result<u64> {
-    match init.base {
-        Some(base) => {
-            let mem64 = instance.module().memory_plans[init.memory_index]
-                .memory
-                .memory64;
-            let val = unsafe {
-                let global = instance.defined_or_imported_global_ptr(base);
-                if mem64 {
-                    *(*global).as_u64()
-                } else {
-                    u64::from(*(*global).as_u32())
-                }
-            };
-
-            init.offset
-                .checked_add(val)
-                .ok_or_else(|| anyhow!("data segment global base overflows"))
-        }
-        None => Ok(init.offset),
-    }
+    init.offset
+        .eval_int(|global| Some(get_global_as_u64(instance, global)))
+        .ok_or_else(|| anyhow!("data segment offset is not a constant integer"))
 }
 
 fn check_memory_init_bounds(
@@ -583,17 +574,6 @@ fn initialize_memories(instance: &mut Instance, module: &Module) -> Result<()> {
         (instance.get_memory(memory).current_length() as u64) / u64::from(WASM_PAGE_SIZE)
     };
 
-    // Loads the `global` value and returns it as a `u64`, but sign-extends
-    // 32-bit globals which can be used as the base for 32-bit memories.
-    let get_global_as_u64 = &mut |instance: &mut Instance, global| unsafe {
-        let def = instance.defined_or_imported_global_ptr(global);
-        if module.globals[global].wasm_ty == WasmType::I64 {
-            *(*def).as_u64()
-        } else {
-            u64::from(*(*def).as_u32())
-        }
-    };
-
     // Delegates to the `init_memory` method which is sort of a duplicate of
     // `instance.memory_init_segment` but is used at compile-time in other
     // contexts so is shared here to have only one method of memory
@@ -606,7 +586,7 @@ fn initialize_memories(instance: &mut Instance, module: &Module) -> Result<()> {
         instance,
         InitMemory::Runtime {
             memory_size_in_pages,
-            get_global_as_u64,
+            get_global_as_u64: &get_global_as_u64,
         },
         |instance, memory_index, init| {
             // If this initializer applies to a defined memory but that memory
@@ -25,13 +25,16 @@ use std::fmt;
 use std::ptr::NonNull;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use wasmtime_environ::{DefinedFuncIndex, DefinedMemoryIndex, HostPtr, VMOffsets};
+use wasmtime_environ::{
+    DefinedFuncIndex, DefinedMemoryIndex, HostPtr, SignatureIndex, VMOffsets, WasmFuncType,
+};
 
 #[macro_use]
 mod trampolines;
 
 #[cfg(feature = "component-model")]
 pub mod component;
+mod exception;
 mod export;
 mod externref;
 mod imports;
@@ -51,6 +54,7 @@ pub mod libcalls;
 
 pub use wasmtime_jit_debug::gdb_jit_int::GdbJitImageRegistration;
 
+pub use crate::exception::{ExceptionState, VMException, VMTag};
 pub use crate::export::*;
 pub use crate::externref::*;
 pub use crate::imports::Imports;
@@ -157,6 +161,10 @@ pub unsafe trait Store {
     /// completely semantically transparent. Returns the new deadline.
     fn new_epoch(&mut self) -> Result<u64, Error>;
 
+    /// Returns the state of the Wasm exceptions thrown and caught within this
+    /// store.
+    fn exceptions(&mut self) -> &mut ExceptionState;
+
     /// Metadata required for resources for the component model.
     #[cfg(feature = "component-model")]
     fn component_calls(&mut self) -> &mut component::CallContexts;
@@ -219,6 +227,9 @@ pub trait ModuleRuntimeInfo: Send + Sync + 'static {
     /// A slice pointing to all data that is referenced by this instance.
     fn wasm_data(&self) -> &[u8];
 
+    /// Returns the type of the signature `index` within this module.
+    fn signature(&self, index: SignatureIndex) -> &WasmFuncType;
+
     /// Returns an array, indexed by `SignatureIndex` of all
     /// `VMSharedSignatureIndex` entries corresponding to the `SignatureIndex`.
     fn signature_ids(&self) -> &[VMSharedSignatureIndex];
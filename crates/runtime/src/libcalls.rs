@@ -57,7 +57,7 @@
 use crate::externref::VMExternRef;
 use crate::table::{Table, TableElementType};
 use crate::vmcontext::VMFuncRef;
-use crate::{Instance, TrapReason};
+use crate::{Instance, TrapReason, ValRaw};
 #[cfg(feature = "wmemcheck")]
 use anyhow::bail;
 use anyhow::Result;
@@ -66,7 +66,7 @@ use std::mem;
 use std::ptr::{self, NonNull};
 use std::time::{Duration, Instant};
 use wasmtime_environ::{
-    DataIndex, ElemIndex, FuncIndex, GlobalIndex, MemoryIndex, TableIndex, Trap,
+    DataIndex, ElemIndex, FuncIndex, GlobalIndex, MemoryIndex, TableIndex, TagIndex, Trap,
 };
 #[cfg(feature = "wmemcheck")]
 use wasmtime_wmemcheck::AccessError::{
@@ -189,6 +189,13 @@ pub mod trampolines {
             self
         }
     }
+
+    impl LibcallResult for u32 {
+        type Abi = u32;
+        unsafe fn convert(self) -> u32 {
+            self
+        }
+    }
 }
 
 fn memory32_grow(
@@ -636,6 +643,60 @@ cfg_if! {
     }
 }
 
+// Implementation of wasm's `throw` instruction.
+unsafe fn throw(instance: &mut Instance, tag: u32, values: *mut u8) {
+    let exception = instance.new_exception(TagIndex::from_u32(tag), values.cast::<ValRaw>());
+    let store = &mut *instance.store();
+    let limits = store.vmruntime_limits();
+    store.exceptions().throw(limits, exception);
+}
+
+// Catches the pending exception at the start of a `try` block's handler.
+unsafe fn exception_catch(instance: &mut Instance) -> u32 {
+    let store = &mut *instance.store();
+    let limits = store.vmruntime_limits();
+    store.exceptions().catch(limits)
+}
+
+// Tests whether a caught exception matches the tag of a `catch` clause.
+unsafe fn exception_matches(instance: &mut Instance, handle: u32, tag: u32) -> u32 {
+    let store = &mut *instance.store();
+    let exception = store.exceptions().caught(handle);
+    u32::from(instance.exception_matches(exception, TagIndex::from_u32(tag)))
+}
+
+// Copies the payload of a caught exception into the frame of its `catch`
+// clause.
+unsafe fn exception_payload(instance: &mut Instance, handle: u32, values: *mut u8) {
+    let store = &mut *instance.store();
+    let exception = store.exceptions().caught(handle).clone();
+    let payload = exception.payload();
+    ptr::copy_nonoverlapping(payload.as_ptr(), values.cast::<ValRaw>(), payload.len());
+
+    // The payload's references are about to become live in a wasm frame, so
+    // they must be rooted in the activations table just like any other
+    // reference handed to wasm. No GC may happen here though, since the
+    // references copied into `values` aren't yet visible in any stack map.
+    let (activations_table, _) = store.externref_activations_table();
+    for externref in exception.refs() {
+        activations_table.insert_without_gc(externref.clone());
+    }
+}
+
+// Implementation of wasm's `rethrow` instruction.
+unsafe fn rethrow(instance: &mut Instance, handle: u32) {
+    let store = &mut *instance.store();
+    let limits = store.vmruntime_limits();
+    let exceptions = store.exceptions();
+    let exception = exceptions.caught(handle).clone();
+    exceptions.throw(limits, exception);
+}
+
+// Releases a caught exception when leaving its `catch` clause.
+unsafe fn exception_drop(instance: &mut Instance, handle: u32) {
+    (*instance.store()).exceptions().drop_caught(handle);
+}
+
 /// This module contains functions which are used for resolving relocations at
 /// runtime if necessary.
 ///
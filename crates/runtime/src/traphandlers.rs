@@ -4,7 +4,7 @@
 mod backtrace;
 mod coredump;
 
-use crate::{Instance, VMContext, VMRuntimeLimits};
+use crate::{Instance, ModuleInfoLookup, VMContext, VMRuntimeLimits};
 use anyhow::Error;
 use std::any::Any;
 use std::cell::{Cell, UnsafeCell};
@@ -288,11 +288,16 @@ impl From<wasmtime_environ::Trap> for TrapReason {
 /// Catches any wasm traps that happen within the execution of `closure`,
 /// returning them as a `Result`.
 ///
+/// When `module_info_lookup` is provided it's used to read the `VMContext` and
+/// the values of wasm locals from the frames of captured backtraces, and it
+/// must remain valid for the duration of this call.
+///
 /// Highly unsafe since `closure` won't have any dtors run.
 pub unsafe fn catch_traps<'a, F>(
     signal_handler: Option<*const SignalHandler<'static>>,
     capture_backtrace: bool,
     capture_coredump: bool,
+    module_info_lookup: Option<*const dyn ModuleInfoLookup>,
     caller: *mut VMContext,
     mut closure: F,
 ) -> Result<(), Box<Trap>>
@@ -301,15 +306,21 @@ where
 {
     let limits = Instance::from_vmctx(caller, |i| i.runtime_limits());
 
-    let result = CallThreadState::new(signal_handler, capture_backtrace, capture_coredump, *limits)
-        .with(|cx| {
-            wasmtime_setjmp(
-                cx.jmp_buf.as_ptr(),
-                call_closure::<F>,
-                &mut closure as *mut F as *mut u8,
-                caller,
-            )
-        });
+    let result = CallThreadState::new(
+        signal_handler,
+        capture_backtrace,
+        capture_coredump,
+        module_info_lookup,
+        *limits,
+    )
+    .with(|cx| {
+        wasmtime_setjmp(
+            cx.jmp_buf.as_ptr(),
+            call_closure::<F>,
+            &mut closure as *mut F as *mut u8,
+            caller,
+        )
+    });
 
     return match result {
         Ok(x) => Ok(x),
@@ -343,6 +354,7 @@ mod call_thread_state {
         pub(super) signal_handler: Option<*const SignalHandler<'static>>,
         pub(super) capture_backtrace: bool,
         pub(super) capture_coredump: bool,
+        pub(super) module_info_lookup: Option<*const dyn ModuleInfoLookup>,
 
         pub(crate) limits: *const VMRuntimeLimits,
 
@@ -377,6 +389,7 @@ mod call_thread_state {
             signal_handler: Option<*const SignalHandler<'static>>,
             capture_backtrace: bool,
             capture_coredump: bool,
+            module_info_lookup: Option<*const dyn ModuleInfoLookup>,
             limits: *const VMRuntimeLimits,
         ) -> CallThreadState {
             CallThreadState {
@@ -385,6 +398,7 @@ mod call_thread_state {
                 signal_handler,
                 capture_backtrace,
                 capture_coredump,
+                module_info_lookup,
                 limits,
                 prev: Cell::new(ptr::null()),
                 old_last_wasm_exit_fp: Cell::new(unsafe { *(*limits).last_wasm_exit_fp.get() }),
@@ -22,10 +22,12 @@
 
 use crate::{
     traphandlers::{tls, CallThreadState},
-    VMRuntimeLimits,
+    VMContext, VMRuntimeLimits,
 };
 use cfg_if::cfg_if;
 use std::ops::ControlFlow;
+use wasm_encoder::CoreDumpValue;
+use wasmtime_environ::{WasmFunctionInfo, WasmLocal, WasmLocalsInfo, WasmType};
 
 // Architecture-specific bits for stack walking. Each of these modules should
 // define and export the following functions:
@@ -62,6 +64,9 @@ pub struct Backtrace(Vec<Frame>);
 pub struct Frame {
     pc: usize,
     fp: usize,
+    vmctx: usize,
+    locals: Vec<CoreDumpValue>,
+    operand_stack: Vec<CoreDumpValue>,
 }
 
 impl Frame {
@@ -74,6 +79,91 @@ impl Frame {
     pub fn fp(&self) -> usize {
         self.fp
     }
+
+    /// Get the `VMContext` of the instance this frame belongs to.
+    ///
+    /// This is only available when the frame's function saves it to its frame
+    /// and the backtrace was captured with a module lookup available.
+    pub fn vmctx(&self) -> Option<*mut VMContext> {
+        if self.vmctx == 0 {
+            None
+        } else {
+            Some(self.vmctx as *mut VMContext)
+        }
+    }
+
+    /// Get the values of this frame's wasm locals.
+    ///
+    /// This is empty unless the frame's function saves its locals to its
+    /// frame and the backtrace was captured with a module lookup available.
+    pub fn locals(&self) -> &[CoreDumpValue] {
+        &self.locals
+    }
+
+    /// Get the values on this frame's operand stack, from its bottom to its
+    /// top, before the instruction the frame is at pops its operands.
+    ///
+    /// This is empty unless the frame's function saves its locals to its
+    /// frame, the frame is at a call or trapping instruction, and the
+    /// backtrace was captured with a module lookup available.
+    pub fn operand_stack(&self) -> &[CoreDumpValue] {
+        &self.operand_stack
+    }
+
+    /// Read this frame's `VMContext` and the values of its wasm locals and
+    /// operand stack from wherever its function saved them, as described by
+    /// `info`, given the offset in the wasm module of the instruction the
+    /// frame is at.
+    unsafe fn read_saved(&mut self, info: &WasmFunctionInfo, file_offset: Option<u32>) {
+        if let Some(offset) = info.vmctx_frame_offset {
+            let ptr = (self.fp - offset as usize) as *const usize;
+            self.vmctx = ptr.read_unaligned();
+        }
+        if let Some(locals) = &info.locals {
+            self.read_locals(locals, file_offset);
+        }
+    }
+
+    /// Read the values of this frame's wasm locals, and of its operand stack
+    /// at the instruction at `file_offset`, from where its function saved
+    /// them, as described by `info`.
+    unsafe fn read_locals(&mut self, info: &WasmLocalsInfo, file_offset: Option<u32>) {
+        self.locals = read_values(self.fp - info.frame_offset as usize, &info.locals);
+
+        let stack = file_offset.and_then(|file_offset| {
+            let index = info
+                .operand_stacks
+                .binary_search_by_key(&file_offset, |stack| stack.file_offset)
+                .ok()?;
+            Some(&info.operand_stacks[index])
+        });
+        if let Some(stack) = stack {
+            self.operand_stack = read_values(
+                self.fp - info.operand_stack_frame_offset as usize,
+                &stack.values,
+            );
+        }
+    }
+}
+
+/// Read the saved `values` which are located relative to `base`.
+unsafe fn read_values(base: usize, values: &[WasmLocal]) -> Vec<CoreDumpValue> {
+    values
+        .iter()
+        .map(|value| {
+            let ptr = match value.offset {
+                Some(offset) => (base + offset as usize) as *const u8,
+                None => return CoreDumpValue::Missing,
+            };
+            match value.ty {
+                WasmType::I32 => CoreDumpValue::I32(ptr.cast::<i32>().read_unaligned()),
+                WasmType::I64 => CoreDumpValue::I64(ptr.cast::<i64>().read_unaligned()),
+                WasmType::F32 => CoreDumpValue::F32(ptr.cast::<f32>().read_unaligned()),
+                WasmType::F64 => CoreDumpValue::F64(ptr.cast::<f64>().read_unaligned()),
+                WasmType::V128 | WasmType::Ref(_) => CoreDumpValue::Missing,
+            }
+        })
+        .collect()
 }
 
 impl Backtrace {
@@ -95,13 +185,33 @@ impl Backtrace {
     /// If Wasm hit a trap, and we calling this from the trap handler, then the
     /// Wasm exit trampoline didn't run, and we use the provided PC and FP
     /// instead of looking them up in `VMRuntimeLimits`.
+    ///
+    /// The `VMContext` and values of wasm locals are read from every frame
+    /// whose function saves them, as found through the `state`'s module
+    /// lookup, if any. This has to happen now since the frames are gone once
+    /// the trap unwinds.
     pub(crate) unsafe fn new_with_trap_state(
         limits: *const VMRuntimeLimits,
         state: &CallThreadState,
         trap_pc_and_fp: Option<(usize, usize)>,
     ) -> Backtrace {
+        let module_info_lookup = state.module_info_lookup.map(|lookup| &*lookup);
+        let trap_pc = trap_pc_and_fp.map(|(pc, _)| pc);
         let mut frames = vec![];
-        Self::trace_with_trap_state(limits, state, trap_pc_and_fp, |frame| {
+        Self::trace_with_trap_state(limits, state, trap_pc_and_fp, |mut frame| {
+            if let Some(module) = module_info_lookup.and_then(|lookup| lookup.lookup(frame.pc)) {
+                if let Some(info) = module.lookup_wasm_func_info(frame.pc) {
+                    // The frame that trapped is at the trapping instruction,
+                    // while every other frame's pc is a return address, just
+                    // after the call instruction that it's at.
+                    let instr_pc = if Some(frame.pc) == trap_pc {
+                        frame.pc
+                    } else {
+                        frame.pc - 1
+                    };
+                    frame.read_saved(info, module.lookup_wasm_file_offset(instr_pc));
+                }
+            }
             frames.push(frame);
             ControlFlow::Continue(())
         });
@@ -219,7 +329,13 @@ impl Backtrace {
             log::trace!("pc = {:p}", pc as *const ());
             log::trace!("fp = {:p}", fp as *const ());
 
-            f(Frame { pc, fp })?;
+            f(Frame {
+                pc,
+                fp,
+                vmctx: 0,
+                locals: Vec::new(),
+                operand_stack: Vec::new(),
+            })?;
 
             pc = arch::get_next_older_pc_from_fp(fp);
 
@@ -10,7 +10,9 @@ pub struct CoreDumpStack {
     /// The backtrace containing the stack frames for the CoreDump
     pub bt: Backtrace,
 
-    /// Unimplemented
+    /// The locals for each stack frame, which are only recovered for
+    /// functions that save their locals to their frame.
+    ///
     /// The indices of the locals and operand_stack all map to each other (ie.
     /// index 0 is the locals for the first frame in the backtrace, etc)
     pub locals: Vec<Vec<CoreDumpValue>>,
@@ -28,10 +30,11 @@ impl CoreDumpStack {
         trap_pc_and_fp: Option<(usize, usize)>,
     ) -> Self {
         let bt = unsafe { Backtrace::new_with_trap_state(limits, cts, trap_pc_and_fp) };
+        let locals = bt.frames().map(|frame| frame.locals().to_vec()).collect();
 
         Self {
             bt,
-            locals: vec![],
+            locals,
             operand_stack: vec![],
         }
     }
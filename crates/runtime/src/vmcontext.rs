@@ -848,6 +848,15 @@ pub struct VMRuntimeLimits {
     /// Used to find the end of a contiguous sequence of Wasm frames when
     /// walking the stack.
     pub last_wasm_entry_sp: UnsafeCell<usize>,
+
+    /// Non-zero while a Wasm exception has been thrown and is propagating
+    /// up the stack without having been caught yet.
+    ///
+    /// When exceptions are enabled compiled code checks this after every call
+    /// and, if set, branches to the innermost enclosing `try` handler or
+    /// returns to its own caller. The exception itself lives in the store,
+    /// see `crate::exception`.
+    pub exception_pending: UnsafeCell<u32>,
 }
 
 // The `VMRuntimeLimits` type is a pod-type with no destructor, and we don't
@@ -866,6 +875,7 @@ impl Default for VMRuntimeLimits {
             last_wasm_exit_fp: UnsafeCell::new(0),
             last_wasm_exit_pc: UnsafeCell::new(0),
             last_wasm_entry_sp: UnsafeCell::new(0),
+            exception_pending: UnsafeCell::new(0),
         }
     }
 }
@@ -915,6 +925,10 @@ mod test_vmruntime_limits {
             offset_of!(VMRuntimeLimits, last_wasm_entry_sp),
             usize::from(offsets.ptr.vmruntime_limits_last_wasm_entry_sp())
         );
+        assert_eq!(
+            offset_of!(VMRuntimeLimits, exception_pending),
+            usize::from(offsets.ptr.vmruntime_limits_exception_pending())
+        );
     }
 }
 
@@ -0,0 +1,67 @@
+use command_tests::wasi::filesystem::preopens;
+use command_tests::wasi::filesystem::types::{
+    self as fs, Descriptor, DescriptorFlags, ErrorCode, OpenFlags, PathFlags,
+};
+
+fn open(dir: Descriptor, path: &str) -> Descriptor {
+    fs::open_at(
+        dir,
+        PathFlags::empty(),
+        path,
+        OpenFlags::empty(),
+        DescriptorFlags::READ | DescriptorFlags::WRITE,
+        fs::Modes::READABLE | fs::Modes::WRITABLE,
+    )
+    .unwrap()
+}
+
+fn main() {
+    let (dir, _) = preopens::get_directories()
+        .into_iter()
+        .find(|(_, path)| path == "/")
+        .expect("a preopen of `/`");
+
+    // Locks are held by a descriptor, so opening the file twice gives two
+    // contenders for the same lock.
+    let a = open(dir, "bar.txt");
+    let b = open(dir, "bar.txt");
+
+    // Any number of shared locks may be held at once, but not alongside an
+    // exclusive one.
+    fs::lock_shared(a).unwrap();
+    fs::try_lock_shared(b).unwrap();
+    assert_eq!(fs::try_lock_exclusive(b), Err(ErrorCode::WouldBlock));
+    fs::unlock(b).unwrap();
+    fs::unlock(a).unwrap();
+
+    // An exclusive lock excludes every other lock.
+    fs::lock_exclusive(a).unwrap();
+    assert_eq!(fs::try_lock_shared(b), Err(ErrorCode::WouldBlock));
+    assert_eq!(fs::try_lock_exclusive(b), Err(ErrorCode::WouldBlock));
+
+    // Once it's released, the lock is free for the taking.
+    fs::unlock(a).unwrap();
+    fs::try_lock_exclusive(b).unwrap();
+    assert_eq!(fs::try_lock_shared(a), Err(ErrorCode::WouldBlock));
+    fs::unlock(b).unwrap();
+
+    // Dropping a descriptor releases its lock.
+    fs::lock_exclusive(b).unwrap();
+    fs::drop_descriptor(b);
+    fs::try_lock_exclusive(a).unwrap();
+    fs::unlock(a).unwrap();
+    fs::drop_descriptor(a);
+
+    // Directories can't be locked.
+    let sub = fs::open_at(
+        dir,
+        PathFlags::empty(),
+        "sub",
+        OpenFlags::DIRECTORY,
+        DescriptorFlags::READ,
+        fs::Modes::READABLE,
+    )
+    .unwrap();
+    assert_eq!(fs::try_lock_shared(sub), Err(ErrorCode::IsDirectory));
+    fs::drop_descriptor(sub);
+}
@@ -0,0 +1,8 @@
+use command_tests::wasi::logging::logging::{log, Level};
+
+fn main() {
+    log(Level::Trace, "main", "entering main");
+    log(Level::Info, "main", "hello from the guest");
+    log(Level::Warn, "config", "no config file found");
+    log(Level::Critical, "main", "out of coffee");
+}
@@ -0,0 +1,53 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+fn list(dir: &str) -> Result<HashSet<PathBuf>, Box<dyn Error>> {
+    Ok(fs::read_dir(dir)?
+        .map(|r| r.map(|d| d.path()))
+        .collect::<Result<_, _>>()?)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    assert_eq!(
+        "And stood awhile in thought",
+        fs::read_to_string("/bar.txt")?
+    );
+    assert_eq!(27, fs::metadata("/bar.txt")?.len());
+    assert!(fs::metadata("/sub")?.is_dir());
+    assert_eq!(
+        ["/bar.txt", "/sub"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect::<HashSet<_>>(),
+        list("/")?
+    );
+
+    OpenOptions::new()
+        .append(true)
+        .open("/bar.txt")?
+        .write_all(b".\n")?;
+
+    fs::write("/sub/new.txt", "Beware the Jabberwock, my son!")?;
+    assert_eq!(
+        [PathBuf::from("/sub/new.txt")]
+            .into_iter()
+            .collect::<HashSet<_>>(),
+        list("/sub")?
+    );
+    fs::rename("/sub/new.txt", "/renamed.txt")?;
+    assert!(list("/sub")?.is_empty());
+
+    fs::create_dir("/made")?;
+    assert!(fs::create_dir("/made").is_err());
+    fs::remove_dir("/made")?;
+    assert!(fs::metadata("/made").is_err());
+
+    assert!(fs::read("/missing.txt").is_err());
+
+    Ok(())
+}
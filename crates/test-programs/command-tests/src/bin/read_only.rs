@@ -1,3 +1,5 @@
+use command_tests::wasi::filesystem::preopens;
+use command_tests::wasi::filesystem::types::{self as filesystem, ErrorCode, Modes, PathFlags};
 use std::{
     error::Error,
     fs::{self, File, OpenOptions},
@@ -31,5 +33,24 @@ fn main() -> Result<(), Box<dyn Error>> {
     assert!(fs::remove_file("bar.txt").is_err());
     assert!(fs::remove_dir("sub").is_err());
 
+    // Changing permissions needs `DirPerms::MUTATE`, which the preopen lacks.
+    let (dir, _) = preopens::get_directories()
+        .into_iter()
+        .find(|(_, path)| path == "/")
+        .expect("a preopen of `/`");
+    assert_eq!(
+        filesystem::change_file_permissions_at(dir, PathFlags::empty(), "bar.txt", Modes::READABLE),
+        Err(ErrorCode::NotPermitted)
+    );
+    assert_eq!(
+        filesystem::change_directory_permissions_at(
+            dir,
+            PathFlags::empty(),
+            "sub",
+            Modes::READABLE
+        ),
+        Err(ErrorCode::NotPermitted)
+    );
+
     Ok(())
 }
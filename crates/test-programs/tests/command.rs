@@ -8,8 +8,8 @@ use wasmtime::{
 use wasmtime_wasi::preview2::{
     command::{add_to_linker, Command},
     pipe::MemoryInputPipe,
-    DirPerms, FilePerms, HostMonotonicClock, HostWallClock, IsATTY, Table, WasiCtx, WasiCtxBuilder,
-    WasiView,
+    DirPerms, FilePerms, HostMonotonicClock, HostWallClock, IsATTY, MemoryDir, Table, WasiCtx,
+    WasiCtxBuilder, WasiView,
 };
 
 lazy_static::lazy_static! {
@@ -405,6 +405,41 @@ async fn directory_list() -> Result<()> {
         .map_err(|()| anyhow::anyhow!("command returned with failing exit status"))
 }
 
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+async fn memory_dir() -> Result<()> {
+    let dir = MemoryDir::new();
+    dir.insert_file("bar.txt", "And stood awhile in thought")?;
+    dir.create_dir_all("sub")?;
+
+    let mut table = Table::new();
+    let wasi = WasiCtxBuilder::new()
+        .inherit_stdout()
+        .inherit_stderr()
+        .preopened_host_dir(dir.clone(), DirPerms::all(), FilePerms::all(), "/")
+        .build(&mut table)?;
+
+    let (mut store, command) =
+        instantiate(get_component("memory_dir"), CommandCtx { table, wasi }).await?;
+
+    command
+        .wasi_cli_run()
+        .call_run(&mut store)
+        .await?
+        .map_err(|()| anyhow::anyhow!("command returned with failing exit status"))?;
+
+    // The guest's changes are visible to the host.
+    assert_eq!(
+        dir.read_file("bar.txt").as_deref(),
+        Some(&b"And stood awhile in thought.\n"[..])
+    );
+    assert_eq!(
+        dir.read_file("renamed.txt").as_deref(),
+        Some(&b"Beware the Jabberwock, my son!"[..])
+    );
+    assert_eq!(dir.read_file("sub/new.txt"), None);
+    Ok(())
+}
+
 #[test_log::test(tokio::test(flavor = "multi_thread"))]
 async fn default_clocks() -> Result<()> {
     let mut table = Table::new();
@@ -460,6 +495,31 @@ async fn read_only() -> Result<()> {
         .map_err(|()| anyhow::anyhow!("command returned with failing exit status"))
 }
 
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+// Locking is only implemented on Unix.
+#[cfg_attr(not(unix), ignore)]
+async fn file_locks() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    std::fs::File::create(dir.path().join("bar.txt"))?.write_all(b"And stood awhile in thought")?;
+    std::fs::create_dir(dir.path().join("sub"))?;
+
+    let mut table = Table::new();
+    let open_dir = Dir::open_ambient_dir(dir.path(), ambient_authority())?;
+    let wasi = WasiCtxBuilder::new()
+        .preopened_dir(open_dir, DirPerms::all(), FilePerms::all(), "/")
+        .build(&mut table)?;
+
+    let (mut store, command) =
+        instantiate(get_component("file_locks"), CommandCtx { table, wasi }).await?;
+
+    command
+        .wasi_cli_run()
+        .call_run(&mut store)
+        .await?
+        .map_err(|()| anyhow::anyhow!("command returned with failing exit status"))
+}
+
 #[test_log::test(tokio::test(flavor = "multi_thread"))]
 async fn stream_pollable_lifetimes() -> Result<()> {
     // Test program has two modes, dispatching based on argument.
@@ -508,3 +568,60 @@ async fn stream_pollable_lifetimes() -> Result<()> {
     }
     Ok(())
 }
+
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+async fn logging() -> Result<()> {
+    use std::sync::Arc;
+    use wasmtime_wasi::preview2::{bindings::logging::logging::Level, HostLogger};
+
+    #[derive(Clone, Default)]
+    struct RecordingLogger(Arc<Mutex<Vec<(Level, String, String)>>>);
+
+    impl HostLogger for RecordingLogger {
+        fn log(&self, level: Level, context: &str, message: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((level, context.to_string(), message.to_string()));
+        }
+    }
+
+    let logger = RecordingLogger::default();
+    let mut table = Table::new();
+    let wasi = WasiCtxBuilder::new()
+        .logger(logger.clone())
+        .min_log_level(Level::Info)
+        .build(&mut table)?;
+
+    let (mut store, command) =
+        instantiate(get_component("logging"), CommandCtx { table, wasi }).await?;
+
+    command
+        .wasi_cli_run()
+        .call_run(&mut store)
+        .await?
+        .map_err(|()| anyhow::anyhow!("command returned with failing exit status"))?;
+
+    let logged = logger.0.lock().unwrap();
+    assert_eq!(
+        *logged,
+        [
+            (
+                Level::Info,
+                "main".to_string(),
+                "hello from the guest".to_string()
+            ),
+            (
+                Level::Warn,
+                "config".to_string(),
+                "no config file found".to_string()
+            ),
+            (
+                Level::Critical,
+                "main".to_string(),
+                "out of coffee".to_string()
+            ),
+        ]
+    );
+    Ok(())
+}
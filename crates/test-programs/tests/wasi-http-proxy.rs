@@ -0,0 +1,106 @@
+#![cfg(all(feature = "test_programs", not(skip_wasi_http_tests)))]
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use wasmtime::{
+    component::{Component, Linker},
+    Config, Engine, Store,
+};
+use wasmtime_wasi::preview2::{command::add_to_linker, Table, WasiCtx, WasiCtxBuilder, WasiView};
+use wasmtime_wasi_http::{Proxy, WasiHttpCtx, WasiHttpView};
+
+lazy_static::lazy_static! {
+    static ref ENGINE: Engine = {
+        let mut config = Config::new();
+        config.wasm_backtrace_details(wasmtime::WasmBacktraceDetails::Enable);
+        config.wasm_component_model(true);
+        config.async_support(true);
+        let engine = Engine::new(&config).unwrap();
+        engine
+    };
+}
+// uses ENGINE, creates a fn get_component(&str) -> Component
+include!(concat!(
+    env!("OUT_DIR"),
+    "/wasi_http_proxy_tests_components.rs"
+));
+
+struct Ctx {
+    table: Table,
+    wasi: WasiCtx,
+    http: WasiHttpCtx,
+}
+
+impl WasiView for Ctx {
+    fn table(&self) -> &Table {
+        &self.table
+    }
+    fn table_mut(&mut self) -> &mut Table {
+        &mut self.table
+    }
+    fn ctx(&self) -> &WasiCtx {
+        &self.wasi
+    }
+    fn ctx_mut(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+impl WasiHttpView for Ctx {
+    fn http_ctx(&self) -> &WasiHttpCtx {
+        &self.http
+    }
+    fn http_ctx_mut(&mut self) -> &mut WasiHttpCtx {
+        &mut self.http
+    }
+}
+
+async fn instantiate_proxy(component: Component) -> anyhow::Result<(Store<Ctx>, Proxy)> {
+    let mut linker = Linker::new(&ENGINE);
+    add_to_linker(&mut linker)?;
+    wasmtime_wasi_http::add_to_component_linker(&mut linker)?;
+
+    let mut table = Table::new();
+    let wasi = WasiCtxBuilder::new().inherit_stdio().build(&mut table)?;
+    let http = WasiHttpCtx::new();
+    let mut store = Store::new(&ENGINE, Ctx { table, wasi, http });
+
+    let (proxy, _instance) = Proxy::instantiate_async(&mut store, &component, &linker).await?;
+    Ok((store, proxy))
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+async fn wasi_http_proxy_tests() -> anyhow::Result<()> {
+    let (mut store, proxy) = instantiate_proxy(get_component("wasi_http_proxy_tests")).await?;
+
+    let request = hyper::Request::builder()
+        .method(hyper::Method::POST)
+        .uri("http://localhost/echo?greeting=hello")
+        .header("x-wasmtime-test", "wasi-http")
+        .body(Full::new(Bytes::from("hello, world")))?;
+    let request = store.data_mut().new_incoming_request(request)?;
+    let (outparam, receiver) = store.data_mut().new_response_outparam()?;
+
+    // The guest streams the response body after setting the response, so it
+    // runs concurrently with reading the response here.
+    let handle = tokio::task::spawn(async move {
+        proxy
+            .wasi_http_incoming_handler()
+            .call_handle(&mut store, request, outparam)
+            .await
+    });
+
+    let response = receiver.await??;
+    assert_eq!(response.status(), 200);
+    let headers = response.headers();
+    assert_eq!(headers["x-wasmtime-method"], "POST");
+    assert_eq!(headers["x-wasmtime-path"], "/echo?greeting=hello");
+    assert_eq!(headers["x-wasmtime-test"], "wasi-http");
+
+    let body = response.into_body().collect().await?;
+    let trailers = body.trailers().cloned().expect("response trailers");
+    assert_eq!(trailers["x-wasmtime-trailer"], "done");
+    assert_eq!(body.to_bytes(), "hello, world");
+
+    handle.await??;
+    Ok(())
+}
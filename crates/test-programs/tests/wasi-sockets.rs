@@ -1,8 +1,13 @@
 #![cfg(all(feature = "test_programs", not(skip_wasi_sockets_tests)))]
-use cap_std::ambient_authority;
+use cap_std::{ambient_authority, fs::Dir};
 use wasmtime::component::Linker;
 use wasmtime::{Config, Engine, Store};
-use wasmtime_wasi::preview2::{self, command::Command, Table, WasiCtx, WasiCtxBuilder, WasiView};
+use wasmtime_wasi::preview2::{
+    self,
+    command::Command,
+    pipe::{ClosedOutputStream, MemoryInputPipe},
+    DirPerms, FilePerms, IsATTY, StaticResolver, Table, WasiCtx, WasiCtxBuilder, WasiView,
+};
 
 lazy_static::lazy_static! {
     static ref ENGINE: Engine = {
@@ -42,6 +47,10 @@ impl WasiView for SocketsCtx {
 }
 
 async fn run(name: &str) -> anyhow::Result<()> {
+    run_with(name, |_| {}).await
+}
+
+async fn run_with(name: &str, configure: impl FnOnce(&mut WasiCtxBuilder)) -> anyhow::Result<()> {
     let component = get_component(name);
     let mut linker = Linker::new(&ENGINE);
 
@@ -61,16 +70,21 @@ async fn run(name: &str) -> anyhow::Result<()> {
     preview2::bindings::filesystem::preopens::add_to_linker(&mut linker, |x| x)?;
     preview2::bindings::sockets::tcp::add_to_linker(&mut linker, |x| x)?;
     preview2::bindings::sockets::tcp_create_socket::add_to_linker(&mut linker, |x| x)?;
+    preview2::bindings::sockets::udp::add_to_linker(&mut linker, |x| x)?;
+    preview2::bindings::sockets::udp_create_socket::add_to_linker(&mut linker, |x| x)?;
     preview2::bindings::sockets::network::add_to_linker(&mut linker, |x| x)?;
     preview2::bindings::sockets::instance_network::add_to_linker(&mut linker, |x| x)?;
+    preview2::bindings::sockets::ip_name_lookup::add_to_linker(&mut linker, |x| x)?;
 
     // Create our wasi context.
     let mut table = Table::new();
-    let wasi = WasiCtxBuilder::new()
+    let mut builder = WasiCtxBuilder::new();
+    builder
         .inherit_stdio()
         .inherit_network(ambient_authority())
-        .arg(name)
-        .build(&mut table)?;
+        .arg(name);
+    configure(&mut builder);
+    let wasi = builder.build(&mut table)?;
 
     let mut store = Store::new(&ENGINE, SocketsCtx { table, wasi });
 
@@ -91,3 +105,44 @@ async fn tcp_v4() {
 async fn tcp_v6() {
     run("tcp_v6").await.unwrap();
 }
+
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+async fn udp_v4() {
+    run("udp_v4").await.unwrap();
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+async fn ip_name_lookup() {
+    run("ip_name_lookup").await.unwrap();
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+async fn ip_name_lookup_static() {
+    run_with("ip_name_lookup_static", |builder| {
+        let mut resolver = StaticResolver::new();
+        resolver
+            .insert("example.test", "192.0.2.1".parse().unwrap())
+            .insert("example.test", "2001:db8::1".parse().unwrap());
+        builder.resolver(resolver);
+    })
+    .await
+    .unwrap();
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+async fn stream_splice() {
+    const TEXT: &str = "'Twas brillig, and the slithy toves did gyre and gimble in the wabe";
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("input.txt"), TEXT).unwrap();
+    let open_dir = Dir::open_ambient_dir(dir.path(), ambient_authority()).unwrap();
+
+    run_with("stream_splice", |builder| {
+        builder
+            .preopened_dir(open_dir, DirPerms::all(), FilePerms::all(), "/")
+            .stdin(MemoryInputPipe::new(TEXT.into()), IsATTY::No)
+            .stdout(ClosedOutputStream, IsATTY::No);
+    })
+    .await
+    .unwrap();
+}
@@ -0,0 +1,80 @@
+//! A `wasi:http/proxy` component which echoes each incoming request back to
+//! the client: the method, path and `x-wasmtime-test` header are returned as
+//! response headers, the request body as the response body, and the response
+//! ends with an `x-wasmtime-trailer` trailer.
+
+wit_bindgen::generate!({
+    path: "../../wasi-http/wit",
+    world: "wasi:http/proxy",
+});
+
+use exports::wasi::http::incoming_handler::IncomingHandler;
+use wasi::http::types::{self as http_types, IncomingRequest, Method, ResponseOutparam};
+use wasi::io::streams::{self, StreamStatus};
+
+struct T;
+
+export_proxy!(T);
+
+impl IncomingHandler for T {
+    fn handle(request: IncomingRequest, outparam: ResponseOutparam) {
+        let method = match http_types::incoming_request_method(request) {
+            Method::Get => "GET".to_string(),
+            Method::Head => "HEAD".to_string(),
+            Method::Post => "POST".to_string(),
+            Method::Put => "PUT".to_string(),
+            Method::Delete => "DELETE".to_string(),
+            Method::Connect => "CONNECT".to_string(),
+            Method::Options => "OPTIONS".to_string(),
+            Method::Trace => "TRACE".to_string(),
+            Method::Patch => "PATCH".to_string(),
+            Method::Other(method) => method,
+        };
+        let path = http_types::incoming_request_path_with_query(request).unwrap_or_default();
+        let headers = http_types::incoming_request_headers(request);
+        let test_header = http_types::fields_get(headers, "x-wasmtime-test")
+            .into_iter()
+            .map(|value| String::from_utf8(value).unwrap())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let body = http_types::incoming_request_consume(request).expect("request body");
+        let mut data = Vec::new();
+        loop {
+            let (chunk, status) =
+                streams::blocking_read(body, 4096).expect("reading the request body");
+            data.extend_from_slice(&chunk);
+            if status == StreamStatus::Ended {
+                break;
+            }
+        }
+        if let Some(trailers) = http_types::finish_incoming_stream(body) {
+            http_types::drop_fields(trailers);
+        }
+        http_types::drop_incoming_request(request);
+
+        let headers = http_types::new_fields(&[
+            ("x-wasmtime-method".to_string(), method),
+            ("x-wasmtime-path".to_string(), path),
+            ("x-wasmtime-test".to_string(), test_header),
+        ]);
+        let response = http_types::new_outgoing_response(200, headers);
+        let out = http_types::outgoing_response_write(response).expect("response body");
+
+        // Send the response before writing its body, which is streamed to the
+        // client as it's written.
+        http_types::set_response_outparam(outparam, Ok(response)).expect("setting the response");
+
+        let mut written = 0;
+        while written < data.len() {
+            let (n, _) =
+                streams::blocking_write(out, &data[written..]).expect("writing the response body");
+            written += n as usize;
+        }
+        let trailers =
+            http_types::new_fields(&[("x-wasmtime-trailer".to_string(), "done".to_string())]);
+        http_types::finish_outgoing_stream(out, Some(trailers));
+        streams::drop_output_stream(out);
+        http_types::drop_outgoing_response(response);
+    }
+}
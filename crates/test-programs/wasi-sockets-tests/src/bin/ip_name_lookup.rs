@@ -0,0 +1,43 @@
+//! Resolving IP address literals and invalid names.
+
+use wasi::poll::poll;
+use wasi::sockets::network::{ErrorCode, IpAddress};
+use wasi::sockets::{instance_network, ip_name_lookup, network};
+use wasi_sockets_tests::*;
+
+fn resolve_one(net: network::Network, name: &str) -> Result<Option<IpAddress>, ErrorCode> {
+    let stream = ip_name_lookup::resolve_addresses(net, name, None, false)?;
+    let sub = ip_name_lookup::subscribe(stream);
+    let result = loop {
+        match ip_name_lookup::resolve_next_address(stream) {
+            Err(ErrorCode::WouldBlock) => {
+                poll::poll_oneoff(&[sub]);
+            }
+            result => break result,
+        }
+    };
+    poll::drop_pollable(sub);
+    ip_name_lookup::drop_resolve_address_stream(stream);
+    result
+}
+
+fn main() {
+    let net = instance_network::instance_network();
+
+    match resolve_one(net, "127.0.0.1") {
+        Ok(Some(IpAddress::Ipv4((127, 0, 0, 1)))) => {}
+        other => panic!("unexpected result: {other:?}"),
+    }
+    match resolve_one(net, "[::1]") {
+        Ok(Some(IpAddress::Ipv6((0, 0, 0, 0, 0, 0, 0, 1)))) => {}
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    assert!(matches!(
+        resolve_one(net, "not a valid name"),
+        Err(ErrorCode::InvalidName)
+    ));
+    assert!(matches!(resolve_one(net, ""), Err(ErrorCode::InvalidName)));
+
+    network::drop_network(net);
+}
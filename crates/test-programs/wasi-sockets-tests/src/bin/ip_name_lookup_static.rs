@@ -0,0 +1,69 @@
+//! Resolving names with the `StaticResolver` installed by the host, which
+//! maps `example.test` to `192.0.2.1` and `2001:db8::1`.
+
+use wasi::poll::poll;
+use wasi::sockets::network::{ErrorCode, IpAddress, IpAddressFamily};
+use wasi::sockets::{instance_network, ip_name_lookup, network};
+use wasi_sockets_tests::*;
+
+fn resolve_all(
+    net: network::Network,
+    name: &str,
+    family: Option<IpAddressFamily>,
+) -> Result<Vec<IpAddress>, ErrorCode> {
+    let stream = ip_name_lookup::resolve_addresses(net, name, family, false)?;
+    let sub = ip_name_lookup::subscribe(stream);
+    let mut addrs = Vec::new();
+    let result = loop {
+        match ip_name_lookup::resolve_next_address(stream) {
+            Err(ErrorCode::WouldBlock) => {
+                poll::poll_oneoff(&[sub]);
+            }
+            Ok(Some(addr)) => addrs.push(addr),
+            Ok(None) => break Ok(addrs),
+            Err(e) => break Err(e),
+        }
+    };
+    poll::drop_pollable(sub);
+    ip_name_lookup::drop_resolve_address_stream(stream);
+    result
+}
+
+fn main() {
+    let net = instance_network::instance_network();
+
+    match resolve_all(net, "example.test", None).as_deref() {
+        Ok(
+            [IpAddress::Ipv4((192, 0, 2, 1)), IpAddress::Ipv6((0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))],
+        ) => {}
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // Names are case-insensitive.
+    match resolve_all(net, "EXAMPLE.test", None).as_deref() {
+        Ok([_, _]) => {}
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    match resolve_all(net, "example.test", Some(IpAddressFamily::Ipv4)).as_deref() {
+        Ok([IpAddress::Ipv4((192, 0, 2, 1))]) => {}
+        other => panic!("unexpected result: {other:?}"),
+    }
+    match resolve_all(net, "example.test", Some(IpAddressFamily::Ipv6)).as_deref() {
+        Ok([IpAddress::Ipv6((0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))]) => {}
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // Names the resolver doesn't know about don't fall back to the host's
+    // resolver.
+    assert!(matches!(
+        resolve_all(net, "localhost", None),
+        Err(ErrorCode::NameUnresolvable)
+    ));
+    assert!(matches!(
+        resolve_all(net, "missing.test", None),
+        Err(ErrorCode::NameUnresolvable)
+    ));
+
+    network::drop_network(net);
+}
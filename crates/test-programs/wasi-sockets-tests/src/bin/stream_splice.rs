@@ -0,0 +1,183 @@
+//! Moving data between file, pipe and TCP streams with `splice`,
+//! `blocking-splice` and `forward`.
+//!
+//! The host preopens a directory containing `input.txt`, passes the same text
+//! on stdin, and closes stdout.
+
+use wasi::cli::{stdin, stdout};
+use wasi::filesystem::{preopens, types as fs};
+use wasi::io::streams::{self, StreamStatus};
+use wasi::poll::poll;
+use wasi::sockets::network::{IpAddressFamily, IpSocketAddress, Ipv4SocketAddress};
+use wasi::sockets::{instance_network, network, tcp, tcp_create_socket};
+use wasi_sockets_tests::*;
+
+const TEXT: &[u8] = b"'Twas brillig, and the slithy toves did gyre and gimble in the wabe";
+
+fn wait(sub: poll::Pollable) {
+    loop {
+        let wait = poll::poll_oneoff(&[sub]);
+        if wait[0] {
+            break;
+        }
+    }
+}
+
+fn open(
+    dir: fs::Descriptor,
+    path: &str,
+    open_flags: fs::OpenFlags,
+    flags: fs::DescriptorFlags,
+) -> fs::Descriptor {
+    fs::open_at(
+        dir,
+        fs::PathFlags::empty(),
+        path,
+        open_flags,
+        flags,
+        fs::Modes::READABLE | fs::Modes::WRITABLE,
+    )
+    .unwrap()
+}
+
+fn create(dir: fs::Descriptor, path: &str) -> (fs::Descriptor, streams::OutputStream) {
+    let file = open(
+        dir,
+        path,
+        fs::OpenFlags::CREATE | fs::OpenFlags::TRUNCATE,
+        fs::DescriptorFlags::WRITE,
+    );
+    let stream = fs::write_via_stream(file, 0).unwrap();
+    (file, stream)
+}
+
+fn read_file(dir: fs::Descriptor, path: &str) -> Vec<u8> {
+    let file = open(dir, path, fs::OpenFlags::empty(), fs::DescriptorFlags::READ);
+    let stream = fs::read_via_stream(file, 0).unwrap();
+    let mut data = Vec::new();
+    loop {
+        let (chunk, status) = streams::blocking_read(stream, 1024).unwrap();
+        data.extend(chunk);
+        if status == StreamStatus::Ended {
+            break;
+        }
+    }
+    streams::drop_input_stream(stream);
+    fs::drop_descriptor(file);
+    data
+}
+
+fn main() {
+    let (dir, _) = preopens::get_directories()
+        .into_iter()
+        .find(|(_, path)| path == "/")
+        .expect("a preopen of `/`");
+
+    // File to file.
+    let input = open(
+        dir,
+        "input.txt",
+        fs::OpenFlags::empty(),
+        fs::DescriptorFlags::READ,
+    );
+    let src = fs::read_via_stream(input, 0).unwrap();
+    let (output, dst) = create(dir, "spliced.txt");
+    // `splice` moves at most `len` bytes...
+    assert_eq!(
+        streams::splice(dst, src, 5).unwrap(),
+        (5, StreamStatus::Open)
+    );
+    // ...and so does `blocking-splice`, which moves the rest here.
+    assert_eq!(
+        streams::blocking_splice(dst, src, 1024).unwrap(),
+        (TEXT.len() as u64 - 5, StreamStatus::Open)
+    );
+    // Once the input is exhausted, its end is reported.
+    assert_eq!(
+        streams::splice(dst, src, 1024).unwrap(),
+        (0, StreamStatus::Ended)
+    );
+    streams::drop_input_stream(src);
+    streams::drop_output_stream(dst);
+    fs::drop_descriptor(output);
+    assert_eq!(read_file(dir, "spliced.txt"), TEXT);
+
+    // Pipe to a closed stream: the transfer is cut short before anything is
+    // read, so no bytes are lost from the input.
+    let stdin = stdin::get_stdin();
+    let stdout = stdout::get_stdout();
+    assert_eq!(
+        streams::blocking_splice(stdout, stdin, 1024).unwrap(),
+        (0, StreamStatus::Ended)
+    );
+    assert_eq!(
+        streams::forward(stdout, stdin).unwrap(),
+        (0, StreamStatus::Ended)
+    );
+    streams::drop_output_stream(stdout);
+
+    // Pipe to file.
+    let (output, dst) = create(dir, "forwarded.txt");
+    assert_eq!(
+        streams::forward(dst, stdin).unwrap(),
+        (TEXT.len() as u64, StreamStatus::Open)
+    );
+    streams::drop_input_stream(stdin);
+    streams::drop_output_stream(dst);
+    fs::drop_descriptor(output);
+    assert_eq!(read_file(dir, "forwarded.txt"), TEXT);
+
+    // File to TCP, and back.
+    let net = instance_network::instance_network();
+    let sock = tcp_create_socket::create_tcp_socket(IpAddressFamily::Ipv4).unwrap();
+    let addr = IpSocketAddress::Ipv4(Ipv4SocketAddress {
+        port: 0,                 // use any free port
+        address: (127, 0, 0, 1), // localhost
+    });
+    let sub = tcp::subscribe(sock);
+    tcp::start_bind(sock, net, addr).unwrap();
+    wait(sub);
+    tcp::finish_bind(sock).unwrap();
+    tcp::start_listen(sock).unwrap();
+    wait(sub);
+    tcp::finish_listen(sock).unwrap();
+    let addr = tcp::local_address(sock).unwrap();
+
+    let client = tcp_create_socket::create_tcp_socket(IpAddressFamily::Ipv4).unwrap();
+    let client_sub = tcp::subscribe(client);
+    tcp::start_connect(client, net, addr).unwrap();
+    wait(client_sub);
+    let (client_input, client_output) = tcp::finish_connect(client).unwrap();
+
+    let src = fs::read_via_stream(input, 0).unwrap();
+    assert_eq!(
+        streams::forward(client_output, src).unwrap(),
+        (TEXT.len() as u64, StreamStatus::Open)
+    );
+    streams::drop_input_stream(src);
+    fs::drop_descriptor(input);
+    // Shutting down the client lets the server see the end of the stream.
+    tcp::shutdown(client, tcp::ShutdownType::Send).unwrap();
+
+    wait(sub);
+    let (accepted, server_input, server_output) = tcp::accept(sock).unwrap();
+    let (output, dst) = create(dir, "received.txt");
+    assert_eq!(
+        streams::forward(dst, server_input).unwrap(),
+        (TEXT.len() as u64, StreamStatus::Open)
+    );
+    streams::drop_output_stream(dst);
+    fs::drop_descriptor(output);
+    assert_eq!(read_file(dir, "received.txt"), TEXT);
+
+    streams::drop_input_stream(server_input);
+    streams::drop_output_stream(server_output);
+    tcp::drop_tcp_socket(accepted);
+    streams::drop_input_stream(client_input);
+    streams::drop_output_stream(client_output);
+    poll::drop_pollable(client_sub);
+    tcp::drop_tcp_socket(client);
+    poll::drop_pollable(sub);
+    tcp::drop_tcp_socket(sock);
+    network::drop_network(net);
+}
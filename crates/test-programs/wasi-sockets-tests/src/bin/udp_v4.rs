@@ -0,0 +1,123 @@
+//! A simple UDP testcase, using IPv4.
+
+use wasi::poll::poll;
+use wasi::sockets::network::{ErrorCode, IpAddressFamily, IpSocketAddress, Ipv4SocketAddress};
+use wasi::sockets::{instance_network, network, udp, udp_create_socket};
+use wasi_sockets_tests::*;
+
+fn wait(sub: poll::Pollable) {
+    loop {
+        let wait = poll::poll_oneoff(&[sub]);
+        if wait[0] {
+            break;
+        }
+    }
+}
+
+fn port(addr: IpSocketAddress) -> u16 {
+    match addr {
+        IpSocketAddress::Ipv4(addr) => addr.port,
+        IpSocketAddress::Ipv6(addr) => addr.port,
+    }
+}
+
+fn main() {
+    let first_message = b"Hello, world!";
+    let second_message = b"Greetings, planet!";
+
+    let net = instance_network::instance_network();
+
+    let addr = IpSocketAddress::Ipv4(Ipv4SocketAddress {
+        port: 0,                 // use any free port
+        address: (127, 0, 0, 1), // localhost
+    });
+
+    let server = udp_create_socket::create_udp_socket(IpAddressFamily::Ipv4).unwrap();
+    let server_sub = udp::subscribe(server);
+
+    // Sending before binding is an error.
+    let datagram = udp::Datagram {
+        data: first_message.to_vec(),
+        remote_address: addr,
+    };
+    assert_eq!(udp::send(server, &[datagram]), Err(ErrorCode::NotBound));
+
+    udp::start_bind(server, net, addr).unwrap();
+    wait(server_sub);
+    udp::finish_bind(server).unwrap();
+
+    let server_addr = udp::local_address(server).unwrap();
+
+    // Nothing has been sent yet.
+    assert_eq!(udp::receive(server, 1), Err(ErrorCode::WouldBlock));
+
+    // An IPv6 address can't be used with an IPv4 socket.
+    let client = udp_create_socket::create_udp_socket(IpAddressFamily::Ipv4).unwrap();
+    let client_sub = udp::subscribe(client);
+    let v6 = IpSocketAddress::Ipv6(network::Ipv6SocketAddress {
+        port: 0,
+        address: (0, 0, 0, 0, 0, 0, 0, 1),
+        flow_info: 0,
+        scope_id: 0,
+    });
+    assert_eq!(
+        udp::start_bind(client, net, v6),
+        Err(ErrorCode::AddressFamilyMismatch)
+    );
+
+    udp::start_bind(client, net, addr).unwrap();
+    wait(client_sub);
+    udp::finish_bind(client).unwrap();
+
+    let datagrams = [
+        udp::Datagram {
+            data: first_message.to_vec(),
+            remote_address: server_addr,
+        },
+        udp::Datagram {
+            data: second_message.to_vec(),
+            remote_address: server_addr,
+        },
+    ];
+    assert_eq!(udp::send(client, &datagrams), Ok(2));
+
+    let client_addr = udp::local_address(client).unwrap();
+    let mut received = Vec::new();
+    while received.len() < 2 {
+        wait(server_sub);
+        match udp::receive(server, 2) {
+            Ok(datagrams) => received.extend(datagrams),
+            Err(ErrorCode::WouldBlock) => {}
+            Err(err) => panic!("receive failed: {err:?}"),
+        }
+    }
+
+    assert_eq!(received[0].data, first_message);
+    assert_eq!(port(received[0].remote_address), port(client_addr));
+    assert_eq!(received[1].data, second_message);
+    assert_eq!(port(received[1].remote_address), port(client_addr));
+
+    // Once connected, datagrams can only be sent to the connected address.
+    udp::start_connect(client, net, server_addr).unwrap();
+    wait(client_sub);
+    udp::finish_connect(client).unwrap();
+    assert_eq!(
+        port(udp::remote_address(client).unwrap()),
+        port(server_addr)
+    );
+
+    let elsewhere = udp::Datagram {
+        data: first_message.to_vec(),
+        remote_address: client_addr,
+    };
+    assert_eq!(
+        udp::send(client, &[elsewhere]),
+        Err(ErrorCode::AlreadyConnected)
+    );
+
+    poll::drop_pollable(client_sub);
+    udp::drop_udp_socket(client);
+    poll::drop_pollable(server_sub);
+    udp::drop_udp_socket(server);
+    network::drop_network(net);
+}
@@ -231,6 +231,11 @@ entity_impl!(TypeIndex);
 pub struct TagIndex(u32);
 entity_impl!(TagIndex);
 
+/// Index type of a defined tag inside the WebAssembly module.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct DefinedTagIndex(u32);
+entity_impl!(DefinedTagIndex);
+
 /// Index into the global list of modules found within an entire component.
 ///
 /// Module translations are saved on the side to get fully compiled after
@@ -250,6 +255,8 @@ pub enum EntityIndex {
     Memory(MemoryIndex),
     /// Global index.
     Global(GlobalIndex),
+    /// Tag index.
+    Tag(TagIndex),
 }
 
 impl From<FuncIndex> for EntityIndex {
@@ -276,6 +283,12 @@ impl From<GlobalIndex> for EntityIndex {
     }
 }
 
+impl From<TagIndex> for EntityIndex {
+    fn from(idx: TagIndex) -> EntityIndex {
+        EntityIndex::Tag(idx)
+    }
+}
+
 /// A type of an item in a wasm module where an item is typically something that
 /// can be exported.
 #[allow(missing_docs)]
@@ -285,8 +298,9 @@ pub enum EntityType {
     Global(Global),
     /// A linear memory with the specified limits
     Memory(Memory),
-    /// An event definition.
-    Tag(Tag),
+    /// An exception tag, where the index points to the signature describing
+    /// the tag's payload.
+    Tag(SignatureIndex),
     /// A table with the specified element type and limits
     Table(Table),
     /// A function type where the index points to the type section and records a
@@ -312,9 +326,9 @@ impl EntityType {
     }
 
     /// Assert that this entity is a tag
-    pub fn unwrap_tag(&self) -> &Tag {
+    pub fn unwrap_tag(&self) -> SignatureIndex {
         match self {
-            EntityType::Tag(g) => g,
+            EntityType::Tag(g) => *g,
             _ => panic!("not a tag"),
         }
     }
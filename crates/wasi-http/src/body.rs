@@ -0,0 +1,300 @@
+//! Streaming adapters between hyper bodies and the `wasi:io/streams`
+//! resources handed out to guests by the `incoming-handler` implementation.
+
+use anyhow::Error;
+use bytes::Bytes;
+use http_body::{Body, Frame};
+use http_body_util::BodyExt;
+use hyper::HeaderMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::{mpsc, oneshot};
+use wasmtime_wasi::preview2::{HostInputStream, HostOutputStream, StreamRuntimeError, StreamState};
+
+/// Create the guest-facing [`HostIncomingBody`] for a hyper body, along with
+/// a receiver which will hold the body's trailers, if any, once the body has
+/// been fully read.
+///
+/// The hyper body is driven by a background task which stops as soon as the
+/// guest drops the stream.
+pub fn incoming<B>(body: B) -> (HostIncomingBody, oneshot::Receiver<HeaderMap>)
+where
+    B: Body<Data = Bytes> + Send + Unpin + 'static,
+    B::Error: std::error::Error + Send + Sync + 'static,
+{
+    let (sender, receiver) = mpsc::channel(1);
+    let (trailers_sender, trailers_receiver) = oneshot::channel();
+
+    tokio::task::spawn(async move {
+        let mut body = body;
+        let mut trailers_sender = Some(trailers_sender);
+        while let Some(frame) = body.frame().await {
+            let frame = match frame {
+                Ok(frame) => frame,
+                Err(e) => {
+                    let _ = sender.send(Err(Error::from(e))).await;
+                    break;
+                }
+            };
+            if let Some(data) = frame.data_ref() {
+                if sender.send(Ok(data.clone())).await.is_err() {
+                    // The guest dropped the stream, stop reading the body.
+                    break;
+                }
+            }
+            if let Some(trailers) = frame.trailers_ref() {
+                if let Some(trailers_sender) = trailers_sender.take() {
+                    let _ = trailers_sender.send(trailers.clone());
+                }
+            }
+        }
+    });
+
+    (
+        HostIncomingBody {
+            state: StreamState::Open,
+            buffer: None,
+            receiver,
+        },
+        trailers_receiver,
+    )
+}
+
+/// Create the guest-facing [`HostOutgoingBody`] along with the
+/// [`HyperOutgoingBody`] which yields everything the guest writes to it.
+pub fn outgoing() -> (HostOutgoingBody, HyperOutgoingBody) {
+    let (sender, receiver) = mpsc::channel(1);
+    (
+        HostOutgoingBody { sender },
+        HyperOutgoingBody {
+            receiver,
+            finished: false,
+        },
+    )
+}
+
+/// A [`HostInputStream`] which reads the body of an incoming request.
+pub struct HostIncomingBody {
+    state: StreamState,
+    buffer: Option<Result<Bytes, Error>>,
+    receiver: mpsc::Receiver<Result<Bytes, Error>>,
+}
+
+impl HostIncomingBody {
+    fn split(&mut self, mut bytes: Bytes, size: usize) -> (Bytes, StreamState) {
+        let rest = bytes.split_off(bytes.len().min(size));
+        if !rest.is_empty() {
+            self.buffer = Some(Ok(rest));
+        }
+        (bytes, StreamState::Open)
+    }
+}
+
+#[async_trait::async_trait]
+impl HostInputStream for HostIncomingBody {
+    fn read(&mut self, size: usize) -> Result<(Bytes, StreamState), Error> {
+        use tokio::sync::mpsc::error::TryRecvError;
+
+        match self.buffer.take() {
+            Some(Ok(bytes)) => return Ok(self.split(bytes, size)),
+            Some(Err(e)) => return Err(StreamRuntimeError::from(e).into()),
+            None => {}
+        }
+
+        if self.state.is_closed() {
+            return Ok((Bytes::new(), StreamState::Closed));
+        }
+
+        match self.receiver.try_recv() {
+            Ok(Ok(bytes)) => Ok(self.split(bytes, size)),
+            Ok(Err(e)) => Err(StreamRuntimeError::from(e).into()),
+            Err(TryRecvError::Empty) => Ok((Bytes::new(), StreamState::Open)),
+            Err(TryRecvError::Disconnected) => {
+                self.state = StreamState::Closed;
+                Ok((Bytes::new(), StreamState::Closed))
+            }
+        }
+    }
+
+    async fn ready(&mut self) -> Result<(), Error> {
+        if self.buffer.is_some() || self.state.is_closed() {
+            return Ok(());
+        }
+        match self.receiver.recv().await {
+            Some(result) => self.buffer = Some(result),
+            None => self.state = StreamState::Closed,
+        }
+        Ok(())
+    }
+}
+
+/// The frames sent from a [`HostOutgoingBody`] to its [`HyperOutgoingBody`].
+pub(crate) enum OutgoingFrame {
+    Data(Bytes),
+    Finish(Option<HeaderMap>),
+}
+
+/// A [`HostOutputStream`] which writes the body of an outgoing response.
+pub struct HostOutgoingBody {
+    sender: mpsc::Sender<OutgoingFrame>,
+}
+
+impl HostOutgoingBody {
+    /// A handle which can be used to finish the body, optionally with
+    /// trailers, once the guest calls `finish-outgoing-stream`.
+    pub(crate) fn finisher(&self) -> mpsc::Sender<OutgoingFrame> {
+        self.sender.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl HostOutputStream for HostOutgoingBody {
+    fn write(&mut self, bytes: Bytes) -> Result<(usize, StreamState), Error> {
+        use tokio::sync::mpsc::error::TrySendError;
+
+        if bytes.is_empty() {
+            let state = if self.sender.is_closed() {
+                StreamState::Closed
+            } else {
+                StreamState::Open
+            };
+            return Ok((0, state));
+        }
+        let len = bytes.len();
+        match self.sender.try_send(OutgoingFrame::Data(bytes)) {
+            Ok(()) => Ok((len, StreamState::Open)),
+            Err(TrySendError::Full(_)) => Ok((0, StreamState::Open)),
+            Err(TrySendError::Closed(_)) => Ok((0, StreamState::Closed)),
+        }
+    }
+
+    async fn ready(&mut self) -> Result<(), Error> {
+        // Reserving a slot waits until hyper has taken the previous frame.
+        // Once the receiver is gone the stream is closed, which is also
+        // reported as ready.
+        let _ = self.sender.reserve().await;
+        Ok(())
+    }
+}
+
+/// The hyper body of a response produced by a guest's `incoming-handler`.
+pub struct HyperOutgoingBody {
+    receiver: mpsc::Receiver<OutgoingFrame>,
+    finished: bool,
+}
+
+impl HyperOutgoingBody {
+    /// A body which has no contents and no trailers.
+    pub fn empty() -> Self {
+        let (_, body) = outgoing();
+        body
+    }
+
+    /// A body which has already been written in full, followed by `trailers`
+    /// if there are any.
+    pub(crate) fn full(data: Bytes, trailers: Option<HeaderMap>) -> Self {
+        let (sender, receiver) = mpsc::channel(2);
+        // The channel has room for both frames, so neither send can fail.
+        if !data.is_empty() {
+            let _ = sender.try_send(OutgoingFrame::Data(data));
+        }
+        let _ = sender.try_send(OutgoingFrame::Finish(trailers));
+        Self {
+            receiver,
+            finished: false,
+        }
+    }
+}
+
+impl Body for HyperOutgoingBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Error>>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        match self.receiver.poll_recv(cx) {
+            Poll::Ready(Some(OutgoingFrame::Data(bytes))) => {
+                Poll::Ready(Some(Ok(Frame::data(bytes))))
+            }
+            Poll::Ready(Some(OutgoingFrame::Finish(trailers))) => {
+                self.finished = true;
+                Poll::Ready(trailers.map(|t| Ok(Frame::trailers(t))))
+            }
+            Poll::Ready(None) => {
+                self.finished = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.finished
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http_body_util::Full;
+
+    #[tokio::test]
+    async fn outgoing_body_with_trailers() {
+        let (mut stream, mut body) = outgoing();
+        let finisher = stream.finisher();
+
+        let writer = tokio::task::spawn(async move {
+            for chunk in ["hello", " ", "world"] {
+                stream.ready().await.unwrap();
+                let (n, state) = stream.write(Bytes::from(chunk)).unwrap();
+                assert_eq!(n, chunk.len());
+                assert_eq!(state, StreamState::Open);
+            }
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-checksum", "abc".parse().unwrap());
+            assert!(finisher
+                .send(OutgoingFrame::Finish(Some(trailers)))
+                .await
+                .is_ok());
+        });
+
+        let mut data = Vec::new();
+        let mut trailers = None;
+        while let Some(frame) = body.frame().await {
+            let frame = frame.unwrap();
+            if let Some(chunk) = frame.data_ref() {
+                data.extend_from_slice(chunk);
+            }
+            if let Some(t) = frame.trailers_ref() {
+                trailers = Some(t.clone());
+            }
+        }
+        writer.await.unwrap();
+
+        assert_eq!(data, b"hello world");
+        assert_eq!(trailers.unwrap()["x-checksum"], "abc");
+        assert!(body.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn incoming_body_reads_to_end() {
+        let (mut stream, _trailers) = incoming(Full::new(Bytes::from("hello world")));
+
+        let mut data = Vec::new();
+        loop {
+            stream.ready().await.unwrap();
+            let (bytes, state) = stream.read(4).unwrap();
+            assert!(bytes.len() <= 4);
+            data.extend_from_slice(&bytes);
+            if state.is_closed() {
+                break;
+            }
+        }
+        assert_eq!(data, b"hello world");
+    }
+}
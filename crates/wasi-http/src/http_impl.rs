@@ -1,9 +1,10 @@
+use crate::body::HyperOutgoingBody;
 use crate::types::{ActiveFields, ActiveFuture, ActiveResponse, HttpResponse, TableHttpExt};
 use crate::wasi::http::types::{FutureIncomingResponse, OutgoingRequest, RequestOptions, Scheme};
 pub use crate::{WasiHttpCtx, WasiHttpView};
 use anyhow::Context;
 use bytes::{Bytes, BytesMut};
-use http_body_util::{BodyExt, Empty, Full};
+use http_body_util::BodyExt;
 use hyper::{Method, Request};
 #[cfg(not(any(target_arch = "riscv64", target_arch = "s390x")))]
 use std::sync::Arc;
@@ -201,6 +202,16 @@ impl<T: WasiHttpView> WasiHttpViewExt for T {
             }
         }
 
+        let trailers = match request.trailers() {
+            Some(id) => Some(
+                self.table()
+                    .get_fields(id)
+                    .context("[handle_async] getting request trailers")?
+                    .to_header_map()?,
+            ),
+            None => None,
+        };
+
         let mut response = ActiveResponse::new();
         let body = match request.body() {
             Some(id) => {
@@ -222,9 +233,9 @@ impl<T: WasiHttpView> WasiHttpViewExt for T {
                     };
                     bytes.extend_from_slice(&chunk[..]);
                 }
-                Full::<Bytes>::new(bytes.freeze()).boxed()
+                HyperOutgoingBody::full(bytes.freeze(), trailers)
             }
-            None => Empty::<Bytes>::new().boxed(),
+            None => HyperOutgoingBody::full(Bytes::new(), trailers),
         };
         let t = timeout(first_bytes_timeout, sender.send_request(call.body(body)?)).await?;
         let mut res = t?;
@@ -13,6 +13,7 @@ wasmtime::component::bindgen!({
     async: true,
 });
 
+pub mod body;
 pub mod component_impl;
 pub mod http_impl;
 pub mod types;
@@ -1,15 +1,21 @@
 //! Implements the base structure (i.e. [WasiHttpCtx]) that will provide the
 //! implementation of the wasi-http API.
 
+use crate::body::{HyperOutgoingBody, OutgoingFrame};
 use crate::wasi::http::types::{
-    IncomingStream, Method, OutgoingRequest, OutgoingStream, RequestOptions, Scheme,
+    Error, IncomingRequest, IncomingStream, Method, OutgoingRequest, OutgoingStream,
+    RequestOptions, ResponseOutparam, Scheme,
 };
+use anyhow::Context;
 use bytes::Bytes;
+use http::header::{HeaderName, HeaderValue};
+use hyper::HeaderMap;
 use std::any::Any;
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
+use tokio::sync::{mpsc, oneshot};
 use wasmtime_wasi::preview2::{
-    pipe::{AsyncReadStream, AsyncWriteStream},
+    pipe::{AsyncReadStream, AsyncWriteStream, ClosedInputStream, ClosedOutputStream},
     HostInputStream, HostOutputStream, Table, TableError, TableStreamExt, WasiView,
 };
 
@@ -18,6 +24,14 @@ const MAX_BUF_SIZE: usize = 65_536;
 /// Capture the state necessary for use in the wasi-http API implementation.
 pub struct WasiHttpCtx {
     pub streams: HashMap<u32, Stream>,
+    /// Trailers of incoming request bodies, keyed by the body's incoming stream.
+    pub(crate) incoming_trailers: HashMap<IncomingStream, oneshot::Receiver<HeaderMap>>,
+    /// Hyper bodies of outgoing responses which have not yet been handed to a
+    /// `response-outparam`, keyed by the response.
+    pub(crate) outgoing_bodies: HashMap<u32, HyperOutgoingBody>,
+    /// Handles used to finish outgoing response bodies, keyed by the body's
+    /// outgoing stream.
+    pub(crate) outgoing_finishers: HashMap<OutgoingStream, mpsc::Sender<OutgoingFrame>>,
 }
 
 impl WasiHttpCtx {
@@ -25,13 +39,112 @@ impl WasiHttpCtx {
     pub fn new() -> Self {
         Self {
             streams: HashMap::new(),
+            incoming_trailers: HashMap::new(),
+            outgoing_bodies: HashMap::new(),
+            outgoing_finishers: HashMap::new(),
         }
     }
 }
 
+/// The response delivered by a guest through a `response-outparam`.
+pub type ResponseOutparamResult = Result<hyper::Response<HyperOutgoingBody>, Error>;
+
 pub trait WasiHttpView: WasiView {
     fn http_ctx(&self) -> &WasiHttpCtx;
     fn http_ctx_mut(&mut self) -> &mut WasiHttpCtx;
+
+    /// Create an `incoming-request` resource from a hyper request, suitable
+    /// for passing to the `wasi:http/incoming-handler` export of a guest.
+    ///
+    /// The request body is streamed to the guest as it reads it.
+    fn new_incoming_request<B>(
+        &mut self,
+        request: hyper::Request<B>,
+    ) -> anyhow::Result<IncomingRequest>
+    where
+        B: http_body::Body<Data = Bytes> + Send + Unpin + 'static,
+        B::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let (parts, body) = request.into_parts();
+
+        let mut req = ActiveRequest::new();
+        req.method = match parts.method {
+            hyper::Method::GET => Method::Get,
+            hyper::Method::HEAD => Method::Head,
+            hyper::Method::POST => Method::Post,
+            hyper::Method::PUT => Method::Put,
+            hyper::Method::DELETE => Method::Delete,
+            hyper::Method::CONNECT => Method::Connect,
+            hyper::Method::OPTIONS => Method::Options,
+            hyper::Method::TRACE => Method::Trace,
+            hyper::Method::PATCH => Method::Patch,
+            ref other => Method::Other(other.to_string()),
+        };
+        req.scheme = match parts.uri.scheme_str() {
+            Some("http") => Some(Scheme::Http),
+            Some("https") => Some(Scheme::Https),
+            Some(other) => Some(Scheme::Other(other.to_string())),
+            None => None,
+        };
+        req.path_with_query = parts
+            .uri
+            .path_and_query()
+            .map(|p| p.as_str())
+            .unwrap_or("/")
+            .to_string();
+        req.authority = match parts.uri.authority() {
+            Some(authority) => authority.to_string(),
+            None => parts
+                .headers
+                .get(hyper::header::HOST)
+                .and_then(|host| host.to_str().ok())
+                .unwrap_or("")
+                .to_string(),
+        };
+
+        let headers = self
+            .table_mut()
+            .push_fields(Box::new(ActiveFields::from(&parts.headers)))
+            .context("[new_incoming_request] pushing headers")?;
+        req.headers = Some(headers);
+        let request_id = self
+            .table_mut()
+            .push_request(Box::new(req))
+            .context("[new_incoming_request] pushing request")?;
+
+        let (body, trailers) = crate::body::incoming(body);
+        let (stream_id, stream) = self
+            .table_mut()
+            .push_incoming_body(Box::new(body), request_id)
+            .context("[new_incoming_request] pushing body")?;
+        self.table_mut()
+            .get_request_mut(request_id)
+            .context("[new_incoming_request] getting request")?
+            .set_body(stream_id);
+        self.http_ctx_mut()
+            .incoming_trailers
+            .insert(stream.incoming(), trailers);
+        self.http_ctx_mut().streams.insert(stream_id, stream);
+
+        Ok(request_id)
+    }
+
+    /// Create a `response-outparam` resource, suitable for passing to the
+    /// `wasi:http/incoming-handler` export of a guest.
+    ///
+    /// The returned receiver resolves once the guest calls
+    /// `set-response-outparam`, at which point the response headers are
+    /// available while its body may still be streaming.
+    fn new_response_outparam(
+        &mut self,
+    ) -> anyhow::Result<(ResponseOutparam, oneshot::Receiver<ResponseOutparamResult>)> {
+        let (sender, receiver) = oneshot::channel();
+        let id = self
+            .table_mut()
+            .push_response_outparam(Box::new(ActiveResponseOutparam::new(sender)))
+            .context("[new_response_outparam] pushing outparam")?;
+        Ok((id, receiver))
+    }
 }
 
 pub type FieldsMap = HashMap<String, Vec<Vec<u8>>>;
@@ -45,6 +158,7 @@ pub struct ActiveRequest {
     pub authority: String,
     pub headers: Option<u32>,
     pub body: Option<u32>,
+    pub trailers: Option<u32>,
 }
 
 pub trait HttpRequest: Send + Sync {
@@ -62,6 +176,8 @@ pub trait HttpRequest: Send + Sync {
     fn set_headers(&mut self, headers: u32);
     fn body(&self) -> Option<u32>;
     fn set_body(&mut self, body: u32);
+    fn trailers(&self) -> Option<u32>;
+    fn set_trailers(&mut self, trailers: u32);
 }
 
 impl HttpRequest for ActiveRequest {
@@ -74,6 +190,7 @@ impl HttpRequest for ActiveRequest {
             authority: "".to_string(),
             headers: None,
             body: None,
+            trailers: None,
         }
     }
 
@@ -112,6 +229,14 @@ impl HttpRequest for ActiveRequest {
     fn set_body(&mut self, body: u32) {
         self.body = Some(body);
     }
+
+    fn trailers(&self) -> Option<u32> {
+        self.trailers
+    }
+
+    fn set_trailers(&mut self, trailers: u32) {
+        self.trailers = Some(trailers);
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -183,6 +308,23 @@ impl HttpResponse for ActiveResponse {
     }
 }
 
+pub struct ActiveResponseOutparam {
+    sender: Option<oneshot::Sender<ResponseOutparamResult>>,
+}
+
+impl ActiveResponseOutparam {
+    pub fn new(sender: oneshot::Sender<ResponseOutparamResult>) -> Self {
+        Self {
+            sender: Some(sender),
+        }
+    }
+
+    /// Take the sender for the response, which is only available once.
+    pub fn take_sender(&mut self) -> Option<oneshot::Sender<ResponseOutparamResult>> {
+        self.sender.take()
+    }
+}
+
 #[derive(Clone)]
 pub struct ActiveFuture {
     request_id: OutgoingRequest,
@@ -239,6 +381,32 @@ pub trait HttpFields: Send + Sync {
     fn as_any(&self) -> &dyn Any;
 }
 
+impl ActiveFields {
+    pub fn to_header_map(&self) -> anyhow::Result<HeaderMap> {
+        let mut map = HeaderMap::new();
+        for (name, values) in self.iter() {
+            let name = HeaderName::from_bytes(name.as_bytes())?;
+            for value in values {
+                map.append(name.clone(), HeaderValue::from_bytes(value)?);
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl From<&HeaderMap> for ActiveFields {
+    fn from(headers: &HeaderMap) -> Self {
+        let mut fields = Self::new();
+        for (name, value) in headers.iter() {
+            fields
+                .entry(name.as_str().to_string())
+                .or_default()
+                .push(value.as_bytes().to_vec());
+        }
+        fields
+    }
+}
+
 impl HttpFields for ActiveFields {
     fn as_any(&self) -> &dyn Any {
         self
@@ -308,7 +476,27 @@ pub trait TableHttpExt {
     fn get_fields_mut(&mut self, id: u32) -> Result<&mut Box<ActiveFields>, TableError>;
     fn delete_fields(&mut self, id: u32) -> Result<(), TableError>;
 
+    fn push_response_outparam(
+        &mut self,
+        outparam: Box<ActiveResponseOutparam>,
+    ) -> Result<u32, TableError>;
+    fn get_response_outparam_mut(
+        &mut self,
+        id: u32,
+    ) -> Result<&mut Box<ActiveResponseOutparam>, TableError>;
+    fn delete_response_outparam(&mut self, id: u32) -> Result<(), TableError>;
+
     fn push_stream(&mut self, content: Bytes, parent: u32) -> Result<(u32, Stream), TableError>;
+    fn push_incoming_body(
+        &mut self,
+        body: Box<dyn HostInputStream>,
+        parent: u32,
+    ) -> Result<(u32, Stream), TableError>;
+    fn push_outgoing_body(
+        &mut self,
+        body: Box<dyn HostOutputStream>,
+        parent: u32,
+    ) -> Result<(u32, Stream), TableError>;
     fn get_stream(&self, id: u32) -> Result<&Stream, TableError>;
     fn get_stream_mut(&mut self, id: u32) -> Result<&mut Box<Stream>, TableError>;
     fn delete_stream(&mut self, id: u32) -> Result<(), TableError>;
@@ -367,6 +555,23 @@ impl TableHttpExt for Table {
         self.delete::<Box<ActiveFields>>(id).map(|_old| ())
     }
 
+    fn push_response_outparam(
+        &mut self,
+        outparam: Box<ActiveResponseOutparam>,
+    ) -> Result<u32, TableError> {
+        self.push(Box::new(outparam))
+    }
+    fn get_response_outparam_mut(
+        &mut self,
+        id: u32,
+    ) -> Result<&mut Box<ActiveResponseOutparam>, TableError> {
+        self.get_mut::<Box<ActiveResponseOutparam>>(id)
+    }
+    fn delete_response_outparam(&mut self, id: u32) -> Result<(), TableError> {
+        self.delete::<Box<ActiveResponseOutparam>>(id)
+            .map(|_old| ())
+    }
+
     fn push_stream(&mut self, content: Bytes, parent: u32) -> Result<(u32, Stream), TableError> {
         let (a, b) = tokio::io::duplex(MAX_BUF_SIZE);
         let (_, write_stream) = tokio::io::split(a);
@@ -390,6 +595,32 @@ impl TableHttpExt for Table {
         let stream_id = self.push(Box::new(Box::new(stream)))?;
         Ok((stream_id, cloned_stream))
     }
+    fn push_incoming_body(
+        &mut self,
+        body: Box<dyn HostInputStream>,
+        parent: u32,
+    ) -> Result<(u32, Stream), TableError> {
+        // The guest can only read from an incoming body, so the outgoing side
+        // of the stream is always closed.
+        let output_id = self.push_output_stream(Box::new(ClosedOutputStream))?;
+        let input_id = self.push_input_stream(body)?;
+        let stream = Stream::new(input_id, output_id, parent);
+        let stream_id = self.push(Box::new(Box::new(stream.clone())))?;
+        Ok((stream_id, stream))
+    }
+    fn push_outgoing_body(
+        &mut self,
+        body: Box<dyn HostOutputStream>,
+        parent: u32,
+    ) -> Result<(u32, Stream), TableError> {
+        // The guest can only write to an outgoing body, so the incoming side
+        // of the stream is always closed.
+        let output_id = self.push_output_stream(body)?;
+        let input_id = self.push_input_stream(Box::new(ClosedInputStream))?;
+        let stream = Stream::new(input_id, output_id, parent);
+        let stream_id = self.push(Box::new(Box::new(stream.clone())))?;
+        Ok((stream_id, stream))
+    }
     fn get_stream(&self, id: u32) -> Result<&Stream, TableError> {
         self.get::<Box<Stream>>(id).map(|f| f.as_ref())
     }
@@ -414,6 +645,18 @@ mod test {
 
     #[test]
     fn instantiate() {
-        WasiHttpCtx::new().unwrap();
+        WasiHttpCtx::new();
+    }
+
+    #[test]
+    fn fields_round_trip_header_map() {
+        let mut headers = HeaderMap::new();
+        headers.append("content-type", HeaderValue::from_static("text/plain"));
+        headers.append("x-multi", HeaderValue::from_static("a"));
+        headers.append("x-multi", HeaderValue::from_static("b"));
+
+        let fields = ActiveFields::from(&headers);
+        assert_eq!(fields["x-multi"], vec![b"a".to_vec(), b"b".to_vec()]);
+        assert_eq!(fields.to_header_map().unwrap(), headers);
     }
 }
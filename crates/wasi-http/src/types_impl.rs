@@ -1,5 +1,8 @@
+use crate::body::{HyperOutgoingBody, OutgoingFrame};
 use crate::http_impl::WasiHttpViewExt;
-use crate::types::{ActiveFields, ActiveRequest, HttpRequest, TableHttpExt};
+use crate::types::{
+    ActiveFields, ActiveRequest, ActiveResponse, HttpRequest, HttpResponse, TableHttpExt,
+};
 use crate::wasi::http::types::{
     Error, Fields, FutureIncomingResponse, Headers, IncomingRequest, IncomingResponse,
     IncomingStream, Method, OutgoingRequest, OutgoingResponse, OutgoingStream, ResponseOutparam,
@@ -106,6 +109,21 @@ impl<T: WasiHttpView + WasiHttpViewExt> crate::wasi::http::types::Host for T {
         &mut self,
         stream_id: IncomingStream,
     ) -> wasmtime::Result<Option<Trailers>> {
+        if let Some(mut trailers) = self.http_ctx_mut().incoming_trailers.remove(&stream_id) {
+            // The trailers of an incoming request are sent before its body
+            // stream is closed, so they are available once the guest has read
+            // the body to the end.
+            return match trailers.try_recv() {
+                Ok(trailers) => {
+                    let id = self
+                        .table_mut()
+                        .push_fields(Box::new(ActiveFields::from(&trailers)))
+                        .context("[finish_incoming_stream] pushing trailers")?;
+                    Ok(Some(id))
+                }
+                Err(_) => Ok(None),
+            };
+        }
         for (_, stream) in self.http_ctx().streams.iter() {
             if stream_id == stream.incoming() {
                 let response = self
@@ -119,13 +137,70 @@ impl<T: WasiHttpView + WasiHttpViewExt> crate::wasi::http::types::Host for T {
     }
     async fn finish_outgoing_stream(
         &mut self,
-        _s: OutgoingStream,
-        _trailers: Option<Trailers>,
+        s: OutgoingStream,
+        trailers: Option<Trailers>,
     ) -> wasmtime::Result<()> {
-        bail!("unimplemented: finish_outgoing_stream")
+        // Outgoing response bodies are streamed, so finishing them ends the
+        // hyper body, sending its trailers.
+        if let Some(finisher) = self.http_ctx_mut().outgoing_finishers.remove(&s) {
+            let trailers = match trailers {
+                Some(t) => Some(
+                    self.table()
+                        .get_fields(t)
+                        .context("[finish_outgoing_stream] getting trailers")?
+                        .to_header_map()?,
+                ),
+                None => None,
+            };
+            // The receiving end is gone if the response was never sent or its
+            // connection was closed, in which case there is nothing to finish.
+            let _ = finisher.send(OutgoingFrame::Finish(trailers)).await;
+            return Ok(());
+        }
+
+        // Outgoing request bodies are buffered until the request is sent, so
+        // their trailers are recorded to be sent along with the request.
+        let request = self
+            .http_ctx()
+            .streams
+            .values()
+            .find(|stream| stream.outgoing() == s)
+            .map(|stream| stream.parent_id())
+            .ok_or_else(|| anyhow!("[finish_outgoing_stream] unknown stream: {s}"))?;
+        if let Some(t) = trailers {
+            self.table_mut()
+                .get_request_mut(request)
+                .context("[finish_outgoing_stream] getting request")?
+                .set_trailers(t);
+        }
+        Ok(())
     }
-    async fn drop_incoming_request(&mut self, _request: IncomingRequest) -> wasmtime::Result<()> {
-        bail!("unimplemented: drop_incoming_request")
+    async fn drop_incoming_request(&mut self, request: IncomingRequest) -> wasmtime::Result<()> {
+        let r = self
+            .table()
+            .get_request(request)
+            .context("[drop_incoming_request] getting request")?;
+
+        // Cleanup dependent resources
+        let body = r.body();
+        let headers = r.headers();
+        if let Some(b) = body {
+            if let Some(stream) = self.http_ctx_mut().streams.remove(&b) {
+                self.http_ctx_mut()
+                    .incoming_trailers
+                    .remove(&stream.incoming());
+            }
+            self.table_mut().delete_stream(b).ok();
+        }
+        if let Some(h) = headers {
+            self.table_mut().delete_fields(h).ok();
+        }
+
+        self.table_mut()
+            .delete_request(request)
+            .context("[drop_incoming_request] deleting request")?;
+
+        Ok(())
     }
     async fn drop_outgoing_request(&mut self, request: OutgoingRequest) -> wasmtime::Result<()> {
         let r = self
@@ -151,39 +226,73 @@ impl<T: WasiHttpView + WasiHttpViewExt> crate::wasi::http::types::Host for T {
     }
     async fn incoming_request_method(
         &mut self,
-        _request: IncomingRequest,
+        request: IncomingRequest,
     ) -> wasmtime::Result<Method> {
-        bail!("unimplemented: incoming_request_method")
+        let r = self
+            .table()
+            .get_request(request)
+            .context("[incoming_request_method] getting request")?;
+        Ok(r.method().clone())
     }
     async fn incoming_request_path_with_query(
         &mut self,
-        _request: IncomingRequest,
+        request: IncomingRequest,
     ) -> wasmtime::Result<Option<String>> {
-        bail!("unimplemented: incoming_request_path")
+        let r = self
+            .table()
+            .get_request(request)
+            .context("[incoming_request_path_with_query] getting request")?;
+        Ok(Some(r.path_with_query().to_string()))
     }
     async fn incoming_request_scheme(
         &mut self,
-        _request: IncomingRequest,
+        request: IncomingRequest,
     ) -> wasmtime::Result<Option<Scheme>> {
-        bail!("unimplemented: incoming_request_scheme")
+        let r = self
+            .table()
+            .get_request(request)
+            .context("[incoming_request_scheme] getting request")?;
+        Ok(r.scheme().clone())
     }
     async fn incoming_request_authority(
         &mut self,
-        _request: IncomingRequest,
+        request: IncomingRequest,
     ) -> wasmtime::Result<Option<String>> {
-        bail!("unimplemented: incoming_request_authority")
+        let r = self
+            .table()
+            .get_request(request)
+            .context("[incoming_request_authority] getting request")?;
+        Ok(match r.authority() {
+            "" => None,
+            authority => Some(authority.to_string()),
+        })
     }
     async fn incoming_request_headers(
         &mut self,
-        _request: IncomingRequest,
+        request: IncomingRequest,
     ) -> wasmtime::Result<Headers> {
-        bail!("unimplemented: incoming_request_headers")
+        let r = self
+            .table()
+            .get_request(request)
+            .context("[incoming_request_headers] getting request")?;
+        r.headers()
+            .ok_or_else(|| anyhow!("[incoming_request_headers] request has no headers"))
     }
     async fn incoming_request_consume(
         &mut self,
-        _request: IncomingRequest,
+        request: IncomingRequest,
     ) -> wasmtime::Result<Result<IncomingStream, ()>> {
-        bail!("unimplemented: incoming_request_consume")
+        let table = self.table();
+        let r = table
+            .get_request(request)
+            .context("[incoming_request_consume] getting request")?;
+        Ok(match r.body() {
+            Some(id) => Ok(table
+                .get_stream(id)
+                .context("[incoming_request_consume] getting body stream")?
+                .incoming()),
+            None => Err(()),
+        })
     }
     async fn new_outgoing_request(
         &mut self,
@@ -232,18 +341,60 @@ impl<T: WasiHttpView + WasiHttpViewExt> crate::wasi::http::types::Host for T {
             .context("[outgoing_request_write] getting stream")?;
         Ok(Ok(stream.outgoing()))
     }
-    async fn drop_response_outparam(
-        &mut self,
-        _response: ResponseOutparam,
-    ) -> wasmtime::Result<()> {
-        bail!("unimplemented: drop_response_outparam")
+    async fn drop_response_outparam(&mut self, response: ResponseOutparam) -> wasmtime::Result<()> {
+        self.table_mut()
+            .delete_response_outparam(response)
+            .context("[drop_response_outparam] deleting outparam")?;
+        Ok(())
     }
     async fn set_response_outparam(
         &mut self,
-        _outparam: ResponseOutparam,
-        _response: Result<OutgoingResponse, Error>,
+        outparam: ResponseOutparam,
+        response: Result<OutgoingResponse, Error>,
     ) -> wasmtime::Result<Result<(), ()>> {
-        bail!("unimplemented: set_response_outparam")
+        let sender = match self
+            .table_mut()
+            .get_response_outparam_mut(outparam)
+            .context("[set_response_outparam] getting outparam")?
+            .take_sender()
+        {
+            Some(sender) => sender,
+            // The response has already been set.
+            None => return Ok(Err(())),
+        };
+
+        let result = match response {
+            Ok(id) => {
+                let r = self
+                    .table()
+                    .get_response(id)
+                    .context("[set_response_outparam] getting response")?;
+                let mut builder = hyper::Response::builder().status(r.status());
+                if let Some(headers) = r.headers() {
+                    let headers = self
+                        .table()
+                        .get_fields(headers)
+                        .context("[set_response_outparam] getting headers")?;
+                    for (name, values) in headers.iter() {
+                        for value in values {
+                            builder = builder.header(name, value.clone());
+                        }
+                    }
+                }
+                let body = self
+                    .http_ctx_mut()
+                    .outgoing_bodies
+                    .remove(&id)
+                    .unwrap_or_else(HyperOutgoingBody::empty);
+                match builder.body(body) {
+                    Ok(response) => Ok(response),
+                    Err(_) => return Ok(Err(())),
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        Ok(sender.send(result).map_err(|_| ()))
     }
     async fn drop_incoming_response(&mut self, response: IncomingResponse) -> wasmtime::Result<()> {
         let r = self
@@ -277,11 +428,24 @@ impl<T: WasiHttpView + WasiHttpViewExt> crate::wasi::http::types::Host for T {
             .context("[drop_incoming_response] deleting response")?;
         Ok(())
     }
-    async fn drop_outgoing_response(
-        &mut self,
-        _response: OutgoingResponse,
-    ) -> wasmtime::Result<()> {
-        bail!("unimplemented: drop_outgoing_response")
+    async fn drop_outgoing_response(&mut self, response: OutgoingResponse) -> wasmtime::Result<()> {
+        let r = self
+            .table()
+            .get_response(response)
+            .context("[drop_outgoing_response] getting response")?;
+
+        // Cleanup dependent resources. The body stream itself is owned by the
+        // guest, which may still be writing to it after the response is sent.
+        let headers = r.headers();
+        if let Some(h) = headers {
+            self.table_mut().delete_fields(h).ok();
+        }
+        self.http_ctx_mut().outgoing_bodies.remove(&response);
+
+        self.table_mut()
+            .delete_response(response)
+            .context("[drop_outgoing_response] deleting response")?;
+        Ok(())
     }
     async fn incoming_response_status(
         &mut self,
@@ -301,38 +465,72 @@ impl<T: WasiHttpView + WasiHttpViewExt> crate::wasi::http::types::Host for T {
             .table()
             .get_response(response)
             .context("[incoming_response_headers] getting response")?;
-        Ok(r.headers().unwrap_or(0 as Headers))
+        r.headers()
+            .ok_or_else(|| anyhow!("[incoming_response_headers] response has no headers"))
     }
     async fn incoming_response_consume(
         &mut self,
         response: IncomingResponse,
     ) -> wasmtime::Result<Result<IncomingStream, ()>> {
-        let table = self.table_mut();
+        let table = self.table();
         let r = table
             .get_response(response)
             .context("[incoming_response_consume] getting response")?;
-        Ok(Ok(r
-            .body()
-            .map(|id| {
-                table
-                    .get_stream(id)
-                    .map(|stream| stream.incoming())
-                    .expect("[incoming_response_consume] response body stream")
-            })
-            .unwrap_or(0 as IncomingStream)))
+        Ok(match r.body() {
+            Some(id) => Ok(table
+                .get_stream(id)
+                .context("[incoming_response_consume] getting body stream")?
+                .incoming()),
+            None => Err(()),
+        })
     }
     async fn new_outgoing_response(
         &mut self,
-        _status_code: StatusCode,
-        _headers: Headers,
+        status_code: StatusCode,
+        headers: Headers,
     ) -> wasmtime::Result<OutgoingResponse> {
-        bail!("unimplemented: new_outgoing_response")
+        let mut response = ActiveResponse::new();
+        response.status = status_code;
+        response.set_headers(headers);
+        let id = self
+            .table_mut()
+            .push_response(Box::new(response))
+            .context("[new_outgoing_response] pushing response")?;
+        Ok(id)
     }
     async fn outgoing_response_write(
         &mut self,
-        _response: OutgoingResponse,
+        response: OutgoingResponse,
     ) -> wasmtime::Result<Result<OutgoingStream, ()>> {
-        bail!("unimplemented: outgoing_response_write")
+        let r = self
+            .table()
+            .get_response(response)
+            .context("[outgoing_response_write] getting response")?;
+        let stream_id = match r.body() {
+            Some(id) => id,
+            None => {
+                let (stream, body) = crate::body::outgoing();
+                let finisher = stream.finisher();
+                let (id, stream) = self
+                    .table_mut()
+                    .push_outgoing_body(Box::new(stream), response)
+                    .context("[outgoing_response_write] pushing body")?;
+                let ctx = self.http_ctx_mut();
+                ctx.outgoing_bodies.insert(response, body);
+                ctx.outgoing_finishers.insert(stream.outgoing(), finisher);
+                ctx.streams.insert(id, stream);
+                self.table_mut()
+                    .get_response_mut(response)
+                    .context("[outgoing_response_write] getting response")?
+                    .set_body(id);
+                id
+            }
+        };
+        let stream = self
+            .table()
+            .get_stream(stream_id)
+            .context("[outgoing_response_write] getting stream")?;
+        Ok(Ok(stream.outgoing()))
     }
     async fn drop_future_incoming_response(
         &mut self,
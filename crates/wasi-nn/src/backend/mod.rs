@@ -2,8 +2,12 @@
 //! this crate. The `Box<dyn ...>` types returned by these interfaces allow
 //! implementations to maintain backend-specific state between calls.
 
+#[cfg(feature = "onnx")]
+mod onnx;
 mod openvino;
 
+#[cfg(feature = "onnx")]
+use self::onnx::OnnxBackend;
 use self::openvino::OpenvinoBackend;
 use crate::wit::types::{ExecutionTarget, Tensor};
 use crate::{ExecutionContext, Graph};
@@ -13,7 +17,11 @@ use wiggle::GuestError;
 
 /// Return a list of all available backend frameworks.
 pub fn list() -> Vec<(BackendKind, Box<dyn Backend>)> {
-    vec![(BackendKind::OpenVINO, Box::new(OpenvinoBackend::default()))]
+    vec![
+        (BackendKind::OpenVINO, Box::new(OpenvinoBackend::default())),
+        #[cfg(feature = "onnx")]
+        (BackendKind::Onnx, Box::new(OnnxBackend::default())),
+    ]
 }
 
 /// A [Backend] contains the necessary state to load [Graph]s.
@@ -65,12 +73,53 @@ pub enum BackendError {
 #[derive(Hash, PartialEq, Debug, Eq, Clone, Copy)]
 pub enum BackendKind {
     OpenVINO,
+    Onnx,
 }
+impl BackendKind {
+    /// Guess the kind of backend able to load a model from its `builders`,
+    /// by looking at their contents.
+    ///
+    /// OpenVINO models are made of two buffers, the first of which is an XML
+    /// description of the network, while ONNX models are a single serialized
+    /// `ModelProto` message, whose first field is always its IR version.
+    pub fn detect(builders: &[&[u8]]) -> Option<BackendKind> {
+        match builders {
+            [xml, _weights] => {
+                let xml = xml.strip_prefix(b"\xef\xbb\xbf").unwrap_or(*xml);
+                match xml.iter().find(|b| !b.is_ascii_whitespace()) {
+                    Some(b'<') => Some(BackendKind::OpenVINO),
+                    _ => None,
+                }
+            }
+            [proto] if proto.first() == Some(&0x08) => Some(BackendKind::Onnx),
+            _ => None,
+        }
+    }
+
+    /// Guess the kind of backend able to load the model stored in the
+    /// directory `path`, from the names of the files it contains.
+    pub fn detect_dir(path: &Path) -> Option<BackendKind> {
+        [BackendKind::OpenVINO, BackendKind::Onnx]
+            .into_iter()
+            .find(|kind| kind.model_files().iter().all(|f| path.join(f).is_file()))
+    }
+
+    /// The files, within a model's directory, that a [BackendFromDir] of this
+    /// kind loads the model from.
+    pub fn model_files(&self) -> &'static [&'static str] {
+        match self {
+            BackendKind::OpenVINO => &["model.xml", "model.bin"],
+            BackendKind::Onnx => &["model.onnx"],
+        }
+    }
+}
+
 impl FromStr for BackendKind {
     type Err = BackendKindParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "openvino" => Ok(BackendKind::OpenVINO),
+            "onnx" => Ok(BackendKind::Onnx),
             _ => Err(BackendKindParseError(s.into())),
         }
     }
@@ -83,3 +132,23 @@ impl fmt::Display for BackendKindParseError {
     }
 }
 impl Error for BackendKindParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect() {
+        let xml: &[u8] = b"\xef\xbb\xbf\n  <?xml version=\"1.0\"?><net></net>";
+        let weights: &[u8] = b"weights";
+        let onnx: &[u8] = b"\x08\x07\x12\x08";
+        assert_eq!(
+            BackendKind::detect(&[xml, weights]),
+            Some(BackendKind::OpenVINO)
+        );
+        assert_eq!(BackendKind::detect(&[weights, xml]), None);
+        assert_eq!(BackendKind::detect(&[onnx]), Some(BackendKind::Onnx));
+        assert_eq!(BackendKind::detect(&[xml]), None);
+        assert_eq!(BackendKind::detect(&[]), None);
+    }
+}
@@ -0,0 +1,290 @@
+//! Implements a `wasi-nn` [`Backend`] for ONNX models using `tract`, a
+//! pure-Rust inference engine which needs no native libraries.
+
+use super::{Backend, BackendError, BackendExecutionContext, BackendFromDir, BackendGraph};
+use crate::wit::types::{ExecutionTarget, Tensor, TensorType};
+use crate::{ExecutionContext, Graph};
+use anyhow::{anyhow, bail};
+use std::path::Path;
+use std::sync::Arc;
+use tract_onnx::prelude::*;
+
+type Plan = SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel>;
+
+/// The type and shape of each input of a model.
+type InputShapes = Vec<(DatumType, Vec<usize>)>;
+
+#[derive(Default)]
+pub(crate) struct OnnxBackend;
+
+impl Backend for OnnxBackend {
+    fn name(&self) -> &str {
+        "onnx"
+    }
+
+    fn load(&mut self, builders: &[&[u8]], target: ExecutionTarget) -> Result<Graph, BackendError> {
+        if builders.len() != 1 {
+            return Err(BackendError::InvalidNumberOfBuilders(1, builders.len()));
+        }
+        if target != ExecutionTarget::Cpu {
+            return Err(anyhow!("the ONNX backend only supports CPU execution").into());
+        }
+
+        // The model is only parsed here; it's optimized into a runnable plan
+        // once the shapes of its inputs are known (see `OnnxExecutionContext`).
+        let model = tract_onnx::onnx().model_for_read(&mut &builders[0][..])?;
+        let box_: Box<dyn BackendGraph> = Box::new(OnnxGraph(Arc::new(model)));
+        Ok(box_.into())
+    }
+
+    fn as_dir_loadable(&mut self) -> Option<&mut dyn BackendFromDir> {
+        Some(self)
+    }
+}
+
+impl BackendFromDir for OnnxBackend {
+    fn load_from_dir(
+        &mut self,
+        path: &Path,
+        target: ExecutionTarget,
+    ) -> Result<Graph, BackendError> {
+        let model = std::fs::read(path.join("model.onnx")).map_err(anyhow::Error::from)?;
+        self.load(&[&model], target)
+    }
+}
+
+struct OnnxGraph(Arc<InferenceModel>);
+
+impl BackendGraph for OnnxGraph {
+    fn init_execution_context(&self) -> Result<ExecutionContext, BackendError> {
+        let box_: Box<dyn BackendExecutionContext> = Box::new(OnnxExecutionContext {
+            model: self.0.clone(),
+            inputs: Vec::new(),
+            plan: None,
+            outputs: Vec::new(),
+        });
+        Ok(box_.into())
+    }
+}
+
+struct OnnxExecutionContext {
+    model: Arc<InferenceModel>,
+    inputs: Vec<Option<tract_onnx::prelude::Tensor>>,
+    /// The plan used by the last call to `compute`, along with the types and
+    /// shapes of the inputs it was optimized for. It's reused as long as the
+    /// inputs keep the same types and shapes.
+    plan: Option<(InputShapes, Plan)>,
+    /// The outputs of the last call to `compute`. They're kept as tensors
+    /// rather than `TValue`s, which may be reference-counted with `Rc`.
+    outputs: Vec<tract_onnx::prelude::Tensor>,
+}
+
+impl OnnxExecutionContext {
+    fn plan_for(&mut self, inputs: InputShapes) -> TractResult<&Plan> {
+        let stale = match &self.plan {
+            Some((planned, _)) => *planned != inputs,
+            None => true,
+        };
+        if stale {
+            let mut model = (*self.model).clone();
+            for (index, (dt, shape)) in inputs.iter().enumerate() {
+                model =
+                    model.with_input_fact(index, InferenceFact::dt_shape(*dt, shape.clone()))?;
+            }
+            let plan = model.into_optimized()?.into_runnable()?;
+            self.plan = Some((inputs, plan));
+        }
+        Ok(&self.plan.as_ref().unwrap().1)
+    }
+}
+
+impl BackendExecutionContext for OnnxExecutionContext {
+    fn set_input(&mut self, index: u32, tensor: &Tensor) -> Result<(), BackendError> {
+        let index = index as usize;
+        let num_inputs = self.model.input_outlets()?.len();
+        if index >= num_inputs {
+            return Err(
+                anyhow!("invalid input index {index}; the model has {num_inputs} inputs").into(),
+            );
+        }
+        if self.inputs.len() < num_inputs {
+            self.inputs.resize(num_inputs, None);
+        }
+        self.inputs[index] = Some(to_tract_tensor(tensor)?);
+        Ok(())
+    }
+
+    fn compute(&mut self) -> Result<(), BackendError> {
+        let num_inputs = self.model.input_outlets()?.len();
+        let mut inputs = TVec::new();
+        let mut shapes = Vec::new();
+        for index in 0..num_inputs {
+            let tensor = match self.inputs.get(index) {
+                Some(Some(tensor)) => tensor.clone(),
+                _ => return Err(anyhow!("input {index} has not been set").into()),
+            };
+            shapes.push((tensor.datum_type(), tensor.shape().to_vec()));
+            inputs.push(tensor.into());
+        }
+        let outputs = self.plan_for(shapes)?.run(inputs)?;
+        self.outputs = outputs.into_iter().map(|o| o.into_tensor()).collect();
+        Ok(())
+    }
+
+    fn get_output(&mut self, index: u32, destination: &mut [u8]) -> Result<u32, BackendError> {
+        let output = self
+            .outputs
+            .get(index as usize)
+            .ok_or_else(|| anyhow!("no output at index {index}; has `compute` been called?"))?;
+        let bytes = to_bytes(output)?;
+        if bytes.len() > destination.len() {
+            return Err(BackendError::NotEnoughMemory(bytes.len()));
+        }
+        destination[..bytes.len()].copy_from_slice(&bytes);
+        Ok(bytes.len() as u32)
+    }
+}
+
+/// Convert a wasi-nn tensor, whose data is little-endian, into a `tract`
+/// tensor.
+fn to_tract_tensor(tensor: &Tensor) -> anyhow::Result<tract_onnx::prelude::Tensor> {
+    let shape = tensor
+        .dimensions
+        .iter()
+        .map(|&d| d as usize)
+        .collect::<Vec<_>>();
+    let data = &tensor.data;
+    Ok(match tensor.tensor_type {
+        TensorType::Fp16 => {
+            let values = data
+                .chunks_exact(2)
+                .map(|b| f16::from_le_bytes([b[0], b[1]]))
+                .collect::<Vec<_>>();
+            tract_onnx::prelude::Tensor::from_shape(&shape, values.as_slice())?
+        }
+        TensorType::Fp32 => {
+            let values = data
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect::<Vec<_>>();
+            tract_onnx::prelude::Tensor::from_shape(&shape, values.as_slice())?
+        }
+        TensorType::U8 => tract_onnx::prelude::Tensor::from_shape(&shape, data.as_slice())?,
+        TensorType::I32 => {
+            let values = data
+                .chunks_exact(4)
+                .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect::<Vec<_>>();
+            tract_onnx::prelude::Tensor::from_shape(&shape, values.as_slice())?
+        }
+        TensorType::Bf16 => bail!("the ONNX backend does not support bf16 tensors"),
+    })
+}
+
+/// Convert a `tract` tensor into the little-endian bytes returned to the
+/// guest.
+fn to_bytes(tensor: &tract_onnx::prelude::Tensor) -> anyhow::Result<Vec<u8>> {
+    Ok(match tensor.datum_type() {
+        DatumType::F16 => le_bytes(tensor.as_slice::<f16>()?, |x| x.to_le_bytes()),
+        DatumType::F32 => le_bytes(tensor.as_slice::<f32>()?, |x| x.to_le_bytes()),
+        DatumType::F64 => le_bytes(tensor.as_slice::<f64>()?, |x| x.to_le_bytes()),
+        DatumType::U8 => tensor.as_slice::<u8>()?.to_vec(),
+        DatumType::I8 => le_bytes(tensor.as_slice::<i8>()?, |x| x.to_le_bytes()),
+        DatumType::I32 => le_bytes(tensor.as_slice::<i32>()?, |x| x.to_le_bytes()),
+        DatumType::I64 => le_bytes(tensor.as_slice::<i64>()?, |x| x.to_le_bytes()),
+        DatumType::Bool => tensor
+            .as_slice::<bool>()?
+            .iter()
+            .map(|&b| b as u8)
+            .collect(),
+        dt => bail!("the ONNX backend cannot return tensors of type {dt:?}"),
+    })
+}
+
+fn le_bytes<T: Copy, const N: usize>(values: &[T], f: impl Fn(T) -> [u8; N]) -> Vec<u8> {
+    values.iter().flat_map(|&x| f(x)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A model with a single `x: f32[1, 4]` input which computes
+    /// `x + [1, 2, 3, 4]`.
+    fn model_dir() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/add")
+    }
+
+    fn f32_tensor(dimensions: &[u32], values: &[f32]) -> Tensor {
+        Tensor {
+            dimensions: dimensions.to_vec(),
+            tensor_type: TensorType::Fp32,
+            data: values.iter().flat_map(|x| x.to_le_bytes()).collect(),
+        }
+    }
+
+    fn output_f32(ctx: &mut ExecutionContext, index: u32) -> Vec<f32> {
+        let mut buffer = vec![0; 1024];
+        let len = ctx.get_output(index, &mut buffer).unwrap();
+        buffer[..len as usize]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn compute() {
+        let graph = OnnxBackend
+            .load_from_dir(&model_dir(), ExecutionTarget::Cpu)
+            .unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+
+        ctx.set_input(0, &f32_tensor(&[1, 4], &[10.0, 20.0, 30.0, 40.0]))
+            .unwrap();
+        ctx.compute().unwrap();
+        assert_eq!(output_f32(&mut ctx, 0), [11.0, 22.0, 33.0, 44.0]);
+
+        // The context may be reused with new inputs.
+        ctx.set_input(0, &f32_tensor(&[1, 4], &[-1.0, -2.0, -3.0, -4.0]))
+            .unwrap();
+        ctx.compute().unwrap();
+        assert_eq!(output_f32(&mut ctx, 0), [0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn load_from_bytes() {
+        let bytes = std::fs::read(model_dir().join("model.onnx")).unwrap();
+        let graph = OnnxBackend.load(&[&bytes], ExecutionTarget::Cpu).unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+        ctx.set_input(0, &f32_tensor(&[1, 4], &[0.5; 4])).unwrap();
+        ctx.compute().unwrap();
+        assert_eq!(output_f32(&mut ctx, 0), [1.5, 2.5, 3.5, 4.5]);
+    }
+
+    #[test]
+    fn errors() {
+        let bytes = std::fs::read(model_dir().join("model.onnx")).unwrap();
+        assert!(matches!(
+            OnnxBackend.load(&[&bytes, &bytes], ExecutionTarget::Cpu),
+            Err(BackendError::InvalidNumberOfBuilders(1, 2))
+        ));
+        assert!(OnnxBackend.load(&[&bytes], ExecutionTarget::Gpu).is_err());
+        assert!(OnnxBackend
+            .load(&[b"not a model"], ExecutionTarget::Cpu)
+            .is_err());
+
+        let graph = OnnxBackend.load(&[&bytes], ExecutionTarget::Cpu).unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+        assert!(ctx.compute().is_err());
+        assert!(ctx.set_input(1, &f32_tensor(&[1, 4], &[0.0; 4])).is_err());
+
+        ctx.set_input(0, &f32_tensor(&[1, 4], &[0.0; 4])).unwrap();
+        ctx.compute().unwrap();
+        let mut small = [0; 4];
+        assert!(matches!(
+            ctx.get_output(0, &mut small),
+            Err(BackendError::NotEnoughMemory(16))
+        ));
+        assert!(ctx.get_output(1, &mut [0; 64]).is_err());
+    }
+}
@@ -53,6 +53,16 @@ impl WasiNnCtx {
             executions: Table::default(),
         }
     }
+
+    /// Make a new context which loads graphs by name from `registry`, e.g. a
+    /// [`NamedStoreRegistry`](crate::NamedStoreRegistry), and loads graphs
+    /// from bytes with all of the available backends.
+    pub fn with_registry(registry: impl GraphRegistry + 'static) -> Self {
+        Self::new(
+            crate::backend::list().into_iter().collect(),
+            Box::new(registry),
+        )
+    }
 }
 
 /// Possible errors while interacting with [WasiNnCtx].
@@ -70,7 +80,7 @@ pub enum WasiNnError {
 pub enum UsageError {
     #[error("Invalid context; has the load function been called?")]
     InvalidContext,
-    #[error("Only OpenVINO's IR is currently supported, passed encoding: {0:?}")]
+    #[error("No backend is available for the graph encoding: {0:?}")]
     InvalidEncoding(GraphEncoding),
     #[error("OpenVINO expects only two buffers (i.e. [ir, weights]), passed: {0}")]
     InvalidNumberOfBuilders(u32),
@@ -82,6 +92,14 @@ pub enum UsageError {
     NotEnoughMemory(u32),
     #[error("No graph found with name: {0}")]
     NotFound(String),
+    #[error("Unable to detect the encoding of the graph")]
+    UndetectableEncoding,
+    #[error("No backend is available for the detected graph encoding: {0:?}")]
+    UnavailableBackend(BackendKind),
+    #[error(
+        "Loading graph {name} of {size} bytes would exceed the model memory limit of {limit} bytes"
+    )]
+    ModelMemoryLimitExceeded { name: String, size: u64, limit: u64 },
 }
 
 pub(crate) type WasiNnResult<T> = std::result::Result<T, WasiNnError>;
@@ -134,8 +152,8 @@ mod test {
     fn example() {
         struct FakeRegistry;
         impl GraphRegistry for FakeRegistry {
-            fn get_mut(&mut self, _: &str) -> Option<&mut Graph> {
-                None
+            fn get_mut(&mut self, _: &str) -> anyhow::Result<Option<&mut Graph>> {
+                Ok(None)
             }
         }
 
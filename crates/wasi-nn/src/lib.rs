@@ -3,7 +3,7 @@ mod ctx;
 mod registry;
 
 pub use ctx::{preload, WasiNnCtx};
-pub use registry::{GraphRegistry, InMemoryRegistry};
+pub use registry::{GraphRegistry, InMemoryRegistry, NamedRegistry, NamedStoreRegistry};
 pub mod wit;
 pub mod witx;
 
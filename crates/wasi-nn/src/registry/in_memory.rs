@@ -37,7 +37,7 @@ impl InMemoryRegistry {
 }
 
 impl GraphRegistry for InMemoryRegistry {
-    fn get_mut(&mut self, name: &str) -> Option<&mut Graph> {
-        self.0.get_mut(name)
+    fn get_mut(&mut self, name: &str) -> anyhow::Result<Option<&mut Graph>> {
+        Ok(self.0.get_mut(name))
     }
 }
@@ -3,14 +3,20 @@
 //! A [`GraphRegistry`] is place to store backend graphs so they can be loaded
 //! by name. This API does not mandate how a graph is loaded or how it must be
 //! stored--it could be stored remotely and rematerialized when needed, e.g. A
-//! naive in-memory implementation, [`InMemoryRegistry`] is provided for use
-//! with the Wasmtime CLI.
+//! naive in-memory implementation, [`InMemoryRegistry`], is provided, while
+//! [`NamedRegistry`] lazily loads models from disk and shares them between the
+//! stores of multiple tenants; the Wasmtime CLI uses the latter.
 
 mod in_memory;
+mod named;
 
 use crate::Graph;
 pub use in_memory::InMemoryRegistry;
+pub use named::{NamedRegistry, NamedStoreRegistry};
 
 pub trait GraphRegistry: Send + Sync {
-    fn get_mut(&mut self, name: &str) -> Option<&mut Graph>;
+    /// Look up the graph called `name`, returning `None` if there is no such
+    /// graph. Registries which materialize graphs on demand return an error
+    /// if doing so fails.
+    fn get_mut(&mut self, name: &str) -> anyhow::Result<Option<&mut Graph>>;
 }
@@ -0,0 +1,269 @@
+//! Implement a [`GraphRegistry`] of named models stored on disk, which are
+//! loaded lazily and shared between stores.
+
+use super::{Graph, GraphRegistry};
+use crate::backend::{self, Backend, BackendKind};
+use crate::ctx::UsageError;
+use crate::wit::types::ExecutionTarget;
+use anyhow::{anyhow, bail, Context};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// A registry mapping names to models stored in directories on disk.
+///
+/// A model is only loaded the first time a store asks for it by name. The
+/// loaded graph is then cached, so that every other store asking for the same
+/// name shares it rather than parsing the model again. Clones of a
+/// `NamedRegistry` share their models and cached graphs.
+///
+/// Stores don't use a `NamedRegistry` directly but rather a
+/// [`NamedStoreRegistry`], created with [`NamedRegistry::store_registry`],
+/// which tracks the graphs loaded by that store and can cap the total size of
+/// the models it loads.
+#[derive(Clone)]
+pub struct NamedRegistry(Arc<RwLock<HashMap<String, Arc<Model>>>>);
+
+struct Model {
+    path: PathBuf,
+    kind: BackendKind,
+    /// The total size, in bytes, of the files the model is loaded from.
+    size: u64,
+    slot: Mutex<Slot>,
+}
+
+/// The backend instance used to load a model and, once it's loaded, the
+/// model's graph. Each model has its own backend so that loading one model
+/// never waits on another.
+struct Slot {
+    backend: Box<dyn Backend>,
+    graph: Option<Graph>,
+}
+
+impl NamedRegistry {
+    /// Create an empty registry, which loads models with all of the available
+    /// backends.
+    pub fn new() -> Self {
+        Self(Arc::default())
+    }
+
+    /// Register the model stored in the directory `path` as `name`.
+    ///
+    /// The model's encoding is detected from the names of the files in the
+    /// directory: `model.xml` and `model.bin` for OpenVINO, or `model.onnx`
+    /// for ONNX. The model isn't loaded until it's first used.
+    pub fn register(&self, name: impl Into<String>, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let kind = BackendKind::detect_dir(path).ok_or_else(|| {
+            anyhow!(
+                "unable to detect the encoding of the model in: {}",
+                path.display()
+            )
+        })?;
+        self.insert(name.into(), path, kind)
+    }
+
+    /// Register the model stored in the directory `path` as `name`, using the
+    /// backend called `encoding`, e.g. `openvino`, to load it.
+    pub fn register_with_encoding(
+        &self,
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+        encoding: &str,
+    ) -> anyhow::Result<()> {
+        self.insert(name.into(), path.as_ref(), encoding.parse()?)
+    }
+
+    fn insert(&self, name: String, path: &Path, kind: BackendKind) -> anyhow::Result<()> {
+        if !path.is_dir() {
+            bail!(
+                "model directory is not a valid directory: {}",
+                path.display()
+            );
+        }
+        let backend = match backend::list().into_iter().find(|(k, _)| *k == kind) {
+            Some((_, backend)) => backend,
+            None => bail!("unsupported backend: {:?}", kind),
+        };
+        let mut size = 0;
+        for file in kind.model_files() {
+            let file = path.join(file);
+            let metadata = file
+                .metadata()
+                .with_context(|| format!("failed to read model file: {}", file.display()))?;
+            size += metadata.len();
+        }
+        let model = Model {
+            path: path.to_owned(),
+            kind,
+            size,
+            slot: Mutex::new(Slot {
+                backend,
+                graph: None,
+            }),
+        };
+        self.0.write().unwrap().insert(name, Arc::new(model));
+        Ok(())
+    }
+
+    /// Load the model registered as `name` now, rather than when a store
+    /// first asks for it.
+    pub fn preload(&self, name: &str) -> anyhow::Result<()> {
+        let model = self
+            .model(name)
+            .ok_or_else(|| UsageError::NotFound(name.to_string()))?;
+        self.load(&model)?;
+        Ok(())
+    }
+
+    /// Create the registry used by a single store.
+    ///
+    /// If `memory_limit` is given then the store may only load models whose
+    /// files add up to at most that many bytes; asking for any more fails.
+    pub fn store_registry(&self, memory_limit: Option<u64>) -> NamedStoreRegistry {
+        NamedStoreRegistry {
+            registry: self.clone(),
+            memory_limit,
+            memory_used: 0,
+            graphs: HashMap::new(),
+        }
+    }
+
+    fn model(&self, name: &str) -> Option<Arc<Model>> {
+        self.0.read().unwrap().get(name).cloned()
+    }
+
+    fn load(&self, model: &Model) -> anyhow::Result<Graph> {
+        // Only this model's slot is locked while it's loaded: stores racing to
+        // load the same model wait for a single load rather than repeating it,
+        // while other models can be loaded at the same time.
+        let mut slot = model.slot.lock().unwrap();
+        if let Some(graph) = &slot.graph {
+            return Ok(graph.clone());
+        }
+        let backend = slot
+            .backend
+            .as_dir_loadable()
+            .ok_or_else(|| anyhow!("{:?} does not support directory loading", model.kind))?;
+        let loaded = backend.load_from_dir(&model.path, ExecutionTarget::Cpu)?;
+        slot.graph = Some(loaded.clone());
+        Ok(loaded)
+    }
+}
+
+impl Default for NamedRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The view of a [`NamedRegistry`] used by a single store.
+pub struct NamedStoreRegistry {
+    registry: NamedRegistry,
+    memory_limit: Option<u64>,
+    memory_used: u64,
+    graphs: HashMap<String, Graph>,
+}
+
+impl NamedStoreRegistry {
+    /// Return the total size, in bytes, of the models loaded by this store.
+    pub fn memory_used(&self) -> u64 {
+        self.memory_used
+    }
+}
+
+impl GraphRegistry for NamedStoreRegistry {
+    fn get_mut(&mut self, name: &str) -> anyhow::Result<Option<&mut Graph>> {
+        if !self.graphs.contains_key(name) {
+            let model = match self.registry.model(name) {
+                Some(model) => model,
+                None => return Ok(None),
+            };
+            let memory_used = self.memory_used + model.size;
+            if let Some(limit) = self.memory_limit {
+                if memory_used > limit {
+                    return Err(UsageError::ModelMemoryLimitExceeded {
+                        name: name.to_string(),
+                        size: model.size,
+                        limit,
+                    }
+                    .into());
+                }
+            }
+            let graph = self.registry.load(&model)?;
+            self.memory_used = memory_used;
+            self.graphs.insert(name.to_string(), graph);
+        }
+        Ok(self.graphs.get_mut(name))
+    }
+}
+
+#[cfg(all(test, feature = "onnx"))]
+mod tests {
+    use super::*;
+
+    fn model_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/add")
+    }
+
+    fn model_size() -> u64 {
+        model_dir().join("model.onnx").metadata().unwrap().len()
+    }
+
+    #[test]
+    fn graphs_are_shared_between_stores() {
+        let registry = NamedRegistry::new();
+        registry.register("add", model_dir()).unwrap();
+
+        let mut a = registry.store_registry(None);
+        let mut b = registry.clone().store_registry(None);
+        let graph_a = a.get_mut("add").unwrap().unwrap().clone();
+        let graph_b = b.get_mut("add").unwrap().unwrap().clone();
+        assert!(Arc::ptr_eq(&graph_a.0, &graph_b.0));
+
+        assert!(a.get_mut("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn memory_limit() {
+        let registry = NamedRegistry::new();
+        registry.register("add", model_dir()).unwrap();
+        registry
+            .register_with_encoding("add2", model_dir(), "onnx")
+            .unwrap();
+        registry.preload("add").unwrap();
+
+        let mut store = registry.store_registry(Some(model_size()));
+        assert!(store.get_mut("add").unwrap().is_some());
+        assert_eq!(store.memory_used(), model_size());
+
+        // Models already loaded by the store aren't counted twice...
+        assert!(store.get_mut("add").unwrap().is_some());
+        // ...but loading another one would exceed the limit.
+        let err = store.get_mut("add2").err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<UsageError>(),
+            Some(UsageError::ModelMemoryLimitExceeded { .. })
+        ));
+        assert_eq!(store.memory_used(), model_size());
+
+        // Limits apply per store.
+        let mut other = registry.store_registry(Some(model_size()));
+        assert!(other.get_mut("add2").unwrap().is_some());
+    }
+
+    #[test]
+    fn register_errors() {
+        let registry = NamedRegistry::new();
+        assert!(registry
+            .register("none", model_dir().join("model.onnx"))
+            .is_err());
+        assert!(registry
+            .register("none", env!("CARGO_MANIFEST_DIR"))
+            .is_err());
+        assert!(registry
+            .register_with_encoding("none", model_dir(), "tensorflow")
+            .is_err());
+        assert!(registry.preload("none").is_err());
+    }
+}
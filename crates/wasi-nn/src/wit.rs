@@ -55,7 +55,7 @@ impl gen::graph::Host for WasiNnCtx {
         &mut self,
         name: String,
     ) -> wasmtime::Result<Result<gen::graph::Graph, gen::errors::Error>> {
-        if let Some(graph) = self.registry.get_mut(&name) {
+        if let Some(graph) = self.registry.get_mut(&name)? {
             let graph_id = self.graphs.insert(graph.clone().into());
             Ok(Ok(graph_id))
         } else {
@@ -142,6 +142,7 @@ impl TryFrom<gen::graph::GraphEncoding> for crate::backend::BackendKind {
     fn try_from(value: gen::graph::GraphEncoding) -> Result<Self, Self::Error> {
         match value {
             gen::graph::GraphEncoding::Openvino => Ok(crate::backend::BackendKind::OpenVINO),
+            gen::graph::GraphEncoding::Onnx => Ok(crate::backend::BackendKind::Onnx),
             _ => Err(UsageError::InvalidEncoding(value.into())),
         }
     }
@@ -13,6 +13,7 @@
 //!
 //! [`types`]: crate::wit::types
 
+use crate::backend::{BackendError, BackendKind};
 use crate::ctx::{UsageError, WasiNnCtx, WasiNnError, WasiNnResult as Result};
 use wiggle::GuestPtr;
 
@@ -58,21 +59,28 @@ impl<'a> gen::wasi_ephemeral_nn::WasiEphemeralNn for WasiNnCtx {
         encoding: gen::types::GraphEncoding,
         target: gen::types::ExecutionTarget,
     ) -> Result<gen::types::Graph> {
-        let graph = if let Some(backend) = self.backends.get_mut(&encoding.try_into()?) {
-            // Retrieve all of the "builder lists" from the Wasm memory (see
-            // $graph_builder_array) as slices for a backend to operate on.
-            let mut slices = vec![];
-            for builder in builders.iter() {
-                let slice = builder?
-                    .read()?
-                    .as_slice()?
-                    .expect("cannot use with shared memories; see https://github.com/bytecodealliance/wasmtime/issues/5235 (TODO)");
-                slices.push(slice);
+        // Retrieve all of the "builder lists" from the Wasm memory (see
+        // $graph_builder_array) as slices for a backend to operate on.
+        let mut slices = vec![];
+        for builder in builders.iter() {
+            let slice = builder?
+                .read()?
+                .as_slice()?
+                .expect("cannot use with shared memories; see https://github.com/bytecodealliance/wasmtime/issues/5235 (TODO)");
+            slices.push(slice);
+        }
+        let slice_refs = slices.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+
+        let kind = match encoding {
+            gen::types::GraphEncoding::Autodetect => {
+                BackendKind::detect(&slice_refs).ok_or(UsageError::UndetectableEncoding)?
             }
-            let slice_refs = slices.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+            encoding => encoding.try_into()?,
+        };
+        let graph = if let Some(backend) = self.backends.get_mut(&kind) {
             backend.load(&slice_refs, target.into())?
         } else {
-            return Err(UsageError::InvalidEncoding(encoding.into()).into());
+            return Err(UsageError::UnavailableBackend(kind).into());
         };
         let graph_id = self.graphs.insert(graph);
         Ok(graph_id.into())
@@ -80,7 +88,7 @@ impl<'a> gen::wasi_ephemeral_nn::WasiEphemeralNn for WasiNnCtx {
 
     fn load_by_name<'b>(&mut self, name: &wiggle::GuestPtr<'b, str>) -> Result<gen::types::Graph> {
         let name = name.as_str()?.unwrap();
-        if let Some(graph) = self.registry.get_mut(&name) {
+        if let Some(graph) = self.registry.get_mut(&name).map_err(BackendError::from)? {
             let graph_id = self.graphs.insert(graph.clone().into());
             Ok(graph_id.into())
         } else {
@@ -154,6 +162,8 @@ impl TryFrom<gen::types::GraphEncoding> for crate::backend::BackendKind {
     fn try_from(value: gen::types::GraphEncoding) -> std::result::Result<Self, Self::Error> {
         match value {
             gen::types::GraphEncoding::Openvino => Ok(crate::backend::BackendKind::OpenVINO),
+            gen::types::GraphEncoding::Onnx => Ok(crate::backend::BackendKind::Onnx),
+            gen::types::GraphEncoding::Autodetect => Err(UsageError::UndetectableEncoding),
             _ => Err(UsageError::InvalidEncoding(value.into())),
         }
     }
@@ -177,7 +187,9 @@ impl From<gen::types::GraphEncoding> for crate::wit::types::GraphEncoding {
             gen::types::GraphEncoding::Tensorflowlite => {
                 crate::wit::types::GraphEncoding::Tensorflowlite
             }
-            gen::types::GraphEncoding::Autodetect => todo!("autodetect not supported"),
+            gen::types::GraphEncoding::Autodetect => {
+                unreachable!("autodetected encodings are resolved before conversion")
+            }
         }
     }
 }
@@ -1,6 +1,10 @@
 pub mod host;
+mod tzif;
+use crate::preview2::bindings::clocks::timezone::TimezoneDisplay;
 use cap_std::time::Duration;
 
+pub use self::host::{FixedTimezone, LocalTimezone};
+
 pub trait HostWallClock: Send + Sync {
     fn resolution(&self) -> Duration;
     fn now(&self) -> Duration;
@@ -10,3 +14,13 @@ pub trait HostMonotonicClock: Send + Sync {
     fn resolution(&self) -> u64;
     fn now(&self) -> u64;
 }
+
+/// The source of the information returned by `wasi:clocks/timezone`.
+pub trait HostTimezone: Send + Sync {
+    /// How to display the given time, as a duration since the Unix epoch.
+    fn display(&self, datetime: Duration) -> TimezoneDisplay;
+
+    fn utc_offset(&self, datetime: Duration) -> i32 {
+        self.display(datetime).utc_offset
+    }
+}
@@ -1,7 +1,10 @@
-use super::{HostMonotonicClock, HostWallClock};
+use super::tzif::Zone;
+use super::{HostMonotonicClock, HostTimezone, HostWallClock};
+use crate::preview2::bindings::clocks::timezone::TimezoneDisplay;
 use cap_std::time::{Duration, Instant, SystemClock};
 use cap_std::{ambient_authority, AmbientAuthority};
 use cap_time_ext::{MonotonicClockExt, SystemClockExt};
+use once_cell::sync::OnceCell;
 
 pub struct WallClock {
     /// The underlying system clock.
@@ -64,6 +67,121 @@ impl HostMonotonicClock for MonotonicClock {
     }
 }
 
+/// A timezone with a fixed offset from UTC which never observes daylight
+/// saving time. Useful for making guests deterministic.
+pub struct FixedTimezone {
+    utc_offset: i32,
+    name: String,
+}
+
+impl FixedTimezone {
+    /// A timezone `utc_offset` seconds east of UTC, displayed as `name`.
+    pub fn new(utc_offset: i32, name: impl Into<String>) -> Self {
+        Self {
+            utc_offset,
+            name: name.into(),
+        }
+    }
+
+    /// Coordinated Universal Time.
+    pub fn utc() -> Self {
+        Self::new(0, "UTC")
+    }
+}
+
+impl HostTimezone for FixedTimezone {
+    fn display(&self, _datetime: Duration) -> TimezoneDisplay {
+        TimezoneDisplay {
+            utc_offset: self.utc_offset,
+            name: self.name.clone(),
+            in_daylight_saving_time: false,
+        }
+    }
+
+    fn utc_offset(&self, _datetime: Duration) -> i32 {
+        self.utc_offset
+    }
+}
+
+/// The host's local timezone, looked up in the host's IANA time zone
+/// database.
+///
+/// The timezone is taken from the `TZ` environment variable if it's set, and
+/// `/etc/localtime` otherwise. When it can't be determined, such as on
+/// Windows, this behaves like [`FixedTimezone::utc`].
+///
+/// The timezone is only looked up the first time it's used, so creating a
+/// `LocalTimezone` is cheap.
+pub struct LocalTimezone {
+    zone: OnceCell<Option<Zone>>,
+}
+
+impl LocalTimezone {
+    pub fn new(_ambient_authority: AmbientAuthority) -> Self {
+        Self {
+            zone: OnceCell::new(),
+        }
+    }
+
+    fn zone(&self) -> Option<&Zone> {
+        self.zone.get_or_init(load_local_zone).as_ref()
+    }
+}
+
+fn load_local_zone() -> Option<Zone> {
+    match std::env::var("TZ") {
+        Ok(tz) => {
+            let tz = tz.strip_prefix(':').unwrap_or(&tz);
+            let path = if tz.starts_with('/') {
+                std::path::PathBuf::from(tz)
+            } else {
+                std::path::Path::new("/usr/share/zoneinfo").join(tz)
+            };
+            // `TZ` may also hold a rule itself rather than name a zone.
+            read_tzif(&path).or_else(|| Zone::parse_tz_string(tz))
+        }
+        Err(_) => read_tzif(std::path::Path::new("/etc/localtime")),
+    }
+}
+
+fn read_tzif(path: &std::path::Path) -> Option<Zone> {
+    Zone::parse_tzif(&std::fs::read(path).ok()?)
+}
+
+/// Seconds since the Unix epoch, saturating for times too far in the future.
+fn unix_time(datetime: Duration) -> i64 {
+    i64::try_from(datetime.as_secs()).unwrap_or(i64::MAX)
+}
+
+impl HostTimezone for LocalTimezone {
+    fn display(&self, datetime: Duration) -> TimezoneDisplay {
+        let Some(zone) = self.zone() else {
+            return FixedTimezone::utc().display(datetime);
+        };
+        let ty = zone.local_time_type(unix_time(datetime));
+        let name = if ty.name.is_empty() {
+            // Zones without an abbreviation are displayed by their offset.
+            let offset = ty.utc_offset.unsigned_abs() / 60;
+            let sign = if ty.utc_offset < 0 { '-' } else { '+' };
+            format!("{sign}{:02}:{:02}", offset / 60, offset % 60)
+        } else {
+            ty.name.clone()
+        };
+        TimezoneDisplay {
+            utc_offset: ty.utc_offset,
+            name,
+            in_daylight_saving_time: ty.is_dst,
+        }
+    }
+
+    fn utc_offset(&self, datetime: Duration) -> i32 {
+        match self.zone() {
+            Some(zone) => zone.local_time_type(unix_time(datetime)).utc_offset,
+            None => 0,
+        }
+    }
+}
+
 pub fn monotonic_clock() -> Box<dyn HostMonotonicClock + Send + Sync> {
     Box::new(MonotonicClock::new(ambient_authority()))
 }
@@ -71,3 +189,7 @@ pub fn monotonic_clock() -> Box<dyn HostMonotonicClock + Send + Sync> {
 pub fn wall_clock() -> Box<dyn HostWallClock + Send + Sync> {
     Box::new(WallClock::new(ambient_authority()))
 }
+
+pub fn timezone() -> Box<dyn HostTimezone + Send + Sync> {
+    Box::new(LocalTimezone::new(ambient_authority()))
+}
@@ -0,0 +1,571 @@
+//! A reader for the TZif files of the IANA time zone database, described in
+//! [RFC 8536], and for the POSIX `TZ` rules found in their footers.
+//!
+//! [RFC 8536]: https://www.rfc-editor.org/rfc/rfc8536
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The latest instant, in seconds either side of the Unix epoch, for which
+/// rules are evaluated: about 8.9 million years. Instants further out are
+/// treated as this one, which keeps the calendar arithmetic from overflowing.
+const MAX_RULE_TIME: i64 = 1 << 48;
+
+/// The local time type observed in a time zone at some instant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalTimeType {
+    /// Seconds east of UTC.
+    pub utc_offset: i32,
+    pub is_dst: bool,
+    /// The abbreviated name, e.g. `CEST`. May be empty.
+    pub name: String,
+}
+
+/// A time zone: a table of transitions between local time types, followed by
+/// an optional rule for instants after the last transition.
+#[derive(Debug)]
+pub struct Zone {
+    transitions: Vec<i64>,
+    transition_types: Vec<u8>,
+    types: Vec<LocalTimeType>,
+    rule: Option<Rule>,
+}
+
+impl Zone {
+    /// Parse the contents of a TZif file, returning `None` if it's malformed.
+    pub fn parse_tzif(data: &[u8]) -> Option<Zone> {
+        let mut r = Reader(data);
+        let mut header = Header::read(&mut r)?;
+        let mut time_size = 4;
+        if header.version >= b'2' {
+            // Version 2 and later files repeat the data with 64-bit times
+            // after the legacy 32-bit block, so skip straight to that.
+            r.bytes(header.data_len(time_size))?;
+            header = Header::read(&mut r)?;
+            time_size = 8;
+        }
+
+        let mut transitions = Vec::with_capacity(header.timecnt);
+        for _ in 0..header.timecnt {
+            transitions.push(match time_size {
+                4 => i64::from(r.i32()?),
+                _ => r.i64()?,
+            });
+        }
+        let transition_types = r.bytes(header.timecnt)?.to_vec();
+
+        let mut raw_types = Vec::with_capacity(header.typecnt);
+        for _ in 0..header.typecnt {
+            raw_types.push((r.i32()?, r.u8()? != 0, usize::from(r.u8()?)));
+        }
+        let chars = r.bytes(header.charcnt)?;
+        let types = raw_types
+            .into_iter()
+            .map(|(utc_offset, is_dst, index)| {
+                let name = chars.get(index..)?;
+                let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+                Some(LocalTimeType {
+                    utc_offset,
+                    is_dst,
+                    name: String::from_utf8_lossy(&name[..len]).into_owned(),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if types.is_empty()
+            || transition_types
+                .iter()
+                .any(|&i| usize::from(i) >= types.len())
+        {
+            return None;
+        }
+
+        let mut rule = None;
+        if header.version >= b'2' {
+            r.bytes(header.leapcnt * (time_size + 4) + header.isstdcnt + header.isutcnt)?;
+            // The footer is a newline-enclosed, possibly empty, `TZ` string.
+            // An unrecognized rule is ignored rather than failing the whole
+            // file, since the transitions are still useful.
+            let footer = r.0.strip_prefix(b"\n")?;
+            let end = footer.iter().position(|&c| c == b'\n')?;
+            rule = std::str::from_utf8(&footer[..end])
+                .ok()
+                .and_then(Rule::parse);
+        }
+
+        Some(Zone {
+            transitions,
+            transition_types,
+            types,
+            rule,
+        })
+    }
+
+    /// Parse a POSIX `TZ` string such as `CET-1CEST,M3.5.0,M10.5.0/3`.
+    pub fn parse_tz_string(s: &str) -> Option<Zone> {
+        let rule = Rule::parse(s)?;
+        Some(Zone {
+            transitions: Vec::new(),
+            transition_types: Vec::new(),
+            types: vec![rule.std.clone()],
+            rule: Some(rule),
+        })
+    }
+
+    /// The local time type observed at `time`, in seconds since the Unix
+    /// epoch.
+    pub fn local_time_type(&self, time: i64) -> &LocalTimeType {
+        let i = self.transitions.partition_point(|&t| t <= time);
+        if i == self.transitions.len() {
+            if let Some(rule) = &self.rule {
+                return rule.local_time_type(time);
+            }
+        }
+        match i {
+            0 => &self.types[0],
+            i => &self.types[usize::from(self.transition_types[i - 1])],
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.0.len() < n {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        Some(i32::from_be_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    fn i64(&mut self) -> Option<i64> {
+        Some(i64::from_be_bytes(self.bytes(8)?.try_into().ok()?))
+    }
+}
+
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Header {
+    fn read(r: &mut Reader<'_>) -> Option<Header> {
+        if r.bytes(4)? != b"TZif" {
+            return None;
+        }
+        let version = r.u8()?;
+        r.bytes(15)?;
+        let mut count = || usize::try_from(r.i32()?).ok();
+        Some(Header {
+            version,
+            isutcnt: count()?,
+            isstdcnt: count()?,
+            leapcnt: count()?,
+            timecnt: count()?,
+            typecnt: count()?,
+            charcnt: count()?,
+        })
+    }
+
+    /// The length of the data block which follows this header.
+    fn data_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+/// A POSIX `TZ` rule: a standard local time type, and optionally a daylight
+/// saving one along with the yearly transitions into and out of it.
+#[derive(Debug)]
+struct Rule {
+    std: LocalTimeType,
+    dst: Option<(LocalTimeType, Transition, Transition)>,
+}
+
+#[derive(Debug)]
+struct Transition {
+    date: RuleDate,
+    /// Seconds after local midnight at which the transition happens.
+    time: i64,
+}
+
+#[derive(Debug)]
+enum RuleDate {
+    /// `Jn`: day `n` of the year in `1..=365`, never counting February 29.
+    Julian1(i64),
+    /// `n`: day `n` of the year in `0..=365`, counting February 29.
+    Julian0(i64),
+    /// `Mm.w.d`: weekday `d` (0 is Sunday) of week `w` of month `m`, where
+    /// week 5 is the last such weekday of the month.
+    MonthWeekDay { month: i64, week: i64, weekday: i64 },
+}
+
+impl Rule {
+    fn parse(s: &str) -> Option<Rule> {
+        let mut p = Parser(s.as_bytes());
+        let std_name = p.name()?;
+        let std_offset = -p.offset()?;
+        let std = LocalTimeType {
+            utc_offset: i32::try_from(std_offset).ok()?,
+            is_dst: false,
+            name: std_name,
+        };
+        if p.0.is_empty() {
+            return Some(Rule { std, dst: None });
+        }
+
+        let dst_name = p.name()?;
+        let dst_offset = match p.0.first() {
+            None | Some(b',') => std_offset + 60 * 60,
+            Some(_) => -p.offset()?,
+        };
+        let dst = LocalTimeType {
+            utc_offset: i32::try_from(dst_offset).ok()?,
+            is_dst: true,
+            name: dst_name,
+        };
+        let (start, end) = if p.eat(b',') {
+            let start = p.transition()?;
+            if !p.eat(b',') {
+                return None;
+            }
+            (start, p.transition()?)
+        } else {
+            // Without explicit rules, POSIX leaves the transitions up to the
+            // implementation. Use the current US rules, as glibc does.
+            let rule = |month, week| Transition {
+                date: RuleDate::MonthWeekDay {
+                    month,
+                    week,
+                    weekday: 0,
+                },
+                time: 2 * 60 * 60,
+            };
+            (rule(3, 2), rule(11, 1))
+        };
+        if !p.0.is_empty() {
+            return None;
+        }
+        Some(Rule {
+            std,
+            dst: Some((dst, start, end)),
+        })
+    }
+
+    fn local_time_type(&self, time: i64) -> &LocalTimeType {
+        let Some((dst, start, end)) = &self.dst else {
+            return &self.std;
+        };
+        let time = time.clamp(-MAX_RULE_TIME, MAX_RULE_TIME);
+        let std_offset = i64::from(self.std.utc_offset);
+        let year = year_from_days((time + std_offset).div_euclid(SECONDS_PER_DAY));
+        // The transition into daylight saving time is given in standard time,
+        // and the one out of it in daylight saving time.
+        let start = start.utc_time(year, std_offset);
+        let end = end.utc_time(year, i64::from(dst.utc_offset));
+        let in_dst = if start <= end {
+            start <= time && time < end
+        } else {
+            // In the southern hemisphere, daylight saving time spans the new
+            // year.
+            !(end <= time && time < start)
+        };
+        if in_dst {
+            dst
+        } else {
+            &self.std
+        }
+    }
+}
+
+impl Transition {
+    /// The instant of this transition in `year`, for a local time
+    /// `utc_offset` seconds east of UTC.
+    fn utc_time(&self, year: i64, utc_offset: i64) -> i64 {
+        let days = match self.date {
+            RuleDate::Julian1(n) => {
+                let leap = is_leap_year(year) && n >= 60;
+                days_from_civil(year, 1, 1) + n - 1 + i64::from(leap)
+            }
+            RuleDate::Julian0(n) => days_from_civil(year, 1, 1) + n,
+            RuleDate::MonthWeekDay {
+                month,
+                week,
+                weekday,
+            } => {
+                let first = days_from_civil(year, month, 1);
+                // January 1, 1970 was a Thursday.
+                let first_weekday = (first + 4).rem_euclid(7);
+                let mut day = (weekday - first_weekday).rem_euclid(7) + (week - 1) * 7;
+                while day >= days_in_month(year, month) {
+                    day -= 7;
+                }
+                first + day
+            }
+        };
+        days * SECONDS_PER_DAY + self.time - utc_offset
+    }
+}
+
+struct Parser<'a>(&'a [u8]);
+
+impl<'a> Parser<'a> {
+    fn eat(&mut self, c: u8) -> bool {
+        match self.0.split_first() {
+            Some((&first, rest)) if first == c => {
+                self.0 = rest;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &'a [u8] {
+        let len = self.0.iter().position(|&c| !f(c)).unwrap_or(self.0.len());
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        taken
+    }
+
+    /// A zone abbreviation, either alphabetic or quoted in angle brackets.
+    fn name(&mut self) -> Option<String> {
+        let name = if self.eat(b'<') {
+            let name = self.take_while(|c| c != b'>');
+            if !self.eat(b'>') {
+                return None;
+            }
+            name
+        } else {
+            self.take_while(|c| c.is_ascii_alphabetic())
+        };
+        if name.len() < 3 {
+            return None;
+        }
+        String::from_utf8(name.to_vec()).ok()
+    }
+
+    fn number(&mut self) -> Option<i64> {
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        std::str::from_utf8(digits).ok()?.parse().ok()
+    }
+
+    /// A signed `hh[:mm[:ss]]`, in seconds.
+    fn offset(&mut self) -> Option<i64> {
+        let sign = if self.eat(b'-') {
+            -1
+        } else {
+            self.eat(b'+');
+            1
+        };
+        let mut seconds = self.number()? * 60 * 60;
+        if self.eat(b':') {
+            seconds += self.number()? * 60;
+            if self.eat(b':') {
+                seconds += self.number()?;
+            }
+        }
+        Some(sign * seconds)
+    }
+
+    fn transition(&mut self) -> Option<Transition> {
+        let date = if self.eat(b'J') {
+            let n = self.number()?;
+            if !(1..=365).contains(&n) {
+                return None;
+            }
+            RuleDate::Julian1(n)
+        } else if self.eat(b'M') {
+            let month = self.number()?;
+            let week = if self.eat(b'.') {
+                self.number()?
+            } else {
+                return None;
+            };
+            let weekday = if self.eat(b'.') {
+                self.number()?
+            } else {
+                return None;
+            };
+            if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+                return None;
+            }
+            RuleDate::MonthWeekDay {
+                month,
+                week,
+                weekday,
+            }
+        } else {
+            let n = self.number()?;
+            if n > 365 {
+                return None;
+            }
+            RuleDate::Julian0(n)
+        };
+        let time = if self.eat(b'/') {
+            self.offset()?
+        } else {
+            2 * 60 * 60
+        };
+        Some(Transition { date, time })
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the Unix epoch of the given proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The proleptic Gregorian year containing the given day since the Unix
+/// epoch.
+fn year_from_days(days: i64) -> i64 {
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400;
+    if month_index >= 10 {
+        year + 1
+    } else {
+        year
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(zone: &Zone, year: i64, month: i64, day: i64, hour: i64) -> &LocalTimeType {
+        zone.local_time_type(days_from_civil(year, month, day) * SECONDS_PER_DAY + hour * 3600)
+    }
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(year_from_days(11017), 2000);
+        assert_eq!(year_from_days(-1), 1969);
+        assert_eq!(year_from_days(days_from_civil(2023, 12, 31)), 2023);
+    }
+
+    #[test]
+    fn northern_rule() {
+        let zone = Zone::parse_tz_string("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        let winter = at(&zone, 2023, 1, 15, 12);
+        assert_eq!((winter.utc_offset, winter.is_dst), (3600, false));
+        assert_eq!(winter.name, "CET");
+
+        let summer = at(&zone, 2023, 7, 1, 12);
+        assert_eq!((summer.utc_offset, summer.is_dst), (7200, true));
+        assert_eq!(summer.name, "CEST");
+
+        // In 2023 daylight saving time started at 01:00 UTC on March 26.
+        assert!(!at(&zone, 2023, 3, 26, 0).is_dst);
+        assert!(at(&zone, 2023, 3, 26, 1).is_dst);
+        // And ended at 01:00 UTC on October 29.
+        assert!(at(&zone, 2023, 10, 29, 0).is_dst);
+        assert!(!at(&zone, 2023, 10, 29, 1).is_dst);
+    }
+
+    #[test]
+    fn southern_rule() {
+        let zone = Zone::parse_tz_string("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert!(at(&zone, 2023, 1, 15, 0).is_dst);
+        assert!(!at(&zone, 2023, 7, 1, 0).is_dst);
+        assert!(at(&zone, 2023, 12, 31, 23).is_dst);
+    }
+
+    #[test]
+    fn extreme_times() {
+        let zone = Zone::parse_tz_string("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        // Rules are still applied, rather than overflowing, far beyond any
+        // year a calendar is needed for.
+        for time in [i64::MIN, i64::MAX] {
+            let name = &zone.local_time_type(time).name;
+            assert!(name == "AEST" || name == "AEDT", "{name}");
+        }
+    }
+
+    #[test]
+    fn fixed_rules() {
+        let zone = Zone::parse_tz_string("<+0530>-5:30").unwrap();
+        let t = at(&zone, 2023, 7, 1, 0);
+        assert_eq!((t.utc_offset, t.name.as_str()), (19800, "+0530"));
+
+        assert!(Zone::parse_tz_string("").is_none());
+        assert!(Zone::parse_tz_string("EST5EDT,M3.2.0").is_none());
+        assert!(Zone::parse_tz_string("EST5EDT,M13.2.0,M11.1.0").is_none());
+    }
+
+    #[test]
+    fn tzif() {
+        // A version 2 file with one transition, from `LMT` to `XST`, at
+        // 1900-01-01, and a footer rule for later instants.
+        fn header(out: &mut Vec<u8>, timecnt: i32, typecnt: i32, charcnt: i32) {
+            out.extend_from_slice(b"TZif2");
+            out.extend_from_slice(&[0; 15]);
+            for count in [0, 0, 0, timecnt, typecnt, charcnt] {
+                out.extend_from_slice(&count.to_be_bytes());
+            }
+        }
+        let mut data = Vec::new();
+        header(&mut data, 0, 1, 4);
+        data.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(b"LMT\0");
+        header(&mut data, 1, 2, 8);
+        data.extend_from_slice(&(-2208988800i64).to_be_bytes());
+        data.push(1);
+        data.extend_from_slice(&[0, 0, 0, 100, 0, 0]);
+        data.extend_from_slice(&[0, 0, 0x0e, 0x10, 0, 4]);
+        data.extend_from_slice(b"LMT\0XST\0");
+        data.extend_from_slice(b"\nXST-1XDT,M3.5.0,M10.5.0/3\n");
+
+        let zone = Zone::parse_tzif(&data).unwrap();
+        assert_eq!(at(&zone, 1800, 1, 1, 0).name, "LMT");
+        assert_eq!(at(&zone, 1800, 1, 1, 0).utc_offset, 100);
+        assert_eq!(at(&zone, 1950, 7, 1, 0).name, "XDT");
+        assert_eq!(at(&zone, 1950, 1, 1, 0).name, "XST");
+
+        assert!(Zone::parse_tzif(&data[..50]).is_none());
+        assert!(Zone::parse_tzif(b"not a tzif file").is_none());
+    }
+}
@@ -12,6 +12,12 @@ wasmtime::component::bindgen!({
        "wasi:filesystem/types": crate::preview2::bindings::filesystem::types,
        "wasi:filesystem/preopens": crate::preview2::bindings::filesystem::preopens,
        "wasi:sockets/tcp": crate::preview2::bindings::sockets::tcp,
+       "wasi:sockets/tcp-create-socket": crate::preview2::bindings::sockets::tcp_create_socket,
+       "wasi:sockets/udp": crate::preview2::bindings::sockets::udp,
+       "wasi:sockets/udp-create-socket": crate::preview2::bindings::sockets::udp_create_socket,
+       "wasi:sockets/network": crate::preview2::bindings::sockets::network,
+       "wasi:sockets/instance-network": crate::preview2::bindings::sockets::instance_network,
+       "wasi:sockets/ip-name-lookup": crate::preview2::bindings::sockets::ip_name_lookup,
        "wasi:clocks/monotonic_clock": crate::preview2::bindings::clocks::monotonic_clock,
        "wasi:poll/poll": crate::preview2::bindings::poll::poll,
        "wasi:io/streams": crate::preview2::bindings::io::streams,
@@ -38,6 +44,12 @@ pub fn add_to_linker<T: WasiView>(l: &mut wasmtime::component::Linker<T>) -> any
     crate::preview2::bindings::filesystem::types::add_to_linker(l, |t| t)?;
     crate::preview2::bindings::filesystem::preopens::add_to_linker(l, |t| t)?;
     crate::preview2::bindings::sockets::tcp::add_to_linker(l, |t| t)?;
+    crate::preview2::bindings::sockets::tcp_create_socket::add_to_linker(l, |t| t)?;
+    crate::preview2::bindings::sockets::udp::add_to_linker(l, |t| t)?;
+    crate::preview2::bindings::sockets::udp_create_socket::add_to_linker(l, |t| t)?;
+    crate::preview2::bindings::sockets::network::add_to_linker(l, |t| t)?;
+    crate::preview2::bindings::sockets::instance_network::add_to_linker(l, |t| t)?;
+    crate::preview2::bindings::sockets::ip_name_lookup::add_to_linker(l, |t| t)?;
     crate::preview2::bindings::poll::poll::add_to_linker(l, |t| t)?;
     crate::preview2::bindings::io::streams::add_to_linker(l, |t| t)?;
     crate::preview2::bindings::random::random::add_to_linker(l, |t| t)?;
@@ -51,6 +63,7 @@ pub fn add_to_linker<T: WasiView>(l: &mut wasmtime::component::Linker<T>) -> any
     crate::preview2::bindings::cli::terminal_stdin::add_to_linker(l, |t| t)?;
     crate::preview2::bindings::cli::terminal_stdout::add_to_linker(l, |t| t)?;
     crate::preview2::bindings::cli::terminal_stderr::add_to_linker(l, |t| t)?;
+    crate::preview2::bindings::logging::logging::add_to_linker(l, |t| t)?;
     Ok(())
 }
 
@@ -69,6 +82,12 @@ pub mod sync {
            "wasi:filesystem/types": crate::preview2::bindings::sync_io::filesystem::types,
            "wasi:filesystem/preopens": crate::preview2::bindings::filesystem::preopens,
            "wasi:sockets/tcp": crate::preview2::bindings::sockets::tcp,
+           "wasi:sockets/tcp-create-socket": crate::preview2::bindings::sockets::tcp_create_socket,
+           "wasi:sockets/udp": crate::preview2::bindings::sockets::udp,
+           "wasi:sockets/udp-create-socket": crate::preview2::bindings::sockets::udp_create_socket,
+           "wasi:sockets/network": crate::preview2::bindings::sockets::network,
+           "wasi:sockets/instance-network": crate::preview2::bindings::sockets::instance_network,
+           "wasi:sockets/ip-name-lookup": crate::preview2::bindings::sockets::ip_name_lookup,
            "wasi:clocks/monotonic_clock": crate::preview2::bindings::clocks::monotonic_clock,
            "wasi:poll/poll": crate::preview2::bindings::sync_io::poll::poll,
            "wasi:io/streams": crate::preview2::bindings::sync_io::io::streams,
@@ -110,6 +129,13 @@ pub mod sync {
         crate::preview2::bindings::cli::terminal_stdout::add_to_linker(l, |t| t)?;
         crate::preview2::bindings::cli::terminal_stderr::add_to_linker(l, |t| t)?;
         crate::preview2::bindings::sockets::tcp::add_to_linker(l, |t| t)?;
+        crate::preview2::bindings::sockets::tcp_create_socket::add_to_linker(l, |t| t)?;
+        crate::preview2::bindings::sockets::udp::add_to_linker(l, |t| t)?;
+        crate::preview2::bindings::sockets::udp_create_socket::add_to_linker(l, |t| t)?;
+        crate::preview2::bindings::sockets::network::add_to_linker(l, |t| t)?;
+        crate::preview2::bindings::sockets::instance_network::add_to_linker(l, |t| t)?;
+        crate::preview2::bindings::sockets::ip_name_lookup::add_to_linker(l, |t| t)?;
+        crate::preview2::bindings::logging::logging::add_to_linker(l, |t| t)?;
         Ok(())
     }
 }
@@ -1,10 +1,14 @@
-use super::clocks::host::{monotonic_clock, wall_clock};
+use super::clocks::host::{monotonic_clock, timezone, wall_clock};
 use crate::preview2::{
-    clocks::{self, HostMonotonicClock, HostWallClock},
-    filesystem::{Dir, TableFsExt},
+    bindings::logging::logging::Level,
+    clocks::{self, HostMonotonicClock, HostTimezone, HostWallClock},
+    filesystem::{Dir, HostDir, OsDir, TableFsExt},
+    ip_name_lookup::{HostResolver, SystemResolver},
+    logging::{HostLogger, TracingLogger},
     pipe, random, stdio,
     stdio::{StdioInput, StdioOutput},
     stream::{HostInputStream, HostOutputStream, TableStreamExt},
+    tcp::{HostTcpSocket, TableTcpSocketExt},
     DirPerms, FilePerms, IsATTY, Table,
 };
 use cap_rand::{Rng, RngCore, SeedableRng};
@@ -13,6 +17,7 @@ use cap_std::net::Pool;
 use cap_std::{ambient_authority, AmbientAuthority};
 use std::mem;
 use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
 
 pub struct WasiCtxBuilder {
     stdin: (Box<dyn HostInputStream>, IsATTY),
@@ -21,13 +26,19 @@ pub struct WasiCtxBuilder {
     env: Vec<(String, String)>,
     args: Vec<String>,
     preopens: Vec<(Dir, String)>,
+    preopened_sockets: Vec<(u32, cap_std::net::TcpListener)>,
 
     pool: Pool,
+    resolver: Arc<dyn HostResolver>,
     random: Box<dyn RngCore + Send + Sync>,
     insecure_random: Box<dyn RngCore + Send + Sync>,
     insecure_random_seed: u128,
     wall_clock: Box<dyn HostWallClock + Send + Sync>,
     monotonic_clock: Box<dyn HostMonotonicClock + Send + Sync>,
+    timezone: Box<dyn HostTimezone + Send + Sync>,
+    logger: Option<Arc<dyn HostLogger>>,
+    instance_name: String,
+    min_log_level: Level,
     built: bool,
 }
 
@@ -42,6 +53,9 @@ impl WasiCtxBuilder {
     /// * no arguments
     /// * no preopens
     /// * clocks use the host implementation of wall/monotonic clocks
+    /// * the timezone is the host's local timezone
+    /// * names are resolved with the host's resolver
+    /// * guest log messages of all levels are emitted as `tracing` events
     /// * RNGs are all initialized with random state and suitable generator
     ///   quality to satisfy the requirements of WASI APIs.
     ///
@@ -68,12 +82,18 @@ impl WasiCtxBuilder {
             env: Vec::new(),
             args: Vec::new(),
             preopens: Vec::new(),
+            preopened_sockets: Vec::new(),
             pool: Pool::new(),
+            resolver: Arc::new(SystemResolver),
             random: random::thread_rng(),
             insecure_random,
             insecure_random_seed,
             wall_clock: wall_clock(),
             monotonic_clock: monotonic_clock(),
+            timezone: timezone(),
+            logger: None,
+            instance_name: String::new(),
+            min_log_level: Level::Trace,
             built: false,
         }
     }
@@ -161,8 +181,36 @@ impl WasiCtxBuilder {
         file_perms: FilePerms,
         path: impl AsRef<str>,
     ) -> &mut Self {
-        self.preopens
-            .push((Dir::new(dir, perms, file_perms), path.as_ref().to_owned()));
+        self.preopened_host_dir(OsDir::new(dir), perms, file_perms, path)
+    }
+
+    /// Preopen a directory from any filesystem backend, such as a
+    /// [`MemoryDir`](crate::preview2::MemoryDir), at `path` in the guest.
+    pub fn preopened_host_dir(
+        &mut self,
+        dir: impl HostDir + 'static,
+        perms: DirPerms,
+        file_perms: FilePerms,
+        path: impl AsRef<str>,
+    ) -> &mut Self {
+        self.preopens.push((
+            Dir::new(Arc::new(dir), perms, file_perms),
+            path.as_ref().to_owned(),
+        ));
+        self
+    }
+
+    /// Provide a listening TCP socket to the guest.
+    ///
+    /// Preview 2 has no notion of preopened sockets, so these are only
+    /// visible to preview 1 guests, as file descriptor `fd`. This mirrors the
+    /// `preopened_socket` method of the `wasi-common` builders.
+    pub fn preopened_socket(
+        &mut self,
+        fd: u32,
+        listener: impl Into<cap_std::net::TcpListener>,
+    ) -> &mut Self {
+        self.preopened_sockets.push((fd, listener.into()));
         self
     }
 
@@ -206,6 +254,17 @@ impl WasiCtxBuilder {
         self
     }
 
+    /// Set the timezone reported by `wasi:clocks/timezone`.
+    ///
+    /// By default this is the host's local timezone, a
+    /// [`LocalTimezone`](crate::preview2::LocalTimezone). Use a
+    /// [`FixedTimezone`](crate::preview2::FixedTimezone) to keep the host's
+    /// timezone from being observed by the guest.
+    pub fn timezone(&mut self, timezone: impl clocks::HostTimezone + 'static) -> &mut Self {
+        self.timezone = Box::new(timezone);
+        self
+    }
+
     /// Add all network addresses accessable to the host to the pool.
     pub fn inherit_network(&mut self, ambient_authority: AmbientAuthority) -> &mut Self {
         self.pool.insert_ip_net_port_any(
@@ -257,6 +316,39 @@ impl WasiCtxBuilder {
         self.pool.insert_ip_net(ip_net, port, ambient_authority())
     }
 
+    /// Set the resolver used by `wasi:sockets/ip-name-lookup`.
+    ///
+    /// Note that resolving names still requires access to a `network`, but
+    /// the resolved addresses aren't checked against the pool.
+    pub fn resolver(&mut self, resolver: impl HostResolver + 'static) -> &mut Self {
+        self.resolver = Arc::new(resolver);
+        self
+    }
+
+    /// Set the logger which receives the messages logged by the guest through
+    /// `wasi:logging/logging`.
+    ///
+    /// By default messages are emitted as `tracing` events by a
+    /// [`TracingLogger`].
+    pub fn logger(&mut self, logger: impl HostLogger + 'static) -> &mut Self {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Set the name of the instance using this context, which the default
+    /// [`TracingLogger`] attaches to every message logged by the guest.
+    pub fn instance_name(&mut self, name: impl AsRef<str>) -> &mut Self {
+        self.instance_name = name.as_ref().to_owned();
+        self
+    }
+
+    /// Discard guest log messages with a level lower than `level`, rather
+    /// than passing them to the logger.
+    pub fn min_log_level(&mut self, level: Level) -> &mut Self {
+        self.min_log_level = level;
+        self
+    }
+
     /// Uses the configured context so far to construct the final `WasiCtx`.
     ///
     /// This will insert resources into the provided `table`.
@@ -278,12 +370,18 @@ impl WasiCtxBuilder {
             env,
             args,
             preopens,
+            preopened_sockets,
             pool,
+            resolver,
             random,
             insecure_random,
             insecure_random_seed,
             wall_clock,
             monotonic_clock,
+            timezone,
+            logger,
+            instance_name,
+            min_log_level,
             built: _,
         } = mem::replace(self, Self::new());
         self.built = true;
@@ -302,6 +400,20 @@ impl WasiCtxBuilder {
             })
             .collect::<anyhow::Result<Vec<_>>>()?;
 
+        let preopened_sockets = preopened_sockets
+            .into_iter()
+            .map(|(fd, listener)| {
+                let socket = crate::preview2::with_ambient_tokio_runtime(|| {
+                    HostTcpSocket::from_tcp_listener(listener)
+                })
+                .with_context(|| format!("preopened socket {fd}"))?;
+                let socket = table
+                    .push_tcp_socket(socket)
+                    .with_context(|| format!("preopened socket {fd}"))?;
+                Ok((fd, socket))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
         Ok(WasiCtx {
             stdin: StdioInput {
                 input_stream: stdin_ix,
@@ -318,12 +430,17 @@ impl WasiCtxBuilder {
             env,
             args,
             preopens,
+            preopened_sockets,
             pool,
+            resolver,
             random,
             insecure_random,
             insecure_random_seed,
             wall_clock,
             monotonic_clock,
+            timezone,
+            logger: logger.unwrap_or_else(|| Arc::new(TracingLogger::new(instance_name))),
+            min_log_level,
         })
     }
 }
@@ -341,11 +458,16 @@ pub struct WasiCtx {
     pub(crate) insecure_random_seed: u128,
     pub(crate) wall_clock: Box<dyn HostWallClock + Send + Sync>,
     pub(crate) monotonic_clock: Box<dyn HostMonotonicClock + Send + Sync>,
+    pub(crate) timezone: Box<dyn HostTimezone + Send + Sync>,
     pub(crate) env: Vec<(String, String)>,
     pub(crate) args: Vec<String>,
     pub(crate) preopens: Vec<(u32, String)>,
+    pub(crate) preopened_sockets: Vec<(u32, u32)>,
     pub(crate) stdin: StdioInput,
     pub(crate) stdout: StdioOutput,
     pub(crate) stderr: StdioOutput,
     pub(crate) pool: Pool,
+    pub(crate) resolver: Arc<dyn HostResolver>,
+    pub(crate) logger: Arc<dyn HostLogger>,
+    pub(crate) min_log_level: Level,
 }
@@ -1,7 +1,19 @@
+use crate::preview2::bindings::clocks::wall_clock;
+use crate::preview2::bindings::filesystem::types::{
+    self, AccessType, Advice, DescriptorFlags, DescriptorStat, DescriptorType, DirectoryEntry,
+    ErrorCode, MetadataHashValue, Modes, NewTimestamp, OpenFlags, PathFlags,
+};
 use crate::preview2::{StreamRuntimeError, StreamState, Table, TableError};
-use bytes::{Bytes, BytesMut};
+use bytes::Bytes;
+use std::any::Any;
 use std::sync::Arc;
 
+mod memory;
+mod os;
+
+pub use self::memory::{MemoryDir, MemoryFile};
+pub use self::os::{OsDir, OsFile};
+
 bitflags::bitflags! {
     #[derive(Copy, Clone, Debug, PartialEq, Eq)]
     pub struct FilePerms: usize {
@@ -10,32 +22,248 @@ bitflags::bitflags! {
     }
 }
 
+/// The outcome of [`HostDir::open_at`].
+pub enum OpenResult {
+    Dir(Box<dyn HostDir>),
+    File(Box<dyn HostFile>),
+}
+
+/// The kinds of advisory lock managed by [`HostFile::lock`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockKind {
+    Shared,
+    Exclusive,
+    Unlock,
+}
+
+/// The entries of a directory, as returned by [`HostDir::read_directory`].
+pub type DirectoryEntries = Box<dyn Iterator<Item = Result<DirectoryEntry, types::Error>> + Send>;
+
+/// A directory in a filesystem backend which guests reach through
+/// `wasi:filesystem/types` descriptors.
+///
+/// Backends include the host's own filesystem, [`OsDir`], and an in-memory
+/// tree, [`MemoryDir`]. Embedders can implement this trait, along with
+/// [`HostFile`], to serve files from anywhere else, and make them available to
+/// guests with [`WasiCtxBuilder::preopened_host_dir`].
+///
+/// Paths given to these methods are relative to the directory, and must never
+/// resolve to anything outside of it. The [`DirPerms`] and [`FilePerms`] of
+/// the descriptor are checked before any method is called, so backends don't
+/// need to enforce them.
+///
+/// Operations a backend doesn't support default to returning
+/// `error-code::unsupported`.
+///
+/// [`WasiCtxBuilder::preopened_host_dir`]: crate::preview2::WasiCtxBuilder::preopened_host_dir
+#[async_trait::async_trait]
+pub trait HostDir: Send + Sync {
+    /// Used to find the concrete type of the other directory passed to
+    /// [`HostDir::link_at`] and [`HostDir::rename_at`].
+    fn as_any(&self) -> &dyn Any;
+
+    /// Open the file or directory at `path`. If `oflags` contains
+    /// `directory`, this fails with `error-code::not-directory` when `path`
+    /// isn't a directory.
+    async fn open_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        oflags: OpenFlags,
+        flags: DescriptorFlags,
+    ) -> Result<OpenResult, types::Error>;
+
+    async fn stat(&self) -> Result<DescriptorStat, types::Error>;
+
+    async fn stat_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+    ) -> Result<DescriptorStat, types::Error>;
+
+    async fn metadata_hash(&self) -> Result<MetadataHashValue, types::Error>;
+
+    async fn metadata_hash_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+    ) -> Result<MetadataHashValue, types::Error>;
+
+    async fn read_directory(&self) -> Result<DirectoryEntries, types::Error>;
+
+    /// The flags of this directory other than `read` and `mutate-directory`,
+    /// which come from its [`DirPerms`].
+    async fn get_flags(&self) -> Result<DescriptorFlags, types::Error> {
+        Ok(DescriptorFlags::empty())
+    }
+
+    async fn set_times(
+        &self,
+        _atim: NewTimestamp,
+        _mtim: NewTimestamp,
+    ) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn set_times_at(
+        &self,
+        _path_flags: PathFlags,
+        _path: String,
+        _atim: NewTimestamp,
+        _mtim: NewTimestamp,
+    ) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn create_directory_at(&self, _path: String) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    /// Create a hard link. Backends should return `error-code::cross-device`
+    /// if `new_dir` belongs to a different backend.
+    async fn link_at(
+        &self,
+        _old_path: String,
+        _new_dir: &dyn HostDir,
+        _new_path: String,
+    ) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    /// Rename a file or directory. Backends should return
+    /// `error-code::cross-device` if `new_dir` belongs to a different backend.
+    async fn rename_at(
+        &self,
+        _old_path: String,
+        _new_dir: &dyn HostDir,
+        _new_path: String,
+    ) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn symlink_at(&self, _old_path: String, _new_path: String) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn readlink_at(&self, _path: String) -> Result<String, types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn remove_directory_at(&self, _path: String) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn unlink_file_at(&self, _path: String) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    /// Check whether `path` is accessible. By default, anything which exists
+    /// is considered accessible in any mode.
+    async fn access_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        _access: AccessType,
+    ) -> Result<(), types::Error> {
+        self.stat_at(path_flags, path).await?;
+        Ok(())
+    }
+
+    async fn change_file_permissions_at(
+        &self,
+        _path_flags: PathFlags,
+        _path: String,
+        _mode: Modes,
+    ) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn change_directory_permissions_at(
+        &self,
+        _path_flags: PathFlags,
+        _path: String,
+        _mode: Modes,
+    ) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn sync(&self) -> Result<(), types::Error> {
+        Ok(())
+    }
+
+    async fn sync_data(&self) -> Result<(), types::Error> {
+        Ok(())
+    }
+}
+
+/// A file in a filesystem backend. See [`HostDir`].
+#[async_trait::async_trait]
+pub trait HostFile: Send + Sync {
+    /// Read up to `len` bytes at `offset`. An empty result means the end of
+    /// the file.
+    async fn read_at(&self, len: usize, offset: u64) -> Result<Bytes, types::Error>;
+
+    /// Write `buf` at `offset`, returning the number of bytes written.
+    async fn write_at(&self, buf: Bytes, offset: u64) -> Result<usize, types::Error>;
+
+    /// Write `buf` at the end of the file, returning the number of bytes
+    /// written.
+    async fn append(&self, buf: Bytes) -> Result<usize, types::Error>;
+
+    async fn stat(&self) -> Result<DescriptorStat, types::Error>;
+
+    async fn metadata_hash(&self) -> Result<MetadataHashValue, types::Error>;
+
+    async fn set_size(&self, size: u64) -> Result<(), types::Error>;
+
+    async fn get_type(&self) -> Result<DescriptorType, types::Error> {
+        Ok(self.stat().await?.type_)
+    }
+
+    /// The flags of this file other than `read` and `write`, which come from
+    /// its [`FilePerms`].
+    async fn get_flags(&self) -> Result<DescriptorFlags, types::Error> {
+        Ok(DescriptorFlags::empty())
+    }
+
+    async fn set_times(
+        &self,
+        _atim: NewTimestamp,
+        _mtim: NewTimestamp,
+    ) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn advise(&self, _offset: u64, _len: u64, _advice: Advice) -> Result<(), types::Error> {
+        Ok(())
+    }
+
+    /// Acquire or release an advisory whole-file lock, like `flock`. When
+    /// `blocking` is not set, a lock held elsewhere yields
+    /// `error-code::would-block`.
+    async fn lock(&self, _kind: LockKind, _blocking: bool) -> Result<(), types::Error> {
+        Err(ErrorCode::Unsupported.into())
+    }
+
+    async fn sync(&self) -> Result<(), types::Error> {
+        Ok(())
+    }
+
+    async fn sync_data(&self) -> Result<(), types::Error> {
+        Ok(())
+    }
+}
+
 pub(crate) struct File {
     /// Wrapped in an Arc because the same underlying file is used for
-    /// implementing the stream types. Also needed for [`spawn_blocking`].
-    ///
-    /// [`spawn_blocking`]: Self::spawn_blocking
-    pub file: Arc<cap_std::fs::File>,
+    /// implementing the stream types.
+    pub file: Arc<dyn HostFile>,
     pub perms: FilePerms,
 }
 
 impl File {
-    pub fn new(file: cap_std::fs::File, perms: FilePerms) -> Self {
-        Self {
-            file: Arc::new(file),
-            perms,
-        }
-    }
-
-    /// Spawn a task on tokio's blocking thread for performing blocking
-    /// syscalls on the underlying [`cap_std::fs::File`].
-    pub(crate) async fn spawn_blocking<F, R>(&self, body: F) -> R
-    where
-        F: FnOnce(&cap_std::fs::File) -> R + Send + 'static,
-        R: Send + 'static,
-    {
-        let f = self.file.clone();
-        tokio::task::spawn_blocking(move || body(&f)).await.unwrap()
+    pub fn new(file: Arc<dyn HostFile>, perms: FilePerms) -> Self {
+        Self { file, perms }
     }
 }
 pub(crate) trait TableFsExt {
@@ -87,56 +315,43 @@ bitflags::bitflags! {
 }
 
 pub(crate) struct Dir {
-    pub dir: Arc<cap_std::fs::Dir>,
+    pub dir: Arc<dyn HostDir>,
     pub perms: DirPerms,
     pub file_perms: FilePerms,
 }
 
 impl Dir {
-    pub fn new(dir: cap_std::fs::Dir, perms: DirPerms, file_perms: FilePerms) -> Self {
+    pub fn new(dir: Arc<dyn HostDir>, perms: DirPerms, file_perms: FilePerms) -> Self {
         Dir {
-            dir: Arc::new(dir),
+            dir,
             perms,
             file_perms,
         }
     }
-
-    /// Spawn a task on tokio's blocking thread for performing blocking
-    /// syscalls on the underlying [`cap_std::fs::Dir`].
-    pub(crate) async fn spawn_blocking<F, R>(&self, body: F) -> R
-    where
-        F: FnOnce(&cap_std::fs::Dir) -> R + Send + 'static,
-        R: Send + 'static,
-    {
-        let d = self.dir.clone();
-        tokio::task::spawn_blocking(move || body(&d)).await.unwrap()
-    }
 }
 
 pub(crate) struct FileInputStream {
-    file: Arc<cap_std::fs::File>,
+    file: Arc<dyn HostFile>,
     position: u64,
 }
 impl FileInputStream {
-    pub fn new(file: Arc<cap_std::fs::File>, position: u64) -> Self {
+    pub fn new(file: Arc<dyn HostFile>, position: u64) -> Self {
         Self { file, position }
     }
 
     pub async fn read(&mut self, size: usize) -> anyhow::Result<(Bytes, StreamState)> {
-        use system_interface::fs::FileIoExt;
-        let f = Arc::clone(&self.file);
-        let p = self.position;
-        let (r, mut buf) = tokio::task::spawn_blocking(move || {
-            let mut buf = BytesMut::zeroed(size);
-            let r = f.read_at(&mut buf, p);
-            (r, buf)
-        })
-        .await
-        .unwrap();
-        let (n, state) = read_result(r)?;
-        buf.truncate(n);
-        self.position += n as u64;
-        Ok((buf.freeze(), state))
+        let buf = self
+            .file
+            .read_at(size, self.position)
+            .await
+            .map_err(stream_error)?;
+        self.position += buf.len() as u64;
+        let state = if buf.is_empty() {
+            StreamState::Closed
+        } else {
+            StreamState::Open
+        };
+        Ok((buf, state))
     }
 
     pub async fn skip(&mut self, nelem: usize) -> anyhow::Result<(usize, StreamState)> {
@@ -154,20 +369,12 @@ impl FileInputStream {
     }
 }
 
-fn read_result(r: Result<usize, std::io::Error>) -> Result<(usize, StreamState), anyhow::Error> {
-    match r {
-        Ok(0) => Ok((0, StreamState::Closed)),
-        Ok(n) => Ok((n, StreamState::Open)),
-        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => Ok((0, StreamState::Open)),
-        Err(e) => Err(StreamRuntimeError::from(anyhow::anyhow!(e)).into()),
-    }
-}
-
-fn write_result(r: Result<usize, std::io::Error>) -> Result<(usize, StreamState), anyhow::Error> {
-    match r {
-        Ok(0) => Ok((0, StreamState::Closed)),
-        Ok(n) => Ok((n, StreamState::Open)),
-        Err(e) => Err(StreamRuntimeError::from(anyhow::anyhow!(e)).into()),
+/// Report a filesystem `error-code` as a stream error, keeping the code
+/// itself, while any other error traps.
+fn stream_error(e: types::Error) -> anyhow::Error {
+    match e.downcast() {
+        Ok(code) => StreamRuntimeError::from(anyhow::Error::from(code)).into(),
+        Err(trap) => trap,
     }
 }
 
@@ -178,17 +385,17 @@ pub(crate) enum FileOutputMode {
 }
 
 pub(crate) struct FileOutputStream {
-    file: Arc<cap_std::fs::File>,
+    file: Arc<dyn HostFile>,
     mode: FileOutputMode,
 }
 impl FileOutputStream {
-    pub fn write_at(file: Arc<cap_std::fs::File>, position: u64) -> Self {
+    pub fn write_at(file: Arc<dyn HostFile>, position: u64) -> Self {
         Self {
             file,
             mode: FileOutputMode::Position(position),
         }
     }
-    pub fn append(file: Arc<cap_std::fs::File>) -> Self {
+    pub fn append(file: Arc<dyn HostFile>) -> Self {
         Self {
             file,
             mode: FileOutputMode::Append,
@@ -196,19 +403,61 @@ impl FileOutputStream {
     }
     /// Write bytes. On success, returns the number of bytes written.
     pub async fn write(&mut self, buf: Bytes) -> anyhow::Result<(usize, StreamState)> {
-        use system_interface::fs::FileIoExt;
-        let f = Arc::clone(&self.file);
-        let m = self.mode;
-        let r = tokio::task::spawn_blocking(move || match m {
-            FileOutputMode::Position(p) => f.write_at(buf.as_ref(), p),
-            FileOutputMode::Append => f.append(buf.as_ref()),
-        })
-        .await
-        .unwrap();
-        let (n, state) = write_result(r)?;
+        let n = match self.mode {
+            FileOutputMode::Position(p) => self.file.write_at(buf, p).await,
+            FileOutputMode::Append => self.file.append(buf).await,
+        }
+        .map_err(stream_error)?;
         if let FileOutputMode::Position(ref mut position) = self.mode {
             *position += n as u64;
         }
+        let state = if n == 0 {
+            StreamState::Closed
+        } else {
+            StreamState::Open
+        };
         Ok((n, state))
     }
 }
+
+/// Combine a device and inode number into a `metadata-hash-value`.
+pub(crate) fn metadata_hash(dev: u64, ino: u64) -> MetadataHashValue {
+    // Without incurring any deps, std provides us with a 64 bit hash
+    // function:
+    use std::hash::Hasher;
+    // Note that this means that the metadata hash (which becomes a preview1 ino) may
+    // change when a different rustc release is used to build this host implementation:
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write_u64(dev);
+    hasher.write_u64(ino);
+    let lower = hasher.finish();
+    // MetadataHashValue has a pair of 64-bit members for representing a
+    // single 128-bit number. However, we only have 64 bits of entropy. To
+    // synthesize the upper 64 bits, lets xor the lower half with an arbitrary
+    // constant, in this case the 64 bit integer corresponding to the IEEE
+    // double representation of (a number as close as possible to) pi.
+    // This seems better than just repeating the same bits in the upper and
+    // lower parts outright, which could make folks wonder if the struct was
+    // mangled in the ABI, or worse yet, lead to consumers of this interface
+    // expecting them to be equal.
+    let upper = lower ^ 4614256656552045848u64;
+    MetadataHashValue { lower, upper }
+}
+
+pub(crate) fn systemtime_from(
+    t: wall_clock::Datetime,
+) -> Result<std::time::SystemTime, types::Error> {
+    use std::time::{Duration, SystemTime};
+    SystemTime::UNIX_EPOCH
+        .checked_add(Duration::new(t.seconds, t.nanoseconds))
+        .ok_or_else(|| ErrorCode::Overflow.into())
+}
+
+pub(crate) fn datetime_from(t: std::time::SystemTime) -> wall_clock::Datetime {
+    // FIXME make this infallible or handle errors properly
+    wall_clock::Datetime::try_from(cap_std::time::SystemTime::from_std(t)).unwrap()
+}
+
+pub(crate) fn symlink_follow(path_flags: PathFlags) -> bool {
+    path_flags.contains(PathFlags::SYMLINK_FOLLOW)
+}
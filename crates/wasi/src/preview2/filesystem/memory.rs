@@ -0,0 +1,951 @@
+//! An in-memory [`HostDir`] and [`HostFile`] backend.
+
+use super::{
+    datetime_from, metadata_hash, symlink_follow, systemtime_from, DirectoryEntries, HostDir,
+    HostFile, OpenResult,
+};
+use crate::preview2::bindings::filesystem::types::{
+    self, DescriptorFlags, DescriptorStat, DescriptorType, DirectoryEntry, ErrorCode,
+    MetadataHashValue, NewTimestamp, OpenFlags, PathFlags,
+};
+use bytes::Bytes;
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+/// The device number reported for in-memory files and directories, chosen to
+/// be unlikely to match a device on the host.
+const MEMORY_DEV: u64 = u64::MAX;
+
+/// The most symbolic links followed while resolving a single path.
+const MAX_SYMLINK_EXPANSIONS: usize = 40;
+
+/// The default for [`MemoryDir::max_size`].
+const DEFAULT_MAX_SIZE: usize = 256 << 20;
+
+static NEXT_INO: AtomicU64 = AtomicU64::new(1);
+
+/// A directory in a tree of files and directories which only exists in
+/// memory, for giving guests a filesystem without touching the host's.
+///
+/// Clones refer to the same directory, so a tree can be populated with
+/// [`MemoryDir::insert_file`] and [`MemoryDir::create_dir_all`] before or
+/// after it's given to a guest with
+/// [`WasiCtxBuilder::preopened_host_dir`], and inspected afterwards.
+///
+/// The files in a tree can hold at most [`MemoryDir::max_size`] bytes between
+/// them, so a guest can't make the host run out of memory.
+///
+/// Advisory locks and permission changes are not supported.
+///
+/// [`WasiCtxBuilder::preopened_host_dir`]: crate::preview2::WasiCtxBuilder::preopened_host_dir
+#[derive(Clone)]
+pub struct MemoryDir {
+    dir: Arc<DirNode>,
+    usage: Arc<Usage>,
+}
+
+impl Default for MemoryDir {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryDir {
+    /// Create an empty directory, whose files can hold up to 256 MiB in
+    /// total.
+    pub fn new() -> Self {
+        Self {
+            dir: Arc::new(DirNode::new()),
+            usage: Arc::new(Usage {
+                max: AtomicUsize::new(DEFAULT_MAX_SIZE),
+                used: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Set the most bytes that the files in this tree can hold in total.
+    ///
+    /// Writes and resizes which would go over the limit fail with
+    /// `file-too-large`. Files which already hold more than the limit are
+    /// kept, but can't grow.
+    pub fn max_size(self, bytes: usize) -> Self {
+        self.usage.max.store(bytes, Ordering::Relaxed);
+        self
+    }
+
+    /// Create the directory at `path`, along with any missing parents.
+    pub fn create_dir_all(&self, path: &str) -> anyhow::Result<()> {
+        self.dir_all(path)?;
+        Ok(())
+    }
+
+    /// Create or replace the file at `path` with `contents`, creating any
+    /// missing parent directories.
+    pub fn insert_file(&self, path: &str, contents: impl Into<Vec<u8>>) -> anyhow::Result<()> {
+        let (parent, name) = match path.rsplit_once('/') {
+            Some((parent, name)) => (self.dir_all(parent)?, name),
+            None => (Arc::clone(&self.dir), path),
+        };
+        if !is_valid_name(name) {
+            anyhow::bail!("invalid file name in path {path:?}");
+        }
+        let contents = contents.into();
+        let too_large = || anyhow::anyhow!("{path:?} doesn't fit in the directory's size limit");
+        let mut entries = parent.entries.lock().unwrap();
+        match entries.get(name) {
+            Some(Node::File(file)) => {
+                let mut data = file.data.write().unwrap();
+                file.usage
+                    .charge(data.len(), contents.len())
+                    .map_err(|_| too_large())?;
+                *data = contents;
+                file.times.lock().unwrap().modified();
+            }
+            Some(_) => anyhow::bail!("{path:?} exists and is not a file"),
+            None => {
+                let file = FileNode::new(&self.usage);
+                file.usage
+                    .charge(0, contents.len())
+                    .map_err(|_| too_large())?;
+                *file.data.write().unwrap() = contents;
+                entries.insert(name.to_owned(), Node::File(Arc::new(file)));
+            }
+        }
+        Ok(())
+    }
+
+    /// The contents of the file at `path`, if there is one. Symbolic links
+    /// are not followed.
+    pub fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        match resolve(&self.dir, path, false).ok()?.node? {
+            Node::File(file) => Some(file.data.read().unwrap().clone()),
+            _ => None,
+        }
+    }
+
+    fn dir_all(&self, path: &str) -> anyhow::Result<Arc<DirNode>> {
+        let mut dir = Arc::clone(&self.dir);
+        for name in path.split('/').filter(|c| !c.is_empty() && *c != ".") {
+            if !is_valid_name(name) {
+                anyhow::bail!("invalid directory name in path {path:?}");
+            }
+            let next = {
+                let mut entries = dir.entries.lock().unwrap();
+                match entries
+                    .entry(name.to_owned())
+                    .or_insert_with(|| Node::Dir(Arc::new(DirNode::new())))
+                {
+                    Node::Dir(next) => Arc::clone(next),
+                    _ => anyhow::bail!("{name:?} in path {path:?} is not a directory"),
+                }
+            };
+            dir = next;
+        }
+        Ok(dir)
+    }
+
+    fn lookup(&self, path_flags: PathFlags, path: &str) -> Result<Node, types::Error> {
+        resolve(&self.dir, path, symlink_follow(path_flags))?
+            .node
+            .ok_or_else(|| ErrorCode::NoEntry.into())
+    }
+}
+
+#[async_trait::async_trait]
+impl HostDir for MemoryDir {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn open_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        oflags: OpenFlags,
+        flags: DescriptorFlags,
+    ) -> Result<OpenResult, types::Error> {
+        let resolved = resolve(&self.dir, &path, symlink_follow(path_flags))?;
+        let node = match resolved.node {
+            Some(_) if oflags.contains(OpenFlags::CREATE | OpenFlags::EXCLUSIVE) => {
+                return Err(ErrorCode::Exist.into())
+            }
+            Some(node) => node,
+            None if oflags.contains(OpenFlags::CREATE) => {
+                let name = resolved.name.ok_or(ErrorCode::Exist)?;
+                let file = Arc::new(FileNode::new(&self.usage));
+                let mut entries = resolved.parent.entries.lock().unwrap();
+                let node = entries.entry(name).or_insert(Node::File(file));
+                node.clone()
+            }
+            None => return Err(ErrorCode::NoEntry.into()),
+        };
+        match node {
+            Node::Dir(dir) => {
+                if flags.contains(DescriptorFlags::WRITE) || oflags.contains(OpenFlags::TRUNCATE) {
+                    return Err(ErrorCode::IsDirectory.into());
+                }
+                Ok(OpenResult::Dir(Box::new(MemoryDir {
+                    dir,
+                    usage: Arc::clone(&self.usage),
+                })))
+            }
+            Node::File(file) => {
+                if oflags.contains(OpenFlags::DIRECTORY) {
+                    return Err(ErrorCode::NotDirectory.into());
+                }
+                if oflags.contains(OpenFlags::TRUNCATE) {
+                    file.set_len(&mut file.data.write().unwrap(), 0)?;
+                    file.times.lock().unwrap().modified();
+                }
+                Ok(OpenResult::File(Box::new(MemoryFile { file })))
+            }
+            // Like `O_NOFOLLOW`, opening a symbolic link itself is an error.
+            Node::Symlink(_) => Err(ErrorCode::Loop.into()),
+        }
+    }
+
+    async fn stat(&self) -> Result<DescriptorStat, types::Error> {
+        Ok(Node::Dir(Arc::clone(&self.dir)).stat())
+    }
+
+    async fn stat_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+    ) -> Result<DescriptorStat, types::Error> {
+        Ok(self.lookup(path_flags, &path)?.stat())
+    }
+
+    async fn metadata_hash(&self) -> Result<MetadataHashValue, types::Error> {
+        Ok(metadata_hash(MEMORY_DEV, self.dir.ino))
+    }
+
+    async fn metadata_hash_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+    ) -> Result<MetadataHashValue, types::Error> {
+        Ok(metadata_hash(
+            MEMORY_DEV,
+            self.lookup(path_flags, &path)?.ino(),
+        ))
+    }
+
+    async fn read_directory(&self) -> Result<DirectoryEntries, types::Error> {
+        let entries = self
+            .dir
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, node)| {
+                Ok(DirectoryEntry {
+                    type_: node.descriptor_type(),
+                    name: name.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    async fn set_times(&self, atim: NewTimestamp, mtim: NewTimestamp) -> Result<(), types::Error> {
+        self.dir.times.lock().unwrap().set(atim, mtim)
+    }
+
+    async fn set_times_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        atim: NewTimestamp,
+        mtim: NewTimestamp,
+    ) -> Result<(), types::Error> {
+        let node = self.lookup(path_flags, &path)?;
+        let mut times = node.times().lock().unwrap();
+        times.set(atim, mtim)
+    }
+
+    async fn create_directory_at(&self, path: String) -> Result<(), types::Error> {
+        let resolved = resolve(&self.dir, &path, false)?;
+        let name = match (resolved.node, resolved.name) {
+            (None, Some(name)) => name,
+            _ => return Err(ErrorCode::Exist.into()),
+        };
+        resolved
+            .parent
+            .insert_new(name, Node::Dir(Arc::new(DirNode::new())))
+    }
+
+    async fn link_at(
+        &self,
+        old_path: String,
+        new_dir: &dyn HostDir,
+        new_path: String,
+    ) -> Result<(), types::Error> {
+        let new_dir = MemoryDir::same_backend(new_dir)?;
+        let file = match self.lookup(PathFlags::empty(), &old_path)? {
+            Node::Dir(_) => return Err(ErrorCode::NotPermitted.into()),
+            node => node,
+        };
+        let resolved = resolve(&new_dir.dir, &new_path, false)?;
+        let name = match (resolved.node, resolved.name) {
+            (None, Some(name)) => name,
+            _ => return Err(ErrorCode::Exist.into()),
+        };
+        resolved.parent.insert_new(name, file.clone())?;
+        if let Node::File(file) = file {
+            file.nlink.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    async fn rename_at(
+        &self,
+        old_path: String,
+        new_dir: &dyn HostDir,
+        new_path: String,
+    ) -> Result<(), types::Error> {
+        let new_dir = MemoryDir::same_backend(new_dir)?;
+        let old = resolve(&self.dir, &old_path, false)?;
+        let (old_node, old_name) = match (old.node, old.name) {
+            (Some(node), Some(name)) => (node, name),
+            (None, _) => return Err(ErrorCode::NoEntry.into()),
+            (Some(_), None) => return Err(ErrorCode::Busy.into()),
+        };
+        let new = resolve(&new_dir.dir, &new_path, false)?;
+        let new_name = new.name.ok_or(ErrorCode::Busy)?;
+
+        if let Node::Dir(dir) = &old_node {
+            // A directory can't be moved inside of itself.
+            if dir.contains(&new.parent) {
+                return Err(ErrorCode::Invalid.into());
+            }
+        }
+        match (&old_node, &new.node) {
+            (_, None) => {}
+            (Node::Dir(_), Some(Node::Dir(existing))) => {
+                if !existing.entries.lock().unwrap().is_empty() {
+                    return Err(ErrorCode::NotEmpty.into());
+                }
+            }
+            (Node::Dir(_), Some(_)) => return Err(ErrorCode::NotDirectory.into()),
+            (_, Some(Node::Dir(_))) => return Err(ErrorCode::IsDirectory.into()),
+            (_, Some(_)) => {}
+        }
+
+        if Arc::ptr_eq(&old.parent, &new.parent) && old_name == new_name {
+            return Ok(());
+        }
+        let node = old.parent.entries.lock().unwrap().remove(&old_name);
+        if let Some(node) = node {
+            let replaced = new.parent.entries.lock().unwrap().insert(new_name, node);
+            if let Some(Node::File(file)) = replaced {
+                file.nlink.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    async fn symlink_at(&self, old_path: String, new_path: String) -> Result<(), types::Error> {
+        let resolved = resolve(&self.dir, &new_path, false)?;
+        let name = match (resolved.node, resolved.name) {
+            (None, Some(name)) => name,
+            _ => return Err(ErrorCode::Exist.into()),
+        };
+        let link = SymlinkNode {
+            ino: NEXT_INO.fetch_add(1, Ordering::Relaxed),
+            times: Mutex::new(Times::now()),
+            target: old_path,
+        };
+        resolved
+            .parent
+            .insert_new(name, Node::Symlink(Arc::new(link)))
+    }
+
+    async fn readlink_at(&self, path: String) -> Result<String, types::Error> {
+        match self.lookup(PathFlags::empty(), &path)? {
+            Node::Symlink(link) => Ok(link.target.clone()),
+            _ => Err(ErrorCode::Invalid.into()),
+        }
+    }
+
+    async fn remove_directory_at(&self, path: String) -> Result<(), types::Error> {
+        let resolved = resolve(&self.dir, &path, false)?;
+        let name = match (resolved.node, resolved.name) {
+            (Some(Node::Dir(dir)), Some(name)) => {
+                if !dir.entries.lock().unwrap().is_empty() {
+                    return Err(ErrorCode::NotEmpty.into());
+                }
+                name
+            }
+            (Some(Node::Dir(_)), None) => return Err(ErrorCode::Invalid.into()),
+            (Some(_), _) => return Err(ErrorCode::NotDirectory.into()),
+            (None, _) => return Err(ErrorCode::NoEntry.into()),
+        };
+        resolved.parent.entries.lock().unwrap().remove(&name);
+        Ok(())
+    }
+
+    async fn unlink_file_at(&self, path: String) -> Result<(), types::Error> {
+        let resolved = resolve(&self.dir, &path, false)?;
+        let name = match (resolved.node, resolved.name) {
+            (Some(Node::Dir(_)), _) => return Err(ErrorCode::IsDirectory.into()),
+            (Some(_), Some(name)) => name,
+            _ => return Err(ErrorCode::NoEntry.into()),
+        };
+        let removed = resolved.parent.entries.lock().unwrap().remove(&name);
+        if let Some(Node::File(file)) = removed {
+            file.nlink.fetch_sub(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+impl MemoryDir {
+    fn same_backend(dir: &dyn HostDir) -> Result<&MemoryDir, types::Error> {
+        dir.as_any()
+            .downcast_ref::<MemoryDir>()
+            .ok_or_else(|| ErrorCode::CrossDevice.into())
+    }
+}
+
+/// A file in a [`MemoryDir`].
+pub struct MemoryFile {
+    file: Arc<FileNode>,
+}
+
+#[async_trait::async_trait]
+impl HostFile for MemoryFile {
+    async fn read_at(&self, len: usize, offset: u64) -> Result<Bytes, types::Error> {
+        let data = self.file.data.read().unwrap();
+        let start = usize::try_from(offset)
+            .unwrap_or(usize::MAX)
+            .min(data.len());
+        let end = start.saturating_add(len).min(data.len());
+        Ok(Bytes::copy_from_slice(&data[start..end]))
+    }
+
+    async fn write_at(&self, buf: Bytes, offset: u64) -> Result<usize, types::Error> {
+        let offset = usize::try_from(offset)?;
+        let end = offset
+            .checked_add(buf.len())
+            .ok_or(ErrorCode::FileTooLarge)?;
+        let mut data = self.file.data.write().unwrap();
+        if data.len() < end {
+            self.file.set_len(&mut data, end)?;
+        }
+        data[offset..end].copy_from_slice(&buf);
+        self.file.times.lock().unwrap().modified();
+        Ok(buf.len())
+    }
+
+    async fn append(&self, buf: Bytes) -> Result<usize, types::Error> {
+        let mut data = self.file.data.write().unwrap();
+        let offset = data.len();
+        let end = offset
+            .checked_add(buf.len())
+            .ok_or(ErrorCode::FileTooLarge)?;
+        self.file.set_len(&mut data, end)?;
+        data[offset..end].copy_from_slice(&buf);
+        drop(data);
+        self.file.times.lock().unwrap().modified();
+        Ok(buf.len())
+    }
+
+    async fn stat(&self) -> Result<DescriptorStat, types::Error> {
+        Ok(Node::File(Arc::clone(&self.file)).stat())
+    }
+
+    async fn metadata_hash(&self) -> Result<MetadataHashValue, types::Error> {
+        Ok(metadata_hash(MEMORY_DEV, self.file.ino))
+    }
+
+    async fn set_size(&self, size: u64) -> Result<(), types::Error> {
+        let size = usize::try_from(size)?;
+        self.file
+            .set_len(&mut self.file.data.write().unwrap(), size)?;
+        self.file.times.lock().unwrap().modified();
+        Ok(())
+    }
+
+    async fn get_type(&self) -> Result<DescriptorType, types::Error> {
+        Ok(DescriptorType::RegularFile)
+    }
+
+    async fn set_times(&self, atim: NewTimestamp, mtim: NewTimestamp) -> Result<(), types::Error> {
+        self.file.times.lock().unwrap().set(atim, mtim)
+    }
+}
+
+#[derive(Clone)]
+enum Node {
+    File(Arc<FileNode>),
+    Dir(Arc<DirNode>),
+    Symlink(Arc<SymlinkNode>),
+}
+
+impl Node {
+    fn ino(&self) -> u64 {
+        match self {
+            Node::File(file) => file.ino,
+            Node::Dir(dir) => dir.ino,
+            Node::Symlink(link) => link.ino,
+        }
+    }
+
+    fn times(&self) -> &Mutex<Times> {
+        match self {
+            Node::File(file) => &file.times,
+            Node::Dir(dir) => &dir.times,
+            Node::Symlink(link) => &link.times,
+        }
+    }
+
+    fn descriptor_type(&self) -> DescriptorType {
+        match self {
+            Node::File(_) => DescriptorType::RegularFile,
+            Node::Dir(_) => DescriptorType::Directory,
+            Node::Symlink(_) => DescriptorType::SymbolicLink,
+        }
+    }
+
+    fn stat(&self) -> DescriptorStat {
+        let (link_count, size) = match self {
+            Node::File(file) => (
+                file.nlink.load(Ordering::Relaxed),
+                file.data.read().unwrap().len() as u64,
+            ),
+            Node::Dir(_) => (1, 0),
+            Node::Symlink(link) => (1, link.target.len() as u64),
+        };
+        let times = self.times().lock().unwrap();
+        DescriptorStat {
+            type_: self.descriptor_type(),
+            link_count,
+            size,
+            data_access_timestamp: datetime_from(times.accessed),
+            data_modification_timestamp: datetime_from(times.modified),
+            status_change_timestamp: datetime_from(times.changed),
+        }
+    }
+}
+
+struct Times {
+    accessed: SystemTime,
+    modified: SystemTime,
+    changed: SystemTime,
+}
+
+impl Times {
+    fn now() -> Self {
+        let now = SystemTime::now();
+        Self {
+            accessed: now,
+            modified: now,
+            changed: now,
+        }
+    }
+
+    fn modified(&mut self) {
+        self.modified = SystemTime::now();
+        self.changed = self.modified;
+    }
+
+    fn set(&mut self, atim: NewTimestamp, mtim: NewTimestamp) -> Result<(), types::Error> {
+        let time = |t: NewTimestamp| match t {
+            NewTimestamp::NoChange => Ok(None),
+            NewTimestamp::Now => Ok(Some(SystemTime::now())),
+            NewTimestamp::Timestamp(t) => systemtime_from(t).map(Some),
+        };
+        let (atim, mtim) = (time(atim)?, time(mtim)?);
+        if let Some(atim) = atim {
+            self.accessed = atim;
+        }
+        if let Some(mtim) = mtim {
+            self.modified = mtim;
+        }
+        self.changed = SystemTime::now();
+        Ok(())
+    }
+}
+
+/// How many bytes the files in a tree hold, and how many they can hold.
+struct Usage {
+    max: AtomicUsize,
+    used: AtomicUsize,
+}
+
+impl Usage {
+    /// Account for a file changing from `old` to `new` bytes long, failing if
+    /// that would take the tree over its limit.
+    fn charge(&self, old: usize, new: usize) -> Result<(), types::Error> {
+        if new <= old {
+            self.used.fetch_sub(old - new, Ordering::Relaxed);
+            return Ok(());
+        }
+        let max = self.max.load(Ordering::Relaxed);
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(new - old).filter(|used| *used <= max)
+            })
+            .map_err(|_| ErrorCode::FileTooLarge)?;
+        Ok(())
+    }
+}
+
+struct FileNode {
+    ino: u64,
+    nlink: AtomicU64,
+    times: Mutex<Times>,
+    data: RwLock<Vec<u8>>,
+    usage: Arc<Usage>,
+}
+
+impl FileNode {
+    fn new(usage: &Arc<Usage>) -> Self {
+        Self {
+            ino: NEXT_INO.fetch_add(1, Ordering::Relaxed),
+            nlink: AtomicU64::new(1),
+            times: Mutex::new(Times::now()),
+            data: RwLock::new(Vec::new()),
+            usage: Arc::clone(usage),
+        }
+    }
+
+    /// Resize `data`, this file's contents, to `len` bytes, zero-filling any
+    /// new bytes. Growing past the tree's size limit fails with
+    /// `file-too-large`, and failing to allocate with `insufficient-memory`.
+    fn set_len(&self, data: &mut Vec<u8>, len: usize) -> Result<(), types::Error> {
+        self.usage.charge(data.len(), len)?;
+        if let Some(additional) = len.checked_sub(data.len()) {
+            if data.try_reserve_exact(additional).is_err() {
+                self.usage.charge(len, data.len())?;
+                return Err(ErrorCode::InsufficientMemory.into());
+            }
+        }
+        data.resize(len, 0);
+        Ok(())
+    }
+}
+
+impl Drop for FileNode {
+    fn drop(&mut self) {
+        if let Ok(data) = self.data.get_mut() {
+            self.usage.charge(data.len(), 0).unwrap();
+        }
+    }
+}
+
+struct DirNode {
+    ino: u64,
+    times: Mutex<Times>,
+    entries: Mutex<BTreeMap<String, Node>>,
+}
+
+impl DirNode {
+    fn new() -> Self {
+        Self {
+            ino: NEXT_INO.fetch_add(1, Ordering::Relaxed),
+            times: Mutex::new(Times::now()),
+            entries: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn insert_new(&self, name: String, node: Node) -> Result<(), types::Error> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.contains_key(&name) {
+            return Err(ErrorCode::Exist.into());
+        }
+        entries.insert(name, node);
+        drop(entries);
+        self.times.lock().unwrap().modified();
+        Ok(())
+    }
+
+    /// Whether `dir` is this directory or one of its descendants.
+    fn contains(self: &Arc<Self>, dir: &Arc<DirNode>) -> bool {
+        if Arc::ptr_eq(self, dir) {
+            return true;
+        }
+        let subdirs = self
+            .entries
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|node| match node {
+                Node::Dir(subdir) => Some(Arc::clone(subdir)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        subdirs.iter().any(|subdir| subdir.contains(dir))
+    }
+}
+
+struct SymlinkNode {
+    ino: u64,
+    times: Mutex<Times>,
+    target: String,
+}
+
+struct Resolved {
+    /// The directory containing the path's final component.
+    parent: Arc<DirNode>,
+    /// The final component, or `None` if the path names `parent` itself, as
+    /// with `.` or `a/..`.
+    name: Option<String>,
+    /// What the path refers to, if it exists.
+    node: Option<Node>,
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains('/')
+}
+
+/// Resolve `path` relative to `root`, never leaving `root`. Symbolic links
+/// in all but the final component are always followed, and in the final
+/// component only if `follow` is set.
+fn resolve(root: &Arc<DirNode>, path: &str, follow: bool) -> Result<Resolved, types::Error> {
+    if path.is_empty() {
+        return Err(ErrorCode::NoEntry.into());
+    }
+    if path.starts_with('/') {
+        return Err(ErrorCode::NotPermitted.into());
+    }
+
+    let mut stack = vec![Arc::clone(root)];
+    let mut components = path
+        .split('/')
+        .filter(|c| !c.is_empty())
+        .map(str::to_owned)
+        .collect::<VecDeque<_>>();
+    let mut expansions = 0;
+
+    while let Some(component) = components.pop_front() {
+        match component.as_str() {
+            "." => continue,
+            ".." => {
+                if stack.len() == 1 {
+                    return Err(ErrorCode::NotPermitted.into());
+                }
+                stack.pop();
+                continue;
+            }
+            _ => {}
+        }
+
+        let dir = stack.last().unwrap();
+        let node = dir.entries.lock().unwrap().get(&component).cloned();
+        let is_last = components.is_empty();
+        match node {
+            Some(Node::Symlink(link)) if !is_last || follow => {
+                expansions += 1;
+                if expansions > MAX_SYMLINK_EXPANSIONS {
+                    return Err(ErrorCode::Loop.into());
+                }
+                if link.target.starts_with('/') {
+                    return Err(ErrorCode::NotPermitted.into());
+                }
+                for c in link.target.split('/').filter(|c| !c.is_empty()).rev() {
+                    components.push_front(c.to_owned());
+                }
+            }
+            node if is_last => {
+                return Ok(Resolved {
+                    parent: Arc::clone(dir),
+                    name: Some(component),
+                    node,
+                })
+            }
+            Some(Node::Dir(subdir)) => stack.push(subdir),
+            Some(_) => return Err(ErrorCode::NotDirectory.into()),
+            None => return Err(ErrorCode::NoEntry.into()),
+        }
+    }
+
+    // The path ended with `.` or `..`, so it names a directory on the stack.
+    let dir = stack.pop().unwrap();
+    Ok(Resolved {
+        parent: Arc::clone(&dir),
+        name: None,
+        node: Some(Node::Dir(dir)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    async fn read_to_string(dir: &MemoryDir, path: &str) -> String {
+        let file = match dir
+            .open_at(
+                PathFlags::SYMLINK_FOLLOW,
+                path.to_string(),
+                OpenFlags::empty(),
+                DescriptorFlags::READ,
+            )
+            .await
+            .unwrap()
+        {
+            OpenResult::File(file) => file,
+            OpenResult::Dir(_) => panic!("{path} is a directory"),
+        };
+        String::from_utf8(file.read_at(1024, 0).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn create_write_and_read() {
+        let root = MemoryDir::new();
+        root.insert_file("a/b/hello.txt", "hello").unwrap();
+        assert_eq!(read_to_string(&root, "a/./b/../b/hello.txt").await, "hello");
+
+        let file = match root
+            .open_at(
+                PathFlags::empty(),
+                "a/new.txt".to_string(),
+                OpenFlags::CREATE,
+                DescriptorFlags::WRITE,
+            )
+            .await
+            .unwrap()
+        {
+            OpenResult::File(file) => file,
+            OpenResult::Dir(_) => unreachable!(),
+        };
+        file.write_at(Bytes::from_static(b"world"), 2)
+            .await
+            .unwrap();
+        assert_eq!(root.read_file("a/new.txt").unwrap(), b"\0\0world");
+        assert_eq!(file.stat().await.unwrap().size, 7);
+
+        let names = root
+            .open_at(
+                PathFlags::empty(),
+                "a".to_string(),
+                OpenFlags::DIRECTORY,
+                DescriptorFlags::READ,
+            )
+            .await
+            .unwrap();
+        let OpenResult::Dir(a) = names else {
+            panic!("a is not a directory")
+        };
+        let names = a
+            .read_directory()
+            .await
+            .unwrap()
+            .map(|e| e.unwrap().name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["b", "new.txt"]);
+    }
+
+    #[tokio::test]
+    async fn paths_cannot_escape() {
+        let root = MemoryDir::new();
+        root.create_dir_all("sub").unwrap();
+        let sub = MemoryDir {
+            dir: match resolve(&root.dir, "sub", false).unwrap().node {
+                Some(Node::Dir(dir)) => dir,
+                _ => unreachable!(),
+            },
+            usage: Arc::clone(&root.usage),
+        };
+        root.insert_file("secret", "shh").unwrap();
+
+        let err = |r: Result<DescriptorStat, types::Error>| r.unwrap_err().downcast().unwrap();
+        assert_eq!(
+            err(sub
+                .stat_at(PathFlags::empty(), "../secret".to_string())
+                .await),
+            ErrorCode::NotPermitted
+        );
+        assert_eq!(
+            err(sub.stat_at(PathFlags::empty(), "/secret".to_string()).await),
+            ErrorCode::NotPermitted
+        );
+
+        sub.symlink_at("../secret".to_string(), "link".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            err(sub
+                .stat_at(PathFlags::SYMLINK_FOLLOW, "link".to_string())
+                .await),
+            ErrorCode::NotPermitted
+        );
+        // Following the same link from the parent stays inside of it.
+        assert_eq!(read_to_string(&root, "sub/link").await, "shh");
+    }
+
+    #[tokio::test]
+    async fn rename_and_remove() {
+        let root = MemoryDir::new();
+        root.insert_file("a/file", "contents").unwrap();
+        root.create_dir_all("b").unwrap();
+
+        let err = |r: Result<(), types::Error>| r.unwrap_err().downcast().unwrap();
+        assert_eq!(
+            err(root
+                .rename_at("a".to_string(), &root, "a/inner".to_string())
+                .await),
+            ErrorCode::Invalid
+        );
+        assert_eq!(
+            err(root.remove_directory_at("a".to_string()).await),
+            ErrorCode::NotEmpty
+        );
+
+        root.rename_at("a/file".to_string(), &root, "b/moved".to_string())
+            .await
+            .unwrap();
+        assert!(root.read_file("a/file").is_none());
+        assert_eq!(root.read_file("b/moved").unwrap(), b"contents");
+
+        root.remove_directory_at("a".to_string()).await.unwrap();
+        root.unlink_file_at("b/moved".to_string()).await.unwrap();
+        assert!(root.read_file("b/moved").is_none());
+    }
+
+    #[tokio::test]
+    async fn size_limit() {
+        let root = MemoryDir::new().max_size(10);
+        root.insert_file("a", "12345").unwrap();
+        assert!(root.insert_file("b", "123456").is_err());
+
+        let file = match root
+            .open_at(
+                PathFlags::empty(),
+                "b".to_string(),
+                OpenFlags::CREATE,
+                DescriptorFlags::WRITE,
+            )
+            .await
+            .unwrap()
+        {
+            OpenResult::File(file) => file,
+            OpenResult::Dir(_) => unreachable!(),
+        };
+        let err = |r: Result<(), types::Error>| r.unwrap_err().downcast().unwrap();
+        assert_eq!(err(file.set_size(1 << 40).await), ErrorCode::FileTooLarge);
+        assert_eq!(
+            err(file
+                .write_at(Bytes::from_static(b"x"), 1 << 40)
+                .await
+                .map(drop)),
+            ErrorCode::FileTooLarge
+        );
+        assert_eq!(root.read_file("b").unwrap(), b"");
+
+        // Up to the limit is fine, and shrinking or removing a file frees up
+        // space for others.
+        file.set_size(5).await.unwrap();
+        assert_eq!(
+            err(file.append(Bytes::from_static(b"x")).await.map(drop)),
+            ErrorCode::FileTooLarge
+        );
+        root.unlink_file_at("a".to_string()).await.unwrap();
+        file.append(Bytes::from_static(b"x")).await.unwrap();
+        assert_eq!(root.read_file("b").unwrap(), b"\0\0\0\0\0x");
+    }
+}
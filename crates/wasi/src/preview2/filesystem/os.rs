@@ -0,0 +1,715 @@
+//! The [`HostDir`] and [`HostFile`] backend for the host's own filesystem,
+//! sandboxed with `cap-std`.
+
+use super::{
+    datetime_from, metadata_hash, symlink_follow, systemtime_from, DirectoryEntries, HostDir,
+    HostFile, LockKind, OpenResult,
+};
+use crate::preview2::bindings::clocks::wall_clock;
+use crate::preview2::bindings::filesystem::types::{
+    self, AccessType, Advice, DescriptorFlags, DescriptorStat, DescriptorType, ErrorCode,
+    MetadataHashValue, Modes, NewTimestamp, OpenFlags, PathFlags,
+};
+use bytes::{Bytes, BytesMut};
+use std::any::Any;
+use std::sync::Arc;
+
+/// A directory on the host's filesystem.
+pub struct OsDir {
+    dir: Arc<cap_std::fs::Dir>,
+}
+
+impl OsDir {
+    pub fn new(dir: cap_std::fs::Dir) -> Self {
+        Self { dir: Arc::new(dir) }
+    }
+
+    /// Spawn a task on tokio's blocking thread for performing blocking
+    /// syscalls on the underlying [`cap_std::fs::Dir`].
+    async fn spawn_blocking<F, R>(&self, body: F) -> R
+    where
+        F: FnOnce(&cap_std::fs::Dir) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let d = self.dir.clone();
+        tokio::task::spawn_blocking(move || body(&d)).await.unwrap()
+    }
+
+    /// The `cap-std` directory behind `dir`, which must belong to this
+    /// backend for links and renames.
+    fn same_backend(dir: &dyn HostDir) -> Result<Arc<cap_std::fs::Dir>, types::Error> {
+        match dir.as_any().downcast_ref::<OsDir>() {
+            Some(dir) => Ok(Arc::clone(&dir.dir)),
+            None => Err(ErrorCode::CrossDevice.into()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HostDir for OsDir {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn open_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        oflags: OpenFlags,
+        flags: DescriptorFlags,
+    ) -> Result<OpenResult, types::Error> {
+        use cap_fs_ext::{FollowSymlinks, OpenOptionsFollowExt, OpenOptionsMaybeDirExt};
+        use system_interface::fs::{FdFlags, GetSetFdFlags};
+
+        let mut opts = cap_std::fs::OpenOptions::new();
+        opts.maybe_dir(true);
+
+        if oflags.contains(OpenFlags::CREATE | OpenFlags::EXCLUSIVE) {
+            opts.create_new(true);
+            opts.write(true);
+        } else if oflags.contains(OpenFlags::CREATE) {
+            opts.create(true);
+            opts.write(true);
+        }
+        if oflags.contains(OpenFlags::TRUNCATE) {
+            opts.truncate(true);
+        }
+        if flags.contains(DescriptorFlags::READ) {
+            opts.read(true);
+        }
+        if flags.contains(DescriptorFlags::WRITE) {
+            opts.write(true);
+        } else {
+            // If not opened write, open read. This way the OS lets us open
+            // the file, but we can use perms to reject use of the file later.
+            opts.read(true);
+        }
+        if symlink_follow(path_flags) {
+            opts.follow(FollowSymlinks::Yes);
+        } else {
+            opts.follow(FollowSymlinks::No);
+        }
+
+        // These flags are not yet supported in cap-std:
+        if flags.contains(DescriptorFlags::FILE_INTEGRITY_SYNC)
+            | flags.contains(DescriptorFlags::DATA_INTEGRITY_SYNC)
+            | flags.contains(DescriptorFlags::REQUESTED_WRITE_SYNC)
+        {
+            Err(ErrorCode::Unsupported)?;
+        }
+
+        // Represents each possible outcome from the spawn_blocking operation.
+        enum Opened {
+            Dir(cap_std::fs::Dir),
+            File(cap_std::fs::File),
+            NotDir,
+        }
+
+        let opened = self
+            .spawn_blocking::<_, std::io::Result<Opened>>(move |d| {
+                let mut opened = d.open_with(&path, &opts)?;
+                if opened.metadata()?.is_dir() {
+                    Ok(Opened::Dir(cap_std::fs::Dir::from_std_file(
+                        opened.into_std(),
+                    )))
+                } else if oflags.contains(OpenFlags::DIRECTORY) {
+                    Ok(Opened::NotDir)
+                } else {
+                    // FIXME cap-std needs a nonblocking open option so that files reads and writes
+                    // are nonblocking. Instead we set it after opening here:
+                    let set_fd_flags = opened.new_set_fd_flags(FdFlags::NONBLOCK)?;
+                    opened.set_fd_flags(set_fd_flags)?;
+                    Ok(Opened::File(opened))
+                }
+            })
+            .await?;
+
+        match opened {
+            Opened::Dir(dir) => Ok(OpenResult::Dir(Box::new(OsDir::new(dir)))),
+            Opened::File(file) => Ok(OpenResult::File(Box::new(OsFile::new(file)))),
+            Opened::NotDir => Err(ErrorCode::NotDirectory.into()),
+        }
+    }
+
+    async fn stat(&self) -> Result<DescriptorStat, types::Error> {
+        let meta = self.spawn_blocking(|d| d.dir_metadata()).await?;
+        Ok(descriptorstat_from(meta))
+    }
+
+    async fn stat_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+    ) -> Result<DescriptorStat, types::Error> {
+        let meta = if symlink_follow(path_flags) {
+            self.spawn_blocking(move |d| d.metadata(&path)).await?
+        } else {
+            self.spawn_blocking(move |d| d.symlink_metadata(&path))
+                .await?
+        };
+        Ok(descriptorstat_from(meta))
+    }
+
+    async fn metadata_hash(&self) -> Result<MetadataHashValue, types::Error> {
+        let meta = self.spawn_blocking(|d| d.dir_metadata()).await?;
+        Ok(calculate_metadata_hash(&meta))
+    }
+
+    async fn metadata_hash_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+    ) -> Result<MetadataHashValue, types::Error> {
+        let meta = self
+            .spawn_blocking(move |d| {
+                if symlink_follow(path_flags) {
+                    d.metadata(path)
+                } else {
+                    d.symlink_metadata(path)
+                }
+            })
+            .await?;
+        Ok(calculate_metadata_hash(&meta))
+    }
+
+    async fn read_directory(&self) -> Result<DirectoryEntries, types::Error> {
+        enum ReaddirError {
+            Io(std::io::Error),
+            IllegalSequence,
+        }
+        impl From<std::io::Error> for ReaddirError {
+            fn from(e: std::io::Error) -> ReaddirError {
+                ReaddirError::Io(e)
+            }
+        }
+
+        let entries = self
+            .spawn_blocking(|d| {
+                // Both `entries` and `metadata` perform syscalls, which is why they are done
+                // within this `block` call, rather than delay calculating the metadata
+                // for entries when they're demanded later in the iterator chain.
+                Ok::<_, std::io::Error>(
+                    d.entries()?
+                        .map(|entry| {
+                            let entry = entry?;
+                            let meta = entry.metadata()?;
+                            let type_ = descriptortype_from(meta.file_type());
+                            let name = entry
+                                .file_name()
+                                .into_string()
+                                .map_err(|_| ReaddirError::IllegalSequence)?;
+                            Ok(types::DirectoryEntry { type_, name })
+                        })
+                        .collect::<Vec<Result<types::DirectoryEntry, ReaddirError>>>(),
+                )
+            })
+            .await?
+            .into_iter();
+
+        // On windows, filter out files like `C:\DumpStack.log.tmp` which we
+        // can't get full metadata for.
+        #[cfg(windows)]
+        let entries = entries.filter(|entry| {
+            use windows_sys::Win32::Foundation::{ERROR_ACCESS_DENIED, ERROR_SHARING_VIOLATION};
+            if let Err(ReaddirError::Io(err)) = entry {
+                if err.raw_os_error() == Some(ERROR_SHARING_VIOLATION as i32)
+                    || err.raw_os_error() == Some(ERROR_ACCESS_DENIED as i32)
+                {
+                    return false;
+                }
+            }
+            true
+        });
+        let entries = entries.map(|r| match r {
+            Ok(r) => Ok(r),
+            Err(ReaddirError::Io(e)) => Err(types::Error::from(e)),
+            Err(ReaddirError::IllegalSequence) => Err(ErrorCode::IllegalByteSequence.into()),
+        });
+        Ok(Box::new(entries))
+    }
+
+    async fn get_flags(&self) -> Result<DescriptorFlags, types::Error> {
+        use system_interface::fs::GetSetFdFlags;
+        let flags = self.spawn_blocking(|d| d.get_fd_flags()).await?;
+        Ok(get_from_fdflags(flags))
+    }
+
+    async fn set_times(&self, atim: NewTimestamp, mtim: NewTimestamp) -> Result<(), types::Error> {
+        use fs_set_times::SetTimes;
+        let atim = systemtimespec_from(atim)?;
+        let mtim = systemtimespec_from(mtim)?;
+        self.spawn_blocking(|d| d.set_times(atim, mtim)).await?;
+        Ok(())
+    }
+
+    async fn set_times_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        atim: NewTimestamp,
+        mtim: NewTimestamp,
+    ) -> Result<(), types::Error> {
+        use cap_fs_ext::DirExt;
+
+        let atim = systemtimespec_from(atim)?;
+        let mtim = systemtimespec_from(mtim)?;
+        if symlink_follow(path_flags) {
+            self.spawn_blocking(move |d| {
+                d.set_times(
+                    &path,
+                    atim.map(cap_fs_ext::SystemTimeSpec::from_std),
+                    mtim.map(cap_fs_ext::SystemTimeSpec::from_std),
+                )
+            })
+            .await?;
+        } else {
+            self.spawn_blocking(move |d| {
+                d.set_symlink_times(
+                    &path,
+                    atim.map(cap_fs_ext::SystemTimeSpec::from_std),
+                    mtim.map(cap_fs_ext::SystemTimeSpec::from_std),
+                )
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn create_directory_at(&self, path: String) -> Result<(), types::Error> {
+        self.spawn_blocking(move |d| d.create_dir(&path)).await?;
+        Ok(())
+    }
+
+    async fn link_at(
+        &self,
+        old_path: String,
+        new_dir: &dyn HostDir,
+        new_path: String,
+    ) -> Result<(), types::Error> {
+        let new_dir = OsDir::same_backend(new_dir)?;
+        self.spawn_blocking(move |d| d.hard_link(&old_path, &new_dir, &new_path))
+            .await?;
+        Ok(())
+    }
+
+    async fn rename_at(
+        &self,
+        old_path: String,
+        new_dir: &dyn HostDir,
+        new_path: String,
+    ) -> Result<(), types::Error> {
+        let new_dir = OsDir::same_backend(new_dir)?;
+        self.spawn_blocking(move |d| d.rename(&old_path, &new_dir, &new_path))
+            .await?;
+        Ok(())
+    }
+
+    async fn symlink_at(&self, old_path: String, new_path: String) -> Result<(), types::Error> {
+        // On windows, Dir.symlink is provided by DirExt
+        #[cfg(windows)]
+        use cap_fs_ext::DirExt;
+
+        self.spawn_blocking(move |d| d.symlink(&old_path, &new_path))
+            .await?;
+        Ok(())
+    }
+
+    async fn readlink_at(&self, path: String) -> Result<String, types::Error> {
+        let link = self.spawn_blocking(move |d| d.read_link(&path)).await?;
+        Ok(link
+            .into_os_string()
+            .into_string()
+            .map_err(|_| ErrorCode::IllegalByteSequence)?)
+    }
+
+    async fn remove_directory_at(&self, path: String) -> Result<(), types::Error> {
+        Ok(self.spawn_blocking(move |d| d.remove_dir(&path)).await?)
+    }
+
+    async fn unlink_file_at(&self, path: String) -> Result<(), types::Error> {
+        use cap_fs_ext::DirExt;
+
+        Ok(self
+            .spawn_blocking(move |d| d.remove_file_or_symlink(&path))
+            .await?)
+    }
+
+    async fn access_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        access: AccessType,
+    ) -> Result<(), types::Error> {
+        use cap_fs_ext::{AccessModes, DirExt};
+
+        let access = match access {
+            AccessType::Exists => cap_fs_ext::AccessType::Exists,
+            AccessType::Access(modes) => {
+                let mut access_modes = AccessModes::empty();
+                if modes.contains(Modes::READABLE) {
+                    access_modes |= AccessModes::READ;
+                }
+                if modes.contains(Modes::WRITABLE) {
+                    access_modes |= AccessModes::WRITE;
+                }
+                if modes.contains(Modes::EXECUTABLE) {
+                    access_modes |= AccessModes::EXECUTE;
+                }
+                cap_fs_ext::AccessType::Access(access_modes)
+            }
+        };
+        let follow = symlink_follow(path_flags);
+        self.spawn_blocking(move |d| {
+            if follow {
+                d.access(&path, access)
+            } else {
+                d.access_symlink(&path, access)
+            }
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn change_file_permissions_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        mode: Modes,
+    ) -> Result<(), types::Error> {
+        let follow = symlink_follow(path_flags);
+        self.spawn_blocking(move |d| {
+            let meta = if follow {
+                d.metadata(&path)?
+            } else {
+                d.symlink_metadata(&path)?
+            };
+            if meta.is_dir() {
+                return Err(ErrorCode::IsDirectory.into());
+            }
+            set_permissions(d, &path, follow, meta.permissions(), mode, false)
+        })
+        .await
+    }
+
+    async fn change_directory_permissions_at(
+        &self,
+        path_flags: PathFlags,
+        path: String,
+        mode: Modes,
+    ) -> Result<(), types::Error> {
+        let follow = symlink_follow(path_flags);
+        self.spawn_blocking(move |d| {
+            let meta = if follow {
+                d.metadata(&path)?
+            } else {
+                d.symlink_metadata(&path)?
+            };
+            if !meta.is_dir() {
+                return Err(ErrorCode::NotDirectory.into());
+            }
+            set_permissions(d, &path, follow, meta.permissions(), mode, true)
+        })
+        .await
+    }
+
+    async fn sync(&self) -> Result<(), types::Error> {
+        self.spawn_blocking(|d| Ok(d.open(std::path::Component::CurDir)?.sync_all()?))
+            .await
+    }
+
+    async fn sync_data(&self) -> Result<(), types::Error> {
+        self.spawn_blocking(|d| Ok(d.open(std::path::Component::CurDir)?.sync_data()?))
+            .await
+    }
+}
+
+/// A file on the host's filesystem.
+pub struct OsFile {
+    file: Arc<cap_std::fs::File>,
+}
+
+impl OsFile {
+    pub fn new(file: cap_std::fs::File) -> Self {
+        Self {
+            file: Arc::new(file),
+        }
+    }
+
+    /// Spawn a task on tokio's blocking thread for performing blocking
+    /// syscalls on the underlying [`cap_std::fs::File`].
+    async fn spawn_blocking<F, R>(&self, body: F) -> R
+    where
+        F: FnOnce(&cap_std::fs::File) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let f = self.file.clone();
+        tokio::task::spawn_blocking(move || body(&f)).await.unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl HostFile for OsFile {
+    async fn read_at(&self, len: usize, offset: u64) -> Result<Bytes, types::Error> {
+        use system_interface::fs::FileIoExt;
+
+        let buf = self
+            .spawn_blocking(move |f| {
+                let mut buf = BytesMut::zeroed(len);
+                loop {
+                    match f.read_at(&mut buf, offset) {
+                        Ok(n) => {
+                            buf.truncate(n);
+                            return Ok(buf);
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                        Err(e) => return Err(e),
+                    }
+                }
+            })
+            .await?;
+        Ok(buf.freeze())
+    }
+
+    async fn write_at(&self, buf: Bytes, offset: u64) -> Result<usize, types::Error> {
+        use system_interface::fs::FileIoExt;
+
+        Ok(self
+            .spawn_blocking(move |f| f.write_at(&buf, offset))
+            .await?)
+    }
+
+    async fn append(&self, buf: Bytes) -> Result<usize, types::Error> {
+        use system_interface::fs::FileIoExt;
+
+        Ok(self.spawn_blocking(move |f| f.append(&buf)).await?)
+    }
+
+    async fn stat(&self) -> Result<DescriptorStat, types::Error> {
+        let meta = self.spawn_blocking(|f| f.metadata()).await?;
+        Ok(descriptorstat_from(meta))
+    }
+
+    async fn metadata_hash(&self) -> Result<MetadataHashValue, types::Error> {
+        let meta = self.spawn_blocking(|f| f.metadata()).await?;
+        Ok(calculate_metadata_hash(&meta))
+    }
+
+    async fn set_size(&self, size: u64) -> Result<(), types::Error> {
+        self.spawn_blocking(move |f| f.set_len(size)).await?;
+        Ok(())
+    }
+
+    async fn get_flags(&self) -> Result<DescriptorFlags, types::Error> {
+        use system_interface::fs::GetSetFdFlags;
+        let flags = self.spawn_blocking(|f| f.get_fd_flags()).await?;
+        Ok(get_from_fdflags(flags))
+    }
+
+    async fn set_times(&self, atim: NewTimestamp, mtim: NewTimestamp) -> Result<(), types::Error> {
+        use fs_set_times::SetTimes;
+        let atim = systemtimespec_from(atim)?;
+        let mtim = systemtimespec_from(mtim)?;
+        self.spawn_blocking(|f| f.set_times(atim, mtim)).await?;
+        Ok(())
+    }
+
+    async fn advise(&self, offset: u64, len: u64, advice: Advice) -> Result<(), types::Error> {
+        use system_interface::fs::{Advice as A, FileIoExt};
+
+        let advice = match advice {
+            Advice::Normal => A::Normal,
+            Advice::Sequential => A::Sequential,
+            Advice::Random => A::Random,
+            Advice::WillNeed => A::WillNeed,
+            Advice::DontNeed => A::DontNeed,
+            Advice::NoReuse => A::NoReuse,
+        };
+        self.spawn_blocking(move |f| f.advise(offset, len, advice))
+            .await?;
+        Ok(())
+    }
+
+    async fn lock(&self, kind: LockKind, blocking: bool) -> Result<(), types::Error> {
+        #[cfg(unix)]
+        {
+            use rustix::fs::{flock, FlockOperation};
+
+            let op = match (kind, blocking) {
+                (LockKind::Shared, true) => FlockOperation::LockShared,
+                (LockKind::Shared, false) => FlockOperation::NonBlockingLockShared,
+                (LockKind::Exclusive, true) => FlockOperation::LockExclusive,
+                (LockKind::Exclusive, false) => FlockOperation::NonBlockingLockExclusive,
+                (LockKind::Unlock, _) => FlockOperation::Unlock,
+            };
+            self.spawn_blocking(move |f| flock(f, op).map_err(std::io::Error::from))
+                .await?;
+            Ok(())
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = (kind, blocking);
+            Err(ErrorCode::Unsupported.into())
+        }
+    }
+
+    async fn sync(&self) -> Result<(), types::Error> {
+        match self.spawn_blocking(|f| f.sync_all()).await {
+            Ok(()) => Ok(()),
+            // On windows, `sync_data` uses `FileFlushBuffers` which fails with
+            // `ERROR_ACCESS_DENIED` if the file is not upen for writing. Ignore
+            // this error, for POSIX compatibility.
+            #[cfg(windows)]
+            Err(e)
+                if e.raw_os_error()
+                    == Some(windows_sys::Win32::Foundation::ERROR_ACCESS_DENIED as _) =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn sync_data(&self) -> Result<(), types::Error> {
+        match self.spawn_blocking(|f| f.sync_data()).await {
+            Ok(()) => Ok(()),
+            // On windows, `sync_data` uses `FileFlushBuffers` which fails with
+            // `ERROR_ACCESS_DENIED` if the file is not upen for writing. Ignore
+            // this error, for POSIX compatibility.
+            #[cfg(windows)]
+            Err(e)
+                if e.raw_os_error()
+                    == Some(windows_sys::Win32::Foundation::ERROR_ACCESS_DENIED as _) =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+fn get_from_fdflags(flags: system_interface::fs::FdFlags) -> DescriptorFlags {
+    use system_interface::fs::FdFlags;
+    let mut out = DescriptorFlags::empty();
+    if flags.contains(FdFlags::DSYNC) {
+        out |= DescriptorFlags::REQUESTED_WRITE_SYNC;
+    }
+    if flags.contains(FdFlags::RSYNC) {
+        out |= DescriptorFlags::DATA_INTEGRITY_SYNC;
+    }
+    if flags.contains(FdFlags::SYNC) {
+        out |= DescriptorFlags::FILE_INTEGRITY_SYNC;
+    }
+    out
+}
+
+/// Set the permissions of `path` in `d` from the WASI `mode`, keeping
+/// whatever bits `mode` doesn't describe from `perms`.
+///
+/// On Unix, `readable` and `executable` apply to everyone while `writable`
+/// only applies to the owner, matching the usual `022` umask. For directories
+/// `readable` also grants search permission.
+fn set_permissions(
+    d: &cap_std::fs::Dir,
+    path: &str,
+    follow: bool,
+    mut perms: cap_std::fs::Permissions,
+    mode: Modes,
+    is_dir: bool,
+) -> Result<(), types::Error> {
+    use cap_fs_ext::DirExt;
+
+    #[cfg(unix)]
+    {
+        use cap_std::fs::PermissionsExt;
+
+        let mut bits = perms.mode() & !0o777;
+        if mode.contains(Modes::READABLE) {
+            bits |= if is_dir { 0o555 } else { 0o444 };
+        }
+        if mode.contains(Modes::WRITABLE) {
+            bits |= 0o200;
+        }
+        if mode.contains(Modes::EXECUTABLE) {
+            bits |= 0o111;
+        }
+        perms.set_mode(bits);
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = is_dir;
+        perms.set_readonly(!mode.contains(Modes::WRITABLE));
+    }
+
+    if follow {
+        d.set_permissions(path, perms)?;
+    } else {
+        d.set_symlink_permissions(path, perms)?;
+    }
+    Ok(())
+}
+
+fn calculate_metadata_hash(meta: &cap_std::fs::Metadata) -> MetadataHashValue {
+    use cap_fs_ext::MetadataExt;
+    metadata_hash(meta.dev(), meta.ino())
+}
+
+fn descriptortype_from(ft: cap_std::fs::FileType) -> DescriptorType {
+    use cap_fs_ext::FileTypeExt;
+    if ft.is_dir() {
+        DescriptorType::Directory
+    } else if ft.is_symlink() {
+        DescriptorType::SymbolicLink
+    } else if ft.is_block_device() {
+        DescriptorType::BlockDevice
+    } else if ft.is_char_device() {
+        DescriptorType::CharacterDevice
+    } else if ft.is_file() {
+        DescriptorType::RegularFile
+    } else {
+        DescriptorType::Unknown
+    }
+}
+
+fn systemtimespec_from(
+    t: NewTimestamp,
+) -> Result<Option<fs_set_times::SystemTimeSpec>, types::Error> {
+    use fs_set_times::SystemTimeSpec;
+    match t {
+        NewTimestamp::NoChange => Ok(None),
+        NewTimestamp::Now => Ok(Some(SystemTimeSpec::SymbolicNow)),
+        NewTimestamp::Timestamp(st) => Ok(Some(SystemTimeSpec::Absolute(systemtime_from(st)?))),
+    }
+}
+
+fn descriptorstat_from(meta: cap_std::fs::Metadata) -> DescriptorStat {
+    use cap_fs_ext::MetadataExt;
+    DescriptorStat {
+        type_: descriptortype_from(meta.file_type()),
+        link_count: meta.nlink(),
+        size: meta.len(),
+        // FIXME change the wit to make these timestamps optional
+        data_access_timestamp: meta
+            .accessed()
+            .map(|t| datetime_from(t.into_std()))
+            .unwrap_or(wall_clock::Datetime {
+                seconds: 0,
+                nanoseconds: 0,
+            }),
+        data_modification_timestamp: meta
+            .modified()
+            .map(|t| datetime_from(t.into_std()))
+            .unwrap_or(wall_clock::Datetime {
+                seconds: 0,
+                nanoseconds: 0,
+            }),
+        status_change_timestamp: meta
+            .created()
+            .map(|t| datetime_from(t.into_std()))
+            .unwrap_or(wall_clock::Datetime {
+                seconds: 0,
+                nanoseconds: 0,
+            }),
+    }
+}
@@ -1,5 +1,3 @@
-#![allow(unused_variables)]
-
 use crate::preview2::bindings::{
     clocks::monotonic_clock::{self, Instant},
     clocks::timezone::{self, Timezone, TimezoneDisplay},
@@ -7,7 +5,7 @@ use crate::preview2::bindings::{
     poll::poll::Pollable,
 };
 use crate::preview2::{HostPollable, TablePollableExt, WasiView};
-use cap_std::time::SystemTime;
+use cap_std::time::{Duration, SystemTime};
 
 impl TryFrom<SystemTime> for Datetime {
     type Error = anyhow::Error;
@@ -51,7 +49,6 @@ impl<T: WasiView> monotonic_clock::Host for T {
     }
 
     fn subscribe(&mut self, when: Instant, absolute: bool) -> anyhow::Result<Pollable> {
-        use std::time::Duration;
         // Calculate time relative to clock object, which may not have the same zero
         // point as tokio Inst::now()
         let clock_now = self.ctx().monotonic_clock.now();
@@ -92,16 +89,24 @@ impl<T: WasiView> monotonic_clock::Host for T {
     }
 }
 
+// There's no way yet for guests to obtain a timezone other than by
+// fabricating a handle, so every handle refers to the context's timezone.
 impl<T: WasiView> timezone::Host for T {
-    fn display(&mut self, timezone: Timezone, when: Datetime) -> anyhow::Result<TimezoneDisplay> {
-        todo!("timezone display is not implemented")
+    fn display(&mut self, _timezone: Timezone, when: Datetime) -> anyhow::Result<TimezoneDisplay> {
+        Ok(self
+            .ctx()
+            .timezone
+            .display(Duration::from_secs(when.seconds)))
     }
 
-    fn utc_offset(&mut self, timezone: Timezone, when: Datetime) -> anyhow::Result<i32> {
-        todo!("timezone utc_offset is not implemented")
+    fn utc_offset(&mut self, _timezone: Timezone, when: Datetime) -> anyhow::Result<i32> {
+        Ok(self
+            .ctx()
+            .timezone
+            .utc_offset(Duration::from_secs(when.seconds)))
     }
 
-    fn drop_timezone(&mut self, timezone: Timezone) -> anyhow::Result<()> {
-        todo!("timezone drop is not implemented")
+    fn drop_timezone(&mut self, _timezone: Timezone) -> anyhow::Result<()> {
+        Ok(())
     }
 }
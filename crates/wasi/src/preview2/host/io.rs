@@ -22,6 +22,100 @@ impl From<StreamState> for streams::StreamStatus {
 
 const ZEROS: &[u8] = &[0; 4 * 1024 * 1024];
 
+/// The most bytes that `splice` and `forward` hold in memory at once.
+const SPLICE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Moves up to `len` bytes from `src` to `dst`, returning the number of bytes
+/// moved along with the status of `src` and of `dst`.
+///
+/// Reading from `src` only waits for data when `blocking` is set. Everything
+/// that is read is then written to `dst`, waiting for it to become ready as
+/// needed, so that backpressure from `dst` never causes data to be dropped.
+///
+/// Nothing is read if `dst` has already ended. Should `dst` end while the
+/// bytes are being written, the transfer is cut short: only the bytes that
+/// were written are counted, and `dst` is reported as ended.
+async fn splice<T: WasiView>(
+    host: &mut T,
+    dst: OutputStream,
+    src: InputStream,
+    len: u64,
+    blocking: bool,
+) -> anyhow::Result<Result<(u64, streams::StreamStatus, streams::StreamStatus), ()>> {
+    use streams::Host;
+
+    match output_ended(host, dst)? {
+        Ok(false) => {}
+        Ok(true) => {
+            return Ok(Ok((
+                0,
+                streams::StreamStatus::Open,
+                streams::StreamStatus::Ended,
+            )))
+        }
+        Err(()) => return Ok(Err(())),
+    }
+
+    let len = len.min(SPLICE_BUFFER_SIZE as u64);
+    let read = if blocking {
+        host.blocking_read(src, len).await?
+    } else {
+        host.read(src, len).await?
+    };
+    let (bytes, read_status) = match read {
+        Ok(r) => r,
+        Err(()) => return Ok(Err(())),
+    };
+    if bytes.is_empty() {
+        return Ok(Ok((0, read_status, streams::StreamStatus::Open)));
+    }
+
+    let nread = bytes.len() as u64;
+    let (nwritten, write_status) = match host.blocking_write(dst, bytes).await? {
+        Ok(r) => r,
+        Err(()) => return Ok(Err(())),
+    };
+    if nwritten < nread {
+        tracing::debug!(
+            "splice: output stream ended after {nwritten} of {nread} bytes were written"
+        );
+    }
+    Ok(Ok((nwritten, read_status, write_status)))
+}
+
+/// Checks whether `dst` has ended, without writing anything to it.
+fn output_ended<T: WasiView>(host: &mut T, dst: OutputStream) -> anyhow::Result<Result<bool, ()>> {
+    match host.table_mut().get_internal_output_stream_mut(dst)? {
+        InternalOutputStream::Host(s) => {
+            match HostOutputStream::write(s.as_mut(), bytes::Bytes::new()) {
+                Ok((_, state)) => Ok(Ok(state == StreamState::Closed)),
+                Err(e) => {
+                    if let Some(e) = e.downcast_ref::<StreamRuntimeError>() {
+                        tracing::debug!("stream runtime error: {e:?}");
+                        Ok(Err(()))
+                    } else {
+                        Err(e)
+                    }
+                }
+            }
+        }
+        // Writing to a file never ends the stream, however far it extends the
+        // file.
+        InternalOutputStream::File(_) => Ok(Ok(false)),
+    }
+}
+
+/// The status reported by `splice`: the input's, unless the output ended.
+fn splice_status(
+    read_status: streams::StreamStatus,
+    write_status: streams::StreamStatus,
+) -> streams::StreamStatus {
+    match write_status {
+        streams::StreamStatus::Ended => write_status,
+        streams::StreamStatus::Open => read_status,
+    }
+}
+
 #[async_trait::async_trait]
 impl<T: WasiView> streams::Host for T {
     async fn drop_input_stream(&mut self, stream: InputStream) -> anyhow::Result<()> {
@@ -337,71 +431,44 @@ impl<T: WasiView> streams::Host for T {
 
     async fn splice(
         &mut self,
-        _src: InputStream,
-        _dst: OutputStream,
-        _len: u64,
+        dst: OutputStream,
+        src: InputStream,
+        len: u64,
     ) -> anyhow::Result<Result<(u64, streams::StreamStatus), ()>> {
-        // TODO: We can't get two streams at the same time because they both
-        // carry the exclusive lifetime of `ctx`. When [`get_many_mut`] is
-        // stabilized, that could allow us to add a `get_many_stream_mut` or
-        // so which lets us do this.
-        //
-        // [`get_many_mut`]: https://doc.rust-lang.org/stable/std/collections/hash_map/struct.HashMap.html#method.get_many_mut
-        /*
-        let s: &mut Box<dyn crate::InputStream> = ctx
-            .table_mut()
-            .get_input_stream_mut(src)
-            ?;
-        let d: &mut Box<dyn crate::OutputStream> = ctx
-            .table_mut()
-            .get_output_stream_mut(dst)
-            ?;
-
-        let bytes_spliced: u64 = s.splice(&mut **d, len).await?;
-
-        Ok(bytes_spliced)
-        */
-        todo!("stream splice is not implemented")
+        Ok(splice(self, dst, src, len, false)
+            .await?
+            .map(|(n, read, write)| (n, splice_status(read, write))))
     }
 
     async fn blocking_splice(
         &mut self,
-        _src: InputStream,
-        _dst: OutputStream,
-        _len: u64,
+        dst: OutputStream,
+        src: InputStream,
+        len: u64,
     ) -> anyhow::Result<Result<(u64, streams::StreamStatus), ()>> {
-        // TODO: once splice is implemented, figure out what the blocking semantics are for waiting
-        // on src and dest here.
-        todo!("stream splice is not implemented")
+        Ok(splice(self, dst, src, len, true)
+            .await?
+            .map(|(n, read, write)| (n, splice_status(read, write))))
     }
 
     async fn forward(
         &mut self,
-        _src: InputStream,
-        _dst: OutputStream,
+        dst: OutputStream,
+        src: InputStream,
     ) -> anyhow::Result<Result<(u64, streams::StreamStatus), ()>> {
-        // TODO: We can't get two streams at the same time because they both
-        // carry the exclusive lifetime of `ctx`. When [`get_many_mut`] is
-        // stabilized, that could allow us to add a `get_many_stream_mut` or
-        // so which lets us do this.
-        //
-        // [`get_many_mut`]: https://doc.rust-lang.org/stable/std/collections/hash_map/struct.HashMap.html#method.get_many_mut
-        /*
-        let s: &mut Box<dyn crate::InputStream> = ctx
-            .table_mut()
-            .get_input_stream_mut(src)
-            ?;
-        let d: &mut Box<dyn crate::OutputStream> = ctx
-            .table_mut()
-            .get_output_stream_mut(dst)
-            ?;
-
-        let bytes_spliced: u64 = s.splice(&mut **d, len).await?;
-
-        Ok(bytes_spliced)
-        */
-
-        todo!("stream forward is not implemented")
+        let mut total: u64 = 0;
+        loop {
+            let (n, read, write) =
+                match splice(self, dst, src, SPLICE_BUFFER_SIZE as u64, true).await? {
+                    Ok(r) => r,
+                    Err(()) => return Ok(Err(())),
+                };
+            total += n;
+            let ended = |s: streams::StreamStatus| matches!(s, streams::StreamStatus::Ended);
+            if ended(read) || ended(write) {
+                return Ok(Ok((total, write)));
+            }
+        }
     }
 
     async fn subscribe_to_input_stream(&mut self, stream: InputStream) -> anyhow::Result<Pollable> {
@@ -561,28 +628,28 @@ pub mod sync {
 
         fn splice(
             &mut self,
-            src: InputStream,
             dst: OutputStream,
+            src: InputStream,
             len: u64,
         ) -> anyhow::Result<Result<(u64, streams::StreamStatus), ()>> {
-            in_tokio(async { AsyncHost::splice(self, src, dst, len).await }).map(xform)
+            in_tokio(async { AsyncHost::splice(self, dst, src, len).await }).map(xform)
         }
 
         fn blocking_splice(
             &mut self,
-            src: InputStream,
             dst: OutputStream,
+            src: InputStream,
             len: u64,
         ) -> anyhow::Result<Result<(u64, streams::StreamStatus), ()>> {
-            in_tokio(async { AsyncHost::blocking_splice(self, src, dst, len).await }).map(xform)
+            in_tokio(async { AsyncHost::blocking_splice(self, dst, src, len).await }).map(xform)
         }
 
         fn forward(
             &mut self,
-            src: InputStream,
             dst: OutputStream,
+            src: InputStream,
         ) -> anyhow::Result<Result<(u64, streams::StreamStatus), ()>> {
-            in_tokio(async { AsyncHost::forward(self, src, dst).await }).map(xform)
+            in_tokio(async { AsyncHost::forward(self, dst, src).await }).map(xform)
         }
 
         fn subscribe_to_input_stream(&mut self, stream: InputStream) -> anyhow::Result<Pollable> {
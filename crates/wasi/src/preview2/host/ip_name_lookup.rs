@@ -0,0 +1,111 @@
+use crate::preview2::bindings::{
+    poll::poll::Pollable,
+    sockets::ip_name_lookup::{self, ResolveAddressStream},
+    sockets::network::{self, ErrorCode, IpAddress, IpAddressFamily, Network},
+};
+use crate::preview2::ip_name_lookup::{HostResolveAddressStream, TableResolveAddressStreamExt};
+use crate::preview2::network::TableNetworkExt;
+use crate::preview2::poll::TablePollableExt;
+use crate::preview2::{HostPollable, PollableFuture, WasiView};
+use std::any::Any;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+impl<T: WasiView> ip_name_lookup::Host for T {
+    fn resolve_addresses(
+        &mut self,
+        network: Network,
+        name: String,
+        address_family: Option<IpAddressFamily>,
+        // `HostResolver`s have no notion of which addresses can currently be
+        // connected to, so every address they return is handed to the guest
+        // and this flag has no effect. The spec leaves what counts as
+        // "available" up to the runtime.
+        _include_unavailable: bool,
+    ) -> Result<ResolveAddressStream, network::Error> {
+        // Resolving names requires a network, even though it's otherwise
+        // unused here.
+        self.table().get_network(network)?;
+
+        let stream = match parse_name(&name)? {
+            Name::Ip(addr) => HostResolveAddressStream::ready(vec![addr], address_family),
+            Name::Domain(name) => {
+                let resolver = Arc::clone(&self.ctx().resolver);
+                HostResolveAddressStream::resolve(resolver, name, address_family)
+            }
+        };
+
+        Ok(self.table_mut().push_resolve_address_stream(stream)?)
+    }
+
+    fn resolve_next_address(
+        &mut self,
+        this: ResolveAddressStream,
+    ) -> Result<Option<IpAddress>, network::Error> {
+        let stream = self.table_mut().get_resolve_address_stream_mut(this)?;
+        Ok(stream.next_address()?.map(IpAddress::from))
+    }
+
+    fn drop_resolve_address_stream(
+        &mut self,
+        this: ResolveAddressStream,
+    ) -> Result<(), anyhow::Error> {
+        // Dropping a stream that is still resolving detaches the resolver
+        // task; its result is discarded.
+        self.table_mut().delete_resolve_address_stream(this)?;
+        Ok(())
+    }
+
+    fn subscribe(&mut self, this: ResolveAddressStream) -> anyhow::Result<Pollable> {
+        fn make_resolve_address_stream_future<'a>(stream: &'a mut dyn Any) -> PollableFuture<'a> {
+            let stream = stream
+                .downcast_mut::<HostResolveAddressStream>()
+                .expect("downcast to HostResolveAddressStream failed");
+            Box::pin(async move {
+                stream.ready_wait().await;
+                Ok(())
+            })
+        }
+
+        let pollable = HostPollable::TableEntry {
+            index: this,
+            make_future: make_resolve_address_stream_future,
+        };
+
+        Ok(self.table_mut().push_host_pollable(pollable)?)
+    }
+}
+
+enum Name {
+    Ip(IpAddr),
+    Domain(String),
+}
+
+/// Parse a name passed to `resolve-addresses`, which is either an IP
+/// address literal or a domain name.
+fn parse_name(name: &str) -> Result<Name, ErrorCode> {
+    // Accept IPv6 literals in URL form, e.g. `[::1]`, too.
+    let literal = name
+        .strip_prefix('[')
+        .and_then(|name| name.strip_suffix(']'))
+        .unwrap_or(name);
+    if let Ok(addr) = literal.parse::<IpAddr>() {
+        return Ok(Name::Ip(addr));
+    }
+
+    // A domain name of at most 253 characters, made of non-empty labels of
+    // at most 63 characters.
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    };
+    let domain = name.strip_suffix('.').unwrap_or(name);
+    if domain.len() > 253 || !domain.split('.').all(valid_label) {
+        return Err(ErrorCode::InvalidName);
+    }
+
+    Ok(Name::Domain(domain.to_owned()))
+}
@@ -0,0 +1,13 @@
+use crate::preview2::bindings::logging::logging::{self, Level};
+use crate::preview2::logging::severity;
+use crate::preview2::WasiView;
+
+impl<T: WasiView> logging::Host for T {
+    fn log(&mut self, level: Level, context: String, message: String) -> anyhow::Result<()> {
+        let ctx = self.ctx();
+        if severity(level) >= severity(ctx.min_log_level) {
+            ctx.logger.log(level, &context, &message);
+        }
+        Ok(())
+    }
+}
@@ -4,7 +4,11 @@ mod exit;
 pub(crate) mod filesystem;
 mod instance_network;
 mod io;
+mod ip_name_lookup;
+mod logging;
 mod network;
 mod random;
 mod tcp;
 mod tcp_create_socket;
+mod udp;
+mod udp_create_socket;
@@ -1,6 +1,6 @@
 use crate::preview2::bindings::sockets::network::{
-    self, ErrorCode, IpAddressFamily, IpSocketAddress, Ipv4Address, Ipv4SocketAddress, Ipv6Address,
-    Ipv6SocketAddress,
+    self, ErrorCode, IpAddress, IpAddressFamily, IpSocketAddress, Ipv4Address, Ipv4SocketAddress,
+    Ipv6Address, Ipv6SocketAddress,
 };
 use crate::preview2::network::TableNetworkExt;
 use crate::preview2::{TableError, WasiView};
@@ -95,6 +95,15 @@ impl From<std::net::SocketAddr> for IpSocketAddress {
     }
 }
 
+impl From<std::net::IpAddr> for IpAddress {
+    fn from(addr: std::net::IpAddr) -> Self {
+        match addr {
+            std::net::IpAddr::V4(v4) => Self::Ipv4(from_ipv4_addr(v4)),
+            std::net::IpAddr::V6(v6) => Self::Ipv6(from_ipv6_addr(v6)),
+        }
+    }
+}
+
 impl From<Ipv4SocketAddress> for std::net::SocketAddrV4 {
     fn from(addr: Ipv4SocketAddress) -> Self {
         Self::new(to_ipv4_addr(addr.address), addr.port)
@@ -0,0 +1,467 @@
+use crate::preview2::bindings::{
+    poll::poll::Pollable,
+    sockets::network::{self, ErrorCode, IpAddressFamily, IpSocketAddress, Network},
+    sockets::udp::{self, Datagram},
+};
+use crate::preview2::network::TableNetworkExt;
+use crate::preview2::poll::TablePollableExt;
+use crate::preview2::udp::{HostUdpSocket, HostUdpState, TableUdpSocketExt};
+use crate::preview2::{HostPollable, PollableFuture, WasiView};
+use cap_net_ext::{AddressFamily, PoolExt};
+use io_lifetimes::AsSocketlike;
+use rustix::io::Errno;
+use rustix::net::sockopt;
+use std::any::Any;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use tokio::io::Interest;
+#[cfg(not(unix))]
+use tokio::task::spawn_blocking;
+
+/// The largest datagram that can be received; the maximum size of a UDP
+/// payload over IPv4.
+const MAX_UDP_DATAGRAM_SIZE: usize = 65507;
+
+impl<T: WasiView> udp::Host for T {
+    fn start_bind(
+        &mut self,
+        this: udp::UdpSocket,
+        network: Network,
+        local_address: IpSocketAddress,
+    ) -> Result<(), network::Error> {
+        let table = self.table_mut();
+        let socket = table.get_udp_socket(this)?;
+
+        match socket.udp_state {
+            HostUdpState::Default => {}
+            HostUdpState::BindStarted | HostUdpState::ConnectReady => {
+                return Err(ErrorCode::ConcurrencyConflict.into())
+            }
+            HostUdpState::Bound | HostUdpState::Connected => {
+                return Err(ErrorCode::AlreadyBound.into())
+            }
+        }
+
+        check_family(socket.family, &local_address)?;
+
+        let network = table.get_network(network)?;
+        let binder = network.0.udp_binder(local_address)?;
+        let pool = network.0.clone();
+
+        // Perform the OS bind call.
+        binder.bind_existing_udp_socket(socket.udp_socket())?;
+
+        let socket = table.get_udp_socket_mut(this)?;
+        socket.udp_state = HostUdpState::BindStarted;
+        socket.pool = Some(pool);
+
+        Ok(())
+    }
+
+    fn finish_bind(&mut self, this: udp::UdpSocket) -> Result<(), network::Error> {
+        let table = self.table_mut();
+        let socket = table.get_udp_socket_mut(this)?;
+
+        match socket.udp_state {
+            HostUdpState::BindStarted => {}
+            _ => return Err(ErrorCode::NotInProgress.into()),
+        }
+
+        socket.udp_state = HostUdpState::Bound;
+
+        Ok(())
+    }
+
+    fn start_connect(
+        &mut self,
+        this: udp::UdpSocket,
+        network: Network,
+        remote_address: IpSocketAddress,
+    ) -> Result<(), network::Error> {
+        let table = self.table_mut();
+        let socket = table.get_udp_socket(this)?;
+
+        // Unlike TCP, a UDP socket may be connected whether or not it's
+        // bound, and may be reconnected to a different remote address.
+        match socket.udp_state {
+            HostUdpState::Default | HostUdpState::Bound | HostUdpState::Connected => {}
+            HostUdpState::BindStarted | HostUdpState::ConnectReady => {
+                return Err(ErrorCode::ConcurrencyConflict.into())
+            }
+        }
+
+        check_family(socket.family, &remote_address)?;
+        check_remote_address(&remote_address)?;
+
+        let network = table.get_network(network)?;
+        let connecter = network.0.udp_connecter(remote_address)?;
+        let pool = network.0.clone();
+
+        // Perform the OS connect call. For UDP this only records the remote
+        // address and never blocks.
+        connecter.connect_existing_udp_socket(socket.udp_socket())?;
+
+        let socket = table.get_udp_socket_mut(this)?;
+        socket.udp_state = HostUdpState::ConnectReady;
+        socket.pool = Some(pool);
+
+        Ok(())
+    }
+
+    fn finish_connect(&mut self, this: udp::UdpSocket) -> Result<(), network::Error> {
+        let table = self.table_mut();
+        let socket = table.get_udp_socket_mut(this)?;
+
+        match socket.udp_state {
+            HostUdpState::ConnectReady => {}
+            _ => return Err(ErrorCode::NotInProgress.into()),
+        }
+
+        socket.udp_state = HostUdpState::Connected;
+
+        Ok(())
+    }
+
+    fn receive(
+        &mut self,
+        this: udp::UdpSocket,
+        max_results: u64,
+    ) -> Result<Vec<Datagram>, network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+
+        match socket.udp_state {
+            HostUdpState::Bound | HostUdpState::Connected => {}
+            _ => return Err(ErrorCode::NotBound.into()),
+        }
+
+        let udp_socket = socket
+            .udp_socket()
+            .as_socketlike_view::<std::net::UdpSocket>();
+
+        let mut datagrams = Vec::new();
+        let mut buf = vec![0; MAX_UDP_DATAGRAM_SIZE];
+        while (datagrams.len() as u64) < max_results {
+            match udp_socket.recv_from(&mut buf) {
+                Ok((size, remote_address)) => datagrams.push(Datagram {
+                    data: buf[..size].to_vec(),
+                    remote_address: remote_address.into(),
+                }),
+                // Return whatever has been received so far, and only report
+                // an error if nothing was.
+                Err(_) if !datagrams.is_empty() => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(datagrams)
+    }
+
+    fn send(
+        &mut self,
+        this: udp::UdpSocket,
+        datagrams: Vec<Datagram>,
+    ) -> Result<u64, network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+
+        let pool = match (&socket.udp_state, &socket.pool) {
+            (HostUdpState::Bound | HostUdpState::Connected, Some(pool)) => pool,
+            _ => return Err(ErrorCode::NotBound.into()),
+        };
+
+        let udp_socket = socket
+            .udp_socket()
+            .as_socketlike_view::<std::net::UdpSocket>();
+        let connected_to = match socket.udp_state {
+            HostUdpState::Connected => Some(udp_socket.peer_addr()?),
+            _ => None,
+        };
+
+        let mut count = 0;
+        for datagram in datagrams {
+            let result = send_one(socket.family, pool, &udp_socket, connected_to, datagram);
+            match result {
+                Ok(()) => count += 1,
+                // As with `receive`, an error is only reported if no
+                // datagram was sent at all.
+                Err(_) if count > 0 => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(count)
+    }
+
+    fn local_address(&mut self, this: udp::UdpSocket) -> Result<IpSocketAddress, network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+
+        match socket.udp_state {
+            HostUdpState::Default | HostUdpState::BindStarted => {
+                return Err(ErrorCode::NotBound.into())
+            }
+            _ => {}
+        }
+
+        let addr = socket
+            .udp_socket()
+            .as_socketlike_view::<std::net::UdpSocket>()
+            .local_addr()?;
+        Ok(addr.into())
+    }
+
+    fn remote_address(&mut self, this: udp::UdpSocket) -> Result<IpSocketAddress, network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+
+        match socket.udp_state {
+            HostUdpState::Connected => {}
+            _ => return Err(ErrorCode::NotConnected.into()),
+        }
+
+        let addr = socket
+            .udp_socket()
+            .as_socketlike_view::<std::net::UdpSocket>()
+            .peer_addr()?;
+        Ok(addr.into())
+    }
+
+    fn address_family(&mut self, this: udp::UdpSocket) -> Result<IpAddressFamily, anyhow::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+        match socket.family {
+            AddressFamily::Ipv4 => Ok(IpAddressFamily::Ipv4),
+            AddressFamily::Ipv6 => Ok(IpAddressFamily::Ipv6),
+        }
+    }
+
+    fn ipv6_only(&mut self, this: udp::UdpSocket) -> Result<bool, network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+
+        match socket.family {
+            AddressFamily::Ipv4 => return Err(ErrorCode::Ipv6OnlyOperation.into()),
+            AddressFamily::Ipv6 => {}
+        }
+
+        Ok(sockopt::get_ipv6_v6only(socket.udp_socket())?)
+    }
+
+    fn set_ipv6_only(&mut self, this: udp::UdpSocket, value: bool) -> Result<(), network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+
+        match socket.family {
+            AddressFamily::Ipv4 => return Err(ErrorCode::Ipv6OnlyOperation.into()),
+            AddressFamily::Ipv6 => {}
+        }
+
+        match socket.udp_state {
+            HostUdpState::Default => {}
+            HostUdpState::BindStarted | HostUdpState::ConnectReady => {
+                return Err(ErrorCode::ConcurrencyConflict.into())
+            }
+            HostUdpState::Bound | HostUdpState::Connected => {
+                return Err(ErrorCode::AlreadyBound.into())
+            }
+        }
+
+        Ok(sockopt::set_ipv6_v6only(socket.udp_socket(), value)?)
+    }
+
+    fn unicast_hop_limit(&mut self, this: udp::UdpSocket) -> Result<u8, network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+
+        match socket.family {
+            AddressFamily::Ipv4 => {
+                let value = sockopt::get_ip_ttl(socket.udp_socket())?;
+                Ok(value.try_into().unwrap())
+            }
+            AddressFamily::Ipv6 => Ok(sockopt::get_ipv6_unicast_hops(socket.udp_socket())?),
+        }
+    }
+
+    fn set_unicast_hop_limit(
+        &mut self,
+        this: udp::UdpSocket,
+        value: u8,
+    ) -> Result<(), network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+
+        match socket.family {
+            AddressFamily::Ipv4 => Ok(sockopt::set_ip_ttl(socket.udp_socket(), value.into())?),
+            AddressFamily::Ipv6 => Ok(sockopt::set_ipv6_unicast_hops(
+                socket.udp_socket(),
+                Some(value),
+            )?),
+        }
+    }
+
+    fn receive_buffer_size(&mut self, this: udp::UdpSocket) -> Result<u64, network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+        Ok(sockopt::get_socket_recv_buffer_size(socket.udp_socket())? as u64)
+    }
+
+    fn set_receive_buffer_size(
+        &mut self,
+        this: udp::UdpSocket,
+        value: u64,
+    ) -> Result<(), network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+        let value = value.try_into().map_err(|_| ErrorCode::OutOfMemory)?;
+        Ok(sockopt::set_socket_recv_buffer_size(
+            socket.udp_socket(),
+            value,
+        )?)
+    }
+
+    fn send_buffer_size(&mut self, this: udp::UdpSocket) -> Result<u64, network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+        Ok(sockopt::get_socket_send_buffer_size(socket.udp_socket())? as u64)
+    }
+
+    fn set_send_buffer_size(
+        &mut self,
+        this: udp::UdpSocket,
+        value: u64,
+    ) -> Result<(), network::Error> {
+        let table = self.table();
+        let socket = table.get_udp_socket(this)?;
+        let value = value.try_into().map_err(|_| ErrorCode::OutOfMemory)?;
+        Ok(sockopt::set_socket_send_buffer_size(
+            socket.udp_socket(),
+            value,
+        )?)
+    }
+
+    fn subscribe(&mut self, this: udp::UdpSocket) -> anyhow::Result<Pollable> {
+        fn make_udp_socket_future<'a>(stream: &'a mut dyn Any) -> PollableFuture<'a> {
+            let socket = stream
+                .downcast_mut::<HostUdpSocket>()
+                .expect("downcast to HostUdpSocket failed");
+
+            // Some states are ready immediately.
+            match socket.udp_state {
+                HostUdpState::BindStarted | HostUdpState::ConnectReady => {
+                    return Box::pin(async { Ok(()) })
+                }
+                _ => {}
+            }
+
+            #[cfg(unix)]
+            let join = Box::pin(async move {
+                socket
+                    .inner
+                    .udp_socket
+                    .ready(Interest::READABLE | Interest::WRITABLE)
+                    .await
+                    .unwrap()
+                    .retain_ready();
+                Ok(())
+            });
+
+            #[cfg(not(unix))]
+            let join = Box::pin(async move {
+                let clone = socket.clone_inner();
+                spawn_blocking(move || loop {
+                    #[cfg(not(windows))]
+                    let poll_flags = rustix::event::PollFlags::IN
+                        | rustix::event::PollFlags::OUT
+                        | rustix::event::PollFlags::ERR
+                        | rustix::event::PollFlags::HUP;
+                    // Windows doesn't appear to support `HUP`, or `ERR`
+                    // combined with `IN`/`OUT`.
+                    #[cfg(windows)]
+                    let poll_flags = rustix::event::PollFlags::IN | rustix::event::PollFlags::OUT;
+                    match rustix::event::poll(
+                        &mut [rustix::event::PollFd::new(&clone.udp_socket, poll_flags)],
+                        -1,
+                    ) {
+                        Ok(_) => break,
+                        Err(Errno::INTR) => (),
+                        Err(err) => Err(err).unwrap(),
+                    }
+                })
+                .await
+                .unwrap();
+
+                Ok(())
+            });
+
+            join
+        }
+
+        let pollable = HostPollable::TableEntry {
+            index: this,
+            make_future: make_udp_socket_future,
+        };
+
+        Ok(self.table_mut().push_host_pollable(pollable)?)
+    }
+
+    fn drop_udp_socket(&mut self, this: udp::UdpSocket) -> Result<(), anyhow::Error> {
+        let table = self.table_mut();
+
+        // As in the TCP implementation, we assume closing a socket doesn't
+        // block.
+        let dropped = table.delete_udp_socket(this)?;
+        drop(dropped);
+
+        Ok(())
+    }
+}
+
+/// Send a single datagram, validating its remote address first.
+fn send_one(
+    family: AddressFamily,
+    pool: &cap_std::net::Pool,
+    udp_socket: &std::net::UdpSocket,
+    connected_to: Option<SocketAddr>,
+    datagram: Datagram,
+) -> Result<(), network::Error> {
+    check_family(family, &datagram.remote_address)?;
+    check_remote_address(&datagram.remote_address)?;
+
+    let remote_address = SocketAddr::from(datagram.remote_address);
+    match connected_to {
+        Some(peer) if peer != remote_address => return Err(ErrorCode::AlreadyConnected.into()),
+        // The remote address was already checked against the pool by
+        // `start_connect`.
+        Some(_) => {
+            udp_socket.send(&datagram.data)?;
+        }
+        None => {
+            // Creating a connecter performs the same allow-list check that
+            // `start_connect` does, without connecting the socket.
+            pool.udp_connecter(remote_address)?;
+            udp_socket.send_to(&datagram.data, remote_address)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that `addr` has the same address family as the socket.
+fn check_family(family: AddressFamily, addr: &IpSocketAddress) -> Result<(), network::Error> {
+    match (family, addr) {
+        (AddressFamily::Ipv4, IpSocketAddress::Ipv4(_))
+        | (AddressFamily::Ipv6, IpSocketAddress::Ipv6(_)) => Ok(()),
+        _ => Err(ErrorCode::AddressFamilyMismatch.into()),
+    }
+}
+
+/// Check that `addr` is a valid destination, i.e. neither its IP address
+/// nor its port are unspecified.
+fn check_remote_address(addr: &IpSocketAddress) -> Result<(), network::Error> {
+    let addr = SocketAddr::from(*addr);
+    if addr.ip().is_unspecified() || addr.port() == 0 {
+        return Err(ErrorCode::InvalidRemoteAddress.into());
+    }
+    Ok(())
+}
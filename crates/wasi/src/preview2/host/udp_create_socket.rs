@@ -0,0 +1,18 @@
+use crate::preview2::bindings::{
+    sockets::network::{self, IpAddressFamily},
+    sockets::udp::UdpSocket,
+    sockets::udp_create_socket,
+};
+use crate::preview2::udp::{HostUdpSocket, TableUdpSocketExt};
+use crate::preview2::WasiView;
+
+impl<T: WasiView> udp_create_socket::Host for T {
+    fn create_udp_socket(
+        &mut self,
+        address_family: IpAddressFamily,
+    ) -> Result<UdpSocket, network::Error> {
+        let socket = HostUdpSocket::new(address_family.into())?;
+        let socket = self.table_mut().push_udp_socket(socket)?;
+        Ok(socket)
+    }
+}
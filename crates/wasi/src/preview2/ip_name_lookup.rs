@@ -0,0 +1,227 @@
+use crate::preview2::bindings::sockets::network::{ErrorCode, IpAddressFamily};
+use crate::preview2::{Table, TableError};
+use futures::FutureExt;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// A name resolver used to implement `wasi:sockets/ip-name-lookup`.
+///
+/// The default resolver, [`SystemResolver`], uses the host's name resolution.
+/// Use [`WasiCtxBuilder::resolver`] to configure a different one, such as a
+/// [`StaticResolver`].
+///
+/// Resolvers return every address a name is known by: the guest's
+/// `include-unavailable` flag is ignored, and no attempt is made to filter
+/// out addresses the host can't currently connect to.
+///
+/// [`WasiCtxBuilder::resolver`]: crate::preview2::WasiCtxBuilder::resolver
+#[async_trait::async_trait]
+pub trait HostResolver: Send + Sync {
+    /// Resolve `name` to the IP addresses it's known by.
+    ///
+    /// An error of kind [`io::ErrorKind::NotFound`] indicates that the name
+    /// does not exist.
+    async fn resolve(&self, name: &str) -> io::Result<Vec<IpAddr>>;
+}
+
+/// A [`HostResolver`] which uses the host's name resolution, e.g.
+/// `getaddrinfo` on POSIX, on a blocking task.
+pub struct SystemResolver;
+
+#[async_trait::async_trait]
+impl HostResolver for SystemResolver {
+    async fn resolve(&self, name: &str) -> io::Result<Vec<IpAddr>> {
+        let name = name.to_owned();
+        tokio::task::spawn_blocking(move || {
+            Ok((name.as_str(), 0)
+                .to_socket_addrs()?
+                .map(|addr| addr.ip())
+                .collect())
+        })
+        .await
+        .unwrap()
+    }
+}
+
+/// A [`HostResolver`] which only knows about a fixed table of names, like a
+/// hosts file. Useful for tests, and to keep guests from observing the
+/// host's name resolution.
+#[derive(Default)]
+pub struct StaticResolver {
+    hosts: HashMap<String, Vec<IpAddr>>,
+}
+
+impl StaticResolver {
+    /// Create a resolver which doesn't know about any names.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `addr` to the addresses `name` resolves to.
+    pub fn insert(&mut self, name: impl AsRef<str>, addr: IpAddr) -> &mut Self {
+        self.hosts
+            .entry(name.as_ref().to_ascii_lowercase())
+            .or_default()
+            .push(addr);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl HostResolver for StaticResolver {
+    async fn resolve(&self, name: &str) -> io::Result<Vec<IpAddr>> {
+        match self.hosts.get(&name.to_ascii_lowercase()) {
+            Some(addrs) => Ok(addrs.clone()),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+}
+
+/// The host representation of a `resolve-address-stream`.
+pub(crate) enum HostResolveAddressStream {
+    /// The resolver is still running.
+    Waiting(JoinHandle<Result<Vec<IpAddr>, ErrorCode>>),
+
+    /// The resolver finished. Addresses are handed out front to back.
+    Done(Result<VecDeque<IpAddr>, ErrorCode>),
+}
+
+impl HostResolveAddressStream {
+    /// Start resolving `name` with `resolver`, keeping only addresses of the
+    /// given `family`, if any.
+    pub fn resolve(
+        resolver: Arc<dyn HostResolver>,
+        name: String,
+        family: Option<IpAddressFamily>,
+    ) -> Self {
+        let task = crate::preview2::spawn(async move {
+            let addrs = resolver
+                .resolve(&name)
+                .await
+                .map_err(|err| match err.kind() {
+                    io::ErrorKind::WouldBlock
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::Interrupted => ErrorCode::TemporaryResolverFailure,
+                    io::ErrorKind::NotFound => ErrorCode::NameUnresolvable,
+                    _ => ErrorCode::PermanentResolverFailure,
+                })?;
+            Ok(filter_family(addrs, family))
+        });
+        Self::Waiting(task)
+    }
+
+    /// A stream which yields `addrs` without doing any resolution, e.g. for
+    /// names which are IP address literals.
+    pub fn ready(addrs: Vec<IpAddr>, family: Option<IpAddressFamily>) -> Self {
+        Self::Done(Ok(filter_family(addrs, family).into()))
+    }
+
+    /// Take the next address, returning `would-block` if the resolver hasn't
+    /// finished yet.
+    pub fn next_address(&mut self) -> Result<Option<IpAddr>, ErrorCode> {
+        if let Self::Waiting(task) = self {
+            match task.now_or_never() {
+                Some(result) => *self = Self::Done(result.unwrap().map(VecDeque::from)),
+                None => return Err(ErrorCode::WouldBlock),
+            }
+        }
+        match self {
+            Self::Done(Ok(addrs)) => Ok(addrs.pop_front()),
+            Self::Done(Err(code)) => Err(*code),
+            Self::Waiting(_) => unreachable!(),
+        }
+    }
+
+    /// Wait for the resolver to finish.
+    pub async fn ready_wait(&mut self) {
+        if let Self::Waiting(task) = self {
+            let result = task.await.unwrap();
+            *self = Self::Done(result.map(VecDeque::from));
+        }
+    }
+}
+
+fn filter_family(addrs: Vec<IpAddr>, family: Option<IpAddressFamily>) -> Vec<IpAddr> {
+    addrs
+        .into_iter()
+        .filter(|addr| match (family, addr) {
+            (None, _)
+            | (Some(IpAddressFamily::Ipv4), IpAddr::V4(_))
+            | (Some(IpAddressFamily::Ipv6), IpAddr::V6(_)) => true,
+            _ => false,
+        })
+        .collect()
+}
+
+pub(crate) trait TableResolveAddressStreamExt {
+    fn push_resolve_address_stream(
+        &mut self,
+        stream: HostResolveAddressStream,
+    ) -> Result<u32, TableError>;
+    fn delete_resolve_address_stream(
+        &mut self,
+        fd: u32,
+    ) -> Result<HostResolveAddressStream, TableError>;
+    fn get_resolve_address_stream_mut(
+        &mut self,
+        fd: u32,
+    ) -> Result<&mut HostResolveAddressStream, TableError>;
+}
+
+impl TableResolveAddressStreamExt for Table {
+    fn push_resolve_address_stream(
+        &mut self,
+        stream: HostResolveAddressStream,
+    ) -> Result<u32, TableError> {
+        self.push(Box::new(stream))
+    }
+    fn delete_resolve_address_stream(
+        &mut self,
+        fd: u32,
+    ) -> Result<HostResolveAddressStream, TableError> {
+        self.delete(fd)
+    }
+    fn get_resolve_address_stream_mut(
+        &mut self,
+        fd: u32,
+    ) -> Result<&mut HostResolveAddressStream, TableError> {
+        self.get_mut(fd)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    fn resolver() -> Arc<dyn HostResolver> {
+        let mut resolver = StaticResolver::new();
+        resolver
+            .insert("example.com", Ipv4Addr::new(192, 0, 2, 1).into())
+            .insert("Example.com", Ipv6Addr::LOCALHOST.into());
+        Arc::new(resolver)
+    }
+
+    #[tokio::test]
+    async fn static_resolver_filters_family() {
+        let mut stream = HostResolveAddressStream::resolve(
+            resolver(),
+            "EXAMPLE.com".to_string(),
+            Some(IpAddressFamily::Ipv6),
+        );
+        stream.ready_wait().await;
+        assert_eq!(stream.next_address(), Ok(Some(Ipv6Addr::LOCALHOST.into())));
+        assert_eq!(stream.next_address(), Ok(None));
+    }
+
+    #[tokio::test]
+    async fn static_resolver_unknown_name() {
+        let mut stream =
+            HostResolveAddressStream::resolve(resolver(), "example.org".to_string(), None);
+        stream.ready_wait().await;
+        assert_eq!(stream.next_address(), Err(ErrorCode::NameUnresolvable));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::preview2::bindings::logging::logging::Level;
+
+/// A sink for the messages logged by guests through `wasi:logging/logging`.
+///
+/// The default logger, [`TracingLogger`], emits each message as a `tracing`
+/// event. Use [`WasiCtxBuilder::logger`] to route messages elsewhere.
+///
+/// Messages below the level configured with
+/// [`WasiCtxBuilder::min_log_level`] are discarded before they reach the
+/// logger.
+///
+/// [`WasiCtxBuilder::logger`]: crate::preview2::WasiCtxBuilder::logger
+/// [`WasiCtxBuilder::min_log_level`]: crate::preview2::WasiCtxBuilder::min_log_level
+pub trait HostLogger: Send + Sync {
+    /// Log `message` at `level`.
+    ///
+    /// The `context` is an uninterpreted string chosen by the guest to help
+    /// group similar messages, such as the name of the module logging it.
+    fn log(&self, level: Level, context: &str, message: &str);
+}
+
+/// A [`HostLogger`] which emits messages as `tracing` events.
+///
+/// Each event carries the name of the instance which logged it, as configured
+/// with [`WasiCtxBuilder::instance_name`], in an `instance` field and the
+/// guest's context in a `context` field. `tracing` has no level above
+/// `ERROR`, so critical messages are emitted at that level with an additional
+/// `critical` field set.
+///
+/// [`WasiCtxBuilder::instance_name`]: crate::preview2::WasiCtxBuilder::instance_name
+pub struct TracingLogger {
+    instance: String,
+}
+
+impl TracingLogger {
+    /// Create a logger for messages from the instance named `instance`.
+    pub fn new(instance: impl Into<String>) -> Self {
+        Self {
+            instance: instance.into(),
+        }
+    }
+}
+
+impl HostLogger for TracingLogger {
+    fn log(&self, level: Level, context: &str, message: &str) {
+        let instance = self.instance.as_str();
+        match level {
+            Level::Trace => tracing::trace!(instance, context, "{message}"),
+            Level::Debug => tracing::debug!(instance, context, "{message}"),
+            Level::Info => tracing::info!(instance, context, "{message}"),
+            Level::Warn => tracing::warn!(instance, context, "{message}"),
+            Level::Error => tracing::error!(instance, context, "{message}"),
+            Level::Critical => tracing::error!(instance, context, critical = true, "{message}"),
+        }
+    }
+}
+
+/// Returns the severity of `level`, for comparing levels against each other.
+pub(crate) fn severity(level: Level) -> u8 {
+    match level {
+        Level::Trace => 0,
+        Level::Debug => 1,
+        Level::Info => 2,
+        Level::Warn => 3,
+        Level::Error => 4,
+        Level::Critical => 5,
+    }
+}
@@ -21,6 +21,8 @@ mod ctx;
 mod error;
 mod filesystem;
 mod host;
+mod ip_name_lookup;
+mod logging;
 mod network;
 pub mod pipe;
 mod poll;
@@ -31,11 +33,19 @@ mod stdio;
 mod stream;
 mod table;
 mod tcp;
+mod udp;
 
-pub use self::clocks::{HostMonotonicClock, HostWallClock};
+pub use self::clocks::{
+    FixedTimezone, HostMonotonicClock, HostTimezone, HostWallClock, LocalTimezone,
+};
 pub use self::ctx::{WasiCtx, WasiCtxBuilder, WasiView};
 pub use self::error::I32Exit;
-pub use self::filesystem::{DirPerms, FilePerms};
+pub use self::filesystem::{
+    DirPerms, DirectoryEntries, FilePerms, HostDir, HostFile, LockKind, MemoryDir, MemoryFile,
+    OpenResult, OsDir, OsFile,
+};
+pub use self::ip_name_lookup::{HostResolver, StaticResolver, SystemResolver};
+pub use self::logging::{HostLogger, TracingLogger};
 pub use self::poll::{ClosureFuture, HostPollable, MakeFuture, PollableFuture, TablePollableExt};
 pub use self::random::{thread_rng, Deterministic};
 pub use self::stdio::{stderr, stdin, stdout, IsATTY, Stderr, Stdin, Stdout};
@@ -121,7 +131,11 @@ pub mod bindings {
               import wasi:cli/terminal-stderr
               import wasi:sockets/tcp
               import wasi:sockets/tcp-create-socket
+              import wasi:sockets/udp
+              import wasi:sockets/udp-create-socket
               import wasi:sockets/instance-network
+              import wasi:sockets/ip-name-lookup
+              import wasi:logging/logging
             ",
         tracing: true,
         trappable_error_type: {
@@ -137,7 +151,7 @@ pub mod bindings {
         });
     }
 
-    pub use self::_internal_rest::wasi::{cli, random, sockets};
+    pub use self::_internal_rest::wasi::{cli, logging, random, sockets};
     pub mod filesystem {
         pub use super::_internal_io::wasi::filesystem::types;
         pub use super::_internal_rest::wasi::filesystem::preopens;
@@ -167,6 +181,16 @@ where
     }
 }
 
+pub(crate) fn with_ambient_tokio_runtime<R>(f: impl FnOnce() -> R) -> R {
+    match tokio::runtime::Handle::try_current() {
+        Ok(_) => f(),
+        Err(_) => {
+            let _enter = RUNTIME.enter();
+            f()
+        }
+    }
+}
+
 pub(crate) fn in_tokio<F: std::future::Future>(f: F) -> F::Output {
     match tokio::runtime::Handle::try_current() {
         Ok(h) => {
@@ -304,10 +304,16 @@ impl HostOutputStream for AsyncWriteStream {
         use tokio::sync::mpsc::error::TryRecvError;
 
         match self.state {
+            // Nothing to hand to the writer task, which only reports back
+            // once it has written something.
+            Some(WriteState::Ready) if bytes.is_empty() => Ok((0, StreamState::Open)),
             Some(WriteState::Ready) => self.send(bytes),
             Some(WriteState::Pending) => match self.result_receiver.try_recv() {
                 Ok(Ok(StreamState::Open)) => {
                     self.state = Some(WriteState::Ready);
+                    if bytes.is_empty() {
+                        return Ok((0, StreamState::Open));
+                    }
                     self.send(bytes)
                 }
 
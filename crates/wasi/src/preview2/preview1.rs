@@ -5,6 +5,7 @@ use crate::preview2::bindings::cli::{
 use crate::preview2::bindings::clocks::{monotonic_clock, wall_clock};
 use crate::preview2::bindings::filesystem::{preopens, types as filesystem};
 use crate::preview2::bindings::io::streams;
+use crate::preview2::bindings::sockets::{network, tcp};
 use crate::preview2::filesystem::TableFsExt;
 use crate::preview2::host::filesystem::TableReaddirExt;
 use crate::preview2::{bindings, IsATTY, TableError, WasiView};
@@ -37,6 +38,20 @@ struct File {
     blocking: bool,
 }
 
+#[derive(Clone, Debug)]
+struct Socket {
+    /// The handle to the preview2 `tcp-socket` that this socket is referencing.
+    fd: tcp::TcpSocket,
+
+    /// The streams of a connected socket, or `None` for a listening socket.
+    streams: Option<(streams::InputStream, streams::OutputStream)>,
+
+    /// In blocking mode, `sock_accept` waits for a connection, and reads and
+    /// writes dispatch to blocking_read and blocking_write on the underlying
+    /// streams.
+    blocking: bool,
+}
+
 #[derive(Clone, Debug)]
 enum Descriptor {
     Stdin {
@@ -53,6 +68,7 @@ enum Descriptor {
     },
     PreopenDirectory((filesystem::Descriptor, String)),
     File(File),
+    Socket(Socket),
 }
 
 #[derive(Debug, Default)]
@@ -81,9 +97,10 @@ impl DerefMut for Descriptors {
 }
 
 impl Descriptors {
-    /// Initializes [Self] using `preopens`
+    /// Initializes [Self] using `preopens` and the preopened sockets of the [`WasiView`]
     fn new(
-        host: &mut (impl preopens::Host
+        host: &mut (impl WasiView
+                  + preopens::Host
                   + stdin::Host
                   + stdout::Host
                   + stderr::Host
@@ -150,6 +167,21 @@ impl Descriptors {
             },
         })?;
 
+        for (fd, socket) in host.ctx().preopened_sockets.clone() {
+            // Preopened sockets are listeners which are put in non-blocking
+            // mode, like the `wasi-common` ones.
+            let socket = Descriptor::Socket(Socket {
+                fd: socket,
+                streams: None,
+                blocking: false,
+            });
+            if descriptors.insert(fd, socket).is_some() {
+                return Err(types::Error::trap(anyhow!(
+                    "preopened socket {fd} conflicts with another descriptor"
+                )));
+            }
+        }
+
         for dir in host
             .get_directories()
             .context("failed to call `get-directories`")
@@ -293,7 +325,10 @@ impl<T: WasiPreview1View + ?Sized> Transaction<'_, T> {
                 Ok(file)
             }
             Some(
-                Descriptor::Stdin { .. } | Descriptor::Stdout { .. } | Descriptor::Stderr { .. },
+                Descriptor::Stdin { .. }
+                | Descriptor::Stdout { .. }
+                | Descriptor::Stderr { .. }
+                | Descriptor::Socket(_),
             ) => {
                 // NOTE: legacy implementation returns SPIPE here
                 Err(types::Errno::Spipe.into())
@@ -302,6 +337,18 @@ impl<T: WasiPreview1View + ?Sized> Transaction<'_, T> {
         }
     }
 
+    /// Borrows [`Socket`] corresponding to `fd`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`types::Errno::Notsock`] if the descriptor is not a [`Descriptor::Socket`]
+    fn get_socket(&mut self, fd: types::Fd) -> Result<&Socket> {
+        match self.get_descriptor(fd)? {
+            Descriptor::Socket(socket) => Ok(socket),
+            _ => Err(types::Errno::Notsock.into()),
+        }
+    }
+
     /// Returns [`filesystem::Descriptor`] corresponding to `fd`
     fn get_fd(&mut self, fd: types::Fd) -> Result<filesystem::Descriptor> {
         match self.get_descriptor(fd)? {
@@ -311,6 +358,7 @@ impl<T: WasiPreview1View + ?Sized> Transaction<'_, T> {
             Descriptor::Stdout { output_stream, .. } | Descriptor::Stderr { output_stream, .. } => {
                 Ok(*output_stream)
             }
+            Descriptor::Socket(Socket { fd, .. }) => Ok(*fd),
         }
     }
 
@@ -414,7 +462,8 @@ wiggle::from_witx!({
             fd_filestat_set_times, fd_read, fd_pread, fd_seek, fd_sync, fd_readdir, fd_write,
             fd_pwrite, poll_oneoff, path_create_directory, path_filestat_get,
             path_filestat_set_times, path_link, path_open, path_readlink, path_remove_directory,
-            path_rename, path_symlink, path_unlink_file
+            path_rename, path_symlink, path_unlink_file, sock_accept, sock_recv, sock_send,
+            sock_shutdown
         }
     },
     errors: { errno => trappable Error },
@@ -433,7 +482,8 @@ mod sync {
                 fd_filestat_set_times, fd_read, fd_pread, fd_seek, fd_sync, fd_readdir, fd_write,
                 fd_pwrite, poll_oneoff, path_create_directory, path_filestat_get,
                 path_filestat_set_times, path_link, path_open, path_readlink, path_remove_directory,
-                path_rename, path_symlink, path_unlink_file
+                path_rename, path_symlink, path_unlink_file, sock_accept, sock_recv, sock_send,
+                sock_shutdown
             }
         },
         errors: { errno => trappable Error },
@@ -671,6 +721,62 @@ impl TryFrom<filesystem::Error> for types::Error {
     }
 }
 
+impl From<network::ErrorCode> for types::Errno {
+    fn from(code: network::ErrorCode) -> Self {
+        match code {
+            network::ErrorCode::Unknown => types::Errno::Io,
+            network::ErrorCode::AccessDenied => types::Errno::Acces,
+            network::ErrorCode::NotSupported => types::Errno::Notsup,
+            network::ErrorCode::OutOfMemory => types::Errno::Nomem,
+            network::ErrorCode::Timeout => types::Errno::Timedout,
+            network::ErrorCode::ConcurrencyConflict => types::Errno::Again,
+            network::ErrorCode::NotInProgress => types::Errno::Inval,
+            network::ErrorCode::WouldBlock => types::Errno::Again,
+            network::ErrorCode::AddressFamilyNotSupported
+            | network::ErrorCode::AddressFamilyMismatch
+            | network::ErrorCode::Ipv4OnlyOperation
+            | network::ErrorCode::Ipv6OnlyOperation => types::Errno::Afnosupport,
+            network::ErrorCode::InvalidRemoteAddress => types::Errno::Addrnotavail,
+            network::ErrorCode::NewSocketLimit => types::Errno::Mfile,
+            network::ErrorCode::AlreadyAttached
+            | network::ErrorCode::AlreadyBound
+            | network::ErrorCode::NotBound
+            | network::ErrorCode::AlreadyListening
+            | network::ErrorCode::NotListening => types::Errno::Inval,
+            network::ErrorCode::AlreadyConnected => types::Errno::Isconn,
+            network::ErrorCode::NotConnected => types::Errno::Notconn,
+            network::ErrorCode::AddressNotBindable
+            | network::ErrorCode::EphemeralPortsExhausted => types::Errno::Addrnotavail,
+            network::ErrorCode::AddressInUse => types::Errno::Addrinuse,
+            network::ErrorCode::RemoteUnreachable => types::Errno::Hostunreach,
+            network::ErrorCode::ConnectionRefused => types::Errno::Connrefused,
+            network::ErrorCode::ConnectionReset => types::Errno::Connreset,
+            network::ErrorCode::DatagramTooLarge => types::Errno::Msgsize,
+            network::ErrorCode::InvalidName => types::Errno::Inval,
+            network::ErrorCode::NameUnresolvable
+            | network::ErrorCode::TemporaryResolverFailure
+            | network::ErrorCode::PermanentResolverFailure => types::Errno::Io,
+        }
+    }
+}
+
+impl From<network::ErrorCode> for types::Error {
+    fn from(code: network::ErrorCode) -> Self {
+        types::Errno::from(code).into()
+    }
+}
+
+impl TryFrom<network::Error> for types::Error {
+    type Error = anyhow::Error;
+
+    fn try_from(err: network::Error) -> Result<Self, Self::Error> {
+        match err.downcast() {
+            Ok(code) => Ok(code.into()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
 impl From<TableError> for types::Error {
     fn from(err: TableError) -> Self {
         types::Error::trap(err.into())
@@ -739,6 +845,35 @@ fn first_non_empty_iovec<'a>(
         .transpose()
 }
 
+/// What [`wasi_snapshot_preview1::WasiSnapshotPreview1::poll_oneoff`] waits on
+/// for a single subscription.
+enum PollWait {
+    /// The subscription's event has already occurred.
+    Ready(types::Event),
+    /// A `monotonic-clock` deadline.
+    Clock { timeout: u64, absolute: bool },
+    /// Readability of a stream.
+    InputStream(streams::InputStream),
+    /// Writability of a stream.
+    OutputStream(streams::OutputStream),
+    /// A connection arriving on a listening socket.
+    TcpSocket(tcp::TcpSocket),
+}
+
+fn fd_event(
+    userdata: types::Userdata,
+    type_: types::Eventtype,
+    nbytes: types::Filesize,
+    flags: types::Eventrwflags,
+) -> types::Event {
+    types::Event {
+        userdata,
+        error: types::Errno::Success,
+        type_,
+        fd_readwrite: types::EventFdReadwrite { nbytes, flags },
+    }
+}
+
 #[async_trait::async_trait]
 // Implement the WasiSnapshotPreview1 trait using only the traits that are
 // required for T, i.e., in terms of the preview 2 wit interface, and state
@@ -751,6 +886,7 @@ impl<
             + bindings::filesystem::types::Host
             + bindings::poll::poll::Host
             + bindings::random::random::Host
+            + bindings::sockets::tcp::Host
             + bindings::io::streams::Host
             + bindings::clocks::monotonic_clock::Host
             + bindings::clocks::wall_clock::Host,
@@ -931,6 +1067,23 @@ impl<
                 .drop_descriptor(fd)
                 .await
                 .context("failed to call `drop-descriptor`"),
+            Descriptor::Socket(Socket {
+                fd,
+                streams: socket_streams,
+                ..
+            }) => {
+                if let Some((input_stream, output_stream)) = socket_streams {
+                    streams::Host::drop_input_stream(self, input_stream)
+                        .await
+                        .context("failed to call `drop-input-stream`")
+                        .map_err(types::Error::trap)?;
+                    streams::Host::drop_output_stream(self, output_stream)
+                        .await
+                        .context("failed to call `drop-output-stream`")
+                        .map_err(types::Error::trap)?;
+                }
+                tcp::Host::drop_tcp_socket(self, fd).context("failed to call `drop-tcp-socket`")
+            }
         }
         .map_err(types::Error::trap)
     }
@@ -1011,6 +1164,25 @@ impl<
                     fs_rights_inheriting,
                 });
             }
+            Descriptor::Socket(Socket { blocking, .. }) => {
+                let fs_rights_base = types::Rights::FD_READ
+                    | types::Rights::FD_WRITE
+                    | types::Rights::FD_FDSTAT_SET_FLAGS
+                    | types::Rights::POLL_FD_READWRITE
+                    | types::Rights::SOCK_SHUTDOWN
+                    | types::Rights::SOCK_ACCEPT;
+                let fs_flags = if *blocking {
+                    types::Fdflags::empty()
+                } else {
+                    types::Fdflags::NONBLOCK
+                };
+                return Ok(types::Fdstat {
+                    fs_filetype: types::Filetype::SocketStream,
+                    fs_flags,
+                    fs_rights_base,
+                    fs_rights_inheriting: fs_rights_base,
+                });
+            }
             Descriptor::File(File {
                 fd,
                 blocking,
@@ -1076,6 +1248,16 @@ impl<
         flags: types::Fdflags,
     ) -> Result<(), types::Error> {
         let mut st = self.transact()?;
+        if let Some(Descriptor::Socket(Socket { blocking, .. })) =
+            st.descriptors.get_mut().get_mut(&u32::from(fd))
+        {
+            // Sockets only support changing the NONBLOCK flag.
+            if flags != types::Fdflags::empty() && flags != types::Fdflags::NONBLOCK {
+                return Err(types::Errno::Inval.into());
+            }
+            *blocking = !flags.contains(types::Fdflags::NONBLOCK);
+            return Ok(());
+        }
         let File {
             append, blocking, ..
         } = st.get_file_mut(fd)?;
@@ -1121,6 +1303,16 @@ impl<
                 mtim: 0,
                 ctim: 0,
             }),
+            Descriptor::Socket(_) => Ok(types::Filestat {
+                dev: 0,
+                ino: 0,
+                filetype: types::Filetype::SocketStream,
+                nlink: 0,
+                size: 0,
+                atim: 0,
+                mtim: 0,
+                ctim: 0,
+            }),
             Descriptor::PreopenDirectory((fd, _)) | Descriptor::File(File { fd, .. }) => {
                 let filesystem::DescriptorStat {
                     type_,
@@ -1255,6 +1447,25 @@ impl<
                 )?;
                 (buf, read, state)
             }
+            Descriptor::Socket(Socket {
+                streams: Some((input_stream, _)),
+                blocking,
+                ..
+            }) => {
+                let Some(buf) = first_non_empty_iovec(iovs)? else {
+                    return Ok(0);
+                };
+                let max = buf.len().try_into().unwrap_or(u64::MAX);
+                let (read, state) = if blocking {
+                    stream_res(streams::Host::blocking_read(self, input_stream, max).await)?
+                } else {
+                    stream_res(streams::Host::read(self, input_stream, max).await)?
+                };
+                if !blocking && state == streams::StreamStatus::Open && read.is_empty() {
+                    return Err(types::Errno::Again.into());
+                }
+                (buf, read, state)
+            }
             _ => return Err(types::Errno::Badf.into()),
         };
         if read.len() > buf.len() {
@@ -1371,6 +1582,24 @@ impl<
                     stream_res(streams::Host::blocking_write(self, output_stream, buf).await)?;
                 n
             }
+            Descriptor::Socket(Socket {
+                streams: Some((_, output_stream)),
+                blocking,
+                ..
+            }) => {
+                let Some(buf) = first_non_empty_ciovec(ciovs)? else {
+                    return Ok(0);
+                };
+                let (n, state) = if blocking {
+                    stream_res(streams::Host::blocking_write(self, output_stream, buf).await)?
+                } else {
+                    stream_res(streams::Host::write(self, output_stream, buf).await)?
+                };
+                if !blocking && state == streams::StreamStatus::Open && n == 0 {
+                    return Err(types::Errno::Again.into());
+                }
+                n
+            }
             _ => return Err(types::Errno::Badf.into()),
         };
         let n = n.try_into()?;
@@ -1909,7 +2138,6 @@ impl<
         })
     }
 
-    #[allow(unused_variables)]
     #[instrument(skip(self))]
     async fn poll_oneoff<'a>(
         &mut self,
@@ -1917,7 +2145,228 @@ impl<
         events: &GuestPtr<'a, types::Event>,
         nsubscriptions: types::Size,
     ) -> Result<types::Size, types::Error> {
-        todo!("preview1 poll_oneoff is not implemented")
+        // Indefinite sleeping is not supported in preview1.
+        if nsubscriptions == 0 {
+            return Err(types::Errno::Inval.into());
+        }
+
+        // Validate all subscriptions before creating any pollables, so that
+        // an invalid one doesn't leave pollables behind in the table.
+        let mut plan = Vec::new();
+        for sub in subs.as_array(nsubscriptions).iter() {
+            let types::Subscription { userdata, u } = sub?.read()?;
+            let wait = match u {
+                types::SubscriptionU::Clock(types::SubscriptionClock {
+                    id,
+                    timeout,
+                    flags,
+                    ..
+                }) => {
+                    let absolute = flags.contains(types::Subclockflags::SUBSCRIPTION_CLOCK_ABSTIME);
+                    match id {
+                        types::Clockid::Monotonic => PollWait::Clock { timeout, absolute },
+                        types::Clockid::Realtime if !absolute => PollWait::Clock {
+                            timeout,
+                            absolute: false,
+                        },
+                        types::Clockid::Realtime => {
+                            // Turn the wall-clock deadline into a monotonic
+                            // timeout.
+                            let now: types::Timestamp = wall_clock::Host::now(self)
+                                .context("failed to call `wall_clock::now`")
+                                .map_err(types::Error::trap)?
+                                .try_into()?;
+                            PollWait::Clock {
+                                timeout: timeout.saturating_sub(now),
+                                absolute: false,
+                            }
+                        }
+                        types::Clockid::ProcessCputimeId | types::Clockid::ThreadCputimeId => {
+                            return Err(types::Errno::Inval.into())
+                        }
+                    }
+                }
+                types::SubscriptionU::FdRead(types::SubscriptionFdReadwrite {
+                    file_descriptor,
+                }) => {
+                    let desc = self.transact()?.get_descriptor(file_descriptor)?.clone();
+                    match desc {
+                        Descriptor::Stdin { input_stream, .. }
+                        | Descriptor::Socket(Socket {
+                            streams: Some((input_stream, _)),
+                            ..
+                        }) => PollWait::InputStream(input_stream),
+                        Descriptor::Socket(Socket {
+                            fd, streams: None, ..
+                        }) => PollWait::TcpSocket(fd),
+                        Descriptor::File(File { fd, position, .. }) if self.table().is_file(fd) => {
+                            // Files are always ready; report how much is left
+                            // to read.
+                            let event = match self.stat(fd).await {
+                                Ok(stat) => {
+                                    let nbytes =
+                                        stat.size.saturating_sub(position.load(Ordering::Relaxed));
+                                    let flags = if nbytes == 0 {
+                                        types::Eventrwflags::FD_READWRITE_HANGUP
+                                    } else {
+                                        types::Eventrwflags::empty()
+                                    };
+                                    fd_event(userdata, types::Eventtype::FdRead, nbytes, flags)
+                                }
+                                Err(e) => {
+                                    let error = e
+                                        .try_into()
+                                        .context("failed to call `stat`")
+                                        .map_err(types::Error::trap)?;
+                                    types::Event {
+                                        error,
+                                        ..fd_event(
+                                            userdata,
+                                            types::Eventtype::FdRead,
+                                            0,
+                                            types::Eventrwflags::empty(),
+                                        )
+                                    }
+                                }
+                            };
+                            PollWait::Ready(event)
+                        }
+                        Descriptor::Stdout { .. } | Descriptor::Stderr { .. } => {
+                            PollWait::Ready(types::Event {
+                                error: types::Errno::Badf,
+                                ..fd_event(
+                                    userdata,
+                                    types::Eventtype::FdRead,
+                                    0,
+                                    types::Eventrwflags::empty(),
+                                )
+                            })
+                        }
+                        Descriptor::File(_) | Descriptor::PreopenDirectory(_) => {
+                            return Err(types::Errno::Badf.into())
+                        }
+                    }
+                }
+                types::SubscriptionU::FdWrite(types::SubscriptionFdReadwrite {
+                    file_descriptor,
+                }) => {
+                    let desc = self.transact()?.get_descriptor(file_descriptor)?.clone();
+                    match desc {
+                        Descriptor::Stdout { output_stream, .. }
+                        | Descriptor::Stderr { output_stream, .. }
+                        | Descriptor::Socket(Socket {
+                            streams: Some((_, output_stream)),
+                            ..
+                        }) => PollWait::OutputStream(output_stream),
+                        Descriptor::File(File { fd, .. }) if self.table().is_file(fd) => {
+                            PollWait::Ready(fd_event(
+                                userdata,
+                                types::Eventtype::FdWrite,
+                                1,
+                                types::Eventrwflags::empty(),
+                            ))
+                        }
+                        Descriptor::Stdin { .. } | Descriptor::Socket(_) => {
+                            PollWait::Ready(types::Event {
+                                error: types::Errno::Badf,
+                                ..fd_event(
+                                    userdata,
+                                    types::Eventtype::FdWrite,
+                                    0,
+                                    types::Eventrwflags::empty(),
+                                )
+                            })
+                        }
+                        Descriptor::File(_) | Descriptor::PreopenDirectory(_) => {
+                            return Err(types::Errno::Badf.into())
+                        }
+                    }
+                }
+            };
+            plan.push((userdata, wait));
+        }
+
+        let mut pollables = Vec::with_capacity(plan.len());
+        let mut any_ready = false;
+        for (_, wait) in plan.iter() {
+            let pollable = match *wait {
+                PollWait::Ready(_) => {
+                    any_ready = true;
+                    continue;
+                }
+                PollWait::Clock { timeout, absolute } => {
+                    monotonic_clock::Host::subscribe(self, timeout, absolute)
+                        .context("failed to call `monotonic_clock::subscribe`")
+                }
+                PollWait::InputStream(stream) => {
+                    streams::Host::subscribe_to_input_stream(self, stream)
+                        .await
+                        .context("failed to call `subscribe-to-input-stream`")
+                }
+                PollWait::OutputStream(stream) => {
+                    streams::Host::subscribe_to_output_stream(self, stream)
+                        .await
+                        .context("failed to call `subscribe-to-output-stream`")
+                }
+                PollWait::TcpSocket(socket) => {
+                    tcp::Host::subscribe(self, socket).context("failed to call `subscribe`")
+                }
+            }
+            .map_err(types::Error::trap)?;
+            pollables.push(pollable);
+        }
+        // If some events have already occurred, only check which of the rest
+        // are ready too, rather than waiting for them.
+        if any_ready {
+            let pollable = monotonic_clock::Host::subscribe(self, 0, false)
+                .context("failed to call `monotonic_clock::subscribe`")
+                .map_err(types::Error::trap)?;
+            pollables.push(pollable);
+        }
+
+        let results = bindings::poll::poll::Host::poll_oneoff(self, pollables.clone())
+            .await
+            .context("failed to call `poll-oneoff`")
+            .map_err(types::Error::trap)?;
+        for pollable in pollables {
+            bindings::poll::poll::Host::drop_pollable(self, pollable)
+                .await
+                .context("failed to call `drop-pollable`")
+                .map_err(types::Error::trap)?;
+        }
+
+        // Events are reported in the order of their subscriptions.
+        let mut results = results.into_iter();
+        let mut count: types::Size = 0;
+        for (userdata, wait) in plan {
+            let event = match wait {
+                PollWait::Ready(event) => event,
+                wait => {
+                    if !results.next().unwrap_or(false) {
+                        continue;
+                    }
+                    let type_ = match wait {
+                        PollWait::Clock { .. } => types::Eventtype::Clock,
+                        PollWait::InputStream(_) | PollWait::TcpSocket(_) => {
+                            types::Eventtype::FdRead
+                        }
+                        PollWait::OutputStream(_) => types::Eventtype::FdWrite,
+                        PollWait::Ready(_) => unreachable!(),
+                    };
+                    // Preview2 can't tell how many bytes are ready, so report
+                    // at least one.
+                    let nbytes = if type_ == types::Eventtype::Clock {
+                        0
+                    } else {
+                        1
+                    };
+                    fd_event(userdata, type_, nbytes, types::Eventrwflags::empty())
+                }
+            };
+            events.add(count)?.write(event)?;
+            count += 1;
+        }
+        Ok(count)
     }
 
     #[instrument(skip(self))]
@@ -1957,41 +2406,162 @@ impl<
         Ok(())
     }
 
-    #[allow(unused_variables)]
     #[instrument(skip(self))]
-    fn sock_accept(
+    async fn sock_accept(
         &mut self,
         fd: types::Fd,
         flags: types::Fdflags,
     ) -> Result<types::Fd, types::Error> {
-        todo!("preview1 sock_accept is not implemented")
+        let Socket {
+            fd: listener,
+            streams: socket_streams,
+            blocking,
+        } = self.transact()?.get_socket(fd)?.clone();
+        if socket_streams.is_some() {
+            return Err(types::Errno::Inval.into());
+        }
+        // Accepted sockets only support the NONBLOCK flag.
+        if flags != types::Fdflags::empty() && flags != types::Fdflags::NONBLOCK {
+            return Err(types::Errno::Inval.into());
+        }
+
+        let (socket, input_stream, output_stream) = loop {
+            match tcp::Host::accept(self, listener) {
+                Ok(accepted) => break accepted,
+                Err(e) => match e.downcast() {
+                    Ok(network::ErrorCode::WouldBlock) if blocking => {}
+                    Ok(code) => return Err(code.into()),
+                    Err(e) => return Err(types::Error::trap(e.context("failed to call `accept`"))),
+                },
+            }
+
+            // Wait for a connection to arrive before trying again.
+            let pollable = tcp::Host::subscribe(self, listener)
+                .context("failed to call `subscribe`")
+                .map_err(types::Error::trap)?;
+            bindings::poll::poll::Host::poll_oneoff(self, vec![pollable])
+                .await
+                .context("failed to call `poll-oneoff`")
+                .map_err(types::Error::trap)?;
+            bindings::poll::poll::Host::drop_pollable(self, pollable)
+                .await
+                .context("failed to call `drop-pollable`")
+                .map_err(types::Error::trap)?;
+        };
+
+        let fd = self
+            .transact()?
+            .descriptors
+            .get_mut()
+            .push(Descriptor::Socket(Socket {
+                fd: socket,
+                streams: Some((input_stream, output_stream)),
+                blocking: !flags.contains(types::Fdflags::NONBLOCK),
+            }))?;
+        Ok(fd.into())
     }
 
-    #[allow(unused_variables)]
     #[instrument(skip(self))]
-    fn sock_recv<'a>(
+    async fn sock_recv<'a>(
         &mut self,
         fd: types::Fd,
         ri_data: &types::IovecArray<'a>,
         ri_flags: types::Riflags,
     ) -> Result<(types::Size, types::Roflags), types::Error> {
-        todo!("preview1 sock_recv is not implemented")
+        let Socket {
+            streams: socket_streams,
+            blocking,
+            ..
+        } = self.transact()?.get_socket(fd)?.clone();
+        let Some((input_stream, _)) = socket_streams else {
+            return Err(types::Errno::Notconn.into());
+        };
+        // Peeking can't be expressed in terms of preview2 streams.
+        if ri_flags.contains(types::Riflags::RECV_PEEK) {
+            return Err(types::Errno::Notsup.into());
+        }
+        let Some(mut buf) = first_non_empty_iovec(ri_data)? else {
+            return Ok((0, types::Roflags::empty()));
+        };
+
+        let mut n = 0;
+        let state = loop {
+            let max = (buf.len() - n).try_into().unwrap_or(u64::MAX);
+            let (read, state) = if blocking {
+                stream_res(streams::Host::blocking_read(self, input_stream, max).await)?
+            } else {
+                stream_res(streams::Host::read(self, input_stream, max).await)?
+            };
+            buf.get_mut(n..n + read.len())
+                .ok_or(types::Errno::Range)?
+                .copy_from_slice(&read);
+            n += read.len();
+
+            // With RECV_WAITALL, a blocking socket keeps reading until the
+            // buffer is full or the peer stops sending.
+            if !blocking
+                || !ri_flags.contains(types::Riflags::RECV_WAITALL)
+                || n == buf.len()
+                || state == streams::StreamStatus::Ended
+            {
+                break state;
+            }
+        };
+        if !blocking && state == streams::StreamStatus::Open && n == 0 {
+            return Err(types::Errno::Again.into());
+        }
+        Ok((n.try_into()?, types::Roflags::empty()))
     }
 
-    #[allow(unused_variables)]
     #[instrument(skip(self))]
-    fn sock_send<'a>(
+    async fn sock_send<'a>(
         &mut self,
         fd: types::Fd,
         si_data: &types::CiovecArray<'a>,
         _si_flags: types::Siflags,
     ) -> Result<types::Size, types::Error> {
-        todo!("preview1 sock_send is not implemented")
+        let Socket {
+            streams: socket_streams,
+            blocking,
+            ..
+        } = self.transact()?.get_socket(fd)?.clone();
+        let Some((_, output_stream)) = socket_streams else {
+            return Err(types::Errno::Notconn.into());
+        };
+        let Some(buf) = first_non_empty_ciovec(si_data)? else {
+            return Ok(0);
+        };
+        let (n, state) = if blocking {
+            stream_res(streams::Host::blocking_write(self, output_stream, buf).await)?
+        } else {
+            stream_res(streams::Host::write(self, output_stream, buf).await)?
+        };
+        if !blocking && state == streams::StreamStatus::Open && n == 0 {
+            return Err(types::Errno::Again.into());
+        }
+        Ok(n.try_into()?)
     }
 
-    #[allow(unused_variables)]
     #[instrument(skip(self))]
-    fn sock_shutdown(&mut self, fd: types::Fd, how: types::Sdflags) -> Result<(), types::Error> {
-        todo!("preview1 sock_shutdown is not implemented")
+    async fn sock_shutdown(
+        &mut self,
+        fd: types::Fd,
+        how: types::Sdflags,
+    ) -> Result<(), types::Error> {
+        let Socket { fd, .. } = self.transact()?.get_socket(fd)?.clone();
+        let how = if how == types::Sdflags::RD | types::Sdflags::WR {
+            tcp::ShutdownType::Both
+        } else if how == types::Sdflags::RD {
+            tcp::ShutdownType::Receive
+        } else if how == types::Sdflags::WR {
+            tcp::ShutdownType::Send
+        } else {
+            return Err(types::Errno::Inval.into());
+        };
+        tcp::Host::shutdown(self, fd, how).map_err(|e| {
+            e.try_into()
+                .context("failed to call `shutdown`")
+                .unwrap_or_else(types::Error::trap)
+        })
     }
 }
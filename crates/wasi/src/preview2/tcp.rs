@@ -98,6 +98,22 @@ impl HostTcpSocket {
         })
     }
 
+    /// Create a `HostTcpSocket` from an existing listening socket.
+    ///
+    /// The socket is switched to non-blocking mode.
+    pub fn from_tcp_listener(tcp_socket: cap_std::net::TcpListener) -> io::Result<Self> {
+        tcp_socket.set_nonblocking(true)?;
+
+        // On Unix, pack it up in an `AsyncFd` so we can efficiently poll it.
+        #[cfg(unix)]
+        let tcp_socket = tokio::io::unix::AsyncFd::new(tcp_socket)?;
+
+        Ok(Self {
+            inner: Arc::new(HostTcpSocketInner { tcp_socket }),
+            tcp_state: HostTcpState::Listening,
+        })
+    }
+
     pub fn tcp_socket(&self) -> &cap_std::net::TcpListener {
         self.inner.tcp_socket()
     }
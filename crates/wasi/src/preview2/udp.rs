@@ -0,0 +1,127 @@
+use crate::preview2::{Table, TableError};
+use cap_net_ext::{AddressFamily, Blocking, UdpSocketExt};
+use cap_std::net::{Pool, UdpSocket};
+use std::io;
+use std::sync::Arc;
+
+/// The state of a UDP socket.
+///
+/// This represents the various states a socket can be in during the
+/// activities of binding and connecting.
+pub(crate) enum HostUdpState {
+    /// The initial state for a newly-created socket.
+    Default,
+
+    /// Binding started via `start_bind`.
+    BindStarted,
+
+    /// Binding finished via `finish_bind`. The socket has an address but
+    /// is not yet connected to a remote address.
+    Bound,
+
+    /// A remote address is being set via `start_connect`.
+    ConnectReady,
+
+    /// A remote address has been set. Datagrams are only sent to and
+    /// received from that address.
+    Connected,
+}
+
+/// A host UDP socket, plus associated bookkeeping.
+///
+/// The inner state is wrapped in an Arc so that it can be moved into the
+/// futures created for pollables, mirroring [`HostTcpSocket`].
+///
+/// [`HostTcpSocket`]: crate::preview2::tcp::HostTcpSocket
+pub(crate) struct HostUdpSocket {
+    /// The part of a `HostUdpSocket` which is reference-counted so that we
+    /// can pass it to async tasks.
+    pub(crate) inner: Arc<HostUdpSocketInner>,
+
+    /// The current state in the bind/connect progression.
+    pub(crate) udp_state: HostUdpState,
+
+    /// The address family the socket was created with.
+    pub(crate) family: AddressFamily,
+
+    /// The pool of the network this socket was bound or connected on. Every
+    /// remote address that datagrams are sent to is checked against it.
+    pub(crate) pool: Option<Pool>,
+}
+
+/// The inner reference-counted state of a `HostUdpSocket`.
+pub(crate) struct HostUdpSocketInner {
+    /// On Unix-family platforms we can use `AsyncFd` for efficient polling.
+    #[cfg(unix)]
+    pub(crate) udp_socket: tokio::io::unix::AsyncFd<UdpSocket>,
+
+    /// On non-Unix, we can use plain `poll`.
+    #[cfg(not(unix))]
+    pub(crate) udp_socket: UdpSocket,
+}
+
+impl HostUdpSocket {
+    /// Create a new socket in the given family.
+    pub fn new(family: AddressFamily) -> io::Result<Self> {
+        // Create a new host socket and set it to non-blocking, which is needed
+        // by our async implementation.
+        let udp_socket = UdpSocket::new(family, Blocking::No)?;
+
+        // On Unix, pack it up in an `AsyncFd` so we can efficiently poll it.
+        #[cfg(unix)]
+        let udp_socket = tokio::io::unix::AsyncFd::new(udp_socket)?;
+
+        Ok(Self {
+            inner: Arc::new(HostUdpSocketInner { udp_socket }),
+            udp_state: HostUdpState::Default,
+            family,
+            pool: None,
+        })
+    }
+
+    pub fn udp_socket(&self) -> &UdpSocket {
+        self.inner.udp_socket()
+    }
+
+    pub fn clone_inner(&self) -> Arc<HostUdpSocketInner> {
+        Arc::clone(&self.inner)
+    }
+}
+
+impl HostUdpSocketInner {
+    pub fn udp_socket(&self) -> &UdpSocket {
+        let udp_socket = &self.udp_socket;
+
+        // Unpack the `AsyncFd`.
+        #[cfg(unix)]
+        let udp_socket = udp_socket.get_ref();
+
+        udp_socket
+    }
+}
+
+pub(crate) trait TableUdpSocketExt {
+    fn push_udp_socket(&mut self, udp_socket: HostUdpSocket) -> Result<u32, TableError>;
+    fn delete_udp_socket(&mut self, fd: u32) -> Result<HostUdpSocket, TableError>;
+    fn is_udp_socket(&self, fd: u32) -> bool;
+    fn get_udp_socket(&self, fd: u32) -> Result<&HostUdpSocket, TableError>;
+    fn get_udp_socket_mut(&mut self, fd: u32) -> Result<&mut HostUdpSocket, TableError>;
+}
+
+impl TableUdpSocketExt for Table {
+    fn push_udp_socket(&mut self, udp_socket: HostUdpSocket) -> Result<u32, TableError> {
+        self.push(Box::new(udp_socket))
+    }
+    fn delete_udp_socket(&mut self, fd: u32) -> Result<HostUdpSocket, TableError> {
+        self.delete(fd)
+    }
+    fn is_udp_socket(&self, fd: u32) -> bool {
+        self.is::<HostUdpSocket>(fd)
+    }
+    fn get_udp_socket(&self, fd: u32) -> Result<&HostUdpSocket, TableError> {
+        self.get(fd)
+    }
+    fn get_udp_socket_mut(&mut self, fd: u32) -> Result<&mut HostUdpSocket, TableError> {
+        self.get_mut(fd)
+    }
+}
@@ -421,6 +421,34 @@ impl Config {
         self
     }
 
+    /// Configures whether the values of wasm locals, and of the operand
+    /// stack, are recovered for each frame of a [`WasmBacktrace`] and of a
+    /// [`WasmCoreDump`].
+    ///
+    /// When enabled, compiled wasm functions save their locals to a dedicated
+    /// area of their stack frame every time a local is written to. This allows
+    /// [`FrameInfo::locals`] to report the values that locals held when a
+    /// backtrace was captured, and those values are included in serialized
+    /// core dumps. Values on the operand stack are similarly saved before
+    /// every call or instruction that may trap, and are reported by
+    /// [`FrameInfo::operand_stack`]. This comes at a runtime cost since every
+    /// write to a local, and many operand stack values, also become stores to
+    /// memory.
+    ///
+    /// Currently only values of type `i32`, `i64`, `f32` and `f64` are
+    /// recovered, and only when compiling with Cranelift.
+    ///
+    /// This option is `false` by default.
+    ///
+    /// [`WasmBacktrace`]: crate::WasmBacktrace
+    /// [`WasmCoreDump`]: crate::WasmCoreDump
+    /// [`FrameInfo::locals`]: crate::FrameInfo::locals
+    /// [`FrameInfo::operand_stack`]: crate::FrameInfo::operand_stack
+    pub fn wasm_backtrace_locals(&mut self, enable: bool) -> &mut Self {
+        self.tunables.capture_wasm_locals = enable;
+        self
+    }
+
     /// Configures whether to generate native unwind information
     /// (e.g. `.eh_frame` on Linux).
     ///
@@ -850,6 +878,46 @@ impl Config {
         self
     }
 
+    /// Configures whether the WebAssembly extended-const [proposal] will
+    /// be enabled for compilation.
+    ///
+    /// This proposal allows the `i32` and `i64` `add`, `sub` and `mul`
+    /// instructions to be used within constant expressions, such as global
+    /// initializers and the offsets of element and data segments.
+    ///
+    /// This is `false` by default.
+    ///
+    /// [proposal]: https://github.com/webassembly/extended-const
+    pub fn wasm_extended_const(&mut self, enable: bool) -> &mut Self {
+        self.features.extended_const = enable;
+        self
+    }
+
+    /// Configures whether the WebAssembly exception-handling [proposal] will
+    /// be enabled for compilation.
+    ///
+    /// This feature gates tags along with the `try`, `catch`, `catch_all`,
+    /// `delegate`, `throw` and `rethrow` instructions. Exceptions which
+    /// propagate out of wasm are returned to the host as a
+    /// [`WasmException`](crate::WasmException) error, and host functions may
+    /// throw an exception into wasm by returning such an error.
+    ///
+    /// Exceptions aren't propagated by unwinding the native stack. Instead
+    /// compiled code checks for a pending exception after each call it
+    /// makes, which adds a small cost to every call when this is enabled.
+    ///
+    /// This is only supported by Cranelift: enabling it along with
+    /// [`Strategy::Winch`] makes engine creation fail.
+    ///
+    /// This is `false` by default.
+    ///
+    /// [proposal]: https://github.com/webassembly/exception-handling
+    pub fn wasm_exceptions(&mut self, enable: bool) -> &mut Self {
+        self.features.exceptions = enable;
+        self.tunables.exceptions = enable;
+        self
+    }
+
     /// Configures whether the WebAssembly component-model [proposal] will
     /// be enabled for compilation.
     ///
@@ -1478,9 +1546,15 @@ impl Config {
     /// Configures whether or not a coredump should be generated and attached to
     /// the anyhow::Error when a trap is raised.
     ///
+    /// When enabled, compiled wasm functions save their `VMContext` to their
+    /// stack frame on entry so that each frame of the coredump is attributed to
+    /// the instance it belongs to, even when a module is instantiated more than
+    /// once.
+    ///
     /// This option is disabled by default.
     pub fn coredump_on_trap(&mut self, enable: bool) -> &mut Self {
         self.coredump_on_trap = enable;
+        self.tunables.save_frame_vmctx = enable;
         self
     }
 
@@ -1541,6 +1615,10 @@ impl Config {
         if self.features.threads && !self.features.bulk_memory {
             bail!("feature 'threads' requires 'bulk_memory' to be enabled");
         }
+        #[cfg(any(feature = "cranelift", feature = "winch"))]
+        if self.features.exceptions && matches!(self.compiler_config.strategy, Strategy::Winch) {
+            bail!("feature 'exceptions' is not supported by Winch");
+        }
         #[cfg(feature = "async")]
         if self.async_support && self.max_wasm_stack > self.async_stack_size {
             bail!("max_wasm_stack size cannot exceed the async_stack_size");
@@ -1777,6 +1855,8 @@ impl fmt::Debug for Config {
             .field("wasm_simd", &self.features.simd)
             .field("wasm_relaxed_simd", &self.features.relaxed_simd)
             .field("wasm_multi_value", &self.features.multi_value)
+            .field("wasm_extended_const", &self.features.extended_const)
+            .field("wasm_exceptions", &self.features.exceptions)
             .field(
                 "static_memory_maximum_size",
                 &(u64::from(self.tunables.static_memory_bound)
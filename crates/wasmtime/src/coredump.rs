@@ -1,6 +1,10 @@
+use std::collections::HashMap;
 use std::fmt;
 
-use crate::{store::StoreOpaque, FrameInfo, Global, Instance, Memory, Module, WasmBacktrace};
+use crate::{
+    store::StoreOpaque, AsContextMut, FrameInfo, Global, Instance, Memory, Module, StoreContextMut,
+    Val, ValType, WasmBacktrace,
+};
 
 /// Representation of a core dump of a WebAssembly module
 ///
@@ -76,6 +80,239 @@ impl WasmCoreDump {
     pub fn store_memories(&self) -> &[Memory] {
         self.store_memories.as_ref()
     }
+
+    /// Serialize this core dump into [the standard core dump binary
+    /// format][spec].
+    ///
+    /// The `name` parameter may be a file path, URL, or arbitrary name for the
+    /// "main" Wasm service or executable that was running in this store.
+    ///
+    /// Once serialized, you can write this core dump to disk, send it over the
+    /// network, or pass it to other debugging tools that consume Wasm core
+    /// dumps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` is not the store this core dump was captured from.
+    ///
+    /// [spec]: https://github.com/WebAssembly/tool-conventions/blob/main/Coredump.md
+    pub fn serialize(&self, mut store: impl AsContextMut, name: &str) -> Vec<u8> {
+        let store = store.as_context_mut();
+        self._serialize(store, name)
+    }
+
+    fn _serialize<T>(&self, mut store: StoreContextMut<'_, T>, name: &str) -> Vec<u8> {
+        let mut core_dump = wasm_encoder::Module::new();
+
+        core_dump.section(&wasm_encoder::CoreDumpSection::new(name));
+
+        // A map from each memory to its index in the core dump's memories
+        // section.
+        let mut memory_to_idx = HashMap::new();
+
+        let mut data = wasm_encoder::DataSection::new();
+
+        {
+            let mut memories = wasm_encoder::MemorySection::new();
+            for mem in self.memories(&mut store) {
+                if memory_to_idx.contains_key(&mem.hash_key(&store.0)) {
+                    continue;
+                }
+
+                memory_to_idx.insert(mem.hash_key(&store.0), memories.len());
+
+                let ty = mem.ty(&store);
+                memories.memory(wasm_encoder::MemoryType {
+                    minimum: mem.size(&store),
+                    maximum: ty.maximum(),
+                    memory64: ty.is_64(),
+                    shared: ty.is_shared(),
+                });
+
+                // Attach the memory data, balancing number of data segments
+                // and binary size. We don't want to attach the whole memory in
+                // one big segment, since it likely contains a bunch of large
+                // runs of zeroes. But we can't encode the data without any
+                // potential runs of zeroes (i.e. including only non-zero data
+                // in our segments) because we can run up against the
+                // implementation limits for number of segments in a Wasm
+                // module this way. So to balance these conflicting desires, we
+                // break the memory up into reasonably-sized chunks and then
+                // trim runs of zeroes from the start and end of each chunk.
+                const CHUNK_SIZE: u32 = 4096;
+                // Shared memories may be concurrently modified by other
+                // threads; a best-effort snapshot is all we need here.
+                let mem_data = unsafe { mem.data_maybe_shared(&store.0) };
+                for (i, chunk) in mem_data.chunks(CHUNK_SIZE as usize).enumerate() {
+                    if let Some(start) = chunk.iter().position(|byte| *byte != 0) {
+                        let end = chunk.iter().rposition(|byte| *byte != 0).unwrap() + 1;
+                        let offset = (i as u32) * CHUNK_SIZE + (start as u32);
+                        let offset = if ty.is_64() {
+                            wasm_encoder::ConstExpr::i64_const(offset as i64)
+                        } else {
+                            wasm_encoder::ConstExpr::i32_const(offset as i32)
+                        };
+                        data.active(
+                            memory_to_idx[&mem.hash_key(&store.0)],
+                            &offset,
+                            chunk[start..end].iter().copied(),
+                        );
+                    }
+                }
+            }
+            core_dump.section(&memories);
+        }
+
+        // A map from each global to its index in the core dump's globals
+        // section.
+        let mut global_to_idx = HashMap::new();
+
+        {
+            let mut globals = wasm_encoder::GlobalSection::new();
+            for g in self.globals(&mut store) {
+                if global_to_idx.contains_key(&g.hash_key(&store.0)) {
+                    continue;
+                }
+
+                global_to_idx.insert(g.hash_key(&store.0), globals.len());
+
+                let ty = g.ty(&store);
+                let mutable = matches!(ty.mutability(), crate::Mutability::Var);
+                let val_type = match ty.content() {
+                    ValType::I32 => wasm_encoder::ValType::I32,
+                    ValType::I64 => wasm_encoder::ValType::I64,
+                    ValType::F32 => wasm_encoder::ValType::F32,
+                    ValType::F64 => wasm_encoder::ValType::F64,
+                    ValType::V128 => wasm_encoder::ValType::V128,
+                    ValType::FuncRef => wasm_encoder::ValType::FUNCREF,
+                    ValType::ExternRef => wasm_encoder::ValType::EXTERNREF,
+                };
+                let init = match g.get(&mut store) {
+                    Val::I32(x) => wasm_encoder::ConstExpr::i32_const(x),
+                    Val::I64(x) => wasm_encoder::ConstExpr::i64_const(x),
+                    Val::F32(x) => wasm_encoder::ConstExpr::f32_const(f32::from_bits(x)),
+                    Val::F64(x) => wasm_encoder::ConstExpr::f64_const(f64::from_bits(x)),
+                    Val::V128(x) => wasm_encoder::ConstExpr::v128_const(x as i128),
+                    // Reference values are opaque to a core dump consumer, so
+                    // they are recorded as null references.
+                    Val::FuncRef(_) => {
+                        wasm_encoder::ConstExpr::ref_null(wasm_encoder::HeapType::Func)
+                    }
+                    Val::ExternRef(_) => {
+                        wasm_encoder::ConstExpr::ref_null(wasm_encoder::HeapType::Extern)
+                    }
+                };
+                globals.global(wasm_encoder::GlobalType { val_type, mutable }, &init);
+            }
+            core_dump.section(&globals);
+        }
+
+        core_dump.section(&data);
+
+        // A map from module id to its index within the core dump's modules
+        // section.
+        let mut module_to_index = HashMap::new();
+
+        {
+            let mut modules = wasm_encoder::CoreDumpModulesSection::new();
+            for (i, module) in self.modules().iter().enumerate() {
+                let i = u32::try_from(i).unwrap();
+                module_to_index.insert(module.compiled_module().unique_id(), i);
+                match module.name() {
+                    Some(name) => modules.module(name),
+                    None => modules.module(&format!("<anonymous-module-{i}>")),
+                };
+            }
+            core_dump.section(&modules);
+        }
+
+        // Frames are attributed to instances through the `VMContext` that
+        // their function saved to their stack frame. Functions that don't save
+        // it, such as those compiled by Winch or loaded from modules that were
+        // precompiled without core dumps enabled, are attributed to the last
+        // instance of their module instead, which is only correct when the
+        // module is instantiated at most once.
+        let mut vmctx_to_instance = HashMap::new();
+        let mut module_to_instance = HashMap::new();
+
+        {
+            let mut instances = wasm_encoder::CoreDumpInstancesSection::new();
+            for (i, instance) in self.instances().iter().enumerate() {
+                let module = instance.module(&store.0);
+                let module_id = module.compiled_module().unique_id();
+                let module_index = module_to_index[&module_id];
+                let i = u32::try_from(i).unwrap();
+                vmctx_to_instance.insert(instance.vmctx(&store.0), i);
+                module_to_instance.insert(module_id, i);
+
+                let memories = instance
+                    .all_memories(&mut store.0)
+                    .into_iter()
+                    .map(|mem| memory_to_idx[&mem.hash_key(&store.0)])
+                    .collect::<Vec<_>>();
+
+                let globals = instance
+                    .all_globals(&mut store.0)
+                    .into_iter()
+                    .map(|g| global_to_idx[&g.hash_key(&store.0)])
+                    .collect::<Vec<_>>();
+
+                instances.instance(module_index, memories, globals);
+            }
+            core_dump.section(&instances);
+        }
+
+        {
+            let thread_name = "main";
+            let mut stack = wasm_encoder::CoreDumpStackSection::new(thread_name);
+            for frame in self.frames() {
+                // See the comment above `vmctx_to_instance` for details.
+                let instance = match frame.vmctx() {
+                    Some(vmctx) => vmctx_to_instance[&vmctx],
+                    None => module_to_instance[&frame.module().compiled_module().unique_id()],
+                };
+
+                let func = frame.func_index();
+
+                let offset = frame
+                    .func_offset()
+                    .and_then(|o| u32::try_from(o).ok())
+                    .unwrap_or(0);
+
+                // Locals and the operand stack are only recovered when locals
+                // are enabled in the config.
+                let locals = frame.locals().iter().map(val_to_coredump_value);
+                let operand_stack = frame.operand_stack().iter().map(val_to_coredump_value);
+
+                stack.frame(instance, func, offset, locals, operand_stack);
+            }
+            core_dump.section(&stack);
+        }
+
+        core_dump.finish()
+    }
+
+    /// All memories reachable from this core dump: those of every instance,
+    /// followed by the ones owned directly by the store.
+    fn memories<T>(&self, store: &mut StoreContextMut<'_, T>) -> Vec<Memory> {
+        let mut memories = Vec::new();
+        for instance in self.instances() {
+            memories.extend(instance.all_memories(&mut store.0));
+        }
+        memories.extend(self.store_memories().iter().copied());
+        memories
+    }
+
+    /// All globals reachable from this core dump: those of every instance,
+    /// followed by the ones owned directly by the store.
+    fn globals<T>(&self, store: &mut StoreContextMut<'_, T>) -> Vec<Global> {
+        let mut globals = Vec::new();
+        for instance in self.instances() {
+            globals.extend(instance.all_globals(&mut store.0));
+        }
+        globals.extend(self.store_globals().iter().copied());
+        globals
+    }
 }
 
 impl fmt::Display for WasmCoreDump {
@@ -113,3 +350,14 @@ impl fmt::Debug for WasmCoreDump {
         write!(f, "<wasm core dump>")
     }
 }
+
+/// Converts a value recovered from a frame to its core dump representation.
+fn val_to_coredump_value(val: &Option<Val>) -> wasm_encoder::CoreDumpValue {
+    match val {
+        Some(Val::I32(x)) => wasm_encoder::CoreDumpValue::I32(*x),
+        Some(Val::I64(x)) => wasm_encoder::CoreDumpValue::I64(*x),
+        Some(Val::F32(x)) => wasm_encoder::CoreDumpValue::F32(f32::from_bits(*x)),
+        Some(Val::F64(x)) => wasm_encoder::CoreDumpValue::F64(f64::from_bits(*x)),
+        _ => wasm_encoder::CoreDumpValue::Missing,
+    }
+}
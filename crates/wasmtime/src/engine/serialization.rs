@@ -344,8 +344,22 @@ impl Metadata {
             // whether it's present or not)
             generate_address_map: _,
 
+            // Similarly to the address map, functions compiled with or without
+            // their locals being saved can be loaded into either kind of
+            // engine, with locals only being recovered for the former.
+            capture_wasm_locals: _,
+
+            // Likewise, frames of functions compiled without saving their
+            // `VMContext` are attributed to an instance of their module
+            // instead.
+            save_frame_vmctx: _,
+
             // Just a debugging aid, doesn't affect functionality at all.
             debug_adapter_modules: _,
+
+            // This is always the same as the `exceptions` wasm feature, which
+            // is checked along with the other features below.
+            exceptions: _,
         } = self.tunables;
 
         Self::check_int(
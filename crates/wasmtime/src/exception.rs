@@ -0,0 +1,164 @@
+//! Exceptions thrown between WebAssembly and the host.
+
+use crate::{AsContextMut, StoreContextMut, Tag, Val, ValRaw};
+use anyhow::{bail, Result};
+use std::fmt;
+use wasmtime_runtime::VMException;
+
+/// A WebAssembly exception, thrown with a [`Tag`] and carrying a payload of
+/// values whose types are described by that tag.
+///
+/// When an exception thrown by WebAssembly isn't caught before it propagates
+/// out of the function called by the host, the call returns an
+/// [`anyhow::Error`] whose root cause is a `WasmException`. It may be
+/// inspected with [`downcast_ref`](anyhow::Error::downcast_ref).
+///
+/// Conversely a host function called by WebAssembly may throw an exception by
+/// returning an error whose root cause is a `WasmException`. The exception is
+/// then propagated through the WebAssembly which called the host function,
+/// where it may be caught by a `catch` clause for its tag, rather than
+/// trapping.
+///
+/// Exceptions are only supported when [`Config::wasm_exceptions`] is enabled.
+///
+/// [`Config::wasm_exceptions`]: crate::Config::wasm_exceptions
+#[derive(Clone)]
+pub struct WasmException {
+    tag: Tag,
+    payload: Vec<Val>,
+}
+
+impl WasmException {
+    /// Creates a new exception for `tag`, carrying the values in `payload`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `payload` doesn't match the parameters of the type
+    /// of `tag`, or if any of its values don't belong to `store`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` doesn't belong to `store`.
+    pub fn new(mut store: impl AsContextMut, tag: &Tag, payload: &[Val]) -> Result<WasmException> {
+        let store = store.as_context_mut();
+        let ty = tag.ty(&store);
+        if ty.params().len() != payload.len() {
+            bail!(
+                "expected {} payload values, got {}",
+                ty.params().len(),
+                payload.len()
+            );
+        }
+        for (ty, val) in ty.params().zip(payload) {
+            if val.ty() != ty {
+                bail!(
+                    "payload type mismatch: found {} but expected {}",
+                    val.ty(),
+                    ty
+                );
+            }
+            if !val.comes_from_same_store(store.0) {
+                bail!("cross-`Store` values are not currently supported");
+            }
+        }
+        Ok(WasmException {
+            tag: *tag,
+            payload: payload.to_vec(),
+        })
+    }
+
+    /// Returns the tag this exception was thrown with.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the values carried by this exception.
+    pub fn payload(&self) -> &[Val] {
+        &self.payload
+    }
+
+    /// Converts an exception which propagated out of wasm.
+    ///
+    /// # Unsafety
+    ///
+    /// `exception` must have been thrown within `store`.
+    pub(crate) unsafe fn from_raw<T>(
+        store: &mut StoreContextMut<'_, T>,
+        exception: VMException,
+    ) -> WasmException {
+        let tag = Tag::from_vmtag(store.0, exception.tag(), exception.params());
+        let payload = exception
+            .params()
+            .iter()
+            .zip(exception.payload())
+            .map(|(ty, raw)| Val::from_raw(&mut *store, *raw, crate::ValType::from_wasm_type(ty)))
+            .collect();
+        WasmException { tag, payload }
+    }
+
+    /// Converts this exception into the representation which is propagated
+    /// through wasm.
+    ///
+    /// # Unsafety
+    ///
+    /// This exception must belong to `store`.
+    unsafe fn into_raw<T>(self, store: &mut StoreContextMut<'_, T>) -> VMException {
+        let vmtag = self.tag.vmimport(store.0);
+        let ty = self.tag.wasmtime_ty(store.0.store_data()).clone();
+        let payload = self
+            .payload
+            .iter()
+            .map(|val| val.to_raw(&mut *store))
+            .collect::<Box<[ValRaw]>>();
+        VMException::new(vmtag, payload, ty.params())
+    }
+}
+
+impl fmt::Debug for WasmException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmException")
+            .field("tag", &self.tag)
+            .field("payload", &self.payload)
+            .finish()
+    }
+}
+
+impl fmt::Display for WasmException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "uncaught wasm exception")
+    }
+}
+
+impl std::error::Error for WasmException {}
+
+/// Throws `error` into the wasm which called a host function within `store`
+/// if it's a [`WasmException`], in which case the host function must then
+/// return normally. Any other error is handed back to be raised as a trap.
+pub(crate) fn throw_from_host<T>(
+    store: &mut StoreContextMut<'_, T>,
+    error: anyhow::Error,
+) -> Result<()> {
+    if !store.0.engine().config().features.exceptions {
+        return Err(error);
+    }
+    let exception = error.downcast::<WasmException>()?;
+    if !exception.tag.comes_from_same_store(store.0) {
+        bail!("cross-`Store` exceptions are not currently supported");
+    }
+    unsafe {
+        let exception = exception.into_raw(store);
+        let limits = store.0.vmruntime_limits();
+        store.0.exceptions().throw(limits, exception);
+    }
+    Ok(())
+}
+
+/// Takes the exception which propagated out of a call to wasm within `store`,
+/// if there is one.
+pub(crate) fn take_pending<T>(store: &mut StoreContextMut<'_, T>) -> Option<WasmException> {
+    unsafe {
+        let limits = store.0.vmruntime_limits();
+        let exception = store.0.exceptions().take_pending(limits)?;
+        Some(WasmException::from_raw(store, exception))
+    }
+}
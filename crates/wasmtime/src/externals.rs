@@ -2,13 +2,13 @@ use crate::store::{StoreData, StoreOpaque, Stored};
 use crate::trampoline::{generate_global_export, generate_table_export};
 use crate::{
     AsContext, AsContextMut, Engine, ExternRef, ExternType, Func, GlobalType, Memory, Mutability,
-    SharedMemory, TableType, Val, ValType,
+    SharedMemory, TableType, TagType, Val, ValType,
 };
 use anyhow::{anyhow, bail, Result};
 use runtime::ExportGlobal;
 use std::mem;
 use std::ptr;
-use wasmtime_runtime::{self as runtime};
+use wasmtime_runtime::{self as runtime, VMTag};
 
 // Externals
 
@@ -33,6 +33,8 @@ pub enum Extern {
     /// A WebAssembly shared memory; these are handled separately from
     /// [`Memory`].
     SharedMemory(SharedMemory),
+    /// A WebAssembly exception tag.
+    Tag(Tag),
 }
 
 impl Extern {
@@ -87,6 +89,16 @@ impl Extern {
         }
     }
 
+    /// Returns the underlying `Tag`, if this external is a tag.
+    ///
+    /// Returns `None` if this is not a tag.
+    pub fn into_tag(self) -> Option<Tag> {
+        match self {
+            Extern::Tag(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
     /// Returns the type associated with this `Extern`.
     ///
     /// The `store` argument provided must own this `Extern` and is used to look
@@ -103,6 +115,7 @@ impl Extern {
             Extern::SharedMemory(ft) => ExternType::Memory(ft.ty()),
             Extern::Table(tt) => ExternType::Table(tt.ty(store)),
             Extern::Global(gt) => ExternType::Global(gt.ty(store)),
+            Extern::Tag(tt) => ExternType::Tag(tt.ty(store)),
         }
     }
 
@@ -127,6 +140,7 @@ impl Extern {
             wasmtime_runtime::Export::Table(t) => {
                 Extern::Table(Table::from_wasmtime_table(t, store))
             }
+            wasmtime_runtime::Export::Tag(t) => Extern::Tag(Tag::from_wasmtime_tag(t, store)),
         }
     }
 
@@ -137,6 +151,7 @@ impl Extern {
             Extern::Memory(m) => m.comes_from_same_store(store),
             Extern::SharedMemory(m) => Engine::same(m.engine(), store.engine()),
             Extern::Table(t) => store.store_data().contains(t.0),
+            Extern::Tag(t) => store.store_data().contains(t.0),
         }
     }
 }
@@ -171,6 +186,12 @@ impl From<Table> for Extern {
     }
 }
 
+impl From<Tag> for Extern {
+    fn from(r: Tag) -> Self {
+        Extern::Tag(r)
+    }
+}
+
 /// A WebAssembly `global` value which can be read and written to.
 ///
 /// A `global` in WebAssembly is sort of like a global variable within an
@@ -349,6 +370,12 @@ impl Global {
         &data[self.0].global
     }
 
+    /// Returns a key that identifies the underlying global, shared by all
+    /// the `Global` handles that refer to it.
+    pub(crate) fn hash_key(&self, store: &StoreOpaque) -> impl std::hash::Hash + Eq {
+        store[self.0].definition as usize
+    }
+
     pub(crate) fn vmimport(&self, store: &StoreOpaque) -> wasmtime_runtime::VMGlobalImport {
         wasmtime_runtime::VMGlobalImport {
             from: store[self.0].definition,
@@ -700,6 +727,107 @@ impl Table {
     }
 }
 
+/// A WebAssembly exception tag.
+///
+/// Tags identify the exceptions thrown by the `throw` instruction, and by
+/// [`WasmException`](crate::WasmException)s thrown from the host. A `catch`
+/// clause only catches exceptions thrown with its own tag, and the values
+/// carried by such exceptions have the types described by the tag's
+/// [`TagType`]. Two tags with the same type are still distinct from each
+/// other, so a tag must be imported by a module for it to catch exceptions
+/// thrown with that tag from elsewhere.
+///
+/// A [`Tag`] "belongs" to the store that it was originally created within
+/// (either via [`Tag::new`] or via instantiating a
+/// [`Module`](crate::Module)). Operations on a [`Tag`] only work with the
+/// store it belongs to, and if another store is passed in by accident then
+/// methods will panic.
+#[derive(Copy, Clone, Debug)]
+#[repr(transparent)] // here for the C API
+pub struct Tag(Stored<TagData>);
+
+pub(crate) struct TagData {
+    tag: VMTag,
+    ty: TagType,
+}
+
+impl Tag {
+    /// Creates a new WebAssembly tag of type `ty`, distinct from all other
+    /// tags.
+    ///
+    /// The `store` argument will be the owner of the [`Tag`] returned. It may
+    /// be provided as an import to [`Instance::new`](crate::Instance::new) or
+    /// [`Linker::define`](crate::Linker::define), and used to throw
+    /// exceptions from the host with [`WasmException::new`](crate::WasmException::new).
+    pub fn new(mut store: impl AsContextMut, ty: &TagType) -> Tag {
+        let store = store.as_context_mut().0;
+        Tag(store.store_data_mut().insert(TagData {
+            tag: VMTag::new(),
+            ty: ty.clone(),
+        }))
+    }
+
+    /// Returns the underlying type of this tag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this tag.
+    pub fn ty(&self, store: impl AsContext) -> TagType {
+        store.as_context()[self.0].ty.clone()
+    }
+
+    pub(crate) unsafe fn from_wasmtime_tag(
+        wasmtime_export: wasmtime_runtime::ExportTag,
+        store: &mut StoreOpaque,
+    ) -> Tag {
+        // The signature is kept registered by the module of the instance
+        // which this tag was exported from.
+        let ty = store
+            .engine()
+            .signatures()
+            .lookup_type(wasmtime_export.signature)
+            .expect("signature should be registered");
+        Tag(store.store_data_mut().insert(TagData {
+            tag: wasmtime_export.tag,
+            ty: TagType::from_wasm_func_type(ty),
+        }))
+    }
+
+    /// Returns a `Tag` for the runtime tag `tag`, whose exceptions carry
+    /// payloads of the types in `params`, reusing an existing `Tag` if one
+    /// already refers to it.
+    pub(crate) fn from_vmtag(
+        store: &mut StoreOpaque,
+        tag: VMTag,
+        params: &[wasmtime_environ::WasmType],
+    ) -> Tag {
+        let data = store.store_data();
+        if let Some(existing) = data.iter::<TagData>().find(|t| data[*t].tag == tag) {
+            return Tag(existing);
+        }
+        let ty = wasmtime_environ::WasmFuncType::new(params.into(), Box::new([]));
+        Tag(store.store_data_mut().insert(TagData {
+            tag,
+            ty: TagType::from_wasm_func_type(ty),
+        }))
+    }
+
+    pub(crate) fn wasmtime_ty<'a>(
+        &self,
+        data: &'a StoreData,
+    ) -> &'a wasmtime_environ::WasmFuncType {
+        data[self.0].ty.as_wasm_func_type()
+    }
+
+    pub(crate) fn vmimport(&self, store: &StoreOpaque) -> VMTag {
+        store[self.0].tag
+    }
+
+    pub(crate) fn comes_from_same_store(&self, store: &StoreOpaque) -> bool {
+        store.store_data().contains(self.0)
+    }
+}
+
 // Exports
 
 /// An exported WebAssembly value.
@@ -765,4 +893,10 @@ impl<'instance> Export<'instance> {
     pub fn into_global(self) -> Option<Global> {
         self.definition.into_global()
     }
+
+    /// Consume this `Export` and return the contained `Tag`, if it's a tag,
+    /// or `None` otherwise.
+    pub fn into_tag(self) -> Option<Tag> {
+        self.definition.into_tag()
+    }
 }
@@ -12,8 +12,9 @@ use std::pin::Pin;
 use std::ptr::{self, NonNull};
 use std::sync::Arc;
 use wasmtime_runtime::{
-    ExportFunction, SendSyncPtr, StoreBox, VMArrayCallHostFuncContext, VMContext, VMFuncRef,
-    VMFunctionImport, VMNativeCallHostFuncContext, VMOpaqueContext, VMSharedSignatureIndex,
+    ExportFunction, ModuleInfoLookup, SendSyncPtr, StoreBox, VMArrayCallHostFuncContext, VMContext,
+    VMFuncRef, VMFunctionImport, VMNativeCallHostFuncContext, VMOpaqueContext,
+    VMSharedSignatureIndex,
 };
 
 /// A WebAssembly function which can be called.
@@ -1360,16 +1361,33 @@ pub(crate) fn invoke_wasm_and_catch_traps<T>(
             exit_wasm(store, exit);
             return Err(trap);
         }
+        let tunables = &store.0.engine().config().tunables;
+        let module_info_lookup = if tunables.capture_wasm_locals || tunables.save_frame_vmctx {
+            let modules: &(dyn ModuleInfoLookup + 'static) = store.0.modules();
+            Some(modules as *const _)
+        } else {
+            None
+        };
         let result = wasmtime_runtime::catch_traps(
             store.0.signal_handler(),
             store.0.engine().config().wasm_backtrace,
             store.0.engine().config().coredump_on_trap,
+            module_info_lookup,
             store.0.default_caller(),
             closure,
         );
         exit_wasm(store, exit);
+        if result.is_err() && *store.0.runtime_limits().stack_limit.get() == usize::MAX {
+            // A trap unwound through every wasm frame in this store, skipping
+            // over the release of any exceptions held by `catch` clauses.
+            store.0.exceptions().clear_caught();
+        }
         store.0.call_hook(CallHook::ReturningFromWasm)?;
-        result.map_err(|t| crate::trap::from_runtime_box(store.0, t))
+        result.map_err(|t| crate::trap::from_runtime_box(store.0, t))?;
+        match crate::exception::take_pending(store) {
+            Some(exception) => Err(exception.into()),
+            None => Ok(()),
+        }
     }
 }
 
@@ -2009,7 +2027,12 @@ macro_rules! impl_into_func {
                                 } else {
                                     match ret.into_abi_for_ret(caller.store.0, retptr) {
                                         Ok(val) => CallResult::Ok(val),
-                                        Err(trap) => CallResult::Trap(trap.into()),
+                                        // The results of a call which threw
+                                        // an exception are never used.
+                                        Err(trap) => match crate::exception::throw_from_host(&mut caller.store, trap.into()) {
+                                            Ok(()) => CallResult::Ok(mem::zeroed()),
+                                            Err(trap) => CallResult::Trap(trap),
+                                        },
                                     }
                                 }
 
@@ -2147,7 +2170,10 @@ impl HostFunc {
         let func = move |caller_vmctx, values: &mut [ValRaw]| {
             Caller::<T>::with(caller_vmctx, |mut caller| {
                 caller.store.0.call_hook(CallHook::CallingHost)?;
-                let result = func(caller.sub_caller(), values)?;
+                let result = match func(caller.sub_caller(), values) {
+                    Ok(result) => result,
+                    Err(error) => crate::exception::throw_from_host(&mut caller.store, error)?,
+                };
                 caller.store.0.call_hook(CallHook::ReturningFromHost)?;
                 Ok(result)
             })
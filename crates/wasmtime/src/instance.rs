@@ -9,10 +9,12 @@ use anyhow::{anyhow, bail, Context, Result};
 use std::mem;
 use std::ptr::NonNull;
 use std::sync::Arc;
-use wasmtime_environ::{EntityType, FuncIndex, GlobalIndex, MemoryIndex, PrimaryMap, TableIndex};
+use wasmtime_environ::{
+    EntityType, FuncIndex, GlobalIndex, MemoryIndex, PrimaryMap, TableIndex, TagIndex,
+};
 use wasmtime_runtime::{
     Imports, InstanceAllocationRequest, StorePtr, VMContext, VMFuncRef, VMFunctionImport,
-    VMGlobalImport, VMMemoryImport, VMNativeCallFunction, VMOpaqueContext, VMTableImport,
+    VMGlobalImport, VMMemoryImport, VMNativeCallFunction, VMOpaqueContext, VMTableImport, VMTag,
 };
 
 /// An instantiated WebAssembly module.
@@ -338,6 +340,49 @@ impl Instance {
         Instance(store.store_data_mut().insert(handle))
     }
 
+    /// Returns the module this instance was instantiated from.
+    pub(crate) fn module<'a>(&self, store: &'a StoreOpaque) -> &'a Module {
+        let env_module = store.instance(store[self.0].id).module();
+        store
+            .modules()
+            .all_modules()
+            .find(|module| Arc::ptr_eq(module.compiled_module().module(), env_module))
+            .expect("instance's module must be registered in its store")
+    }
+
+    /// Returns the address of this instance's `VMContext`.
+    pub(crate) fn vmctx(&self, store: &StoreOpaque) -> usize {
+        store.instance(store[self.0].id).vmctx() as usize
+    }
+
+    /// Returns all the memories of this instance, both defined and
+    /// imported, in the order of the module's memory index space.
+    pub(crate) fn all_memories(&self, store: &mut StoreOpaque) -> Vec<Memory> {
+        let id = store[self.0].id;
+        let indices: Vec<MemoryIndex> = store.instance(id).module().memory_plans.keys().collect();
+        indices
+            .into_iter()
+            .map(|index| {
+                let export = store.instance_mut(id).get_exported_memory(index);
+                unsafe { Memory::from_wasmtime_memory(export, store) }
+            })
+            .collect()
+    }
+
+    /// Returns all the globals of this instance, both defined and
+    /// imported, in the order of the module's global index space.
+    pub(crate) fn all_globals(&self, store: &mut StoreOpaque) -> Vec<Global> {
+        let id = store[self.0].id;
+        let indices: Vec<GlobalIndex> = store.instance(id).module().globals.keys().collect();
+        indices
+            .into_iter()
+            .map(|index| {
+                let export = store.instance_mut(id).get_exported_global(index);
+                unsafe { Global::from_wasmtime_global(export, store) }
+            })
+            .collect()
+    }
+
     fn start_raw<T>(&self, store: &mut StoreContextMut<'_, T>, start: FuncIndex) -> Result<()> {
         let id = store.0.store_data()[self.0].id;
         // If a start function is present, invoke it. Make sure we use all the
@@ -537,6 +582,7 @@ pub(crate) struct OwnedImports {
     tables: PrimaryMap<TableIndex, VMTableImport>,
     memories: PrimaryMap<MemoryIndex, VMMemoryImport>,
     globals: PrimaryMap<GlobalIndex, VMGlobalImport>,
+    tags: PrimaryMap<TagIndex, VMTag>,
 }
 
 impl OwnedImports {
@@ -552,6 +598,7 @@ impl OwnedImports {
             tables: PrimaryMap::new(),
             memories: PrimaryMap::new(),
             globals: PrimaryMap::new(),
+            tags: PrimaryMap::new(),
         }
     }
 
@@ -561,6 +608,7 @@ impl OwnedImports {
         self.tables.reserve(raw.num_imported_tables);
         self.memories.reserve(raw.num_imported_memories);
         self.globals.reserve(raw.num_imported_globals);
+        self.tags.reserve(raw.num_imported_tags);
     }
 
     #[cfg(feature = "component-model")]
@@ -569,6 +617,7 @@ impl OwnedImports {
         self.tables.clear();
         self.memories.clear();
         self.globals.clear();
+        self.tags.clear();
     }
 
     fn push(&mut self, item: &Extern, store: &mut StoreOpaque, module: &Module) {
@@ -588,6 +637,9 @@ impl OwnedImports {
             Extern::SharedMemory(i) => {
                 self.memories.push(i.vmimport(store));
             }
+            Extern::Tag(i) => {
+                self.tags.push(i.vmimport(store));
+            }
         }
     }
 
@@ -621,6 +673,9 @@ impl OwnedImports {
                     index: m.index,
                 });
             }
+            wasmtime_runtime::Export::Tag(t) => {
+                self.tags.push(t.tag);
+            }
         }
     }
 
@@ -630,6 +685,7 @@ impl OwnedImports {
             globals: self.globals.values().as_slice(),
             memories: self.memories.values().as_slice(),
             functions: self.functions.values().as_slice(),
+            tags: self.tags.values().as_slice(),
         }
     }
 }
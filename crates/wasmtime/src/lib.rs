@@ -397,6 +397,7 @@ mod code;
 mod config;
 mod coredump;
 mod engine;
+mod exception;
 mod externals;
 mod instance;
 mod limits;
@@ -416,6 +417,7 @@ mod values;
 pub use crate::config::*;
 pub use crate::coredump::*;
 pub use crate::engine::*;
+pub use crate::exception::WasmException;
 pub use crate::externals::*;
 pub use crate::func::*;
 pub use crate::instance::{Instance, InstancePre};
@@ -130,6 +130,7 @@ pub(crate) enum DefinitionType {
     // no longer be the current size of the table/memory.
     Table(wasmtime_environ::Table, u32),
     Memory(wasmtime_environ::Memory, u64),
+    Tag(wasmtime_environ::WasmFuncType),
 }
 
 macro_rules! generate_wrap_async_func {
@@ -1373,6 +1374,7 @@ impl DefinitionType {
                 DefinitionType::Memory(*t.wasmtime_ty(data), t.internal_size(store))
             }
             Extern::SharedMemory(t) => DefinitionType::Memory(*t.ty().wasmtime_memory(), t.size()),
+            Extern::Tag(t) => DefinitionType::Tag(t.wasmtime_ty(data).clone()),
         }
     }
 
@@ -1382,6 +1384,7 @@ impl DefinitionType {
             DefinitionType::Table(..) => "table",
             DefinitionType::Memory(..) => "memory",
             DefinitionType::Global(_) => "global",
+            DefinitionType::Tag(_) => "tag",
         }
     }
 }
@@ -569,6 +569,24 @@ impl Memory {
         &store[self.0].memory.memory
     }
 
+    /// Returns a key that identifies the underlying memory, shared by all
+    /// the `Memory` handles that refer to it.
+    pub(crate) fn hash_key(&self, store: &StoreOpaque) -> impl std::hash::Hash + Eq {
+        store[self.0].definition as usize
+    }
+
+    /// Returns the contents of this memory, which may be shared.
+    ///
+    /// # Unsafety
+    ///
+    /// Shared memories may be concurrently modified by other threads while
+    /// the returned slice is alive; callers must tolerate observing such
+    /// modifications, e.g. when taking a best-effort snapshot.
+    pub(crate) unsafe fn data_maybe_shared<'a>(&self, store: &'a StoreOpaque) -> &'a [u8] {
+        let definition = &*store[self.0].definition;
+        slice::from_raw_parts(definition.base, definition.current_length())
+    }
+
     pub(crate) fn vmimport(&self, store: &StoreOpaque) -> wasmtime_runtime::VMMemoryImport {
         let export = &store[self.0];
         wasmtime_runtime::VMMemoryImport {
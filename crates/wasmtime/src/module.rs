@@ -7,6 +7,7 @@ use crate::{
 };
 use anyhow::{bail, Context, Result};
 use once_cell::sync::OnceCell;
+use std::fmt;
 use std::fs;
 use std::mem;
 use std::ops::Range;
@@ -16,7 +17,7 @@ use std::sync::Arc;
 use wasmparser::{Parser, ValidPayload, Validator};
 use wasmtime_environ::{
     DefinedFuncIndex, DefinedMemoryIndex, HostPtr, ModuleEnvironment, ModuleTypes, ObjectKind,
-    VMOffsets,
+    SignatureIndex, VMOffsets, WasmFuncType,
 };
 use wasmtime_jit::{CodeMemory, CompiledModule, CompiledModuleInfo};
 use wasmtime_runtime::{
@@ -1096,6 +1097,14 @@ impl Module {
     }
 }
 
+impl fmt::Debug for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Module")
+            .field("name", &self.name())
+            .finish_non_exhaustive()
+    }
+}
+
 impl ModuleInner {
     fn memory_images(&self) -> Result<Option<&ModuleMemoryImages>> {
         let images = self
@@ -1216,6 +1225,10 @@ impl wasmtime_runtime::ModuleRuntimeInfo for ModuleInner {
         self.code.signatures().as_module_map().values().as_slice()
     }
 
+    fn signature(&self, index: SignatureIndex) -> &WasmFuncType {
+        &self.code.module_types()[index]
+    }
+
     fn offsets(&self) -> &VMOffsets<HostPtr> {
         &self.offsets
     }
@@ -1245,6 +1258,31 @@ impl wasmtime_runtime::ModuleInfo for ModuleInner {
 
         Some(&info.stack_maps[index].stack_map)
     }
+
+    fn lookup_wasm_func_info(&self, pc: usize) -> Option<&wasmtime_environ::WasmFunctionInfo> {
+        let text_offset = pc - self.module.text().as_ptr() as usize;
+
+        // Stack overflow is checked for in a function's prologue, before its
+        // frame has been allocated and anything saved to it, so there's
+        // nothing to read from the frame in that case.
+        let trap =
+            wasmtime_environ::lookup_trap_code(self.module.code_memory().trap_data(), text_offset);
+        if trap == Some(wasmtime_environ::Trap::StackOverflow) {
+            return None;
+        }
+
+        let (index, _func_offset) = self.module.func_by_text_offset(text_offset)?;
+        Some(self.module.wasm_func_info(index))
+    }
+
+    fn lookup_wasm_file_offset(&self, pc: usize) -> Option<u32> {
+        let text_offset = pc - self.module.text().as_ptr() as usize;
+        wasmtime_environ::lookup_file_pos(
+            self.module.code_memory().address_map_data(),
+            text_offset,
+        )?
+        .file_offset()
+    }
 }
 
 /// A barebones implementation of ModuleRuntimeInfo that is useful for
@@ -1324,6 +1362,10 @@ impl wasmtime_runtime::ModuleRuntimeInfo for BareModuleInfo {
         }
     }
 
+    fn signature(&self, _index: SignatureIndex) -> &WasmFuncType {
+        unreachable!()
+    }
+
     fn offsets(&self) -> &VMOffsets<HostPtr> {
         &self.offsets
     }
@@ -96,9 +96,9 @@ use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use wasmtime_runtime::{
-    ExportGlobal, ExportMemory, InstanceAllocationRequest, InstanceAllocator, InstanceHandle,
-    ModuleInfo, OnDemandInstanceAllocator, SignalHandler, StoreBox, StorePtr, VMContext,
-    VMExternRef, VMExternRefActivationsTable, VMFuncRef, VMRuntimeLimits, WasmFault,
+    ExceptionState, ExportGlobal, ExportMemory, InstanceAllocationRequest, InstanceAllocator,
+    InstanceHandle, ModuleInfo, OnDemandInstanceAllocator, SignalHandler, StoreBox, StorePtr,
+    VMContext, VMExternRef, VMExternRefActivationsTable, VMFuncRef, VMRuntimeLimits, WasmFault,
 };
 
 mod context;
@@ -295,6 +295,9 @@ pub struct StoreOpaque {
     num_component_instances: usize,
     signal_handler: Option<Box<SignalHandler<'static>>>,
     externref_activations_table: VMExternRefActivationsTable,
+    /// The exception currently propagating through wasm, if any, and those
+    /// caught by executing `catch` clauses.
+    exceptions: ExceptionState,
     modules: ModuleRegistry,
     func_refs: FuncRefs,
     host_globals: Vec<StoreBox<VMHostGlobalContext>>,
@@ -467,6 +470,7 @@ impl<T> Store<T> {
                 num_component_instances: 0,
                 signal_handler: None,
                 externref_activations_table: VMExternRefActivationsTable::new(),
+                exceptions: ExceptionState::default(),
                 modules: ModuleRegistry::default(),
                 func_refs: FuncRefs::default(),
                 host_globals: Vec::new(),
@@ -1296,6 +1300,11 @@ impl StoreOpaque {
         &mut self.externref_activations_table
     }
 
+    #[inline]
+    pub fn exceptions(&mut self) -> &mut ExceptionState {
+        &mut self.exceptions
+    }
+
     pub fn gc(&mut self) {
         // For this crate's API, we ensure that `set_stack_canary` invariants
         // are upheld for all host-->Wasm calls.
@@ -1967,6 +1976,10 @@ unsafe impl<T> wasmtime_runtime::Store for StoreInner<T> {
         (&mut inner.externref_activations_table, &inner.modules)
     }
 
+    fn exceptions(&mut self) -> &mut ExceptionState {
+        <StoreOpaque>::exceptions(self)
+    }
+
     fn memory_growing(
         &mut self,
         current: usize,
@@ -1,8 +1,9 @@
 use crate::coredump::WasmCoreDump;
 use crate::store::StoreOpaque;
-use crate::{AsContext, Module};
+use crate::{AsContext, Module, Val};
 use anyhow::Error;
 use std::fmt;
+use wasm_encoder::CoreDumpValue;
 use wasmtime_environ::{EntityRef, FilePos};
 use wasmtime_jit::{demangle_function_name, demangle_function_name_or_index};
 
@@ -331,7 +332,14 @@ impl WasmBacktrace {
             // Some(..)` instead of the `unwrap` you might otherwise expect and
             // we ignore frames from modules that were not registered in this
             // store's module registry.
-            if let Some((info, module)) = store.modules().lookup_frame_info(pc_to_lookup) {
+            if let Some((mut info, module)) = store.modules().lookup_frame_info(pc_to_lookup) {
+                info.locals = frame.locals().iter().map(saved_value_to_val).collect();
+                info.operand_stack = frame
+                    .operand_stack()
+                    .iter()
+                    .map(saved_value_to_val)
+                    .collect();
+                info.vmctx = frame.vmctx().map(|vmctx| vmctx as usize);
                 wasm_trace.push(info);
 
                 // If this frame has unparsed debug information and the
@@ -361,6 +369,18 @@ impl WasmBacktrace {
     }
 }
 
+/// Converts a value read from a frame while capturing a backtrace, returning
+/// `None` if it couldn't be recovered.
+fn saved_value_to_val(value: &CoreDumpValue) -> Option<Val> {
+    match *value {
+        CoreDumpValue::I32(x) => Some(Val::I32(x)),
+        CoreDumpValue::I64(x) => Some(Val::I64(x)),
+        CoreDumpValue::F32(x) => Some(Val::F32(x.to_bits())),
+        CoreDumpValue::F64(x) => Some(Val::F64(x.to_bits())),
+        _ => None,
+    }
+}
+
 impl fmt::Display for WasmBacktrace {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "error while executing at wasm backtrace:")?;
@@ -425,12 +445,15 @@ impl fmt::Display for WasmBacktrace {
 /// to acquire this `FrameInfo`. For more information see [`WasmBacktrace`].
 #[derive(Debug)]
 pub struct FrameInfo {
-    module_name: Option<String>,
+    module: Module,
     func_index: u32,
     func_name: Option<String>,
     func_start: FilePos,
     instr: Option<FilePos>,
     symbols: Vec<FrameSymbol>,
+    locals: Vec<Option<Val>>,
+    operand_stack: Vec<Option<Val>>,
+    vmctx: Option<usize>,
 }
 
 impl FrameInfo {
@@ -439,11 +462,13 @@ impl FrameInfo {
     /// Returns an object if this `pc` is known to this module, or returns `None`
     /// if no information can be found.
     pub(crate) fn new(module: &Module, text_offset: usize) -> Option<FrameInfo> {
-        let module = module.compiled_module();
-        let (index, _func_offset) = module.func_by_text_offset(text_offset)?;
-        let info = module.wasm_func_info(index);
-        let instr =
-            wasmtime_environ::lookup_file_pos(module.code_memory().address_map_data(), text_offset);
+        let compiled_module = module.compiled_module();
+        let (index, _func_offset) = compiled_module.func_by_text_offset(text_offset)?;
+        let info = compiled_module.wasm_func_info(index);
+        let instr = wasmtime_environ::lookup_file_pos(
+            compiled_module.code_memory().address_map_data(),
+            text_offset,
+        );
 
         // In debug mode for now assert that we found a mapping for `pc` within
         // the function, because otherwise something is buggy along the way and
@@ -453,7 +478,7 @@ impl FrameInfo {
         // Note that if the module doesn't even have an address map due to
         // compilation settings then it's expected that `instr` is `None`.
         debug_assert!(
-            instr.is_some() || !module.has_address_map(),
+            instr.is_some() || !compiled_module.has_address_map(),
             "failed to find instruction for {:#x}",
             text_offset
         );
@@ -468,7 +493,7 @@ impl FrameInfo {
         // custom section contents.
         let mut symbols = Vec::new();
 
-        if let Some(s) = &module.symbolize_context().ok().and_then(|c| c) {
+        if let Some(s) = &compiled_module.symbolize_context().ok().and_then(|c| c) {
             if let Some(offset) = instr.and_then(|i| i.file_offset()) {
                 let to_lookup = u64::from(offset) - s.code_section_offset();
                 if let Ok(mut frames) = s.addr2line().find_frames(to_lookup).skip_all_loads() {
@@ -492,15 +517,18 @@ impl FrameInfo {
             }
         }
 
-        let index = module.module().func_index(index);
+        let index = compiled_module.module().func_index(index);
 
         Some(FrameInfo {
-            module_name: module.module().name.clone(),
+            module: module.clone(),
             func_index: index.index() as u32,
-            func_name: module.func_name(index).map(|s| s.to_string()),
+            func_name: compiled_module.func_name(index).map(|s| s.to_string()),
             instr,
             func_start: info.start_srcloc,
             symbols,
+            locals: Vec::new(),
+            operand_stack: Vec::new(),
+            vmctx: None,
         })
     }
 
@@ -522,7 +550,12 @@ impl FrameInfo {
     ///
     /// This function returns `None` when no name can be found or inferred.
     pub fn module_name(&self) -> Option<&str> {
-        self.module_name.as_deref()
+        self.module.name()
+    }
+
+    /// Returns the module that this frame is for.
+    pub fn module(&self) -> &Module {
+        &self.module
     }
 
     /// Returns a descriptive name of the function for this frame, if one is
@@ -585,6 +618,39 @@ impl FrameInfo {
     pub fn symbols(&self) -> &[FrameSymbol] {
         &self.symbols
     }
+
+    /// Returns the values of this frame's wasm locals, indexed by local
+    /// index, at the time the backtrace was captured.
+    ///
+    /// Locals are only recovered when
+    /// [`Config::wasm_backtrace_locals`](crate::Config::wasm_backtrace_locals)
+    /// is enabled, otherwise this returns an empty slice. Locals whose value
+    /// couldn't be recovered, such as those of reference or vector types, are
+    /// `None`.
+    pub fn locals(&self) -> &[Option<Val>] {
+        &self.locals
+    }
+
+    /// Returns the values on this frame's operand stack, from its bottom to
+    /// its top, at the time the backtrace was captured.
+    ///
+    /// For the frame that trapped these are the values before the trapping
+    /// instruction popped its operands, and for other frames the values before
+    /// the call they're in popped its arguments. They are only recovered under
+    /// the same conditions as [`FrameInfo::locals`], and when the module was
+    /// compiled with
+    /// [`Config::generate_address_map`](crate::Config::generate_address_map)
+    /// enabled, otherwise this returns an empty slice.
+    pub fn operand_stack(&self) -> &[Option<Val>] {
+        &self.operand_stack
+    }
+
+    /// Returns the address of the `VMContext` of the instance this frame
+    /// belongs to, if the frame's function saved it to its frame (see
+    /// `Config::coredump_on_trap`).
+    pub(crate) fn vmctx(&self) -> Option<usize> {
+        self.vmctx
+    }
 }
 
 /// Debug information for a symbol that is attached to a [`FrameInfo`].
@@ -124,6 +124,8 @@ pub enum ExternType {
     Table(TableType),
     /// This external type is the type of a WebAssembly memory.
     Memory(MemoryType),
+    /// This external type is the type of a WebAssembly exception tag.
+    Tag(TagType),
 }
 
 macro_rules! accessors {
@@ -156,6 +158,7 @@ impl ExternType {
         (Global(GlobalType) global unwrap_global)
         (Table(TableType) table unwrap_table)
         (Memory(MemoryType) memory unwrap_memory)
+        (Tag(TagType) tag unwrap_tag)
     }
 
     pub(crate) fn from_wasmtime(types: &ModuleTypes, ty: &EntityType) -> ExternType {
@@ -164,7 +167,7 @@ impl ExternType {
             EntityType::Global(ty) => GlobalType::from_wasmtime_global(ty).into(),
             EntityType::Memory(ty) => MemoryType::from_wasmtime_memory(ty).into(),
             EntityType::Table(ty) => TableType::from_wasmtime_table(ty).into(),
-            EntityType::Tag(_) => unimplemented!("wasm tag support"),
+            EntityType::Tag(idx) => TagType::from_wasm_func_type(types[*idx].clone()).into(),
         }
     }
 }
@@ -193,6 +196,12 @@ impl From<TableType> for ExternType {
     }
 }
 
+impl From<TagType> for ExternType {
+    fn from(ty: TagType) -> ExternType {
+        ExternType::Tag(ty)
+    }
+}
+
 /// A descriptor for a function in a WebAssembly module.
 ///
 /// WebAssembly functions can have 0 or more parameters and results.
@@ -239,6 +248,45 @@ impl FuncType {
     }
 }
 
+// Tag Types
+
+/// A descriptor for an exception tag in a WebAssembly module.
+///
+/// Tags describe the payload carried by the exceptions thrown with them.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct TagType {
+    // Tags are described by a function type with no results, whose
+    // parameters are the payload of the tag.
+    sig: WasmFuncType,
+}
+
+impl TagType {
+    /// Creates a new tag descriptor whose exceptions carry values of the
+    /// types in `params`.
+    pub fn new(params: impl IntoIterator<Item = ValType>) -> TagType {
+        TagType {
+            sig: WasmFuncType::new(
+                params.into_iter().map(|t| t.to_wasm_type()).collect(),
+                Box::new([]),
+            ),
+        }
+    }
+
+    /// Returns the types of the payload of exceptions thrown with this tag.
+    #[inline]
+    pub fn params(&self) -> impl ExactSizeIterator<Item = ValType> + '_ {
+        self.sig.params().iter().map(ValType::from_wasm_type)
+    }
+
+    pub(crate) fn as_wasm_func_type(&self) -> &WasmFuncType {
+        &self.sig
+    }
+
+    pub(crate) fn from_wasm_func_type(sig: WasmFuncType) -> TagType {
+        Self { sig }
+    }
+}
+
 // Global Types
 
 /// A WebAssembly global descriptor.
@@ -64,7 +64,10 @@ impl MatchCx<'_> {
                 DefinitionType::Func(actual) => self.vmshared_signature_index(*expected, *actual),
                 _ => bail!("expected func, but found {}", actual.desc()),
             },
-            EntityType::Tag(_) => unimplemented!(),
+            EntityType::Tag(expected) => match actual {
+                DefinitionType::Tag(actual) => tag_ty(&self.types[*expected], actual),
+                _ => bail!("expected tag, but found {}", actual.desc()),
+            },
         }
     }
 }
@@ -105,7 +108,10 @@ pub fn entity_ty(
             }
             _ => bail!("expected func found {}", entity_desc(actual)),
         },
-        EntityType::Tag(_) => unimplemented!(),
+        EntityType::Tag(expected) => match actual {
+            EntityType::Tag(actual) => tag_ty(&expected_types[*expected], &actual_types[*actual]),
+            _ => bail!("expected tag found {}", entity_desc(actual)),
+        },
     }
 }
 
@@ -132,6 +138,26 @@ fn func_ty_mismatch(msg: &str, expected: &WasmFuncType, actual: &WasmFuncType) -
     )
 }
 
+fn tag_ty(expected: &WasmFuncType, actual: &WasmFuncType) -> Result<()> {
+    // Exceptions flow both into and out of the functions that use a tag, so
+    // its payload types must match exactly.
+    if expected.params() != actual.params() {
+        let render = |ty: &WasmFuncType| {
+            ty.params()
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        bail!(
+            "tag types incompatible: expected tag of type `({})`, found tag of type `({})`",
+            render(expected),
+            render(actual)
+        );
+    }
+    Ok(())
+}
+
 fn global_ty(expected: &Global, actual: &Global) -> Result<()> {
     // Subtyping is only sound on immutable global
     // references. Therefore if either type is mutable we perform a
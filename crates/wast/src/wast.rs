@@ -346,6 +346,14 @@ impl<T> WastContext<T> {
         bail!("expected '{}', got '{}'", expected, actual)
     }
 
+    fn assert_exception(&self, result: Outcome) -> Result<()> {
+        match result {
+            Outcome::Ok(values) => bail!("expected exception, got {:?}", values),
+            Outcome::Trap(e) if e.is::<WasmException>() => Ok(()),
+            Outcome::Trap(e) => bail!("expected exception, got '{:?}'", e),
+        }
+    }
+
     /// Run a wast script from a byte buffer.
     pub fn run_buffer(&mut self, filename: &str, wast: &[u8]) -> Result<()> {
         let wast = str::from_utf8(wast)?;
@@ -464,7 +472,10 @@ impl<T> WastContext<T> {
                     )
                 }
             }
-            AssertException { .. } => bail!("unimplemented assert_exception"),
+            AssertException { span: _, exec } => {
+                let result = self.perform_execute(exec)?;
+                self.assert_exception(result)?;
+            }
         }
 
         Ok(())
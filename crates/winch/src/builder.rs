@@ -2,17 +2,24 @@ use crate::compiler::Compiler;
 use anyhow::{bail, Result};
 use std::sync::Arc;
 use wasmtime_cranelift_shared::isa_builder::IsaBuilder;
-use wasmtime_environ::{CompilerBuilder, Setting};
+use wasmtime_environ::{CompilerBuilder, Setting, Tunables};
 use winch_codegen::{isa, TargetIsa};
 
 /// Compiler builder.
 struct Builder {
     inner: IsaBuilder<Result<Box<dyn TargetIsa>>>,
+    tunables: Tunables,
+    /// Builder for the Cranelift compiler used to generate the
+    /// trampolines that Winch doesn't support yet, namely the host
+    /// function and component trampolines.
+    cranelift: Box<dyn CompilerBuilder>,
 }
 
 pub fn builder() -> Box<dyn CompilerBuilder> {
     Box::new(Builder {
         inner: IsaBuilder::new(|triple| isa::lookup(triple).map_err(|e| e.into())),
+        tunables: Tunables::default(),
+        cranelift: wasmtime_cranelift::builder(),
     })
 }
 
@@ -22,31 +29,40 @@ impl CompilerBuilder for Builder {
     }
 
     fn target(&mut self, target: target_lexicon::Triple) -> Result<()> {
-        self.inner.target(target)?;
+        self.inner.target(target.clone())?;
+        self.cranelift.target(target)?;
         Ok(())
     }
 
     fn set(&mut self, name: &str, value: &str) -> Result<()> {
-        self.inner.set(name, value)
+        self.inner.set(name, value)?;
+        self.cranelift.set(name, value)
     }
 
     fn enable(&mut self, name: &str) -> Result<()> {
-        self.inner.enable(name)
+        self.inner.enable(name)?;
+        self.cranelift.enable(name)
     }
 
     fn settings(&self) -> Vec<Setting> {
         self.inner.settings()
     }
 
-    fn set_tunables(&mut self, tunables: wasmtime_environ::Tunables) -> Result<()> {
-        let _ = tunables;
+    fn set_tunables(&mut self, tunables: Tunables) -> Result<()> {
+        self.cranelift.set_tunables(tunables.clone())?;
+        self.tunables = tunables;
         Ok(())
     }
 
     fn build(&self) -> Result<Box<dyn wasmtime_environ::Compiler>> {
         let isa = self.inner.build()?;
+        let trampolines = self.cranelift.build()?;
 
-        Ok(Box::new(Compiler::new(isa)))
+        Ok(Box::new(Compiler::new(
+            isa,
+            trampolines,
+            self.tunables.clone(),
+        )))
     }
 
     fn enable_incremental_compilation(
@@ -1,17 +1,23 @@
 use anyhow::Result;
 use object::write::{Object, SymbolId};
 use std::any::Any;
+use std::ops::Range;
 use std::sync::Mutex;
 use wasmparser::FuncValidatorAllocations;
 use wasmtime_cranelift_shared::{CompiledFunction, ModuleTextBuilder};
 use wasmtime_environ::{
-    CompileError, DefinedFuncIndex, FilePos, FuncIndex, FunctionBodyData, FunctionLoc,
-    ModuleTranslation, ModuleTypes, PrimaryMap, TrapEncodingBuilder, WasmFunctionInfo,
+    AddressMapSection, CompileError, DefinedFuncIndex, FuncIndex, FunctionBodyData, FunctionLoc,
+    ModuleTranslation, ModuleTypes, PrimaryMap, TrapEncodingBuilder, Tunables, WasmFunctionInfo,
 };
 use winch_codegen::{TargetIsa, TrampolineKind};
 
 pub(crate) struct Compiler {
     isa: Box<dyn TargetIsa>,
+    /// Cranelift-based compiler, used to generate the trampolines for
+    /// host functions and components, which Winch doesn't generate
+    /// on its own.
+    trampolines: Box<dyn wasmtime_environ::Compiler>,
+    tunables: Tunables,
     allocations: Mutex<Vec<FuncValidatorAllocations>>,
 }
 
@@ -27,9 +33,15 @@ impl wasmtime_cranelift_shared::CompiledFuncEnv for CompiledFuncEnv {
 }
 
 impl Compiler {
-    pub fn new(isa: Box<dyn TargetIsa>) -> Self {
+    pub fn new(
+        isa: Box<dyn TargetIsa>,
+        trampolines: Box<dyn wasmtime_environ::Compiler>,
+        tunables: Tunables,
+    ) -> Self {
         Self {
             isa,
+            trampolines,
+            tunables,
             allocations: Mutex::new(Vec::new()),
         }
     }
@@ -59,26 +71,31 @@ impl wasmtime_environ::Compiler for Compiler {
         let sig = translation.module.functions[index].signature;
         let ty = &types[sig];
         let FunctionBodyData { body, validator } = data;
-        let start_srcloc = FilePos::new(
-            body.get_binary_reader()
-                .original_position()
-                .try_into()
-                .unwrap(),
-        );
         let mut validator = validator.into_validator(self.take_allocations());
         let buffer = self
             .isa
-            .compile_function(ty, &body, &translation, &mut validator)
+            .compile_function(ty, &body, &translation, &self.tunables, &mut validator)
             .map_err(|e| CompileError::Codegen(format!("{e:?}")));
         self.save_allocations(validator.into_allocations());
         let buffer = buffer?;
-        let compiled_function =
+        let mut compiled_function =
             CompiledFunction::new(buffer, CompiledFuncEnv {}, self.isa.function_alignment());
 
+        let reader = body.get_binary_reader();
+        compiled_function.set_address_map(
+            reader.original_position() as u32,
+            reader.bytes_remaining() as u32,
+            self.tunables.generate_address_map,
+        );
+
         Ok((
             WasmFunctionInfo {
-                start_srcloc,
+                start_srcloc: compiled_function.metadata().address_map.start_srcloc,
                 stack_maps: Box::new([]),
+                // Winch doesn't yet support saving wasm locals or the
+                // `VMContext` to the frame.
+                locals: None,
+                vmctx_frame_offset: None,
             },
             Box::new(compiled_function),
         ))
@@ -147,16 +164,27 @@ impl wasmtime_environ::Compiler for Compiler {
     ) -> Result<Vec<(SymbolId, FunctionLoc)>> {
         let mut builder =
             ModuleTextBuilder::new(obj, self, self.isa.text_section_builder(funcs.len()));
+        let mut addrs = AddressMapSection::default();
         let mut traps = TrapEncodingBuilder::default();
 
         let mut ret = Vec::with_capacity(funcs.len());
         for (i, (sym, func)) in funcs.iter().enumerate() {
-            let func = func
-                .downcast_ref::<CompiledFunction<CompiledFuncEnv>>()
-                .unwrap();
-
-            let (sym, range) = builder.append_func(&sym, func, |idx| resolve_reloc(i, idx));
-            traps.push(range.clone(), &func.traps().collect::<Vec<_>>());
+            // Besides the functions compiled by Winch, `funcs` might
+            // contain the component trampolines compiled by Cranelift.
+            let func_addrs = self.tunables.generate_address_map.then_some(&mut addrs);
+            let (sym, range) = match func.downcast_ref::<CompiledFunction<CompiledFuncEnv>>() {
+                Some(func) => append_func(&mut builder, &mut traps, func_addrs, sym, func, |idx| {
+                    resolve_reloc(i, idx)
+                }),
+                None => {
+                    let func = func
+                        .downcast_ref::<CompiledFunction<wasmtime_cranelift::CompiledFuncEnv>>()
+                        .unwrap();
+                    append_func(&mut builder, &mut traps, func_addrs, sym, func, |idx| {
+                        resolve_reloc(i, idx)
+                    })
+                }
+            };
 
             let info = FunctionLoc {
                 start: u32::try_from(range.start).unwrap(),
@@ -165,6 +193,10 @@ impl wasmtime_environ::Compiler for Compiler {
             ret.push((sym, info));
         }
         builder.finish();
+
+        if self.tunables.generate_address_map {
+            addrs.append_to(obj);
+        }
         traps.append_to(obj);
         Ok(ret)
     }
@@ -177,8 +209,8 @@ impl wasmtime_environ::Compiler for Compiler {
         host_fn: usize,
         obj: &mut Object<'static>,
     ) -> Result<(FunctionLoc, FunctionLoc)> {
-        drop((ty, host_fn, obj));
-        todo!()
+        self.trampolines
+            .emit_trampolines_for_array_call_host_func(ty, host_fn, obj)
     }
 
     fn triple(&self) -> &target_lexicon::Triple {
@@ -199,19 +231,48 @@ impl wasmtime_environ::Compiler for Compiler {
 
     #[cfg(feature = "component-model")]
     fn component_compiler(&self) -> &dyn wasmtime_environ::component::ComponentCompiler {
-        todo!()
+        self.trampolines.component_compiler()
     }
 
     fn append_dwarf(
         &self,
-        _obj: &mut Object<'_>,
-        _translation: &ModuleTranslation<'_>,
-        _funcs: &PrimaryMap<DefinedFuncIndex, (SymbolId, &(dyn Any + Send))>,
+        obj: &mut Object<'_>,
+        translation: &ModuleTranslation<'_>,
+        funcs: &PrimaryMap<DefinedFuncIndex, (SymbolId, &(dyn Any + Send))>,
     ) -> Result<()> {
-        todo!()
+        // The DWARF transform only needs the metadata of each function,
+        // which is handed over to the Cranelift-based compiler.
+        let funcs = funcs
+            .iter()
+            .map(|(_, (sym, func))| {
+                let func = func
+                    .downcast_ref::<CompiledFunction<CompiledFuncEnv>>()
+                    .unwrap();
+                (*sym, func.metadata() as &(dyn Any + Send))
+            })
+            .collect();
+        self.trampolines.append_dwarf(obj, translation, &funcs)
     }
 
     fn create_systemv_cie(&self) -> Option<gimli::write::CommonInformationEntry> {
         self.isa.create_systemv_cie()
     }
 }
+
+/// Appends the given function to the text section, recording its trap
+/// information and, if requested, its address map.
+fn append_func<'a>(
+    builder: &mut ModuleTextBuilder<'a>,
+    traps: &mut TrapEncodingBuilder,
+    addrs: Option<&mut AddressMapSection>,
+    sym: &str,
+    func: &'a CompiledFunction<impl wasmtime_cranelift_shared::CompiledFuncEnv>,
+    resolve_reloc: impl Fn(FuncIndex) -> usize,
+) -> (SymbolId, Range<u64>) {
+    let (sym, range) = builder.append_func(sym, func, resolve_reloc);
+    if let Some(addrs) = addrs {
+        addrs.push(range.clone(), &func.address_map().instructions);
+    }
+    traps.push(range.clone(), &func.traps().collect::<Vec<_>>());
+    (sym, range)
+}
@@ -9,6 +9,9 @@ use wasmtime_cli::commands::{
     CompileCommand, ConfigCommand, ExploreCommand, RunCommand, SettingsCommand, WastCommand,
 };
 
+#[cfg(feature = "serve")]
+use wasmtime_cli::commands::ServeCommand;
+
 /// Wasmtime WebAssembly Runtime
 #[derive(Parser)]
 #[clap(
@@ -53,6 +56,9 @@ enum Subcommand {
     Explore(ExploreCommand),
     /// Runs a WebAssembly module
     Run(RunCommand),
+    /// Serves requests from a wasi-http proxy component
+    #[cfg(feature = "serve")]
+    Serve(ServeCommand),
     /// Displays available Cranelift settings for a target.
     Settings(SettingsCommand),
     /// Runs a WebAssembly test script file
@@ -68,6 +74,8 @@ impl Wasmtime {
             Subcommand::Compile(c) => c.execute(),
             Subcommand::Explore(c) => c.execute(),
             Subcommand::Run(c) => c.execute(),
+            #[cfg(feature = "serve")]
+            Subcommand::Serve(c) => c.execute(),
             Subcommand::Settings(c) => c.execute(),
             Subcommand::Wast(c) => c.execute(),
         }
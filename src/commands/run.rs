@@ -5,6 +5,7 @@
     allow(irrefutable_let_patterns, unreachable_patterns)
 )]
 
+use crate::common::RunCommon;
 use anyhow::{anyhow, bail, Context as _, Error, Result};
 use clap::Parser;
 use once_cell::sync::Lazy;
@@ -16,18 +17,19 @@ use std::thread;
 use std::time::Duration;
 use wasmtime::{
     AsContextMut, Engine, Func, GuestProfiler, Module, Precompiled, Store, StoreLimits,
-    StoreLimitsBuilder, UpdateDeadline, Val, ValType,
+    UpdateDeadline, Val, ValType,
 };
-use wasmtime_cli_flags::{CommonOptions, WasiModules};
+use wasmtime_cli_flags::WasiModules;
 use wasmtime_wasi::maybe_exit_on_error;
 use wasmtime_wasi::preview2;
-use wasmtime_wasi::sync::{ambient_authority, Dir, TcpListener, WasiCtxBuilder};
+use wasmtime_wasi::preview2::bindings::logging::logging::Level as WasiLogLevel;
+use wasmtime_wasi::sync::{TcpListener, WasiCtxBuilder};
 
 #[cfg(feature = "component-model")]
 use wasmtime::component::Component;
 
 #[cfg(feature = "wasi-nn")]
-use wasmtime_wasi_nn::WasiNnCtx;
+use wasmtime_wasi_nn::{NamedRegistry, WasiNnCtx};
 
 #[cfg(feature = "wasi-threads")]
 use wasmtime_wasi_threads::WasiThreadsCtx;
@@ -35,22 +37,6 @@ use wasmtime_wasi_threads::WasiThreadsCtx;
 // #[cfg(feature = "wasi-http")]
 // use wasmtime_wasi_http::WasiHttpCtx;
 
-fn parse_env_var(s: &str) -> Result<(String, Option<String>)> {
-    let mut parts = s.splitn(2, '=');
-    Ok((
-        parts.next().unwrap().to_string(),
-        parts.next().map(|s| s.to_string()),
-    ))
-}
-
-fn parse_map_dirs(s: &str) -> Result<(String, String)> {
-    let parts: Vec<&str> = s.split("::").collect();
-    if parts.len() != 2 {
-        bail!("must contain exactly one double colon ('::')");
-    }
-    Ok((parts[0].into(), parts[1].into()))
-}
-
 fn parse_graphs(s: &str) -> Result<(String, String)> {
     let parts: Vec<&str> = s.split("::").collect();
     if parts.len() != 2 {
@@ -99,6 +85,18 @@ fn parse_profile(s: &str) -> Result<Profile> {
     }
 }
 
+fn parse_wasi_log_level(s: &str) -> Result<WasiLogLevel> {
+    Ok(match s {
+        "trace" => WasiLogLevel::Trace,
+        "debug" => WasiLogLevel::Debug,
+        "info" => WasiLogLevel::Info,
+        "warn" => WasiLogLevel::Warn,
+        "error" => WasiLogLevel::Error,
+        "critical" => WasiLogLevel::Critical,
+        _ => bail!("unknown log level: {s}"),
+    })
+}
+
 static AFTER_HELP: Lazy<String> = Lazy::new(|| crate::FLAG_EXPLANATIONS.to_string());
 
 /// Runs a WebAssembly module
@@ -106,7 +104,7 @@ static AFTER_HELP: Lazy<String> = Lazy::new(|| crate::FLAG_EXPLANATIONS.to_strin
 #[structopt(name = "run", after_help = AFTER_HELP.as_str())]
 pub struct RunCommand {
     #[clap(flatten)]
-    common: CommonOptions,
+    run: RunCommon,
 
     /// Allow unknown exports when running commands.
     #[clap(long = "allow-unknown-exports")]
@@ -122,15 +120,6 @@ pub struct RunCommand {
     #[clap(long = "default-values-unknown-imports")]
     default_values_unknown_imports: bool,
 
-    /// Allow executing precompiled WebAssembly modules as `*.cwasm` files.
-    ///
-    /// Note that this option is not safe to pass if the module being passed in
-    /// is arbitrary user input. Only `wasmtime`-precompiled modules generated
-    /// via the `wasmtime compile` command or equivalent should be passed as an
-    /// argument with this option specified.
-    #[clap(long = "allow-precompiled")]
-    allow_precompiled: bool,
-
     /// Inherit environment variables and file descriptors following the
     /// systemd listen fd specification (UNIX only)
     #[clap(long = "listenfd")]
@@ -144,36 +133,20 @@ pub struct RunCommand {
     )]
     tcplisten: Vec<String>,
 
-    /// Grant access to the given host directory
-    #[clap(long = "dir", number_of_values = 1, value_name = "DIRECTORY")]
-    dirs: Vec<String>,
-
-    /// Pass an environment variable to the program.
-    ///
-    /// The `--env FOO=BAR` form will set the environment variable named `FOO`
-    /// to the value `BAR` for the guest program using WASI. The `--env FOO`
-    /// form will set the environment variable named `FOO` to the same value it
-    /// has in the calling process for the guest, or in other words it will
-    /// cause the environment variable `FOO` to be inherited.
-    #[clap(long = "env", number_of_values = 1, value_name = "NAME[=VAL]", value_parser = parse_env_var)]
-    vars: Vec<(String, Option<String>)>,
-
     /// The name of the function to run
     #[clap(long, value_name = "FUNCTION")]
     invoke: Option<String>,
 
-    /// Grant access to a guest directory mapped as a host directory
-    #[clap(long = "mapdir", number_of_values = 1, value_name = "GUEST_DIR::HOST_DIR", value_parser = parse_map_dirs)]
-    map_dirs: Vec<(String, String)>,
-
     /// Pre-load machine learning graphs (i.e., models) for use by wasi-nn.
     ///
     /// Each use of the flag will preload a ML model from the host directory
     /// using the given model encoding. The model will be mapped to the
     /// directory name: e.g., `--wasi-nn-graph openvino:/foo/bar` will preload
-    /// an OpenVINO model named `bar`. Note that which model encodings are
+    /// an OpenVINO model named `bar`, which the guest can then load with
+    /// `load_by_name`. Note that which model encodings are
     /// available is dependent on the backends implemented in the
-    /// `wasmtime_wasi_nn` crate.
+    /// `wasmtime_wasi_nn` crate: `onnx`, for example, requires Wasmtime to be
+    /// built with the `wasi-nn-onnx` feature.
     #[clap(long = "wasi-nn-graph", value_name = "FORMAT::HOST_DIR", value_parser = parse_graphs)]
     graphs: Vec<(String, String)>,
 
@@ -220,37 +193,19 @@ pub struct RunCommand {
     #[clap(long = "coredump-on-trap", value_name = "PATH")]
     coredump_on_trap: Option<String>,
 
-    /// Maximum size, in bytes, that a linear memory is allowed to reach.
+    /// Minimum level of the messages logged by the guest through
+    /// `wasi:logging` to pass on to the host's logger (valid options are:
+    /// trace, debug, info, warn, error, critical)
     ///
-    /// Growth beyond this limit will cause `memory.grow` instructions in
-    /// WebAssembly modules to return -1 and fail.
-    #[clap(long, value_name = "BYTES")]
-    max_memory_size: Option<usize>,
-
-    /// Maximum size, in table elements, that a table is allowed to reach.
-    #[clap(long)]
-    max_table_elements: Option<u32>,
-
-    /// Maximum number of WebAssembly instances allowed to be created.
-    #[clap(long)]
-    max_instances: Option<usize>,
-
-    /// Maximum number of WebAssembly tables allowed to be created.
-    #[clap(long)]
-    max_tables: Option<usize>,
-
-    /// Maximum number of WebAssembly linear memories allowed to be created.
-    #[clap(long)]
-    max_memories: Option<usize>,
-
-    /// Force a trap to be raised on `memory.grow` and `table.grow` failure
-    /// instead of returning -1 from these instructions.
-    ///
-    /// This is not necessarily a spec-compliant option to enable but can be
-    /// useful for tracking down a backtrace of what is requesting so much
-    /// memory, for example.
-    #[clap(long)]
-    trap_on_grow_failure: bool,
+    /// When this is set guest messages at or above `LEVEL` are printed on
+    /// stderr. Otherwise they are logged under the `wasi_logging` target and
+    /// are subject to the filter configured with `RUST_LOG`.
+    #[clap(
+        long = "wasi-log-level",
+        value_name = "LEVEL",
+        value_parser = parse_wasi_log_level,
+    )]
+    wasi_log_level: Option<WasiLogLevel>,
 
     /// Enables memory error checking.
     ///
@@ -314,9 +269,9 @@ impl CliModule {
 impl RunCommand {
     /// Executes the command.
     pub fn execute(&self) -> Result<()> {
-        self.common.init_logging();
+        self.run.common.init_logging();
 
-        let mut config = self.common.config(None)?;
+        let mut config = self.run.common.config(None)?;
 
         if self.wasm_timeout.is_some() {
             config.epoch_interruption(true);
@@ -334,6 +289,10 @@ impl RunCommand {
 
         config.wmemcheck(self.wmemcheck);
 
+        if self.coredump_on_trap.is_some() {
+            config.coredump_on_trap(true);
+        }
+
         let engine = Engine::new(&config)?;
 
         // Read the wasm module binary either as `*.wat` or a raw binary.
@@ -369,30 +328,12 @@ impl RunCommand {
         let mut store = Store::new(&engine, host);
         self.populate_with_wasi(&mut linker, &mut store, &main)?;
 
-        let mut limits = StoreLimitsBuilder::new();
-        if let Some(max) = self.max_memory_size {
-            limits = limits.memory_size(max);
-        }
-        if let Some(max) = self.max_table_elements {
-            limits = limits.table_elements(max);
-        }
-        if let Some(max) = self.max_instances {
-            limits = limits.instances(max);
-        }
-        if let Some(max) = self.max_tables {
-            limits = limits.tables(max);
-        }
-        if let Some(max) = self.max_memories {
-            limits = limits.memories(max);
-        }
-        store.data_mut().limits = limits
-            .trap_on_grow_failure(self.trap_on_grow_failure)
-            .build();
+        store.data_mut().limits = self.run.store_limits();
         store.limiter(|t| &mut t.limits);
 
         // If fuel has been configured, we want to add the configured
         // fuel amount to this store.
-        if let Some(fuel) = self.common.fuel {
+        if let Some(fuel) = self.run.common.fuel {
             store.add_fuel(fuel)?;
         }
 
@@ -447,42 +388,39 @@ impl RunCommand {
         Ok(())
     }
 
-    fn compute_preopen_dirs(&self) -> Result<Vec<(String, Dir)>> {
-        let mut preopen_dirs = Vec::new();
-
-        for dir in self.dirs.iter() {
-            preopen_dirs.push((
-                dir.clone(),
-                Dir::open_ambient_dir(dir, ambient_authority())
-                    .with_context(|| format!("failed to open directory '{}'", dir))?,
-            ));
-        }
+    /// Computes the sockets to preopen, from `--listenfd` and `--tcplisten`,
+    /// along with the file descriptor each one is given in the guest.
+    fn compute_preopen_sockets(&self) -> Result<Vec<(u32, TcpListener)>> {
+        let mut listeners = vec![];
 
-        for (guest, host) in self.map_dirs.iter() {
-            preopen_dirs.push((
-                guest.clone(),
-                Dir::open_ambient_dir(host, ambient_authority())
-                    .with_context(|| format!("failed to open directory '{}'", host))?,
-            ));
+        if self.listenfd {
+            listeners.extend(listenfd_sockets()?);
         }
 
-        Ok(preopen_dirs)
-    }
-
-    fn compute_preopen_sockets(&self) -> Result<Vec<TcpListener>> {
-        let mut listeners = vec![];
-
+        let mut num_fd = listeners.last().map_or(3, |(fd, _)| fd + 1);
         for address in &self.tcplisten {
             let stdlistener = std::net::TcpListener::bind(address)
                 .with_context(|| format!("failed to bind to address '{}'", address))?;
 
             let _ = stdlistener.set_nonblocking(true)?;
 
-            listeners.push(TcpListener::from_std(stdlistener))
+            listeners.push((num_fd, TcpListener::from_std(stdlistener)));
+            num_fd += 1;
         }
         Ok(listeners)
     }
 
+    /// Computes the environment variables `--listenfd` passes through.
+    fn compute_listenfd_env(&self) -> Vec<(String, String)> {
+        if !self.listenfd {
+            return Vec::new();
+        }
+        ["LISTEN_FDS", "LISTEN_FDNAMES"]
+            .into_iter()
+            .filter_map(|env| Some((env.to_string(), std::env::var(env).ok()?)))
+            .collect()
+    }
+
     fn compute_argv(&self) -> Result<Vec<String>> {
         let mut result = Vec::new();
 
@@ -655,7 +593,7 @@ impl RunCommand {
                     .wasi_cli_run()
                     .call_run(&mut *store)
                     .context("failed to invoke `run` function")
-                    .map_err(|e| self.handle_coredump(e));
+                    .map_err(|e| self.handle_coredump(&mut *store, e));
 
                 // Translate the `Result<(),()>` produced by wasm into a feigned
                 // explicit exit here with status 1 if `Err(())` is returned.
@@ -709,7 +647,7 @@ impl RunCommand {
         // Invoke the function and then afterwards print all the results that came
         // out, if there are any.
         let mut results = vec![Val::null(); ty.results().len()];
-        let invoke_res = func.call(store, &values, &mut results).with_context(|| {
+        let invoke_res = func.call(&mut *store, &values, &mut results).with_context(|| {
             if let Some(name) = &self.invoke {
                 format!("failed to invoke `{}`", name)
             } else {
@@ -718,7 +656,7 @@ impl RunCommand {
         });
 
         if let Err(err) = invoke_res {
-            return Err(self.handle_coredump(err));
+            return Err(self.handle_coredump(store, err));
         }
 
         if !results.is_empty() {
@@ -743,7 +681,7 @@ impl RunCommand {
         Ok(())
     }
 
-    fn handle_coredump(&self, err: Error) -> Error {
+    fn handle_coredump(&self, store: &mut Store<Host>, err: Error) -> Error {
         let coredump_path = match &self.coredump_on_trap {
             Some(path) => path,
             None => return err,
@@ -755,7 +693,7 @@ impl RunCommand {
             .to_str()
             .unwrap_or_else(|| "unknown");
 
-        if let Err(coredump_err) = generate_coredump(&err, &source_name, coredump_path) {
+        if let Err(coredump_err) = generate_coredump(&err, store, &source_name, coredump_path) {
             eprintln!("warning: coredump failed to generate: {}", coredump_err);
             err
         } else {
@@ -822,13 +760,13 @@ impl RunCommand {
     ) -> Result<CliModule> {
         Ok(match engine.detect_precompiled(bytes) {
             Some(Precompiled::Module) => {
-                self.ensure_allow_precompiled()?;
+                self.run.ensure_allow_precompiled()?;
                 CliModule::Core(deserialize_module()?)
             }
             #[cfg(feature = "component-model")]
             Some(Precompiled::Component) => {
-                self.ensure_allow_precompiled()?;
-                self.ensure_allow_components()?;
+                self.run.ensure_allow_precompiled()?;
+                self.run.ensure_allow_components()?;
                 CliModule::Component(deserialize_component()?)
             }
             #[cfg(not(feature = "component-model"))]
@@ -845,7 +783,7 @@ impl RunCommand {
                 if wasmparser::Parser::is_component(&wasm) {
                     #[cfg(feature = "component-model")]
                     {
-                        self.ensure_allow_components()?;
+                        self.run.ensure_allow_components()?;
                         CliModule::Component(Component::new(engine, &wasm)?)
                     }
                     #[cfg(not(feature = "component-model"))]
@@ -859,29 +797,6 @@ impl RunCommand {
         })
     }
 
-    fn ensure_allow_precompiled(&self) -> Result<()> {
-        if self.allow_precompiled {
-            Ok(())
-        } else {
-            bail!("running a precompiled module requires the `--allow-precompiled` flag")
-        }
-    }
-
-    #[cfg(feature = "component-model")]
-    fn ensure_allow_components(&self) -> Result<()> {
-        if !self
-            .common
-            .wasm_features
-            .unwrap_or_default()
-            .component_model
-            .unwrap_or(false)
-        {
-            bail!("cannot execute a component without `--wasm-features component-model`");
-        }
-
-        Ok(())
-    }
-
     /// Populates the given `Linker` with WASI APIs.
     fn populate_with_wasi(
         &self,
@@ -889,14 +804,18 @@ impl RunCommand {
         store: &mut Store<Host>,
         module: &CliModule,
     ) -> Result<()> {
-        let wasi_modules = self.common.wasi_modules.unwrap_or(WasiModules::default());
+        let wasi_modules = self
+            .run
+            .common
+            .wasi_modules
+            .unwrap_or(WasiModules::default());
 
         if wasi_modules.wasi_common {
             match linker {
                 CliLinker::Core(linker) => {
                     if self.preview2 {
                         wasmtime_wasi::preview2::preview1::add_to_linker_sync(linker)?;
-                        self.set_preview2_ctx(store)?;
+                        self.set_preview2_ctx(store, false)?;
                     } else {
                         wasmtime_wasi::add_to_linker(linker, |host| {
                             host.preview1_ctx.as_mut().unwrap()
@@ -907,7 +826,7 @@ impl RunCommand {
                 #[cfg(feature = "component-model")]
                 CliLinker::Component(linker) => {
                     wasmtime_wasi::preview2::command::sync::add_to_linker(linker)?;
-                    self.set_preview2_ctx(store)?;
+                    self.set_preview2_ctx(store, true)?;
                 }
             }
         }
@@ -941,8 +860,18 @@ impl RunCommand {
                         })?;
                     }
                 }
-                let (backends, registry) = wasmtime_wasi_nn::preload(&self.graphs)?;
-                store.data_mut().wasi_nn = Some(Arc::new(WasiNnCtx::new(backends, registry)));
+                let registry = NamedRegistry::new();
+                for (kind, path) in &self.graphs {
+                    let name = Path::new(path)
+                        .file_name()
+                        .map(|s| s.to_string_lossy())
+                        .ok_or_else(|| anyhow!("no file name in path: {path}"))?;
+                    registry.register_with_encoding(&*name, path, kind)?;
+                    registry.preload(&name)?;
+                }
+                store.data_mut().wasi_nn = Some(Arc::new(WasiNnCtx::with_registry(
+                    registry.store_registry(None),
+                )));
             }
         }
 
@@ -992,27 +921,19 @@ impl RunCommand {
         let mut builder = WasiCtxBuilder::new();
         builder.inherit_stdio().args(&self.compute_argv()?)?;
 
-        for (key, value) in self.vars.iter() {
-            let value = match value {
-                Some(value) => value.clone(),
-                None => std::env::var(key)
-                    .map_err(|_| anyhow!("environment varialbe `{key}` not found"))?,
-            };
-            builder.env(key, &value)?;
+        for (key, value) in self.run.compute_env()? {
+            builder.env(&key, &value)?;
         }
 
-        let mut num_fd: usize = 3;
-
-        if self.listenfd {
-            num_fd = ctx_set_listenfd(num_fd, &mut builder)?;
+        for (key, value) in self.compute_listenfd_env() {
+            builder.env(&key, &value)?;
         }
 
-        for listener in self.compute_preopen_sockets()? {
-            builder.preopened_socket(num_fd as _, listener)?;
-            num_fd += 1;
+        for (fd, listener) in self.compute_preopen_sockets()? {
+            builder.preopened_socket(fd, listener)?;
         }
 
-        for (name, dir) in self.compute_preopen_dirs()? {
+        for (name, dir) in self.run.compute_preopen_dirs()? {
             builder.preopened_dir(dir, name)?;
         }
 
@@ -1020,27 +941,46 @@ impl RunCommand {
         Ok(())
     }
 
-    fn set_preview2_ctx(&self, store: &mut Store<Host>) -> Result<()> {
+    fn set_preview2_ctx(&self, store: &mut Store<Host>, is_component: bool) -> Result<()> {
+        let argv = self.compute_argv()?;
         let mut builder = preview2::WasiCtxBuilder::new();
-        builder.inherit_stdio().args(&self.compute_argv()?);
+        builder.inherit_stdio().args(&argv);
+
+        let instance = argv[0].clone();
+        match self.wasi_log_level {
+            Some(level) => {
+                builder
+                    .logger(StderrGuestLogger { instance })
+                    .min_log_level(level);
+            }
+            None => {
+                builder.logger(GuestLogger { instance });
+            }
+        }
 
-        for (key, value) in self.vars.iter() {
-            let value = match value {
-                Some(value) => value.clone(),
-                None => std::env::var(key)
-                    .map_err(|_| anyhow!("environment varialbe `{key}` not found"))?,
-            };
-            builder.env(key, &value);
+        for (key, value) in self.run.compute_env()? {
+            builder.env(key, value);
         }
 
-        if self.listenfd {
-            bail!("components do not support --listenfd");
+        // Preopened sockets are only visible to preview1 guests.
+        if is_component {
+            if self.listenfd {
+                bail!("components do not support --listenfd");
+            }
+            if !self.tcplisten.is_empty() {
+                bail!("components do not support --tcplisten");
+            }
         }
-        for _ in self.compute_preopen_sockets()? {
-            bail!("components do not support --tcplisten");
+
+        for (key, value) in self.compute_listenfd_env() {
+            builder.env(key, value);
+        }
+
+        for (fd, listener) in self.compute_preopen_sockets()? {
+            builder.preopened_socket(fd, listener);
         }
 
-        for (name, dir) in self.compute_preopen_dirs()? {
+        for (name, dir) in self.run.compute_preopen_dirs()? {
             builder.preopened_dir(
                 dir,
                 preview2::DirPerms::all(),
@@ -1057,6 +997,48 @@ impl RunCommand {
     }
 }
 
+/// Forwards messages logged by the guest through `wasi:logging` to the `log`
+/// crate, which the CLI's own logging is configured through.
+struct GuestLogger {
+    instance: String,
+}
+
+impl preview2::HostLogger for GuestLogger {
+    fn log(&self, level: WasiLogLevel, context: &str, message: &str) {
+        let level = match level {
+            WasiLogLevel::Trace => log::Level::Trace,
+            WasiLogLevel::Debug => log::Level::Debug,
+            WasiLogLevel::Info => log::Level::Info,
+            WasiLogLevel::Warn => log::Level::Warn,
+            WasiLogLevel::Error | WasiLogLevel::Critical => log::Level::Error,
+        };
+        let instance = &self.instance;
+        log::log!(target: "wasi_logging", level, "{instance}: {context}: {message}");
+    }
+}
+
+/// Prints messages logged by the guest through `wasi:logging` on stderr,
+/// used when `--wasi-log-level` is passed so they show up regardless of how
+/// the CLI's own logging is configured.
+struct StderrGuestLogger {
+    instance: String,
+}
+
+impl preview2::HostLogger for StderrGuestLogger {
+    fn log(&self, level: WasiLogLevel, context: &str, message: &str) {
+        let level = match level {
+            WasiLogLevel::Trace => "TRACE",
+            WasiLogLevel::Debug => "DEBUG",
+            WasiLogLevel::Info => "INFO",
+            WasiLogLevel::Warn => "WARN",
+            WasiLogLevel::Error => "ERROR",
+            WasiLogLevel::Critical => "CRITICAL",
+        };
+        let instance = &self.instance;
+        eprintln!("{level} {instance}: {context}: {message}");
+    }
+}
+
 #[derive(Default, Clone)]
 struct Host {
     preview1_ctx: Option<wasmtime_wasi::WasiCtx>,
@@ -1111,64 +1093,43 @@ impl preview2::preview1::WasiPreview1View for Host {
 }
 
 #[cfg(not(unix))]
-fn ctx_set_listenfd(num_fd: usize, _builder: &mut WasiCtxBuilder) -> Result<usize> {
-    Ok(num_fd)
+fn listenfd_sockets() -> Result<Vec<(u32, TcpListener)>> {
+    Ok(Vec::new())
 }
 
 #[cfg(unix)]
-fn ctx_set_listenfd(mut num_fd: usize, builder: &mut WasiCtxBuilder) -> Result<usize> {
+fn listenfd_sockets() -> Result<Vec<(u32, TcpListener)>> {
     use listenfd::ListenFd;
 
-    for env in ["LISTEN_FDS", "LISTEN_FDNAMES"] {
-        if let Ok(val) = std::env::var(env) {
-            builder.env(env, &val)?;
-        }
-    }
-
     let mut listenfd = ListenFd::from_env();
+    let mut listeners = Vec::new();
 
     for i in 0..listenfd.len() {
         if let Some(stdlistener) = listenfd.take_tcp_listener(i)? {
             let _ = stdlistener.set_nonblocking(true)?;
             let listener = TcpListener::from_std(stdlistener);
-            builder.preopened_socket((3 + i) as _, listener)?;
-            num_fd = 3 + i;
+            listeners.push(((3 + i) as u32, listener));
         }
     }
 
-    Ok(num_fd)
+    Ok(listeners)
 }
 
-fn generate_coredump(err: &anyhow::Error, source_name: &str, coredump_path: &str) -> Result<()> {
-    let bt = err
-        .downcast_ref::<wasmtime::WasmBacktrace>()
-        .ok_or_else(|| anyhow!("no wasm backtrace found to generate coredump with"))?;
-
-    let coredump = wasm_encoder::CoreDumpSection::new(source_name);
-    let mut stacksection = wasm_encoder::CoreDumpStackSection::new("main");
-    for f in bt.frames() {
-        // We don't have the information at this point to map frames to
-        // individual instances of a module, so we won't be able to create the
-        // "frame ∈ instance ∈ module" hierarchy described in the core dump spec
-        // until we move core dump generation into the runtime. So for now
-        // instanceidx will be 0 for all frames
-        let instanceidx = 0;
-        stacksection.frame(
-            instanceidx,
-            f.func_index(),
-            u32::try_from(f.func_offset().unwrap_or(0)).unwrap(),
-            // We don't currently have access to locals/stack values
-            [],
-            [],
-        );
-    }
-    let mut module = wasm_encoder::Module::new();
-    module.section(&coredump);
-    module.section(&stacksection);
+fn generate_coredump(
+    err: &anyhow::Error,
+    store: &mut Store<Host>,
+    source_name: &str,
+    coredump_path: &str,
+) -> Result<()> {
+    let coredump = err
+        .downcast_ref::<wasmtime::WasmCoreDump>()
+        .ok_or_else(|| anyhow!("no wasm coredump found to generate coredump with"))?;
+
+    let coredump = coredump.serialize(store, source_name);
 
     let mut f = File::create(coredump_path)
         .context(format!("failed to create file at `{}`", coredump_path))?;
-    f.write_all(module.as_slice())
+    f.write_all(&coredump)
         .with_context(|| format!("failed to write coredump file at `{}`", coredump_path))?;
     Ok(())
 }
@@ -0,0 +1,263 @@
+//! The module that implements the `wasmtime serve` command.
+
+use crate::common::RunCommon;
+use anyhow::{anyhow, bail, Context as _, Result};
+use clap::Parser;
+use once_cell::sync::Lazy;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use wasmtime::component::{Component, InstancePre, Linker};
+use wasmtime::{Engine, Precompiled, Store, StoreLimits};
+use wasmtime_wasi::preview2::{self, Table, WasiCtx, WasiView};
+use wasmtime_wasi_http::body::HyperOutgoingBody;
+use wasmtime_wasi_http::{Proxy, WasiHttpCtx, WasiHttpView};
+
+static AFTER_HELP: Lazy<String> = Lazy::new(|| crate::FLAG_EXPLANATIONS.to_string());
+
+/// Runs a WebAssembly component which exports `wasi:http/incoming-handler`,
+/// serving each incoming HTTP request with a fresh instance.
+#[derive(Parser)]
+#[structopt(name = "serve", after_help = AFTER_HELP.as_str())]
+pub struct ServeCommand {
+    #[clap(flatten)]
+    run: RunCommon,
+
+    /// Socket address for the web server to bind to.
+    #[clap(
+        long = "addr",
+        value_name = "SOCKADDR",
+        default_value = "127.0.0.1:8080"
+    )]
+    addr: SocketAddr,
+
+    /// The WebAssembly component to run.
+    #[clap(value_name = "WASM", required = true)]
+    component: PathBuf,
+}
+
+impl ServeCommand {
+    /// Start a server to run the given wasi-http proxy component
+    pub fn execute(mut self) -> Result<()> {
+        self.run.common.init_logging();
+
+        // Every request is served by a fresh instance, so use the pooling
+        // allocator to make instantiation cheap unless told otherwise.
+        #[cfg(feature = "pooling-allocator")]
+        {
+            if self.run.common.pooling_allocator.is_none() {
+                self.run.common.pooling_allocator = Some(true);
+            }
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_time()
+            .enable_io()
+            .build()?;
+
+        runtime.block_on(self.serve())
+    }
+
+    fn new_store(&self, engine: &Engine) -> Result<Store<Host>> {
+        let mut builder = preview2::WasiCtxBuilder::new();
+        builder.inherit_stdout().inherit_stderr();
+
+        for (key, value) in self.run.compute_env()? {
+            builder.env(key, value);
+        }
+
+        for (name, dir) in self.run.compute_preopen_dirs()? {
+            builder.preopened_dir(
+                dir,
+                preview2::DirPerms::all(),
+                preview2::FilePerms::all(),
+                name,
+            );
+        }
+
+        let mut table = Table::new();
+        let ctx = builder.build(&mut table)?;
+        let host = Host {
+            table,
+            ctx,
+            http: WasiHttpCtx::new(),
+            limits: self.run.store_limits(),
+        };
+
+        let mut store = Store::new(engine, host);
+        store.limiter(|t| &mut t.limits);
+
+        if let Some(fuel) = self.run.common.fuel {
+            store.add_fuel(fuel)?;
+        }
+
+        Ok(store)
+    }
+
+    fn add_to_linker(&self, linker: &mut Linker<Host>) -> Result<()> {
+        preview2::command::add_to_linker(linker)?;
+        wasmtime_wasi_http::add_to_component_linker(linker)?;
+        Ok(())
+    }
+
+    async fn serve(self) -> Result<()> {
+        use hyper::server::conn::http1;
+
+        let mut config = self.run.common.config(None)?;
+        config.wasm_component_model(true);
+        config.async_support(true);
+
+        let engine = Engine::new(&config)?;
+
+        let mut linker = Linker::new(&engine);
+        self.add_to_linker(&mut linker)?;
+
+        let component = self.load_component(&engine)?;
+        let instance = linker.instantiate_pre(&component)?;
+
+        let listener = tokio::net::TcpListener::bind(self.addr)
+            .await
+            .with_context(|| format!("failed to bind to address '{}'", self.addr))?;
+        eprintln!("Serving HTTP on http://{}/", listener.local_addr()?);
+
+        let handler = ProxyHandler::new(self, engine, instance);
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let h = handler.clone();
+            tokio::task::spawn(async move {
+                if let Err(e) = http1::Builder::new()
+                    .keep_alive(true)
+                    .serve_connection(
+                        stream,
+                        hyper::service::service_fn(move |req| handle_request(h.clone(), req)),
+                    )
+                    .await
+                {
+                    eprintln!("error: {e:?}");
+                }
+            });
+        }
+    }
+
+    fn load_component(&self, engine: &Engine) -> Result<Component> {
+        let path = &self.component;
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("failed to read file: {}", path.display()))?;
+        match engine.detect_precompiled(&bytes) {
+            Some(Precompiled::Component) => {
+                self.run.ensure_allow_precompiled()?;
+                unsafe { Component::deserialize(engine, &bytes) }
+            }
+            Some(Precompiled::Module) => bail!("`wasmtime serve` requires a component"),
+            None => {
+                // Parse the text format here specifically to add the `path` to
+                // the error message if there's a syntax error.
+                let wasm = wat::parse_bytes(&bytes).map_err(|mut e| {
+                    e.set_path(path);
+                    e
+                })?;
+                if !wasmparser::Parser::is_component(&wasm) {
+                    bail!("`wasmtime serve` requires a component");
+                }
+                Component::new(engine, &wasm)
+            }
+        }
+    }
+}
+
+struct Host {
+    table: Table,
+    ctx: WasiCtx,
+    http: WasiHttpCtx,
+    limits: StoreLimits,
+}
+
+impl WasiView for Host {
+    fn table(&self) -> &Table {
+        &self.table
+    }
+
+    fn table_mut(&mut self) -> &mut Table {
+        &mut self.table
+    }
+
+    fn ctx(&self) -> &WasiCtx {
+        &self.ctx
+    }
+
+    fn ctx_mut(&mut self) -> &mut WasiCtx {
+        &mut self.ctx
+    }
+}
+
+impl WasiHttpView for Host {
+    fn http_ctx(&self) -> &WasiHttpCtx {
+        &self.http
+    }
+
+    fn http_ctx_mut(&mut self) -> &mut WasiHttpCtx {
+        &mut self.http
+    }
+}
+
+struct ProxyHandlerInner {
+    cmd: ServeCommand,
+    engine: Engine,
+    instance_pre: InstancePre<Host>,
+}
+
+#[derive(Clone)]
+struct ProxyHandler(Arc<ProxyHandlerInner>);
+
+impl ProxyHandler {
+    fn new(cmd: ServeCommand, engine: Engine, instance_pre: InstancePre<Host>) -> Self {
+        Self(Arc::new(ProxyHandlerInner {
+            cmd,
+            engine,
+            instance_pre,
+        }))
+    }
+}
+
+async fn handle_request(
+    ProxyHandler(inner): ProxyHandler,
+    req: hyper::Request<hyper::body::Incoming>,
+) -> Result<hyper::Response<HyperOutgoingBody>> {
+    let mut store = inner.cmd.new_store(&inner.engine)?;
+
+    let req = store.data_mut().new_incoming_request(req)?;
+    let (out, receiver) = store.data_mut().new_response_outparam()?;
+
+    let (proxy, _inst) = Proxy::instantiate_pre(&mut store, &inner.instance_pre).await?;
+
+    // The guest may keep writing the response body after it has set the
+    // response, so the call runs in its own task while the headers are sent.
+    let task = tokio::task::spawn(async move {
+        proxy
+            .wasi_http_incoming_handler()
+            .call_handle(&mut store, req, out)
+            .await
+    });
+
+    match receiver.await {
+        Ok(Ok(resp)) => {
+            // The response has been sent, so failures while the guest keeps
+            // running can no longer be reported to the client; log them.
+            tokio::task::spawn(async move {
+                if let Err(e) = task.await.map_err(anyhow::Error::from).and_then(|r| r) {
+                    eprintln!("error: guest failed after sending a response: {e:?}");
+                }
+            });
+            Ok(resp)
+        }
+        Ok(Err(e)) => Err(anyhow!("guest returned an error: {e}")),
+        // The outparam was dropped without being set, so report why the
+        // guest failed if it did.
+        Err(_) => match task.await {
+            Ok(Ok(())) => bail!("guest never invoked `response-outparam::set` method"),
+            Ok(Err(e)) => Err(e),
+            Err(e) => Err(e.into()),
+        },
+    }
+}
@@ -0,0 +1,180 @@
+//! Common functionality shared between command implementations.
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use wasmtime::{StoreLimits, StoreLimitsBuilder};
+use wasmtime_cli_flags::CommonOptions;
+use wasmtime_wasi::sync::{ambient_authority, Dir};
+
+fn parse_env_var(s: &str) -> Result<(String, Option<String>)> {
+    let mut parts = s.splitn(2, '=');
+    Ok((
+        parts.next().unwrap().to_string(),
+        parts.next().map(|s| s.to_string()),
+    ))
+}
+
+fn parse_map_dirs(s: &str) -> Result<(String, String)> {
+    let parts: Vec<&str> = s.split("::").collect();
+    if parts.len() != 2 {
+        bail!("must contain exactly one double colon ('::')");
+    }
+    Ok((parts[0].into(), parts[1].into()))
+}
+
+/// Common command line arguments for commands which run WebAssembly, such as
+/// `wasmtime run` and `wasmtime serve`.
+#[derive(Parser)]
+pub struct RunCommon {
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Allow executing precompiled WebAssembly modules as `*.cwasm` files.
+    ///
+    /// Note that this option is not safe to pass if the module being passed in
+    /// is arbitrary user input. Only `wasmtime`-precompiled modules generated
+    /// via the `wasmtime compile` command or equivalent should be passed as an
+    /// argument with this option specified.
+    #[clap(long = "allow-precompiled")]
+    pub allow_precompiled: bool,
+
+    /// Grant access to the given host directory
+    #[clap(long = "dir", number_of_values = 1, value_name = "DIRECTORY")]
+    pub dirs: Vec<String>,
+
+    /// Pass an environment variable to the program.
+    ///
+    /// The `--env FOO=BAR` form will set the environment variable named `FOO`
+    /// to the value `BAR` for the guest program using WASI. The `--env FOO`
+    /// form will set the environment variable named `FOO` to the same value it
+    /// has in the calling process for the guest, or in other words it will
+    /// cause the environment variable `FOO` to be inherited.
+    #[clap(long = "env", number_of_values = 1, value_name = "NAME[=VAL]", value_parser = parse_env_var)]
+    pub vars: Vec<(String, Option<String>)>,
+
+    /// Grant access to a guest directory mapped as a host directory
+    #[clap(long = "mapdir", number_of_values = 1, value_name = "GUEST_DIR::HOST_DIR", value_parser = parse_map_dirs)]
+    pub map_dirs: Vec<(String, String)>,
+
+    /// Maximum size, in bytes, that a linear memory is allowed to reach.
+    ///
+    /// Growth beyond this limit will cause `memory.grow` instructions in
+    /// WebAssembly modules to return -1 and fail.
+    #[clap(long, value_name = "BYTES")]
+    pub max_memory_size: Option<usize>,
+
+    /// Maximum size, in table elements, that a table is allowed to reach.
+    #[clap(long)]
+    pub max_table_elements: Option<u32>,
+
+    /// Maximum number of WebAssembly instances allowed to be created.
+    #[clap(long)]
+    pub max_instances: Option<usize>,
+
+    /// Maximum number of WebAssembly tables allowed to be created.
+    #[clap(long)]
+    pub max_tables: Option<usize>,
+
+    /// Maximum number of WebAssembly linear memories allowed to be created.
+    #[clap(long)]
+    pub max_memories: Option<usize>,
+
+    /// Force a trap to be raised on `memory.grow` and `table.grow` failure
+    /// instead of returning -1 from these instructions.
+    ///
+    /// This is not necessarily a spec-compliant option to enable but can be
+    /// useful for tracking down a backtrace of what is requesting so much
+    /// memory, for example.
+    #[clap(long)]
+    pub trap_on_grow_failure: bool,
+}
+
+impl RunCommon {
+    /// Builds the `StoreLimits` configured on the command line.
+    pub fn store_limits(&self) -> StoreLimits {
+        let mut limits = StoreLimitsBuilder::new();
+        if let Some(max) = self.max_memory_size {
+            limits = limits.memory_size(max);
+        }
+        if let Some(max) = self.max_table_elements {
+            limits = limits.table_elements(max);
+        }
+        if let Some(max) = self.max_instances {
+            limits = limits.instances(max);
+        }
+        if let Some(max) = self.max_tables {
+            limits = limits.tables(max);
+        }
+        if let Some(max) = self.max_memories {
+            limits = limits.memories(max);
+        }
+        limits
+            .trap_on_grow_failure(self.trap_on_grow_failure)
+            .build()
+    }
+
+    /// Opens all directories granted with `--dir` and `--mapdir`, paired with
+    /// the name the guest sees them as.
+    pub fn compute_preopen_dirs(&self) -> Result<Vec<(String, Dir)>> {
+        let mut preopen_dirs = Vec::new();
+
+        for dir in self.dirs.iter() {
+            preopen_dirs.push((
+                dir.clone(),
+                Dir::open_ambient_dir(dir, ambient_authority())
+                    .with_context(|| format!("failed to open directory '{}'", dir))?,
+            ));
+        }
+
+        for (guest, host) in self.map_dirs.iter() {
+            preopen_dirs.push((
+                guest.clone(),
+                Dir::open_ambient_dir(host, ambient_authority())
+                    .with_context(|| format!("failed to open directory '{}'", host))?,
+            ));
+        }
+
+        Ok(preopen_dirs)
+    }
+
+    /// Resolves the environment variables passed with `--env`, inheriting
+    /// those without a value from the calling process.
+    pub fn compute_env(&self) -> Result<Vec<(String, String)>> {
+        let mut env = Vec::new();
+        for (key, value) in self.vars.iter() {
+            let value = match value {
+                Some(value) => value.clone(),
+                None => std::env::var(key)
+                    .map_err(|_| anyhow!("environment varialbe `{key}` not found"))?,
+            };
+            env.push((key.clone(), value));
+        }
+        Ok(env)
+    }
+
+    /// Fails unless precompiled artifacts were allowed with
+    /// `--allow-precompiled`.
+    pub fn ensure_allow_precompiled(&self) -> Result<()> {
+        if self.allow_precompiled {
+            Ok(())
+        } else {
+            bail!("running a precompiled module requires the `--allow-precompiled` flag")
+        }
+    }
+
+    /// Fails unless the component model was enabled with `--wasm-features`.
+    #[cfg(feature = "component-model")]
+    pub fn ensure_allow_components(&self) -> Result<()> {
+        if !self
+            .common
+            .wasm_features
+            .unwrap_or_default()
+            .component_model
+            .unwrap_or(false)
+        {
+            bail!("cannot execute a component without `--wasm-features component-model`");
+        }
+
+        Ok(())
+    }
+}
@@ -61,3 +61,5 @@ static FLAG_EXPLANATIONS: Lazy<String> = Lazy::new(|| {
 });
 
 pub mod commands;
+
+mod common;
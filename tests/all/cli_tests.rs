@@ -574,6 +574,22 @@ fn run_threads() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "wasi-nn-onnx")]
+#[test]
+fn wasi_nn_load_by_name() -> Result<()> {
+    let wasm = build_wasm("tests/all/cli_tests/wasi_nn_load_by_name.wat")?;
+    run_wasmtime(&[
+        "run",
+        "--wasi-modules",
+        "experimental-wasi-nn",
+        "--wasi-nn-graph",
+        "onnx::crates/wasi-nn/tests/fixtures/add",
+        "--disable-cache",
+        wasm.path().to_str().unwrap(),
+    ])?;
+    Ok(())
+}
+
 #[cfg(feature = "wasi-threads")]
 #[test]
 fn run_simple_with_wasi_threads() -> Result<()> {
@@ -730,6 +746,79 @@ fn wasi_misaligned_pointer() -> Result<()> {
     Ok(())
 }
 
+// A `--tcplisten` socket behaves the same way with both preview1
+// implementations.
+#[test]
+fn tcplisten_accept() -> Result<()> {
+    let wasm = build_wasm("tests/all/cli_tests/tcplisten_accept.wat")?;
+    for preview2 in [false, true] {
+        let mut args = vec!["--disable-cache", "--tcplisten", "127.0.0.1:0"];
+        if preview2 {
+            args.push("--preview2");
+        }
+        args.push(wasm.path().to_str().unwrap());
+        let output = run_wasmtime_for_output(&args, None)?;
+        assert_eq!(output.status.code(), Some(0), "preview2: {preview2}");
+    }
+    Ok(())
+}
+
+// A connection accepted on a `--tcplisten` socket can be read from, written
+// to and shut down, with both preview1 implementations.
+#[test]
+fn tcplisten_echo() -> Result<()> {
+    use std::io::Read;
+    use std::net::{Shutdown, TcpListener, TcpStream};
+    use std::process::Stdio;
+    use std::time::{Duration, Instant};
+
+    let wasm = build_wasm("tests/all/cli_tests/tcplisten_echo.wat")?;
+    for preview2 in [false, true] {
+        // Find a free port for the guest to listen on.
+        let addr = TcpListener::bind("127.0.0.1:0")?.local_addr()?.to_string();
+
+        let mut cmd = get_wasmtime_command()?;
+        cmd.args(["--disable-cache", "--tcplisten", &addr]);
+        if preview2 {
+            cmd.arg("--preview2");
+        }
+        let mut child = cmd
+            .arg(wasm.path())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // The listener is bound once wasmtime has started up.
+        let start = Instant::now();
+        let mut stream = loop {
+            match TcpStream::connect(&addr) {
+                Ok(stream) => break stream,
+                Err(_) if start.elapsed() < Duration::from_secs(30) => {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                Err(e) => {
+                    child.kill()?;
+                    return Err(e.into());
+                }
+            }
+        };
+        stream.write_all(b"ping")?;
+        stream.shutdown(Shutdown::Write)?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+
+        let output = child.wait_with_output()?;
+        assert_eq!(
+            output.status.code(),
+            Some(0),
+            "preview2: {preview2}, stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(response, "pong", "preview2: {preview2}");
+    }
+    Ok(())
+}
+
 #[test]
 #[ignore] // FIXME(#6811) currently is flaky and may produce no output
 fn hello_with_preview2() -> Result<()> {
@@ -886,3 +975,89 @@ fn table_growth_failure2() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+#[cfg_attr(not(feature = "serve"), ignore)]
+fn serve_requires_component() -> Result<()> {
+    let output = get_wasmtime_command()?
+        .arg("serve")
+        .arg("--disable-cache")
+        .arg("tests/all/cli_tests/simple.wat")
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("`wasmtime serve` requires a component"),
+        "bad stderr: {stderr}"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(not(feature = "serve"), ignore)]
+fn serve_loopback() -> Result<()> {
+    serve_hello_wasi_http(&[])
+}
+
+#[test]
+#[cfg_attr(not(feature = "serve"), ignore)]
+fn serve_loopback_without_pooling() -> Result<()> {
+    serve_hello_wasi_http(&["--pooling-allocator=false"])
+}
+
+fn serve_hello_wasi_http(args: &[&str]) -> Result<()> {
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpStream;
+    use std::process::Stdio;
+
+    let mut child = get_wasmtime_command()?
+        .arg("serve")
+        .arg("--disable-cache")
+        .arg("--addr=127.0.0.1:0")
+        .args(args)
+        .arg("tests/all/cli_tests/hello_wasi_http.wat")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // The server prints the address it's listening on once it's bound, which
+    // is needed to find the port picked by the OS.
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    let addr = loop {
+        line.clear();
+        if stderr.read_line(&mut line)? == 0 {
+            child.wait()?;
+            bail!("`wasmtime serve` exited before listening");
+        }
+        if let Some(addr) = line.trim().strip_prefix("Serving HTTP on http://") {
+            break addr.trim_end_matches('/').to_string();
+        }
+    };
+
+    let request = || -> Result<String> {
+        let mut stream = TcpStream::connect(&addr)?;
+        stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    };
+    // Each request is served by a fresh instance.
+    let responses = (0..2).map(|_| request()).collect::<Vec<_>>();
+
+    child.kill()?;
+    child.wait()?;
+
+    for response in responses {
+        let response = response?;
+        assert!(
+            response.starts_with("HTTP/1.1 200 OK"),
+            "bad response: {response}"
+        );
+        assert!(
+            response.contains("Hello, wasi:http!"),
+            "bad response: {response}"
+        );
+    }
+    Ok(())
+}
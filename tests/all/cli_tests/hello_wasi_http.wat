@@ -0,0 +1,112 @@
+;; A minimal `wasi:http/proxy` component which responds to every request with
+;; a 200 status and a short body.
+(component
+  (import (interface "wasi:http/types") (instance $types
+    (type $error-def (variant
+      (case "invalid-url" string)
+      (case "timeout-error" string)
+      (case "protocol-error" string)
+      (case "unexpected-error" string)))
+    (export "error" (type $error (eq $error-def)))
+    (export "new-fields"
+      (func (param "entries" (list (tuple string string))) (result u32)))
+    (export "new-outgoing-response"
+      (func (param "status-code" u16) (param "headers" u32) (result u32)))
+    (export "outgoing-response-write"
+      (func (param "response" u32) (result (result u32))))
+    (export "set-response-outparam"
+      (func (param "param" u32) (param "response" (result u32 (error $error)))
+        (result (result))))
+    (export "finish-outgoing-stream"
+      (func (param "s" u32) (param "trailers" (option u32))))
+  ))
+  (import (interface "wasi:io/streams") (instance $streams
+    (type $status-def (enum "open" "ended"))
+    (export "stream-status" (type $status (eq $status-def)))
+    (export "blocking-write"
+      (func (param "this" u32) (param "buf" (list u8))
+        (result (result (tuple u64 $status)))))
+  ))
+
+  (core module $libc
+    (memory (export "memory") 1))
+  (core instance $libc (instantiate $libc))
+
+  (core func $new-fields
+    (canon lower (func $types "new-fields") (memory $libc "memory")))
+  (core func $new-outgoing-response
+    (canon lower (func $types "new-outgoing-response")))
+  (core func $outgoing-response-write
+    (canon lower (func $types "outgoing-response-write") (memory $libc "memory")))
+  (core func $set-response-outparam
+    (canon lower (func $types "set-response-outparam") (memory $libc "memory")))
+  (core func $finish-outgoing-stream
+    (canon lower (func $types "finish-outgoing-stream")))
+  (core func $blocking-write
+    (canon lower (func $streams "blocking-write") (memory $libc "memory")))
+
+  (core module $m
+    (import "libc" "memory" (memory 1))
+    (import "types" "new-fields" (func $new-fields (param i32 i32) (result i32)))
+    (import "types" "new-outgoing-response"
+      (func $new-outgoing-response (param i32 i32) (result i32)))
+    (import "types" "outgoing-response-write"
+      (func $outgoing-response-write (param i32 i32)))
+    (import "types" "set-response-outparam"
+      (func $set-response-outparam (param i32 i32 i32 i32 i32) (result i32)))
+    (import "types" "finish-outgoing-stream"
+      (func $finish-outgoing-stream (param i32 i32 i32)))
+    (import "streams" "blocking-write"
+      (func $blocking-write (param i32 i32 i32 i32)))
+
+    (data (i32.const 64) "Hello, wasi:http!\n")
+
+    (func (export "handle") (param $request i32) (param $outparam i32)
+      (local $response i32)
+      (local $body i32)
+
+      ;; response = new-outgoing-response(200, new-fields([]))
+      (local.set $response
+        (call $new-outgoing-response
+          (i32.const 200)
+          (call $new-fields (i32.const 0) (i32.const 0))))
+
+      ;; body = outgoing-response-write(response), returned at address 0
+      (call $outgoing-response-write (local.get $response) (i32.const 0))
+      (if (i32.load8_u (i32.const 0)) (then unreachable))
+      (local.set $body (i32.load (i32.const 4)))
+
+      ;; set-response-outparam(outparam, ok(response))
+      (if (call $set-response-outparam
+            (local.get $outparam)
+            (i32.const 0)
+            (local.get $response)
+            (i32.const 0)
+            (i32.const 0))
+        (then unreachable))
+
+      ;; blocking-write(body, "Hello, wasi:http!\n"), returned at address 16
+      (call $blocking-write (local.get $body) (i32.const 64) (i32.const 18) (i32.const 16))
+      (if (i32.load8_u (i32.const 16)) (then unreachable))
+
+      ;; finish-outgoing-stream(body, none)
+      (call $finish-outgoing-stream (local.get $body) (i32.const 0) (i32.const 0)))
+  )
+
+  (core instance $i (instantiate $m
+    (with "libc" (instance $libc))
+    (with "types" (instance
+      (export "new-fields" (func $new-fields))
+      (export "new-outgoing-response" (func $new-outgoing-response))
+      (export "outgoing-response-write" (func $outgoing-response-write))
+      (export "set-response-outparam" (func $set-response-outparam))
+      (export "finish-outgoing-stream" (func $finish-outgoing-stream))))
+    (with "streams" (instance
+      (export "blocking-write" (func $blocking-write))))))
+
+  (func $handle (param "request" u32) (param "response-out" u32)
+    (canon lift (core func $i "handle")))
+
+  (instance (export (interface "wasi:http/incoming-handler"))
+    (export "handle" (func $handle)))
+)
@@ -0,0 +1,27 @@
+(module
+  (import "wasi_snapshot_preview1" "fd_fdstat_get"
+    (func $fd_fdstat_get (param i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "sock_accept"
+    (func $sock_accept (param i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "proc_exit"
+    (func $proc_exit (param i32)))
+
+  (memory (export "memory") 1)
+
+  (func (export "_start")
+    ;; fd 3 is the preopened listener.
+    (if (i32.ne (call $fd_fdstat_get (i32.const 3) (i32.const 0)) (i32.const 0))
+      (then (call $proc_exit (i32.const 1))))
+
+    ;; fs_filetype must be `socket_stream`.
+    (if (i32.ne (i32.load8_u (i32.const 0)) (i32.const 6))
+      (then (call $proc_exit (i32.const 2))))
+
+    ;; Nothing is connecting and the listener is non-blocking, so accepting
+    ;; fails with `again`.
+    (if (i32.ne
+          (call $sock_accept (i32.const 3) (i32.const 0) (i32.const 32))
+          (i32.const 6))
+      (then (call $proc_exit (i32.const 3))))
+  )
+)
@@ -0,0 +1,73 @@
+(module
+  (import "wasi_snapshot_preview1" "sock_accept"
+    (func $sock_accept (param i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "sock_recv"
+    (func $sock_recv (param i32 i32 i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "sock_send"
+    (func $sock_send (param i32 i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "sock_shutdown"
+    (func $sock_shutdown (param i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "sched_yield"
+    (func $sched_yield (result i32)))
+  (import "wasi_snapshot_preview1" "proc_exit"
+    (func $proc_exit (param i32)))
+
+  (memory (export "memory") 1)
+
+  ;; Memory layout:
+  ;;   0: the accepted connection's fd
+  ;;   8: the number of bytes received, followed by the returned roflags
+  ;;  16: the iovec to receive into, pointing at 64..128
+  ;;  24: the ciovec to send, pointing at "pong"
+  ;;  64: the receive buffer
+  (data (i32.const 16) "\40\00\00\00\40\00\00\00")
+  (data (i32.const 24) "\20\00\00\00\04\00\00\00")
+  (data (i32.const 32) "pong")
+
+  (func (export "_start")
+    (local $errno i32)
+    (local $conn i32)
+
+    ;; fd 3 is the preopened listener, which is non-blocking, so keep trying
+    ;; until the test connects.
+    (block $accepted
+      (loop $accept
+        (local.set $errno
+          (call $sock_accept (i32.const 3) (i32.const 0) (i32.const 0)))
+        (br_if $accepted (i32.eqz (local.get $errno)))
+        ;; Anything other than `again` is a failure.
+        (if (i32.ne (local.get $errno) (i32.const 6))
+          (then (call $proc_exit (i32.const 1))))
+        (drop (call $sched_yield))
+        (br $accept)))
+    (local.set $conn (i32.load (i32.const 0)))
+
+    ;; The accepted connection is blocking. The test sends "ping" and then
+    ;; shuts down its side, so waiting for the whole buffer (RECV_WAITALL)
+    ;; stops at the end of the stream.
+    (if (i32.ne
+          (call $sock_recv
+            (local.get $conn) (i32.const 16) (i32.const 1) (i32.const 2)
+            (i32.const 8) (i32.const 12))
+          (i32.const 0))
+      (then (call $proc_exit (i32.const 2))))
+    (if (i32.ne (i32.load (i32.const 8)) (i32.const 4))
+      (then (call $proc_exit (i32.const 3))))
+    ;; "ping", little-endian.
+    (if (i32.ne (i32.load (i32.const 64)) (i32.const 0x676e6970))
+      (then (call $proc_exit (i32.const 4))))
+
+    (if (i32.ne
+          (call $sock_send
+            (local.get $conn) (i32.const 24) (i32.const 1) (i32.const 0)
+            (i32.const 8))
+          (i32.const 0))
+      (then (call $proc_exit (i32.const 5))))
+    (if (i32.ne (i32.load (i32.const 8)) (i32.const 4))
+      (then (call $proc_exit (i32.const 6))))
+
+    ;; Shutting down the sending side lets the test read to the end.
+    (if (i32.ne (call $sock_shutdown (local.get $conn) (i32.const 2)) (i32.const 0))
+      (then (call $proc_exit (i32.const 7))))
+  )
+)
@@ -0,0 +1,65 @@
+;; Loads the `add` model, preloaded with `--wasi-nn-graph`, by name and checks
+;; that it computes `x + [1, 2, 3, 4]`. Any error traps.
+(module
+  (import "wasi_ephemeral_nn" "load_by_name"
+    (func $load_by_name (param i32 i32 i32) (result i32)))
+  (import "wasi_ephemeral_nn" "init_execution_context"
+    (func $init_execution_context (param i32 i32) (result i32)))
+  (import "wasi_ephemeral_nn" "set_input"
+    (func $set_input (param i32 i32 i32) (result i32)))
+  (import "wasi_ephemeral_nn" "compute"
+    (func $compute (param i32) (result i32)))
+  (import "wasi_ephemeral_nn" "get_output"
+    (func $get_output (param i32 i32 i32 i32 i32) (result i32)))
+
+  (memory (export "memory") 1)
+
+  ;; The name of the graph.
+  (data (i32.const 0) "add")
+  ;; The input tensor's dimensions, `[1, 4]`...
+  (data (i32.const 16) "\01\00\00\00\04\00\00\00")
+  ;; ...and its data, `[10.0, 20.0, 30.0, 40.0]`.
+  (data (i32.const 32) "\00\00\20\41\00\00\a0\41\00\00\f0\41\00\00\20\42")
+  ;; The input tensor: its dimensions, its type (f32) and its data.
+  (data (i32.const 64)
+    "\10\00\00\00\02\00\00\00"
+    "\01\00\00\00"
+    "\20\00\00\00\10\00\00\00")
+
+  (func $check (param i32)
+    (if (local.get 0) (then unreachable)))
+
+  (func $check_output (param $index i32) (param $expected f32)
+    (if (f32.ne
+          (f32.load offset=128 (i32.mul (local.get $index) (i32.const 4)))
+          (local.get $expected))
+      (then unreachable)))
+
+  (func (export "_start")
+    ;; Load the graph into address 96 and create an execution context for it
+    ;; at address 100.
+    (call $check (call $load_by_name (i32.const 0) (i32.const 3) (i32.const 96)))
+    (call $check (call $init_execution_context
+      (i32.load (i32.const 96))
+      (i32.const 100)))
+
+    (call $check (call $set_input
+      (i32.load (i32.const 100))
+      (i32.const 0)
+      (i32.const 64)))
+    (call $check (call $compute (i32.load (i32.const 100))))
+
+    ;; Read the output into address 128, and its size into address 104.
+    (call $check (call $get_output
+      (i32.load (i32.const 100))
+      (i32.const 0)
+      (i32.const 128)
+      (i32.const 64)
+      (i32.const 104)))
+    (if (i32.ne (i32.load (i32.const 104)) (i32.const 16))
+      (then unreachable))
+    (call $check_output (i32.const 0) (f32.const 11))
+    (call $check_output (i32.const 1) (f32.const 22))
+    (call $check_output (i32.const 2) (f32.const 33))
+    (call $check_output (i32.const 3) (f32.const 44)))
+)
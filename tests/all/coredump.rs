@@ -145,3 +145,239 @@ fn test_coredump_has_import_globals_and_memory() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_coredump_serialize() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    let engine = Engine::new(&config).unwrap();
+    let mut store = Store::<()>::new(&engine, ());
+
+    let wat = r#"
+      (module $foo
+        (memory (export "memory") 1)
+        (global $g (mut i64) (i64.const 42))
+        (data (i32.const 100) "hello")
+        (func (export "a")
+            global.get $g
+            drop
+            unreachable
+        )
+      )
+    "#;
+
+    let module = Module::new(store.engine(), wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let a_func = instance.get_typed_func::<(), ()>(&mut store, "a")?;
+
+    let e = a_func.call(&mut store, ()).unwrap_err();
+    let cd = e.downcast_ref::<WasmCoreDump>().unwrap();
+    let bytes = cd.serialize(&mut store, "test.wasm");
+
+    wasmparser::validate(&bytes)?;
+
+    let mut custom_sections = Vec::new();
+    let mut data = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(&bytes) {
+        match payload? {
+            wasmparser::Payload::CustomSection(s) => custom_sections.push(s.name().to_string()),
+            wasmparser::Payload::DataSection(reader) => {
+                for segment in reader {
+                    data.extend_from_slice(segment?.data);
+                }
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(
+        custom_sections,
+        ["core", "coremodules", "coreinstances", "corestack"]
+    );
+    assert_eq!(data, b"hello");
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_coredump_has_locals() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    config.wasm_backtrace_locals(true);
+    let engine = Engine::new(&config).unwrap();
+    let mut store = Store::<()>::new(&engine, ());
+
+    let wat = r#"
+      (module
+        (func $a (export "a") (param i32)
+            (local i64 f64)
+            (local.set 1 (i64.const 7))
+            (call $b (f32.const 1.5))
+        )
+        (func $b (param f32)
+            (local externref i32)
+            (local.set 2 (i32.const 42))
+            unreachable
+        )
+      )
+    "#;
+
+    let module = Module::new(store.engine(), wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let a_func = instance.get_typed_func::<i32, ()>(&mut store, "a")?;
+
+    let e = a_func.call(&mut store, 3).unwrap_err();
+    let cd = e.downcast_ref::<WasmCoreDump>().unwrap();
+    assert_eq!(cd.frames().len(), 2);
+
+    let b_locals = cd.frames()[0].locals();
+    assert_eq!(b_locals.len(), 3);
+    assert_eq!(b_locals[0].as_ref().and_then(|v| v.f32()), Some(1.5));
+    assert!(b_locals[1].is_none());
+    assert_eq!(b_locals[2].as_ref().and_then(|v| v.i32()), Some(42));
+
+    let a_locals = cd.frames()[1].locals();
+    assert_eq!(a_locals.len(), 3);
+    assert_eq!(a_locals[0].as_ref().and_then(|v| v.i32()), Some(3));
+    assert_eq!(a_locals[1].as_ref().and_then(|v| v.i64()), Some(7));
+    assert_eq!(a_locals[2].as_ref().and_then(|v| v.f64()), Some(0.0));
+
+    // The backtrace attached to the error has the same locals.
+    let bt = e.downcast_ref::<WasmBacktrace>().unwrap();
+    assert_eq!(bt.frames()[0].locals().len(), 3);
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_coredump_has_operand_stack() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    config.wasm_backtrace_locals(true);
+    let engine = Engine::new(&config).unwrap();
+    let mut store = Store::<()>::new(&engine, ());
+
+    let wat = r#"
+      (module
+        (func $a (export "a") (param i32) (result i32)
+            (i64.const 5)
+            (f64.const 2.5)
+            (local.get 0)
+            (call $b)
+            (drop)
+            (drop)
+            (i32.wrap_i64)
+        )
+        (func $b (param i32) (result i32)
+            (i32.const 3)
+            (i32.const 4)
+            (local.get 0)
+            (i32.div_u)
+            (i32.add)
+        )
+      )
+    "#;
+
+    let module = Module::new(store.engine(), wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let a_func = instance.get_typed_func::<i32, i32>(&mut store, "a")?;
+
+    let e = a_func.call(&mut store, 0).unwrap_err();
+    let cd = e.downcast_ref::<WasmCoreDump>().unwrap();
+    assert_eq!(cd.frames().len(), 2);
+
+    // The trapping frame has the operands of the division on its stack.
+    let b_stack = cd.frames()[0].operand_stack();
+    assert_eq!(b_stack.len(), 3);
+    assert_eq!(b_stack[0].as_ref().and_then(|v| v.i32()), Some(3));
+    assert_eq!(b_stack[1].as_ref().and_then(|v| v.i32()), Some(4));
+    assert_eq!(b_stack[2].as_ref().and_then(|v| v.i32()), Some(0));
+
+    // The calling frame has the argument of the call on top of its stack.
+    let a_stack = cd.frames()[1].operand_stack();
+    assert_eq!(a_stack.len(), 3);
+    assert_eq!(a_stack[0].as_ref().and_then(|v| v.i64()), Some(5));
+    assert_eq!(a_stack[1].as_ref().and_then(|v| v.f64()), Some(2.5));
+    assert_eq!(a_stack[2].as_ref().and_then(|v| v.i32()), Some(0));
+
+    // Without locals being captured, the operand stack isn't either.
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    let engine = Engine::new(&config).unwrap();
+    let mut store = Store::<()>::new(&engine, ());
+    let module = Module::new(store.engine(), wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let a_func = instance.get_typed_func::<i32, i32>(&mut store, "a")?;
+    let e = a_func.call(&mut store, 0).unwrap_err();
+    let cd = e.downcast_ref::<WasmCoreDump>().unwrap();
+    assert!(cd.frames()[0].operand_stack().is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_coredump_frames_have_their_instance() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    assert_trap_attributed_to_first_instance(&config)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_coredump_frames_have_their_instance_with_locals() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    config.wasm_backtrace_locals(true);
+    assert_trap_attributed_to_first_instance(&config)
+}
+
+fn assert_trap_attributed_to_first_instance(config: &Config) -> Result<()> {
+    let engine = Engine::new(config).unwrap();
+    let mut store = Store::<()>::new(&engine, ());
+
+    let wat = r#"
+      (module
+        (func (export "a")
+            unreachable
+        )
+      )
+    "#;
+
+    // Instantiate the same module twice and trap in the first instance.
+    let module = Module::new(store.engine(), wat)?;
+    let first = Instance::new(&mut store, &module, &[])?;
+    let _second = Instance::new(&mut store, &module, &[])?;
+    let a_func = first.get_typed_func::<(), ()>(&mut store, "a")?;
+
+    let e = a_func.call(&mut store, ()).unwrap_err();
+    let cd = e.downcast_ref::<WasmCoreDump>().unwrap();
+    assert_eq!(cd.instances().len(), 2);
+    let bytes = cd.serialize(&mut store, "test.wasm");
+
+    let mut stack = None;
+    for payload in wasmparser::Parser::new(0).parse_all(&bytes) {
+        if let wasmparser::Payload::CustomSection(s) = payload? {
+            if s.name() == "corestack" {
+                stack = Some(s.data().to_vec());
+            }
+        }
+    }
+
+    // The section holds the thread info (a zero byte and the thread name), the
+    // number of frames and then each frame, which starts with a zero byte
+    // followed by its instance index. All of these numbers fit in a single
+    // LEB128 byte here.
+    let stack = stack.unwrap();
+    assert_eq!(stack[0], 0);
+    let name_len = usize::from(stack[1]);
+    assert_eq!(&stack[2..][..name_len], b"main");
+    let frames = &stack[2 + name_len..];
+    assert_eq!(frames[0], 1);
+    assert_eq!(frames[1], 0);
+    assert_eq!(frames[2], 0, "frame should belong to the first instance");
+
+    Ok(())
+}
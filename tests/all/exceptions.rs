@@ -0,0 +1,146 @@
+use anyhow::Result;
+use wasmtime::*;
+
+fn exceptions_engine() -> Engine {
+    let mut config = Config::new();
+    config.wasm_exceptions(true);
+    Engine::new(&config).unwrap()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn uncaught_exception_reaches_host() -> Result<()> {
+    let engine = exceptions_engine();
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (tag $e (export "e") (param i32 i64))
+                (func (export "throw") (param i32)
+                    (throw $e (local.get 0) (i64.const 42))))
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let throw = instance.get_typed_func::<i32, ()>(&mut store, "throw")?;
+
+    let err = throw.call(&mut store, 7).unwrap_err();
+    let exception = err.downcast_ref::<WasmException>().unwrap();
+    let payload = exception.payload();
+    assert_eq!(payload.len(), 2);
+    assert_eq!(payload[0].unwrap_i32(), 7);
+    assert_eq!(payload[1].unwrap_i64(), 42);
+    let params = exception.tag().ty(&store).params().collect::<Vec<_>>();
+    assert_eq!(params, [ValType::I32, ValType::I64]);
+
+    // The store is still usable, and the same function may throw again.
+    let err = throw.call(&mut store, 8).unwrap_err();
+    let exception = err.downcast_ref::<WasmException>().unwrap();
+    assert_eq!(exception.payload()[0].unwrap_i32(), 8);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn host_throws_into_wasm() -> Result<()> {
+    let engine = exceptions_engine();
+    let mut store = Store::new(&engine, ());
+    let tag = Tag::new(&mut store, &TagType::new([ValType::I32]));
+    let host = Func::wrap(&mut store, move |mut caller: Caller<'_, ()>, x: i32| {
+        let exception = WasmException::new(&mut caller, &tag, &[Val::I32(x * 2)])?;
+        Err::<(), _>(exception.into())
+    });
+
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (import "" "tag" (tag $e (param i32)))
+                (import "" "host" (func $host (param i32)))
+                (func (export "run") (param i32) (result i32)
+                    (try (result i32)
+                        (do (call $host (local.get 0)) (i32.const 0))
+                        (catch $e))))
+        "#,
+    )?;
+    let instance = Instance::new(&mut store, &module, &[tag.into(), host.into()])?;
+    let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+    assert_eq!(run.call(&mut store, 21)?, 42);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn host_exception_without_catch_returns_to_host() -> Result<()> {
+    let engine = exceptions_engine();
+    let mut store = Store::new(&engine, ());
+    let tag = Tag::new(&mut store, &TagType::new([]));
+    let host = Func::wrap(&mut store, move |mut caller: Caller<'_, ()>| {
+        let exception = WasmException::new(&mut caller, &tag, &[])?;
+        Err::<(), _>(exception.into())
+    });
+
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (import "" "host" (func $host))
+                (func (export "run") (call $host) (unreachable)))
+        "#,
+    )?;
+    let instance = Instance::new(&mut store, &module, &[host.into()])?;
+    let run = instance.get_typed_func::<(), ()>(&mut store, "run")?;
+    let err = run.call(&mut store, ()).unwrap_err();
+    assert!(err.is::<WasmException>(), "unexpected error: {err:?}");
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn exception_payload_type_is_checked() -> Result<()> {
+    let engine = exceptions_engine();
+    let mut store = Store::new(&engine, ());
+    let tag = Tag::new(&mut store, &TagType::new([ValType::I32]));
+    assert!(WasmException::new(&mut store, &tag, &[]).is_err());
+    assert!(WasmException::new(&mut store, &tag, &[Val::I64(0)]).is_err());
+    assert!(WasmException::new(&mut store, &tag, &[Val::I32(0)]).is_ok());
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn exception_propagates_through_tail_calls() -> Result<()> {
+    let mut config = Config::new();
+    config.wasm_exceptions(true);
+    config.wasm_tail_call(true);
+    let engine = Engine::new(&config)?;
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (tag $e (param i32))
+                (func $throw (param i32) (throw $e (local.get 0)))
+                (func $tail (param i32) (return_call $throw (local.get 0)))
+                (func $outer (param i32) (call $tail (local.get 0)))
+                (func (export "run") (param i32) (result i32)
+                    (try (result i32)
+                        (do (call $outer (local.get 0)) (i32.const 0))
+                        (catch $e (i32.add (i32.const 1))))))
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+    assert_eq!(run.call(&mut store, 30)?, 31);
+    Ok(())
+}
+
+#[test]
+fn exceptions_disabled_by_default() {
+    let engine = Engine::default();
+    let err = Module::new(&engine, "(module (tag))").unwrap_err();
+    assert!(
+        format!("{err:?}").contains("exceptions proposal not enabled"),
+        "unexpected error: {err:?}"
+    );
+}
@@ -31,24 +31,29 @@ fn run() -> Result<()> {
     let buf = ParseBuffer::new(&test)?;
     let mut wast = parser::parse::<FuelWast<'_>>(&buf)?;
     for (span, fuel, module) in wast.assertions.iter_mut() {
-        let consumed = fuel_consumed(&module.encode()?);
-        if consumed == *fuel {
-            continue;
+        let wasm = module.encode()?;
+        for strategy in crate::strategies() {
+            let consumed = fuel_consumed(strategy, &wasm);
+            if consumed == *fuel {
+                continue;
+            }
+            let (line, col) = span.linecol_in(&test);
+            panic!(
+                "tests/all/fuel.wast:{}:{} - expected {} fuel, found {} ({:?})",
+                line + 1,
+                col + 1,
+                fuel,
+                consumed,
+                strategy
+            );
         }
-        let (line, col) = span.linecol_in(&test);
-        panic!(
-            "tests/all/fuel.wast:{}:{} - expected {} fuel, found {}",
-            line + 1,
-            col + 1,
-            fuel,
-            consumed
-        );
     }
     Ok(())
 }
 
-fn fuel_consumed(wasm: &[u8]) -> u64 {
+fn fuel_consumed(strategy: Strategy, wasm: &[u8]) -> u64 {
     let mut config = Config::new();
+    config.strategy(strategy);
     config.consume_fuel(true);
     let engine = Engine::new(&config).unwrap();
     let module = Module::new(&engine, wasm).unwrap();
@@ -111,14 +116,17 @@ fn iloop() {
     );
 
     fn iloop_aborts(wat: &str) {
-        let mut config = Config::new();
-        config.consume_fuel(true);
-        let engine = Engine::new(&config).unwrap();
-        let module = Module::new(&engine, wat).unwrap();
-        let mut store = Store::new(&engine, ());
-        store.add_fuel(10_000).unwrap();
-        let error = Instance::new(&mut store, &module, &[]).err().unwrap();
-        assert_eq!(error.downcast::<Trap>().unwrap(), Trap::OutOfFuel);
+        for strategy in crate::strategies() {
+            let mut config = Config::new();
+            config.strategy(strategy);
+            config.consume_fuel(true);
+            let engine = Engine::new(&config).unwrap();
+            let module = Module::new(&engine, wat).unwrap();
+            let mut store = Store::new(&engine, ());
+            store.add_fuel(10_000).unwrap();
+            let error = Instance::new(&mut store, &module, &[]).err().unwrap();
+            assert_eq!(error.downcast::<Trap>().unwrap(), Trap::OutOfFuel);
+        }
     }
 }
 
@@ -149,32 +157,35 @@ fn manual_fuel() {
 #[cfg_attr(miri, ignore)]
 fn host_function_consumes_all() {
     const FUEL: u64 = 10_000;
-    let mut config = Config::new();
-    config.consume_fuel(true);
-    let engine = Engine::new(&config).unwrap();
-    let module = Module::new(
-        &engine,
-        r#"
-            (module
-                (import "" "" (func))
-                (func (export "")
-                    call 0
-                    call $other)
-                (func $other))
-        "#,
-    )
-    .unwrap();
-    let mut store = Store::new(&engine, ());
-    store.add_fuel(FUEL).unwrap();
-    let func = Func::wrap(&mut store, |mut caller: Caller<'_, ()>| {
-        let consumed = caller.fuel_consumed().unwrap();
-        assert_eq!(caller.consume_fuel((FUEL - consumed) - 1).unwrap(), 1);
-    });
-
-    let instance = Instance::new(&mut store, &module, &[func.into()]).unwrap();
-    let export = instance.get_typed_func::<(), ()>(&mut store, "").unwrap();
-    let trap = export.call(&mut store, ()).unwrap_err();
-    assert_eq!(trap.downcast::<Trap>().unwrap(), Trap::OutOfFuel);
+    for strategy in crate::strategies() {
+        let mut config = Config::new();
+        config.strategy(strategy);
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).unwrap();
+        let module = Module::new(
+            &engine,
+            r#"
+                (module
+                    (import "" "" (func))
+                    (func (export "")
+                        call 0
+                        call $other)
+                    (func $other))
+            "#,
+        )
+        .unwrap();
+        let mut store = Store::new(&engine, ());
+        store.add_fuel(FUEL).unwrap();
+        let func = Func::wrap(&mut store, |mut caller: Caller<'_, ()>| {
+            let consumed = caller.fuel_consumed().unwrap();
+            assert_eq!(caller.consume_fuel((FUEL - consumed) - 1).unwrap(), 1);
+        });
+
+        let instance = Instance::new(&mut store, &module, &[func.into()]).unwrap();
+        let export = instance.get_typed_func::<(), ()>(&mut store, "").unwrap();
+        let trap = export.call(&mut store, ()).unwrap_err();
+        assert_eq!(trap.downcast::<Trap>().unwrap(), Trap::OutOfFuel);
+    }
 }
 
 #[test]
@@ -193,44 +204,47 @@ fn manual_edge_cases() {
 #[test]
 #[cfg_attr(miri, ignore)]
 fn unconditionally_trapping_memory_accesses_save_fuel_before_trapping() {
-    let mut config = Config::new();
-    config.consume_fuel(true);
-    config.static_memory_maximum_size(0x1_0000);
-
-    let engine = Engine::new(&config).unwrap();
-
-    let module = Module::new(
-        &engine,
-        r#"
-            (module
-              (memory 1 1)
-              (func (export "f") (param i32) (result i32)
-                local.get 0
-                local.get 0
-                i32.add
-                ;; This offset is larger than our memory max size and therefore
-                ;; will unconditionally trap.
-                i32.load8_s offset=0xffffffff))
-        "#,
-    )
-    .unwrap();
+    for strategy in crate::strategies() {
+        let mut config = Config::new();
+        config.strategy(strategy);
+        config.consume_fuel(true);
+        config.static_memory_maximum_size(0x1_0000);
 
-    let mut store = Store::new(&engine, ());
-    let init_fuel = 1_000;
-    store.add_fuel(init_fuel).unwrap();
-    assert_eq!(init_fuel, store.fuel_remaining().unwrap());
+        let engine = Engine::new(&config).unwrap();
 
-    let instance = Instance::new(&mut store, &module, &[]).unwrap();
-    let f = instance
-        .get_typed_func::<i32, i32>(&mut store, "f")
+        let module = Module::new(
+            &engine,
+            r#"
+                (module
+                  (memory 1 1)
+                  (func (export "f") (param i32) (result i32)
+                    local.get 0
+                    local.get 0
+                    i32.add
+                    ;; This offset is larger than our memory max size and therefore
+                    ;; will unconditionally trap.
+                    i32.load8_s offset=0xffffffff))
+            "#,
+        )
         .unwrap();
 
-    let trap = f.call(&mut store, 0).unwrap_err();
-    assert_eq!(trap.downcast::<Trap>().unwrap(), Trap::MemoryOutOfBounds);
-
-    // The `i32.add` consumed some fuel before the unconditionally trapping
-    // memory access.
-    let consumed_fuel = store.fuel_consumed().unwrap();
-    assert!(consumed_fuel > 0);
-    assert_eq!(init_fuel, consumed_fuel + store.fuel_remaining().unwrap());
+        let mut store = Store::new(&engine, ());
+        let init_fuel = 1_000;
+        store.add_fuel(init_fuel).unwrap();
+        assert_eq!(init_fuel, store.fuel_remaining().unwrap());
+
+        let instance = Instance::new(&mut store, &module, &[]).unwrap();
+        let f = instance
+            .get_typed_func::<i32, i32>(&mut store, "f")
+            .unwrap();
+
+        let trap = f.call(&mut store, 0).unwrap_err();
+        assert_eq!(trap.downcast::<Trap>().unwrap(), Trap::MemoryOutOfBounds);
+
+        // The `i32.add` consumed some fuel before the unconditionally trapping
+        // memory access.
+        let consumed_fuel = store.fuel_consumed().unwrap();
+        assert!(consumed_fuel > 0);
+        assert_eq!(init_fuel, consumed_fuel + store.fuel_remaining().unwrap());
+    }
 }
@@ -8,6 +8,7 @@ mod coredump;
 mod custom_signal_handler;
 mod debug;
 mod epoch_interruption;
+mod exceptions;
 mod externals;
 mod fuel;
 mod func;
@@ -73,6 +74,14 @@ pub(crate) fn ref_types_module(
     Ok((store, module))
 }
 
+/// The compilation strategies that tests exercising codegen-dependent
+/// behavior should run under. Winch is currently only supported on x86_64.
+pub(crate) fn strategies() -> impl Iterator<Item = wasmtime::Strategy> {
+    [wasmtime::Strategy::Cranelift]
+        .into_iter()
+        .chain(cfg!(target_arch = "x86_64").then_some(wasmtime::Strategy::Winch))
+}
+
 /// A helper determining whether the pooling allocator tests should be skipped.
 pub(crate) fn skip_pooling_allocator_tests() -> bool {
     // There are a couple of issues when running the pooling allocator tests under QEMU:
@@ -22,8 +22,10 @@ fn run_wast(wast: &str, strategy: Strategy, pooling: bool) -> anyhow::Result<()>
     let wast = Path::new(wast);
 
     let memory64 = feature_found(wast, "memory64");
+    let extended_const = feature_found(wast, "extended-const");
     let multi_memory = feature_found(wast, "multi-memory");
     let threads = feature_found(wast, "threads");
+    let exceptions = feature_found(wast, "exceptions");
     let function_references = feature_found(wast, "function-references");
     let reference_types = !(threads && feature_found(wast, "proposals"));
     let relaxed_simd = feature_found(wast, "relaxed-simd");
@@ -45,10 +47,12 @@ fn run_wast(wast: &str, strategy: Strategy, pooling: bool) -> anyhow::Result<()>
     cfg.wasm_multi_memory(multi_memory)
         .wasm_threads(threads)
         .wasm_memory64(memory64)
+        .wasm_extended_const(extended_const)
         .wasm_function_references(function_references)
         .wasm_reference_types(reference_types)
         .wasm_relaxed_simd(relaxed_simd)
         .wasm_tail_call(tail_call)
+        .wasm_exceptions(exceptions)
         .strategy(strategy);
 
     if is_cranelift {
@@ -239,3 +239,206 @@ fn wasm_to_native_trap() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg(target_arch = "x86_64")]
+fn float_rounding_without_sse41() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    // Without SSE4.1 Winch can't use `roundss`/`roundsd` and falls back to
+    // libcalls instead.
+    unsafe {
+        c.cranelift_flag_set("has_sse41", "false");
+    }
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (module
+          (func (export "f32.ceil") (param f32) (result f32) (f32.ceil (local.get 0)))
+          (func (export "f32.floor") (param f32) (result f32) (f32.floor (local.get 0)))
+          (func (export "f32.trunc") (param f32) (result f32) (f32.trunc (local.get 0)))
+          (func (export "f32.nearest") (param f32) (result f32) (f32.nearest (local.get 0)))
+          (func (export "f64.ceil") (param f64) (result f64) (f64.ceil (local.get 0)))
+          (func (export "f64.floor") (param f64) (result f64) (f64.floor (local.get 0)))
+          (func (export "f64.trunc") (param f64) (result f64) (f64.trunc (local.get 0)))
+          (func (export "f64.nearest") (param f64) (result f64) (f64.nearest (local.get 0))))
+    "#;
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+
+    let f32_tests: [(&str, f32, f32); 8] = [
+        ("f32.ceil", -1.5, -1.0),
+        ("f32.ceil", 1.25, 2.0),
+        ("f32.floor", -1.5, -2.0),
+        ("f32.floor", 1.75, 1.0),
+        ("f32.trunc", -1.75, -1.0),
+        ("f32.trunc", 1.75, 1.0),
+        ("f32.nearest", 2.5, 2.0),
+        ("f32.nearest", -3.5, -4.0),
+    ];
+    for (name, arg, expected) in f32_tests {
+        let f = instance.get_typed_func::<f32, f32>(&mut store, name)?;
+        assert_eq!(f.call(&mut store, arg)?, expected, "{name}({arg})");
+    }
+
+    let f64_tests: [(&str, f64, f64); 8] = [
+        ("f64.ceil", -1.5, -1.0),
+        ("f64.ceil", 1.25, 2.0),
+        ("f64.floor", -1.5, -2.0),
+        ("f64.floor", 1.75, 1.0),
+        ("f64.trunc", -1.75, -1.0),
+        ("f64.trunc", 1.75, 1.0),
+        ("f64.nearest", 2.5, 2.0),
+        ("f64.nearest", -3.5, -4.0),
+    ];
+    for (name, arg, expected) in f64_tests {
+        let f = instance.get_typed_func::<f64, f64>(&mut store, name)?;
+        assert_eq!(f.call(&mut store, arg)?, expected, "{name}({arg})");
+    }
+
+    // NaNs come back as NaNs.
+    let f = instance.get_typed_func::<f64, f64>(&mut store, "f64.nearest")?;
+    assert!(f.call(&mut store, f64::NAN)?.is_nan());
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn externref_table_ops_are_rejected() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (module
+          (table 1 externref)
+          (func (param i32)
+            (drop (table.get 0 (local.get 0)))))
+    "#;
+    let err = Module::new(&engine, wat).unwrap_err();
+    assert!(
+        format!("{err:?}").contains("extern tables are not supported by Winch"),
+        "unexpected error: {err:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn exceptions_are_rejected() {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch).wasm_exceptions(true);
+    let err = Engine::new(&c).err().unwrap();
+    assert!(
+        format!("{err:?}").contains("feature 'exceptions' is not supported by Winch"),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn host_func_new() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    let engine = Engine::new(&c)?;
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, MODULE)?;
+
+    // `Func::new` requires a host-to-array trampoline, which is compiled
+    // separately from the Winch-compiled module.
+    let ty = FuncType::new([ValType::I32, ValType::I32], [ValType::I32]);
+    let add_fn = Func::new(&mut store, ty, |_, params, results| {
+        results[0] = Val::I32(params[0].unwrap_i32() + params[1].unwrap_i32());
+        Ok(())
+    });
+    let instance = Instance::new(&mut store, &module, &[add_fn.into()])?;
+
+    let call_add = instance.get_typed_func::<(i32, i32), i32>(&mut store, "call_add")?;
+    assert_eq!(call_add.call(&mut store, (41, 1))?, 42);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn component_with_host_import() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    c.wasm_component_model(true);
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (component
+          (import "double" (func $double (param "x" u32) (result u32)))
+          (core func $double_lower (canon lower (func $double)))
+          (core module $m
+            (import "" "double" (func $double (param i32) (result i32)))
+            (func (export "run") (param i32) (result i32)
+              (i32.add
+                (call $double (local.get 0))
+                (i32.const 1))))
+          (core instance $i (instantiate $m
+            (with "" (instance (export "double" (func $double_lower))))))
+          (func (export "run") (param "x" u32) (result u32)
+            (canon lift (core func $i "run"))))
+    "#;
+    let component = component::Component::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let mut linker = component::Linker::new(&engine);
+    linker
+        .root()
+        .func_wrap("double", |_, (x,): (u32,)| Ok((x * 2,)))?;
+    let instance = linker.instantiate(&mut store, &component)?;
+
+    let run = instance.get_typed_func::<(u32,), (u32,)>(&mut store, "run")?;
+    assert_eq!(run.call(&mut store, (20,))?, (41,));
+    run.post_return(&mut store)?;
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg_attr(windows, ignore)]
+fn backtrace_details() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    c.wasm_backtrace_details(WasmBacktraceDetails::Enable);
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (module $hello_mod
+            (func (export "run") (call $hello))
+            (func $hello (unreachable))
+        )
+    "#;
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<(), ()>(&mut store, "run")?;
+    let err = run.call(&mut store, ()).unwrap_err();
+
+    let trace = err.downcast_ref::<WasmBacktrace>().unwrap().frames();
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace[0].module_name(), Some("hello_mod"));
+    assert_eq!(trace[0].func_index(), 1);
+    assert_eq!(trace[0].func_name(), Some("hello"));
+    assert_eq!(trace[0].func_offset(), Some(1));
+    assert_eq!(trace[0].module_offset(), Some(0x26));
+    assert_eq!(trace[1].func_index(), 0);
+    assert!(trace[1].module_offset().is_some());
+    assert_eq!(err.downcast::<Trap>()?, Trap::UnreachableCodeReached);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn debug_info() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    c.debug_info(true);
+    let engine = Engine::new(&c)?;
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, MODULE)?;
+
+    let add_fn = add_fn(store.as_context_mut());
+    let instance = Instance::new(&mut store, &module, &[add_fn.into()])?;
+    let call_add = instance.get_typed_func::<(i32, i32), i32>(&mut store, "call_add")?;
+    assert_eq!(call_add.call(&mut store, (41, 1))?, 42);
+    Ok(())
+}
@@ -0,0 +1,148 @@
+(module
+  (tag $e0)
+  (tag $e1 (param i32))
+  (tag $e2 (param i32 i64))
+
+  (func $throw-if (param i32)
+    (if (local.get 0) (then (throw $e1 (local.get 0)))))
+
+  (func (export "catch-same-function") (result i32)
+    (try (result i32)
+      (do (throw $e1 (i32.const 1)))
+      (catch $e1)))
+
+  (func (export "catch-from-callee") (param i32) (result i32)
+    (try (result i32)
+      (do (call $throw-if (local.get 0)) (i32.const 0))
+      (catch $e1 (i32.add (i32.const 100)))))
+
+  (func (export "catch-multi-value") (result i64)
+    (try (result i64)
+      (do (throw $e2 (i32.const 3) (i64.const 4)) (i64.const 0))
+      (catch $e2 (i64.add (i64.extend_i32_u)))))
+
+  (func (export "catch-all") (result i32)
+    (try (result i32)
+      (do (throw $e0) (i32.const 0))
+      (catch $e1)
+      (catch_all (i32.const 2))))
+
+  (func (export "no-match") (result i32)
+    (try (result i32)
+      (do (throw $e0) (i32.const 0))
+      (catch $e1)))
+
+  (func (export "rethrow") (result i32)
+    (try (result i32)
+      (do
+        (try (result i32)
+          (do (throw $e1 (i32.const 5)))
+          (catch $e1 (drop) (rethrow 0))))
+      (catch $e1 (i32.mul (i32.const 2)))))
+
+  (func (export "delegate") (result i32)
+    (try (result i32)
+      (do
+        (try (result i32)
+          (do (throw $e1 (i32.const 7)))
+          (delegate 0)))
+      (catch $e1 (i32.add (i32.const 1)))))
+
+  (func (export "branch-out-of-catch") (result i32)
+    (block $out (result i32)
+      (try (result i32)
+        (do (throw $e1 (i32.const 9)))
+        (catch $e1 (br $out)))))
+
+  (func (export "catch-in-loop") (param i32) (result i32)
+    (local $sum i32)
+    (loop $l
+      (try
+        (do (throw $e1 (local.get 0)))
+        (catch $e1 (local.set $sum (i32.add (local.get $sum)))))
+      (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+      (br_if $l (local.get 0)))
+    (local.get $sum))
+
+  (func (export "uncaught") (throw $e0))
+)
+
+(assert_return (invoke "catch-same-function") (i32.const 1))
+(assert_return (invoke "catch-from-callee" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "catch-from-callee" (i32.const 1)) (i32.const 101))
+(assert_return (invoke "catch-multi-value") (i64.const 7))
+(assert_return (invoke "catch-all") (i32.const 2))
+(assert_exception (invoke "no-match"))
+(assert_return (invoke "rethrow") (i32.const 10))
+(assert_return (invoke "delegate") (i32.const 8))
+(assert_return (invoke "branch-out-of-catch") (i32.const 9))
+(assert_return (invoke "catch-in-loop" (i32.const 4)) (i32.const 10))
+(assert_exception (invoke "uncaught"))
+
+;; Exceptions propagate across instances through imported tags.
+(module $thrower
+  (tag $e (export "e") (param i32))
+  (func (export "throw") (param i32) (throw $e (local.get 0))))
+(register "thrower" $thrower)
+
+(module
+  (import "thrower" "e" (tag $e (param i32)))
+  (import "thrower" "throw" (func $throw (param i32)))
+  (tag $other (param i32))
+
+  (func (export "catch-imported") (result i32)
+    (try (result i32)
+      (do (call $throw (i32.const 11)) (i32.const 0))
+      (catch $other)
+      (catch $e)))
+)
+
+(assert_return (invoke "catch-imported") (i32.const 11))
+
+;; Exceptions propagate through callers which never throw themselves, however
+;; far away the throw is, including through recursion.
+(module
+  (import "thrower" "e" (tag $e (param i32)))
+  (import "thrower" "throw" (func $throw (param i32)))
+
+  (func $leaf (param i32) (result i32) (i32.add (local.get 0) (i32.const 1)))
+  (func $middle (param i32) (call $throw (call $leaf (local.get 0))))
+  (func $outer (param i32) (call $middle (local.get 0)))
+
+  (func $even (param i32)
+    (if (i32.eqz (local.get 0))
+      (then (call $outer (i32.const 20)))
+      (else (call $odd (i32.sub (local.get 0) (i32.const 1))))))
+  (func $odd (param i32) (call $even (i32.sub (local.get 0) (i32.const 1))))
+
+  (func (export "catch-through-callers") (result i32)
+    (try (result i32)
+      (do (call $outer (i32.const 12)) (i32.const 0))
+      (catch $e)))
+
+  (func (export "catch-through-recursion") (result i32)
+    (try (result i32)
+      (do (call $even (i32.const 6)) (i32.const 0))
+      (catch $e)))
+
+  (func (export "no-throw") (result i32)
+    (try (result i32)
+      (do (call $leaf (i32.const 40)))
+      (catch $e (i32.const 0)))))
+
+(assert_return (invoke "catch-through-callers") (i32.const 13))
+(assert_return (invoke "catch-through-recursion") (i32.const 21))
+(assert_return (invoke "no-throw") (i32.const 41))
+
+;; Traps are not exceptions and are never caught.
+(module
+  (func (export "trap") (result i32)
+    (try (result i32)
+      (do (unreachable))
+      (catch_all (i32.const 0)))))
+
+(assert_trap (invoke "trap") "unreachable")
+
+(assert_unlinkable
+  (module (import "thrower" "e" (tag (param i64))))
+  "incompatible import type")
@@ -0,0 +1,35 @@
+(module
+  (import "spectest" "global_i32" (global $imported i32))
+
+  (global $a i32 (i32.add (global.get $imported) (i32.const 10)))
+  (global $b i64 (i64.mul (i64.const 3) (i64.sub (i64.const 10) (i64.const 3))))
+  (global $c i32 (i32.sub (i32.const 0) (i32.const 1)))
+
+  (memory 1)
+  (data (i32.add (global.get $imported) (i32.mul (i32.const 2) (i32.const 8))) "\2a")
+
+  (table 10 funcref)
+  (elem (i32.sub (i32.const 5) (i32.const 2)) $f)
+  (func $f (result i32) i32.const 7)
+
+  (func (export "a") (result i32) global.get $a)
+  (func (export "b") (result i64) global.get $b)
+  (func (export "c") (result i32) global.get $c)
+  (func (export "load") (param i32) (result i32) (i32.load8_u (local.get 0)))
+  (func (export "call") (param i32) (result i32)
+    (call_indirect (result i32) (local.get 0)))
+)
+
+;; `spectest.global_i32` is 666
+(assert_return (invoke "a") (i32.const 676))
+(assert_return (invoke "b") (i64.const 21))
+(assert_return (invoke "c") (i32.const -1))
+(assert_return (invoke "load" (i32.const 682)) (i32.const 42))
+(assert_return (invoke "call" (i32.const 3)) (i32.const 7))
+
+;; Offsets computed by extended constant expressions are still bounds-checked.
+(assert_trap
+  (module
+    (memory 1)
+    (data (i32.add (i32.const 65535) (i32.const 1)) "\00"))
+  "out of bounds")
@@ -0,0 +1,75 @@
+;; Test `br_table` operator
+
+(module
+  (func (export "singleton") (param i32) (result i32)
+    (block
+      (block
+        (br_table 1 0 (local.get 0))
+        (return (i32.const 21))
+      )
+      (return (i32.const 20))
+    )
+    (i32.const 22)
+  )
+
+  (func (export "multiple") (param i32) (result i32)
+    (block
+      (block
+        (block
+          (block
+            (block
+              (br_table 3 2 1 0 4 (local.get 0))
+              (return (i32.const 99))
+            )
+            (return (i32.const 100))
+          )
+          (return (i32.const 101))
+        )
+        (return (i32.const 102))
+      )
+      (return (i32.const 103))
+    )
+    (i32.const 104)
+  )
+
+  (func (export "value") (param i32) (result i32)
+    (block (result i32)
+      (block (result i32)
+        (br_table 1 0 (i32.const 10) (local.get 0))
+      )
+      (i32.add (i32.const 2))
+    )
+  )
+
+  (func (export "in-loop") (param i32) (result i32)
+    (local i32)
+    (block
+      (loop
+        (local.set 1 (i32.add (local.get 1) (i32.const 1)))
+        (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+        (br_table 1 0 (i32.ge_s (local.get 0) (i32.const 0)))
+      )
+    )
+    (local.get 1)
+  )
+)
+
+(assert_return (invoke "singleton" (i32.const 0)) (i32.const 22))
+(assert_return (invoke "singleton" (i32.const 1)) (i32.const 20))
+(assert_return (invoke "singleton" (i32.const 11)) (i32.const 20))
+(assert_return (invoke "singleton" (i32.const -1)) (i32.const 20))
+
+(assert_return (invoke "multiple" (i32.const 0)) (i32.const 102))
+(assert_return (invoke "multiple" (i32.const 1)) (i32.const 101))
+(assert_return (invoke "multiple" (i32.const 2)) (i32.const 100))
+(assert_return (invoke "multiple" (i32.const 3)) (i32.const 99))
+(assert_return (invoke "multiple" (i32.const 4)) (i32.const 103))
+(assert_return (invoke "multiple" (i32.const 5)) (i32.const 103))
+(assert_return (invoke "multiple" (i32.const -1)) (i32.const 103))
+
+(assert_return (invoke "value" (i32.const 0)) (i32.const 10))
+(assert_return (invoke "value" (i32.const 1)) (i32.const 12))
+(assert_return (invoke "value" (i32.const 100)) (i32.const 12))
+
+(assert_return (invoke "in-loop" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "in-loop" (i32.const 5)) (i32.const 6))
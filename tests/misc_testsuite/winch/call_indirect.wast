@@ -0,0 +1,48 @@
+;; Test `call_indirect` operator
+
+(module
+  (type $i32-i32 (func (param i32) (result i32)))
+  (type $i64-i64 (func (param i64) (result i64)))
+  (type $void-i32 (func (result i32)))
+
+  (table 5 funcref)
+  (elem (i32.const 0) $inc $dec $wide $seven)
+
+  (func $inc (type $i32-i32) (i32.add (local.get 0) (i32.const 1)))
+  (func $dec (type $i32-i32) (i32.sub (local.get 0) (i32.const 1)))
+  (func $wide (type $i64-i64) (i64.add (local.get 0) (i64.const 1)))
+  (func $seven (type $void-i32) (i32.const 7))
+
+  (func (export "dispatch") (param i32 i32) (result i32)
+    (call_indirect (type $i32-i32) (local.get 1) (local.get 0))
+  )
+  (func (export "dispatch-i64") (param i32 i64) (result i64)
+    (call_indirect (type $i64-i64) (local.get 1) (local.get 0))
+  )
+  (func (export "dispatch-void") (param i32) (result i32)
+    (call_indirect (type $void-i32) (local.get 0))
+  )
+  (func (export "dispatch-in-expr") (param i32) (result i32)
+    (i32.add
+      (call_indirect (type $i32-i32) (i32.const 10) (i32.const 0))
+      (call_indirect (type $i32-i32) (local.get 0) (i32.const 1)))
+  )
+)
+
+(assert_return (invoke "dispatch" (i32.const 0) (i32.const 41)) (i32.const 42))
+(assert_return (invoke "dispatch" (i32.const 1) (i32.const 41)) (i32.const 40))
+(assert_return (invoke "dispatch-i64" (i32.const 2) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "dispatch-void" (i32.const 3)) (i32.const 7))
+(assert_return (invoke "dispatch-in-expr" (i32.const 5)) (i32.const 15))
+
+;; Signature mismatch.
+(assert_trap (invoke "dispatch" (i32.const 2) (i32.const 0)) "indirect call type mismatch")
+(assert_trap (invoke "dispatch" (i32.const 3) (i32.const 0)) "indirect call type mismatch")
+(assert_trap (invoke "dispatch-void" (i32.const 0)) "indirect call type mismatch")
+
+;; Null entry.
+(assert_trap (invoke "dispatch" (i32.const 4) (i32.const 0)) "uninitialized element")
+
+;; Out of bounds index.
+(assert_trap (invoke "dispatch" (i32.const 5) (i32.const 0)) "undefined element")
+(assert_trap (invoke "dispatch" (i32.const -1) (i32.const 0)) "undefined element")
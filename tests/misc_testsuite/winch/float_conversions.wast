@@ -0,0 +1,139 @@
+;; Test conversions between integers and floating point values
+
+(module
+  (func (export "i32.trunc_f32_s") (param f32) (result i32) (i32.trunc_f32_s (local.get 0)))
+  (func (export "i32.trunc_f32_u") (param f32) (result i32) (i32.trunc_f32_u (local.get 0)))
+  (func (export "i32.trunc_f64_s") (param f64) (result i32) (i32.trunc_f64_s (local.get 0)))
+  (func (export "i32.trunc_f64_u") (param f64) (result i32) (i32.trunc_f64_u (local.get 0)))
+  (func (export "i64.trunc_f32_s") (param f32) (result i64) (i64.trunc_f32_s (local.get 0)))
+  (func (export "i64.trunc_f32_u") (param f32) (result i64) (i64.trunc_f32_u (local.get 0)))
+  (func (export "i64.trunc_f64_s") (param f64) (result i64) (i64.trunc_f64_s (local.get 0)))
+  (func (export "i64.trunc_f64_u") (param f64) (result i64) (i64.trunc_f64_u (local.get 0)))
+
+  (func (export "i32.trunc_sat_f32_s") (param f32) (result i32) (i32.trunc_sat_f32_s (local.get 0)))
+  (func (export "i32.trunc_sat_f32_u") (param f32) (result i32) (i32.trunc_sat_f32_u (local.get 0)))
+  (func (export "i32.trunc_sat_f64_s") (param f64) (result i32) (i32.trunc_sat_f64_s (local.get 0)))
+  (func (export "i32.trunc_sat_f64_u") (param f64) (result i32) (i32.trunc_sat_f64_u (local.get 0)))
+  (func (export "i64.trunc_sat_f32_s") (param f32) (result i64) (i64.trunc_sat_f32_s (local.get 0)))
+  (func (export "i64.trunc_sat_f32_u") (param f32) (result i64) (i64.trunc_sat_f32_u (local.get 0)))
+  (func (export "i64.trunc_sat_f64_s") (param f64) (result i64) (i64.trunc_sat_f64_s (local.get 0)))
+  (func (export "i64.trunc_sat_f64_u") (param f64) (result i64) (i64.trunc_sat_f64_u (local.get 0)))
+
+  (func (export "f32.convert_i32_s") (param i32) (result f32) (f32.convert_i32_s (local.get 0)))
+  (func (export "f32.convert_i32_u") (param i32) (result f32) (f32.convert_i32_u (local.get 0)))
+  (func (export "f32.convert_i64_s") (param i64) (result f32) (f32.convert_i64_s (local.get 0)))
+  (func (export "f32.convert_i64_u") (param i64) (result f32) (f32.convert_i64_u (local.get 0)))
+  (func (export "f64.convert_i32_s") (param i32) (result f64) (f64.convert_i32_s (local.get 0)))
+  (func (export "f64.convert_i32_u") (param i32) (result f64) (f64.convert_i32_u (local.get 0)))
+  (func (export "f64.convert_i64_s") (param i64) (result f64) (f64.convert_i64_s (local.get 0)))
+  (func (export "f64.convert_i64_u") (param i64) (result f64) (f64.convert_i64_u (local.get 0)))
+
+  (func (export "f32.demote_f64") (param f64) (result f32) (f32.demote_f64 (local.get 0)))
+  (func (export "f64.promote_f32") (param f32) (result f64) (f64.promote_f32 (local.get 0)))
+
+  (func (export "i32.reinterpret_f32") (param f32) (result i32) (i32.reinterpret_f32 (local.get 0)))
+  (func (export "i64.reinterpret_f64") (param f64) (result i64) (i64.reinterpret_f64 (local.get 0)))
+  (func (export "f32.reinterpret_i32") (param i32) (result f32) (f32.reinterpret_i32 (local.get 0)))
+  (func (export "f64.reinterpret_i64") (param i64) (result f64) (f64.reinterpret_i64 (local.get 0)))
+)
+
+;; Trapping truncation.
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -1.9)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -2147483648)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 2147483648)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -2147483904)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const nan)) "invalid conversion to integer")
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 1.9)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const -0.9)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 4294967040)) (i32.const -256))
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 4294967296)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -1)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const nan)) "invalid conversion to integer")
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -2147483648.9)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 2147483647.9)) (i32.const 2147483647))
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -2147483649)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 2147483648)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -nan)) "invalid conversion to integer")
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 4294967295.9)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 2147483648)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 4294967296)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -1)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const nan)) "invalid conversion to integer")
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -9223372036854775808)) (i64.const -9223372036854775808))
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const 9223372036854775808)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const nan)) "invalid conversion to integer")
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 18446742974197923840)) (i64.const -1099511627776))
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const 18446744073709551616)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -1)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const nan)) "invalid conversion to integer")
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -9223372036854775808)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 9223372036854774784)) (i64.const 9223372036854774784))
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const 9223372036854775808)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const -9223372036854777856)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const nan)) "invalid conversion to integer")
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 18446744073709549568)) (i64.const -2048))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 9223372036854775808)) (i64.const -9223372036854775808))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const 18446744073709551616)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -1)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const nan)) "invalid conversion to integer")
+
+;; Saturating truncation.
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -1.9)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 2147483648)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -inf)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const nan)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 1.9)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 4294967040)) (i32.const -256))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 4294967296)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const nan)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 2147483647.9)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 2147483648)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -2147483649)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -nan)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 4294967295.9)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const inf)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -inf)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const nan)) (i32.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 9223372036854775808)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -inf)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const nan)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 18446742974197923840)) (i64.const -1099511627776))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 18446744073709551616)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const nan)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 9223372036854775808)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -9223372036854777856)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const nan)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 18446744073709549568)) (i64.const -2048))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const inf)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0.9)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const nan)) (i64.const 0))
+
+;; Conversions to floating point values.
+(assert_return (invoke "f32.convert_i32_s" (i32.const -1)) (f32.const -1))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 16777217)) (f32.const 16777216))
+(assert_return (invoke "f32.convert_i32_u" (i32.const -1)) (f32.const 4294967296))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0x80000000)) (f32.const 2147483648))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -1)) (f32.const -1))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -1)) (f32.const 18446744073709551616))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 0x8000008000000001)) (f32.const 9223373136366403584))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 1)) (f32.const 1))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -2147483648)) (f64.const -2147483648))
+(assert_return (invoke "f64.convert_i32_u" (i32.const -1)) (f64.const 4294967295))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -9223372036854775808)) (f64.const -9223372036854775808))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -1)) (f64.const 18446744073709551616))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 0x8000000000000401)) (f64.const 9223372036854777856))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 42)) (f64.const 42))
+
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffe0000000p-127)) (f32.const 0x1p-126))
+(assert_return (invoke "f32.demote_f64" (f64.const 1e300)) (f32.const inf))
+(assert_return (invoke "f32.demote_f64" (f64.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1p-149)) (f64.const -0x1p-149))
+(assert_return (invoke "f64.promote_f32" (f32.const nan)) (f64.const nan:canonical))
+
+(assert_return (invoke "i32.reinterpret_f32" (f32.const -0)) (i32.const 0x80000000))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const nan:0x200000)) (i32.const 0x7fa00000))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const -1)) (i64.const 0xbff0000000000000))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 0x7fa00000)) (f32.const nan:0x200000))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 0x8000000000000000)) (f64.const -0))
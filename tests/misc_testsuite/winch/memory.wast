@@ -0,0 +1,106 @@
+;; Test linear memory loads, stores, memory.size and memory.grow
+
+(module
+  (memory 1 3)
+  (data (i32.const 0) "\01\02\03\04\05\06\07\08\ff\fe")
+
+  (func (export "i32.load") (param i32) (result i32) (i32.load (local.get 0)))
+  (func (export "i32.load8_s") (param i32) (result i32) (i32.load8_s (local.get 0)))
+  (func (export "i32.load8_u") (param i32) (result i32) (i32.load8_u (local.get 0)))
+  (func (export "i32.load16_s") (param i32) (result i32) (i32.load16_s (local.get 0)))
+  (func (export "i32.load16_u") (param i32) (result i32) (i32.load16_u (local.get 0)))
+  (func (export "i64.load") (param i32) (result i64) (i64.load (local.get 0)))
+  (func (export "i64.load8_s") (param i32) (result i64) (i64.load8_s (local.get 0)))
+  (func (export "i64.load8_u") (param i32) (result i64) (i64.load8_u (local.get 0)))
+  (func (export "i64.load16_s") (param i32) (result i64) (i64.load16_s (local.get 0)))
+  (func (export "i64.load16_u") (param i32) (result i64) (i64.load16_u (local.get 0)))
+  (func (export "i64.load32_s") (param i32) (result i64) (i64.load32_s (local.get 0)))
+  (func (export "i64.load32_u") (param i32) (result i64) (i64.load32_u (local.get 0)))
+  (func (export "f32.load") (param i32) (result f32) (f32.load (local.get 0)))
+  (func (export "f64.load") (param i32) (result f64) (f64.load (local.get 0)))
+  (func (export "i32.load offset=4") (param i32) (result i32)
+    (i32.load offset=4 (local.get 0)))
+  (func (export "i32.load offset=0xffffffff") (param i32) (result i32)
+    (i32.load offset=0xffffffff (local.get 0)))
+
+  (func (export "i32.store") (param i32 i32) (i32.store (local.get 0) (local.get 1)))
+  (func (export "i32.store8") (param i32 i32) (i32.store8 (local.get 0) (local.get 1)))
+  (func (export "i32.store16") (param i32 i32) (i32.store16 (local.get 0) (local.get 1)))
+  (func (export "i64.store") (param i32 i64) (i64.store (local.get 0) (local.get 1)))
+  (func (export "i64.store8") (param i32 i64) (i64.store8 (local.get 0) (local.get 1)))
+  (func (export "i64.store16") (param i32 i64) (i64.store16 (local.get 0) (local.get 1)))
+  (func (export "i64.store32") (param i32 i64) (i64.store32 (local.get 0) (local.get 1)))
+  (func (export "f32.store") (param i32 f32) (f32.store (local.get 0) (local.get 1)))
+  (func (export "f64.store") (param i32 f64) (f64.store (local.get 0) (local.get 1)))
+  (func (export "i32.store offset=4") (param i32 i32)
+    (i32.store offset=4 (local.get 0) (local.get 1)))
+
+  (func (export "size") (result i32) (memory.size))
+  (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+)
+
+(assert_return (invoke "i32.load" (i32.const 0)) (i32.const 0x04030201))
+(assert_return (invoke "i32.load" (i32.const 6)) (i32.const 0xfeff0807))
+(assert_return (invoke "i32.load offset=4" (i32.const 0)) (i32.const 0x08070605))
+(assert_return (invoke "i32.load8_s" (i32.const 8)) (i32.const -1))
+(assert_return (invoke "i32.load8_u" (i32.const 8)) (i32.const 0xff))
+(assert_return (invoke "i32.load16_s" (i32.const 8)) (i32.const -257))
+(assert_return (invoke "i32.load16_u" (i32.const 8)) (i32.const 0xfeff))
+(assert_return (invoke "i64.load" (i32.const 0)) (i64.const 0x0807060504030201))
+(assert_return (invoke "i64.load8_s" (i32.const 9)) (i64.const -2))
+(assert_return (invoke "i64.load8_u" (i32.const 9)) (i64.const 0xfe))
+(assert_return (invoke "i64.load16_s" (i32.const 8)) (i64.const -257))
+(assert_return (invoke "i64.load16_u" (i32.const 8)) (i64.const 0xfeff))
+(assert_return (invoke "i64.load32_s" (i32.const 6)) (i64.const -16840697))
+(assert_return (invoke "i64.load32_u" (i32.const 6)) (i64.const 0xfeff0807))
+(assert_return (invoke "i32.load" (i32.const 65532)) (i32.const 0))
+
+(assert_return (invoke "i32.store" (i32.const 16) (i32.const 0x11223344)))
+(assert_return (invoke "i32.load" (i32.const 16)) (i32.const 0x11223344))
+(assert_return (invoke "i32.store8" (i32.const 16) (i32.const 0xaabb)))
+(assert_return (invoke "i32.load" (i32.const 16)) (i32.const 0x112233bb))
+(assert_return (invoke "i32.store16" (i32.const 18) (i32.const 0xccddee)))
+(assert_return (invoke "i32.load" (i32.const 16)) (i32.const 0xddee33bb))
+(assert_return (invoke "i64.store" (i32.const 24) (i64.const 0x0102030405060708)))
+(assert_return (invoke "i64.load" (i32.const 24)) (i64.const 0x0102030405060708))
+(assert_return (invoke "i64.store8" (i32.const 24) (i64.const -1)))
+(assert_return (invoke "i64.store16" (i32.const 26) (i64.const 0)))
+(assert_return (invoke "i64.store32" (i32.const 28) (i64.const 0x1122334455667788)))
+(assert_return (invoke "i64.load" (i32.const 24)) (i64.const 0x55667788000007ff))
+(assert_return (invoke "f32.store" (i32.const 32) (f32.const -1.5)))
+(assert_return (invoke "f32.load" (i32.const 32)) (f32.const -1.5))
+(assert_return (invoke "i32.load" (i32.const 32)) (i32.const 0xbfc00000))
+(assert_return (invoke "f64.store" (i32.const 40) (f64.const 0x1.8p+1)))
+(assert_return (invoke "f64.load" (i32.const 40)) (f64.const 0x1.8p+1))
+(assert_return (invoke "i32.store offset=4" (i32.const 48) (i32.const 7)))
+(assert_return (invoke "i32.load" (i32.const 52)) (i32.const 7))
+(assert_return (invoke "i32.store" (i32.const 65532) (i32.const 1)))
+
+(assert_trap (invoke "i32.load" (i32.const 65533)) "out of bounds memory access")
+(assert_trap (invoke "i32.load" (i32.const -1)) "out of bounds memory access")
+(assert_trap (invoke "i32.load offset=4" (i32.const 65530)) "out of bounds memory access")
+(assert_trap (invoke "i32.load offset=0xffffffff" (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "i64.load" (i32.const 65529)) "out of bounds memory access")
+(assert_trap (invoke "i64.load32_u" (i32.const 65533)) "out of bounds memory access")
+(assert_trap (invoke "f64.load" (i32.const 65529)) "out of bounds memory access")
+(assert_trap (invoke "i32.store" (i32.const 65533) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "i32.store8" (i32.const 65536) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "i64.store" (i32.const -8) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "f32.store" (i32.const 65533) (f32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "i32.store offset=4" (i32.const 65532) (i32.const 0)) "out of bounds memory access")
+
+;; A trapping store doesn't write any bytes.
+(assert_return (invoke "i32.load" (i32.const 65532)) (i32.const 1))
+
+(assert_return (invoke "size") (i32.const 1))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "size") (i32.const 2))
+(assert_return (invoke "i32.load" (i32.const 65536)) (i32.const 0))
+(assert_return (invoke "i32.store" (i32.const 131068) (i32.const 2)))
+(assert_return (invoke "i32.load" (i32.const 131068)) (i32.const 2))
+(assert_trap (invoke "i32.load" (i32.const 131069)) "out of bounds memory access")
+(assert_return (invoke "grow" (i32.const 2)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const 0)) (i32.const 2))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 2))
+(assert_return (invoke "size") (i32.const 3))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const -1))
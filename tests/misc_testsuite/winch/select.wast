@@ -0,0 +1,54 @@
+;; Test `select` and `drop` operators
+
+(module
+  (func (export "select-i32") (param i32 i32 i32) (result i32)
+    (select (local.get 0) (local.get 1) (local.get 2))
+  )
+  (func (export "select-i64") (param i64 i64 i32) (result i64)
+    (select (local.get 0) (local.get 1) (local.get 2))
+  )
+  (func (export "select-f32") (param f32 f32 i32) (result f32)
+    (select (local.get 0) (local.get 1) (local.get 2))
+  )
+  (func (export "select-f64") (param f64 f64 i32) (result f64)
+    (select (local.get 0) (local.get 1) (local.get 2))
+  )
+  (func (export "typed-select-f32") (param f32 f32 i32) (result f32)
+    (select (result f32) (local.get 0) (local.get 1) (local.get 2))
+  )
+  (func (export "typed-select-f64") (param f64 f64 i32) (result f64)
+    (select (result f64) (local.get 0) (local.get 1) (local.get 2))
+  )
+
+  (func (export "drop") (param i32) (result i32)
+    (drop (i32.const 1))
+    (drop (i64.const 2))
+    (drop (f32.const 3))
+    (drop (f64.const 4))
+    (local.get 0)
+  )
+  (func (export "drop-param") (param i32 f64) (result i32)
+    (drop (local.get 1))
+    (local.get 0)
+  )
+)
+
+(assert_return (invoke "select-i32" (i32.const 1) (i32.const 2) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "select-i32" (i32.const 1) (i32.const 2) (i32.const 0)) (i32.const 2))
+(assert_return (invoke "select-i32" (i32.const 1) (i32.const 2) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "select-i64" (i64.const 2) (i64.const 1) (i32.const 1)) (i64.const 2))
+(assert_return (invoke "select-i64" (i64.const 2) (i64.const 1) (i32.const 0)) (i64.const 1))
+(assert_return (invoke "select-i64" (i64.const -1) (i64.const 1) (i32.const 7)) (i64.const -1))
+(assert_return (invoke "select-f32" (f32.const 1) (f32.const 2) (i32.const 1)) (f32.const 1))
+(assert_return (invoke "select-f32" (f32.const 1) (f32.const 2) (i32.const 0)) (f32.const 2))
+(assert_return (invoke "select-f32" (f32.const nan) (f32.const 2) (i32.const 1)) (f32.const nan))
+(assert_return (invoke "select-f64" (f64.const 1) (f64.const 2) (i32.const 1)) (f64.const 1))
+(assert_return (invoke "select-f64" (f64.const 1) (f64.const 2) (i32.const 0)) (f64.const 2))
+(assert_return (invoke "select-f64" (f64.const -0) (f64.const nan) (i32.const 0)) (f64.const nan))
+(assert_return (invoke "typed-select-f32" (f32.const 1) (f32.const 2) (i32.const 1)) (f32.const 1))
+(assert_return (invoke "typed-select-f32" (f32.const 1) (f32.const 2) (i32.const 0)) (f32.const 2))
+(assert_return (invoke "typed-select-f64" (f64.const 1) (f64.const 2) (i32.const 1)) (f64.const 1))
+(assert_return (invoke "typed-select-f64" (f64.const 1) (f64.const 2) (i32.const 0)) (f64.const 2))
+
+(assert_return (invoke "drop" (i32.const 7)) (i32.const 7))
+(assert_return (invoke "drop-param" (i32.const 8) (f64.const 1.5)) (i32.const 8))
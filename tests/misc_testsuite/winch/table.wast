@@ -0,0 +1,69 @@
+;; Test `table.get`, `table.set`, `table.grow`, `table.size` and `ref.func`
+;; operators on funcref tables
+
+(module
+  (type $t (func (result i32)))
+
+  (table $t1 3 10 funcref)
+  (elem (table $t1) (i32.const 0) func $one $two)
+
+  (func $one (type $t) (i32.const 1))
+  (func $two (type $t) (i32.const 2))
+  (func $three (type $t) (i32.const 3))
+  (elem declare func $three)
+
+  (func (export "size") (result i32)
+    (table.size $t1)
+  )
+
+  (func (export "call") (param i32) (result i32)
+    (call_indirect $t1 (type $t) (local.get 0))
+  )
+
+  ;; Reads the lazily initialized entry at `src` and writes it to `dst`.
+  (func (export "copy") (param i32 i32)
+    (table.set $t1 (local.get 1) (table.get $t1 (local.get 0)))
+  )
+
+  (func (export "set-three") (param i32)
+    (table.set $t1 (local.get 0) (ref.func $three))
+  )
+
+  (func (export "grow-three") (param i32) (result i32)
+    (table.grow $t1 (ref.func $three) (local.get 0))
+  )
+
+  ;; Grows the table with the entry at `src`.
+  (func (export "grow-from") (param i32 i32) (result i32)
+    (table.grow $t1 (table.get $t1 (local.get 0)) (local.get 1))
+  )
+)
+
+(assert_return (invoke "size") (i32.const 3))
+(assert_return (invoke "call" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "call" (i32.const 1)) (i32.const 2))
+(assert_trap (invoke "call" (i32.const 2)) "uninitialized element")
+
+;; `table.get` of an element segment entry that hasn't been called yet.
+(invoke "copy" (i32.const 1) (i32.const 2))
+(assert_return (invoke "call" (i32.const 2)) (i32.const 2))
+
+(invoke "set-three" (i32.const 0))
+(assert_return (invoke "call" (i32.const 0)) (i32.const 3))
+
+(assert_trap (invoke "copy" (i32.const 3) (i32.const 0)) "out of bounds table access")
+(assert_trap (invoke "copy" (i32.const 0) (i32.const 3)) "out of bounds table access")
+(assert_trap (invoke "set-three" (i32.const -1)) "out of bounds table access")
+
+(assert_return (invoke "grow-three" (i32.const 2)) (i32.const 3))
+(assert_return (invoke "size") (i32.const 5))
+(assert_return (invoke "call" (i32.const 3)) (i32.const 3))
+(assert_return (invoke "call" (i32.const 4)) (i32.const 3))
+
+(assert_return (invoke "grow-from" (i32.const 1) (i32.const 1)) (i32.const 5))
+(assert_return (invoke "size") (i32.const 6))
+(assert_return (invoke "call" (i32.const 5)) (i32.const 2))
+
+(assert_return (invoke "grow-three" (i32.const 0)) (i32.const 6))
+(assert_return (invoke "grow-three" (i32.const 5)) (i32.const -1))
+(assert_return (invoke "size") (i32.const 6))
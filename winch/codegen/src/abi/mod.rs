@@ -221,6 +221,11 @@ impl ABISig {
             stack_bytes,
         }
     }
+
+    /// Returns the registers used to pass parameters.
+    pub fn param_regs(&self) -> SmallVec<[Reg; 6]> {
+        self.params.iter().filter_map(|p| p.get_reg()).collect()
+    }
 }
 
 /// Returns the size in bytes of a given WebAssembly type.
@@ -228,6 +233,9 @@ pub(crate) fn ty_size(ty: &WasmType) -> u32 {
     match *ty {
         WasmType::I32 | WasmType::F32 => 4,
         WasmType::I64 | WasmType::F64 => 8,
+        // FIXME Remove hardcoded size of references, this will be
+        // needed once 32-bit architectures are supported.
+        WasmType::Ref(_) => 8,
         _ => panic!(),
     }
 }
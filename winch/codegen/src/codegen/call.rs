@@ -6,6 +6,7 @@ use crate::{
     masm::{CalleeKind, MacroAssembler, OperandSize},
     reg::Reg,
 };
+use cranelift_codegen::ir::LibCall;
 use wasmtime_environ::FuncIndex;
 
 /// All the information needed to emit a function call.
@@ -163,6 +164,37 @@ impl<'a> FnCall<'a> {
         self.post_call::<M>(masm, context, reserved_stack);
     }
 
+    /// Emit a call to a library function.
+    pub fn lib<M: MacroAssembler>(&self, masm: &mut M, context: &mut CodeGenContext, lib: LibCall) {
+        let reserved_stack = masm.call(self.arg_stack_space, |masm| {
+            self.assign_args(context, masm, <M::ABI as ABI>::scratch_reg());
+            CalleeKind::LibCall(lib)
+        });
+        self.post_call::<M>(masm, context, reserved_stack);
+    }
+
+    /// Emit a call to a builtin function.
+    ///
+    /// The callee address is loaded from the array of builtin
+    /// functions, located at `builtins`, at the given `offset`.
+    pub fn builtin<M: MacroAssembler>(
+        &self,
+        masm: &mut M,
+        context: &mut CodeGenContext,
+        builtins: M::Address,
+        offset: u32,
+    ) {
+        let reserved_stack = masm.call(self.arg_stack_space, |masm| {
+            let scratch = <M::ABI as ABI>::scratch_reg();
+            self.assign_args(context, masm, scratch);
+            masm.load(builtins, scratch, OperandSize::S64);
+            let addr = masm.address_at_reg(scratch, offset);
+            masm.load(addr, scratch, OperandSize::S64);
+            CalleeKind::Indirect(scratch)
+        });
+        self.post_call::<M>(masm, context, reserved_stack);
+    }
+
     fn post_call<M: MacroAssembler>(&self, masm: &mut M, context: &mut CodeGenContext, size: u32) {
         masm.free_stack(self.call_stack_space + size);
         context.drop_last(self.abi_sig.params.len());
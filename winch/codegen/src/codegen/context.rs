@@ -61,7 +61,7 @@ impl<'a> CodeGenContext<'a> {
     pub fn reg_for_type<M: MacroAssembler>(&mut self, ty: WasmType, masm: &mut M) -> Reg {
         use WasmType::*;
         match ty {
-            I32 | I64 => self.reg_for_class(RegClass::Int, masm),
+            I32 | I64 | Ref(_) => self.reg_for_class(RegClass::Int, masm),
             F32 | F64 => self.reg_for_class(RegClass::Float, masm),
             t => panic!("unsupported type {:?}", t),
         }
@@ -102,6 +102,27 @@ impl<'a> CodeGenContext<'a> {
         result
     }
 
+    /// Executes the provided function, guaranteeing that the given
+    /// registers remain unallocatable throughout the function's
+    /// execution.
+    pub fn without_regs<T, M, F>(&mut self, regs: &[Reg], masm: &mut M, mut f: F) -> T
+    where
+        M: MacroAssembler,
+        F: FnMut(&mut Self, &mut M) -> T,
+    {
+        for reg in regs {
+            self.reg(*reg, masm);
+        }
+
+        let result = f(self, masm);
+
+        for reg in regs {
+            self.free_reg(*reg);
+        }
+
+        result
+    }
+
     /// Free the given register.
     pub fn free_reg(&mut self, reg: impl Into<Reg>) {
         let reg: Reg = reg.into();
@@ -240,6 +261,52 @@ impl<'a> CodeGenContext<'a> {
         }
     }
 
+    /// Prepares arguments for emitting a floating point binary operation.
+    pub fn float_binop<F, M>(&mut self, masm: &mut M, size: OperandSize, mut emit: F)
+    where
+        F: FnMut(&mut M, Reg, Reg, OperandSize),
+        M: MacroAssembler,
+    {
+        let src = self.pop_to_reg(masm, None);
+        let dst = self.pop_to_reg(masm, None);
+        emit(masm, dst.reg, src.reg, size);
+        self.free_reg(src);
+        self.stack.push(dst.into());
+    }
+
+    /// Prepares arguments for emitting a floating point comparison.
+    /// The closure receives the destination integer register followed
+    /// by the left and right operands of the comparison.
+    pub fn float_cmp_op<F, M>(&mut self, masm: &mut M, size: OperandSize, mut emit: F)
+    where
+        F: FnMut(&mut M, Reg, Reg, Reg, OperandSize),
+        M: MacroAssembler,
+    {
+        let rhs = self.pop_to_reg(masm, None);
+        let lhs = self.pop_to_reg(masm, None);
+        let dst = self.any_gpr(masm);
+        emit(masm, dst, lhs.reg, rhs.reg, size);
+        self.free_reg(lhs);
+        self.free_reg(rhs);
+        self.stack.push(Val::reg(dst, WasmType::I32));
+    }
+
+    /// Prepares arguments for emitting a conversion of the value at the
+    /// top of the stack to the given type. The closure receives the
+    /// destination and source registers and the size of the destination
+    /// type.
+    pub fn convert_op<F, M>(&mut self, masm: &mut M, dst_ty: WasmType, mut emit: F)
+    where
+        F: FnMut(&mut M, Reg, Reg, OperandSize),
+        M: MacroAssembler,
+    {
+        let src = self.pop_to_reg(masm, None);
+        let dst = self.reg_for_type(dst_ty, masm);
+        emit(masm, dst, src.reg, dst_ty.into());
+        self.free_reg(src);
+        self.stack.push(Val::reg(dst, dst_ty));
+    }
+
     /// Saves any live registers in the value stack in a particular
     /// range defined by the caller.  This is a specialization of the
     /// spill function; made available for cases in which spilling
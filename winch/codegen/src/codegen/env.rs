@@ -1,8 +1,9 @@
+use crate::masm::OperandSize;
 use smallvec::{smallvec, SmallVec};
 use wasmparser::BlockType;
 use wasmtime_environ::{
-    FuncIndex, GlobalIndex, ModuleTranslation, PtrSize, TypeConvert, VMOffsets, WasmFuncType,
-    WasmType,
+    FuncIndex, GlobalIndex, MemoryIndex, MemoryStyle, ModuleTranslation, PtrSize, TableIndex,
+    Tunables, TypeConvert, TypeIndex, VMOffsets, WasmFuncType, WasmType, WASM_PAGE_SIZE,
 };
 
 /// The function environment.
@@ -14,15 +15,19 @@ pub struct FuncEnv<'a, P> {
     pub vmoffsets: VMOffsets<P>,
     /// Metadata about the translation process of a WebAssembly module.
     pub translation: &'a ModuleTranslation<'a>,
+    /// Tunable parameters, used to determine whether fuel metering or
+    /// epoch interruption checks must be emitted.
+    pub tunables: &'a Tunables,
 }
 
 impl<'a, P: PtrSize> FuncEnv<'a, P> {
     /// Create a new function environment.
-    pub fn new(ptr: P, translation: &'a ModuleTranslation) -> Self {
+    pub fn new(ptr: P, translation: &'a ModuleTranslation, tunables: &'a Tunables) -> Self {
         let vmoffsets = VMOffsets::new(ptr, &translation.module);
         Self {
             vmoffsets,
             translation,
+            tunables,
         }
     }
 
@@ -40,6 +45,13 @@ impl<'a, P: PtrSize> FuncEnv<'a, P> {
         }
     }
 
+    /// Resolves the function type at the given type index.
+    pub fn resolve_func_type(&self, idx: TypeIndex) -> WasmFuncType {
+        let types = &self.translation.get_types();
+        let ty = types[types.core_type_at(idx.as_u32())].unwrap_func();
+        self.translation.module.convert_func_type(ty)
+    }
+
     /// Resolves the type of the block in terms of [`wasmtime_environ::WasmType`].
     pub fn resolve_block_type(&self, blockty: BlockType) -> SmallVec<[WasmType; 1]> {
         use BlockType::*;
@@ -60,6 +72,149 @@ impl<'a, P: PtrSize> FuncEnv<'a, P> {
 
         (ty, offset)
     }
+
+    /// Resolves the [`TableData`] of the table at the given index.
+    pub(crate) fn resolve_table_data(&self, index: TableIndex) -> TableData {
+        let module = &self.translation.module;
+        let (import_from, offset, current_elems_offset) = match module.defined_table_index(index) {
+            Some(defined) => (
+                None,
+                self.vmoffsets.vmctx_vmtable_definition_base(defined),
+                self.vmoffsets
+                    .vmctx_vmtable_definition_current_elements(defined),
+            ),
+            None => (
+                Some(self.vmoffsets.vmctx_vmtable_import_from(index)),
+                u32::from(self.vmoffsets.vmtable_definition_base()),
+                u32::from(self.vmoffsets.vmtable_definition_current_elements()),
+            ),
+        };
+
+        TableData {
+            offset,
+            current_elems_offset,
+            import_from,
+            element_size: OperandSize::from_bytes(self.vmoffsets.ptr.size()),
+            current_elements_size: OperandSize::from_bytes(
+                self.vmoffsets.size_of_vmtable_definition_current_elements(),
+            ),
+        }
+    }
+
+    /// Resolves the [`HeapData`] of the linear memory at the given index.
+    pub fn resolve_heap(&self, index: MemoryIndex) -> HeapData {
+        let module = &self.translation.module;
+        let plan = &module.memory_plans[index];
+        let ptr = &self.vmoffsets.ptr;
+
+        // Owned memories keep their `VMMemoryDefinition` inline in the
+        // `VMContext`; imported and shared memories are reached through a
+        // pointer to it.
+        let (import_from, offset, current_length_offset) = match module.defined_memory_index(index)
+        {
+            Some(defined) if !plan.memory.shared => {
+                let owned = module.owned_memory_index(defined);
+                (
+                    None,
+                    self.vmoffsets.vmctx_vmmemory_definition_base(owned),
+                    self.vmoffsets
+                        .vmctx_vmmemory_definition_current_length(owned),
+                )
+            }
+            Some(defined) => (
+                Some(self.vmoffsets.vmctx_vmmemory_pointer(defined)),
+                u32::from(ptr.vmmemory_definition_base()),
+                u32::from(ptr.vmmemory_definition_current_length()),
+            ),
+            None => (
+                Some(self.vmoffsets.vmctx_vmmemory_import_from(index)),
+                u32::from(ptr.vmmemory_definition_base()),
+                u32::from(ptr.vmmemory_definition_current_length()),
+            ),
+        };
+
+        let style = match plan.style {
+            MemoryStyle::Static { bound } => HeapStyle::Static {
+                bound: bound * u64::from(WASM_PAGE_SIZE),
+            },
+            MemoryStyle::Dynamic { .. } => HeapStyle::Dynamic,
+        };
+
+        HeapData {
+            offset,
+            current_length_offset,
+            import_from,
+            ty: if plan.memory.memory64 {
+                WasmType::I64
+            } else {
+                WasmType::I32
+            },
+            style,
+            offset_guard_size: plan.offset_guard_size,
+        }
+    }
+}
+
+/// Metadata about a table, used by the code generation to emit
+/// accesses to its elements.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct TableData {
+    /// The offset of the base pointer of the table's elements.
+    ///
+    /// Relative to the `VMContext` for defined tables, or relative to
+    /// the `VMTableDefinition` pointed to by `import_from` otherwise.
+    pub offset: u32,
+    /// The offset of the current number of elements of the table,
+    /// relative to the same location as `offset`.
+    pub current_elems_offset: u32,
+    /// The offset, relative to the `VMContext`, of the pointer to the
+    /// table's `VMTableDefinition`, for imported tables.
+    pub import_from: Option<u32>,
+    /// The size of each of the table's elements.
+    pub element_size: OperandSize,
+    /// The size of the current number of elements of the table.
+    pub current_elements_size: OperandSize,
+}
+
+/// The bounds checking strategy of a linear memory.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HeapStyle {
+    /// The heap reserves a fixed amount of address space up front,
+    /// followed by an offset guard region; accesses that land in the
+    /// unmapped part of the reservation fault and are turned into
+    /// traps.
+    Static {
+        /// The size, in bytes, of the reserved address space, excluding
+        /// the offset guard region.
+        bound: u64,
+    },
+    /// The heap can be resized and moved, every access is explicitly
+    /// checked against its current length.
+    Dynamic,
+}
+
+/// Metadata about a linear memory, used by the code generation to
+/// emit loads, stores and bounds checks.
+#[derive(Debug, Copy, Clone)]
+pub struct HeapData {
+    /// The offset of the base pointer of the heap.
+    ///
+    /// Relative to the `VMContext` for owned memories, or relative to
+    /// the `VMMemoryDefinition` pointed to by `import_from` otherwise.
+    pub offset: u32,
+    /// The offset of the current length of the heap, relative to the
+    /// same location as `offset`.
+    pub current_length_offset: u32,
+    /// The offset, relative to the `VMContext`, of the pointer to the
+    /// heap's `VMMemoryDefinition`, for imported and shared memories.
+    pub import_from: Option<u32>,
+    /// The type of the heap's index, [`WasmType::I32`] or
+    /// [`WasmType::I64`] for 64-bit memories.
+    pub ty: WasmType,
+    /// The bounds checking strategy of the heap.
+    pub style: HeapStyle,
+    /// The size, in bytes, of the offset guard region.
+    pub offset_guard_size: u64,
 }
 
 /// Metadata about a function callee.  Use by the code generation
@@ -1,14 +1,24 @@
 use crate::{
     abi::{ABISig, ABI},
-    masm::{MacroAssembler, OperandSize},
-    stack::TypedReg,
+    isa::reg::Reg,
+    masm::{CmpKind, ExtendKind, MacroAssembler, OperandSize, RegImm, ShiftKind},
+    stack::{TypedReg, Val},
     CallingConvention,
 };
-use anyhow::Result;
+use anyhow::{bail, Result};
 use call::FnCall;
+use cranelift_codegen::{
+    binemit::CodeOffset,
+    ir::{RelSourceLoc, SourceLoc, TrapCode},
+};
 use smallvec::SmallVec;
-use wasmparser::{BinaryReader, FuncValidator, Operator, ValidatorResources, VisitOperator};
-use wasmtime_environ::{FuncIndex, WasmFuncType, WasmType};
+use wasmparser::{
+    BinaryReader, FuncValidator, MemArg, Operator, ValidatorResources, VisitOperator,
+};
+use wasmtime_environ::{
+    BuiltinFunctionIndex, FuncIndex, MemoryIndex, PtrSize, TableIndex, TableStyle, TypeIndex,
+    WasmFuncType, WasmHeapType, WasmRefType, WasmType, FUNCREF_INIT_BIT, FUNCREF_MASK,
+};
 
 mod context;
 pub(crate) use context::*;
@@ -18,6 +28,19 @@ pub mod call;
 mod control;
 pub(crate) use control::*;
 
+/// Tracks the source locations of the operators visited during code
+/// generation, used to map native code offsets back to Wasm offsets.
+#[derive(Default)]
+pub(crate) struct SourceLocation {
+    /// The base source location of the function, i.e. the offset of
+    /// its first operator. Every other source location is relative to
+    /// it.
+    pub base: Option<SourceLoc>,
+    /// The code offset at which the current operator's source location
+    /// starts.
+    pub start: CodeOffset,
+}
+
 /// The code generation abstraction.
 pub(crate) struct CodeGen<'a, M>
 where
@@ -39,6 +62,14 @@ where
     // NB The 64 is set arbitrarily, we can adjust it as
     // we see fit.
     pub control_frames: SmallVec<[ControlStackFrame; 64]>,
+
+    /// Source location tracking.
+    pub source_location: SourceLocation,
+
+    /// The amount of fuel consumed by the operators visited since the
+    /// last time fuel was flushed to the `VMRuntimeLimits`. Only used
+    /// when fuel metering is enabled.
+    fuel_consumed: i64,
 }
 
 impl<'a, M> CodeGen<'a, M>
@@ -57,6 +88,10 @@ where
             masm,
             env,
             control_frames: Default::default(),
+            source_location: Default::default(),
+            // Every function consumes at least one unit of fuel, to
+            // account for its invocation.
+            fuel_consumed: 1,
         }
     }
 
@@ -173,14 +208,22 @@ where
             OperandSize::S64,
         );
 
+        // Check for fuel exhaustion and epoch changes on function entry.
+        self.maybe_emit_fuel_check();
+        self.maybe_emit_epoch_check();
+
         while !body.eof() {
             let offset = body.original_position();
-            body.visit_operator(&mut ValidateThenVisit(validator.visitor(offset), self))??;
+            body.visit_operator(&mut ValidateThenVisit(
+                validator.visitor(offset),
+                self,
+                offset,
+            ))??;
         }
         validator.finish(body.original_position())?;
         return Ok(());
 
-        struct ValidateThenVisit<'a, T, U>(T, &'a mut U);
+        struct ValidateThenVisit<'a, T, U>(T, &'a mut U, usize);
 
         macro_rules! validate_then_visit {
             ($( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident)*) => {
@@ -192,8 +235,14 @@ where
                         // ignored except for If, Block, Loop, Else and End. These operators need
                         // to be observed in order to keep the control stack frames balanced and to
                         // determine if reachability should be restored.
-                        let visit_when_unreachable = visit_op_when_unreachable(Operator::$op $({ $($arg: $arg.clone()),* })?);
-                        if self.1.is_reachable() || visit_when_unreachable  {
+                        let op = Operator::$op $({ $($arg: $arg.clone()),* })?;
+                        if self.1.is_reachable() {
+                            self.1.check_supported(&op)?;
+                            self.1.before_visit_op(&op, self.2);
+                            let res = self.1.$visit($($($arg),*)?);
+                            self.1.after_visit_op();
+                            Ok(res)
+                        } else if visit_op_when_unreachable(&op) {
                             Ok(self.1.$visit($($($arg),*)?))
                         } else {
                             Ok(U::Output::default())
@@ -203,7 +252,7 @@ where
             };
         }
 
-        fn visit_op_when_unreachable(op: Operator) -> bool {
+        fn visit_op_when_unreachable(op: &Operator) -> bool {
             use Operator::*;
             match op {
                 If { .. } | Block { .. } | Loop { .. } | Else | End => true,
@@ -211,22 +260,75 @@ where
             }
         }
 
-        /// Trait to handle reachability state.
-        trait ReachableState {
+        /// Trait to handle reachability state and to hook into the
+        /// visit of each reachable operator.
+        trait VisitorHooks {
             /// Returns true if the current state of the program is reachable.
             fn is_reachable(&self) -> bool;
+            /// Returns an error if the operator is valid but can't be
+            /// compiled.
+            fn check_supported(&self, op: &Operator) -> Result<()>;
+            /// Invoked before visiting a reachable operator, located
+            /// at the given offset in the module.
+            fn before_visit_op(&mut self, op: &Operator, offset: usize);
+            /// Invoked after visiting a reachable operator.
+            fn after_visit_op(&mut self);
         }
 
-        impl<'a, M: MacroAssembler> ReachableState for CodeGen<'a, M> {
+        impl<'a, M: MacroAssembler> VisitorHooks for CodeGen<'a, M> {
             fn is_reachable(&self) -> bool {
                 self.context.reachable
             }
+
+            fn check_supported(&self, op: &Operator) -> Result<()> {
+                use Operator::*;
+                let table = match op {
+                    TableGet { table } | TableSet { table } | TableGrow { table } => *table,
+                    _ => return Ok(()),
+                };
+                match self.table_heap_type(TableIndex::from_u32(table)) {
+                    WasmHeapType::Func | WasmHeapType::TypedFunc(_) => Ok(()),
+                    ty => bail!("{ty} tables are not supported by Winch"),
+                }
+            }
+
+            fn before_visit_op(&mut self, op: &Operator, offset: usize) {
+                // Attribute all the code emitted for the operator,
+                // including any fuel accounting, to its offset.
+                let loc = SourceLoc::new(offset as u32);
+                let base = *self.source_location.base.get_or_insert(loc);
+                self.source_location.start = self
+                    .masm
+                    .start_source_loc(RelSourceLoc::from_base_offset(base, loc));
+
+                if self.env.tunables.consume_fuel {
+                    self.fuel_before_visit_op(op);
+                }
+            }
+
+            fn after_visit_op(&mut self) {
+                // Code is emitted in a single pass, and the machine buffer
+                // may remove a branch emitted before this operator when a
+                // label is bound right after it, leaving the current offset
+                // before the start of the source location. In that case
+                // nothing emitted for the operator is left to attribute.
+                if self.masm.current_code_offset() >= self.source_location.start {
+                    self.masm.end_source_loc();
+                }
+
+                // Any fuel accounted for after the last flush belongs
+                // to code that can't continue executing, so it's
+                // discarded once the code becomes unreachable.
+                if !self.context.reachable {
+                    self.fuel_consumed = 0;
+                }
+            }
         }
 
         impl<'a, T, U> VisitOperator<'a> for ValidateThenVisit<'_, T, U>
         where
             T: VisitOperator<'a, Output = wasmparser::Result<()>>,
-            U: VisitOperator<'a> + ReachableState,
+            U: VisitOperator<'a> + VisitorHooks,
             U::Output: Default,
         {
             type Output = Result<U::Output>;
@@ -282,6 +384,726 @@ where
         }
     }
 
+    /// Loads the field located at `offset` within the
+    /// `VMMemoryDefinition` of the given heap into `dst`.
+    pub fn emit_load_heap_field(&mut self, heap: &HeapData, offset: u32, dst: Reg) {
+        let vmctx = <M::ABI as ABI>::vmctx_reg();
+        // FIXME Remove harcoded operand size, this will be needed
+        // once 32-bit architectures are supported.
+        let base = if let Some(import_from) = heap.import_from {
+            let addr = self.masm.address_at_reg(vmctx, import_from);
+            self.masm.load(addr, dst, OperandSize::S64);
+            dst
+        } else {
+            vmctx
+        };
+        let addr = self.masm.address_at_reg(base, offset);
+        self.masm.load(addr, dst, OperandSize::S64);
+    }
+
+    /// Pops the index of a linear memory access from the value stack
+    /// and emits the instruction sequence to compute its effective
+    /// address, including any needed bounds checks.
+    ///
+    /// Returns the register holding the effective address, or `None`
+    /// if the access is statically known to be out of bounds, in which
+    /// case an unconditional trap is emitted and the rest of the
+    /// current block is considered unreachable.
+    pub fn emit_compute_heap_address(
+        &mut self,
+        memarg: &MemArg,
+        access_size: OperandSize,
+    ) -> Option<Reg> {
+        let heap = self.env.resolve_heap(MemoryIndex::from_u32(memarg.memory));
+        let index = self.context.pop_to_reg(self.masm, None);
+        let offset_with_access_size = memarg
+            .offset
+            .checked_add(u64::from(access_size.bytes()))
+            .filter(|v| match heap.style {
+                HeapStyle::Static { bound } => *v <= bound,
+                HeapStyle::Dynamic => true,
+            });
+
+        let offset_with_access_size = match offset_with_access_size {
+            Some(v) => v,
+            None => {
+                // The access can never be in bounds, regardless of the
+                // value of the index.
+                self.context.free_reg(index);
+                self.emit_trap(TrapCode::HeapOutOfBounds);
+                return None;
+            }
+        };
+
+        // Bounds checks are performed against the index extended to
+        // the pointer width.
+        if heap.ty == WasmType::I32 {
+            self.masm
+                .extend(index.reg, index.reg, ExtendKind::I64ExtendI32U);
+        }
+
+        match heap.style {
+            // Any 32-bit index, plus the static offset and access size,
+            // lands either in the reserved bound or in the offset guard
+            // region, so the bounds check can be elided entirely.
+            HeapStyle::Static { bound }
+                if heap.ty == WasmType::I32
+                    && u64::from(u32::MAX)
+                        <= bound + heap.offset_guard_size - offset_with_access_size => {}
+            // index + offset + access_size <= bound
+            // becomes
+            // index <= bound - (offset + access_size).
+            HeapStyle::Static { bound } => {
+                let adjusted_bound = bound - offset_with_access_size;
+                self.masm.cmp(
+                    RegImm::i64(adjusted_bound as i64),
+                    index.reg,
+                    OperandSize::S64,
+                );
+                self.masm.trapif(CmpKind::GtU, TrapCode::HeapOutOfBounds);
+            }
+            // index + offset + access_size <= current_length.
+            HeapStyle::Dynamic => {
+                let bound = self.context.any_gpr(self.masm);
+                self.emit_load_heap_field(&heap, heap.current_length_offset, bound);
+                let end = self.context.any_gpr(self.masm);
+                self.masm
+                    .mov(index.reg.into(), end.into(), OperandSize::S64);
+                self.masm.checked_uadd(
+                    end,
+                    end,
+                    RegImm::i64(offset_with_access_size as i64),
+                    OperandSize::S64,
+                    TrapCode::HeapOutOfBounds,
+                );
+                self.masm.cmp(bound.into(), end, OperandSize::S64);
+                self.masm.trapif(CmpKind::GtU, TrapCode::HeapOutOfBounds);
+                self.context.free_reg(end);
+                self.context.free_reg(bound);
+            }
+        }
+
+        let addr = self.context.any_gpr(self.masm);
+        self.emit_load_heap_field(&heap, heap.offset, addr);
+        self.masm
+            .add(addr.into(), addr.into(), index.reg.into(), OperandSize::S64);
+        self.context.free_reg(index);
+
+        if memarg.offset != 0 {
+            self.masm.add(
+                addr.into(),
+                addr.into(),
+                RegImm::i64(memarg.offset as i64),
+                OperandSize::S64,
+            );
+        }
+
+        Some(addr)
+    }
+
+    /// Emits a WebAssembly load of the given type and size, optionally
+    /// sign extending the loaded value.
+    pub fn emit_wasm_load(
+        &mut self,
+        memarg: &MemArg,
+        ty: WasmType,
+        size: OperandSize,
+        kind: Option<ExtendKind>,
+    ) {
+        if let Some(addr) = self.emit_compute_heap_address(memarg, size) {
+            let dst = self.context.reg_for_type(ty, self.masm);
+            let src = self.masm.address_at_reg(addr, 0);
+            self.masm.wasm_load(src, dst, size, kind);
+            self.context.free_reg(addr);
+            self.context.stack.push(TypedReg::new(ty, dst).into());
+        }
+    }
+
+    /// Emits a WebAssembly store of the given size.
+    pub fn emit_wasm_store(&mut self, memarg: &MemArg, size: OperandSize) {
+        let src = self.context.pop_to_reg(self.masm, None);
+        if let Some(addr) = self.emit_compute_heap_address(memarg, size) {
+            let dst = self.masm.address_at_reg(addr, 0);
+            self.masm.wasm_store(src.reg, dst, size);
+            self.context.free_reg(addr);
+        }
+        self.context.free_reg(src);
+    }
+
+    /// Emits the `memory.size` instruction sequence.
+    pub fn emit_memory_size(&mut self, mem: u32) {
+        let heap = self.env.resolve_heap(MemoryIndex::from_u32(mem));
+        let dst = self.context.any_gpr(self.masm);
+        self.emit_load_heap_field(&heap, heap.current_length_offset, dst);
+        // Convert the current length in bytes to pages. The shift is
+        // performed at 64 bits, given that the length of a 32-bit
+        // memory might not fit in 32 bits.
+        self.context.stack.push(TypedReg::i64(dst).into());
+        self.context.stack.push(Val::i64(16));
+        self.masm
+            .shift(&mut self.context, ShiftKind::ShrU, OperandSize::S64);
+        self.retype_stack_top(heap.ty);
+    }
+
+    /// Emits the `memory.grow` instruction sequence, by calling the
+    /// corresponding builtin function.
+    pub fn emit_memory_grow(&mut self, mem: u32) {
+        use WasmType::*;
+        let heap = self.env.resolve_heap(MemoryIndex::from_u32(mem));
+
+        // The builtin expects the delta as a 64-bit value.
+        if heap.ty == I32 {
+            let delta = self.context.pop_to_reg(self.masm, None);
+            self.masm
+                .extend(delta.reg, delta.reg, ExtendKind::I64ExtendI32U);
+            self.context.stack.push(TypedReg::i64(delta.reg).into());
+        }
+        self.context.stack.push(Val::i32(mem as i32));
+        self.emit_builtin_call(BuiltinFunctionIndex::memory32_grow(), &[I64, I32], &[I64]);
+
+        // The builtin returns the previous size in pages as a
+        // pointer-sized value; -1 signals failure.
+        self.retype_stack_top(heap.ty);
+    }
+
+    /// Loads the `VMRuntimeLimits` pointer from the `VMContext` into
+    /// `dst`.
+    fn emit_load_runtime_limits(&mut self, dst: Reg) {
+        let addr = self.masm.address_at_reg(
+            <M::ABI as ABI>::vmctx_reg(),
+            self.env.vmoffsets.vmctx_runtime_limits(),
+        );
+        self.masm.load(addr, dst, OperandSize::S64);
+    }
+
+    /// Accounts for the fuel consumed by the given operator and, for
+    /// operators that transfer control, flushes the accumulated fuel
+    /// to the `VMRuntimeLimits`.
+    ///
+    /// The cost of each operator mirrors the one used by Cranelift, so
+    /// that fuel consumption is identical across compilation
+    /// strategies.
+    fn fuel_before_visit_op(&mut self, op: &Operator) {
+        use Operator::*;
+
+        self.fuel_consumed += match op {
+            // Nop and drop generate no code, so don't consume fuel for
+            // them.
+            Nop | Drop => 0,
+            // Control flow may create branches, but is generally cheap
+            // and free, so don't consume fuel.
+            Block { .. } | Loop { .. } | Unreachable | Return | Else | End => 0,
+            // Everything else, just call it one operation.
+            _ => 1,
+        };
+
+        match op {
+            // Flush the accumulated fuel before leaving the function or
+            // calling into other code, which might inspect or consume
+            // fuel as well.
+            Unreachable
+            | Return
+            | Call { .. }
+            | CallIndirect { .. }
+            | ReturnCall { .. }
+            | ReturnCallIndirect { .. }
+            // Flush the accumulated fuel before any control flow
+            // transfer, given that the fuel counter is not tracked
+            // across blocks.
+            | Loop { .. }
+            | If { .. }
+            | Br { .. }
+            | BrIf { .. }
+            | BrTable { .. }
+            | End
+            | Else => self.emit_fuel_increment(),
+            _ => {}
+        }
+    }
+
+    /// Adds the fuel accumulated since the last flush to the
+    /// `fuel_consumed` field of the `VMRuntimeLimits`.
+    fn emit_fuel_increment(&mut self) {
+        let fuel_consumed = std::mem::replace(&mut self.fuel_consumed, 0);
+        if fuel_consumed == 0 {
+            return;
+        }
+
+        let limits = self.context.any_gpr(self.masm);
+        self.emit_load_runtime_limits(limits);
+        let fuel = self.context.any_gpr(self.masm);
+        let fuel_addr = self.masm.address_at_reg(
+            limits,
+            u32::from(self.env.vmoffsets.ptr.vmruntime_limits_fuel_consumed()),
+        );
+        self.masm.load(fuel_addr, fuel, OperandSize::S64);
+        self.masm.add(
+            fuel.into(),
+            fuel.into(),
+            RegImm::i64(fuel_consumed),
+            OperandSize::S64,
+        );
+        self.masm.store(fuel.into(), fuel_addr, OperandSize::S64);
+        self.context.free_reg(fuel);
+        self.context.free_reg(limits);
+    }
+
+    /// Emits a fuel check, if fuel metering is enabled.
+    ///
+    /// Fuel is stored as a negative number that is incremented as it is
+    /// consumed; once it becomes non-negative the `out_of_gas` builtin
+    /// is invoked, which either traps or refuels.
+    pub fn maybe_emit_fuel_check(&mut self) {
+        if !self.env.tunables.consume_fuel {
+            return;
+        }
+
+        self.emit_fuel_increment();
+        // The slow path calls into the runtime; spill the value stack
+        // so that the register state is the same regardless of the
+        // path taken.
+        self.context.spill(self.masm);
+
+        let limits = self.context.any_gpr(self.masm);
+        self.emit_load_runtime_limits(limits);
+        let fuel = self.context.any_gpr(self.masm);
+        let fuel_addr = self.masm.address_at_reg(
+            limits,
+            u32::from(self.env.vmoffsets.ptr.vmruntime_limits_fuel_consumed()),
+        );
+        self.masm.load(fuel_addr, fuel, OperandSize::S64);
+
+        let continuation = self.masm.get_label();
+        // fuel < 0, continue executing.
+        self.masm.branch(
+            CmpKind::LtS,
+            RegImm::i64(0),
+            fuel.into(),
+            continuation,
+            OperandSize::S64,
+        );
+        self.context.free_reg(fuel);
+        self.context.free_reg(limits);
+
+        self.emit_builtin_call(BuiltinFunctionIndex::out_of_gas(), &[], &[]);
+        self.masm.bind(continuation);
+    }
+
+    /// Emits an epoch check, if epoch interruption is enabled.
+    ///
+    /// The current epoch is compared against the deadline stored in
+    /// the `VMRuntimeLimits`; once the deadline is reached the
+    /// `new_epoch` builtin is invoked, which either traps, yields or
+    /// updates the deadline.
+    pub fn maybe_emit_epoch_check(&mut self) {
+        if !self.env.tunables.epoch_interruption {
+            return;
+        }
+
+        // See the comment in `maybe_emit_fuel_check`.
+        self.context.spill(self.masm);
+
+        let epoch = self.context.any_gpr(self.masm);
+        let epoch_ptr_addr = self.masm.address_at_reg(
+            <M::ABI as ABI>::vmctx_reg(),
+            self.env.vmoffsets.vmctx_epoch_ptr(),
+        );
+        self.masm.load(epoch_ptr_addr, epoch, OperandSize::S64);
+        let epoch_addr = self.masm.address_at_reg(epoch, 0);
+        self.masm.load(epoch_addr, epoch, OperandSize::S64);
+
+        let deadline = self.context.any_gpr(self.masm);
+        self.emit_load_runtime_limits(deadline);
+        let deadline_addr = self.masm.address_at_reg(
+            deadline,
+            u32::from(self.env.vmoffsets.ptr.vmruntime_limits_epoch_deadline()),
+        );
+        self.masm.load(deadline_addr, deadline, OperandSize::S64);
+
+        let continuation = self.masm.get_label();
+        // epoch < deadline, continue executing.
+        self.masm.branch(
+            CmpKind::LtU,
+            deadline.into(),
+            epoch.into(),
+            continuation,
+            OperandSize::S64,
+        );
+        self.context.free_reg(deadline);
+        self.context.free_reg(epoch);
+
+        // The deadline is always reloaded from the `VMRuntimeLimits`,
+        // so the new deadline returned by the builtin is not needed.
+        self.emit_builtin_call(BuiltinFunctionIndex::new_epoch(), &[], &[WasmType::I64]);
+        let new_deadline = self.context.pop_to_reg(self.masm, None);
+        self.context.free_reg(new_deadline);
+        self.masm.bind(continuation);
+    }
+
+    /// Emits a call to the given builtin function.
+    ///
+    /// The `VMContext` pointer is passed as the first argument; the
+    /// rest of the arguments, described by `params`, are expected to
+    /// be at the top of the value stack.
+    fn emit_builtin_call(
+        &mut self,
+        builtin: BuiltinFunctionIndex,
+        params: &[WasmType],
+        returns: &[WasmType],
+    ) {
+        // Pass a copy of the vmctx pointer, given that the call
+        // sequence frees the registers holding the arguments.
+        let vmctx = self.context.any_gpr(self.masm);
+        self.masm.mov(
+            <M::ABI as ABI>::vmctx_reg().into(),
+            vmctx.into(),
+            OperandSize::S64,
+        );
+        let location = self.context.stack.len() - params.len();
+        self.context
+            .stack
+            .insert(location, TypedReg::i64(vmctx).into());
+
+        let mut sig_params = vec![WasmType::I64];
+        sig_params.extend_from_slice(params);
+        let sig = WasmFuncType::new(sig_params.into(), returns.into());
+        let sig = <M::ABI as ABI>::sig(&sig, &CallingConvention::Default);
+        let builtins = self.masm.address_at_reg(
+            <M::ABI as ABI>::vmctx_reg(),
+            self.env.vmoffsets.vmctx_builtin_functions(),
+        );
+        let offset = builtin.index() * u32::from(self.env.vmoffsets.ptr.size());
+        let fncall = FnCall::new::<M>(&sig, &mut self.context, self.masm);
+        fncall.builtin::<M>(self.masm, &mut self.context, builtins, offset);
+    }
+
+    /// Emit an indirect function call through the table at the given
+    /// index, checking the signature of the callee against the
+    /// function type at `type_index`.
+    pub fn emit_call_indirect(&mut self, type_index: TypeIndex, table_index: TableIndex) {
+        let ty = self.env.resolve_func_type(type_index);
+        let mut params = vec![WasmType::I64, WasmType::I64];
+        params.extend_from_slice(ty.params());
+        let sig = WasmFuncType::new(params.into(), ty.returns().into());
+        let sig = <M::ABI as ABI>::sig(&sig, &CallingConvention::Default);
+
+        self.emit_lazy_init_funcref(table_index);
+
+        // The funcref is needed to compute the callee address after
+        // the arguments are assigned, so make sure that it doesn't
+        // land in any of the registers used to pass them.
+        let funcref = self
+            .context
+            .without_regs(&sig.param_regs(), self.masm, |cx, masm| {
+                cx.pop_to_reg(masm, None)
+            })
+            .reg;
+        self.masm.cmp(RegImm::i64(0), funcref, OperandSize::S64);
+        self.masm.trapif(CmpKind::Eq, TrapCode::IndirectCallToNull);
+
+        let vmctx = <M::ABI as ABI>::vmctx_reg();
+        match self.env.translation.module.table_plans[table_index].style {
+            TableStyle::CallerChecksSignature => {
+                let offsets = &self.env.vmoffsets;
+                let sig_index = self.env.translation.module.types[type_index].unwrap_function();
+                let sig_id_size: OperandSize =
+                    OperandSize::from_bytes(offsets.size_of_vmshared_signature_index());
+
+                // Load the caller's signature id from the array of
+                // signature ids.
+                let caller_id = self.context.any_gpr(self.masm);
+                let addr = self
+                    .masm
+                    .address_at_reg(vmctx, offsets.vmctx_signature_ids_array());
+                self.masm.load(addr, caller_id, OperandSize::S64);
+                let addr = self
+                    .masm
+                    .address_at_reg(caller_id, sig_index.as_u32() * sig_id_size.bytes());
+                self.masm.load(addr, caller_id, sig_id_size);
+
+                // Load the callee's signature id from the funcref.
+                let callee_id = self.context.any_gpr(self.masm);
+                let addr = self
+                    .masm
+                    .address_at_reg(funcref, u32::from(offsets.ptr.vm_func_ref_type_index()));
+                self.masm.load(addr, callee_id, sig_id_size);
+
+                self.masm.cmp(caller_id.into(), callee_id, sig_id_size);
+                self.masm.trapif(CmpKind::Ne, TrapCode::BadSignature);
+                self.context.free_reg(callee_id);
+                self.context.free_reg(caller_id);
+            }
+        }
+
+        // FIXME Remove harcoded operand size, this will be needed
+        // once 32-bit architectures are supported.
+        let callee_vmctx = self.context.any_gpr(self.masm);
+        let addr = self.masm.address_at_reg(
+            funcref,
+            u32::from(self.env.vmoffsets.ptr.vm_func_ref_vmctx()),
+        );
+        self.masm.load(addr, callee_vmctx, OperandSize::S64);
+        let caller_vmctx = self.context.any_gpr(self.masm);
+        self.masm
+            .mov(vmctx.into(), caller_vmctx.into(), OperandSize::S64);
+
+        // Put the callee / caller vmctx at the start of the range of
+        // the stack so that they are used as first and second
+        // arguments.
+        let stack = &mut self.context.stack;
+        let location = stack.len() - ty.params().len();
+        stack.insert(location, TypedReg::i64(caller_vmctx).into());
+        stack.insert(location, TypedReg::i64(callee_vmctx).into());
+
+        let callee_addr = self.masm.address_at_reg(
+            funcref,
+            u32::from(self.env.vmoffsets.ptr.vm_func_ref_wasm_call()),
+        );
+        let fncall = FnCall::new::<M>(&sig, &mut self.context, self.masm);
+        // The funcref is only needed to load the callee address, and no
+        // registers are allocated until the call is emitted, so release
+        // it now; it might be the register in which the call returns.
+        self.context.free_reg(funcref);
+        fncall.indirect::<M>(self.masm, &mut self.context, callee_addr);
+    }
+
+    /// Loads the field located at `offset` within the
+    /// `VMTableDefinition` of the given table into `dst`.
+    fn emit_load_table_field(
+        &mut self,
+        table: &TableData,
+        offset: u32,
+        dst: Reg,
+        size: OperandSize,
+    ) {
+        let vmctx = <M::ABI as ABI>::vmctx_reg();
+        // FIXME Remove harcoded operand size, this will be needed
+        // once 32-bit architectures are supported.
+        let base = if let Some(import_from) = table.import_from {
+            let addr = self.masm.address_at_reg(vmctx, import_from);
+            self.masm.load(addr, dst, OperandSize::S64);
+            dst
+        } else {
+            vmctx
+        };
+        let addr = self.masm.address_at_reg(base, offset);
+        self.masm.load(addr, dst, size);
+    }
+
+    /// Emits the instruction sequence to compute the address of the
+    /// table element at `index`, trapping if the index is out of
+    /// bounds. The resulting address is computed in `base`.
+    fn emit_compute_table_elem_addr(
+        &mut self,
+        index: Reg,
+        base: Reg,
+        table: &TableData,
+    ) -> M::Address {
+        let bound = self.context.any_gpr(self.masm);
+        self.emit_load_table_field(
+            table,
+            table.current_elems_offset,
+            bound,
+            table.current_elements_size,
+        );
+        self.masm
+            .cmp(bound.into(), index, table.current_elements_size);
+        self.masm.trapif(CmpKind::GeU, TrapCode::TableOutOfBounds);
+        self.context.free_reg(bound);
+
+        // FIXME Remove harcoded operand size, this will be needed
+        // once 32-bit architectures are supported.
+        self.emit_load_table_field(table, table.offset, base, OperandSize::S64);
+        // The scratch register can't be used as the temporary here,
+        // since the multiplication by an immediate might need it to
+        // materialize the immediate on some ISAs.
+        let tmp = self.context.any_gpr(self.masm);
+        self.masm.extend(tmp, index, ExtendKind::I64ExtendI32U);
+        self.masm.mul(
+            tmp.into(),
+            tmp.into(),
+            RegImm::i32(table.element_size.bytes() as i32),
+            OperandSize::S64,
+        );
+        self.masm
+            .add(base.into(), base.into(), tmp.into(), OperandSize::S64);
+        self.context.free_reg(tmp);
+        self.masm.address_at_reg(base, 0)
+    }
+
+    /// Pops the index of a funcref table element from the value
+    /// stack and pushes the element, calling into the runtime to
+    /// initialize it if it hasn't been initialized yet.
+    ///
+    /// See the documentation of `FUNCREF_INIT_BIT` in
+    /// `wasmtime_environ` for more details.
+    pub fn emit_lazy_init_funcref(&mut self, table_index: TableIndex) {
+        use WasmType::*;
+        let table = self.env.resolve_table_data(table_index);
+        let funcref = Ref(WasmRefType::FUNCREF);
+        let returns = [funcref];
+        let result = <M::ABI as ABI>::result(&returns, &CallingConvention::Default);
+
+        // Unconditionally spill before emitting control flow, and
+        // hold the element in the builtin's result register, so that
+        // both paths agree on the state of the value stack at the
+        // join below.
+        self.context.spill(self.masm);
+        let elem = self.context.reg(result.result_reg().unwrap(), self.masm);
+        let index = self.context.pop_to_reg(self.masm, None);
+        let base = self.context.any_gpr(self.masm);
+        let elem_addr = self.emit_compute_table_elem_addr(index.reg, base, &table);
+        self.masm.load(elem_addr, elem, table.element_size);
+        self.context.free_reg(base);
+
+        let (defined, cont) = (self.masm.get_label(), self.masm.get_label());
+        self.context
+            .stack
+            .push(Val::i32(table_index.as_u32() as i32));
+        self.context.stack.push(index.into());
+        self.masm.branch(
+            CmpKind::Ne,
+            elem.into(),
+            elem.into(),
+            defined,
+            table.element_size,
+        );
+
+        // The call below pushes its result, in the same register, to
+        // the value stack.
+        self.context.free_reg(elem);
+        self.emit_builtin_call(
+            BuiltinFunctionIndex::table_get_lazy_init_func_ref(),
+            &[I32, I32],
+            &returns,
+        );
+        self.masm.jmp(cont);
+
+        // The element is already initialized, mask off the init bit.
+        self.masm.bind(defined);
+        self.masm.and(
+            elem.into(),
+            elem.into(),
+            RegImm::i64(FUNCREF_MASK as i64),
+            table.element_size,
+        );
+        self.masm.bind(cont);
+    }
+
+    /// Emits the `table.get` instruction sequence.
+    pub fn emit_table_get(&mut self, table_index: TableIndex) {
+        match self.table_heap_type(table_index) {
+            WasmHeapType::Func | WasmHeapType::TypedFunc(_) => {
+                self.emit_lazy_init_funcref(table_index)
+            }
+            ty => unreachable!("{ty} tables are rejected before emission"),
+        }
+    }
+
+    /// Emits the `table.set` instruction sequence.
+    pub fn emit_table_set(&mut self, table_index: TableIndex) {
+        match self.table_heap_type(table_index) {
+            WasmHeapType::Func | WasmHeapType::TypedFunc(_) => {
+                let table = self.env.resolve_table_data(table_index);
+                let value = self.context.pop_to_reg(self.masm, None);
+                let index = self.context.pop_to_reg(self.masm, None);
+                let base = self.context.any_gpr(self.masm);
+                let elem_addr = self.emit_compute_table_elem_addr(index.reg, base, &table);
+
+                // Set the init bit, so that the element is not
+                // lazily initialized when read.
+                self.masm.or(
+                    value.reg.into(),
+                    value.reg.into(),
+                    RegImm::i64(FUNCREF_INIT_BIT as i64),
+                    table.element_size,
+                );
+                self.masm
+                    .store(value.reg.into(), elem_addr, table.element_size);
+                self.context.free_reg(base);
+                self.context.free_reg(index);
+                self.context.free_reg(value);
+            }
+            ty => unreachable!("{ty} tables are rejected before emission"),
+        }
+    }
+
+    /// Emits the `table.size` instruction sequence.
+    pub fn emit_table_size(&mut self, table_index: TableIndex) {
+        let table = self.env.resolve_table_data(table_index);
+        let dst = self.context.any_gpr(self.masm);
+        self.emit_load_table_field(
+            &table,
+            table.current_elems_offset,
+            dst,
+            table.current_elements_size,
+        );
+        self.context
+            .stack
+            .push(TypedReg::new(WasmType::I32, dst).into());
+    }
+
+    /// Emits the `table.grow` instruction sequence, by calling the
+    /// corresponding builtin function.
+    pub fn emit_table_grow(&mut self, table_index: TableIndex) {
+        use WasmType::*;
+        let builtin = match self.table_heap_type(table_index) {
+            WasmHeapType::Func | WasmHeapType::TypedFunc(_) => {
+                BuiltinFunctionIndex::table_grow_func_ref()
+            }
+            ty => unreachable!("{ty} tables are rejected before emission"),
+        };
+
+        // The builtin expects the delta before the initial value,
+        // which is the reverse of the operand order.
+        let delta = self.context.pop_to_reg(self.masm, None);
+        let init = self.context.pop_to_reg(self.masm, None);
+        self.context
+            .stack
+            .push(Val::i32(table_index.as_u32() as i32));
+        self.context.stack.push(delta.into());
+        self.context.stack.push(init.into());
+        self.emit_builtin_call(builtin, &[I32, I32, init.ty], &[I32]);
+    }
+
+    /// Emits the `ref.func` instruction sequence, by calling the
+    /// corresponding builtin function.
+    pub fn emit_ref_func(&mut self, index: FuncIndex) {
+        use WasmType::*;
+        self.context.stack.push(Val::i32(index.as_u32() as i32));
+        self.emit_builtin_call(
+            BuiltinFunctionIndex::ref_func(),
+            &[I32],
+            &[Ref(WasmRefType::FUNCREF)],
+        );
+    }
+
+    /// Returns the heap type of the elements of the table at the
+    /// given index.
+    fn table_heap_type(&self, table_index: TableIndex) -> WasmHeapType {
+        self.env.translation.module.table_plans[table_index]
+            .table
+            .wasm_ty
+            .heap_type
+    }
+
+    /// Emits an unconditional trap, marking the rest of the current
+    /// block as unreachable.
+    fn emit_trap(&mut self, code: TrapCode) {
+        self.masm.trap(code);
+        self.context.reachable = false;
+        // Set the implicit outermost frame as target to perform the necessary
+        // stack clean up.
+        let outermost = &mut self.control_frames[0];
+        outermost.set_as_target();
+    }
+
+    /// Changes the type of the register at the top of the value stack.
+    fn retype_stack_top(&mut self, ty: WasmType) {
+        let TypedReg { reg, .. } = self.context.pop_to_reg(self.masm, None);
+        self.context.stack.push(TypedReg::new(ty, reg).into());
+    }
+
     /// Emit the usual function end instruction sequence.
     fn emit_end(&mut self) -> Result<()> {
         assert!(self.context.stack.len() == 0);
@@ -322,7 +1144,7 @@ where
                     .expect("arg should be associated to a register");
 
                 match &ty {
-                    I32 | I64 | F32 | F64 => self.masm.store(src.into(), addr, ty.into()),
+                    I32 | I64 | F32 | F64 | Ref(_) => self.masm.store(src.into(), addr, ty.into()),
                     _ => panic!("Unsupported type {:?}", ty),
                 }
             });
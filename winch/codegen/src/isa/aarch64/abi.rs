@@ -65,7 +65,7 @@ impl ABI for Aarch64ABI {
     }
 
     fn sig(wasm_sig: &WasmFuncType, call_conv: &CallingConvention) -> ABISig {
-        assert!(call_conv.is_apple_aarch64() || call_conv.is_default());
+        assert!(call_conv.is_apple_aarch64() || call_conv.is_systemv() || call_conv.is_default());
 
         if wasm_sig.returns().len() > 1 {
             panic!("multi-value not supported");
@@ -90,7 +90,7 @@ impl ABI for Aarch64ABI {
 
         let ty = returns.get(0).copied();
         let reg = ty.map(|ty| match ty {
-            WasmType::I32 | WasmType::I64 => regs::xreg(0),
+            WasmType::I32 | WasmType::I64 | WasmType::Ref(_) => regs::xreg(0),
             WasmType::F32 | WasmType::F64 => regs::vreg(0),
             t => panic!("Unsupported return type {:?}", t),
         });
@@ -98,11 +98,11 @@ impl ABI for Aarch64ABI {
     }
 
     fn scratch_reg() -> Reg {
-        todo!()
+        regs::scratch()
     }
 
     fn sp_reg() -> Reg {
-        todo!()
+        regs::sp()
     }
 
     fn fp_reg() -> Reg {
@@ -118,7 +118,8 @@ impl ABI for Aarch64ABI {
     }
 
     fn stack_arg_slot_size_for_type(_ty: WasmType) -> u32 {
-        todo!()
+        // All stack arguments take a full word; see `to_abi_arg`.
+        Self::word_bytes()
     }
 }
 
@@ -129,7 +130,9 @@ impl Aarch64ABI {
         index_env: &mut RegIndexEnv,
     ) -> ABIArg {
         let (reg, ty) = match wasm_arg {
-            ty @ (WasmType::I32 | WasmType::I64) => (index_env.next_xreg().map(regs::xreg), ty),
+            ty @ (WasmType::I32 | WasmType::I64 | WasmType::Ref(_)) => {
+                (index_env.next_xreg().map(regs::xreg), ty)
+            }
 
             ty @ (WasmType::F32 | WasmType::F64) => (index_env.next_vreg().map(regs::vreg), ty),
 
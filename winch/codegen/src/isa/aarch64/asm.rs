@@ -1,17 +1,28 @@
 //! Assembler library implementation for Aarch64.
 
 use super::{address::Address, regs};
-use crate::{masm::OperandSize, reg::Reg};
+use crate::{
+    masm::{CalleeKind, CmpKind, DivKind, ExtendKind, OperandSize, RoundingMode, ShiftKind},
+    reg::Reg,
+};
 use cranelift_codegen::{
-    ir::MemFlags,
-    isa::aarch64::inst::{
-        self,
-        emit::{EmitInfo, EmitState},
-        ALUOp, ALUOp3, AMode, ExtendOp, Imm12, Inst, PairAMode,
+    entity::EntityRef,
+    ir::{types, ExternalName, MemFlags, Opcode, SourceLoc, TrapCode, UserExternalNameRef},
+    isa::{
+        aarch64::inst::{
+            self,
+            emit::{EmitInfo, EmitState},
+            ALUOp, ALUOp3, AMode, BitOp, BranchTarget, CallIndInfo, CallInfo, Cond, CondBrKind,
+            ExtendOp, FPUOp1, FPUOp2, FpuRoundMode, FpuToIntOp, Imm12, ImmLogic, ImmShift, Inst,
+            IntToFpuOp, JTSequenceInfo, PairAMode, ScalarSize, UImm5, VecLanesOp, VecMisc2,
+            VectorSize, NZCV,
+        },
+        CallConv,
     },
     settings, Final, MachBuffer, MachBufferFinalized, MachInstEmit, MachInstEmitState, MachLabel,
     Writable,
 };
+use smallvec::smallvec;
 
 impl From<OperandSize> for inst::OperandSize {
     fn from(size: OperandSize) -> Self {
@@ -23,6 +34,35 @@ impl From<OperandSize> for inst::OperandSize {
     }
 }
 
+impl From<OperandSize> for ScalarSize {
+    fn from(size: OperandSize) -> Self {
+        match size {
+            OperandSize::S8 => Self::Size8,
+            OperandSize::S16 => Self::Size16,
+            OperandSize::S32 => Self::Size32,
+            OperandSize::S64 => Self::Size64,
+            OperandSize::S128 => Self::Size128,
+        }
+    }
+}
+
+impl From<CmpKind> for Cond {
+    fn from(value: CmpKind) -> Self {
+        match value {
+            CmpKind::Eq => Cond::Eq,
+            CmpKind::Ne => Cond::Ne,
+            CmpKind::LtS => Cond::Lt,
+            CmpKind::LtU => Cond::Lo,
+            CmpKind::GtS => Cond::Gt,
+            CmpKind::GtU => Cond::Hi,
+            CmpKind::LeS => Cond::Le,
+            CmpKind::LeU => Cond::Ls,
+            CmpKind::GeS => Cond::Ge,
+            CmpKind::GeU => Cond::Hs,
+        }
+    }
+}
+
 /// Low level assembler implementation for Aarch64.
 pub(crate) struct Assembler {
     /// The machine instruction buffer.
@@ -46,12 +86,12 @@ impl Assembler {
 
 impl Assembler {
     /// Return the emitted code.
-    pub fn finalize(mut self) -> MachBufferFinalized<Final> {
+    pub fn finalize(mut self, base: Option<SourceLoc>) -> MachBufferFinalized<Final> {
         let constants = Default::default();
         let stencil = self
             .buffer
             .finish(&constants, self.emit_state.ctrl_plane_mut());
-        stencil.apply_base_srcloc(Default::default())
+        stencil.apply_base_srcloc(base.unwrap_or_default())
     }
 
     fn emit(&mut self, inst: Inst) {
@@ -79,47 +119,106 @@ impl Assembler {
 
     /// Store a register.
     pub fn str(&mut self, reg: Reg, addr: Address, size: OperandSize) {
+        self.store(reg, addr, size, MemFlags::trusted());
+    }
+
+    /// Store a register with the given memory flags.
+    ///
+    /// Floating point registers are stored through the FPU store
+    /// instructions; integer registers support stores of 8, 16, 32
+    /// and 64 bits.
+    pub fn store(&mut self, reg: Reg, addr: Address, size: OperandSize, flags: MemFlags) {
         let mem: AMode = addr.try_into().unwrap();
-        let flags = MemFlags::trusted();
+        let rd = reg.into();
 
         use OperandSize::*;
-        let inst = match size {
-            S64 => Inst::Store64 {
-                rd: reg.into(),
+        let inst = match (reg.is_float(), size) {
+            (true, S32) => Inst::FpuStore32 { rd, mem, flags },
+            (true, S64) => Inst::FpuStore64 { rd, mem, flags },
+            (false, S8) => Inst::Store8 { rd, mem, flags },
+            (false, S16) => Inst::Store16 { rd, mem, flags },
+            (false, S32) => Inst::Store32 { rd, mem, flags },
+            (false, S64) => Inst::Store64 { rd, mem, flags },
+            (_, s) => panic!("Invalid store size {:?}", s),
+        };
+
+        self.emit(inst);
+    }
+
+    /// Load a register.
+    pub fn ldr(&mut self, addr: Address, rd: Reg, size: OperandSize) {
+        self.uload(addr, rd, size, MemFlags::trusted());
+    }
+
+    /// Zero-extending load with the given memory flags.
+    ///
+    /// Floating point registers are loaded through the FPU load
+    /// instructions.
+    pub fn uload(&mut self, addr: Address, rd: Reg, size: OperandSize, flags: MemFlags) {
+        use OperandSize::*;
+        let writable_reg = Writable::from_reg(rd.into());
+        let mem: AMode = addr.try_into().unwrap();
+
+        let inst = match (rd.is_float(), size) {
+            (true, S32) => Inst::FpuLoad32 {
+                rd: writable_reg,
                 mem,
                 flags,
             },
-            S32 => Inst::Store32 {
-                rd: reg.into(),
+            (true, S64) => Inst::FpuLoad64 {
+                rd: writable_reg,
                 mem,
                 flags,
             },
-
-            _ => unreachable!(),
+            (false, S8) => Inst::ULoad8 {
+                rd: writable_reg,
+                mem,
+                flags,
+            },
+            (false, S16) => Inst::ULoad16 {
+                rd: writable_reg,
+                mem,
+                flags,
+            },
+            (false, S32) => Inst::ULoad32 {
+                rd: writable_reg,
+                mem,
+                flags,
+            },
+            (false, S64) => Inst::ULoad64 {
+                rd: writable_reg,
+                mem,
+                flags,
+            },
+            (_, s) => panic!("Invalid load size {:?}", s),
         };
 
         self.emit(inst);
     }
 
-    /// Load a register.
-    pub fn ldr(&mut self, addr: Address, rd: Reg, size: OperandSize) {
+    /// Sign-extending load with the given memory flags.
+    pub fn sload(&mut self, addr: Address, rd: Reg, size: OperandSize, flags: MemFlags) {
         use OperandSize::*;
         let writable_reg = Writable::from_reg(rd.into());
         let mem: AMode = addr.try_into().unwrap();
-        let flags = MemFlags::trusted();
 
         let inst = match size {
-            S64 => Inst::ULoad64 {
+            S8 => Inst::SLoad8 {
+                rd: writable_reg,
+                mem,
+                flags,
+            },
+            S16 => Inst::SLoad16 {
                 rd: writable_reg,
                 mem,
                 flags,
             },
-            S32 => Inst::ULoad32 {
+            S32 => Inst::SLoad32 {
                 rd: writable_reg,
                 mem,
                 flags,
             },
-            _ => unreachable!(),
+            s => panic!("Invalid sign-extending load size {:?}", s),
         };
 
         self.emit(inst);
@@ -195,6 +294,516 @@ impl Assembler {
         self.emit_alu_rrrr(ALUOp3::MAdd, scratch, rn, rd, regs::zero(), size);
     }
 
+    /// Signed or unsigned division with three registers.
+    ///
+    /// Cranelift only emits 64-bit divisions, so 32-bit operands are sign
+    /// or zero extended to 64 bits in place first.
+    pub fn div_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, kind: DivKind, size: OperandSize) {
+        let (op, extend) = match kind {
+            DivKind::Signed => (ALUOp::SDiv, ExtendKind::I64Extend32S),
+            DivKind::Unsigned => (ALUOp::UDiv, ExtendKind::I64ExtendI32U),
+        };
+        if size == OperandSize::S32 {
+            self.extend(rm, rm, extend);
+            self.extend(rn, rn, extend);
+        }
+        self.emit_alu_rrr(op, rm, rn, rd, OperandSize::S64);
+    }
+
+    /// Multiply-subtract: `rd = ra - (rn * rm)`.
+    pub fn msub_rrrr(&mut self, rm: Reg, rn: Reg, ra: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrrr(ALUOp3::MSub, rm, rn, rd, ra, size);
+    }
+
+    /// Negate a register.
+    pub fn neg_rr(&mut self, rm: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::Sub, rm, regs::zero(), rd, size);
+    }
+
+    /// And with three registers.
+    pub fn and_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::And, rm, rn, rd, size);
+    }
+
+    /// And immediate and register.
+    pub fn and_ir(&mut self, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rri_logic(ALUOp::And, imm, rn, rd, size);
+    }
+
+    /// Or with three registers.
+    pub fn or_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::Orr, rm, rn, rd, size);
+    }
+
+    /// Or immediate and register.
+    pub fn or_ir(&mut self, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rri_logic(ALUOp::Orr, imm, rn, rd, size);
+    }
+
+    /// Xor with three registers.
+    pub fn xor_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::Eor, rm, rn, rd, size);
+    }
+
+    /// Xor immediate and register.
+    pub fn xor_ir(&mut self, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rri_logic(ALUOp::Eor, imm, rn, rd, size);
+    }
+
+    /// Shift with three registers.
+    ///
+    /// Left rotations are not supported by the instruction set, so
+    /// callers are expected to lower them as right rotations by the
+    /// negated amount.
+    pub fn shift_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, kind: ShiftKind, size: OperandSize) {
+        self.emit_alu_rrr(Self::shift_kind_to_alu_op(kind), rm, rn, rd, size);
+    }
+
+    /// Shift immediate and register.
+    pub fn shift_ir(&mut self, imm: u64, rn: Reg, rd: Reg, kind: ShiftKind, size: OperandSize) {
+        let immshift = ImmShift::maybe_from_u64(imm).expect("valid shift amount");
+        self.emit(Inst::AluRRImmShift {
+            alu_op: Self::shift_kind_to_alu_op(kind),
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            immshift,
+        });
+    }
+
+    /// Add with three registers, setting the condition flags.
+    pub fn adds_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::AddS, rm, rn, rd, size);
+    }
+
+    /// Add immediate and register, setting the condition flags.
+    pub fn adds_ir(&mut self, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        let alu_op = ALUOp::AddS;
+        if let Some(imm) = Imm12::maybe_from_u64(imm) {
+            self.emit_alu_rri(alu_op, imm, rn, rd, size);
+        } else {
+            let scratch = regs::scratch();
+            self.load_constant(imm, scratch);
+            self.emit_alu_rrr(alu_op, scratch, rn, rd, size);
+        }
+    }
+
+    /// Compare two registers, setting the condition flags according
+    /// to `rn - rm`.
+    pub fn cmp_rr(&mut self, rm: Reg, rn: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::SubS, rm, rn, regs::zero(), size);
+    }
+
+    /// Compare a register and an immediate, setting the condition
+    /// flags according to `rn - imm`.
+    pub fn cmp_ir(&mut self, imm: u64, rn: Reg, size: OperandSize) {
+        let alu_op = ALUOp::SubS;
+        if let Some(imm) = Imm12::maybe_from_u64(imm) {
+            self.emit_alu_rri(alu_op, imm, rn, regs::zero(), size);
+        } else {
+            let scratch = regs::scratch();
+            self.load_constant(imm, scratch);
+            self.emit_alu_rrr(alu_op, scratch, rn, regs::zero(), size);
+        }
+    }
+
+    /// Compare negative: set the condition flags according to
+    /// `rn + imm`.
+    pub fn cmn_ir(&mut self, imm: u64, rn: Reg, size: OperandSize) {
+        self.adds_ir(imm, rn, regs::zero(), size);
+    }
+
+    /// Conditional compare: if `cond` holds, set the condition
+    /// flags according to `rn - imm`, else set them to `nzcv`.
+    pub fn ccmp_ir(&mut self, imm: u8, rn: Reg, nzcv: NZCV, cond: Cond, size: OperandSize) {
+        self.emit(Inst::CCmpImm {
+            size: size.into(),
+            rn: rn.into(),
+            imm: UImm5::maybe_from_u8(imm).expect("valid 5-bit immediate"),
+            nzcv,
+            cond,
+        });
+    }
+
+    /// Bitwise test of two registers, setting the condition flags
+    /// according to `rn & rm`.
+    pub fn tst_rr(&mut self, rm: Reg, rn: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::AndS, rm, rn, regs::zero(), size);
+    }
+
+    /// Set the destination register to 1 if the condition holds,
+    /// else to 0.
+    pub fn cset(&mut self, rd: Reg, cond: Cond) {
+        self.emit(Inst::CSet {
+            rd: Writable::from_reg(rd.into()),
+            cond,
+        });
+    }
+
+    /// Conditional select: `rd = cond ? rn : rm`.
+    pub fn csel(&mut self, rn: Reg, rm: Reg, rd: Reg, cond: Cond) {
+        self.emit(Inst::CSel {
+            rd: Writable::from_reg(rd.into()),
+            cond,
+            rn: rn.into(),
+            rm: rm.into(),
+        });
+    }
+
+    /// Floating point conditional select: `rd = cond ? rn : rm`.
+    pub fn fpu_csel(&mut self, rn: Reg, rm: Reg, rd: Reg, cond: Cond, size: OperandSize) {
+        let rd = Writable::from_reg(rd.into());
+        let (rn, rm) = (rn.into(), rm.into());
+        let inst = match size {
+            OperandSize::S32 => Inst::FpuCSel32 { rd, rn, rm, cond },
+            OperandSize::S64 => Inst::FpuCSel64 { rd, rn, rm, cond },
+            s => panic!("Invalid operand size {:?} for floating point select", s),
+        };
+        self.emit(inst);
+    }
+
+    /// Count leading zeros.
+    pub fn clz(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_bit_rr(BitOp::Clz, rn, rd, size);
+    }
+
+    /// Reverse the bits of a register.
+    pub fn rbit(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_bit_rr(BitOp::RBit, rn, rd, size);
+    }
+
+    /// Count the set bits in each byte of the lower 64 bits of a
+    /// vector register.
+    pub fn cnt(&mut self, rn: Reg, rd: Reg) {
+        self.emit(Inst::VecMisc {
+            op: VecMisc2::Cnt,
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            size: VectorSize::Size8x8,
+        });
+    }
+
+    /// Add the bytes in the lower 64 bits of a vector register.
+    pub fn addv(&mut self, rn: Reg, rd: Reg) {
+        self.emit(Inst::VecLanes {
+            op: VecLanesOp::Addv,
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            size: VectorSize::Size8x8,
+        });
+    }
+
+    /// Sign or zero extend a register.
+    pub fn extend(&mut self, rn: Reg, rd: Reg, kind: ExtendKind) {
+        let (signed, from_bits, to_bits) = match kind {
+            ExtendKind::I32Extend8S => (true, 8, 32),
+            ExtendKind::I32Extend16S => (true, 16, 32),
+            ExtendKind::I64Extend8S => (true, 8, 64),
+            ExtendKind::I64Extend16S => (true, 16, 64),
+            ExtendKind::I64Extend32S => (true, 32, 64),
+            ExtendKind::I64ExtendI32U => (false, 32, 64),
+        };
+        self.emit(Inst::Extend {
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            signed,
+            from_bits,
+            to_bits,
+        });
+    }
+
+    /// Floating point register to register move.
+    pub fn fmov_rr(&mut self, rn: Reg, rd: Reg) {
+        self.emit(Inst::FpuMove64 {
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    /// Move the bits of a general purpose register into the lowest
+    /// lane of a floating point register.
+    pub fn mov_to_fpu(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::MovToFpu {
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            size: size.into(),
+        });
+    }
+
+    /// Move the bits of the lowest lane of a floating point register
+    /// into a general purpose register.
+    pub fn mov_from_vec(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::MovFromVec {
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            idx: 0,
+            size: size.into(),
+        });
+    }
+
+    /// Floating point add with three registers.
+    pub fn fadd_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Add, rm, rn, rd, size);
+    }
+
+    /// Floating point subtract with three registers.
+    pub fn fsub_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Sub, rm, rn, rd, size);
+    }
+
+    /// Floating point multiply with three registers.
+    pub fn fmul_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Mul, rm, rn, rd, size);
+    }
+
+    /// Floating point divide with three registers.
+    pub fn fdiv_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Div, rm, rn, rd, size);
+    }
+
+    /// Floating point minimum with three registers.
+    pub fn fmin_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Min, rm, rn, rd, size);
+    }
+
+    /// Floating point maximum with three registers.
+    pub fn fmax_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Max, rm, rn, rd, size);
+    }
+
+    /// Floating point negation.
+    pub fn fneg_rr(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rr(FPUOp1::Neg, rn, rd, size);
+    }
+
+    /// Floating point absolute value.
+    pub fn fabs_rr(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rr(FPUOp1::Abs, rn, rd, size);
+    }
+
+    /// Floating point square root.
+    pub fn fsqrt_rr(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rr(FPUOp1::Sqrt, rn, rd, size);
+    }
+
+    /// Convert between single and double precision floating point
+    /// values.
+    pub fn cvt_float_to_float(
+        &mut self,
+        rn: Reg,
+        rd: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+    ) {
+        let op = match (src_size, dst_size) {
+            (OperandSize::S32, OperandSize::S64) => FPUOp1::Cvt32To64,
+            (OperandSize::S64, OperandSize::S32) => FPUOp1::Cvt64To32,
+            (src, dst) => panic!("Invalid float conversion from {:?} to {:?}", src, dst),
+        };
+        self.emit_fpu_rr(op, rn, rd, src_size);
+    }
+
+    /// Round a floating point value to an integral value.
+    pub fn fround_rr(&mut self, rn: Reg, rd: Reg, mode: RoundingMode, size: OperandSize) {
+        use OperandSize::*;
+        use RoundingMode::*;
+
+        let op = match (mode, size) {
+            (Nearest, S32) => FpuRoundMode::Nearest32,
+            (Nearest, S64) => FpuRoundMode::Nearest64,
+            (Up, S32) => FpuRoundMode::Plus32,
+            (Up, S64) => FpuRoundMode::Plus64,
+            (Down, S32) => FpuRoundMode::Minus32,
+            (Down, S64) => FpuRoundMode::Minus64,
+            (Zero, S32) => FpuRoundMode::Zero32,
+            (Zero, S64) => FpuRoundMode::Zero64,
+            (_, s) => panic!("Invalid operand size {:?} for rounding", s),
+        };
+        self.emit(Inst::FpuRound {
+            op,
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    /// Compare two floating point registers, setting the condition
+    /// flags according to `rn - rm`.
+    pub fn fcmp(&mut self, rm: Reg, rn: Reg, size: OperandSize) {
+        self.emit(Inst::FpuCmp {
+            size: size.into(),
+            rn: rn.into(),
+            rm: rm.into(),
+        });
+    }
+
+    /// Convert a floating point value to an integer, rounding towards
+    /// zero. Out of range values are saturated and NaN is converted
+    /// to zero.
+    pub fn cvt_float_to_int(
+        &mut self,
+        rn: Reg,
+        rd: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        signed: bool,
+    ) {
+        use OperandSize::*;
+
+        let op = match (src_size, dst_size, signed) {
+            (S32, S32, true) => FpuToIntOp::F32ToI32,
+            (S32, S32, false) => FpuToIntOp::F32ToU32,
+            (S32, S64, true) => FpuToIntOp::F32ToI64,
+            (S32, S64, false) => FpuToIntOp::F32ToU64,
+            (S64, S32, true) => FpuToIntOp::F64ToI32,
+            (S64, S32, false) => FpuToIntOp::F64ToU32,
+            (S64, S64, true) => FpuToIntOp::F64ToI64,
+            (S64, S64, false) => FpuToIntOp::F64ToU64,
+            (src, dst, _) => panic!("Invalid conversion from {:?} to {:?}", src, dst),
+        };
+        self.emit(Inst::FpuToInt {
+            op,
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    /// Convert an integer to a floating point value.
+    pub fn cvt_int_to_float(
+        &mut self,
+        rn: Reg,
+        rd: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        signed: bool,
+    ) {
+        use OperandSize::*;
+
+        let op = match (src_size, dst_size, signed) {
+            (S32, S32, true) => IntToFpuOp::I32ToF32,
+            (S32, S32, false) => IntToFpuOp::U32ToF32,
+            (S32, S64, true) => IntToFpuOp::I32ToF64,
+            (S32, S64, false) => IntToFpuOp::U32ToF64,
+            (S64, S32, true) => IntToFpuOp::I64ToF32,
+            (S64, S32, false) => IntToFpuOp::U64ToF32,
+            (S64, S64, true) => IntToFpuOp::I64ToF64,
+            (S64, S64, false) => IntToFpuOp::U64ToF64,
+            (src, dst, _) => panic!("Invalid conversion from {:?} to {:?}", src, dst),
+        };
+        self.emit(Inst::IntToFpu {
+            op,
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    /// Emit a function call.
+    pub fn call(&mut self, callee: CalleeKind) {
+        match callee {
+            CalleeKind::Indirect(reg) => self.call_ind(reg),
+            CalleeKind::LibCall(lib) => {
+                // Library functions are resolved through absolute
+                // relocations; load the address into the scratch
+                // register and call it.
+                let scratch = regs::scratch();
+                self.emit(Inst::LoadExtName {
+                    rd: Writable::from_reg(scratch.into()),
+                    name: Box::new(ExternalName::LibCall(lib)),
+                    offset: 0,
+                });
+                self.call_ind(scratch);
+            }
+            CalleeKind::Direct(index) => {
+                let dest = ExternalName::user(UserExternalNameRef::new(index as usize));
+                self.emit(Inst::Call {
+                    info: Box::new(CallInfo {
+                        dest,
+                        uses: smallvec![],
+                        defs: smallvec![],
+                        clobbers: Default::default(),
+                        opcode: Opcode::Call,
+                        caller_callconv: CallConv::SystemV,
+                        callee_callconv: CallConv::SystemV,
+                        callee_pop_size: 0,
+                    }),
+                });
+            }
+        }
+    }
+
+    fn call_ind(&mut self, rn: Reg) {
+        self.emit(Inst::CallInd {
+            info: Box::new(CallIndInfo {
+                rn: rn.into(),
+                uses: smallvec![],
+                defs: smallvec![],
+                clobbers: Default::default(),
+                opcode: Opcode::CallIndirect,
+                caller_callconv: CallConv::SystemV,
+                callee_callconv: CallConv::SystemV,
+                callee_pop_size: 0,
+            }),
+        });
+    }
+
+    /// Performs an unconditional jump to the given label.
+    pub fn jmp(&mut self, target: MachLabel) {
+        self.emit(Inst::Jump {
+            dest: BranchTarget::Label(target),
+        });
+    }
+
+    /// Emits a conditional jump to the given label.
+    pub fn jmp_if(&mut self, cond: Cond, taken: MachLabel) {
+        // Conditional branches are always paired with an
+        // unconditional branch to the not taken target; bind a label
+        // right after the branch so that the machine buffer elides
+        // the unconditional branch.
+        let not_taken = self.get_label();
+        self.emit(Inst::CondBr {
+            taken: BranchTarget::Label(taken),
+            not_taken: BranchTarget::Label(not_taken),
+            kind: CondBrKind::Cond(cond),
+        });
+        self.buffer
+            .bind_label(not_taken, self.emit_state.ctrl_plane_mut());
+    }
+
+    /// Emits a jump table sequence. The sequence expects the
+    /// condition flags to be set by a prior comparison of the index
+    /// against the number of targets, branching to the default target
+    /// when the index is greater than or equal to it.
+    pub fn jmp_table(
+        &mut self,
+        targets: &[MachLabel],
+        default: MachLabel,
+        index: Reg,
+        tmp1: Reg,
+        tmp2: Reg,
+    ) {
+        self.emit(Inst::JTSequence {
+            info: Box::new(JTSequenceInfo {
+                targets: targets.iter().map(|l| BranchTarget::Label(*l)).collect(),
+                default_target: BranchTarget::Label(default),
+            }),
+            ridx: index.into(),
+            rtmp1: Writable::from_reg(tmp1.into()),
+            rtmp2: Writable::from_reg(tmp2.into()),
+        });
+    }
+
+    /// Emit a trap instruction.
+    pub fn udf(&mut self, code: TrapCode) {
+        self.emit(Inst::Udf { trap_code: code });
+    }
+
+    /// Conditional trap.
+    pub fn trapif(&mut self, cond: Cond, trap_code: TrapCode) {
+        self.emit(Inst::TrapIf {
+            kind: CondBrKind::Cond(cond),
+            trap_code,
+        });
+    }
+
     /// Return instruction.
     pub fn ret(&mut self) {
         self.emit(Inst::Ret {
@@ -215,6 +824,36 @@ impl Assembler {
         });
     }
 
+    fn emit_alu_rrr(&mut self, op: ALUOp, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::AluRRR {
+            alu_op: op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            rm: rm.into(),
+        });
+    }
+
+    fn emit_alu_rri_logic(&mut self, op: ALUOp, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        let ty = match size {
+            OperandSize::S32 => types::I32,
+            _ => types::I64,
+        };
+        if let Some(imml) = ImmLogic::maybe_from_u64(imm, ty) {
+            self.emit(Inst::AluRRImmLogic {
+                alu_op: op,
+                size: size.into(),
+                rd: Writable::from_reg(rd.into()),
+                rn: rn.into(),
+                imml,
+            });
+        } else {
+            let scratch = regs::scratch();
+            self.load_constant(imm, scratch);
+            self.emit_alu_rrr(op, scratch, rn, rd, size);
+        }
+    }
+
     fn emit_alu_rrr_extend(&mut self, op: ALUOp, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
         self.emit(Inst::AluRRRExtend {
             alu_op: op,
@@ -237,11 +876,56 @@ impl Assembler {
         });
     }
 
+    fn emit_bit_rr(&mut self, op: BitOp, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::BitRR {
+            op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    // Helpers for floating point operations.
+
+    fn emit_fpu_rr(&mut self, op: FPUOp1, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::FpuRR {
+            fpu_op: op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    fn emit_fpu_rrr(&mut self, op: FPUOp2, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::FpuRRR {
+            fpu_op: op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            rm: rm.into(),
+        });
+    }
+
+    fn shift_kind_to_alu_op(kind: ShiftKind) -> ALUOp {
+        match kind {
+            ShiftKind::Shl => ALUOp::Lsl,
+            ShiftKind::ShrS => ALUOp::Asr,
+            ShiftKind::ShrU => ALUOp::Lsr,
+            ShiftKind::Rotr => ALUOp::RotR,
+            ShiftKind::Rotl => panic!("Left rotations must be lowered as right rotations"),
+        }
+    }
+
     /// Get a label from the underlying machine code buffer.
     pub fn get_label(&mut self) -> MachLabel {
         self.buffer.get_label()
     }
 
+    /// Get a reference to the underlying machine buffer.
+    pub fn buffer(&self) -> &MachBuffer<Inst> {
+        &self.buffer
+    }
+
     /// Get a mutable reference to underlying
     /// machine buffer.
     pub fn buffer_mut(&mut self) -> &mut MachBuffer<Inst> {
@@ -1,14 +1,26 @@
 use super::{abi::Aarch64ABI, address::Address, asm::Assembler, regs};
 use crate::{
-    abi::{self, local::LocalSlot},
+    abi::{self, align_to, calculate_frame_adjustment, local::LocalSlot},
     codegen::CodeGenContext,
     isa::reg::Reg,
     masm::{
-        CalleeKind, CmpKind, DivKind, Imm as I, MacroAssembler as Masm, OperandSize, RegImm,
-        RemKind, ShiftKind, StackSlot,
+        CalleeKind, CmpKind, DivKind, ExtendKind, FloatCmpKind, Imm as I, MacroAssembler as Masm,
+        OperandSize, RegImm, RemKind, RoundingMode, ShiftKind, StackSlot, TruncKind,
     },
+    stack::TypedReg,
 };
-use cranelift_codegen::{settings, Final, MachBufferFinalized, MachLabel};
+use cranelift_codegen::{
+    binemit::CodeOffset,
+    ir::{MemFlags, RelSourceLoc, SourceLoc, TrapCode},
+    isa::aarch64::inst::{Cond, NZCV},
+    settings, Final, MachBufferFinalized, MachLabel,
+};
+use regalloc2::RegClass;
+use wasmtime_environ::WasmType;
+
+/// The size of the stack slot used to save the shadow stack pointer
+/// in the function prologue.
+const SHADOW_STACK_POINTER_SLOT_SIZE: u8 = 16;
 
 /// Aarch64 MacroAssembler.
 pub(crate) struct MacroAssembler {
@@ -41,6 +53,12 @@ impl Masm for MacroAssembler {
 
         self.asm.stp(fp, lr, addr);
         self.asm.mov_rr(sp, fp, OperandSize::S64);
+
+        // The shadow stack pointer is callee-saved; save it before
+        // clobbering it. A full slot is used to keep the real stack
+        // pointer 16-byte aligned.
+        let addr = Address::pre_indexed_from_sp(-(SHADOW_STACK_POINTER_SLOT_SIZE as i64));
+        self.asm.str(regs::shadow_sp(), addr, OperandSize::S64);
         self.move_sp_to_shadow_sp();
     }
 
@@ -54,6 +72,10 @@ impl Masm for MacroAssembler {
             self.move_sp_to_shadow_sp();
         }
 
+        let shadow_sp = regs::shadow_sp();
+        let addr = Address::post_indexed_from_sp(SHADOW_STACK_POINTER_SLOT_SIZE as i64);
+        self.asm.ldr(addr, shadow_sp, OperandSize::S64);
+
         let lr = regs::lr();
         let fp = regs::fp();
         let addr = Address::post_indexed_from_sp(16);
@@ -74,8 +96,16 @@ impl Masm for MacroAssembler {
         self.increment_sp(bytes);
     }
 
-    fn free_stack(&mut self, _bytes: u32) {
-        todo!()
+    fn free_stack(&mut self, bytes: u32) {
+        if bytes == 0 {
+            return;
+        }
+
+        let sp = regs::sp();
+        self.asm.add_ir(bytes as u64, sp, sp, OperandSize::S64);
+        self.move_sp_to_shadow_sp();
+
+        self.decrement_sp(bytes);
     }
 
     fn reset_stack_pointer(&mut self, offset: u32) {
@@ -97,12 +127,12 @@ impl Masm for MacroAssembler {
         Address::offset(reg, offset as i64)
     }
 
-    fn address_from_sp(&self, _offset: u32) -> Self::Address {
-        todo!()
+    fn address_from_sp(&self, offset: u32) -> Self::Address {
+        Address::from_shadow_sp((self.sp_offset - offset) as i64)
     }
 
-    fn address_at_sp(&self, _offset: u32) -> Self::Address {
-        todo!()
+    fn address_at_sp(&self, offset: u32) -> Self::Address {
+        Address::from_shadow_sp(offset as i64)
     }
 
     fn store(&mut self, src: RegImm, dst: Address, size: OperandSize) {
@@ -117,6 +147,10 @@ impl Masm for MacroAssembler {
                 self.asm.load_constant(imm, scratch);
                 scratch
             }
+            // The stack pointer can't be the source of a store, since
+            // its encoding is shared with the zero register; its value
+            // is always mirrored by the shadow stack pointer.
+            RegImm::Reg(reg) if reg == regs::sp() => regs::shadow_sp(),
             RegImm::Reg(reg) => reg,
         };
 
@@ -125,44 +159,101 @@ impl Masm for MacroAssembler {
 
     fn call(
         &mut self,
-        _stack_args_size: u32,
-        _load_callee: impl FnMut(&mut Self) -> CalleeKind,
+        stack_args_size: u32,
+        mut load_callee: impl FnMut(&mut Self) -> CalleeKind,
     ) -> u32 {
-        todo!()
+        let alignment: u32 = <Self::ABI as abi::ABI>::call_stack_align().into();
+        let addend: u32 = <Self::ABI as abi::ABI>::arg_base_offset().into();
+        let delta = calculate_frame_adjustment(self.sp_offset(), addend, alignment);
+        let aligned_args_size = align_to(stack_args_size, alignment);
+        let total_stack = delta + aligned_args_size;
+        self.reserve_stack(total_stack);
+        let callee = load_callee(self);
+        self.asm.call(callee);
+        // The callee is free to use the shadow stack pointer, restore
+        // it from the real stack pointer.
+        self.move_sp_to_shadow_sp();
+        total_stack
     }
 
     fn load(&mut self, src: Address, dst: Reg, size: OperandSize) {
         self.asm.ldr(src, dst, size);
     }
 
-    fn pop(&mut self, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn wasm_load(
+        &mut self,
+        src: Self::Address,
+        dst: Reg,
+        size: OperandSize,
+        kind: Option<ExtendKind>,
+    ) {
+        let flags = MemFlags::new();
+        match kind {
+            Some(ExtendKind::I64ExtendI32U) | None => self.asm.uload(src, dst, size, flags),
+            Some(_) => self.asm.sload(src, dst, size, flags),
+        }
+    }
+
+    fn wasm_store(&mut self, src: Reg, dst: Self::Address, size: OperandSize) {
+        self.asm.store(src, dst, size, MemFlags::new());
+    }
+
+    fn pop(&mut self, dst: Reg, size: OperandSize) {
+        let addr = self.address_from_sp(self.sp_offset);
+        self.asm.ldr(addr, dst, size);
+        self.free_stack(<Self::ABI as abi::ABI>::word_bytes());
     }
 
     fn sp_offset(&self) -> u32 {
         self.sp_offset
     }
 
-    fn finalize(self) -> MachBufferFinalized<Final> {
-        self.asm.finalize()
+    fn finalize(self, base: Option<SourceLoc>) -> MachBufferFinalized<Final> {
+        self.asm.finalize(base)
+    }
+
+    fn start_source_loc(&mut self, loc: RelSourceLoc) -> CodeOffset {
+        let offset = self.current_code_offset();
+        self.asm.buffer_mut().start_srcloc(loc);
+        offset
+    }
+
+    fn end_source_loc(&mut self) {
+        self.asm.buffer_mut().end_srcloc();
+    }
+
+    fn current_code_offset(&self) -> CodeOffset {
+        self.asm.buffer().cur_offset()
     }
 
     fn mov(&mut self, src: RegImm, dst: RegImm, size: OperandSize) {
         match (src, dst) {
             (RegImm::Imm(v), RegImm::Reg(rd)) => {
-                let imm = match v {
-                    I::I32(v) => v as u64,
-                    I::I64(v) => v,
-                    _ => panic!(),
-                };
-
                 let scratch = regs::scratch();
-                self.asm.load_constant(imm as u64, scratch);
-                self.asm.mov_rr(scratch, rd, size);
-            }
-            (RegImm::Reg(rs), RegImm::Reg(rd)) => {
-                self.asm.mov_rr(rs, rd, size);
+                match v {
+                    I::I32(v) => {
+                        self.asm.load_constant(v as u64, scratch);
+                        self.asm.mov_rr(scratch, rd, size);
+                    }
+                    I::I64(v) => {
+                        self.asm.load_constant(v, scratch);
+                        self.asm.mov_rr(scratch, rd, size);
+                    }
+                    I::F32(v) => {
+                        self.asm.load_constant(v as u64, scratch);
+                        self.asm.mov_to_fpu(scratch, rd, size);
+                    }
+                    I::F64(v) => {
+                        self.asm.load_constant(v, scratch);
+                        self.asm.mov_to_fpu(scratch, rd, size);
+                    }
+                }
             }
+            (RegImm::Reg(rs), RegImm::Reg(rd)) => match rs.class() {
+                RegClass::Int => self.asm.mov_rr(rs, rd, size),
+                RegClass::Float => self.asm.fmov_rr(rs, rd),
+                RegClass::Vector => unreachable!(),
+            },
             _ => Self::handle_invalid_two_form_operand_combination(src, dst),
         }
     }
@@ -224,42 +315,185 @@ impl Masm for MacroAssembler {
         }
     }
 
-    fn and(&mut self, _dst: RegImm, _lhs: RegImm, _rhs: RegImm, _size: OperandSize) {
-        todo!()
+    fn and(&mut self, dst: RegImm, lhs: RegImm, rhs: RegImm, size: OperandSize) {
+        match (rhs, lhs, dst) {
+            (RegImm::Imm(v), RegImm::Reg(rn), RegImm::Reg(rd)) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+
+                self.asm.and_ir(imm, rn, rd, size);
+            }
+
+            (RegImm::Reg(rm), RegImm::Reg(rn), RegImm::Reg(rd)) => {
+                self.asm.and_rrr(rm, rn, rd, size);
+            }
+            _ => Self::handle_invalid_three_form_operand_combination(dst, lhs, rhs),
+        }
     }
 
-    fn or(&mut self, _dst: RegImm, _lhs: RegImm, _rhs: RegImm, _size: OperandSize) {
-        todo!()
+    fn or(&mut self, dst: RegImm, lhs: RegImm, rhs: RegImm, size: OperandSize) {
+        match (rhs, lhs, dst) {
+            (RegImm::Imm(v), RegImm::Reg(rn), RegImm::Reg(rd)) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+
+                self.asm.or_ir(imm, rn, rd, size);
+            }
+
+            (RegImm::Reg(rm), RegImm::Reg(rn), RegImm::Reg(rd)) => {
+                self.asm.or_rrr(rm, rn, rd, size);
+            }
+            _ => Self::handle_invalid_three_form_operand_combination(dst, lhs, rhs),
+        }
     }
 
-    fn xor(&mut self, _dst: RegImm, _lhs: RegImm, _rhs: RegImm, _size: OperandSize) {
-        todo!()
+    fn xor(&mut self, dst: RegImm, lhs: RegImm, rhs: RegImm, size: OperandSize) {
+        match (rhs, lhs, dst) {
+            (RegImm::Imm(v), RegImm::Reg(rn), RegImm::Reg(rd)) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+
+                self.asm.xor_ir(imm, rn, rd, size);
+            }
+
+            (RegImm::Reg(rm), RegImm::Reg(rn), RegImm::Reg(rd)) => {
+                self.asm.xor_rrr(rm, rn, rd, size);
+            }
+            _ => Self::handle_invalid_three_form_operand_combination(dst, lhs, rhs),
+        }
     }
 
-    fn shift(&mut self, _context: &mut CodeGenContext, _kind: ShiftKind, _size: OperandSize) {
-        todo!()
+    fn shift(&mut self, context: &mut CodeGenContext, kind: ShiftKind, size: OperandSize) {
+        let top = context.stack.peek().expect("value at stack top");
+
+        let amount = if size == OperandSize::S32 && top.is_i32_const() {
+            Some(
+                context
+                    .stack
+                    .pop_i32_const()
+                    .expect("i32 const value at stack top") as u64,
+            )
+        } else if size == OperandSize::S64 && top.is_i64_const() {
+            Some(
+                context
+                    .stack
+                    .pop_i64_const()
+                    .expect("i64 const value at stack top") as u64,
+            )
+        } else {
+            None
+        };
+
+        match amount {
+            Some(amount) => {
+                let typed_reg = context.pop_to_reg(self, None);
+                let dst = typed_reg.reg;
+                // The shift amount is taken modulo the bit width of
+                // the operand, matching Wasm semantics.
+                let mask = size.num_bits() as u64 - 1;
+                let (kind, amount) = match kind {
+                    ShiftKind::Rotl => (ShiftKind::Rotr, amount.wrapping_neg() & mask),
+                    kind => (kind, amount & mask),
+                };
+                self.asm.shift_ir(amount, dst, dst, kind, size);
+                context.stack.push(typed_reg.into());
+            }
+            None => {
+                let src = context.pop_to_reg(self, None);
+                let dst = context.pop_to_reg(self, None);
+                let kind = match kind {
+                    // Left rotations are lowered as right rotations by
+                    // the negated amount.
+                    ShiftKind::Rotl => {
+                        self.asm.neg_rr(src.reg, src.reg, size);
+                        ShiftKind::Rotr
+                    }
+                    kind => kind,
+                };
+                self.asm.shift_rrr(src.reg, dst.reg, dst.reg, kind, size);
+                context.free_reg(src);
+                context.stack.push(dst.into());
+            }
+        }
     }
 
-    fn div(&mut self, _context: &mut CodeGenContext, _kind: DivKind, _size: OperandSize) {
-        todo!()
+    fn div(&mut self, context: &mut CodeGenContext, kind: DivKind, size: OperandSize) {
+        let divisor = context.pop_to_reg(self, None);
+        let dividend = context.pop_to_reg(self, None);
+
+        self.emit_div_by_zero_check(divisor.reg, size);
+        if let DivKind::Signed = kind {
+            // Trap on `MIN / -1`: compare the divisor against -1 and,
+            // only if equal, compare the dividend against 1, which
+            // overflows only for the minimum value.
+            self.asm.cmn_ir(1, divisor.reg, size);
+            self.asm.ccmp_ir(
+                1,
+                dividend.reg,
+                NZCV::new(false, false, false, false),
+                Cond::Eq,
+                size,
+            );
+            self.asm.trapif(Cond::Vs, TrapCode::IntegerOverflow);
+        }
+        self.asm
+            .div_rrr(divisor.reg, dividend.reg, dividend.reg, kind, size);
+
+        context.free_reg(divisor);
+        context.stack.push(dividend.into());
     }
 
-    fn rem(&mut self, _context: &mut CodeGenContext, _kind: RemKind, _size: OperandSize) {
-        todo!()
+    fn rem(&mut self, context: &mut CodeGenContext, kind: RemKind, size: OperandSize) {
+        let divisor = context.pop_to_reg(self, None);
+        let dividend = context.pop_to_reg(self, None);
+
+        self.emit_div_by_zero_check(divisor.reg, size);
+        let kind = match kind {
+            RemKind::Signed => DivKind::Signed,
+            RemKind::Unsigned => DivKind::Unsigned,
+        };
+        // rem = dividend - (dividend / divisor) * divisor. The
+        // quotient of `MIN / -1` doesn't trap and results in a
+        // remainder of zero.
+        let scratch = regs::scratch();
+        self.asm
+            .div_rrr(divisor.reg, dividend.reg, scratch, kind, size);
+        self.asm
+            .msub_rrrr(divisor.reg, scratch, dividend.reg, dividend.reg, size);
+
+        context.free_reg(divisor);
+        context.stack.push(dividend.into());
     }
 
     fn zero(&mut self, reg: Reg) {
         self.asm.load_constant(0, reg);
     }
 
-    fn popcnt(&mut self, _context: &mut CodeGenContext, _size: OperandSize) {
-        todo!()
+    fn popcnt(&mut self, context: &mut CodeGenContext, size: OperandSize) {
+        let src = context.pop_to_reg(self, None);
+        let tmp = regs::float_scratch();
+        // Count the set bits of each byte and add them up through the
+        // vector unit.
+        self.asm.mov_to_fpu(src.reg, tmp, size);
+        self.asm.cnt(tmp, tmp);
+        self.asm.addv(tmp, tmp);
+        self.asm.mov_from_vec(tmp, src.reg, OperandSize::S8);
+        context.stack.push(src.into());
     }
 
     fn push(&mut self, reg: Reg, _size: OperandSize) -> StackSlot {
         let size = <Self::ABI as abi::ABI>::word_bytes();
         self.reserve_stack(size);
-        let address = Address::from_shadow_sp(size as i64);
+        let address = Address::from_shadow_sp(0);
         self.asm.str(reg, address, OperandSize::S64);
 
         StackSlot {
@@ -272,20 +506,216 @@ impl Masm for MacroAssembler {
         Address::offset(reg, offset as i64)
     }
 
-    fn cmp_with_set(&mut self, _src: RegImm, _dst: Reg, _kind: CmpKind, _size: OperandSize) {
-        todo!()
+    fn cmp_with_set(&mut self, src: RegImm, dst: Reg, kind: CmpKind, size: OperandSize) {
+        self.cmp(src, dst, size);
+        self.asm.cset(dst, kind.into());
     }
 
-    fn cmp(&mut self, _src: RegImm, _dest: Reg, _size: OperandSize) {
-        todo!()
+    fn cmp(&mut self, src: RegImm, dst: Reg, size: OperandSize) {
+        match src {
+            RegImm::Imm(v) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+
+                self.asm.cmp_ir(imm, dst, size);
+            }
+            RegImm::Reg(rm) => {
+                self.asm.cmp_rr(rm, dst, size);
+            }
+        }
+    }
+
+    fn clz(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.asm.clz(src, dst, size);
+    }
+
+    fn ctz(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        // ctz(x) = clz(reverse_bits(x)).
+        self.asm.rbit(src, dst, size);
+        self.asm.clz(dst, dst, size);
+    }
+
+    fn float_add(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fadd_rrr(rhs, lhs, dst, size);
+    }
+
+    fn float_sub(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fsub_rrr(rhs, lhs, dst, size);
+    }
+
+    fn float_mul(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fmul_rrr(rhs, lhs, dst, size);
+    }
+
+    fn float_div(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fdiv_rrr(rhs, lhs, dst, size);
+    }
+
+    fn float_min(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fmin_rrr(rhs, lhs, dst, size);
+    }
+
+    fn float_max(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fmax_rrr(rhs, lhs, dst, size);
+    }
+
+    fn float_copysign(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        // There's no scalar copysign instruction; combine the bits
+        // of both operands in general purpose registers instead.
+        let sign = regs::ip1();
+        let magnitude = regs::scratch();
+        let sign_mask = 1u64 << (size.num_bits() - 1);
+
+        self.asm.mov_from_vec(rhs, sign, size);
+        self.asm.and_ir(sign_mask, sign, sign, size);
+        self.asm.mov_from_vec(lhs, magnitude, size);
+        self.asm.and_ir(!sign_mask, magnitude, magnitude, size);
+        self.asm.or_rrr(sign, magnitude, magnitude, size);
+        self.asm.mov_to_fpu(magnitude, dst, size);
+    }
+
+    fn float_neg(&mut self, dst: Reg, size: OperandSize) {
+        self.asm.fneg_rr(dst, dst, size);
+    }
+
+    fn float_abs(&mut self, dst: Reg, size: OperandSize) {
+        self.asm.fabs_rr(dst, dst, size);
+    }
+
+    fn float_sqrt(&mut self, dst: Reg, src: Reg, size: OperandSize) {
+        self.asm.fsqrt_rr(src, dst, size);
+    }
+
+    fn float_round(&mut self, mode: RoundingMode, context: &mut CodeGenContext, size: OperandSize) {
+        context.unop(self, size, &mut |masm: &mut Self, reg, size| {
+            masm.asm.fround_rr(reg, reg, mode, size);
+        });
+    }
+
+    fn float_cmp_with_set(
+        &mut self,
+        src1: Reg,
+        src2: Reg,
+        dst: Reg,
+        kind: FloatCmpKind,
+        size: OperandSize,
+    ) {
+        // The condition codes are chosen so that comparisons
+        // involving NaN (unordered) result in `0`, except for not
+        // equal.
+        let cond = match kind {
+            FloatCmpKind::Eq => Cond::Eq,
+            FloatCmpKind::Ne => Cond::Ne,
+            FloatCmpKind::Lt => Cond::Mi,
+            FloatCmpKind::Gt => Cond::Gt,
+            FloatCmpKind::Le => Cond::Ls,
+            FloatCmpKind::Ge => Cond::Ge,
+        };
+        self.asm.fcmp(src2, src1, size);
+        self.asm.cset(dst, cond);
+    }
+
+    fn signed_truncate(
+        &mut self,
+        dst: Reg,
+        src: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        kind: TruncKind,
+    ) {
+        if kind == TruncKind::Checked {
+            self.emit_float_to_int_checks(src, src_size, dst_size, true);
+        }
+        self.asm
+            .cvt_float_to_int(src, dst, src_size, dst_size, true);
+    }
+
+    fn unsigned_truncate(
+        &mut self,
+        context: &mut CodeGenContext,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        kind: TruncKind,
+    ) {
+        let dst_ty = match dst_size {
+            OperandSize::S32 => WasmType::I32,
+            OperandSize::S64 => WasmType::I64,
+            s => panic!("Invalid operand size {:?} for truncation", s),
+        };
+        let src = context.pop_to_reg(self, None);
+        let dst = context.reg_for_type(dst_ty, self);
+        if kind == TruncKind::Checked {
+            self.emit_float_to_int_checks(src.reg, src_size, dst_size, false);
+        }
+        self.asm
+            .cvt_float_to_int(src.reg, dst, src_size, dst_size, false);
+        context.free_reg(src);
+        context.stack.push(TypedReg::new(dst_ty, dst).into());
+    }
+
+    fn signed_convert(&mut self, dst: Reg, src: Reg, src_size: OperandSize, dst_size: OperandSize) {
+        self.asm
+            .cvt_int_to_float(src, dst, src_size, dst_size, true);
+    }
+
+    fn unsigned_convert(
+        &mut self,
+        context: &mut CodeGenContext,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+    ) {
+        let dst_ty = match dst_size {
+            OperandSize::S32 => WasmType::F32,
+            OperandSize::S64 => WasmType::F64,
+            s => panic!("Invalid operand size {:?} for conversion", s),
+        };
+        let src = context.pop_to_reg(self, None);
+        let dst = context.reg_for_type(dst_ty, self);
+        self.asm
+            .cvt_int_to_float(src.reg, dst, src_size, dst_size, false);
+        context.free_reg(src);
+        context.stack.push(TypedReg::new(dst_ty, dst).into());
+    }
+
+    fn demote(&mut self, dst: Reg, src: Reg) {
+        self.asm
+            .cvt_float_to_float(src, dst, OperandSize::S64, OperandSize::S32);
+    }
+
+    fn promote(&mut self, dst: Reg, src: Reg) {
+        self.asm
+            .cvt_float_to_float(src, dst, OperandSize::S32, OperandSize::S64);
+    }
+
+    fn reinterpret_float_as_int(&mut self, dst: Reg, src: Reg, size: OperandSize) {
+        self.asm.mov_from_vec(src, dst, size);
+    }
+
+    fn reinterpret_int_as_float(&mut self, dst: Reg, src: Reg, size: OperandSize) {
+        self.asm.mov_to_fpu(src, dst, size);
     }
 
-    fn clz(&mut self, _src: Reg, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn extend(&mut self, dst: Reg, src: Reg, kind: ExtendKind) {
+        self.asm.extend(src, dst, kind);
     }
 
-    fn ctz(&mut self, _src: Reg, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn checked_uadd(&mut self, dst: Reg, lhs: Reg, rhs: RegImm, size: OperandSize, trap: TrapCode) {
+        match rhs {
+            RegImm::Imm(v) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+                self.asm.adds_ir(imm, lhs, dst, size);
+            }
+            RegImm::Reg(rm) => self.asm.adds_rrr(rm, lhs, dst, size),
+        }
+        // Unsigned overflow sets the carry flag.
+        self.asm.trapif(Cond::Hs, trap);
     }
 
     fn get_label(&mut self) -> MachLabel {
@@ -299,21 +729,67 @@ impl Masm for MacroAssembler {
 
     fn branch(
         &mut self,
-        _kind: CmpKind,
-        _lhs: RegImm,
-        _rhs: RegImm,
-        _taken: MachLabel,
-        _size: OperandSize,
+        kind: CmpKind,
+        lhs: RegImm,
+        rhs: RegImm,
+        taken: MachLabel,
+        size: OperandSize,
     ) {
-        todo!()
+        use CmpKind::*;
+
+        match &(lhs, rhs) {
+            (RegImm::Reg(rlhs), RegImm::Reg(rrhs)) => {
+                // If the comparision kind is zero or not zero and both operands
+                // are the same register, emit a test instruction. Else we emit
+                // a normal comparison.
+                if (kind == Eq || kind == Ne) && (rlhs == rrhs) {
+                    self.asm.tst_rr(*rrhs, *rlhs, size);
+                } else {
+                    self.cmp(lhs, rhs.get_reg().unwrap(), size);
+                }
+            }
+            _ => self.cmp(lhs, rhs.get_reg().unwrap(), size),
+        }
+        self.asm.jmp_if(kind.into(), taken);
     }
 
-    fn jmp(&mut self, _target: MachLabel) {
-        todo!()
+    fn jmp(&mut self, target: MachLabel) {
+        self.asm.jmp(target);
+    }
+
+    fn jmp_table(&mut self, targets: &[MachLabel], index: Reg, tmp: Reg) {
+        // At least one default target.
+        assert!(targets.len() >= 1);
+        let default_index = targets.len() - 1;
+        // The jump table sequence branches to the default target if
+        // the index is greater than or equal to the number of targets.
+        self.asm
+            .cmp_ir(default_index as u64, index, OperandSize::S32);
+
+        let default = targets[default_index];
+        let rest = &targets[0..default_index];
+        self.asm
+            .jmp_table(rest, default, index, regs::scratch(), tmp);
+    }
+
+    fn cmov(&mut self, src: Reg, dst: Reg, kind: CmpKind, size: OperandSize) {
+        match src.class() {
+            RegClass::Int => self.asm.csel(src, dst, dst, kind.into()),
+            RegClass::Float => self.asm.fpu_csel(src, dst, dst, kind.into(), size),
+            RegClass::Vector => unreachable!(),
+        }
     }
 
     fn unreachable(&mut self) {
-        todo!()
+        self.asm.udf(TrapCode::UnreachableCodeReached);
+    }
+
+    fn trap(&mut self, code: TrapCode) {
+        self.asm.udf(code);
+    }
+
+    fn trapif(&mut self, kind: CmpKind, code: TrapCode) {
+        self.asm.trapif(kind.into(), code);
     }
 }
 
@@ -322,6 +798,69 @@ impl MacroAssembler {
         self.sp_offset += bytes;
     }
 
+    fn decrement_sp(&mut self, bytes: u32) {
+        assert!(
+            self.sp_offset >= bytes,
+            "sp offset = {}; bytes = {}",
+            self.sp_offset,
+            bytes
+        );
+        self.sp_offset -= bytes;
+    }
+
+    // Traps with `IntegerDivisionByZero` if the divisor is zero.
+    fn emit_div_by_zero_check(&mut self, divisor: Reg, size: OperandSize) {
+        self.asm.tst_rr(divisor, divisor, size);
+        self.asm.trapif(Cond::Eq, TrapCode::IntegerDivisionByZero);
+    }
+
+    // Emits the checks needed by the trapping float to integer
+    // conversions: NaN traps with `BadConversionToInteger` and values
+    // out of the range of the destination integer type trap with
+    // `IntegerOverflow`. The bounds are exclusive and exactly
+    // representable in the source type.
+    fn emit_float_to_int_checks(
+        &mut self,
+        src: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        signed: bool,
+    ) {
+        use OperandSize::*;
+
+        self.asm.fcmp(src, src, src_size);
+        self.asm.trapif(Cond::Vs, TrapCode::BadConversionToInteger);
+
+        let (min, min_cond) = match (signed, src_size, dst_size) {
+            (true, S32, S32) => (i32::MIN as f64, Cond::Lt),
+            (true, S64, S32) => (i32::MIN as f64 - 1., Cond::Le),
+            (true, _, S64) => (i64::MIN as f64, Cond::Lt),
+            (false, _, _) => (-1., Cond::Le),
+            (_, src, dst) => panic!("Invalid conversion from {:?} to {:?}", src, dst),
+        };
+        let max = match (signed, dst_size) {
+            (true, S32) => i32::MAX as f64 + 1.,
+            (true, S64) => (i64::MAX as u64 + 1) as f64,
+            (false, S32) => u32::MAX as f64 + 1.,
+            (false, S64) => (u64::MAX as u128 + 1) as f64,
+            (_, s) => panic!("Invalid operand size {:?} for truncation", s),
+        };
+
+        let bound = regs::float_scratch();
+        for (value, cond) in [(min, min_cond), (max, Cond::Ge)] {
+            let bits = match src_size {
+                S32 => (value as f32).to_bits() as u64,
+                S64 => value.to_bits(),
+                s => panic!("Invalid operand size {:?} for truncation", s),
+            };
+            let scratch = regs::scratch();
+            self.asm.load_constant(bits, scratch);
+            self.asm.mov_to_fpu(scratch, bound, src_size);
+            self.asm.fcmp(bound, src, src_size);
+            self.asm.trapif(cond, TrapCode::IntegerOverflow);
+        }
+    }
+
     // Copies the value of the stack pointer to the shadow stack
     // pointer: mov x28, sp
 
@@ -1,4 +1,4 @@
-use self::regs::{scratch, ALL_GPR};
+use self::regs::{ALL_FPR, ALL_GPR};
 use crate::{
     abi::ABI,
     codegen::{CodeGen, CodeGenContext, FuncEnv},
@@ -8,6 +8,7 @@ use crate::{
     regalloc::RegAlloc,
     regset::RegSet,
     stack::Stack,
+    trampoline::Trampoline,
     TrampolineKind,
 };
 use anyhow::Result;
@@ -17,7 +18,7 @@ use cranelift_codegen::{MachTextSectionBuilder, TextSectionBuilder};
 use masm::MacroAssembler as Aarch64Masm;
 use target_lexicon::Triple;
 use wasmparser::{FuncValidator, FunctionBody, ValidatorResources};
-use wasmtime_environ::{ModuleTranslation, WasmFuncType};
+use wasmtime_environ::{ModuleTranslation, Tunables, WasmFuncType};
 
 mod abi;
 mod address;
@@ -87,6 +88,7 @@ impl TargetIsa for Aarch64 {
         sig: &WasmFuncType,
         body: &FunctionBody,
         translation: &ModuleTranslation,
+        tunables: &Tunables,
         validator: &mut FuncValidator<ValidatorResources>,
     ) -> Result<MachBufferFinalized<Final>> {
         let mut body = body.get_binary_reader();
@@ -96,14 +98,15 @@ impl TargetIsa for Aarch64 {
 
         let defined_locals = DefinedLocals::new(translation, &mut body, validator)?;
         let frame = Frame::new::<abi::Aarch64ABI>(&abi_sig, &defined_locals)?;
-        // TODO: Add floating point bitmask
-        let regalloc = RegAlloc::new(RegSet::new(ALL_GPR, 0), scratch());
+        let regalloc = RegAlloc::new(RegSet::new(ALL_GPR, ALL_FPR), regs::scratch());
         let codegen_context = CodeGenContext::new(regalloc, stack, &frame);
-        let env = FuncEnv::new(self.pointer_bytes(), translation);
+        let env = FuncEnv::new(self.pointer_bytes(), translation, tunables);
         let mut codegen = CodeGen::new(&mut masm, codegen_context, env, abi_sig);
 
         codegen.emit(&mut body, validator)?;
-        Ok(masm.finalize())
+        let base = codegen.source_location.base;
+
+        Ok(masm.finalize(base))
     }
 
     fn text_section_builder(&self, num_funcs: usize) -> Box<dyn TextSectionBuilder> {
@@ -119,9 +122,28 @@ impl TargetIsa for Aarch64 {
 
     fn compile_trampoline(
         &self,
-        _ty: &WasmFuncType,
-        _kind: TrampolineKind,
+        ty: &WasmFuncType,
+        kind: TrampolineKind,
     ) -> Result<MachBufferFinalized<Final>> {
-        todo!()
+        use TrampolineKind::*;
+
+        let mut masm = Aarch64Masm::new(self.shared_flags.clone());
+        let call_conv = self.wasmtime_call_conv();
+
+        let mut trampoline = Trampoline::new(
+            &mut masm,
+            regs::scratch(),
+            regs::argv(),
+            &call_conv,
+            self.pointer_bytes(),
+        );
+
+        match kind {
+            ArrayToWasm(idx) => trampoline.emit_array_to_wasm(ty, idx)?,
+            NativeToWasm(idx) => trampoline.emit_native_to_wasm(ty, idx)?,
+            WasmToNative => trampoline.emit_wasm_to_native(ty)?,
+        }
+
+        Ok(masm.finalize(None))
     }
 }
@@ -33,6 +33,23 @@ pub(crate) const fn ip1() -> Reg {
     xreg(17)
 }
 
+/// This register is used as a scratch register, in the context of trampolines only,
+/// where we assume that callee-saved registers are given the correct handling
+/// according to the system ABI. x19 is chosen given that it's a callee-saved,
+/// non-argument register.
+///
+/// In the context of all other internal functions, this register is not excluded
+/// from register allocation, so no extra assumptions should be made regarding
+/// its availability.
+pub(crate) const fn argv() -> Reg {
+    xreg(19)
+}
+
+/// Scratch register for floating point operations.
+pub(crate) const fn float_scratch() -> Reg {
+    vreg(31)
+}
+
 /// Register used to carry platform state.
 const fn platform() -> Reg {
     xreg(18)
@@ -143,6 +160,12 @@ const NON_ALLOCATABLE_GPR: u32 = (1 << ip0().hw_enc())
 /// Bitmask to represent the available general purpose registers.
 pub(crate) const ALL_GPR: u32 = u32::MAX & !NON_ALLOCATABLE_GPR;
 
+// V31: Is used as the scratch register for floating point operations.
+const NON_ALLOCATABLE_FPR: u32 = 1 << float_scratch().hw_enc();
+
+/// Bitmask to represent the available floating point registers.
+pub(crate) const ALL_FPR: u32 = u32::MAX & !NON_ALLOCATABLE_FPR;
+
 /// Returns the callee-saved registers.
 ///
 /// This function will return the set of registers that need to be saved
@@ -160,7 +183,6 @@ pub(crate) fn callee_saved() -> SmallVec<[(Reg, OperandSize); 18]> {
         xreg(25),
         xreg(26),
         xreg(27),
-        xreg(28),
         vreg(8),
         vreg(9),
         vreg(10),
@@ -133,7 +133,7 @@ impl ABI for X64ABI {
             match ty {
                 // The `Default`, `WasmtimeFastcall` and `WasmtimeSystemV use `rax` and `xmm0`.
                 // NOTE This should be updated when supporting multi-value.
-                WasmType::I32 | WasmType::I64 => regs::rax(),
+                WasmType::I32 | WasmType::I64 | WasmType::Ref(_) => regs::rax(),
                 WasmType::F32 | WasmType::F64 => regs::xmm0(),
                 t => panic!("Unsupported return type {:?}", t),
             }
@@ -164,7 +164,7 @@ impl ABI for X64ABI {
 
     fn stack_arg_slot_size_for_type(ty: WasmType) -> u32 {
         match ty {
-            WasmType::F64 | WasmType::I32 | WasmType::I64 => Self::word_bytes(),
+            WasmType::F64 | WasmType::I32 | WasmType::I64 | WasmType::Ref(_) => Self::word_bytes(),
             WasmType::F32 => Self::word_bytes() / 2,
             _ => unreachable!(),
         }
@@ -179,7 +179,7 @@ impl X64ABI {
         fastcall: bool,
     ) -> ABIArg {
         let (reg, ty) = match wasm_arg {
-            ty @ (WasmType::I32 | WasmType::I64) => {
+            ty @ (WasmType::I32 | WasmType::I64 | WasmType::Ref(_)) => {
                 (Self::int_reg_for(index_env.next_gpr(), fastcall), ty)
             }
 
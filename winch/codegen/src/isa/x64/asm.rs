@@ -2,30 +2,33 @@
 
 use crate::{
     isa::reg::Reg,
-    masm::{CalleeKind, CmpKind, DivKind, OperandSize, RemKind, ShiftKind},
+    masm::{
+        CalleeKind, CmpKind, DivKind, ExtendKind, FloatCmpKind, OperandSize, RemKind, RoundingMode,
+        ShiftKind,
+    },
 };
 use cranelift_codegen::{
     entity::EntityRef,
-    ir::{ConstantPool, TrapCode},
+    ir::{types, ConstantPool, MemFlags, SourceLoc, TrapCode},
     ir::{ExternalName, Opcode, UserExternalNameRef},
     isa::{
         x64::{
             args::{
                 self, AluRmiROpcode, Amode, CmpOpcode, DivSignedness, ExtMode, FromWritableReg,
-                Gpr, GprMem, GprMemImm, Imm8Gpr, Imm8Reg, RegMem, RegMemImm,
+                Gpr, GprMem, GprMemImm, Imm8Gpr, Imm8Reg, RegMem, RegMemImm, RoundImm,
                 ShiftKind as CraneliftShiftKind, SseOpcode, SyntheticAmode, WritableGpr,
-                WritableXmm, Xmm, XmmMem, CC,
+                WritableXmm, Xmm, XmmMem, XmmMemAligned, CC,
             },
             settings as x64_settings, CallInfo, EmitInfo, EmitState, Inst,
         },
         CallConv,
     },
     settings, Final, MachBuffer, MachBufferFinalized, MachInstEmit, MachInstEmitState, MachLabel,
-    VCodeConstantData, VCodeConstants, Writable,
+    RelocDistance, VCodeConstantData, VCodeConstants, Writable,
 };
 
-use super::address::Address;
-use smallvec::smallvec;
+use super::{address::Address, regs};
+use smallvec::{smallvec, SmallVec};
 
 // Conversions between winch-codegen x64 types and cranelift-codegen x64 types.
 
@@ -85,9 +88,23 @@ impl From<Reg> for Xmm {
     }
 }
 
+impl From<Reg> for XmmMem {
+    fn from(reg: Reg) -> Self {
+        XmmMem::new(RegMem::reg(reg.into())).expect("valid XmmMem")
+    }
+}
+
+impl From<Reg> for XmmMemAligned {
+    fn from(reg: Reg) -> Self {
+        XmmMemAligned::new(RegMem::reg(reg.into())).expect("valid XmmMemAligned")
+    }
+}
+
 impl From<OperandSize> for args::OperandSize {
     fn from(size: OperandSize) -> Self {
         match size {
+            OperandSize::S8 => Self::Size8,
+            OperandSize::S16 => Self::Size16,
             OperandSize::S32 => Self::Size32,
             OperandSize::S64 => Self::Size64,
             s => panic!("Invalid operand size {:?}", s),
@@ -95,6 +112,18 @@ impl From<OperandSize> for args::OperandSize {
     }
 }
 
+impl From<ExtendKind> for ExtMode {
+    fn from(value: ExtendKind) -> Self {
+        match value {
+            ExtendKind::I32Extend8S => ExtMode::BL,
+            ExtendKind::I32Extend16S => ExtMode::WL,
+            ExtendKind::I64Extend8S => ExtMode::BQ,
+            ExtendKind::I64Extend16S => ExtMode::WQ,
+            ExtendKind::I64Extend32S | ExtendKind::I64ExtendI32U => ExtMode::LQ,
+        }
+    }
+}
+
 impl From<DivKind> for DivSignedness {
     fn from(kind: DivKind) -> DivSignedness {
         match kind {
@@ -121,6 +150,17 @@ impl From<CmpKind> for CC {
     }
 }
 
+impl From<RoundingMode> for RoundImm {
+    fn from(value: RoundingMode) -> Self {
+        match value {
+            RoundingMode::Nearest => RoundImm::RoundNearest,
+            RoundingMode::Up => RoundImm::RoundUp,
+            RoundingMode::Down => RoundImm::RoundDown,
+            RoundingMode::Zero => RoundImm::RoundZero,
+        }
+    }
+}
+
 impl From<ShiftKind> for CraneliftShiftKind {
     fn from(value: ShiftKind) -> Self {
         match value {
@@ -162,6 +202,11 @@ impl Assembler {
         }
     }
 
+    /// Get a reference to the underlying machine buffer.
+    pub fn buffer(&self) -> &MachBuffer<Inst> {
+        &self.buffer
+    }
+
     /// Get a mutable reference to underlying
     /// machine buffer.
     pub fn buffer_mut(&mut self) -> &mut MachBuffer<Inst> {
@@ -175,11 +220,11 @@ impl Assembler {
     }
 
     /// Return the emitted code.
-    pub fn finalize(mut self) -> MachBufferFinalized<Final> {
+    pub fn finalize(mut self, base: Option<SourceLoc>) -> MachBufferFinalized<Final> {
         let stencil = self
             .buffer
             .finish(&self.constants, self.emit_state.ctrl_plane_mut());
-        stencil.apply_base_srcloc(Default::default())
+        stencil.apply_base_srcloc(base.unwrap_or_default())
     }
 
     fn emit(&mut self, inst: Inst) {
@@ -191,11 +236,14 @@ impl Assembler {
         pool: &mut ConstantPool,
         constants: &mut VCodeConstants,
         buffer: &mut MachBuffer<Inst>,
+        flags: MemFlags,
     ) -> SyntheticAmode {
         match addr {
-            Address::Offset { base, offset } => {
-                SyntheticAmode::real(Amode::imm_reg(*offset as i32, (*base).into()))
-            }
+            Address::Offset { base, offset } => SyntheticAmode::real(Amode::ImmReg {
+                simm32: *offset as i32,
+                base: (*base).into(),
+                flags,
+            }),
             Address::Const(c) => {
                 // Defer the creation of the
                 // `SyntheticAmode::ConstantOffset` addressing mode
@@ -240,10 +288,15 @@ impl Assembler {
     }
 
     /// Register-to-memory move.
-    pub fn mov_rm(&mut self, src: Reg, addr: &Address, size: OperandSize) {
+    pub fn mov_rm(&mut self, src: Reg, addr: &Address, size: OperandSize, flags: MemFlags) {
         assert!(addr.is_offset());
-        let dst =
-            Self::to_synthetic_amode(addr, &mut self.pool, &mut self.constants, &mut self.buffer);
+        let dst = Self::to_synthetic_amode(
+            addr,
+            &mut self.pool,
+            &mut self.constants,
+            &mut self.buffer,
+            flags,
+        );
         self.emit(Inst::MovRM {
             size: size.into(),
             src: src.into(),
@@ -252,10 +305,15 @@ impl Assembler {
     }
 
     /// Immediate-to-memory move.
-    pub fn mov_im(&mut self, src: u64, addr: &Address, size: OperandSize) {
+    pub fn mov_im(&mut self, src: u64, addr: &Address, size: OperandSize, flags: MemFlags) {
         assert!(addr.is_offset());
-        let dst =
-            Self::to_synthetic_amode(addr, &mut self.pool, &mut self.constants, &mut self.buffer);
+        let dst = Self::to_synthetic_amode(
+            addr,
+            &mut self.pool,
+            &mut self.constants,
+            &mut self.buffer,
+            flags,
+        );
         self.emit(Inst::MovImmM {
             size: size.into(),
             simm64: src,
@@ -276,11 +334,18 @@ impl Assembler {
     }
 
     /// Memory-to-register load.
-    pub fn mov_mr(&mut self, addr: &Address, dst: Reg, size: OperandSize) {
-        use OperandSize::S64;
+    ///
+    /// Loads narrower than 64 bits are zero extended.
+    pub fn mov_mr(&mut self, addr: &Address, dst: Reg, size: OperandSize, flags: MemFlags) {
+        use OperandSize::*;
 
-        let src =
-            Self::to_synthetic_amode(addr, &mut self.pool, &mut self.constants, &mut self.buffer);
+        let src = Self::to_synthetic_amode(
+            addr,
+            &mut self.pool,
+            &mut self.constants,
+            &mut self.buffer,
+            flags,
+        );
 
         if size == S64 {
             self.emit(Inst::Mov64MR {
@@ -288,17 +353,77 @@ impl Assembler {
                 dst: dst.into(),
             });
         } else {
+            let ext_mode = match size {
+                S8 => ExtMode::BQ,
+                S16 => ExtMode::WQ,
+                S32 => ExtMode::LQ,
+                _ => unreachable!(),
+            };
             let reg_mem = RegMem::mem(src);
             self.emit(Inst::MovzxRmR {
-                ext_mode: ExtMode::LQ,
+                ext_mode,
                 src: GprMem::new(reg_mem).expect("valid memory address"),
                 dst: dst.into(),
             });
         }
     }
 
+    /// Memory-to-register load with sign extension.
+    pub fn movsx_mr(&mut self, addr: &Address, dst: Reg, kind: ExtendKind, flags: MemFlags) {
+        let src = Self::to_synthetic_amode(
+            addr,
+            &mut self.pool,
+            &mut self.constants,
+            &mut self.buffer,
+            flags,
+        );
+
+        let reg_mem = RegMem::mem(src);
+        self.emit(Inst::MovsxRmR {
+            ext_mode: kind.into(),
+            src: GprMem::new(reg_mem).expect("valid memory address"),
+            dst: dst.into(),
+        });
+    }
+
+    /// Register-to-register move with zero extension.
+    pub fn movzx_rr(&mut self, src: Reg, dst: Reg, kind: ExtendKind) {
+        self.emit(Inst::MovzxRmR {
+            ext_mode: kind.into(),
+            src: src.into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Register-to-register move with sign extension.
+    pub fn movsx_rr(&mut self, src: Reg, dst: Reg, kind: ExtendKind) {
+        self.emit(Inst::MovsxRmR {
+            ext_mode: kind.into(),
+            src: src.into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Floating point register-to-register move.
+    pub fn xmm_mov_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        use OperandSize::*;
+
+        let op = match size {
+            S32 => SseOpcode::Movaps,
+            S64 => SseOpcode::Movapd,
+            S128 => SseOpcode::Movdqa,
+            S8 | S16 => unreachable!(),
+        };
+
+        self.emit(Inst::XmmUnaryRmRUnaligned {
+            op,
+            src: src.into(),
+            dst: dst.into(),
+        });
+    }
+
     /// Single and double precision floating point load.
-    pub fn xmm_mov_mr(&mut self, src: &Address, dst: Reg, size: OperandSize) {
+    pub fn xmm_mov_mr(&mut self, src: &Address, dst: Reg, size: OperandSize, flags: MemFlags) {
         use OperandSize::*;
 
         assert!(dst.is_float());
@@ -306,10 +431,16 @@ impl Assembler {
             S32 => SseOpcode::Movss,
             S64 => SseOpcode::Movsd,
             S128 => SseOpcode::Movdqu,
+            S8 | S16 => unreachable!(),
         };
 
-        let src =
-            Self::to_synthetic_amode(src, &mut self.pool, &mut self.constants, &mut self.buffer);
+        let src = Self::to_synthetic_amode(
+            src,
+            &mut self.pool,
+            &mut self.constants,
+            &mut self.buffer,
+            flags,
+        );
         self.emit(Inst::XmmUnaryRmRUnaligned {
             op,
             src: XmmMem::new(RegMem::mem(src)).expect("valid xmm unaligned"),
@@ -318,7 +449,7 @@ impl Assembler {
     }
 
     /// Single and double precision floating point store.
-    pub fn xmm_mov_rm(&mut self, src: Reg, dst: &Address, size: OperandSize) {
+    pub fn xmm_mov_rm(&mut self, src: Reg, dst: &Address, size: OperandSize, flags: MemFlags) {
         use OperandSize::*;
 
         assert!(src.is_float());
@@ -327,10 +458,16 @@ impl Assembler {
             S32 => SseOpcode::Movss,
             S64 => SseOpcode::Movsd,
             S128 => SseOpcode::Movdqu,
+            S8 | S16 => unreachable!(),
         };
 
-        let dst =
-            Self::to_synthetic_amode(dst, &mut self.pool, &mut self.constants, &mut self.buffer);
+        let dst = Self::to_synthetic_amode(
+            dst,
+            &mut self.pool,
+            &mut self.constants,
+            &mut self.buffer,
+            flags,
+        );
         self.emit(Inst::XmmMovRM {
             op,
             src: src.into(),
@@ -338,6 +475,314 @@ impl Assembler {
         });
     }
 
+    /// Emit a scalar floating point binary operation with register
+    /// operands, selecting the single or double precision opcode
+    /// according to the operand size.
+    fn xmm_rm_r(&mut self, ops: (SseOpcode, SseOpcode), src: Reg, dst: Reg, size: OperandSize) {
+        let op = match size {
+            OperandSize::S32 => ops.0,
+            OperandSize::S64 => ops.1,
+            s => panic!("Invalid operand size {:?} for floating point operation", s),
+        };
+
+        self.emit(Inst::XmmRmRUnaligned {
+            op,
+            src1: dst.into(),
+            src2: src.into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Floating point add.
+    pub fn xmm_add_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.xmm_rm_r((SseOpcode::Addss, SseOpcode::Addsd), src, dst, size);
+    }
+
+    /// Floating point subtraction.
+    pub fn xmm_sub_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.xmm_rm_r((SseOpcode::Subss, SseOpcode::Subsd), src, dst, size);
+    }
+
+    /// Floating point multiplication.
+    pub fn xmm_mul_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.xmm_rm_r((SseOpcode::Mulss, SseOpcode::Mulsd), src, dst, size);
+    }
+
+    /// Floating point division.
+    pub fn xmm_div_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.xmm_rm_r((SseOpcode::Divss, SseOpcode::Divsd), src, dst, size);
+    }
+
+    /// Bitwise and of floating point registers.
+    pub fn xmm_and_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.xmm_rm_r((SseOpcode::Andps, SseOpcode::Andpd), src, dst, size);
+    }
+
+    /// Bitwise or of floating point registers.
+    pub fn xmm_or_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.xmm_rm_r((SseOpcode::Orps, SseOpcode::Orpd), src, dst, size);
+    }
+
+    /// Bitwise exclusive or of floating point registers.
+    pub fn xmm_xor_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.xmm_rm_r((SseOpcode::Xorps, SseOpcode::Xorpd), src, dst, size);
+    }
+
+    /// Floating point minimum or maximum, with the correct handling of
+    /// NaNs and signed zeros.
+    pub fn xmm_min_max_seq(&mut self, src: Reg, dst: Reg, size: OperandSize, is_min: bool) {
+        self.emit(Inst::XmmMinMaxSeq {
+            size: size.into(),
+            is_min,
+            lhs: src.into(),
+            rhs: dst.into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Floating point square root.
+    pub fn xmm_sqrt_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        let op = match size {
+            OperandSize::S32 => SseOpcode::Sqrtss,
+            OperandSize::S64 => SseOpcode::Sqrtsd,
+            s => panic!("Invalid operand size {:?} for floating point operation", s),
+        };
+
+        self.emit(Inst::XmmUnaryRmRUnaligned {
+            op,
+            src: src.into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Floating point rounding.
+    /// Requires the `has_sse41` flag.
+    pub fn xmm_rounds_rr(&mut self, src: Reg, dst: Reg, mode: RoundingMode, size: OperandSize) {
+        assert!(self.isa_flags.has_sse41(), "Requires has_sse41 flag");
+        let op = match size {
+            OperandSize::S32 => SseOpcode::Roundss,
+            OperandSize::S64 => SseOpcode::Roundsd,
+            s => panic!("Invalid operand size {:?} for floating point operation", s),
+        };
+
+        self.emit(Inst::XmmUnaryRmRImm {
+            op,
+            src: src.into(),
+            imm: RoundImm::from(mode) as u8,
+            dst: dst.into(),
+        });
+    }
+
+    /// Unordered floating point comparison, setting the status flags.
+    pub fn ucomis(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        let op = match size {
+            OperandSize::S32 => SseOpcode::Ucomiss,
+            OperandSize::S64 => SseOpcode::Ucomisd,
+            s => panic!("Invalid operand size {:?} for floating point operation", s),
+        };
+
+        self.emit(Inst::XmmCmpRmR {
+            op,
+            src: src.into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Set `dst` to `0` or `1` according to the floating point
+    /// comparison described by [`FloatCmpKind`] between `src1` and
+    /// `src2`.
+    ///
+    /// The comparisons are ordered: any comparison involving a NaN,
+    /// except for not equal, results in `0`.
+    pub fn float_cmp_with_set(
+        &mut self,
+        src1: Reg,
+        src2: Reg,
+        dst: Reg,
+        kind: FloatCmpKind,
+        size: OperandSize,
+    ) {
+        // `ucomis` sets the parity flag for unordered operands. Greater
+        // than and greater than or equal are checked through the carry
+        // and zero flags, which are set for unordered operands, so less
+        // than and less than or equal are lowered by swapping the operands.
+        let (src, cmp_dst, cc) = match kind {
+            FloatCmpKind::Eq | FloatCmpKind::Ne => (src2, src1, None),
+            FloatCmpKind::Gt => (src2, src1, Some(CC::NBE)),
+            FloatCmpKind::Ge => (src2, src1, Some(CC::NB)),
+            FloatCmpKind::Lt => (src1, src2, Some(CC::NBE)),
+            FloatCmpKind::Le => (src1, src2, Some(CC::NB)),
+        };
+        self.ucomis(src, cmp_dst, size);
+
+        match (kind, cc) {
+            (_, Some(cc)) => self.setcc(cc, dst),
+            // Equal: ZF == 1 && PF == 0.
+            (FloatCmpKind::Eq, None) => {
+                let scratch = regs::scratch();
+                self.setcc(CC::NP, dst);
+                self.setcc(CC::Z, scratch);
+                self.and_rr(scratch, dst, OperandSize::S32);
+            }
+            // Not equal: ZF == 0 || PF == 1.
+            (FloatCmpKind::Ne, None) => {
+                let scratch = regs::scratch();
+                self.setcc(CC::P, dst);
+                self.setcc(CC::NZ, scratch);
+                self.or_rr(scratch, dst, OperandSize::S32);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Move the bits of a general purpose register to a floating point
+    /// register.
+    pub fn gpr_to_xmm(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        let op = match size {
+            OperandSize::S32 => SseOpcode::Movd,
+            OperandSize::S64 => SseOpcode::Movq,
+            s => panic!("Invalid operand size {:?} for move", s),
+        };
+
+        self.emit(Inst::GprToXmm {
+            op,
+            src: src.into(),
+            dst: dst.into(),
+            src_size: size.into(),
+        });
+    }
+
+    /// Move the bits of a floating point register to a general purpose
+    /// register.
+    pub fn xmm_to_gpr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        let op = match size {
+            OperandSize::S32 => SseOpcode::Movd,
+            OperandSize::S64 => SseOpcode::Movq,
+            s => panic!("Invalid operand size {:?} for move", s),
+        };
+
+        self.emit(Inst::XmmToGpr {
+            op,
+            src: src.into(),
+            dst: dst.into(),
+            dst_size: size.into(),
+        });
+    }
+
+    /// Convert a floating point value to a signed integer.
+    ///
+    /// For checked conversions the emitted sequence traps if the value
+    /// is NaN or out of range.
+    pub fn cvt_float_to_sint_seq(
+        &mut self,
+        src: Reg,
+        dst: Reg,
+        tmp_gpr: Reg,
+        tmp_xmm: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        is_saturating: bool,
+    ) {
+        self.emit(Inst::CvtFloatToSintSeq {
+            dst_size: dst_size.into(),
+            src_size: src_size.into(),
+            is_saturating,
+            src: src.into(),
+            dst: dst.into(),
+            tmp_gpr: tmp_gpr.into(),
+            tmp_xmm: tmp_xmm.into(),
+        });
+    }
+
+    /// Convert a floating point value to an unsigned integer.
+    ///
+    /// For checked conversions the emitted sequence traps if the value
+    /// is NaN or out of range.
+    pub fn cvt_float_to_uint_seq(
+        &mut self,
+        src: Reg,
+        dst: Reg,
+        tmp_gpr: Reg,
+        tmp_xmm: Reg,
+        tmp_xmm2: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        is_saturating: bool,
+    ) {
+        self.emit(Inst::CvtFloatToUintSeq {
+            dst_size: dst_size.into(),
+            src_size: src_size.into(),
+            is_saturating,
+            src: src.into(),
+            dst: dst.into(),
+            tmp_gpr: tmp_gpr.into(),
+            tmp_xmm: tmp_xmm.into(),
+            tmp_xmm2: tmp_xmm2.into(),
+        });
+    }
+
+    /// Convert a signed integer to a floating point value.
+    pub fn cvt_sint_to_float(
+        &mut self,
+        src: Reg,
+        dst: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+    ) {
+        let op = match dst_size {
+            OperandSize::S32 => SseOpcode::Cvtsi2ss,
+            OperandSize::S64 => SseOpcode::Cvtsi2sd,
+            s => panic!("Invalid operand size {:?} for conversion", s),
+        };
+
+        self.emit(Inst::GprToXmm {
+            op,
+            src: src.into(),
+            dst: dst.into(),
+            src_size: src_size.into(),
+        });
+    }
+
+    /// Convert an unsigned 64-bit integer to a floating point value.
+    pub fn cvt_uint64_to_float_seq(
+        &mut self,
+        src: Reg,
+        dst: Reg,
+        tmp_gpr1: Reg,
+        tmp_gpr2: Reg,
+        dst_size: OperandSize,
+    ) {
+        self.emit(Inst::CvtUint64ToFloatSeq {
+            dst_size: dst_size.into(),
+            src: src.into(),
+            dst: dst.into(),
+            tmp_gpr1: tmp_gpr1.into(),
+            tmp_gpr2: tmp_gpr2.into(),
+        });
+    }
+
+    /// Convert between single and double precision floating point
+    /// values.
+    pub fn cvt_float_to_float(
+        &mut self,
+        src: Reg,
+        dst: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+    ) {
+        let op = match (src_size, dst_size) {
+            (OperandSize::S32, OperandSize::S64) => SseOpcode::Cvtss2sd,
+            (OperandSize::S64, OperandSize::S32) => SseOpcode::Cvtsd2ss,
+            (s, d) => panic!("Invalid operand sizes {:?} -> {:?} for conversion", s, d),
+        };
+
+        self.emit(Inst::XmmUnaryRmRUnaligned {
+            op,
+            src: src.into(),
+            dst: dst.into(),
+        });
+    }
+
     /// Subtract register and register
     pub fn sub_rr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
         self.emit(Inst::AluRmiR {
@@ -656,7 +1101,7 @@ impl Assembler {
 
     /// Set value in dst to `0` or `1` based on flags in status register and
     /// [`CmpKind`].
-    pub fn setcc(&mut self, kind: CmpKind, dst: Reg) {
+    pub fn setcc(&mut self, kind: impl Into<CC>, dst: Reg) {
         // Clear the dst register or bits 1 to 31 may be incorrectly set.
         // Don't use xor since it updates the status register.
         self.emit(Inst::Imm {
@@ -746,6 +1191,29 @@ impl Assembler {
                     }),
                 });
             }
+            CalleeKind::LibCall(lib) => {
+                // Library functions are resolved through absolute
+                // relocations; load the address into the scratch
+                // register and call it.
+                let scratch = regs::scratch();
+                self.emit(Inst::LoadExtName {
+                    dst: Writable::from_reg(scratch.into()),
+                    name: Box::new(ExternalName::LibCall(lib)),
+                    offset: 0,
+                    distance: RelocDistance::Far,
+                });
+                self.emit(Inst::CallUnknown {
+                    dest: RegMem::reg(scratch.into()),
+                    info: Box::new(CallInfo {
+                        uses: smallvec![],
+                        defs: smallvec![],
+                        clobbers: Default::default(),
+                        opcode: Opcode::Call,
+                        callee_pop_size: 0,
+                        callee_conv: CallConv::SystemV,
+                    }),
+                });
+            }
             CalleeKind::Direct(index) => {
                 let dest = ExternalName::user(UserExternalNameRef::new(index as usize));
                 self.emit(Inst::CallKnown {
@@ -763,6 +1231,59 @@ impl Assembler {
         }
     }
 
+    /// Conditional move of `src` into `dst` if the condition
+    /// described by `kind` holds for the flags set by the last
+    /// comparison.
+    pub fn cmov(&mut self, src: Reg, dst: Reg, kind: impl Into<CC>, size: OperandSize) {
+        self.emit(Inst::Cmove {
+            size: size.into(),
+            cc: kind.into(),
+            consequent: src.into(),
+            alternative: dst.into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Conditional move of the floating point value in `src` into
+    /// `dst` if the condition described by `kind` holds for the flags
+    /// set by the last comparison.
+    pub fn xmm_cmov(&mut self, src: Reg, dst: Reg, kind: impl Into<CC>, size: OperandSize) {
+        let ty = match size {
+            OperandSize::S32 => types::F32,
+            OperandSize::S64 => types::F64,
+            _ => unreachable!(),
+        };
+
+        self.emit(Inst::XmmCmove {
+            ty,
+            cc: kind.into(),
+            consequent: src.into(),
+            alternative: dst.into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Emits a jump table sequence. The `index` register is expected
+    /// to be in bounds; the default target is placed at the end of
+    /// the jump table and used for any index equal to the number of
+    /// `targets`.
+    pub fn jmp_table(
+        &mut self,
+        targets: SmallVec<[MachLabel; 4]>,
+        default: MachLabel,
+        index: Reg,
+        tmp1: Reg,
+        tmp2: Reg,
+    ) {
+        self.emit(Inst::JmpTableSeq {
+            idx: index.into(),
+            tmp1: Writable::from_reg(tmp1.into()),
+            tmp2: Writable::from_reg(tmp2.into()),
+            default_target: default,
+            targets: Box::new(targets),
+        });
+    }
+
     /// Emits a conditional jump to the given label.
     pub fn jmp_if(&mut self, cc: impl Into<CC>, taken: MachLabel) {
         self.emit(Inst::JmpIf {
@@ -780,4 +1301,12 @@ impl Assembler {
     pub fn trap(&mut self, code: TrapCode) {
         self.emit(Inst::Ud2 { trap_code: code })
     }
+
+    /// Conditional trap.
+    pub fn trapif(&mut self, cc: impl Into<CC>, trap_code: TrapCode) {
+        self.emit(Inst::TrapIf {
+            cc: cc.into(),
+            trap_code,
+        });
+    }
 }
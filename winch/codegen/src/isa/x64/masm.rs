@@ -6,19 +6,25 @@ use super::{
 };
 
 use crate::masm::{
-    CmpKind, DivKind, Imm as I, MacroAssembler as Masm, OperandSize, RegImm, RemKind, ShiftKind,
+    CmpKind, DivKind, ExtendKind, FloatCmpKind, Imm as I, MacroAssembler as Masm, OperandSize,
+    RegImm, RemKind, RoundingMode, ShiftKind, TruncKind,
 };
 use crate::{abi::ABI, masm::StackSlot};
 use crate::{
     abi::{self, align_to, calculate_frame_adjustment, LocalSlot},
-    codegen::CodeGenContext,
-    stack::Val,
+    codegen::{call::FnCall, CodeGenContext},
+    stack::{TypedReg, Val},
+    CallingConvention,
 };
 use crate::{isa::reg::Reg, masm::CalleeKind};
 use cranelift_codegen::{
-    ir::TrapCode, isa::x64::settings as x64_settings, settings, Final, MachBufferFinalized,
-    MachLabel,
+    binemit::CodeOffset,
+    ir::{LibCall, MemFlags, RelSourceLoc, SourceLoc, TrapCode},
+    isa::x64::settings as x64_settings,
+    settings, Final, MachBufferFinalized, MachLabel,
 };
+use regalloc2::RegClass;
+use wasmtime_environ::{WasmFuncType, WasmType};
 
 /// x64 MacroAssembler.
 pub(crate) struct MacroAssembler {
@@ -53,8 +59,12 @@ impl Masm for MacroAssembler {
         } else {
             let bytes = size.bytes();
             self.reserve_stack(bytes);
-            self.asm
-                .xmm_mov_rm(reg, &self.address_from_sp(self.sp_offset), size);
+            self.asm.xmm_mov_rm(
+                reg,
+                &self.address_from_sp(self.sp_offset),
+                size,
+                MemFlags::trusted(),
+            );
             bytes
         };
 
@@ -111,8 +121,8 @@ impl Masm for MacroAssembler {
     fn store(&mut self, src: RegImm, dst: Address, size: OperandSize) {
         match src {
             RegImm::Imm(imm) => match imm {
-                I::I32(v) => self.asm.mov_im(v as u64, &dst, size),
-                I::I64(v) => self.asm.mov_im(v, &dst, size),
+                I::I32(v) => self.asm.mov_im(v as u64, &dst, size, MemFlags::trusted()),
+                I::I64(v) => self.asm.mov_im(v, &dst, size, MemFlags::trusted()),
                 // Immediate to memory moves are currently only used
                 // to zero a memory range, which only involves
                 // ints. See [`MacroAssembler::zero_mem_range`].
@@ -120,9 +130,9 @@ impl Masm for MacroAssembler {
             },
             RegImm::Reg(reg) => {
                 if reg.is_int() {
-                    self.asm.mov_rm(reg, &dst, size);
+                    self.asm.mov_rm(reg, &dst, size, MemFlags::trusted());
                 } else {
-                    self.asm.xmm_mov_rm(reg, &dst, size);
+                    self.asm.xmm_mov_rm(reg, &dst, size, MemFlags::trusted());
                 }
             }
         }
@@ -134,7 +144,7 @@ impl Masm for MacroAssembler {
             self.decrement_sp(<Self::ABI as abi::ABI>::word_bytes());
         } else {
             let addr = self.address_at_sp(self.sp_offset);
-            self.asm.xmm_mov_mr(&addr, dst, size);
+            self.asm.xmm_mov_mr(&addr, dst, size, MemFlags::trusted());
             self.free_stack(size.bytes());
         }
     }
@@ -157,9 +167,35 @@ impl Masm for MacroAssembler {
 
     fn load(&mut self, src: Address, dst: Reg, size: OperandSize) {
         if dst.is_int() {
-            self.asm.mov_mr(&src, dst, size);
+            self.asm.mov_mr(&src, dst, size, MemFlags::trusted());
+        } else {
+            self.asm.xmm_mov_mr(&src, dst, size, MemFlags::trusted());
+        }
+    }
+
+    fn wasm_load(
+        &mut self,
+        src: Self::Address,
+        dst: Reg,
+        size: OperandSize,
+        kind: Option<ExtendKind>,
+    ) {
+        let flags = MemFlags::new();
+        if dst.is_float() {
+            self.asm.xmm_mov_mr(&src, dst, size, flags);
+        } else if let Some(kind) = kind {
+            self.asm.movsx_mr(&src, dst, kind, flags);
         } else {
-            self.asm.xmm_mov_mr(&src, dst, size);
+            self.asm.mov_mr(&src, dst, size, flags);
+        }
+    }
+
+    fn wasm_store(&mut self, src: Reg, dst: Self::Address, size: OperandSize) {
+        let flags = MemFlags::new();
+        if src.is_int() {
+            self.asm.mov_rm(src, &dst, size, flags);
+        } else {
+            self.asm.xmm_mov_rm(src, &dst, size, flags);
         }
     }
 
@@ -173,17 +209,23 @@ impl Masm for MacroAssembler {
 
     fn mov(&mut self, src: RegImm, dst: RegImm, size: OperandSize) {
         match (src, dst) {
-            (RegImm::Reg(src), RegImm::Reg(dst)) => self.asm.mov_rr(src, dst, size),
+            (RegImm::Reg(src), RegImm::Reg(dst)) => {
+                if src.is_float() {
+                    self.asm.xmm_mov_rr(src, dst, size);
+                } else {
+                    self.asm.mov_rr(src, dst, size);
+                }
+            }
             (RegImm::Imm(imm), RegImm::Reg(dst)) => match imm {
                 I::I32(v) => self.asm.mov_ir(v as u64, dst, size),
                 I::I64(v) => self.asm.mov_ir(v as u64, dst, size),
                 I::F32(v) => {
                     let addr = self.asm.add_constant(v.to_le_bytes().as_slice());
-                    self.asm.xmm_mov_mr(&addr, dst, size);
+                    self.asm.xmm_mov_mr(&addr, dst, size, MemFlags::trusted());
                 }
                 I::F64(v) => {
                     let addr = self.asm.add_constant(v.to_le_bytes().as_slice());
-                    self.asm.xmm_mov_mr(&addr, dst, size);
+                    self.asm.xmm_mov_mr(&addr, dst, size, MemFlags::trusted());
                 }
             },
             _ => Self::handle_invalid_operand_combination(src, dst),
@@ -400,8 +442,22 @@ impl Masm for MacroAssembler {
         self.asm.ret();
     }
 
-    fn finalize(self) -> MachBufferFinalized<Final> {
-        self.asm.finalize()
+    fn finalize(self, base: Option<SourceLoc>) -> MachBufferFinalized<Final> {
+        self.asm.finalize(base)
+    }
+
+    fn start_source_loc(&mut self, loc: RelSourceLoc) -> CodeOffset {
+        let offset = self.current_code_offset();
+        self.asm.buffer_mut().start_srcloc(loc);
+        offset
+    }
+
+    fn end_source_loc(&mut self) {
+        self.asm.buffer_mut().end_srcloc();
+    }
+
+    fn current_code_offset(&self) -> CodeOffset {
+        self.asm.buffer().cur_offset()
     }
 
     fn address_at_reg(&self, reg: Reg, offset: u32) -> Self::Address {
@@ -467,6 +523,232 @@ impl Masm for MacroAssembler {
         }
     }
 
+    fn float_add(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        Self::ensure_two_argument_form(&dst.into(), &lhs.into());
+        self.asm.xmm_add_rr(rhs, dst, size);
+    }
+
+    fn float_sub(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        Self::ensure_two_argument_form(&dst.into(), &lhs.into());
+        self.asm.xmm_sub_rr(rhs, dst, size);
+    }
+
+    fn float_mul(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        Self::ensure_two_argument_form(&dst.into(), &lhs.into());
+        self.asm.xmm_mul_rr(rhs, dst, size);
+    }
+
+    fn float_div(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        Self::ensure_two_argument_form(&dst.into(), &lhs.into());
+        self.asm.xmm_div_rr(rhs, dst, size);
+    }
+
+    fn float_min(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        Self::ensure_two_argument_form(&dst.into(), &lhs.into());
+        self.asm.xmm_min_max_seq(rhs, dst, size, true);
+    }
+
+    fn float_max(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        Self::ensure_two_argument_form(&dst.into(), &lhs.into());
+        self.asm.xmm_min_max_seq(rhs, dst, size, false);
+    }
+
+    fn float_copysign(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        Self::ensure_two_argument_form(&dst.into(), &lhs.into());
+        let scratch_gpr = regs::scratch();
+        let scratch_xmm = regs::scratch_xmm();
+        let sign_mask = Self::float_sign_mask(size);
+
+        // dst = lhs & !sign_mask.
+        self.load_constant(&Self::float_magnitude_mask(size), scratch_gpr, size);
+        self.asm.gpr_to_xmm(scratch_gpr, scratch_xmm, size);
+        self.asm.xmm_and_rr(scratch_xmm, dst, size);
+        // scratch = rhs & sign_mask.
+        self.load_constant(&sign_mask, scratch_gpr, size);
+        self.asm.gpr_to_xmm(scratch_gpr, scratch_xmm, size);
+        self.asm.xmm_and_rr(rhs, scratch_xmm, size);
+        // dst = dst | scratch.
+        self.asm.xmm_or_rr(scratch_xmm, dst, size);
+    }
+
+    fn float_neg(&mut self, dst: Reg, size: OperandSize) {
+        let scratch_gpr = regs::scratch();
+        let scratch_xmm = regs::scratch_xmm();
+        self.load_constant(&Self::float_sign_mask(size), scratch_gpr, size);
+        self.asm.gpr_to_xmm(scratch_gpr, scratch_xmm, size);
+        self.asm.xmm_xor_rr(scratch_xmm, dst, size);
+    }
+
+    fn float_abs(&mut self, dst: Reg, size: OperandSize) {
+        let scratch_gpr = regs::scratch();
+        let scratch_xmm = regs::scratch_xmm();
+        self.load_constant(&Self::float_magnitude_mask(size), scratch_gpr, size);
+        self.asm.gpr_to_xmm(scratch_gpr, scratch_xmm, size);
+        self.asm.xmm_and_rr(scratch_xmm, dst, size);
+    }
+
+    fn float_sqrt(&mut self, dst: Reg, src: Reg, size: OperandSize) {
+        self.asm.xmm_sqrt_rr(src, dst, size);
+    }
+
+    fn float_round(&mut self, mode: RoundingMode, context: &mut CodeGenContext, size: OperandSize) {
+        if self.flags.has_sse41() {
+            context.unop(self, size, &mut |masm: &mut Self, reg, size| {
+                masm.asm.xmm_rounds_rr(reg, reg, mode, size);
+            });
+        } else {
+            // Without SSE4.1, fall back to the library implementation of
+            // the rounding operations.
+            let (ty, libcall) = match (mode, size) {
+                (RoundingMode::Nearest, OperandSize::S32) => (WasmType::F32, LibCall::NearestF32),
+                (RoundingMode::Up, OperandSize::S32) => (WasmType::F32, LibCall::CeilF32),
+                (RoundingMode::Down, OperandSize::S32) => (WasmType::F32, LibCall::FloorF32),
+                (RoundingMode::Zero, OperandSize::S32) => (WasmType::F32, LibCall::TruncF32),
+                (RoundingMode::Nearest, OperandSize::S64) => (WasmType::F64, LibCall::NearestF64),
+                (RoundingMode::Up, OperandSize::S64) => (WasmType::F64, LibCall::CeilF64),
+                (RoundingMode::Down, OperandSize::S64) => (WasmType::F64, LibCall::FloorF64),
+                (RoundingMode::Zero, OperandSize::S64) => (WasmType::F64, LibCall::TruncF64),
+                (_, s) => panic!("Invalid operand size {:?} for rounding", s),
+            };
+            let sig = WasmFuncType::new([ty].into(), [ty].into());
+            let sig = <Self::ABI as ABI>::sig(&sig, &CallingConvention::Default);
+            let fncall = FnCall::new::<Self>(&sig, context, self);
+            fncall.lib::<Self>(self, context, libcall);
+        }
+    }
+
+    fn float_cmp_with_set(
+        &mut self,
+        src1: Reg,
+        src2: Reg,
+        dst: Reg,
+        kind: FloatCmpKind,
+        size: OperandSize,
+    ) {
+        self.asm.float_cmp_with_set(src1, src2, dst, kind, size);
+    }
+
+    fn signed_truncate(
+        &mut self,
+        dst: Reg,
+        src: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        kind: TruncKind,
+    ) {
+        self.asm.cvt_float_to_sint_seq(
+            src,
+            dst,
+            regs::scratch(),
+            regs::scratch_xmm(),
+            src_size,
+            dst_size,
+            kind == TruncKind::Saturating,
+        );
+    }
+
+    fn unsigned_truncate(
+        &mut self,
+        context: &mut CodeGenContext,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        kind: TruncKind,
+    ) {
+        let dst_ty = match dst_size {
+            OperandSize::S32 => WasmType::I32,
+            OperandSize::S64 => WasmType::I64,
+            s => panic!("Invalid operand size {:?} for truncation", s),
+        };
+        let src = context.pop_to_reg(self, None);
+        let dst = context.reg_for_type(dst_ty, self);
+        // The unsigned conversion sequence needs an extra temporary
+        // floating point register.
+        let tmp_xmm = context.reg_for_class(RegClass::Float, self);
+        self.asm.cvt_float_to_uint_seq(
+            src.reg,
+            dst,
+            regs::scratch(),
+            regs::scratch_xmm(),
+            tmp_xmm,
+            src_size,
+            dst_size,
+            kind == TruncKind::Saturating,
+        );
+        context.free_reg(tmp_xmm);
+        context.free_reg(src);
+        context.stack.push(TypedReg::new(dst_ty, dst).into());
+    }
+
+    fn signed_convert(&mut self, dst: Reg, src: Reg, src_size: OperandSize, dst_size: OperandSize) {
+        self.asm.cvt_sint_to_float(src, dst, src_size, dst_size);
+    }
+
+    fn unsigned_convert(
+        &mut self,
+        context: &mut CodeGenContext,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+    ) {
+        let dst_ty = match dst_size {
+            OperandSize::S32 => WasmType::F32,
+            OperandSize::S64 => WasmType::F64,
+            s => panic!("Invalid operand size {:?} for conversion", s),
+        };
+        let src = context.pop_to_reg(self, None);
+        let dst = context.reg_for_type(dst_ty, self);
+        match src_size {
+            // Any unsigned 32-bit value can be represented as a signed
+            // 64-bit value, so zero extend it and perform a signed
+            // conversion instead.
+            OperandSize::S32 => {
+                self.asm
+                    .movzx_rr(src.reg, src.reg, ExtendKind::I64ExtendI32U);
+                self.asm
+                    .cvt_sint_to_float(src.reg, dst, OperandSize::S64, dst_size);
+            }
+            OperandSize::S64 => {
+                let tmp_gpr = context.any_gpr(self);
+                self.asm
+                    .cvt_uint64_to_float_seq(src.reg, dst, regs::scratch(), tmp_gpr, dst_size);
+                context.free_reg(tmp_gpr);
+            }
+            s => panic!("Invalid operand size {:?} for conversion", s),
+        }
+        context.free_reg(src);
+        context.stack.push(TypedReg::new(dst_ty, dst).into());
+    }
+
+    fn demote(&mut self, dst: Reg, src: Reg) {
+        self.asm
+            .cvt_float_to_float(src, dst, OperandSize::S64, OperandSize::S32);
+    }
+
+    fn promote(&mut self, dst: Reg, src: Reg) {
+        self.asm
+            .cvt_float_to_float(src, dst, OperandSize::S32, OperandSize::S64);
+    }
+
+    fn reinterpret_float_as_int(&mut self, dst: Reg, src: Reg, size: OperandSize) {
+        self.asm.xmm_to_gpr(src, dst, size);
+    }
+
+    fn reinterpret_int_as_float(&mut self, dst: Reg, src: Reg, size: OperandSize) {
+        self.asm.gpr_to_xmm(src, dst, size);
+    }
+
+    fn extend(&mut self, dst: Reg, src: Reg, kind: ExtendKind) {
+        if kind == ExtendKind::I64ExtendI32U {
+            self.asm.movzx_rr(src, dst, kind);
+        } else {
+            self.asm.movsx_rr(src, dst, kind);
+        }
+    }
+
+    fn checked_uadd(&mut self, dst: Reg, lhs: Reg, rhs: RegImm, size: OperandSize, trap: TrapCode) {
+        self.add(dst.into(), lhs.into(), rhs, size);
+        self.asm.trapif(CmpKind::LtU, trap);
+    }
+
     fn get_label(&mut self) -> MachLabel {
         let buffer = self.asm.buffer_mut();
         buffer.get_label()
@@ -507,6 +789,32 @@ impl Masm for MacroAssembler {
         self.asm.jmp(target);
     }
 
+    fn jmp_table(&mut self, targets: &[MachLabel], index: Reg, tmp: Reg) {
+        // At least one default target.
+        assert!(targets.len() >= 1);
+        let default_index = targets.len() - 1;
+        // Emit the bounds check, by conditionally moving the index
+        // of the default target into the index register if the
+        // index is greater than or equal to it.
+        let size = OperandSize::S32;
+        self.asm.mov_ir(default_index as u64, tmp, size);
+        self.asm.cmp_rr(tmp, index, size);
+        self.asm.cmov(tmp, index, CmpKind::GeU, size);
+
+        let default = targets[default_index];
+        let rest = &targets[0..default_index];
+        self.asm
+            .jmp_table(rest.into(), default, index, regs::scratch(), tmp);
+    }
+
+    fn cmov(&mut self, src: Reg, dst: Reg, kind: CmpKind, size: OperandSize) {
+        match src.class() {
+            RegClass::Int => self.asm.cmov(src, dst, kind, size),
+            RegClass::Float => self.asm.xmm_cmov(src, dst, kind, size),
+            RegClass::Vector => unreachable!(),
+        }
+    }
+
     fn popcnt(&mut self, context: &mut CodeGenContext, size: OperandSize) {
         let src = context.pop_to_reg(self, None);
         if self.flags.has_popcnt() {
@@ -576,6 +884,14 @@ impl Masm for MacroAssembler {
     fn unreachable(&mut self) {
         self.asm.trap(TrapCode::UnreachableCodeReached)
     }
+
+    fn trap(&mut self, code: TrapCode) {
+        self.asm.trap(code);
+    }
+
+    fn trapif(&mut self, kind: CmpKind, code: TrapCode) {
+        self.asm.trapif(kind, code);
+    }
 }
 
 impl MacroAssembler {
@@ -610,6 +926,26 @@ impl MacroAssembler {
         }
     }
 
+    /// The mask of the sign bit of a floating point value of the
+    /// given size.
+    fn float_sign_mask(size: OperandSize) -> I {
+        match size {
+            OperandSize::S32 => I::I32(0x8000_0000),
+            OperandSize::S64 => I::I64(0x8000_0000_0000_0000),
+            s => panic!("Invalid operand size {:?} for floating point operation", s),
+        }
+    }
+
+    /// The mask of all the bits, except the sign bit, of a floating
+    /// point value of the given size.
+    fn float_magnitude_mask(size: OperandSize) -> I {
+        match size {
+            OperandSize::S32 => I::I32(0x7fff_ffff),
+            OperandSize::S64 => I::I64(0x7fff_ffff_ffff_ffff),
+            s => panic!("Invalid operand size {:?} for floating point operation", s),
+        }
+    }
+
     fn handle_invalid_operand_combination<T>(src: RegImm, dst: RegImm) -> T {
         panic!("Invalid operand combination; src={:?}, dst={:?}", src, dst);
     }
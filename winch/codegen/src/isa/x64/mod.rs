@@ -19,7 +19,7 @@ use cranelift_codegen::{isa::x64::settings as x64_settings, Final, MachBufferFin
 use cranelift_codegen::{MachTextSectionBuilder, TextSectionBuilder};
 use target_lexicon::Triple;
 use wasmparser::{FuncValidator, FunctionBody, ValidatorResources};
-use wasmtime_environ::{ModuleTranslation, WasmFuncType};
+use wasmtime_environ::{ModuleTranslation, Tunables, WasmFuncType};
 
 use self::regs::{ALL_FPR, ALL_GPR};
 
@@ -91,6 +91,7 @@ impl TargetIsa for X64 {
         sig: &WasmFuncType,
         body: &FunctionBody,
         translation: &ModuleTranslation,
+        tunables: &Tunables,
         validator: &mut FuncValidator<ValidatorResources>,
     ) -> Result<MachBufferFinalized<Final>> {
         let mut body = body.get_binary_reader();
@@ -102,12 +103,13 @@ impl TargetIsa for X64 {
         let frame = Frame::new::<abi::X64ABI>(&abi_sig, &defined_locals)?;
         let regalloc = RegAlloc::new(RegSet::new(ALL_GPR, ALL_FPR), regs::scratch());
         let codegen_context = CodeGenContext::new(regalloc, stack, &frame);
-        let env = FuncEnv::new(self.pointer_bytes(), translation);
+        let env = FuncEnv::new(self.pointer_bytes(), translation, tunables);
         let mut codegen = CodeGen::new(&mut masm, codegen_context, env, abi_sig);
 
         codegen.emit(&mut body, validator)?;
+        let base = codegen.source_location.base;
 
-        Ok(masm.finalize())
+        Ok(masm.finalize(base))
     }
 
     fn text_section_builder(&self, num_funcs: usize) -> Box<dyn TextSectionBuilder> {
@@ -143,6 +145,6 @@ impl TargetIsa for X64 {
             WasmToNative => trampoline.emit_wasm_to_native(ty)?,
         }
 
-        Ok(masm.finalize())
+        Ok(masm.finalize(None))
     }
 }
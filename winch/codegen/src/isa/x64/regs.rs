@@ -166,6 +166,12 @@ pub(crate) fn xmm15() -> Reg {
     fpr(15)
 }
 
+/// Used as the scratch register for floating point operations.
+/// Non-allocatable in Winch's default ABI.
+pub(crate) fn scratch_xmm() -> Reg {
+    xmm15()
+}
+
 const GPR: u32 = 16;
 const FPR: u32 = 16;
 const ALLOCATABLE_GPR: u32 = (1 << GPR) - 1;
@@ -176,10 +182,11 @@ const NON_ALLOCATABLE_GPR: u32 = (1 << ENC_RBP) | (1 << ENC_RSP) | (1 << ENC_R11
 
 /// Bitmask to represent the available general purpose registers.
 pub(crate) const ALL_GPR: u32 = ALLOCATABLE_GPR & !NON_ALLOCATABLE_GPR;
+// XMM15: Is used as the scratch register for floating point operations.
+const NON_ALLOCATABLE_FPR: u32 = 1 << 15;
+
 /// Bitmask to represent the available floating point registers.
-// Note: at the time of writing all floating point registers are allocatable,
-// but we might need a scratch register in the future.
-pub(crate) const ALL_FPR: u32 = ALLOCATABLE_FPR;
+pub(crate) const ALL_FPR: u32 = ALLOCATABLE_FPR & !NON_ALLOCATABLE_FPR;
 
 /// Returns the callee-saved registers according to a particular calling
 /// convention.
@@ -2,7 +2,11 @@ use crate::abi::{self, align_to, LocalSlot};
 use crate::codegen::CodeGenContext;
 use crate::isa::reg::Reg;
 use crate::regalloc::RegAlloc;
-use cranelift_codegen::{Final, MachBufferFinalized, MachLabel};
+use cranelift_codegen::{
+    binemit::CodeOffset,
+    ir::{LibCall, RelSourceLoc, SourceLoc, TrapCode},
+    Final, MachBufferFinalized, MachLabel,
+};
 use std::{fmt::Debug, ops::Range};
 use wasmtime_environ::PtrSize;
 
@@ -58,6 +62,50 @@ pub(crate) enum CmpKind {
     GeU,
 }
 
+/// Kinds of floating point comparisons in WebAssembly. The [`masm`]
+/// implementation for each ISA is responsible for emitting the correct
+/// sequence of instructions when lowering to machine code.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum FloatCmpKind {
+    /// Equal.
+    Eq,
+    /// Not equal.
+    Ne,
+    /// Less than.
+    Lt,
+    /// Greater than.
+    Gt,
+    /// Less than or equal.
+    Le,
+    /// Greater than or equal.
+    Ge,
+}
+
+/// Rounding modes of the floating point rounding operators in
+/// WebAssembly.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum RoundingMode {
+    /// Round to the nearest integer, ties to even.
+    Nearest,
+    /// Round towards positive infinity.
+    Up,
+    /// Round towards negative infinity.
+    Down,
+    /// Round towards zero.
+    Zero,
+}
+
+/// Kinds of float to integer truncations in WebAssembly.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum TruncKind {
+    /// Traps if the value is NaN or if it's out of the range of the
+    /// destination integer type.
+    Checked,
+    /// Saturates to the range of the destination integer type, NaN
+    /// is converted to zero.
+    Saturating,
+}
+
 /// Kinds of shifts in WebAssembly.The [`masm`] implementation for each ISA is
 /// responsible for emitting the correct sequence of instructions when
 /// lowering to machine code.
@@ -74,9 +122,32 @@ pub(crate) enum ShiftKind {
     Rotr,
 }
 
+/// Kinds of extends in WebAssembly. The [`masm`] implementation for each ISA
+/// is responsible for emitting the correct sequence of instructions when
+/// lowering to machine code.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum ExtendKind {
+    /// Sign extends the low 8 bits of a 32-bit value.
+    I32Extend8S,
+    /// Sign extends the low 16 bits of a 32-bit value.
+    I32Extend16S,
+    /// Sign extends the low 8 bits of a 64-bit value.
+    I64Extend8S,
+    /// Sign extends the low 16 bits of a 64-bit value.
+    I64Extend16S,
+    /// Sign extends the low 32 bits of a 64-bit value.
+    I64Extend32S,
+    /// Zero extends a 32-bit value to 64 bits.
+    I64ExtendI32U,
+}
+
 /// Operand size, in bits.
 #[derive(Copy, Debug, Clone, Eq, PartialEq)]
 pub(crate) enum OperandSize {
+    /// 8 bits.
+    S8,
+    /// 16 bits.
+    S16,
     /// 32 bits.
     S32,
     /// 64 bits.
@@ -89,6 +160,8 @@ impl OperandSize {
     /// The number of bits in the operand.
     pub fn num_bits(&self) -> i32 {
         match self {
+            OperandSize::S8 => 8,
+            OperandSize::S16 => 16,
             OperandSize::S32 => 32,
             OperandSize::S64 => 64,
             OperandSize::S128 => 128,
@@ -98,15 +171,32 @@ impl OperandSize {
     /// The number of bytes in the operand.
     pub fn bytes(&self) -> u32 {
         match self {
+            Self::S8 => 1,
+            Self::S16 => 2,
             Self::S32 => 4,
             Self::S64 => 8,
             Self::S128 => 16,
         }
     }
 
+    /// Create an [`OperandSize`] from the given number of bytes.
+    pub fn from_bytes(bytes: u8) -> Self {
+        use OperandSize::*;
+        match bytes {
+            1 => S8,
+            2 => S16,
+            4 => S32,
+            8 => S64,
+            16 => S128,
+            _ => panic!("Invalid bytes {} for OperandSize", bytes),
+        }
+    }
+
     /// The binary logarithm of the number of bits in the operand.
     pub fn log2(&self) -> u8 {
         match self {
+            OperandSize::S8 => 3,
+            OperandSize::S16 => 4,
             OperandSize::S32 => 5,
             OperandSize::S64 => 6,
             OperandSize::S128 => 7,
@@ -177,6 +267,8 @@ pub(crate) enum CalleeKind {
     Indirect(Reg),
     /// A function call to a local function.
     Direct(u32),
+    /// A function call to a known library function.
+    LibCall(LibCall),
 }
 
 impl RegImm {
@@ -299,6 +391,27 @@ pub(crate) trait MacroAssembler {
     /// Perform a stack load.
     fn load(&mut self, src: Self::Address, dst: Reg, size: OperandSize);
 
+    /// Perform a WebAssembly load.
+    ///
+    /// Loads narrower than the destination are zero extended, unless
+    /// an [`ExtendKind`] is given, in which case they are sign extended
+    /// accordingly. In contrast to [`Self::load`], the access is
+    /// considered untrusted and any fault is reported as an out of
+    /// bounds heap access.
+    fn wasm_load(
+        &mut self,
+        src: Self::Address,
+        dst: Reg,
+        size: OperandSize,
+        kind: Option<ExtendKind>,
+    );
+
+    /// Perform a WebAssembly store.
+    ///
+    /// Like [`Self::wasm_load`], any fault is reported as an out of
+    /// bounds heap access.
+    fn wasm_store(&mut self, src: Reg, dst: Self::Address, size: OperandSize);
+
     /// Pop a value from the machine stack into the given register.
     fn pop(&mut self, dst: Reg, size: OperandSize);
 
@@ -364,6 +477,125 @@ pub(crate) trait MacroAssembler {
     /// false.
     fn ctz(&mut self, src: Reg, dst: Reg, size: OperandSize);
 
+    /// Sign or zero extend the value in `src`, according to the given
+    /// [`ExtendKind`] and put the result in `dst`.
+    fn extend(&mut self, dst: Reg, src: Reg, kind: ExtendKind);
+
+    /// Add `rhs` to `lhs` and put the result in `dst`, trapping with the
+    /// given code if the unsigned addition overflows.
+    fn checked_uadd(&mut self, dst: Reg, lhs: Reg, rhs: RegImm, size: OperandSize, trap: TrapCode);
+
+    /// Perform a floating point add operation.
+    fn float_add(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize);
+
+    /// Perform a floating point subtraction operation.
+    fn float_sub(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize);
+
+    /// Perform a floating point multiplication operation.
+    fn float_mul(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize);
+
+    /// Perform a floating point division operation.
+    fn float_div(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize);
+
+    /// Perform a floating point minimum operation, following the
+    /// WebAssembly semantics for NaNs and signed zeros.
+    fn float_min(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize);
+
+    /// Perform a floating point maximum operation, following the
+    /// WebAssembly semantics for NaNs and signed zeros.
+    fn float_max(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize);
+
+    /// Compose a floating point value with the magnitude of `lhs` and
+    /// the sign of `rhs`, and put the result in `dst`.
+    fn float_copysign(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize);
+
+    /// Negate the floating point value in `dst`.
+    fn float_neg(&mut self, dst: Reg, size: OperandSize);
+
+    /// Compute the absolute value of the floating point value in `dst`.
+    fn float_abs(&mut self, dst: Reg, size: OperandSize);
+
+    /// Compute the square root of the floating point value in `src` and
+    /// put the result in `dst`.
+    fn float_sqrt(&mut self, dst: Reg, src: Reg, size: OperandSize);
+
+    /// Round the floating point value at the top of the value stack
+    /// according to the given [`RoundingMode`].
+    /// Some architectures might not have native support for all the
+    /// rounding modes and might need to emit a call to a library
+    /// function; we give this function access to the code generation
+    /// context, allowing each implementation to decide the lowering
+    /// path.
+    fn float_round(&mut self, mode: RoundingMode, context: &mut CodeGenContext, size: OperandSize);
+
+    /// Compare the floating point values in `src1` and `src2` and put
+    /// the result, 0 or 1, in the integer register `dst`.
+    fn float_cmp_with_set(
+        &mut self,
+        src1: Reg,
+        src2: Reg,
+        dst: Reg,
+        kind: FloatCmpKind,
+        size: OperandSize,
+    );
+
+    /// Convert the floating point value in `src` to a signed integer
+    /// and put the result in `dst`.
+    fn signed_truncate(
+        &mut self,
+        dst: Reg,
+        src: Reg,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        kind: TruncKind,
+    );
+
+    /// Convert the floating point value at the top of the value stack
+    /// to an unsigned integer.
+    /// Unsigned truncation might need extra temporary registers
+    /// depending on the architecture; we give this function access to
+    /// the code generation context, allowing each implementation to
+    /// allocate them as needed.
+    fn unsigned_truncate(
+        &mut self,
+        context: &mut CodeGenContext,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+        kind: TruncKind,
+    );
+
+    /// Convert the signed integer in `src` to a floating point value
+    /// and put the result in `dst`.
+    fn signed_convert(&mut self, dst: Reg, src: Reg, src_size: OperandSize, dst_size: OperandSize);
+
+    /// Convert the unsigned integer at the top of the value stack to a
+    /// floating point value.
+    /// Like [`Self::unsigned_truncate`], this function has access to
+    /// the code generation context to allocate any needed temporary
+    /// registers.
+    fn unsigned_convert(
+        &mut self,
+        context: &mut CodeGenContext,
+        src_size: OperandSize,
+        dst_size: OperandSize,
+    );
+
+    /// Convert the 64-bit floating point value in `src` to a 32-bit
+    /// floating point value and put the result in `dst`.
+    fn demote(&mut self, dst: Reg, src: Reg);
+
+    /// Convert the 32-bit floating point value in `src` to a 64-bit
+    /// floating point value and put the result in `dst`.
+    fn promote(&mut self, dst: Reg, src: Reg);
+
+    /// Move the bits of the floating point value in `src` to the
+    /// integer register `dst`.
+    fn reinterpret_float_as_int(&mut self, dst: Reg, src: Reg, size: OperandSize);
+
+    /// Move the bits of the integer value in `src` to the floating
+    /// point register `dst`.
+    fn reinterpret_int_as_float(&mut self, dst: Reg, src: Reg, size: OperandSize);
+
     /// Push the register to the stack, returning the stack slot metadata.
     // NB
     // The stack alignment should not be assumed after any call to `push`,
@@ -373,7 +605,24 @@ pub(crate) trait MacroAssembler {
     fn push(&mut self, src: Reg, size: OperandSize) -> StackSlot;
 
     /// Finalize the assembly and return the result.
-    fn finalize(self) -> MachBufferFinalized<Final>;
+    ///
+    /// The source locations recorded through [`Self::start_source_loc`]
+    /// are relative to `base`, if any.
+    fn finalize(self, base: Option<SourceLoc>) -> MachBufferFinalized<Final>;
+
+    /// Mark the start of a source location, relative to the function's
+    /// base source location. All the code emitted until the next call
+    /// to [`Self::end_source_loc`] is attributed to `loc`.
+    ///
+    /// Returns the code offset at which the source location starts.
+    fn start_source_loc(&mut self, loc: RelSourceLoc) -> CodeOffset;
+
+    /// Mark the end of the source location started by the last call to
+    /// [`Self::start_source_loc`].
+    fn end_source_loc(&mut self);
+
+    /// The offset of the next instruction to be emitted.
+    fn current_code_offset(&self) -> CodeOffset;
 
     /// Zero a particular register.
     fn zero(&mut self, reg: Reg);
@@ -453,6 +702,22 @@ pub(crate) trait MacroAssembler {
     /// Emits and unconditional jump to the given label.
     fn jmp(&mut self, target: MachLabel);
 
+    /// Emits a jump table sequence. The default target is expected to
+    /// be the last label in `targets`; any `index` outside the bounds
+    /// of the table jumps to the default target.
+    fn jmp_table(&mut self, targets: &[MachLabel], index: Reg, tmp: Reg);
+
+    /// Move `src` into `dst` if the condition described by the
+    /// [`CmpKind`] holds for the flags set by the last comparison.
+    fn cmov(&mut self, src: Reg, dst: Reg, kind: CmpKind, size: OperandSize);
+
     /// Emit an unreachable code trap.
     fn unreachable(&mut self);
+
+    /// Emit an unconditional trap with the given code.
+    fn trap(&mut self, code: TrapCode);
+
+    /// Trap with the given code if the condition described by the
+    /// [`CmpKind`] holds for the flags set by the last comparison.
+    fn trapif(&mut self, kind: CmpKind, code: TrapCode);
 }
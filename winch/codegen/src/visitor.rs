@@ -7,10 +7,14 @@
 use crate::abi::ABI;
 use crate::codegen::CodeGen;
 use crate::codegen::ControlStackFrame;
-use crate::masm::{CmpKind, DivKind, MacroAssembler, OperandSize, RegImm, RemKind, ShiftKind};
+use crate::masm::{
+    CmpKind, DivKind, ExtendKind, FloatCmpKind, MacroAssembler, OperandSize, RegImm, RemKind,
+    RoundingMode, ShiftKind, TruncKind,
+};
 use crate::stack::{TypedReg, Val};
-use wasmparser::{BlockType, Ieee32, Ieee64, VisitOperator};
-use wasmtime_environ::{FuncIndex, GlobalIndex, WasmType};
+use smallvec::SmallVec;
+use wasmparser::{BlockType, BrTable, Ieee32, Ieee64, MemArg, ValType, VisitOperator};
+use wasmtime_environ::{FuncIndex, GlobalIndex, TableIndex, TypeIndex, WasmType};
 
 /// A macro to define unsupported WebAssembly operators.
 ///
@@ -108,9 +112,114 @@ macro_rules! def_unsupported {
     (emit BrIf $($rest:tt)*) => {};
     (emit Return $($rest:tt)*) => {};
     (emit Unreachable $($rest:tt)*) => {};
+    (emit BrTable $($rest:tt)*) => {};
+    (emit Select $($rest:tt)*) => {};
+    (emit TypedSelect $($rest:tt)*) => {};
+    (emit Drop $($rest:tt)*) => {};
+    (emit CallIndirect $($rest:tt)*) => {};
+    (emit TableGet $($rest:tt)*) => {};
+    (emit TableSet $($rest:tt)*) => {};
+    (emit TableGrow $($rest:tt)*) => {};
+    (emit TableSize $($rest:tt)*) => {};
+    (emit RefFunc $($rest:tt)*) => {};
     (emit LocalTee $($rest:tt)*) => {};
     (emit GlobalGet $($rest:tt)*) => {};
     (emit GlobalSet $($rest:tt)*) => {};
+    (emit I32Load $($rest:tt)*) => {};
+    (emit I32Load8S $($rest:tt)*) => {};
+    (emit I32Load8U $($rest:tt)*) => {};
+    (emit I32Load16S $($rest:tt)*) => {};
+    (emit I32Load16U $($rest:tt)*) => {};
+    (emit I64Load $($rest:tt)*) => {};
+    (emit I64Load8S $($rest:tt)*) => {};
+    (emit I64Load8U $($rest:tt)*) => {};
+    (emit I64Load16S $($rest:tt)*) => {};
+    (emit I64Load16U $($rest:tt)*) => {};
+    (emit I64Load32S $($rest:tt)*) => {};
+    (emit I64Load32U $($rest:tt)*) => {};
+    (emit F32Load $($rest:tt)*) => {};
+    (emit F64Load $($rest:tt)*) => {};
+    (emit I32Store $($rest:tt)*) => {};
+    (emit I32Store8 $($rest:tt)*) => {};
+    (emit I32Store16 $($rest:tt)*) => {};
+    (emit I64Store $($rest:tt)*) => {};
+    (emit I64Store8 $($rest:tt)*) => {};
+    (emit I64Store16 $($rest:tt)*) => {};
+    (emit I64Store32 $($rest:tt)*) => {};
+    (emit F32Store $($rest:tt)*) => {};
+    (emit F64Store $($rest:tt)*) => {};
+    (emit MemorySize $($rest:tt)*) => {};
+    (emit MemoryGrow $($rest:tt)*) => {};
+    (emit F32Add $($rest:tt)*) => {};
+    (emit F32Sub $($rest:tt)*) => {};
+    (emit F32Mul $($rest:tt)*) => {};
+    (emit F32Div $($rest:tt)*) => {};
+    (emit F32Min $($rest:tt)*) => {};
+    (emit F32Max $($rest:tt)*) => {};
+    (emit F32Copysign $($rest:tt)*) => {};
+    (emit F32Abs $($rest:tt)*) => {};
+    (emit F32Neg $($rest:tt)*) => {};
+    (emit F32Sqrt $($rest:tt)*) => {};
+    (emit F32Ceil $($rest:tt)*) => {};
+    (emit F32Floor $($rest:tt)*) => {};
+    (emit F32Trunc $($rest:tt)*) => {};
+    (emit F32Nearest $($rest:tt)*) => {};
+    (emit F32Eq $($rest:tt)*) => {};
+    (emit F32Ne $($rest:tt)*) => {};
+    (emit F32Lt $($rest:tt)*) => {};
+    (emit F32Gt $($rest:tt)*) => {};
+    (emit F32Le $($rest:tt)*) => {};
+    (emit F32Ge $($rest:tt)*) => {};
+    (emit F64Add $($rest:tt)*) => {};
+    (emit F64Sub $($rest:tt)*) => {};
+    (emit F64Mul $($rest:tt)*) => {};
+    (emit F64Div $($rest:tt)*) => {};
+    (emit F64Min $($rest:tt)*) => {};
+    (emit F64Max $($rest:tt)*) => {};
+    (emit F64Copysign $($rest:tt)*) => {};
+    (emit F64Abs $($rest:tt)*) => {};
+    (emit F64Neg $($rest:tt)*) => {};
+    (emit F64Sqrt $($rest:tt)*) => {};
+    (emit F64Ceil $($rest:tt)*) => {};
+    (emit F64Floor $($rest:tt)*) => {};
+    (emit F64Trunc $($rest:tt)*) => {};
+    (emit F64Nearest $($rest:tt)*) => {};
+    (emit F64Eq $($rest:tt)*) => {};
+    (emit F64Ne $($rest:tt)*) => {};
+    (emit F64Lt $($rest:tt)*) => {};
+    (emit F64Gt $($rest:tt)*) => {};
+    (emit F64Le $($rest:tt)*) => {};
+    (emit F64Ge $($rest:tt)*) => {};
+    (emit I32TruncF32S $($rest:tt)*) => {};
+    (emit I32TruncF32U $($rest:tt)*) => {};
+    (emit I32TruncF64S $($rest:tt)*) => {};
+    (emit I32TruncF64U $($rest:tt)*) => {};
+    (emit I64TruncF32S $($rest:tt)*) => {};
+    (emit I64TruncF32U $($rest:tt)*) => {};
+    (emit I64TruncF64S $($rest:tt)*) => {};
+    (emit I64TruncF64U $($rest:tt)*) => {};
+    (emit I32TruncSatF32S $($rest:tt)*) => {};
+    (emit I32TruncSatF32U $($rest:tt)*) => {};
+    (emit I32TruncSatF64S $($rest:tt)*) => {};
+    (emit I32TruncSatF64U $($rest:tt)*) => {};
+    (emit I64TruncSatF32S $($rest:tt)*) => {};
+    (emit I64TruncSatF32U $($rest:tt)*) => {};
+    (emit I64TruncSatF64S $($rest:tt)*) => {};
+    (emit I64TruncSatF64U $($rest:tt)*) => {};
+    (emit F32ConvertI32S $($rest:tt)*) => {};
+    (emit F32ConvertI32U $($rest:tt)*) => {};
+    (emit F32ConvertI64S $($rest:tt)*) => {};
+    (emit F32ConvertI64U $($rest:tt)*) => {};
+    (emit F64ConvertI32S $($rest:tt)*) => {};
+    (emit F64ConvertI32U $($rest:tt)*) => {};
+    (emit F64ConvertI64S $($rest:tt)*) => {};
+    (emit F64ConvertI64U $($rest:tt)*) => {};
+    (emit F32DemoteF64 $($rest:tt)*) => {};
+    (emit F64PromoteF32 $($rest:tt)*) => {};
+    (emit I32ReinterpretF32 $($rest:tt)*) => {};
+    (emit I64ReinterpretF64 $($rest:tt)*) => {};
+    (emit F32ReinterpretI32 $($rest:tt)*) => {};
+    (emit F64ReinterpretI64 $($rest:tt)*) => {};
 
     (emit $unsupported:tt $($rest:tt)*) => {$($rest)*};
 }
@@ -501,7 +610,7 @@ where
             .get_local(index)
             .unwrap_or_else(|| panic!("valid local at slot = {}", index));
         match slot.ty {
-            I32 | I64 | F32 | F64 => context.stack.push(Val::local(index, slot.ty)),
+            I32 | I64 | F32 | F64 | Ref(_) => context.stack.push(Val::local(index, slot.ty)),
             _ => panic!("Unsupported type {:?} for local", slot.ty),
         }
     }
@@ -516,8 +625,41 @@ where
         self.emit_call(FuncIndex::from_u32(index));
     }
 
+    fn visit_call_indirect(&mut self, type_index: u32, table_index: u32, _table_byte: u8) {
+        self.emit_call_indirect(
+            TypeIndex::from_u32(type_index),
+            TableIndex::from_u32(table_index),
+        );
+    }
+
     fn visit_nop(&mut self) {}
 
+    fn visit_drop(&mut self) {
+        match self.context.stack.pop().expect("a value at stack top") {
+            Val::Reg(typed_reg) => self.context.free_reg(typed_reg),
+            Val::Memory(mem) => self.masm.free_stack(mem.slot.size),
+            _ => {}
+        }
+    }
+
+    fn visit_select(&mut self) {
+        let cond = self.context.pop_to_reg(self.masm, None);
+        let val2 = self.context.pop_to_reg(self.masm, None);
+        let val1 = self.context.pop_to_reg(self.masm, None);
+        self.masm.cmp(RegImm::i32(0), cond.reg, OperandSize::S32);
+        // Move the first value into the second one if the condition
+        // is not zero.
+        self.masm
+            .cmov(val1.reg, val2.reg, CmpKind::Ne, val1.ty.into());
+        self.context.stack.push(val2.into());
+        self.context.free_reg(val1);
+        self.context.free_reg(cond);
+    }
+
+    fn visit_typed_select(&mut self, _ty: ValType) {
+        self.visit_select();
+    }
+
     fn visit_if(&mut self, blockty: BlockType) {
         self.control_frames.push(ControlStackFrame::if_(
             &self.env.resolve_block_type(blockty),
@@ -552,6 +694,11 @@ where
             self.masm,
             &mut self.context,
         ));
+
+        // Check for fuel exhaustion and epoch changes on every
+        // iteration, at the loop header.
+        self.maybe_emit_fuel_check();
+        self.maybe_emit_epoch_check();
     }
 
     fn visit_br(&mut self, depth: u32) {
@@ -584,6 +731,51 @@ where
         self.context.free_reg(top);
     }
 
+    fn visit_br_table(&mut self, targets: BrTable<'a>) {
+        // +1 to account for the default target.
+        let len = targets.len() + 1;
+        // The default target is bundled as the last element of the
+        // labels.
+        let labels: SmallVec<[_; 5]> = (0..len).map(|_| self.masm.get_label()).collect();
+
+        let default_frame = Self::control_at(&mut self.control_frames, targets.default());
+        let default_result = *default_frame.result();
+        let (index, tmp) = self.context.without::<(TypedReg, _), M, _>(
+            default_result.result_reg(),
+            self.masm,
+            |cx, masm| (cx.pop_to_reg(masm, None), cx.any_gpr(masm)),
+        );
+
+        // All the targets share the same result, which is handled
+        // once before jumping to the jump table.
+        self.context.pop_abi_results(&default_result, self.masm);
+        self.context.push_abi_results(&default_result, self.masm);
+        self.masm.jmp_table(&labels, index.reg, tmp);
+
+        // Each of the targets might adjust the stack pointer according
+        // to its base offset, so it must be reset before emitting the
+        // code for each target.
+        let current_sp = self.masm.sp_offset();
+        for (target, label) in targets
+            .targets()
+            .chain(std::iter::once(Ok(targets.default())))
+            .zip(labels.iter())
+        {
+            let frame = Self::control_at(&mut self.control_frames, target.unwrap());
+            self.masm.reset_stack_pointer(current_sp);
+            self.masm.bind(*label);
+            self.context.pop_sp_for_branch(&frame, self.masm);
+            self.masm.jmp(*frame.label());
+            frame.set_as_target();
+        }
+        // The reachability analysis ensures that the stack pointer is
+        // correctly placed once reachability is restored.
+        self.masm.reset_stack_pointer(current_sp);
+        self.context.reachable = false;
+        self.context.free_reg(index);
+        self.context.free_reg(tmp);
+    }
+
     fn visit_return(&mut self) {
         // Grab the outermost frame, which is the function's body frame. We
         // don't rely on `Self::control_at` since this frame is implicit and we
@@ -637,6 +829,597 @@ where
         self.masm.store(typed_reg.reg.into(), addr, ty.into());
     }
 
+    fn visit_table_get(&mut self, table: u32) {
+        self.emit_table_get(TableIndex::from_u32(table));
+    }
+
+    fn visit_table_set(&mut self, table: u32) {
+        self.emit_table_set(TableIndex::from_u32(table));
+    }
+
+    fn visit_table_grow(&mut self, table: u32) {
+        self.emit_table_grow(TableIndex::from_u32(table));
+    }
+
+    fn visit_table_size(&mut self, table: u32) {
+        self.emit_table_size(TableIndex::from_u32(table));
+    }
+
+    fn visit_ref_func(&mut self, function_index: u32) {
+        self.emit_ref_func(FuncIndex::from_u32(function_index));
+    }
+
+    fn visit_i32_load(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::I32, OperandSize::S32, None);
+    }
+
+    fn visit_i32_load8_s(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(
+            &memarg,
+            WasmType::I32,
+            OperandSize::S8,
+            Some(ExtendKind::I32Extend8S),
+        );
+    }
+
+    fn visit_i32_load8_u(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::I32, OperandSize::S8, None);
+    }
+
+    fn visit_i32_load16_s(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(
+            &memarg,
+            WasmType::I32,
+            OperandSize::S16,
+            Some(ExtendKind::I32Extend16S),
+        );
+    }
+
+    fn visit_i32_load16_u(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::I32, OperandSize::S16, None);
+    }
+
+    fn visit_i64_load(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::I64, OperandSize::S64, None);
+    }
+
+    fn visit_i64_load8_s(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(
+            &memarg,
+            WasmType::I64,
+            OperandSize::S8,
+            Some(ExtendKind::I64Extend8S),
+        );
+    }
+
+    fn visit_i64_load8_u(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::I64, OperandSize::S8, None);
+    }
+
+    fn visit_i64_load16_s(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(
+            &memarg,
+            WasmType::I64,
+            OperandSize::S16,
+            Some(ExtendKind::I64Extend16S),
+        );
+    }
+
+    fn visit_i64_load16_u(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::I64, OperandSize::S16, None);
+    }
+
+    fn visit_i64_load32_s(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(
+            &memarg,
+            WasmType::I64,
+            OperandSize::S32,
+            Some(ExtendKind::I64Extend32S),
+        );
+    }
+
+    fn visit_i64_load32_u(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::I64, OperandSize::S32, None);
+    }
+
+    fn visit_f32_load(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::F32, OperandSize::S32, None);
+    }
+
+    fn visit_f64_load(&mut self, memarg: MemArg) {
+        self.emit_wasm_load(&memarg, WasmType::F64, OperandSize::S64, None);
+    }
+
+    fn visit_i32_store(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S32);
+    }
+
+    fn visit_i32_store8(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S8);
+    }
+
+    fn visit_i32_store16(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S16);
+    }
+
+    fn visit_i64_store(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S64);
+    }
+
+    fn visit_i64_store8(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S8);
+    }
+
+    fn visit_i64_store16(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S16);
+    }
+
+    fn visit_i64_store32(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S32);
+    }
+
+    fn visit_f32_store(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S32);
+    }
+
+    fn visit_f64_store(&mut self, memarg: MemArg) {
+        self.emit_wasm_store(&memarg, OperandSize::S64);
+    }
+
+    fn visit_memory_size(&mut self, mem: u32, _: u8) {
+        self.emit_memory_size(mem);
+    }
+
+    fn visit_memory_grow(&mut self, mem: u32, _: u8) {
+        self.emit_memory_grow(mem);
+    }
+
+    fn visit_f32_add(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S32, |masm, dst, src, size| {
+                masm.float_add(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f32_sub(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S32, |masm, dst, src, size| {
+                masm.float_sub(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f32_mul(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S32, |masm, dst, src, size| {
+                masm.float_mul(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f32_div(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S32, |masm, dst, src, size| {
+                masm.float_div(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f32_min(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S32, |masm, dst, src, size| {
+                masm.float_min(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f32_max(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S32, |masm, dst, src, size| {
+                masm.float_max(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f32_copysign(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S32, |masm, dst, src, size| {
+                masm.float_copysign(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f32_abs(&mut self) {
+        self.context
+            .unop(self.masm, OperandSize::S32, &mut |masm, reg, size| {
+                masm.float_abs(reg, size);
+            });
+    }
+
+    fn visit_f32_neg(&mut self) {
+        self.context
+            .unop(self.masm, OperandSize::S32, &mut |masm, reg, size| {
+                masm.float_neg(reg, size);
+            });
+    }
+
+    fn visit_f32_sqrt(&mut self) {
+        self.context
+            .unop(self.masm, OperandSize::S32, &mut |masm, reg, size| {
+                masm.float_sqrt(reg, reg, size);
+            });
+    }
+
+    fn visit_f32_ceil(&mut self) {
+        self.masm
+            .float_round(RoundingMode::Up, &mut self.context, OperandSize::S32);
+    }
+
+    fn visit_f32_floor(&mut self) {
+        self.masm
+            .float_round(RoundingMode::Down, &mut self.context, OperandSize::S32);
+    }
+
+    fn visit_f32_trunc(&mut self) {
+        self.masm
+            .float_round(RoundingMode::Zero, &mut self.context, OperandSize::S32);
+    }
+
+    fn visit_f32_nearest(&mut self) {
+        self.masm
+            .float_round(RoundingMode::Nearest, &mut self.context, OperandSize::S32);
+    }
+
+    fn visit_f32_eq(&mut self) {
+        self.cmp_floats(FloatCmpKind::Eq, OperandSize::S32);
+    }
+
+    fn visit_f32_ne(&mut self) {
+        self.cmp_floats(FloatCmpKind::Ne, OperandSize::S32);
+    }
+
+    fn visit_f32_lt(&mut self) {
+        self.cmp_floats(FloatCmpKind::Lt, OperandSize::S32);
+    }
+
+    fn visit_f32_gt(&mut self) {
+        self.cmp_floats(FloatCmpKind::Gt, OperandSize::S32);
+    }
+
+    fn visit_f32_le(&mut self) {
+        self.cmp_floats(FloatCmpKind::Le, OperandSize::S32);
+    }
+
+    fn visit_f32_ge(&mut self) {
+        self.cmp_floats(FloatCmpKind::Ge, OperandSize::S32);
+    }
+
+    fn visit_f64_add(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S64, |masm, dst, src, size| {
+                masm.float_add(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f64_sub(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S64, |masm, dst, src, size| {
+                masm.float_sub(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f64_mul(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S64, |masm, dst, src, size| {
+                masm.float_mul(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f64_div(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S64, |masm, dst, src, size| {
+                masm.float_div(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f64_min(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S64, |masm, dst, src, size| {
+                masm.float_min(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f64_max(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S64, |masm, dst, src, size| {
+                masm.float_max(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f64_copysign(&mut self) {
+        self.context
+            .float_binop(self.masm, OperandSize::S64, |masm, dst, src, size| {
+                masm.float_copysign(dst, dst, src, size);
+            });
+    }
+
+    fn visit_f64_abs(&mut self) {
+        self.context
+            .unop(self.masm, OperandSize::S64, &mut |masm, reg, size| {
+                masm.float_abs(reg, size);
+            });
+    }
+
+    fn visit_f64_neg(&mut self) {
+        self.context
+            .unop(self.masm, OperandSize::S64, &mut |masm, reg, size| {
+                masm.float_neg(reg, size);
+            });
+    }
+
+    fn visit_f64_sqrt(&mut self) {
+        self.context
+            .unop(self.masm, OperandSize::S64, &mut |masm, reg, size| {
+                masm.float_sqrt(reg, reg, size);
+            });
+    }
+
+    fn visit_f64_ceil(&mut self) {
+        self.masm
+            .float_round(RoundingMode::Up, &mut self.context, OperandSize::S64);
+    }
+
+    fn visit_f64_floor(&mut self) {
+        self.masm
+            .float_round(RoundingMode::Down, &mut self.context, OperandSize::S64);
+    }
+
+    fn visit_f64_trunc(&mut self) {
+        self.masm
+            .float_round(RoundingMode::Zero, &mut self.context, OperandSize::S64);
+    }
+
+    fn visit_f64_nearest(&mut self) {
+        self.masm
+            .float_round(RoundingMode::Nearest, &mut self.context, OperandSize::S64);
+    }
+
+    fn visit_f64_eq(&mut self) {
+        self.cmp_floats(FloatCmpKind::Eq, OperandSize::S64);
+    }
+
+    fn visit_f64_ne(&mut self) {
+        self.cmp_floats(FloatCmpKind::Ne, OperandSize::S64);
+    }
+
+    fn visit_f64_lt(&mut self) {
+        self.cmp_floats(FloatCmpKind::Lt, OperandSize::S64);
+    }
+
+    fn visit_f64_gt(&mut self) {
+        self.cmp_floats(FloatCmpKind::Gt, OperandSize::S64);
+    }
+
+    fn visit_f64_le(&mut self) {
+        self.cmp_floats(FloatCmpKind::Le, OperandSize::S64);
+    }
+
+    fn visit_f64_ge(&mut self) {
+        self.cmp_floats(FloatCmpKind::Ge, OperandSize::S64);
+    }
+
+    fn visit_i32_trunc_f32_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I32, |masm, dst, src, dst_size| {
+                masm.signed_truncate(dst, src, OperandSize::S32, dst_size, TruncKind::Checked);
+            });
+    }
+
+    fn visit_i32_trunc_f32_u(&mut self) {
+        self.masm.unsigned_truncate(
+            &mut self.context,
+            OperandSize::S32,
+            OperandSize::S32,
+            TruncKind::Checked,
+        );
+    }
+
+    fn visit_i32_trunc_f64_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I32, |masm, dst, src, dst_size| {
+                masm.signed_truncate(dst, src, OperandSize::S64, dst_size, TruncKind::Checked);
+            });
+    }
+
+    fn visit_i32_trunc_f64_u(&mut self) {
+        self.masm.unsigned_truncate(
+            &mut self.context,
+            OperandSize::S64,
+            OperandSize::S32,
+            TruncKind::Checked,
+        );
+    }
+
+    fn visit_i64_trunc_f32_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I64, |masm, dst, src, dst_size| {
+                masm.signed_truncate(dst, src, OperandSize::S32, dst_size, TruncKind::Checked);
+            });
+    }
+
+    fn visit_i64_trunc_f32_u(&mut self) {
+        self.masm.unsigned_truncate(
+            &mut self.context,
+            OperandSize::S32,
+            OperandSize::S64,
+            TruncKind::Checked,
+        );
+    }
+
+    fn visit_i64_trunc_f64_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I64, |masm, dst, src, dst_size| {
+                masm.signed_truncate(dst, src, OperandSize::S64, dst_size, TruncKind::Checked);
+            });
+    }
+
+    fn visit_i64_trunc_f64_u(&mut self) {
+        self.masm.unsigned_truncate(
+            &mut self.context,
+            OperandSize::S64,
+            OperandSize::S64,
+            TruncKind::Checked,
+        );
+    }
+
+    fn visit_i32_trunc_sat_f32_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I32, |masm, dst, src, dst_size| {
+                masm.signed_truncate(dst, src, OperandSize::S32, dst_size, TruncKind::Saturating);
+            });
+    }
+
+    fn visit_i32_trunc_sat_f32_u(&mut self) {
+        self.masm.unsigned_truncate(
+            &mut self.context,
+            OperandSize::S32,
+            OperandSize::S32,
+            TruncKind::Saturating,
+        );
+    }
+
+    fn visit_i32_trunc_sat_f64_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I32, |masm, dst, src, dst_size| {
+                masm.signed_truncate(dst, src, OperandSize::S64, dst_size, TruncKind::Saturating);
+            });
+    }
+
+    fn visit_i32_trunc_sat_f64_u(&mut self) {
+        self.masm.unsigned_truncate(
+            &mut self.context,
+            OperandSize::S64,
+            OperandSize::S32,
+            TruncKind::Saturating,
+        );
+    }
+
+    fn visit_i64_trunc_sat_f32_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I64, |masm, dst, src, dst_size| {
+                masm.signed_truncate(dst, src, OperandSize::S32, dst_size, TruncKind::Saturating);
+            });
+    }
+
+    fn visit_i64_trunc_sat_f32_u(&mut self) {
+        self.masm.unsigned_truncate(
+            &mut self.context,
+            OperandSize::S32,
+            OperandSize::S64,
+            TruncKind::Saturating,
+        );
+    }
+
+    fn visit_i64_trunc_sat_f64_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I64, |masm, dst, src, dst_size| {
+                masm.signed_truncate(dst, src, OperandSize::S64, dst_size, TruncKind::Saturating);
+            });
+    }
+
+    fn visit_i64_trunc_sat_f64_u(&mut self) {
+        self.masm.unsigned_truncate(
+            &mut self.context,
+            OperandSize::S64,
+            OperandSize::S64,
+            TruncKind::Saturating,
+        );
+    }
+
+    fn visit_f32_convert_i32_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::F32, |masm, dst, src, dst_size| {
+                masm.signed_convert(dst, src, OperandSize::S32, dst_size);
+            });
+    }
+
+    fn visit_f32_convert_i32_u(&mut self) {
+        self.masm
+            .unsigned_convert(&mut self.context, OperandSize::S32, OperandSize::S32);
+    }
+
+    fn visit_f32_convert_i64_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::F32, |masm, dst, src, dst_size| {
+                masm.signed_convert(dst, src, OperandSize::S64, dst_size);
+            });
+    }
+
+    fn visit_f32_convert_i64_u(&mut self) {
+        self.masm
+            .unsigned_convert(&mut self.context, OperandSize::S64, OperandSize::S32);
+    }
+
+    fn visit_f64_convert_i32_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::F64, |masm, dst, src, dst_size| {
+                masm.signed_convert(dst, src, OperandSize::S32, dst_size);
+            });
+    }
+
+    fn visit_f64_convert_i32_u(&mut self) {
+        self.masm
+            .unsigned_convert(&mut self.context, OperandSize::S32, OperandSize::S64);
+    }
+
+    fn visit_f64_convert_i64_s(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::F64, |masm, dst, src, dst_size| {
+                masm.signed_convert(dst, src, OperandSize::S64, dst_size);
+            });
+    }
+
+    fn visit_f64_convert_i64_u(&mut self) {
+        self.masm
+            .unsigned_convert(&mut self.context, OperandSize::S64, OperandSize::S64);
+    }
+
+    fn visit_f32_demote_f64(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::F32, |masm, dst, src, _size| {
+                masm.demote(dst, src);
+            });
+    }
+
+    fn visit_f64_promote_f32(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::F64, |masm, dst, src, _size| {
+                masm.promote(dst, src);
+            });
+    }
+
+    fn visit_i32_reinterpret_f32(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I32, |masm, dst, src, size| {
+                masm.reinterpret_float_as_int(dst, src, size);
+            });
+    }
+
+    fn visit_i64_reinterpret_f64(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::I64, |masm, dst, src, size| {
+                masm.reinterpret_float_as_int(dst, src, size);
+            });
+    }
+
+    fn visit_f32_reinterpret_i32(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::F32, |masm, dst, src, size| {
+                masm.reinterpret_int_as_float(dst, src, size);
+            });
+    }
+
+    fn visit_f64_reinterpret_i64(&mut self) {
+        self.context
+            .convert_op(self.masm, WasmType::F64, |masm, dst, src, size| {
+                masm.reinterpret_int_as_float(dst, src, size);
+            });
+    }
+
     wasmparser::for_each_operator!(def_unsupported);
 }
 
@@ -656,6 +1439,13 @@ where
                 masm.cmp_with_set(src, dst.get_reg().unwrap(), kind, size);
             });
     }
+
+    fn cmp_floats(&mut self, kind: FloatCmpKind, size: OperandSize) {
+        self.context
+            .float_cmp_op(self.masm, size, move |masm, dst, lhs, rhs, size| {
+                masm.float_cmp_with_set(lhs, rhs, dst, kind, size);
+            });
+    }
 }
 
 impl From<WasmType> for OperandSize {
@@ -663,6 +1453,9 @@ impl From<WasmType> for OperandSize {
         match ty {
             WasmType::I32 | WasmType::F32 => OperandSize::S32,
             WasmType::I64 | WasmType::F64 => OperandSize::S64,
+            // FIXME Remove hardcoded size of references, this will be
+            // needed once 32-bit architectures are supported.
+            WasmType::Ref(_) => OperandSize::S64,
             ty => todo!("unsupported type {:?}", ty),
         }
     }
@@ -0,0 +1,44 @@
+;;! target = "aarch64"
+
+(module
+  (func $dummy)
+  (func (export "as-if-condition")
+   (block (result i32) (i32.const 1)) (if (then (call $dummy)))
+  )
+)
+  
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 300080d2             	mov	x16, #1
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 1f00006a             	tst	w0, w0
+;;   28:	 e0000054             	b.eq	#0x44
+;;   2c:	 ff2300d1             	sub	sp, sp, #8
+;;   30:	 fc030091             	mov	x28, sp
+;;   34:	 00000094             	bl	#0x34
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 ff230091             	add	sp, sp, #8
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 ff230091             	add	sp, sp, #8
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   50:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   54:	 c0035fd6             	ret	
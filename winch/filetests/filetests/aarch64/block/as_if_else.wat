@@ -0,0 +1,27 @@
+;;! target = "aarch64"
+(module
+  (func (export "as-if-else") (result i32)
+      (if (result i32) (i32.const 1) (then (i32.const 2)) (else (block (result i32) (i32.const 1))))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 300080d2             	mov	x16, #1
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 1f00006a             	tst	w0, w0
+;;   28:	 80000054             	b.eq	#0x38
+;;   2c:	 500080d2             	mov	x16, #2
+;;   30:	 e003102a             	mov	w0, w16
+;;   34:	 03000014             	b	#0x40
+;;   38:	 300080d2             	mov	x16, #1
+;;   3c:	 e003102a             	mov	w0, w16
+;;   40:	 ff230091             	add	sp, sp, #8
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
@@ -0,0 +1,22 @@
+;;! target = "aarch64"
+
+(module
+  (func (export "singular") (result i32)
+    (block (nop))
+    (block (result i32) (i32.const 7))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 f00080d2             	mov	x16, #7
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 ff230091             	add	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
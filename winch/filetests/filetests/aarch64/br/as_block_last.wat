@@ -0,0 +1,39 @@
+;;! target = "aarch64"
+
+(module
+  (func $dummy)
+  (func (export "as-block-last")
+    (block (nop) (call $dummy) (br 0))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff2300d1             	sub	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 00000094             	bl	#0x24
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 ff230091             	add	sp, sp, #8
+;;   30:	 fc030091             	mov	x28, sp
+;;   34:	 ff230091             	add	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   40:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   44:	 c0035fd6             	ret	
@@ -0,0 +1,41 @@
+;;! target = "aarch64"
+
+(module
+  (func $dummy)
+  (func (export "as-block-value") (result i32)
+    (block (result i32) (nop) (call $dummy) (br 0 (i32.const 2)))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff2300d1             	sub	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 00000094             	bl	#0x24
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 ff230091             	add	sp, sp, #8
+;;   30:	 fc030091             	mov	x28, sp
+;;   34:	 500080d2             	mov	x16, #2
+;;   38:	 e003102a             	mov	w0, w16
+;;   3c:	 ff230091             	add	sp, sp, #8
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   48:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   4c:	 c0035fd6             	ret	
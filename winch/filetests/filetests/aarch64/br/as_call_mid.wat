@@ -0,0 +1,42 @@
+;;! target = "aarch64"
+
+(module
+  (func $f (param i32 i32 i32) (result i32) (i32.const -1))
+  (func (export "as-call-mid") (result i32)
+    (block (result i32)
+      (call $f (i32.const 1) (br 0 (i32.const 13)) (i32.const 3))
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804301b8             	stur	w0, [x28, #0x14]
+;;   1c:	 810301b8             	stur	w1, [x28, #0x10]
+;;   20:	 82c300b8             	stur	w2, [x28, #0xc]
+;;   24:	 894300f8             	stur	x9, [x28, #4]
+;;   28:	 f07f40b2             	orr	x16, xzr, #0xffffffff
+;;   2c:	 e003102a             	mov	w0, w16
+;;   30:	 ff630091             	add	sp, sp, #0x18
+;;   34:	 fc030091             	mov	x28, sp
+;;   38:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   3c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   40:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 b00180d2             	mov	x16, #0xd
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 ff230091             	add	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
@@ -0,0 +1,32 @@
+;;! target = "aarch64"
+(module
+  (func (export "as-if-else") (param i32 i32) (result i32)
+    (block (result i32)
+      (if (result i32) (local.get 0)
+        (then (local.get 1))
+        (else (br 1 (i32.const 4)))
+      )
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   28:	 1f00006a             	tst	w0, w0
+;;   2c:	 60000054             	b.eq	#0x38
+;;   30:	 808340b8             	ldur	w0, [x28, #8]
+;;   34:	 03000014             	b	#0x40
+;;   38:	 900080d2             	mov	x16, #4
+;;   3c:	 e003102a             	mov	w0, w16
+;;   40:	 ff430091             	add	sp, sp, #0x10
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
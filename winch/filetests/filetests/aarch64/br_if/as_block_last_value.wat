@@ -0,0 +1,43 @@
+;;! target = "aarch64"
+(module
+  (func $dummy)
+  (func (export "as-block-last-value") (param i32) (result i32)
+    (block (result i32)
+      (call $dummy) (call $dummy) (br_if 0 (i32.const 11) (local.get 0))
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 00000094             	bl	#0x20
+;;   24:	 fc030091             	mov	x28, sp
+;;   28:	 00000094             	bl	#0x28
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   34:	 700180d2             	mov	x16, #0xb
+;;   38:	 e003102a             	mov	w0, w16
+;;   3c:	 3f00016a             	tst	w1, w1
+;;   40:	 ff430091             	add	sp, sp, #0x10
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
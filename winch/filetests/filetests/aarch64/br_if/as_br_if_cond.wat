@@ -0,0 +1,25 @@
+;;! target = "aarch64"
+(module
+  (func (export "as-br-if-cond")
+    (block (br_if 0 (br_if 0 (i32.const 1) (i32.const 1))))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 300080d2             	mov	x16, #1
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 1f00006a             	tst	w0, w0
+;;   28:	 81000054             	b.ne	#0x38
+;;   2c:	 300080d2             	mov	x16, #1
+;;   30:	 e003102a             	mov	w0, w16
+;;   34:	 1f00006a             	tst	w0, w0
+;;   38:	 ff230091             	add	sp, sp, #8
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   44:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   48:	 c0035fd6             	ret	
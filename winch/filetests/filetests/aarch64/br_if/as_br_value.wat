@@ -0,0 +1,23 @@
+;;! target = "aarch64"
+(module
+  (func (export "as-br-value") (result i32)
+    (block (result i32) (br 0 (br_if 0 (i32.const 1) (i32.const 2))))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 500080d2             	mov	x16, #2
+;;   20:	 e103102a             	mov	w1, w16
+;;   24:	 300080d2             	mov	x16, #1
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 3f00016a             	tst	w1, w1
+;;   30:	 ff230091             	add	sp, sp, #8
+;;   34:	 fc030091             	mov	x28, sp
+;;   38:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   3c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   40:	 c0035fd6             	ret	
@@ -0,0 +1,59 @@
+;;! target = "aarch64"
+(module
+  (func $f (param i32 i32 i32) (result i32) (i32.const -1))
+  (func (export "as-call-first") (result i32)
+    (block (result i32)
+      (call $f
+        (br_if 0 (i32.const 12) (i32.const 1)) (i32.const 2) (i32.const 3)
+      )
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804301b8             	stur	w0, [x28, #0x14]
+;;   1c:	 810301b8             	stur	w1, [x28, #0x10]
+;;   20:	 82c300b8             	stur	w2, [x28, #0xc]
+;;   24:	 894300f8             	stur	x9, [x28, #4]
+;;   28:	 f07f40b2             	orr	x16, xzr, #0xffffffff
+;;   2c:	 e003102a             	mov	w0, w16
+;;   30:	 ff630091             	add	sp, sp, #0x18
+;;   34:	 fc030091             	mov	x28, sp
+;;   38:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   3c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   40:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 300080d2             	mov	x16, #1
+;;   20:	 e103102a             	mov	w1, w16
+;;   24:	 900180d2             	mov	x16, #0xc
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 3f00016a             	tst	w1, w1
+;;   30:	 a1010054             	b.ne	#0x64
+;;   34:	 ff2300d1             	sub	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 800300f8             	stur	x0, [x28]
+;;   40:	 800340b8             	ldur	w0, [x28]
+;;   44:	 500080d2             	mov	x16, #2
+;;   48:	 e103102a             	mov	w1, w16
+;;   4c:	 700080d2             	mov	x16, #3
+;;   50:	 e203102a             	mov	w2, w16
+;;   54:	 00000094             	bl	#0x54
+;;   58:	 fc030091             	mov	x28, sp
+;;   5c:	 ff230091             	add	sp, sp, #8
+;;   60:	 fc030091             	mov	x28, sp
+;;   64:	 ff230091             	add	sp, sp, #8
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   70:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   74:	 c0035fd6             	ret	
@@ -0,0 +1,44 @@
+;;! target = "aarch64"
+(module
+  (func $dummy)
+  (func (export "as-if-else") (param i32 i32)
+    (block
+      (if (local.get 0) (then (call $dummy)) (else (br_if 1 (local.get 1))))
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   28:	 1f00006a             	tst	w0, w0
+;;   2c:	 80000054             	b.eq	#0x3c
+;;   30:	 00000094             	bl	#0x30
+;;   34:	 fc030091             	mov	x28, sp
+;;   38:	 03000014             	b	#0x44
+;;   3c:	 808340b8             	ldur	w0, [x28, #8]
+;;   40:	 1f00006a             	tst	w0, w0
+;;   44:	 ff430091             	add	sp, sp, #0x10
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   50:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   54:	 c0035fd6             	ret	
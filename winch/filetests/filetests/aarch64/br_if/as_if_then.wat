@@ -0,0 +1,45 @@
+;;! target = "aarch64"
+(module
+  (func $dummy)
+  (func (export "as-if-then") (param i32 i32)
+    (block
+      (if (local.get 0) (then (br_if 1 (local.get 1))) (else (call $dummy)))
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   28:	 1f00006a             	tst	w0, w0
+;;   2c:	 a0000054             	b.eq	#0x40
+;;   30:	 808340b8             	ldur	w0, [x28, #8]
+;;   34:	 1f00006a             	tst	w0, w0
+;;   38:	 81000054             	b.ne	#0x48
+;;   3c:	 03000014             	b	#0x48
+;;   40:	 00000094             	bl	#0x40
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 ff430091             	add	sp, sp, #0x10
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   54:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   58:	 c0035fd6             	ret	
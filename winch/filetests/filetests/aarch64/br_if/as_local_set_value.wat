@@ -0,0 +1,31 @@
+;;! target = "aarch64"
+(module
+  (func (export "as-local-set-value") (param i32) (result i32)
+    (local i32)
+    (block (result i32)
+      (local.set 0 (br_if 0 (i32.const 17) (local.get 0)))
+      (i32.const -1)
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   24:	 300280d2             	mov	x16, #0x11
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 3f00016a             	tst	w1, w1
+;;   30:	 81000054             	b.ne	#0x40
+;;   34:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   38:	 f07f40b2             	orr	x16, xzr, #0xffffffff
+;;   3c:	 e003102a             	mov	w0, w16
+;;   40:	 ff430091             	add	sp, sp, #0x10
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
@@ -0,0 +1,38 @@
+;;! target = "aarch64"
+
+(module
+  (func (param i32) (result i32)
+    (block
+      (block
+        (br_table 0 1 (local.get 0)))
+      (return (i32.const 10)))
+    (i32.const 20))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 1f040071             	cmp	w0, #1
+;;   28:	 62010054             	b.hs	#0x54
+;;   2c:	 e123809a             	csel	x1, xzr, x0, hs
+;;   30:	 9f2203d5             	csdb	
+;;   34:	 90000010             	adr	x16, #0x44
+;;   38:	 015aa1b8             	ldrsw	x1, [x16, w1, uxtw #2]
+;;   3c:	 1002018b             	add	x16, x16, x1
+;;   40:	 00021fd6             	br	x16
+;;   44:	 04000000             	udf	#4
+;;   48:	 500180d2             	mov	x16, #0xa
+;;   4c:	 e003102a             	mov	w0, w16
+;;   50:	 03000014             	b	#0x5c
+;;   54:	 900280d2             	mov	x16, #0x14
+;;   58:	 e003102a             	mov	w0, w16
+;;   5c:	 ff430091             	add	sp, sp, #0x10
+;;   60:	 fc030091             	mov	x28, sp
+;;   64:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   68:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   6c:	 c0035fd6             	ret	
@@ -0,0 +1,74 @@
+;;! target = "aarch64"
+
+(module
+  (func $fibonacci8 (param $n i32) (result i32)
+    (if (result i32) (i32.le_s (local.get $n) (i32.const 1))
+      (then
+        ;; If n <= 1, return n (base case)
+        (local.get $n)
+      )
+      (else
+        ;; Else, return fibonacci(n - 1) + fibonacci(n - 2)
+        (i32.add
+          (call $fibonacci8
+            (i32.sub (local.get $n) (i32.const 1)) ;; Calculate n - 1
+          )
+          (call $fibonacci8
+            (i32.sub (local.get $n) (i32.const 2)) ;; Calculate n - 2
+          )
+        )
+      )
+    )
+  )
+  (export "fib" (func $fibonacci8))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 1f040071             	cmp	w0, #1
+;;   28:	 e0c79f9a             	cset	x0, le
+;;   2c:	 1f00006a             	tst	w0, w0
+;;   30:	 60000054             	b.eq	#0x3c
+;;   34:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   38:	 1f000014             	b	#0xb4
+;;   3c:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   40:	 00040051             	sub	w0, w0, #1
+;;   44:	 ff2300d1             	sub	sp, sp, #8
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 800300f8             	stur	x0, [x28]
+;;   50:	 ff2300d1             	sub	sp, sp, #8
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 808340b8             	ldur	w0, [x28, #8]
+;;   5c:	 00000094             	bl	#0x5c
+;;   60:	 fc030091             	mov	x28, sp
+;;   64:	 ff430091             	add	sp, sp, #0x10
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   70:	 21080051             	sub	w1, w1, #2
+;;   74:	 ff2300d1             	sub	sp, sp, #8
+;;   78:	 fc030091             	mov	x28, sp
+;;   7c:	 800300f8             	stur	x0, [x28]
+;;   80:	 ff2300d1             	sub	sp, sp, #8
+;;   84:	 fc030091             	mov	x28, sp
+;;   88:	 810300f8             	stur	x1, [x28]
+;;   8c:	 800340b8             	ldur	w0, [x28]
+;;   90:	 00000094             	bl	#0x90
+;;   94:	 fc030091             	mov	x28, sp
+;;   98:	 ff230091             	add	sp, sp, #8
+;;   9c:	 fc030091             	mov	x28, sp
+;;   a0:	 810340b8             	ldur	w1, [x28]
+;;   a4:	 ff230091             	add	sp, sp, #8
+;;   a8:	 fc030091             	mov	x28, sp
+;;   ac:	 2160200b             	add	w1, w1, w0, uxtx
+;;   b0:	 e003012a             	mov	w0, w1
+;;   b4:	 ff430091             	add	sp, sp, #0x10
+;;   b8:	 fc030091             	mov	x28, sp
+;;   bc:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   c0:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   c4:	 c0035fd6             	ret	
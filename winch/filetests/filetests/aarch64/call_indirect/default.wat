@@ -0,0 +1,93 @@
+;;! target = "aarch64"
+
+(module
+  (type $t (func (param i32) (result i32)))
+  (table 1 funcref)
+  (func (param i32) (result i32)
+    (i32.const 5)
+    (local.get 0)
+    (call_indirect (type $t)))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   24:	 ff2300d1             	sub	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 900300f8             	stur	x16, [x28]
+;;   30:	 810340b8             	ldur	w1, [x28]
+;;   34:	 ff230091             	add	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 230145b8             	ldur	w3, [x9, #0x50]
+;;   40:	 3f00036b             	cmp	w1, w3
+;;   44:	 e2070054             	b.hs	#0x140
+;;   48:	 228144f8             	ldur	x2, [x9, #0x48]
+;;   4c:	 e303012a             	mov	w3, w1
+;;   50:	 100180d2             	mov	x16, #8
+;;   54:	 637c109b             	mul	x3, x3, x16
+;;   58:	 4260238b             	add	x2, x2, x3, uxtx
+;;   5c:	 400040f8             	ldur	x0, [x2]
+;;   60:	 1f0000ea             	tst	x0, x0
+;;   64:	 61020054             	b.ne	#0xb0
+;;   68:	 e00309aa             	mov	x0, x9
+;;   6c:	 ff2300d1             	sub	sp, sp, #8
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 800300f8             	stur	x0, [x28]
+;;   78:	 ff2300d1             	sub	sp, sp, #8
+;;   7c:	 fc030091             	mov	x28, sp
+;;   80:	 810300f8             	stur	x1, [x28]
+;;   84:	 808340f8             	ldur	x0, [x28, #8]
+;;   88:	 100080d2             	mov	x16, #0
+;;   8c:	 e103102a             	mov	w1, w16
+;;   90:	 820340b8             	ldur	w2, [x28]
+;;   94:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   98:	 108244f8             	ldur	x16, [x16, #0x48]
+;;   9c:	 00023fd6             	blr	x16
+;;   a0:	 fc030091             	mov	x28, sp
+;;   a4:	 ff430091             	add	sp, sp, #0x10
+;;   a8:	 fc030091             	mov	x28, sp
+;;   ac:	 02000014             	b	#0xb4
+;;   b0:	 00f87f92             	and	x0, x0, #0xfffffffffffffffe
+;;   b4:	 ff2300d1             	sub	sp, sp, #8
+;;   b8:	 fc030091             	mov	x28, sp
+;;   bc:	 800300f8             	stur	x0, [x28]
+;;   c0:	 830340f8             	ldur	x3, [x28]
+;;   c4:	 ff230091             	add	sp, sp, #8
+;;   c8:	 fc030091             	mov	x28, sp
+;;   cc:	 7f0000f1             	cmp	x3, #0
+;;   d0:	 a0030054             	b.eq	#0x144
+;;   d4:	 200144f8             	ldur	x0, [x9, #0x40]
+;;   d8:	 000040b8             	ldur	w0, [x0]
+;;   dc:	 618041b8             	ldur	w1, [x3, #0x18]
+;;   e0:	 3f00006b             	cmp	w1, w0
+;;   e4:	 21030054             	b.ne	#0x148
+;;   e8:	 600042f8             	ldur	x0, [x3, #0x20]
+;;   ec:	 e10309aa             	mov	x1, x9
+;;   f0:	 ff2300d1             	sub	sp, sp, #8
+;;   f4:	 fc030091             	mov	x28, sp
+;;   f8:	 800300f8             	stur	x0, [x28]
+;;   fc:	 ff2300d1             	sub	sp, sp, #8
+;;  100:	 fc030091             	mov	x28, sp
+;;  104:	 810300f8             	stur	x1, [x28]
+;;  108:	 808340f8             	ldur	x0, [x28, #8]
+;;  10c:	 810340f8             	ldur	x1, [x28]
+;;  110:	 b00080d2             	mov	x16, #5
+;;  114:	 e203102a             	mov	w2, w16
+;;  118:	 700041f8             	ldur	x16, [x3, #0x10]
+;;  11c:	 00023fd6             	blr	x16
+;;  120:	 fc030091             	mov	x28, sp
+;;  124:	 ff430091             	add	sp, sp, #0x10
+;;  128:	 fc030091             	mov	x28, sp
+;;  12c:	 ff430091             	add	sp, sp, #0x10
+;;  130:	 fc030091             	mov	x28, sp
+;;  134:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;  138:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  13c:	 c0035fd6             	ret	
+;;  140:	 1fc10000             	udf	#0xc11f
+;;  144:	 1fc10000             	udf	#0xc11f
+;;  148:	 1fc10000             	udf	#0xc11f
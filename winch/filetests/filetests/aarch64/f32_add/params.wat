@@ -0,0 +1,26 @@
+;;! target = "aarch64"
+
+(module
+  (func (param f32 f32) (result f32)
+    (local.get 0)
+    (local.get 1)
+    (f32.add))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300bc             	stur	s0, [x28, #0xc]
+;;   1c:	 818300bc             	stur	s1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340bc             	ldur	s0, [x28, #8]
+;;   28:	 81c340bc             	ldur	s1, [x28, #0xc]
+;;   2c:	 2128201e             	fadd	s1, s1, s0
+;;   30:	 2040601e             	fmov	d0, d1
+;;   34:	 ff430091             	add	sp, sp, #0x10
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   40:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   44:	 c0035fd6             	ret	
@@ -0,0 +1,42 @@
+;;! target = "aarch64"
+
+(module
+  (func $id-f32 (param f32) (result f32) (local.get 0))
+  (func (export "type-first-f32") (result f32) (call $id-f32 (f32.const 1.32)))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300bc             	stur	s0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340bc             	ldur	s0, [x28, #0xc]
+;;   24:	 ff430091             	add	sp, sp, #0x10
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff2300d1             	sub	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 70b89e52             	mov	w16, #0xf5c3
+;;   28:	 10f5a772             	movk	w16, #0x3fa8, lsl #16
+;;   2c:	 0002271e             	fmov	s0, w16
+;;   30:	 00000094             	bl	#0x30
+;;   34:	 fc030091             	mov	x28, sp
+;;   38:	 ff230091             	add	sp, sp, #8
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 ff230091             	add	sp, sp, #8
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
@@ -0,0 +1,22 @@
+;;! target = "aarch64"
+
+(module
+  (func (param i32) (result f32)
+    (local.get 0)
+    (f32.convert_i32_s))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 0000221e             	scvtf	s0, w0
+;;   28:	 ff430091             	add	sp, sp, #0x10
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   34:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   38:	 c0035fd6             	ret	
@@ -0,0 +1,22 @@
+;;! target = "aarch64"
+
+(module
+  (func (param i64) (result f32)
+    (local.get 0)
+    (f32.convert_i64_s))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808300f8             	stur	x0, [x28, #8]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 808340f8             	ldur	x0, [x28, #8]
+;;   24:	 0000229e             	scvtf	s0, x0
+;;   28:	 ff430091             	add	sp, sp, #0x10
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   34:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   38:	 c0035fd6             	ret	
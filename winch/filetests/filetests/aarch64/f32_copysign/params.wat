@@ -0,0 +1,31 @@
+;;! target = "aarch64"
+
+(module
+  (func (param f32 f32) (result f32)
+    (local.get 0)
+    (local.get 1)
+    (f32.copysign))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300bc             	stur	s0, [x28, #0xc]
+;;   1c:	 818300bc             	stur	s1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340bc             	ldur	s0, [x28, #8]
+;;   28:	 81c340bc             	ldur	s1, [x28, #0xc]
+;;   2c:	 113c040e             	mov	w17, v0.s[0]
+;;   30:	 31020112             	and	w17, w17, #0x80000000
+;;   34:	 303c040e             	mov	w16, v1.s[0]
+;;   38:	 107a0012             	and	w16, w16, #0x7fffffff
+;;   3c:	 1002112a             	orr	w16, w16, w17
+;;   40:	 0102271e             	fmov	s1, w16
+;;   44:	 2040601e             	fmov	d0, d1
+;;   48:	 ff430091             	add	sp, sp, #0x10
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   54:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   58:	 c0035fd6             	ret	
@@ -0,0 +1,29 @@
+;;! target = "aarch64"
+
+(module
+  (memory 1)
+  (func (param i32 f32)
+    (local.get 0)
+    (local.get 1)
+    (f32.store))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 808300bc             	stur	s0, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340bc             	ldur	s0, [x28, #8]
+;;   28:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   2c:	 e003002a             	mov	w0, w0
+;;   30:	 210145f8             	ldur	x1, [x9, #0x50]
+;;   34:	 2160208b             	add	x1, x1, x0, uxtx
+;;   38:	 200000bc             	stur	s0, [x1]
+;;   3c:	 ff430091             	add	sp, sp, #0x10
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   48:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   4c:	 c0035fd6             	ret	
@@ -0,0 +1,22 @@
+;;! target = "aarch64"
+
+(module
+  (func (param f64) (result f64)
+    (local.get 0)
+    (f64.abs))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808300fc             	stur	d0, [x28, #8]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 808340fc             	ldur	d0, [x28, #8]
+;;   24:	 00c0601e             	fabs	d0, d0
+;;   28:	 ff430091             	add	sp, sp, #0x10
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   34:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   38:	 c0035fd6             	ret	
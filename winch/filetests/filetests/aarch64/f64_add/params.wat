@@ -0,0 +1,26 @@
+;;! target = "aarch64"
+
+(module
+  (func (param f64 f64) (result f64)
+    (local.get 0)
+    (local.get 1)
+    (f64.add))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 800301fc             	stur	d0, [x28, #0x10]
+;;   1c:	 818300fc             	stur	d1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340fc             	ldur	d0, [x28, #8]
+;;   28:	 810341fc             	ldur	d1, [x28, #0x10]
+;;   2c:	 2128601e             	fadd	d1, d1, d0
+;;   30:	 2040601e             	fmov	d0, d1
+;;   34:	 ff630091             	add	sp, sp, #0x18
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   40:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   44:	 c0035fd6             	ret	
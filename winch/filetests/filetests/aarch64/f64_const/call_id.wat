@@ -0,0 +1,44 @@
+;;! target = "aarch64"
+
+(module
+  (func $id-f64 (param f64) (result f64) (local.get 0))
+  (func (export "type-first-f64") (result f64) (call $id-f64 (f64.const 1.32)))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808300fc             	stur	d0, [x28, #8]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 808340fc             	ldur	d0, [x28, #8]
+;;   24:	 ff430091             	add	sp, sp, #0x10
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff2300d1             	sub	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 f0a390d2             	mov	x16, #0x851f
+;;   28:	 703daaf2             	movk	x16, #0x51eb, lsl #16
+;;   2c:	 10d7c3f2             	movk	x16, #0x1eb8, lsl #32
+;;   30:	 b0fee7f2             	movk	x16, #0x3ff5, lsl #48
+;;   34:	 0002679e             	fmov	d0, x16
+;;   38:	 00000094             	bl	#0x38
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 ff230091             	add	sp, sp, #8
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 ff230091             	add	sp, sp, #8
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   54:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   58:	 c0035fd6             	ret	
@@ -0,0 +1,27 @@
+;;! target = "aarch64"
+
+(module
+  (memory 1)
+  (func (param i32) (result f64)
+    (local.get 0)
+    (f64.load offset=8))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 e003002a             	mov	w0, w0
+;;   28:	 210145f8             	ldur	x1, [x9, #0x50]
+;;   2c:	 2160208b             	add	x1, x1, x0, uxtx
+;;   30:	 21200091             	add	x1, x1, #8
+;;   34:	 200040fc             	ldur	d0, [x1]
+;;   38:	 ff430091             	add	sp, sp, #0x10
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   44:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   48:	 c0035fd6             	ret	
@@ -0,0 +1,30 @@
+;;! target = "aarch64"
+
+(module
+  (memory 1)
+  (func (param i32 f64)
+    (local.get 0)
+    (local.get 1)
+    (f64.store offset=8))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804301b8             	stur	w0, [x28, #0x14]
+;;   1c:	 808300fc             	stur	d0, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340fc             	ldur	d0, [x28, #8]
+;;   28:	 804341b8             	ldur	w0, [x28, #0x14]
+;;   2c:	 e003002a             	mov	w0, w0
+;;   30:	 210145f8             	ldur	x1, [x9, #0x50]
+;;   34:	 2160208b             	add	x1, x1, x0, uxtx
+;;   38:	 21200091             	add	x1, x1, #8
+;;   3c:	 200000fc             	stur	d0, [x1]
+;;   40:	 ff630091             	add	sp, sp, #0x18
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
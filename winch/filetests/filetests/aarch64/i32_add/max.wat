@@ -8,14 +8,16 @@
 )
 ;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
 ;;    4:	 fd030091             	mov	x29, sp
-;;    8:	 fc030091             	mov	x28, sp
-;;    c:	 ff2300d1             	sub	sp, sp, #8
-;;   10:	 fc030091             	mov	x28, sp
-;;   14:	 890300f8             	stur	x9, [x28]
-;;   18:	 f07b40b2             	orr	x16, xzr, #0x7fffffff
-;;   1c:	 e003102a             	mov	w0, w16
-;;   20:	 00040011             	add	w0, w0, #1
-;;   24:	 ff230091             	add	sp, sp, #8
-;;   28:	 fc030091             	mov	x28, sp
-;;   2c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
-;;   30:	 c0035fd6             	ret	
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 f07b40b2             	orr	x16, xzr, #0x7fffffff
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 00040011             	add	w0, w0, #1
+;;   28:	 ff230091             	add	sp, sp, #8
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   34:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   38:	 c0035fd6             	ret	
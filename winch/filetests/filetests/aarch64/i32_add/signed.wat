@@ -9,15 +9,17 @@
 )
 ;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
 ;;    4:	 fd030091             	mov	x29, sp
-;;    8:	 fc030091             	mov	x28, sp
-;;    c:	 ff2300d1             	sub	sp, sp, #8
-;;   10:	 fc030091             	mov	x28, sp
-;;   14:	 890300f8             	stur	x9, [x28]
-;;   18:	 f07f40b2             	orr	x16, xzr, #0xffffffff
-;;   1c:	 e003102a             	mov	w0, w16
-;;   20:	 f07f40b2             	orr	x16, xzr, #0xffffffff
-;;   24:	 0060300b             	add	w0, w0, w16, uxtx
-;;   28:	 ff230091             	add	sp, sp, #8
-;;   2c:	 fc030091             	mov	x28, sp
-;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
-;;   34:	 c0035fd6             	ret	
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 f07f40b2             	orr	x16, xzr, #0xffffffff
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 f07f40b2             	orr	x16, xzr, #0xffffffff
+;;   28:	 0060300b             	add	w0, w0, w16, uxtx
+;;   2c:	 ff230091             	add	sp, sp, #8
+;;   30:	 fc030091             	mov	x28, sp
+;;   34:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   38:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   3c:	 c0035fd6             	ret	
@@ -0,0 +1,42 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+        (local $foo i32)  
+        (local $bar i32)
+
+        (i32.const 1)
+        (local.set $foo)
+
+        (i32.const 2)
+        (local.set $bar)
+
+        (local.get $foo)
+        (local.get $bar)
+        (i32.and)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 908300f8             	stur	x16, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 300080d2             	mov	x16, #1
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   30:	 500080d2             	mov	x16, #2
+;;   34:	 e003102a             	mov	w0, w16
+;;   38:	 808300b8             	stur	w0, [x28, #8]
+;;   3c:	 808340b8             	ldur	w0, [x28, #8]
+;;   40:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   44:	 2100000a             	and	w1, w1, w0
+;;   48:	 e003012a             	mov	w0, w1
+;;   4c:	 ff430091             	add	sp, sp, #0x10
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   58:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   5c:	 c0035fd6             	ret	
@@ -0,0 +1,32 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+        (local $foo i32)
+
+        (i32.const 2)
+        (local.set $foo)
+
+        (local.get $foo)
+        (i32.clz)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 908300f8             	stur	x16, [x28, #8]
+;;   20:	 894300f8             	stur	x9, [x28, #4]
+;;   24:	 500080d2             	mov	x16, #2
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   30:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   34:	 0010c05a             	clz	w0, w0
+;;   38:	 ff430091             	add	sp, sp, #0x10
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   44:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   48:	 c0035fd6             	ret	
@@ -0,0 +1,36 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+	(i32.const 20)
+	(i32.const 10)
+	(i32.div_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 500180d2             	mov	x16, #0xa
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 900280d2             	mov	x16, #0x14
+;;   28:	 e103102a             	mov	w1, w16
+;;   2c:	 1f00006a             	tst	w0, w0
+;;   30:	 a0010054             	b.eq	#0x64
+;;   34:	 1f040031             	cmn	w0, #1
+;;   38:	 2008417a             	ccmp	w1, #1, #0, eq
+;;   3c:	 66010054             	b.vs	#0x68
+;;   40:	 007c4093             	sxtw	x0, w0
+;;   44:	 217c4093             	sxtw	x1, w1
+;;   48:	 210cc09a             	sdiv	x1, x1, x0
+;;   4c:	 e003012a             	mov	w0, w1
+;;   50:	 ff230091             	add	sp, sp, #8
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   5c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   60:	 c0035fd6             	ret	
+;;   64:	 1fc10000             	udf	#0xc11f
+;;   68:	 1fc10000             	udf	#0xc11f
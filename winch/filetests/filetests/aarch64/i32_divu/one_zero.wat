@@ -0,0 +1,32 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+	(i32.const 1)
+	(i32.const 0)
+	(i32.div_u)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 100080d2             	mov	x16, #0
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 300080d2             	mov	x16, #1
+;;   28:	 e103102a             	mov	w1, w16
+;;   2c:	 1f00006a             	tst	w0, w0
+;;   30:	 40010054             	b.eq	#0x58
+;;   34:	 e003002a             	mov	w0, w0
+;;   38:	 e103012a             	mov	w1, w1
+;;   3c:	 2108c09a             	udiv	x1, x1, x0
+;;   40:	 e003012a             	mov	w0, w1
+;;   44:	 ff230091             	add	sp, sp, #8
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   50:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   54:	 c0035fd6             	ret	
+;;   58:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,25 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+        (i32.const 2)
+        (i32.const 3)
+        (i32.ge_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 500080d2             	mov	x16, #2
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 1f0c0071             	cmp	w0, #3
+;;   28:	 e0b79f9a             	cset	x0, ge
+;;   2c:	 ff230091             	add	sp, sp, #8
+;;   30:	 fc030091             	mov	x28, sp
+;;   34:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   38:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   3c:	 c0035fd6             	ret	
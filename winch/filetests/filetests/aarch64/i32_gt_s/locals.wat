@@ -0,0 +1,42 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+        (local $foo i32)
+        (local $bar i32)
+
+        (i32.const 2)
+        (local.set $foo)
+        (i32.const 3)
+        (local.set $bar)
+
+        (local.get $foo)
+        (local.get $bar)
+        (i32.gt_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 908300f8             	stur	x16, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 500080d2             	mov	x16, #2
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   30:	 700080d2             	mov	x16, #3
+;;   34:	 e003102a             	mov	w0, w16
+;;   38:	 808300b8             	stur	w0, [x28, #8]
+;;   3c:	 808340b8             	ldur	w0, [x28, #8]
+;;   40:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   44:	 3f00006b             	cmp	w1, w0
+;;   48:	 e1d79f9a             	cset	x1, gt
+;;   4c:	 e003012a             	mov	w0, w1
+;;   50:	 ff430091             	add	sp, sp, #0x10
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   5c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   60:	 c0035fd6             	ret	
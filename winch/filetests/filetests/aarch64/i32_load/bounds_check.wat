@@ -0,0 +1,32 @@
+;;! target = "aarch64"
+
+(module
+  (memory 1)
+  (func (param i32) (result i32)
+    (local.get 0)
+    (i32.load offset=0x80000000))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 e003002a             	mov	w0, w0
+;;   28:	 f0737eb2             	orr	x16, xzr, #0x7ffffffc
+;;   2c:	 1f0010eb             	cmp	x0, x16
+;;   30:	 68010054             	b.hi	#0x5c
+;;   34:	 210145f8             	ldur	x1, [x9, #0x50]
+;;   38:	 2160208b             	add	x1, x1, x0, uxtx
+;;   3c:	 1000b0d2             	mov	x16, #0x80000000
+;;   40:	 2160308b             	add	x1, x1, x16, uxtx
+;;   44:	 200040b8             	ldur	w0, [x1]
+;;   48:	 ff430091             	add	sp, sp, #0x10
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   54:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   58:	 c0035fd6             	ret	
+;;   5c:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,34 @@
+;;! target = "aarch64"
+;;! static_memory_bound = 0
+(module
+  (memory 1)
+  (func (param i32) (result i32)
+    (local.get 0)
+    (i32.load))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 e003002a             	mov	w0, w0
+;;   28:	 218145f8             	ldur	x1, [x9, #0x58]
+;;   2c:	 e20300aa             	mov	x2, x0
+;;   30:	 421000b1             	adds	x2, x2, #4
+;;   34:	 62010054             	b.hs	#0x60
+;;   38:	 5f0001eb             	cmp	x2, x1
+;;   3c:	 48010054             	b.hi	#0x64
+;;   40:	 210145f8             	ldur	x1, [x9, #0x50]
+;;   44:	 2160208b             	add	x1, x1, x0, uxtx
+;;   48:	 200040b8             	ldur	w0, [x1]
+;;   4c:	 ff430091             	add	sp, sp, #0x10
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   58:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   5c:	 c0035fd6             	ret	
+;;   60:	 1fc10000             	udf	#0xc11f
+;;   64:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,33 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+	(i32.const 7)
+	(i32.const 5)
+	(i32.rem_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 b00080d2             	mov	x16, #5
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 f00080d2             	mov	x16, #7
+;;   28:	 e103102a             	mov	w1, w16
+;;   2c:	 1f00006a             	tst	w0, w0
+;;   30:	 60010054             	b.eq	#0x5c
+;;   34:	 007c4093             	sxtw	x0, w0
+;;   38:	 217c4093             	sxtw	x1, w1
+;;   3c:	 300cc09a             	sdiv	x16, x1, x0
+;;   40:	 0186001b             	msub	w1, w16, w0, w1
+;;   44:	 e003012a             	mov	w0, w1
+;;   48:	 ff230091             	add	sp, sp, #8
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   54:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   58:	 c0035fd6             	ret	
+;;   5c:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,29 @@
+;;! target = "aarch64"
+
+(module
+  (memory 1)
+  (func (param i32 i32)
+    (local.get 0)
+    (local.get 1)
+    (i32.store))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340b8             	ldur	w0, [x28, #8]
+;;   28:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   2c:	 e103012a             	mov	w1, w1
+;;   30:	 220145f8             	ldur	x2, [x9, #0x50]
+;;   34:	 4260218b             	add	x2, x2, x1, uxtx
+;;   38:	 400000b8             	stur	w0, [x2]
+;;   3c:	 ff430091             	add	sp, sp, #0x10
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   48:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   4c:	 c0035fd6             	ret	
@@ -9,17 +9,19 @@
 )
 ;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
 ;;    4:	 fd030091             	mov	x29, sp
-;;    8:	 fc030091             	mov	x28, sp
-;;    c:	 ff4300d1             	sub	sp, sp, #0x10
-;;   10:	 fc030091             	mov	x28, sp
-;;   14:	 80c300b8             	stur	w0, [x28, #0xc]
-;;   18:	 818300b8             	stur	w1, [x28, #8]
-;;   1c:	 890300f8             	stur	x9, [x28]
-;;   20:	 808340b8             	ldur	w0, [x28, #8]
-;;   24:	 81c340b8             	ldur	w1, [x28, #0xc]
-;;   28:	 2160204b             	sub	w1, w1, w0, uxtx
-;;   2c:	 e003012a             	mov	w0, w1
-;;   30:	 ff430091             	add	sp, sp, #0x10
-;;   34:	 fc030091             	mov	x28, sp
-;;   38:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
-;;   3c:	 c0035fd6             	ret	
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340b8             	ldur	w0, [x28, #8]
+;;   28:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   2c:	 2160204b             	sub	w1, w1, w0, uxtx
+;;   30:	 e003012a             	mov	w0, w1
+;;   34:	 ff430091             	add	sp, sp, #0x10
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   40:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   44:	 c0035fd6             	ret	
@@ -0,0 +1,35 @@
+;;! target = "aarch64"
+
+(module
+  (func (param f32) (result i32)
+    (local.get 0)
+    (i32.trunc_f32_s))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300bc             	stur	s0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340bc             	ldur	s0, [x28, #0xc]
+;;   24:	 0020201e             	fcmp	s0, s0
+;;   28:	 e6010054             	b.vs	#0x64
+;;   2c:	 10e0b9d2             	mov	x16, #0xcf000000
+;;   30:	 1f02271e             	fmov	s31, w16
+;;   34:	 00203f1e             	fcmp	s0, s31
+;;   38:	 8b010054             	b.lt	#0x68
+;;   3c:	 10e0a9d2             	mov	x16, #0x4f000000
+;;   40:	 1f02271e             	fmov	s31, w16
+;;   44:	 00203f1e             	fcmp	s0, s31
+;;   48:	 2a010054             	b.ge	#0x6c
+;;   4c:	 0000381e             	fcvtzs	w0, s0
+;;   50:	 ff430091             	add	sp, sp, #0x10
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   5c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   60:	 c0035fd6             	ret	
+;;   64:	 1fc10000             	udf	#0xc11f
+;;   68:	 1fc10000             	udf	#0xc11f
+;;   6c:	 1fc10000             	udf	#0xc11f
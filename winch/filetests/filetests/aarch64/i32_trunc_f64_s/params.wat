@@ -0,0 +1,36 @@
+;;! target = "aarch64"
+
+(module
+  (func (param f64) (result i32)
+    (local.get 0)
+    (i32.trunc_f64_s))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808300fc             	stur	d0, [x28, #8]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 808340fc             	ldur	d0, [x28, #8]
+;;   24:	 0020601e             	fcmp	d0, d0
+;;   28:	 06020054             	b.vs	#0x68
+;;   2c:	 1004a0d2             	mov	x16, #0x200000
+;;   30:	 103cf8f2             	movk	x16, #0xc1e0, lsl #48
+;;   34:	 1f02679e             	fmov	d31, x16
+;;   38:	 00207f1e             	fcmp	d0, d31
+;;   3c:	 8d010054             	b.le	#0x6c
+;;   40:	 103ce8d2             	mov	x16, #0x41e0000000000000
+;;   44:	 1f02679e             	fmov	d31, x16
+;;   48:	 00207f1e             	fcmp	d0, d31
+;;   4c:	 2a010054             	b.ge	#0x70
+;;   50:	 0000781e             	fcvtzs	w0, d0
+;;   54:	 ff430091             	add	sp, sp, #0x10
+;;   58:	 fc030091             	mov	x28, sp
+;;   5c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   60:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   64:	 c0035fd6             	ret	
+;;   68:	 1fc10000             	udf	#0xc11f
+;;   6c:	 1fc10000             	udf	#0xc11f
+;;   70:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,34 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i64)
+	(i64.const 0x8000000000000000)
+	(i64.const -1)
+	(i64.div_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 10008092             	mov	x16, #-1
+;;   20:	 e00310aa             	mov	x0, x16
+;;   24:	 1000f0d2             	mov	x16, #-0x8000000000000000
+;;   28:	 e10310aa             	mov	x1, x16
+;;   2c:	 1f0000ea             	tst	x0, x0
+;;   30:	 60010054             	b.eq	#0x5c
+;;   34:	 1f0400b1             	cmn	x0, #1
+;;   38:	 200841fa             	ccmp	x1, #1, #0, eq
+;;   3c:	 26010054             	b.vs	#0x60
+;;   40:	 210cc09a             	sdiv	x1, x1, x0
+;;   44:	 e00301aa             	mov	x0, x1
+;;   48:	 ff230091             	add	sp, sp, #8
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   54:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   58:	 c0035fd6             	ret	
+;;   5c:	 1fc10000             	udf	#0xc11f
+;;   60:	 1fc10000             	udf	#0xc11f
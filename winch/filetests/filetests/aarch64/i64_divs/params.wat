@@ -0,0 +1,34 @@
+;;! target = "aarch64"
+
+(module
+    (func (param i64) (param i64) (result i64)
+	(local.get 0)
+	(local.get 1)
+	(i64.div_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 800301f8             	stur	x0, [x28, #0x10]
+;;   1c:	 818300f8             	stur	x1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340f8             	ldur	x0, [x28, #8]
+;;   28:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   2c:	 1f0000ea             	tst	x0, x0
+;;   30:	 60010054             	b.eq	#0x5c
+;;   34:	 1f0400b1             	cmn	x0, #1
+;;   38:	 200841fa             	ccmp	x1, #1, #0, eq
+;;   3c:	 26010054             	b.vs	#0x60
+;;   40:	 210cc09a             	sdiv	x1, x1, x0
+;;   44:	 e00301aa             	mov	x0, x1
+;;   48:	 ff630091             	add	sp, sp, #0x18
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   54:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   58:	 c0035fd6             	ret	
+;;   5c:	 1fc10000             	udf	#0xc11f
+;;   60:	 1fc10000             	udf	#0xc11f
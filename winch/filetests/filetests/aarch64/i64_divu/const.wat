@@ -0,0 +1,30 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i64)
+	(i64.const 20)
+	(i64.const 10)
+	(i64.div_u)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 500180d2             	mov	x16, #0xa
+;;   20:	 e00310aa             	mov	x0, x16
+;;   24:	 900280d2             	mov	x16, #0x14
+;;   28:	 e10310aa             	mov	x1, x16
+;;   2c:	 1f0000ea             	tst	x0, x0
+;;   30:	 00010054             	b.eq	#0x50
+;;   34:	 2108c09a             	udiv	x1, x1, x0
+;;   38:	 e00301aa             	mov	x0, x1
+;;   3c:	 ff230091             	add	sp, sp, #8
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   48:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   4c:	 c0035fd6             	ret	
+;;   50:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,43 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+        (local $foo i64)
+        (local $bar i64)
+
+        (i64.const 2)
+        (local.set $foo)
+        (i64.const 3)
+        (local.set $bar)
+
+        (local.get $foo)
+        (local.get $bar)
+        (i64.ge_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 900301f8             	stur	x16, [x28, #0x10]
+;;   20:	 908300f8             	stur	x16, [x28, #8]
+;;   24:	 890300f8             	stur	x9, [x28]
+;;   28:	 500080d2             	mov	x16, #2
+;;   2c:	 e00310aa             	mov	x0, x16
+;;   30:	 800301f8             	stur	x0, [x28, #0x10]
+;;   34:	 700080d2             	mov	x16, #3
+;;   38:	 e00310aa             	mov	x0, x16
+;;   3c:	 808300f8             	stur	x0, [x28, #8]
+;;   40:	 808340f8             	ldur	x0, [x28, #8]
+;;   44:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   48:	 3f0000eb             	cmp	x1, x0
+;;   4c:	 e1b79f9a             	cset	x1, ge
+;;   50:	 e00301aa             	mov	x0, x1
+;;   54:	 ff630091             	add	sp, sp, #0x18
+;;   58:	 fc030091             	mov	x28, sp
+;;   5c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   60:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   64:	 c0035fd6             	ret	
@@ -0,0 +1,28 @@
+;;! target = "aarch64"
+
+(module
+    (func (param i64) (param i64) (result i32)
+        (local.get 0)
+        (local.get 1)
+        (i64.ge_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 800301f8             	stur	x0, [x28, #0x10]
+;;   1c:	 818300f8             	stur	x1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340f8             	ldur	x0, [x28, #8]
+;;   28:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   2c:	 3f0000eb             	cmp	x1, x0
+;;   30:	 e1b79f9a             	cset	x1, ge
+;;   34:	 e00301aa             	mov	x0, x1
+;;   38:	 ff630091             	add	sp, sp, #0x18
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   44:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   48:	 c0035fd6             	ret	
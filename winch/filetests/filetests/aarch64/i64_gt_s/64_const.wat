@@ -0,0 +1,26 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+        (i64.const 9223372036854775806)
+        (i64.const 9223372036854775807)
+        (i64.gt_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 f0f77fb2             	orr	x16, xzr, #0x7ffffffffffffffe
+;;   20:	 e00310aa             	mov	x0, x16
+;;   24:	 1000f092             	mov	x16, #0x7fffffffffffffff
+;;   28:	 1f0010eb             	cmp	x0, x16
+;;   2c:	 e0d79f9a             	cset	x0, gt
+;;   30:	 ff230091             	add	sp, sp, #8
+;;   34:	 fc030091             	mov	x28, sp
+;;   38:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   3c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   40:	 c0035fd6             	ret	
@@ -9,17 +9,19 @@
 )
 ;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
 ;;    4:	 fd030091             	mov	x29, sp
-;;    8:	 fc030091             	mov	x28, sp
-;;    c:	 ff6300d1             	sub	sp, sp, #0x18
-;;   10:	 fc030091             	mov	x28, sp
-;;   14:	 800301f8             	stur	x0, [x28, #0x10]
-;;   18:	 818300f8             	stur	x1, [x28, #8]
-;;   1c:	 890300f8             	stur	x9, [x28]
-;;   20:	 808340f8             	ldur	x0, [x28, #8]
-;;   24:	 810341f8             	ldur	x1, [x28, #0x10]
-;;   28:	 217c009b             	mul	x1, x1, x0
-;;   2c:	 e00301aa             	mov	x0, x1
-;;   30:	 ff630091             	add	sp, sp, #0x18
-;;   34:	 fc030091             	mov	x28, sp
-;;   38:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
-;;   3c:	 c0035fd6             	ret	
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 800301f8             	stur	x0, [x28, #0x10]
+;;   1c:	 818300f8             	stur	x1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340f8             	ldur	x0, [x28, #8]
+;;   28:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   2c:	 217c009b             	mul	x1, x1, x0
+;;   30:	 e00301aa             	mov	x0, x1
+;;   34:	 ff630091             	add	sp, sp, #0x18
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   40:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   44:	 c0035fd6             	ret	
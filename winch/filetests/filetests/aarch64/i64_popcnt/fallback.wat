@@ -0,0 +1,26 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i64)
+      i64.const 15
+      i64.popcnt
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 f00180d2             	mov	x16, #0xf
+;;   20:	 e00310aa             	mov	x0, x16
+;;   24:	 1f00679e             	fmov	d31, x0
+;;   28:	 ff5b200e             	cnt	v31.8b, v31.8b
+;;   2c:	 ffbb310e             	addv	b31, v31.8b
+;;   30:	 e03f010e             	umov	w0, v31.b[0]
+;;   34:	 ff230091             	add	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   40:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   44:	 c0035fd6             	ret	
@@ -0,0 +1,31 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i64)
+	(i64.const 0x8000000000000000)
+	(i64.const -1)
+	(i64.rem_s)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 10008092             	mov	x16, #-1
+;;   20:	 e00310aa             	mov	x0, x16
+;;   24:	 1000f0d2             	mov	x16, #-0x8000000000000000
+;;   28:	 e10310aa             	mov	x1, x16
+;;   2c:	 1f0000ea             	tst	x0, x0
+;;   30:	 20010054             	b.eq	#0x54
+;;   34:	 300cc09a             	sdiv	x16, x1, x0
+;;   38:	 0186009b             	msub	x1, x16, x0, x1
+;;   3c:	 e00301aa             	mov	x0, x1
+;;   40:	 ff230091             	add	sp, sp, #8
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
+;;   54:	 1fc10000             	udf	#0xc11f
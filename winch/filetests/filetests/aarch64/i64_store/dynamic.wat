@@ -0,0 +1,38 @@
+;;! target = "aarch64"
+;;! static_memory_bound = 0
+(module
+  (memory 1)
+  (func (param i32 i64)
+    (local.get 0)
+    (local.get 1)
+    (i64.store offset=8))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804301b8             	stur	w0, [x28, #0x14]
+;;   1c:	 818300f8             	stur	x1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340f8             	ldur	x0, [x28, #8]
+;;   28:	 814341b8             	ldur	w1, [x28, #0x14]
+;;   2c:	 e103012a             	mov	w1, w1
+;;   30:	 228145f8             	ldur	x2, [x9, #0x58]
+;;   34:	 e30301aa             	mov	x3, x1
+;;   38:	 634000b1             	adds	x3, x3, #0x10
+;;   3c:	 82010054             	b.hs	#0x6c
+;;   40:	 7f0002eb             	cmp	x3, x2
+;;   44:	 68010054             	b.hi	#0x70
+;;   48:	 220145f8             	ldur	x2, [x9, #0x50]
+;;   4c:	 4260218b             	add	x2, x2, x1, uxtx
+;;   50:	 42200091             	add	x2, x2, #8
+;;   54:	 400000f8             	stur	x0, [x2]
+;;   58:	 ff630091             	add	sp, sp, #0x18
+;;   5c:	 fc030091             	mov	x28, sp
+;;   60:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   64:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   68:	 c0035fd6             	ret	
+;;   6c:	 1fc10000             	udf	#0xc11f
+;;   70:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,35 @@
+;;! target = "aarch64"
+
+(module
+  (func (param f64) (result i64)
+    (local.get 0)
+    (i64.trunc_f64_u))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808300fc             	stur	d0, [x28, #8]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 808340fc             	ldur	d0, [x28, #8]
+;;   24:	 0020601e             	fcmp	d0, d0
+;;   28:	 e6010054             	b.vs	#0x64
+;;   2c:	 10fef7d2             	mov	x16, #-0x4010000000000000
+;;   30:	 1f02679e             	fmov	d31, x16
+;;   34:	 00207f1e             	fcmp	d0, d31
+;;   38:	 8d010054             	b.le	#0x68
+;;   3c:	 107ee8d2             	mov	x16, #0x43f0000000000000
+;;   40:	 1f02679e             	fmov	d31, x16
+;;   44:	 00207f1e             	fcmp	d0, d31
+;;   48:	 2a010054             	b.ge	#0x6c
+;;   4c:	 0000799e             	fcvtzu	x0, d0
+;;   50:	 ff430091             	add	sp, sp, #0x10
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   5c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   60:	 c0035fd6             	ret	
+;;   64:	 1fc10000             	udf	#0xc11f
+;;   68:	 1fc10000             	udf	#0xc11f
+;;   6c:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,59 @@
+;;! target = "aarch64"
+
+(module
+  (func $dummy)
+  (func (export "as-if-condition") (param i32) (result i32)
+    (if (result i32)
+      (if (result i32) (local.get 0)
+        (then (i32.const 1)) (else (i32.const 0))
+      )
+      (then (call $dummy) (i32.const 2))
+      (else (call $dummy) (i32.const 3))
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 1f00006a             	tst	w0, w0
+;;   28:	 80000054             	b.eq	#0x38
+;;   2c:	 300080d2             	mov	x16, #1
+;;   30:	 e003102a             	mov	w0, w16
+;;   34:	 03000014             	b	#0x40
+;;   38:	 100080d2             	mov	x16, #0
+;;   3c:	 e003102a             	mov	w0, w16
+;;   40:	 1f00006a             	tst	w0, w0
+;;   44:	 c0000054             	b.eq	#0x5c
+;;   48:	 00000094             	bl	#0x48
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 500080d2             	mov	x16, #2
+;;   54:	 e003102a             	mov	w0, w16
+;;   58:	 05000014             	b	#0x6c
+;;   5c:	 00000094             	bl	#0x5c
+;;   60:	 fc030091             	mov	x28, sp
+;;   64:	 700080d2             	mov	x16, #3
+;;   68:	 e003102a             	mov	w0, w16
+;;   6c:	 ff430091             	add	sp, sp, #0x10
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   78:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   7c:	 c0035fd6             	ret	
@@ -0,0 +1,99 @@
+;;! target = "aarch64"
+(module
+  (func $dummy)
+  (func (export "nested") (param i32 i32) (result i32)
+    (if (result i32) (local.get 0)
+      (then
+        (if (local.get 1) (then (call $dummy) (nop)))
+        (if (local.get 1) (then) (else (call $dummy) (nop)))
+        (if (result i32) (local.get 1)
+          (then (call $dummy) (i32.const 9))
+          (else (call $dummy) (i32.const 10))
+        )
+      )
+      (else
+        (if (local.get 1) (then (call $dummy) (nop)))
+        (if (local.get 1) (then) (else (call $dummy) (nop)))
+        (if (result i32) (local.get 1)
+          (then (call $dummy) (i32.const 10))
+          (else (call $dummy) (i32.const 11))
+        )
+      )
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   28:	 1f00006a             	tst	w0, w0
+;;   2c:	 00030054             	b.eq	#0x8c
+;;   30:	 808340b8             	ldur	w0, [x28, #8]
+;;   34:	 1f00006a             	tst	w0, w0
+;;   38:	 60000054             	b.eq	#0x44
+;;   3c:	 00000094             	bl	#0x3c
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 808340b8             	ldur	w0, [x28, #8]
+;;   48:	 1f00006a             	tst	w0, w0
+;;   4c:	 61000054             	b.ne	#0x58
+;;   50:	 00000094             	bl	#0x50
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 808340b8             	ldur	w0, [x28, #8]
+;;   5c:	 1f00006a             	tst	w0, w0
+;;   60:	 c0000054             	b.eq	#0x78
+;;   64:	 00000094             	bl	#0x64
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 300180d2             	mov	x16, #9
+;;   70:	 e003102a             	mov	w0, w16
+;;   74:	 1c000014             	b	#0xe4
+;;   78:	 00000094             	bl	#0x78
+;;   7c:	 fc030091             	mov	x28, sp
+;;   80:	 500180d2             	mov	x16, #0xa
+;;   84:	 e003102a             	mov	w0, w16
+;;   88:	 17000014             	b	#0xe4
+;;   8c:	 808340b8             	ldur	w0, [x28, #8]
+;;   90:	 1f00006a             	tst	w0, w0
+;;   94:	 60000054             	b.eq	#0xa0
+;;   98:	 00000094             	bl	#0x98
+;;   9c:	 fc030091             	mov	x28, sp
+;;   a0:	 808340b8             	ldur	w0, [x28, #8]
+;;   a4:	 1f00006a             	tst	w0, w0
+;;   a8:	 61000054             	b.ne	#0xb4
+;;   ac:	 00000094             	bl	#0xac
+;;   b0:	 fc030091             	mov	x28, sp
+;;   b4:	 808340b8             	ldur	w0, [x28, #8]
+;;   b8:	 1f00006a             	tst	w0, w0
+;;   bc:	 c0000054             	b.eq	#0xd4
+;;   c0:	 00000094             	bl	#0xc0
+;;   c4:	 fc030091             	mov	x28, sp
+;;   c8:	 500180d2             	mov	x16, #0xa
+;;   cc:	 e003102a             	mov	w0, w16
+;;   d0:	 05000014             	b	#0xe4
+;;   d4:	 00000094             	bl	#0xd4
+;;   d8:	 fc030091             	mov	x28, sp
+;;   dc:	 700180d2             	mov	x16, #0xb
+;;   e0:	 e003102a             	mov	w0, w16
+;;   e4:	 ff430091             	add	sp, sp, #0x10
+;;   e8:	 fc030091             	mov	x28, sp
+;;   ec:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   f0:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   f4:	 c0035fd6             	ret	
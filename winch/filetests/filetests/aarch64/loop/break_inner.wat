@@ -0,0 +1,86 @@
+;;! target = "aarch64"
+(module
+  (func (export "break-inner") (result i32)
+    (local i32)
+    (local.set 0 (i32.const 0))
+    (local.set 0 (i32.add (local.get 0) (block (result i32) (loop (result i32) (block (result i32) (br 2 (i32.const 0x1)))))))
+    (local.set 0 (i32.add (local.get 0) (block (result i32) (loop (result i32) (loop (result i32) (br 2 (i32.const 0x2)))))))
+    (local.set 0 (i32.add (local.get 0) (block (result i32) (loop (result i32) (block (result i32) (loop (result i32) (br 1 (i32.const 0x4))))))))
+    (local.set 0 (i32.add (local.get 0) (block (result i32) (loop (result i32) (i32.ctz (br 1 (i32.const 0x8)))))))
+    (local.set 0 (i32.add (local.get 0) (block (result i32) (loop (result i32) (i32.ctz (loop (result i32) (br 2 (i32.const 0x10))))))))
+    (local.get 0)
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 908300f8             	stur	x16, [x28, #8]
+;;   20:	 894300f8             	stur	x9, [x28, #4]
+;;   24:	 100080d2             	mov	x16, #0
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   30:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   34:	 ff2300d1             	sub	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 900300f8             	stur	x16, [x28]
+;;   40:	 300080d2             	mov	x16, #1
+;;   44:	 e003102a             	mov	w0, w16
+;;   48:	 810340b8             	ldur	w1, [x28]
+;;   4c:	 ff230091             	add	sp, sp, #8
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 2160200b             	add	w1, w1, w0, uxtx
+;;   58:	 81c300b8             	stur	w1, [x28, #0xc]
+;;   5c:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   60:	 ff2300d1             	sub	sp, sp, #8
+;;   64:	 fc030091             	mov	x28, sp
+;;   68:	 900300f8             	stur	x16, [x28]
+;;   6c:	 500080d2             	mov	x16, #2
+;;   70:	 e003102a             	mov	w0, w16
+;;   74:	 810340b8             	ldur	w1, [x28]
+;;   78:	 ff230091             	add	sp, sp, #8
+;;   7c:	 fc030091             	mov	x28, sp
+;;   80:	 2160200b             	add	w1, w1, w0, uxtx
+;;   84:	 81c300b8             	stur	w1, [x28, #0xc]
+;;   88:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   8c:	 ff2300d1             	sub	sp, sp, #8
+;;   90:	 fc030091             	mov	x28, sp
+;;   94:	 900300f8             	stur	x16, [x28]
+;;   98:	 900080d2             	mov	x16, #4
+;;   9c:	 e003102a             	mov	w0, w16
+;;   a0:	 810340b8             	ldur	w1, [x28]
+;;   a4:	 ff230091             	add	sp, sp, #8
+;;   a8:	 fc030091             	mov	x28, sp
+;;   ac:	 2160200b             	add	w1, w1, w0, uxtx
+;;   b0:	 81c300b8             	stur	w1, [x28, #0xc]
+;;   b4:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   b8:	 ff2300d1             	sub	sp, sp, #8
+;;   bc:	 fc030091             	mov	x28, sp
+;;   c0:	 900300f8             	stur	x16, [x28]
+;;   c4:	 100180d2             	mov	x16, #8
+;;   c8:	 e003102a             	mov	w0, w16
+;;   cc:	 810340b8             	ldur	w1, [x28]
+;;   d0:	 ff230091             	add	sp, sp, #8
+;;   d4:	 fc030091             	mov	x28, sp
+;;   d8:	 2160200b             	add	w1, w1, w0, uxtx
+;;   dc:	 81c300b8             	stur	w1, [x28, #0xc]
+;;   e0:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   e4:	 ff2300d1             	sub	sp, sp, #8
+;;   e8:	 fc030091             	mov	x28, sp
+;;   ec:	 900300f8             	stur	x16, [x28]
+;;   f0:	 100280d2             	mov	x16, #0x10
+;;   f4:	 e003102a             	mov	w0, w16
+;;   f8:	 810340b8             	ldur	w1, [x28]
+;;   fc:	 ff230091             	add	sp, sp, #8
+;;  100:	 fc030091             	mov	x28, sp
+;;  104:	 2160200b             	add	w1, w1, w0, uxtx
+;;  108:	 81c300b8             	stur	w1, [x28, #0xc]
+;;  10c:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;  110:	 ff430091             	add	sp, sp, #0x10
+;;  114:	 fc030091             	mov	x28, sp
+;;  118:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;  11c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  120:	 c0035fd6             	ret	
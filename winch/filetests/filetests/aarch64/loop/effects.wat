@@ -0,0 +1,49 @@
+;;! target = "aarch64"
+(module
+  (func $fx (export "effects") (result i32)
+    (local i32)
+    (block
+      (loop
+        (local.set 0 (i32.const 1))
+        (local.set 0 (i32.mul (local.get 0) (i32.const 3)))
+        (local.set 0 (i32.sub (local.get 0) (i32.const 5)))
+        (local.set 0 (i32.mul (local.get 0) (i32.const 7)))
+        (br 1)
+        (local.set 0 (i32.mul (local.get 0) (i32.const 100)))
+      )
+    )
+    (i32.eq (local.get 0) (i32.const -14))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 908300f8             	stur	x16, [x28, #8]
+;;   20:	 894300f8             	stur	x9, [x28, #4]
+;;   24:	 300080d2             	mov	x16, #1
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   30:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   34:	 700080d2             	mov	x16, #3
+;;   38:	 007c101b             	mul	w0, w0, w16
+;;   3c:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   40:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   44:	 00140051             	sub	w0, w0, #5
+;;   48:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   4c:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   50:	 f00080d2             	mov	x16, #7
+;;   54:	 007c101b             	mul	w0, w0, w16
+;;   58:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   5c:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   60:	 b0018012             	mov	w16, #-0xe
+;;   64:	 1f00106b             	cmp	w0, w16
+;;   68:	 e0179f9a             	cset	x0, eq
+;;   6c:	 ff430091             	add	sp, sp, #0x10
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   78:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   7c:	 c0035fd6             	ret	
@@ -0,0 +1,53 @@
+;;! target = "aarch64"
+(module
+  (func (export "for-") (param i64) (result i64)
+    (local i64 i64)
+    (local.set 1 (i64.const 1))
+    (local.set 2 (i64.const 2))
+    (block
+      (loop
+        (br_if 1 (i64.gt_u (local.get 2) (local.get 0)))
+        (local.set 1 (i64.mul (local.get 1) (local.get 2)))
+        (local.set 2 (i64.add (local.get 2) (i64.const 1)))
+        (br 0)
+      )
+    )
+    (local.get 1)
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff8300d1             	sub	sp, sp, #0x20
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808301f8             	stur	x0, [x28, #0x18]
+;;   1c:	 100080d2             	mov	x16, #0
+;;   20:	 900301f8             	stur	x16, [x28, #0x10]
+;;   24:	 890300f8             	stur	x9, [x28]
+;;   28:	 300080d2             	mov	x16, #1
+;;   2c:	 e00310aa             	mov	x0, x16
+;;   30:	 800301f8             	stur	x0, [x28, #0x10]
+;;   34:	 500080d2             	mov	x16, #2
+;;   38:	 e00310aa             	mov	x0, x16
+;;   3c:	 808300f8             	stur	x0, [x28, #8]
+;;   40:	 808341f8             	ldur	x0, [x28, #0x18]
+;;   44:	 818340f8             	ldur	x1, [x28, #8]
+;;   48:	 3f0000eb             	cmp	x1, x0
+;;   4c:	 e1979f9a             	cset	x1, hi
+;;   50:	 3f00016a             	tst	w1, w1
+;;   54:	 21010054             	b.ne	#0x78
+;;   58:	 808340f8             	ldur	x0, [x28, #8]
+;;   5c:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   60:	 217c009b             	mul	x1, x1, x0
+;;   64:	 810301f8             	stur	x1, [x28, #0x10]
+;;   68:	 808340f8             	ldur	x0, [x28, #8]
+;;   6c:	 00040091             	add	x0, x0, #1
+;;   70:	 808300f8             	stur	x0, [x28, #8]
+;;   74:	 f3ffff17             	b	#0x40
+;;   78:	 800341f8             	ldur	x0, [x28, #0x10]
+;;   7c:	 ff830091             	add	sp, sp, #0x20
+;;   80:	 fc030091             	mov	x28, sp
+;;   84:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   88:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   8c:	 c0035fd6             	ret	
@@ -0,0 +1,46 @@
+;;! target = "aarch64"
+(module
+  (func (export "while-") (param i64) (result i64)
+    (local i64)
+    (local.set 1 (i64.const 1))
+    (block
+      (loop
+        (br_if 1 (i64.eqz (local.get 0)))
+        (local.set 1 (i64.mul (local.get 0) (local.get 1)))
+        (local.set 0 (i64.sub (local.get 0) (i64.const 1)))
+        (br 0)
+      )
+    )
+    (local.get 1)
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 800301f8             	stur	x0, [x28, #0x10]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 300080d2             	mov	x16, #1
+;;   24:	 e00310aa             	mov	x0, x16
+;;   28:	 808300f8             	stur	x0, [x28, #8]
+;;   2c:	 800341f8             	ldur	x0, [x28, #0x10]
+;;   30:	 1f0000f1             	cmp	x0, #0
+;;   34:	 e0179f9a             	cset	x0, eq
+;;   38:	 1f00006a             	tst	w0, w0
+;;   3c:	 21010054             	b.ne	#0x60
+;;   40:	 808340f8             	ldur	x0, [x28, #8]
+;;   44:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   48:	 217c009b             	mul	x1, x1, x0
+;;   4c:	 818300f8             	stur	x1, [x28, #8]
+;;   50:	 800341f8             	ldur	x0, [x28, #0x10]
+;;   54:	 000400d1             	sub	x0, x0, #1
+;;   58:	 800301f8             	stur	x0, [x28, #0x10]
+;;   5c:	 f4ffff17             	b	#0x2c
+;;   60:	 808340f8             	ldur	x0, [x28, #8]
+;;   64:	 ff630091             	add	sp, sp, #0x18
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   70:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   74:	 c0035fd6             	ret	
@@ -0,0 +1,40 @@
+;;! target = "aarch64"
+
+(module
+  (memory 1)
+  (func (param i32) (result i32)
+    (local.get 0)
+    (memory.grow))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 e003002a             	mov	w0, w0
+;;   28:	 e10309aa             	mov	x1, x9
+;;   2c:	 ff2300d1             	sub	sp, sp, #8
+;;   30:	 fc030091             	mov	x28, sp
+;;   34:	 810300f8             	stur	x1, [x28]
+;;   38:	 ff2300d1             	sub	sp, sp, #8
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 800300f8             	stur	x0, [x28]
+;;   44:	 808340f8             	ldur	x0, [x28, #8]
+;;   48:	 810340f8             	ldur	x1, [x28]
+;;   4c:	 100080d2             	mov	x16, #0
+;;   50:	 e203102a             	mov	w2, w16
+;;   54:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   58:	 100240f8             	ldur	x16, [x16]
+;;   5c:	 00023fd6             	blr	x16
+;;   60:	 fc030091             	mov	x28, sp
+;;   64:	 ff430091             	add	sp, sp, #0x10
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 ff430091             	add	sp, sp, #0x10
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   78:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   7c:	 c0035fd6             	ret	
@@ -0,0 +1,29 @@
+;;! target = "aarch64"
+
+(module
+  (func (param f32 f32 i32) (result f32)
+    (local.get 0)
+    (local.get 1)
+    (local.get 2)
+    (select))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804301bc             	stur	s0, [x28, #0x14]
+;;   1c:	 810301bc             	stur	s1, [x28, #0x10]
+;;   20:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   24:	 894300f8             	stur	x9, [x28, #4]
+;;   28:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   2c:	 800341bc             	ldur	s0, [x28, #0x10]
+;;   30:	 814341bc             	ldur	s1, [x28, #0x14]
+;;   34:	 1f000071             	cmp	w0, #0
+;;   38:	 201c201e             	fcsel	s0, s1, s0, ne
+;;   3c:	 ff630091             	add	sp, sp, #0x18
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   48:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   4c:	 c0035fd6             	ret	
@@ -0,0 +1,30 @@
+;;! target = "aarch64"
+
+(module
+  (func (param i32 i32 i32) (result i32)
+    (local.get 0)
+    (local.get 1)
+    (local.get 2)
+    (select))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804301b8             	stur	w0, [x28, #0x14]
+;;   1c:	 810301b8             	stur	w1, [x28, #0x10]
+;;   20:	 82c300b8             	stur	w2, [x28, #0xc]
+;;   24:	 894300f8             	stur	x9, [x28, #4]
+;;   28:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   2c:	 810341b8             	ldur	w1, [x28, #0x10]
+;;   30:	 824341b8             	ldur	w2, [x28, #0x14]
+;;   34:	 1f000071             	cmp	w0, #0
+;;   38:	 4110819a             	csel	x1, x2, x1, ne
+;;   3c:	 e003012a             	mov	w0, w1
+;;   40:	 ff630091             	add	sp, sp, #0x18
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
@@ -0,0 +1,59 @@
+;;! target = "aarch64"
+
+(module
+  (table 1 funcref)
+  (func (param i32) (result funcref)
+    (local.get 0)
+    (table.get 0))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 894300f8             	stur	x9, [x28, #4]
+;;   20:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   24:	 ff2300d1             	sub	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 900300f8             	stur	x16, [x28]
+;;   30:	 810340b8             	ldur	w1, [x28]
+;;   34:	 ff230091             	add	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 230145b8             	ldur	w3, [x9, #0x50]
+;;   40:	 3f00036b             	cmp	w1, w3
+;;   44:	 22040054             	b.hs	#0xc8
+;;   48:	 228144f8             	ldur	x2, [x9, #0x48]
+;;   4c:	 e303012a             	mov	w3, w1
+;;   50:	 100180d2             	mov	x16, #8
+;;   54:	 637c109b             	mul	x3, x3, x16
+;;   58:	 4260238b             	add	x2, x2, x3, uxtx
+;;   5c:	 400040f8             	ldur	x0, [x2]
+;;   60:	 1f0000ea             	tst	x0, x0
+;;   64:	 61020054             	b.ne	#0xb0
+;;   68:	 e00309aa             	mov	x0, x9
+;;   6c:	 ff2300d1             	sub	sp, sp, #8
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 800300f8             	stur	x0, [x28]
+;;   78:	 ff2300d1             	sub	sp, sp, #8
+;;   7c:	 fc030091             	mov	x28, sp
+;;   80:	 810300f8             	stur	x1, [x28]
+;;   84:	 808340f8             	ldur	x0, [x28, #8]
+;;   88:	 100080d2             	mov	x16, #0
+;;   8c:	 e103102a             	mov	w1, w16
+;;   90:	 820340b8             	ldur	w2, [x28]
+;;   94:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   98:	 108244f8             	ldur	x16, [x16, #0x48]
+;;   9c:	 00023fd6             	blr	x16
+;;   a0:	 fc030091             	mov	x28, sp
+;;   a4:	 ff430091             	add	sp, sp, #0x10
+;;   a8:	 fc030091             	mov	x28, sp
+;;   ac:	 02000014             	b	#0xb4
+;;   b0:	 00f87f92             	and	x0, x0, #0xfffffffffffffffe
+;;   b4:	 ff430091             	add	sp, sp, #0x10
+;;   b8:	 fc030091             	mov	x28, sp
+;;   bc:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   c0:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   c4:	 c0035fd6             	ret	
+;;   c8:	 1fc10000             	udf	#0xc11f
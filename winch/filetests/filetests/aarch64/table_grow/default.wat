@@ -0,0 +1,46 @@
+;;! target = "aarch64"
+
+(module
+  (table 1 funcref)
+  (func (param funcref i32) (result i32)
+    (local.get 0)
+    (local.get 1)
+    (table.grow 0))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 800301f8             	stur	x0, [x28, #0x10]
+;;   1c:	 81c300b8             	stur	w1, [x28, #0xc]
+;;   20:	 894300f8             	stur	x9, [x28, #4]
+;;   24:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   28:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   2c:	 e20309aa             	mov	x2, x9
+;;   30:	 ff2300d1             	sub	sp, sp, #8
+;;   34:	 fc030091             	mov	x28, sp
+;;   38:	 820300f8             	stur	x2, [x28]
+;;   3c:	 ff2300d1             	sub	sp, sp, #8
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 800300f8             	stur	x0, [x28]
+;;   48:	 ff2300d1             	sub	sp, sp, #8
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 810300f8             	stur	x1, [x28]
+;;   54:	 800341f8             	ldur	x0, [x28, #0x10]
+;;   58:	 100080d2             	mov	x16, #0
+;;   5c:	 e103102a             	mov	w1, w16
+;;   60:	 828340b8             	ldur	w2, [x28, #8]
+;;   64:	 830340f8             	ldur	x3, [x28]
+;;   68:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   6c:	 100245f8             	ldur	x16, [x16, #0x50]
+;;   70:	 00023fd6             	blr	x16
+;;   74:	 fc030091             	mov	x28, sp
+;;   78:	 ff630091             	add	sp, sp, #0x18
+;;   7c:	 fc030091             	mov	x28, sp
+;;   80:	 ff630091             	add	sp, sp, #0x18
+;;   84:	 fc030091             	mov	x28, sp
+;;   88:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   8c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   90:	 c0035fd6             	ret	
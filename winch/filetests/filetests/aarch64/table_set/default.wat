@@ -0,0 +1,36 @@
+;;! target = "aarch64"
+
+(module
+  (table 1 funcref)
+  (func (param i32 funcref)
+    (local.get 0)
+    (local.get 1)
+    (table.set 0))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804301b8             	stur	w0, [x28, #0x14]
+;;   1c:	 818300f8             	stur	x1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340f8             	ldur	x0, [x28, #8]
+;;   28:	 814341b8             	ldur	w1, [x28, #0x14]
+;;   2c:	 230145b8             	ldur	w3, [x9, #0x50]
+;;   30:	 3f00036b             	cmp	w1, w3
+;;   34:	 a2010054             	b.hs	#0x68
+;;   38:	 228144f8             	ldur	x2, [x9, #0x48]
+;;   3c:	 e303012a             	mov	w3, w1
+;;   40:	 100180d2             	mov	x16, #8
+;;   44:	 637c109b             	mul	x3, x3, x16
+;;   48:	 4260238b             	add	x2, x2, x3, uxtx
+;;   4c:	 000040b2             	orr	x0, x0, #1
+;;   50:	 400000f8             	stur	x0, [x2]
+;;   54:	 ff630091             	add	sp, sp, #0x18
+;;   58:	 fc030091             	mov	x28, sp
+;;   5c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   60:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   64:	 c0035fd6             	ret	
+;;   68:	 1fc10000             	udf	#0xc11f
@@ -0,0 +1,20 @@
+;;! target = "aarch64"
+
+(module
+  (func (export "as-br-value") (result i32)
+    (block (result i32) (br 0 (unreachable)))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 1fc10000             	udf	#0xc11f
+;;   20:	 ff230091             	add	sp, sp, #8
+;;   24:	 fc030091             	mov	x28, sp
+;;   28:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   2c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   30:	 c0035fd6             	ret	
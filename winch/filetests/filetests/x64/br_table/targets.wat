@@ -0,0 +1,33 @@
+;;! target = "x86_64"
+
+(module
+  (func (param i32) (result i32)
+    (block
+      (block
+        (br_table 0 1 (local.get 0)))
+      (return (i32.const 10)))
+    (i32.const 20))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   15:	 b901000000           	mov	ecx, 1
+;;   1a:	 39c8                 	cmp	eax, ecx
+;;   1c:	 0f43c1               	cmovae	eax, ecx
+;;   1f:	 4c8d1d0a000000       	lea	r11, [rip + 0xa]
+;;   26:	 49630c83             	movsxd	rcx, dword ptr [r11 + rax*4]
+;;   2a:	 4901cb               	add	r11, rcx
+;;   2d:	 41ffe3               	jmp	r11
+;;   30:	 0800                 	or	byte ptr [rax], al
+;;   32:	 0000                 	add	byte ptr [rax], al
+;;   34:	 1200                 	adc	al, byte ptr [rax]
+;;   36:	 0000                 	add	byte ptr [rax], al
+;;   38:	 b80a000000           	mov	eax, 0xa
+;;   3d:	 e905000000           	jmp	0x47
+;;   42:	 b814000000           	mov	eax, 0x14
+;;   47:	 4883c410             	add	rsp, 0x10
+;;   4b:	 5d                   	pop	rbp
+;;   4c:	 c3                   	ret	
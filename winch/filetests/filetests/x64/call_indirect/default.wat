@@ -0,0 +1,63 @@
+;;! target = "x86_64"
+
+(module
+  (type $t (func (param i32) (result i32)))
+  (table 1 funcref)
+  (func (param i32) (result i32)
+    (i32.const 5)
+    (local.get 0)
+    (call_indirect (type $t)))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 448b5c240c           	mov	r11d, dword ptr [rsp + 0xc]
+;;   16:	 4153                 	push	r11
+;;   18:	 59                   	pop	rcx
+;;   19:	 418b5e50             	mov	ebx, dword ptr [r14 + 0x50]
+;;   1d:	 39d9                 	cmp	ecx, ebx
+;;   1f:	 0f8386000000         	jae	0xab
+;;   25:	 498b5648             	mov	rdx, qword ptr [r14 + 0x48]
+;;   29:	 8bd9                 	mov	ebx, ecx
+;;   2b:	 486bdb08             	imul	rbx, rbx, 8
+;;   2f:	 4801da               	add	rdx, rbx
+;;   32:	 488b02               	mov	rax, qword ptr [rdx]
+;;   35:	 4885c0               	test	rax, rax
+;;   38:	 0f8526000000         	jne	0x64
+;;   3e:	 4c89f0               	mov	rax, r14
+;;   41:	 50                   	push	rax
+;;   42:	 51                   	push	rcx
+;;   43:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   48:	 be00000000           	mov	esi, 0
+;;   4d:	 8b1424               	mov	edx, dword ptr [rsp]
+;;   50:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   54:	 4d8b5b48             	mov	r11, qword ptr [r11 + 0x48]
+;;   58:	 41ffd3               	call	r11
+;;   5b:	 4883c410             	add	rsp, 0x10
+;;   5f:	 e904000000           	jmp	0x68
+;;   64:	 4883e0fe             	and	rax, 0xfffffffffffffffe
+;;   68:	 4883f800             	cmp	rax, 0
+;;   6c:	 0f843b000000         	je	0xad
+;;   72:	 498b4e40             	mov	rcx, qword ptr [r14 + 0x40]
+;;   76:	 8b09                 	mov	ecx, dword ptr [rcx]
+;;   78:	 8b5018               	mov	edx, dword ptr [rax + 0x18]
+;;   7b:	 39ca                 	cmp	edx, ecx
+;;   7d:	 0f852c000000         	jne	0xaf
+;;   83:	 488b4820             	mov	rcx, qword ptr [rax + 0x20]
+;;   87:	 4c89f2               	mov	rdx, r14
+;;   8a:	 51                   	push	rcx
+;;   8b:	 52                   	push	rdx
+;;   8c:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   91:	 488b3424             	mov	rsi, qword ptr [rsp]
+;;   95:	 ba05000000           	mov	edx, 5
+;;   9a:	 4c8b5810             	mov	r11, qword ptr [rax + 0x10]
+;;   9e:	 41ffd3               	call	r11
+;;   a1:	 4883c410             	add	rsp, 0x10
+;;   a5:	 4883c410             	add	rsp, 0x10
+;;   a9:	 5d                   	pop	rbp
+;;   aa:	 c3                   	ret	
+;;   ab:	 0f0b                 	ud2	
+;;   ad:	 0f0b                 	ud2	
+;;   af:	 0f0b                 	ud2	
@@ -0,0 +1,18 @@
+;;! target = "x86_64"
+
+(module
+  (func (param i32) (result f32)
+    (local.get 0)
+    (f32.convert_i32_u))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   15:	 8bc0                 	mov	eax, eax
+;;   17:	 f3480f2ac0           	cvtsi2ss	xmm0, rax
+;;   1c:	 4883c410             	add	rsp, 0x10
+;;   20:	 5d                   	pop	rbp
+;;   21:	 c3                   	ret	
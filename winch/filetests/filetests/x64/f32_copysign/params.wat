@@ -0,0 +1,27 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32 f32) (result f32)
+    (local.get 0)
+    (local.get 1)
+    (f32.copysign))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 f30f114c2408         	movss	dword ptr [rsp + 8], xmm1
+;;   14:	 4c893424             	mov	qword ptr [rsp], r14
+;;   18:	 f30f10442408         	movss	xmm0, dword ptr [rsp + 8]
+;;   1e:	 f30f104c240c         	movss	xmm1, dword ptr [rsp + 0xc]
+;;   24:	 41bbffffff7f         	mov	r11d, 0x7fffffff
+;;   2a:	 66450f6efb           	movd	xmm15, r11d
+;;   2f:	 410f54cf             	andps	xmm1, xmm15
+;;   33:	 41bb00000080         	mov	r11d, 0x80000000
+;;   39:	 66450f6efb           	movd	xmm15, r11d
+;;   3e:	 440f54f8             	andps	xmm15, xmm0
+;;   42:	 410f56cf             	orps	xmm1, xmm15
+;;   46:	 0f28c1               	movaps	xmm0, xmm1
+;;   49:	 4883c410             	add	rsp, 0x10
+;;   4d:	 5d                   	pop	rbp
+;;   4e:	 c3                   	ret	
@@ -0,0 +1,18 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f64) (result f32)
+    (local.get 0)
+    (f32.demote_f64))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f20f11442408         	movsd	qword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   18:	 f20f5ac8             	cvtsd2ss	xmm1, xmm0
+;;   1c:	 0f28c1               	movaps	xmm0, xmm1
+;;   1f:	 4883c410             	add	rsp, 0x10
+;;   23:	 5d                   	pop	rbp
+;;   24:	 c3                   	ret	
@@ -0,0 +1,22 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (param i32) (result f32)
+    (local.get 0)
+    (f32.load offset=8))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   15:	 8bc0                 	mov	eax, eax
+;;   17:	 498b4e50             	mov	rcx, qword ptr [r14 + 0x50]
+;;   1b:	 4801c1               	add	rcx, rax
+;;   1e:	 4883c108             	add	rcx, 8
+;;   22:	 f30f1001             	movss	xmm0, dword ptr [rcx]
+;;   26:	 4883c410             	add	rsp, 0x10
+;;   2a:	 5d                   	pop	rbp
+;;   2b:	 c3                   	ret	
@@ -0,0 +1,25 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (param i32 f32)
+    (local.get 0)
+    (local.get 1)
+    (f32.store offset=8))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 f30f11442408         	movss	dword ptr [rsp + 8], xmm0
+;;   12:	 4c893424             	mov	qword ptr [rsp], r14
+;;   16:	 f30f10442408         	movss	xmm0, dword ptr [rsp + 8]
+;;   1c:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   20:	 8bc0                 	mov	eax, eax
+;;   22:	 498b4e50             	mov	rcx, qword ptr [r14 + 0x50]
+;;   26:	 4801c1               	add	rcx, rax
+;;   29:	 4883c108             	add	rcx, 8
+;;   2d:	 f30f1101             	movss	dword ptr [rcx], xmm0
+;;   31:	 4883c410             	add	rsp, 0x10
+;;   35:	 5d                   	pop	rbp
+;;   36:	 c3                   	ret	
@@ -0,0 +1,29 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f64 f64) (result f64)
+    (local.get 0)
+    (local.get 1)
+    (f64.copysign))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 f20f11442410         	movsd	qword ptr [rsp + 0x10], xmm0
+;;    e:	 f20f114c2408         	movsd	qword ptr [rsp + 8], xmm1
+;;   14:	 4c893424             	mov	qword ptr [rsp], r14
+;;   18:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   1e:	 f20f104c2410         	movsd	xmm1, qword ptr [rsp + 0x10]
+;;   24:	 49bbffffffffffffff7f 	
+;; 				movabs	r11, 0x7fffffffffffffff
+;;   2e:	 664d0f6efb           	movq	xmm15, r11
+;;   33:	 66410f54cf           	andpd	xmm1, xmm15
+;;   38:	 49bb0000000000000080 	
+;; 				movabs	r11, 0x8000000000000000
+;;   42:	 664d0f6efb           	movq	xmm15, r11
+;;   47:	 66440f54f8           	andpd	xmm15, xmm0
+;;   4c:	 66410f56cf           	orpd	xmm1, xmm15
+;;   51:	 660f28c1             	movapd	xmm0, xmm1
+;;   55:	 4883c418             	add	rsp, 0x18
+;;   59:	 5d                   	pop	rbp
+;;   5a:	 c3                   	ret	
@@ -0,0 +1,21 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (param i32) (result f64)
+    (local.get 0)
+    (f64.load))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   15:	 8bc0                 	mov	eax, eax
+;;   17:	 498b4e50             	mov	rcx, qword ptr [r14 + 0x50]
+;;   1b:	 4801c1               	add	rcx, rax
+;;   1e:	 f20f1001             	movsd	xmm0, qword ptr [rcx]
+;;   22:	 4883c410             	add	rsp, 0x10
+;;   26:	 5d                   	pop	rbp
+;;   27:	 c3                   	ret	
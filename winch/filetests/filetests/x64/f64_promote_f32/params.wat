@@ -0,0 +1,18 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result f64)
+    (local.get 0)
+    (f64.promote_f32))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 f30f5ac8             	cvtss2sd	xmm1, xmm0
+;;   1d:	 660f28c1             	movapd	xmm0, xmm1
+;;   21:	 4883c410             	add	rsp, 0x10
+;;   25:	 5d                   	pop	rbp
+;;   26:	 c3                   	ret	
@@ -0,0 +1,25 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (param i32 f64)
+    (local.get 0)
+    (local.get 1)
+    (f64.store offset=8))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 897c2414             	mov	dword ptr [rsp + 0x14], edi
+;;    c:	 f20f11442408         	movsd	qword ptr [rsp + 8], xmm0
+;;   12:	 4c893424             	mov	qword ptr [rsp], r14
+;;   16:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   1c:	 8b442414             	mov	eax, dword ptr [rsp + 0x14]
+;;   20:	 8bc0                 	mov	eax, eax
+;;   22:	 498b4e50             	mov	rcx, qword ptr [r14 + 0x50]
+;;   26:	 4801c1               	add	rcx, rax
+;;   29:	 4883c108             	add	rcx, 8
+;;   2d:	 f20f1101             	movsd	qword ptr [rcx], xmm0
+;;   31:	 4883c418             	add	rsp, 0x18
+;;   35:	 5d                   	pop	rbp
+;;   36:	 c3                   	ret	
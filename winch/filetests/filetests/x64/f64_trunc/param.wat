@@ -0,0 +1,19 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f64) (result f64)
+    (local.get 0)
+    (f64.trunc))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f20f11442408         	movsd	qword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   18:	 49bb0000000000000000 	
+;; 				movabs	r11, 0
+;;   22:	 41ffd3               	call	r11
+;;   25:	 4883c410             	add	rsp, 0x10
+;;   29:	 5d                   	pop	rbp
+;;   2a:	 c3                   	ret	
@@ -0,0 +1,27 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (param i32) (result i32)
+    (local.get 0)
+    (i32.load offset=0x80000000))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   15:	 8bc0                 	mov	eax, eax
+;;   17:	 4881f8fcffff7f       	cmp	rax, 0x7ffffffc
+;;   1e:	 0f871c000000         	ja	0x40
+;;   24:	 498b4e50             	mov	rcx, qword ptr [r14 + 0x50]
+;;   28:	 4801c1               	add	rcx, rax
+;;   2b:	 49bb0000008000000000 	
+;; 				movabs	r11, 0x80000000
+;;   35:	 4c01d9               	add	rcx, r11
+;;   38:	 8b01                 	mov	eax, dword ptr [rcx]
+;;   3a:	 4883c410             	add	rsp, 0x10
+;;   3e:	 5d                   	pop	rbp
+;;   3f:	 c3                   	ret	
+;;   40:	 0f0b                 	ud2	
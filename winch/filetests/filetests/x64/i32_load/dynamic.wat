@@ -0,0 +1,29 @@
+;;! target = "x86_64"
+;;! static_memory_bound = 0
+(module
+  (memory 1)
+  (func (param i32) (result i32)
+    (local.get 0)
+    (i32.load))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   15:	 8bc0                 	mov	eax, eax
+;;   17:	 498b4e58             	mov	rcx, qword ptr [r14 + 0x58]
+;;   1b:	 4889c2               	mov	rdx, rax
+;;   1e:	 4883c204             	add	rdx, 4
+;;   22:	 0f8218000000         	jb	0x40
+;;   28:	 4839ca               	cmp	rdx, rcx
+;;   2b:	 0f8711000000         	ja	0x42
+;;   31:	 498b4e50             	mov	rcx, qword ptr [r14 + 0x50]
+;;   35:	 4801c1               	add	rcx, rax
+;;   38:	 8b01                 	mov	eax, dword ptr [rcx]
+;;   3a:	 4883c410             	add	rsp, 0x10
+;;   3e:	 5d                   	pop	rbp
+;;   3f:	 c3                   	ret	
+;;   40:	 0f0b                 	ud2	
+;;   42:	 0f0b                 	ud2	
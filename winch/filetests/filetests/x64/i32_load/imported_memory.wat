@@ -0,0 +1,22 @@
+;;! target = "x86_64"
+
+(module
+  (import "env" "memory" (memory 1))
+  (func (param i32) (result i32)
+    (local.get 0)
+    (i32.load))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   15:	 8bc0                 	mov	eax, eax
+;;   17:	 498b4e48             	mov	rcx, qword ptr [r14 + 0x48]
+;;   1b:	 488b09               	mov	rcx, qword ptr [rcx]
+;;   1e:	 4801c1               	add	rcx, rax
+;;   21:	 8b01                 	mov	eax, dword ptr [rcx]
+;;   23:	 4883c410             	add	rsp, 0x10
+;;   27:	 5d                   	pop	rbp
+;;   28:	 c3                   	ret	
@@ -0,0 +1,24 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (param i32 i32)
+    (local.get 0)
+    (local.get 1)
+    (i32.store16))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 89742408             	mov	dword ptr [rsp + 8], esi
+;;   10:	 4c893424             	mov	qword ptr [rsp], r14
+;;   14:	 8b442408             	mov	eax, dword ptr [rsp + 8]
+;;   18:	 8b4c240c             	mov	ecx, dword ptr [rsp + 0xc]
+;;   1c:	 8bc9                 	mov	ecx, ecx
+;;   1e:	 498b5650             	mov	rdx, qword ptr [r14 + 0x50]
+;;   22:	 4801ca               	add	rdx, rcx
+;;   25:	 668902               	mov	word ptr [rdx], ax
+;;   28:	 4883c410             	add	rsp, 0x10
+;;   2c:	 5d                   	pop	rbp
+;;   2d:	 c3                   	ret	
@@ -0,0 +1,31 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result i32)
+    (local.get 0)
+    (i32.trunc_f32_s))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 f30f2cc0             	cvttss2si	eax, xmm0
+;;   1d:	 83f801               	cmp	eax, 1
+;;   20:	 0f812d000000         	jno	0x53
+;;   26:	 0f2ec0               	ucomiss	xmm0, xmm0
+;;   29:	 0f8a2a000000         	jp	0x59
+;;   2f:	 41bb000000cf         	mov	r11d, 0xcf000000
+;;   35:	 66450f6efb           	movd	xmm15, r11d
+;;   3a:	 410f2ec7             	ucomiss	xmm0, xmm15
+;;   3e:	 0f8217000000         	jb	0x5b
+;;   44:	 66450f57ff           	xorpd	xmm15, xmm15
+;;   49:	 440f2ef8             	ucomiss	xmm15, xmm0
+;;   4d:	 0f820a000000         	jb	0x5d
+;;   53:	 4883c410             	add	rsp, 0x10
+;;   57:	 5d                   	pop	rbp
+;;   58:	 c3                   	ret	
+;;   59:	 0f0b                 	ud2	
+;;   5b:	 0f0b                 	ud2	
+;;   5d:	 0f0b                 	ud2	
@@ -0,0 +1,33 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result i32)
+    (local.get 0)
+    (i32.trunc_f32_u))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 41bb0000004f         	mov	r11d, 0x4f000000
+;;   1f:	 66450f6efb           	movd	xmm15, r11d
+;;   24:	 410f2ec7             	ucomiss	xmm0, xmm15
+;;   28:	 0f8315000000         	jae	0x43
+;;   2e:	 0f8a30000000         	jp	0x64
+;;   34:	 f30f2cc0             	cvttss2si	eax, xmm0
+;;   38:	 83f800               	cmp	eax, 0
+;;   3b:	 0f8d1d000000         	jge	0x5e
+;;   41:	 0f0b                 	ud2	
+;;   43:	 0f28c8               	movaps	xmm1, xmm0
+;;   46:	 f3410f5ccf           	subss	xmm1, xmm15
+;;   4b:	 f30f2cc1             	cvttss2si	eax, xmm1
+;;   4f:	 83f800               	cmp	eax, 0
+;;   52:	 0f8c0e000000         	jl	0x66
+;;   58:	 81c000000080         	add	eax, 0x80000000
+;;   5e:	 4883c410             	add	rsp, 0x10
+;;   62:	 5d                   	pop	rbp
+;;   63:	 c3                   	ret	
+;;   64:	 0f0b                 	ud2	
+;;   66:	 0f0b                 	ud2	
@@ -0,0 +1,27 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result i32)
+    (local.get 0)
+    (i32.trunc_sat_f32_s))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 f30f2cc0             	cvttss2si	eax, xmm0
+;;   1d:	 83f801               	cmp	eax, 1
+;;   20:	 0f8124000000         	jno	0x4a
+;;   26:	 0f2ec0               	ucomiss	xmm0, xmm0
+;;   29:	 0f8b07000000         	jnp	0x36
+;;   2f:	 31c0                 	xor	eax, eax
+;;   31:	 e914000000           	jmp	0x4a
+;;   36:	 66450f57ff           	xorpd	xmm15, xmm15
+;;   3b:	 440f2ef8             	ucomiss	xmm15, xmm0
+;;   3f:	 0f8305000000         	jae	0x4a
+;;   45:	 b8ffffff7f           	mov	eax, 0x7fffffff
+;;   4a:	 4883c410             	add	rsp, 0x10
+;;   4e:	 5d                   	pop	rbp
+;;   4f:	 c3                   	ret	
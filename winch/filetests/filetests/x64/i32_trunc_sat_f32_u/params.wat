@@ -0,0 +1,36 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result i32)
+    (local.get 0)
+    (i32.trunc_sat_f32_u))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 41bb0000004f         	mov	r11d, 0x4f000000
+;;   1f:	 66450f6efb           	movd	xmm15, r11d
+;;   24:	 410f2ec7             	ucomiss	xmm0, xmm15
+;;   28:	 0f8321000000         	jae	0x4f
+;;   2e:	 0f8b07000000         	jnp	0x3b
+;;   34:	 31c0                 	xor	eax, eax
+;;   36:	 e939000000           	jmp	0x74
+;;   3b:	 f30f2cc0             	cvttss2si	eax, xmm0
+;;   3f:	 83f800               	cmp	eax, 0
+;;   42:	 0f8d2c000000         	jge	0x74
+;;   48:	 31c0                 	xor	eax, eax
+;;   4a:	 e925000000           	jmp	0x74
+;;   4f:	 0f28c8               	movaps	xmm1, xmm0
+;;   52:	 f3410f5ccf           	subss	xmm1, xmm15
+;;   57:	 f30f2cc1             	cvttss2si	eax, xmm1
+;;   5b:	 83f800               	cmp	eax, 0
+;;   5e:	 0f8d0a000000         	jge	0x6e
+;;   64:	 b8ffffffff           	mov	eax, 0xffffffff
+;;   69:	 e906000000           	jmp	0x74
+;;   6e:	 81c000000080         	add	eax, 0x80000000
+;;   74:	 4883c410             	add	rsp, 0x10
+;;   78:	 5d                   	pop	rbp
+;;   79:	 c3                   	ret	
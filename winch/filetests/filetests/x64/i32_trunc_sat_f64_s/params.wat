@@ -0,0 +1,27 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f64) (result i32)
+    (local.get 0)
+    (i32.trunc_sat_f64_s))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f20f11442408         	movsd	qword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   18:	 f20f2cc0             	cvttsd2si	eax, xmm0
+;;   1c:	 83f801               	cmp	eax, 1
+;;   1f:	 0f8126000000         	jno	0x4b
+;;   25:	 660f2ec0             	ucomisd	xmm0, xmm0
+;;   29:	 0f8b07000000         	jnp	0x36
+;;   2f:	 31c0                 	xor	eax, eax
+;;   31:	 e915000000           	jmp	0x4b
+;;   36:	 66450f57ff           	xorpd	xmm15, xmm15
+;;   3b:	 66440f2ef8           	ucomisd	xmm15, xmm0
+;;   40:	 0f8305000000         	jae	0x4b
+;;   46:	 b8ffffff7f           	mov	eax, 0x7fffffff
+;;   4b:	 4883c410             	add	rsp, 0x10
+;;   4f:	 5d                   	pop	rbp
+;;   50:	 c3                   	ret	
@@ -0,0 +1,37 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f64) (result i32)
+    (local.get 0)
+    (i32.trunc_sat_f64_u))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f20f11442408         	movsd	qword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   18:	 49bb000000000000e041 	
+;; 				movabs	r11, 0x41e0000000000000
+;;   22:	 664d0f6efb           	movq	xmm15, r11
+;;   27:	 66410f2ec7           	ucomisd	xmm0, xmm15
+;;   2c:	 0f8321000000         	jae	0x53
+;;   32:	 0f8b07000000         	jnp	0x3f
+;;   38:	 31c0                 	xor	eax, eax
+;;   3a:	 e939000000           	jmp	0x78
+;;   3f:	 f20f2cc0             	cvttsd2si	eax, xmm0
+;;   43:	 83f800               	cmp	eax, 0
+;;   46:	 0f8d2c000000         	jge	0x78
+;;   4c:	 31c0                 	xor	eax, eax
+;;   4e:	 e925000000           	jmp	0x78
+;;   53:	 0f28c8               	movaps	xmm1, xmm0
+;;   56:	 f2410f5ccf           	subsd	xmm1, xmm15
+;;   5b:	 f20f2cc1             	cvttsd2si	eax, xmm1
+;;   5f:	 83f800               	cmp	eax, 0
+;;   62:	 0f8d0a000000         	jge	0x72
+;;   68:	 b8ffffffff           	mov	eax, 0xffffffff
+;;   6d:	 e906000000           	jmp	0x78
+;;   72:	 81c000000080         	add	eax, 0x80000000
+;;   78:	 4883c410             	add	rsp, 0x10
+;;   7c:	 5d                   	pop	rbp
+;;   7d:	 c3                   	ret	
@@ -0,0 +1,33 @@
+;;! target = "x86_64"
+;;! static_memory_bound = 0
+(module
+  (memory 1)
+  (func (param i32 i64)
+    (local.get 0)
+    (local.get 1)
+    (i64.store offset=8))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 897c2414             	mov	dword ptr [rsp + 0x14], edi
+;;    c:	 4889742408           	mov	qword ptr [rsp + 8], rsi
+;;   11:	 4c893424             	mov	qword ptr [rsp], r14
+;;   15:	 488b442408           	mov	rax, qword ptr [rsp + 8]
+;;   1a:	 8b4c2414             	mov	ecx, dword ptr [rsp + 0x14]
+;;   1e:	 8bc9                 	mov	ecx, ecx
+;;   20:	 498b5658             	mov	rdx, qword ptr [r14 + 0x58]
+;;   24:	 4889cb               	mov	rbx, rcx
+;;   27:	 4883c310             	add	rbx, 0x10
+;;   2b:	 0f821d000000         	jb	0x4e
+;;   31:	 4839d3               	cmp	rbx, rdx
+;;   34:	 0f8716000000         	ja	0x50
+;;   3a:	 498b5650             	mov	rdx, qword ptr [r14 + 0x50]
+;;   3e:	 4801ca               	add	rdx, rcx
+;;   41:	 4883c208             	add	rdx, 8
+;;   45:	 488902               	mov	qword ptr [rdx], rax
+;;   48:	 4883c418             	add	rsp, 0x18
+;;   4c:	 5d                   	pop	rbp
+;;   4d:	 c3                   	ret	
+;;   4e:	 0f0b                 	ud2	
+;;   50:	 0f0b                 	ud2	
@@ -0,0 +1,24 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (param i32 i64)
+    (local.get 0)
+    (local.get 1)
+    (i64.store))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 897c2414             	mov	dword ptr [rsp + 0x14], edi
+;;    c:	 4889742408           	mov	qword ptr [rsp + 8], rsi
+;;   11:	 4c893424             	mov	qword ptr [rsp], r14
+;;   15:	 488b442408           	mov	rax, qword ptr [rsp + 8]
+;;   1a:	 8b4c2414             	mov	ecx, dword ptr [rsp + 0x14]
+;;   1e:	 8bc9                 	mov	ecx, ecx
+;;   20:	 498b5650             	mov	rdx, qword ptr [r14 + 0x50]
+;;   24:	 4801ca               	add	rdx, rcx
+;;   27:	 488902               	mov	qword ptr [rdx], rax
+;;   2a:	 4883c418             	add	rsp, 0x18
+;;   2e:	 5d                   	pop	rbp
+;;   2f:	 c3                   	ret	
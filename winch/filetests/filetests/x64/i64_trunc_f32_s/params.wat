@@ -0,0 +1,31 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result i64)
+    (local.get 0)
+    (i64.trunc_f32_s))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 f3480f2cc0           	cvttss2si	rax, xmm0
+;;   1e:	 4883f801             	cmp	rax, 1
+;;   22:	 0f812d000000         	jno	0x55
+;;   28:	 0f2ec0               	ucomiss	xmm0, xmm0
+;;   2b:	 0f8a2a000000         	jp	0x5b
+;;   31:	 41bb000000df         	mov	r11d, 0xdf000000
+;;   37:	 66450f6efb           	movd	xmm15, r11d
+;;   3c:	 410f2ec7             	ucomiss	xmm0, xmm15
+;;   40:	 0f8217000000         	jb	0x5d
+;;   46:	 66450f57ff           	xorpd	xmm15, xmm15
+;;   4b:	 440f2ef8             	ucomiss	xmm15, xmm0
+;;   4f:	 0f820a000000         	jb	0x5f
+;;   55:	 4883c410             	add	rsp, 0x10
+;;   59:	 5d                   	pop	rbp
+;;   5a:	 c3                   	ret	
+;;   5b:	 0f0b                 	ud2	
+;;   5d:	 0f0b                 	ud2	
+;;   5f:	 0f0b                 	ud2	
@@ -0,0 +1,35 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result i64)
+    (local.get 0)
+    (i64.trunc_f32_u))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 41bb0000005f         	mov	r11d, 0x5f000000
+;;   1f:	 66450f6efb           	movd	xmm15, r11d
+;;   24:	 410f2ec7             	ucomiss	xmm0, xmm15
+;;   28:	 0f8317000000         	jae	0x45
+;;   2e:	 0f8a3b000000         	jp	0x6f
+;;   34:	 f3480f2cc0           	cvttss2si	rax, xmm0
+;;   39:	 4883f800             	cmp	rax, 0
+;;   3d:	 0f8d26000000         	jge	0x69
+;;   43:	 0f0b                 	ud2	
+;;   45:	 0f28c8               	movaps	xmm1, xmm0
+;;   48:	 f3410f5ccf           	subss	xmm1, xmm15
+;;   4d:	 f3480f2cc1           	cvttss2si	rax, xmm1
+;;   52:	 4883f800             	cmp	rax, 0
+;;   56:	 0f8c15000000         	jl	0x71
+;;   5c:	 49bb0000000000000080 	
+;; 				movabs	r11, 0x8000000000000000
+;;   66:	 4c01d8               	add	rax, r11
+;;   69:	 4883c410             	add	rsp, 0x10
+;;   6d:	 5d                   	pop	rbp
+;;   6e:	 c3                   	ret	
+;;   6f:	 0f0b                 	ud2	
+;;   71:	 0f0b                 	ud2	
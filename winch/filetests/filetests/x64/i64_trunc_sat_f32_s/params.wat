@@ -0,0 +1,28 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result i64)
+    (local.get 0)
+    (i64.trunc_sat_f32_s))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 f3480f2cc0           	cvttss2si	rax, xmm0
+;;   1e:	 4883f801             	cmp	rax, 1
+;;   22:	 0f812a000000         	jno	0x52
+;;   28:	 0f2ec0               	ucomiss	xmm0, xmm0
+;;   2b:	 0f8b08000000         	jnp	0x39
+;;   31:	 4831c0               	xor	rax, rax
+;;   34:	 e919000000           	jmp	0x52
+;;   39:	 66450f57ff           	xorpd	xmm15, xmm15
+;;   3e:	 440f2ef8             	ucomiss	xmm15, xmm0
+;;   42:	 0f830a000000         	jae	0x52
+;;   48:	 48b8ffffffffffffff7f 	
+;; 				movabs	rax, 0x7fffffffffffffff
+;;   52:	 4883c410             	add	rsp, 0x10
+;;   56:	 5d                   	pop	rbp
+;;   57:	 c3                   	ret	
@@ -0,0 +1,38 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f32) (result i64)
+    (local.get 0)
+    (i64.trunc_sat_f32_u))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f30f1144240c         	movss	dword ptr [rsp + 0xc], xmm0
+;;    e:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   13:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   19:	 41bb0000005f         	mov	r11d, 0x5f000000
+;;   1f:	 66450f6efb           	movd	xmm15, r11d
+;;   24:	 410f2ec7             	ucomiss	xmm0, xmm15
+;;   28:	 0f8325000000         	jae	0x53
+;;   2e:	 0f8b08000000         	jnp	0x3c
+;;   34:	 4831c0               	xor	rax, rax
+;;   37:	 e947000000           	jmp	0x83
+;;   3c:	 f3480f2cc0           	cvttss2si	rax, xmm0
+;;   41:	 4883f800             	cmp	rax, 0
+;;   45:	 0f8d38000000         	jge	0x83
+;;   4b:	 4831c0               	xor	rax, rax
+;;   4e:	 e930000000           	jmp	0x83
+;;   53:	 0f28c8               	movaps	xmm1, xmm0
+;;   56:	 f3410f5ccf           	subss	xmm1, xmm15
+;;   5b:	 f3480f2cc1           	cvttss2si	rax, xmm1
+;;   60:	 4883f800             	cmp	rax, 0
+;;   64:	 0f8d0c000000         	jge	0x76
+;;   6a:	 48c7c0ffffffff       	mov	rax, 0xffffffffffffffff
+;;   71:	 e90d000000           	jmp	0x83
+;;   76:	 49bb0000000000000080 	
+;; 				movabs	r11, 0x8000000000000000
+;;   80:	 4c01d8               	add	rax, r11
+;;   83:	 4883c410             	add	rsp, 0x10
+;;   87:	 5d                   	pop	rbp
+;;   88:	 c3                   	ret	
@@ -0,0 +1,28 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f64) (result i64)
+    (local.get 0)
+    (i64.trunc_sat_f64_s))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f20f11442408         	movsd	qword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   18:	 f2480f2cc0           	cvttsd2si	rax, xmm0
+;;   1d:	 4883f801             	cmp	rax, 1
+;;   21:	 0f812c000000         	jno	0x53
+;;   27:	 660f2ec0             	ucomisd	xmm0, xmm0
+;;   2b:	 0f8b08000000         	jnp	0x39
+;;   31:	 4831c0               	xor	rax, rax
+;;   34:	 e91a000000           	jmp	0x53
+;;   39:	 66450f57ff           	xorpd	xmm15, xmm15
+;;   3e:	 66440f2ef8           	ucomisd	xmm15, xmm0
+;;   43:	 0f830a000000         	jae	0x53
+;;   49:	 48b8ffffffffffffff7f 	
+;; 				movabs	rax, 0x7fffffffffffffff
+;;   53:	 4883c410             	add	rsp, 0x10
+;;   57:	 5d                   	pop	rbp
+;;   58:	 c3                   	ret	
@@ -0,0 +1,39 @@
+;;! target = "x86_64"
+
+(module
+  (func (param f64) (result i64)
+    (local.get 0)
+    (i64.trunc_sat_f64_u))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 f20f11442408         	movsd	qword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   18:	 49bb000000000000e043 	
+;; 				movabs	r11, 0x43e0000000000000
+;;   22:	 664d0f6efb           	movq	xmm15, r11
+;;   27:	 66410f2ec7           	ucomisd	xmm0, xmm15
+;;   2c:	 0f8325000000         	jae	0x57
+;;   32:	 0f8b08000000         	jnp	0x40
+;;   38:	 4831c0               	xor	rax, rax
+;;   3b:	 e947000000           	jmp	0x87
+;;   40:	 f2480f2cc0           	cvttsd2si	rax, xmm0
+;;   45:	 4883f800             	cmp	rax, 0
+;;   49:	 0f8d38000000         	jge	0x87
+;;   4f:	 4831c0               	xor	rax, rax
+;;   52:	 e930000000           	jmp	0x87
+;;   57:	 0f28c8               	movaps	xmm1, xmm0
+;;   5a:	 f2410f5ccf           	subsd	xmm1, xmm15
+;;   5f:	 f2480f2cc1           	cvttsd2si	rax, xmm1
+;;   64:	 4883f800             	cmp	rax, 0
+;;   68:	 0f8d0c000000         	jge	0x7a
+;;   6e:	 48c7c0ffffffff       	mov	rax, 0xffffffffffffffff
+;;   75:	 e90d000000           	jmp	0x87
+;;   7a:	 49bb0000000000000080 	
+;; 				movabs	r11, 0x8000000000000000
+;;   84:	 4c01d8               	add	rax, r11
+;;   87:	 4883c410             	add	rsp, 0x10
+;;   8b:	 5d                   	pop	rbp
+;;   8c:	 c3                   	ret	
@@ -0,0 +1,28 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (param i32) (result i32)
+    (local.get 0)
+    (memory.grow))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   15:	 8bc0                 	mov	eax, eax
+;;   17:	 4c89f1               	mov	rcx, r14
+;;   1a:	 51                   	push	rcx
+;;   1b:	 50                   	push	rax
+;;   1c:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   21:	 488b3424             	mov	rsi, qword ptr [rsp]
+;;   25:	 ba00000000           	mov	edx, 0
+;;   2a:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   2e:	 4d8b1b               	mov	r11, qword ptr [r11]
+;;   31:	 41ffd3               	call	r11
+;;   34:	 4883c410             	add	rsp, 0x10
+;;   38:	 4883c410             	add	rsp, 0x10
+;;   3c:	 5d                   	pop	rbp
+;;   3d:	 c3                   	ret	
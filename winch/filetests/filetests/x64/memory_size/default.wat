@@ -0,0 +1,16 @@
+;;! target = "x86_64"
+
+(module
+  (memory 1)
+  (func (result i32)
+    (memory.size))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec08             	sub	rsp, 8
+;;    8:	 4c893424             	mov	qword ptr [rsp], r14
+;;    c:	 498b4658             	mov	rax, qword ptr [r14 + 0x58]
+;;   10:	 48c1e810             	shr	rax, 0x10
+;;   14:	 4883c408             	add	rsp, 8
+;;   18:	 5d                   	pop	rbp
+;;   19:	 c3                   	ret	
@@ -0,0 +1,42 @@
+;;! target = "x86_64"
+
+(module
+  (table 1 funcref)
+  (func (param i32) (result funcref)
+    (local.get 0)
+    (table.get 0))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   11:	 448b5c240c           	mov	r11d, dword ptr [rsp + 0xc]
+;;   16:	 4153                 	push	r11
+;;   18:	 59                   	pop	rcx
+;;   19:	 418b5e50             	mov	ebx, dword ptr [r14 + 0x50]
+;;   1d:	 39d9                 	cmp	ecx, ebx
+;;   1f:	 0f8349000000         	jae	0x6e
+;;   25:	 498b5648             	mov	rdx, qword ptr [r14 + 0x48]
+;;   29:	 8bd9                 	mov	ebx, ecx
+;;   2b:	 486bdb08             	imul	rbx, rbx, 8
+;;   2f:	 4801da               	add	rdx, rbx
+;;   32:	 488b02               	mov	rax, qword ptr [rdx]
+;;   35:	 4885c0               	test	rax, rax
+;;   38:	 0f8526000000         	jne	0x64
+;;   3e:	 4c89f0               	mov	rax, r14
+;;   41:	 50                   	push	rax
+;;   42:	 51                   	push	rcx
+;;   43:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   48:	 be00000000           	mov	esi, 0
+;;   4d:	 8b1424               	mov	edx, dword ptr [rsp]
+;;   50:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   54:	 4d8b5b48             	mov	r11, qword ptr [r11 + 0x48]
+;;   58:	 41ffd3               	call	r11
+;;   5b:	 4883c410             	add	rsp, 0x10
+;;   5f:	 e904000000           	jmp	0x68
+;;   64:	 4883e0fe             	and	rax, 0xfffffffffffffffe
+;;   68:	 4883c410             	add	rsp, 0x10
+;;   6c:	 5d                   	pop	rbp
+;;   6d:	 c3                   	ret	
+;;   6e:	 0f0b                 	ud2	
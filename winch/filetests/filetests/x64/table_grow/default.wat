@@ -0,0 +1,32 @@
+;;! target = "x86_64"
+
+(module
+  (table 1 funcref)
+  (func (param funcref i32) (result i32)
+    (local.get 0)
+    (local.get 1)
+    (table.grow 0))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 48897c2410           	mov	qword ptr [rsp + 0x10], rdi
+;;    d:	 8974240c             	mov	dword ptr [rsp + 0xc], esi
+;;   11:	 4c89742404           	mov	qword ptr [rsp + 4], r14
+;;   16:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   1a:	 488b4c2410           	mov	rcx, qword ptr [rsp + 0x10]
+;;   1f:	 4c89f2               	mov	rdx, r14
+;;   22:	 52                   	push	rdx
+;;   23:	 50                   	push	rax
+;;   24:	 51                   	push	rcx
+;;   25:	 488b7c2410           	mov	rdi, qword ptr [rsp + 0x10]
+;;   2a:	 be00000000           	mov	esi, 0
+;;   2f:	 8b542408             	mov	edx, dword ptr [rsp + 8]
+;;   33:	 488b0c24             	mov	rcx, qword ptr [rsp]
+;;   37:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   3b:	 4d8b5b50             	mov	r11, qword ptr [r11 + 0x50]
+;;   3f:	 41ffd3               	call	r11
+;;   42:	 4883c418             	add	rsp, 0x18
+;;   46:	 4883c418             	add	rsp, 0x18
+;;   4a:	 5d                   	pop	rbp
+;;   4b:	 c3                   	ret	
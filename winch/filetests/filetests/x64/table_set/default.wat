@@ -0,0 +1,30 @@
+;;! target = "x86_64"
+
+(module
+  (table 1 funcref)
+  (func (param i32 funcref)
+    (local.get 0)
+    (local.get 1)
+    (table.set 0))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 897c2414             	mov	dword ptr [rsp + 0x14], edi
+;;    c:	 4889742408           	mov	qword ptr [rsp + 8], rsi
+;;   11:	 4c893424             	mov	qword ptr [rsp], r14
+;;   15:	 488b442408           	mov	rax, qword ptr [rsp + 8]
+;;   1a:	 8b4c2414             	mov	ecx, dword ptr [rsp + 0x14]
+;;   1e:	 418b5e50             	mov	ebx, dword ptr [r14 + 0x50]
+;;   22:	 39d9                 	cmp	ecx, ebx
+;;   24:	 0f831a000000         	jae	0x44
+;;   2a:	 498b5648             	mov	rdx, qword ptr [r14 + 0x48]
+;;   2e:	 8bd9                 	mov	ebx, ecx
+;;   30:	 486bdb08             	imul	rbx, rbx, 8
+;;   34:	 4801da               	add	rdx, rbx
+;;   37:	 4883c801             	or	rax, 1
+;;   3b:	 488902               	mov	qword ptr [rdx], rax
+;;   3e:	 4883c418             	add	rsp, 0x18
+;;   42:	 5d                   	pop	rbp
+;;   43:	 c3                   	ret	
+;;   44:	 0f0b                 	ud2	
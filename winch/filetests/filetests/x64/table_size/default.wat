@@ -0,0 +1,14 @@
+;;! target = "x86_64"
+
+(module
+  (table 1 funcref)
+  (func (result i32) (table.size 0))
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec08             	sub	rsp, 8
+;;    8:	 4c893424             	mov	qword ptr [rsp], r14
+;;    c:	 418b4650             	mov	eax, dword ptr [r14 + 0x50]
+;;   10:	 4883c408             	add	rsp, 8
+;;   14:	 5d                   	pop	rbp
+;;   15:	 c3                   	ret	
@@ -21,6 +21,9 @@ mod test {
     struct TestConfig {
         target: String,
         flags: Option<Vec<String>>,
+        /// Overrides the default `static_memory_bound` tunable, in Wasm
+        /// pages; a bound of `0` makes every memory dynamic.
+        static_memory_bound: Option<u64>,
     }
 
     /// A helper function to parse the test configuration from the top of the file.
@@ -103,7 +106,10 @@ mod test {
         let mut validator = Validator::new();
         let parser = WasmParser::new(0);
         let mut types = Default::default();
-        let tunables = Tunables::default();
+        let mut tunables = Tunables::default();
+        if let Some(bound) = config.static_memory_bound {
+            tunables.static_memory_bound = bound;
+        }
         let mut translation = ModuleEnvironment::new(&tunables, &mut validator, &mut types)
             .translate(parser, &wasm)
             .context("Failed to translate WebAssembly module")
@@ -114,7 +120,7 @@ mod test {
 
         let binding = body_inputs
             .into_iter()
-            .map(|func| compile(&isa, &translation, func).join("\n"))
+            .map(|func| compile(&isa, &translation, &tunables, func).join("\n"))
             .collect::<Vec<String>>()
             .join("\n\n");
         let actual = binding.as_str();
@@ -148,6 +154,7 @@ mod test {
     fn compile(
         isa: &Box<dyn TargetIsa>,
         translation: &ModuleTranslation,
+        tunables: &Tunables,
         f: (DefinedFuncIndex, FunctionBodyData<'_>),
     ) -> Vec<String> {
         let module = &translation.module;
@@ -160,7 +167,7 @@ mod test {
         let FunctionBodyData { body, validator } = f.1;
         let mut validator = validator.into_validator(Default::default());
         let buffer = isa
-            .compile_function(&sig, &body, &translation, &mut validator)
+            .compile_function(&sig, &body, &translation, tunables, &mut validator)
             .expect("Couldn't compile function");
 
         disasm(buffer.data(), isa).unwrap()
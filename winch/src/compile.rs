@@ -41,7 +41,7 @@ pub fn run(opt: &Options) -> Result<()> {
 
     body_inputs
         .into_iter()
-        .try_for_each(|func| compile(&isa, &translation, func))?;
+        .try_for_each(|func| compile(&isa, &translation, &tunables, func))?;
 
     Ok(())
 }
@@ -49,6 +49,7 @@ pub fn run(opt: &Options) -> Result<()> {
 fn compile(
     isa: &Box<dyn TargetIsa>,
     translation: &ModuleTranslation,
+    tunables: &Tunables,
     f: (DefinedFuncIndex, FunctionBodyData<'_>),
 ) -> Result<()> {
     let index = translation.module.func_index(f.0);
@@ -58,7 +59,7 @@ fn compile(
     let FunctionBodyData { body, validator } = f.1;
     let mut validator = validator.into_validator(Default::default());
     let buffer = isa
-        .compile_function(&sig, &body, &translation, &mut validator)
+        .compile_function(&sig, &body, &translation, tunables, &mut validator)
         .expect("Couldn't compile function");
 
     println!("Disassembly for function: {}", index.as_u32());